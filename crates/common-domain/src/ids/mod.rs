@@ -48,6 +48,7 @@ id_type!(CustomTaxId, "tax_");
 id_type!(CheckoutSessionId, "che_");
 id_type!(PlanVersionAddOnId, "pva_");
 id_type!(PriceId, "pri_");
+id_type!(PriceChangeId, "prc_");
 id_type!(ScheduledEventId, "sev_");
 id_type!(ConnectedAccountId, "cacc_");
 id_type!(OAuthAppId, "app_");
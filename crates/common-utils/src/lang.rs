@@ -0,0 +1,30 @@
+/// Primary language subtag of a language tag ("de-AT" -> "de"). Underscores are accepted as
+/// separators, as in POSIX locales ("de_AT").
+pub fn primary_language(tag: &str) -> &str {
+    tag.split(['-', '_']).next().unwrap_or_default()
+}
+
+/// The candidate best matching a language tag: the one with the same tag, else the first one
+/// of the same primary language, when the region differs ("de-AT" -> "de-DE"). Tags are
+/// compared ignoring case.
+pub fn match_language<T>(
+    candidates: impl IntoIterator<Item = T>,
+    tag: &str,
+    tag_of: impl Fn(&T) -> &str,
+) -> Option<T> {
+    let primary = primary_language(tag);
+    let mut same_language = None;
+
+    for candidate in candidates {
+        let candidate_tag = tag_of(&candidate);
+        if candidate_tag.eq_ignore_ascii_case(tag) {
+            return Some(candidate);
+        }
+        if same_language.is_none() && primary_language(candidate_tag).eq_ignore_ascii_case(primary)
+        {
+            same_language = Some(candidate);
+        }
+    }
+
+    same_language
+}
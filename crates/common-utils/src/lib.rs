@@ -5,5 +5,6 @@ pub mod timed;
 
 pub mod decimals;
 pub mod integers;
+pub mod lang;
 pub mod misc;
 pub mod rng;
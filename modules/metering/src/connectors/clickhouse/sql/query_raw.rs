@@ -171,6 +171,15 @@ pub fn query_meter_sql(params: QueryMeterParams, events_table: &str) -> Result<S
         }
     }
 
+    for (column, values) in &params.property_filters {
+        if values.is_empty() {
+            return Err(format!("Empty filter for property: {column}"));
+        }
+        let path = PropertyColumn(column).path_sql(&mut subquery_binds);
+        subquery_binds.push(BindValue::Strings(values.clone()));
+        subquery_conditions.push(format!("{path} IN ?"));
+    }
+
     if let Some(ref value_prop) = params.value_property
         && !matches!(params.aggregation, MeterAggregation::Count)
     {
@@ -403,6 +412,7 @@ mod tests {
             value_property: Some("amount".to_string()),
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: Some(WindowSize::Minute),
             window_time_zone: Some(chrono_tz::UTC),
@@ -457,6 +467,7 @@ mod tests {
             value_property: None,
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -507,6 +518,7 @@ mod tests {
                 CustomerId::from(Uuid::from_u128(2)),
             ],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: Some(WindowSize::Hour),
             window_time_zone: None,
@@ -566,6 +578,7 @@ mod tests {
             value_property: Some("duration".to_string()),
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec!["region".to_string(), "endpoint".to_string()],
             window_size: Some(WindowSize::Day),
             window_time_zone: None,
@@ -628,6 +641,7 @@ mod tests {
                 ),
                 ("tier".to_string(), vec!["premium".to_string()]),
             ])),
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -676,6 +690,53 @@ mod tests {
         assert!(bs.contains(&"S:premium".to_string()));
     }
 
+    #[test]
+    fn test_query_meter_dedup_with_property_filters() {
+        let params = QueryMeterParams {
+            aggregation: MeterAggregation::Count,
+            tenant_id: TenantId::default(),
+            code: "api_call".to_string(),
+            value_property: None,
+            customer_ids: vec![],
+            segmentation_filter: None,
+            property_filters: vec![("instance_id".to_string(), vec!["project-a".to_string()])],
+            group_by: vec![],
+            window_size: None,
+            window_time_zone: None,
+
+            from: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            to: None,
+        };
+
+        let result = query_meter_sql(params, "raw_events_v2").unwrap();
+        let expected = r#"
+            SELECT
+                min(toDateTime(timestamp)) AS window_start,
+                max(toDateTime(timestamp)) AS window_end,
+                toFloat64(count(*)) AS value
+            FROM (
+                SELECT
+                    id,
+                    customer_id,
+                    timestamp,
+                    properties
+                FROM raw_events_v2
+                WHERE tenant_id = ?
+                    AND code = ?
+                    AND timestamp >= toDateTime(?)
+                    AND properties[?] IN ?
+                ORDER BY timestamp DESC
+                LIMIT 1 BY id, customer_id
+            )
+        "#;
+
+        assert_eq!(normalize_sql(&result.sql), normalize_sql(expected));
+        assert_bind_parity(&result);
+        let bs = bind_strings(&result.binds);
+        assert!(bs.contains(&"S:instance_id".to_string()));
+        assert!(bs.contains(&"A:project-a".to_string()));
+    }
+
     #[test]
     fn test_query_meter_dedup_with_linked_segmentation() {
         let mut values = HashMap::new();
@@ -696,6 +757,7 @@ mod tests {
                 dimension2_key: "version".to_string(),
                 values,
             }),
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -789,6 +851,7 @@ mod tests {
             value_property: Some("user_id".to_string()),
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: Some(WindowSize::Day),
             window_time_zone: None,
@@ -839,6 +902,7 @@ mod tests {
             value_property: Some("value".to_string()),
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -889,6 +953,7 @@ mod tests {
                 "region".to_string(),
                 vec![],
             )])),
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -911,6 +976,7 @@ mod tests {
             value_property: None,
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -936,6 +1002,7 @@ mod tests {
             value_property: Some("price".to_string()),
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -982,6 +1049,7 @@ mod tests {
             value_property: None,
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -1028,6 +1096,7 @@ mod tests {
             value_property: Some("amount".to_string()),
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec!["code".to_string()],
             window_size: None,
             window_time_zone: None,
@@ -1080,6 +1149,7 @@ mod tests {
                 "customer_id".to_string(),
                 vec!["cust1".to_string(), "cust2".to_string()],
             )])),
+            property_filters: vec![],
             group_by: vec![],
             window_size: None,
             window_time_zone: None,
@@ -1132,6 +1202,7 @@ mod tests {
             value_property: None,
             customer_ids: vec![],
             segmentation_filter: None,
+            property_filters: vec![],
             group_by: vec![
                 "customer_id".to_string(),
                 "region".to_string(),
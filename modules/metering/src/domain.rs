@@ -63,6 +63,8 @@ pub struct QueryMeterParams {
     pub value_property: Option<String>,
     pub customer_ids: Vec<CustomerId>,
     pub segmentation_filter: Option<SegmentationFilter>,
+    /// Events must match every filter, unlike segmentation they are not grouped by.
+    pub property_filters: Vec<(String, Vec<String>)>,
     pub group_by: Vec<String>,
     pub window_size: Option<WindowSize>,
     pub window_time_zone: Option<chrono_tz::Tz>,
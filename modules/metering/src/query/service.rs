@@ -115,6 +115,11 @@ impl UsageQueryServiceGrpc for UsageQueryService {
             window_size,
             window_time_zone,
             segmentation_filter,
+            property_filters: req
+                .property_filters
+                .into_iter()
+                .map(|f| (f.property_name, f.property_value))
+                .collect(),
             from: req
                 .from
                 .map(timestamp_to_datetime)
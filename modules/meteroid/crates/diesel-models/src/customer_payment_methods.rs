@@ -96,6 +96,10 @@ pub struct ResolvedSubscriptionPaymentContext {
     #[diesel(select_expression = crate::schema::customer::id)]
     #[diesel(select_expression_type = crate::schema::customer::id)]
     pub customer_id: CustomerId,
+
+    #[diesel(select_expression = crate::schema::customer::parent_customer_id)]
+    #[diesel(select_expression_type = crate::schema::customer::parent_customer_id)]
+    pub parent_customer_id: Option<CustomerId>,
 }
 
 #[derive(Debug, Queryable, Selectable)]
@@ -37,6 +37,12 @@ pub struct CustomerRow {
     pub vat_number_validation_status: Option<CustomerVatValidationStatusEnum>,
     pub vat_number_checked_at: Option<NaiveDateTime>,
     pub vat_number_vies_check: Option<serde_json::Value>,
+    pub custom_attributes: serde_json::Value,
+    pub parent_customer_id: Option<CustomerId>,
+    pub pool_usage_with_parent: bool,
+    pub invoice_language: Option<String>,
+    pub invoice_custom_fields: serde_json::Value,
+    pub invoice_approval_required: bool,
 }
 
 #[derive(Clone, Debug, Queryable, Selectable)]
@@ -73,6 +79,10 @@ pub struct CustomerRowNew {
     pub vat_number_format_valid: bool,
     pub connected_account_id: Option<ConnectedAccountId>,
     pub vat_number_validation_status: Option<CustomerVatValidationStatusEnum>,
+    pub custom_attributes: serde_json::Value,
+    pub invoice_language: Option<String>,
+    pub invoice_custom_fields: serde_json::Value,
+    pub invoice_approval_required: bool,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -100,6 +110,10 @@ pub struct CustomerRowPatch {
     pub vat_number_validation_status: Option<Option<CustomerVatValidationStatusEnum>>,
     pub vat_number_checked_at: Option<Option<NaiveDateTime>>,
     pub vat_number_vies_check: Option<Option<serde_json::Value>>,
+    pub custom_attributes: Option<serde_json::Value>,
+    pub invoice_language: Option<Option<String>>,
+    pub invoice_custom_fields: Option<serde_json::Value>,
+    pub invoice_approval_required: Option<bool>,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -124,4 +138,8 @@ pub struct CustomerRowUpdate {
     pub vat_number_validation_status: Option<CustomerVatValidationStatusEnum>,
     pub vat_number_checked_at: Option<NaiveDateTime>,
     pub vat_number_vies_check: Option<serde_json::Value>,
+    pub custom_attributes: serde_json::Value,
+    pub invoice_language: Option<String>,
+    pub invoice_custom_fields: serde_json::Value,
+    pub invoice_approval_required: bool,
 }
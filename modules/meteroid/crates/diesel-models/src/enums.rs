@@ -60,6 +60,8 @@ pub enum BillingMetricAggregateEnum {
 #[ExistingTypePath = "crate::schema::sql_types::BillingPeriodEnum"]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum BillingPeriodEnum {
+    Daily,
+    Weekly,
     Monthly,
     Quarterly,
     Semiannual,
@@ -118,6 +120,15 @@ pub enum QuoteStatusEnum {
     Cancelled,
 }
 
+#[derive(diesel_derive_enum::DbEnum, Debug, Clone, Eq, PartialEq)]
+#[ExistingTypePath = "crate::schema::sql_types::InvoiceApprovalStatusEnum"]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum InvoiceApprovalStatusEnum {
+    Pending,
+    Approved,
+    Rejected,
+}
+
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone, Eq, PartialEq)]
 #[ExistingTypePath = "crate::schema::sql_types::InvoicePaymentStatus"]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
@@ -139,7 +150,7 @@ pub enum InvoiceType {
     Recurring,
     OneOff,
     Adjustment,
-    // Imported,
+    Imported,
     UsageThreshold,
 }
 
@@ -188,6 +199,15 @@ pub enum MrrMovementType {
     Reactivation,
 }
 
+#[derive(diesel_derive_enum::DbEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::NumberingResetEnum"]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum NumberingResetEnum {
+    Never,
+    Yearly,
+    Monthly,
+}
+
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone, Copy, PartialEq, Eq)]
 #[ExistingTypePath = "crate::schema::sql_types::OrganizationUserRole"]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
@@ -259,6 +279,24 @@ pub enum PlanTypeEnum {
     Custom,
 }
 
+#[derive(diesel_derive_enum::DbEnum, Debug, Clone, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::PriceChangeGrandfatheringEnum"]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum PriceChangeGrandfatheringEnum {
+    AllSubscriptions,
+    NewSubscriptionsOnly,
+    RenewingAfter,
+}
+
+#[derive(diesel_derive_enum::DbEnum, Debug, Clone, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::PriceChangeStatusEnum"]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum PriceChangeStatusEnum {
+    Scheduled,
+    Applied,
+    Cancelled,
+}
+
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone, Default, PartialEq)]
 #[ExistingTypePath = "crate::schema::sql_types::SubscriptionActivationConditionEnum"]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
@@ -274,6 +312,8 @@ pub enum SubscriptionActivationConditionEnum {
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
 pub enum SubscriptionFeeBillingPeriod {
     OneTime,
+    Daily,
+    Weekly,
     Monthly,
     Quarterly,
     Semiannual,
@@ -290,6 +330,7 @@ pub enum SubscriptionEventType {
     Cancelled,
     Reactivated,
     Updated,
+    Renewed,
 }
 
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone)]
@@ -356,6 +397,16 @@ pub enum CycleActionEnum {
     EndSubscription,
 }
 
+#[derive(diesel_derive_enum::DbEnum, Debug, Clone, Copy, PartialEq, Eq)]
+#[ExistingTypePath = "crate::schema::sql_types::RenewalPolicyEnum"]
+#[DbValueStyle = "SCREAMING_SNAKE_CASE"]
+pub enum RenewalPolicyEnum {
+    Expire,
+    RenewSameTerm,
+    RenewMonthToMonth,
+    RenewToPlanVersion,
+}
+
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone, PartialEq)]
 #[ExistingTypePath = "crate::schema::sql_types::ScheduledEventTypeEnum"]
 #[DbValueStyle = "SCREAMING_SNAKE_CASE"]
@@ -367,6 +418,8 @@ pub enum ScheduledEventTypeEnum {
     PauseSubscription,
     EndTrial,
     ApplyAmendment,
+    ApplyPriceChange,
+    ResumeSubscription,
 }
 
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone)]
@@ -437,6 +490,7 @@ pub enum BatchJobTypeEnum {
     CustomerCsvImport,
     SubscriptionCsvImport,
     SubscriptionPlanMigration,
+    InvoiceExport,
 }
 
 #[derive(diesel_derive_enum::DbEnum, Debug, Clone, PartialEq, Eq)]
@@ -1,4 +1,6 @@
-use crate::enums::{InvoicePaymentStatus, InvoiceStatusEnum, InvoiceType};
+use crate::enums::{
+    InvoiceApprovalStatusEnum, InvoicePaymentStatus, InvoiceStatusEnum, InvoiceType,
+};
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 
@@ -6,7 +8,7 @@ use crate::customers::CustomerRow;
 use crate::plan_versions::PlanVersionRowOverview;
 use common_domain::ids::{
     CustomerId, InvoiceId, InvoicingEntityId, PlanVersionId, StoredDocumentId, SubscriptionId,
-    TenantId,
+    TenantId, UserId,
 };
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
 
@@ -59,6 +61,13 @@ pub struct InvoiceRow {
     pub invoicing_entity_id: InvoicingEntityId,
     pub parent_invoice_id: Option<InvoiceId>,
     pub consolidated_into_invoice_id: Option<InvoiceId>,
+    pub custom_fields: serde_json::Value,
+    pub sub_line_presentations: Option<serde_json::Value>,
+    pub approval_status: Option<InvoiceApprovalStatusEnum>,
+    pub approval_requested_at: Option<NaiveDateTime>,
+    pub approval_decided_at: Option<NaiveDateTime>,
+    pub approval_decided_by: Option<UserId>,
+    pub approval_comment: Option<String>,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -86,6 +95,7 @@ pub struct InvoiceRowPatch {
     pub memo: Option<String>,
     pub reference: Option<String>,
     pub purchase_order: Option<String>,
+    pub custom_fields: serde_json::Value,
     pub due_at: Option<NaiveDateTime>,
     pub invoicing_entity_id: InvoicingEntityId,
     pub subtotal: i64,
@@ -135,6 +145,7 @@ pub struct InvoiceRowNew {
     pub invoicing_entity_id: InvoicingEntityId,
     pub parent_invoice_id: Option<InvoiceId>,
     pub consolidated_into_invoice_id: Option<InvoiceId>,
+    pub custom_fields: serde_json::Value,
 }
 
 #[derive(Debug, Queryable, Selectable)]
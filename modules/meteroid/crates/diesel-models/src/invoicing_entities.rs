@@ -1,9 +1,9 @@
 use crate::bank_accounts::BankAccountRow;
 use crate::connectors::ConnectorRow;
-use crate::enums::TaxResolverEnum;
+use crate::enums::{NumberingResetEnum, TaxResolverEnum};
 use common_domain::country::CountryCode;
 use common_domain::ids::{
-    BankAccountId, ConnectorId, InvoicingEntityId, StoredDocumentId, TenantId,
+    BankAccountId, ConnectorId, InvoicingEntityId, StoredDocumentId, TenantId, UserId,
 };
 use diesel::{AsChangeset, Identifiable, Insertable, Queryable, Selectable};
 
@@ -41,6 +41,13 @@ pub struct InvoicingEntityRow {
     pub require_billing_information: bool,
     pub portal_theme_mode: Option<String>,
     pub portal_roundness: Option<String>,
+    pub invoice_number_reset: NumberingResetEnum,
+    pub credit_note_number_pattern: String,
+    pub credit_note_number_reset: NumberingResetEnum,
+    pub invoice_translations: serde_json::Value,
+    pub invoice_template: Option<String>,
+    pub invoice_approval_threshold: Option<i64>,
+    pub invoice_approver_ids: Vec<Option<UserId>>,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -70,6 +77,13 @@ pub struct InvoicingEntityRowPatch {
     pub require_billing_information: Option<bool>,
     pub portal_theme_mode: Option<Option<String>>,
     pub portal_roundness: Option<Option<String>>,
+    pub invoice_number_reset: Option<NumberingResetEnum>,
+    pub credit_note_number_pattern: Option<String>,
+    pub credit_note_number_reset: Option<NumberingResetEnum>,
+    pub invoice_translations: Option<serde_json::Value>,
+    pub invoice_template: Option<Option<String>>,
+    pub invoice_approval_threshold: Option<Option<i64>>,
+    pub invoice_approver_ids: Option<Vec<Option<UserId>>>,
 }
 
 #[derive(Debug, AsChangeset)]
@@ -16,6 +16,7 @@ pub mod organizations;
 pub mod plan_component_prices;
 pub mod plan_versions;
 pub mod plans;
+pub mod price_changes;
 pub mod price_components;
 pub mod prices;
 pub mod product_families;
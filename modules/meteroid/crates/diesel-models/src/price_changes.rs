@@ -0,0 +1,43 @@
+use chrono::{NaiveDate, NaiveDateTime};
+
+use crate::enums::{PriceChangeGrandfatheringEnum, PriceChangeStatusEnum};
+use common_domain::ids::{PriceChangeId, PriceId, ProductId, TenantId};
+use diesel::{Identifiable, Insertable, Queryable, Selectable};
+use uuid::Uuid;
+
+#[derive(Queryable, Debug, Clone, Identifiable, Selectable)]
+#[diesel(table_name = crate::schema::price_change)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PriceChangeRow {
+    pub id: PriceChangeId,
+    pub tenant_id: TenantId,
+    pub product_id: ProductId,
+    pub price_id: PriceId,
+    /// Pre-allocated id of the price row materialized when the change first applies.
+    pub new_price_id: PriceId,
+    pub new_pricing: serde_json::Value,
+    pub effective_date: NaiveDate,
+    pub grandfathering: PriceChangeGrandfatheringEnum,
+    pub renewing_after: Option<NaiveDate>,
+    pub status: PriceChangeStatusEnum,
+    pub created_by: Uuid,
+    pub created_at: NaiveDateTime,
+    pub applied_at: Option<NaiveDateTime>,
+    pub cancelled_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = crate::schema::price_change)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct PriceChangeRowNew {
+    pub id: PriceChangeId,
+    pub tenant_id: TenantId,
+    pub product_id: ProductId,
+    pub price_id: PriceId,
+    pub new_price_id: PriceId,
+    pub new_pricing: serde_json::Value,
+    pub effective_date: NaiveDate,
+    pub grandfathering: PriceChangeGrandfatheringEnum,
+    pub renewing_after: Option<NaiveDate>,
+    pub created_by: Uuid,
+}
@@ -12,6 +12,8 @@ pub struct PriceComponentRow {
     pub plan_version_id: PlanVersionId,
     pub product_id: Option<ProductId>,
     pub billable_metric_id: Option<BillableMetricId>,
+    pub sub_line_presentation: serde_json::Value,
+    pub line_metadata: serde_json::Value,
 }
 
 #[derive(Debug, Insertable)]
@@ -24,6 +26,8 @@ pub struct PriceComponentRowNew {
     pub plan_version_id: PlanVersionId,
     pub product_id: Option<ProductId>,
     pub billable_metric_id: Option<BillableMetricId>,
+    pub sub_line_presentation: serde_json::Value,
+    pub line_metadata: serde_json::Value,
 }
 
 // the changeset one
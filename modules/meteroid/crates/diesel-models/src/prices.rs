@@ -17,6 +17,7 @@ pub struct PriceRow {
     pub created_at: NaiveDateTime,
     pub archived_at: Option<NaiveDateTime>,
     pub catalog: bool,
+    pub variants: serde_json::Value,
 }
 
 #[derive(Debug, Insertable)]
@@ -30,4 +31,5 @@ pub struct PriceRowNew {
     pub pricing: serde_json::Value,
     pub tenant_id: TenantId,
     pub catalog: bool,
+    pub variants: serde_json::Value,
 }
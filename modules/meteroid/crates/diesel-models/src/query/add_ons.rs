@@ -3,7 +3,7 @@ use crate::errors::IntoDbResult;
 use crate::extend::order::{OrderByParam, OrderDirection};
 use crate::extend::pagination::{Paginate, PaginatedVec, PaginationRequest};
 use crate::{DbResult, PgConn};
-use common_domain::ids::{AddOnId, PlanVersionId, TenantId};
+use common_domain::ids::{AddOnId, PlanVersionId, PriceId, TenantId};
 use diesel::{ExpressionMethods, PgTextExpressionMethods, QueryDsl, SelectableHelper, debug_query};
 use diesel_async::RunQueryDsl;
 use error_stack::ResultExt;
@@ -187,6 +187,30 @@ impl AddOnRow {
             .attach("Error while fetching add-ons")
             .into_db_result()
     }
+
+    /// Repoint every add-on priced at `old_price_id` to `new_price_id`.
+    pub async fn replace_price_id(
+        conn: &mut PgConn,
+        old_price_id: PriceId,
+        new_price_id: PriceId,
+        tenant_id: TenantId,
+    ) -> DbResult<usize> {
+        use crate::schema::add_on::dsl as ao_dsl;
+
+        let query = diesel::update(ao_dsl::add_on)
+            .filter(ao_dsl::price_id.eq(old_price_id))
+            .filter(ao_dsl::tenant_id.eq(tenant_id))
+            .set(ao_dsl::price_id.eq(new_price_id));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .tap_err(|e| log::error!("Error while replacing add-on price: {e:?}"))
+            .attach("Error while replacing add-on price")
+            .into_db_result()
+    }
 }
 
 impl AddOnRowPatch {
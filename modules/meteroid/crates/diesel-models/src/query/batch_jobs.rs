@@ -297,6 +297,45 @@ impl BatchJobRow {
             .into_db_result()
     }
 
+    /// Set the output_key (S3 key of the document produced by the job, e.g. an export archive).
+    pub async fn set_output_key(
+        conn: &mut PgConn,
+        job_id: BatchJobId,
+        key: String,
+    ) -> DbResult<usize> {
+        use crate::schema::batch_job::dsl;
+
+        let now = Utc::now().naive_utc();
+        let query = diesel::update(dsl::batch_job)
+            .filter(dsl::id.eq(job_id))
+            .set((dsl::output_key.eq(Some(key)), dsl::updated_at.eq(now)));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while setting output_key")
+            .into_db_result()
+    }
+
+    pub async fn set_input_source_key(
+        conn: &mut PgConn,
+        job_id: BatchJobId,
+        key: String,
+    ) -> DbResult<usize> {
+        use crate::schema::batch_job::dsl;
+
+        let now = Utc::now().naive_utc();
+        let query = diesel::update(dsl::batch_job)
+            .filter(dsl::id.eq(job_id))
+            .set((dsl::input_source_key.eq(Some(key)), dsl::updated_at.eq(now)));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while setting input_source_key")
+            .into_db_result()
+    }
+
     /// Mark a job as failed. Only applies to non-terminal states.
     pub async fn mark_as_failed(
         conn: &mut PgConn,
@@ -378,7 +417,8 @@ impl BatchJobRow {
         tenant_id_param: TenantId,
     ) -> DbResult<usize> {
         use crate::schema::batch_job::dsl::{
-            batch_job, completed_at, error_output_key, id, status, tenant_id, updated_at,
+            batch_job, completed_at, error_output_key, id, output_key, status, tenant_id,
+            updated_at,
         };
 
         let now = Utc::now().naive_utc();
@@ -394,6 +434,7 @@ impl BatchJobRow {
                 status.eq(BatchJobStatusEnum::Processing),
                 completed_at.eq(None::<chrono::NaiveDateTime>),
                 error_output_key.eq(None::<String>),
+                output_key.eq(None::<String>),
                 updated_at.eq(now),
             ));
 
@@ -5,7 +5,9 @@ use crate::extend::pagination::{Paginate, PaginatedVec, PaginationRequest};
 use crate::{DbResult, PgConn};
 
 use crate::extend::order::{OrderByParam, OrderDirection};
-use common_domain::ids::{CreditNoteId, CustomerId, InvoiceId, StoredDocumentId, TenantId};
+use common_domain::ids::{
+    CreditNoteId, CustomerId, InvoiceId, InvoicingEntityId, StoredDocumentId, TenantId,
+};
 use diesel::{
     BoolExpressionMethods, ExpressionMethods, JoinOnDsl, PgTextExpressionMethods, QueryDsl,
     SelectableHelper, debug_query,
@@ -290,6 +292,39 @@ impl CreditNoteRow {
             .into_db_result()
     }
 
+    /// Issued (non-draft) credit notes of an invoicing entity finalized within `[from, to]`,
+    /// in ledger order. Used by the accounting export.
+    pub async fn list_for_export(
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        invoicing_entity_id: InvoicingEntityId,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> DbResult<Vec<CreditNoteRow>> {
+        use crate::schema::credit_note::dsl as cn_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let start = from.and_time(chrono::NaiveTime::MIN);
+        let end = (to + chrono::Days::new(1)).and_time(chrono::NaiveTime::MIN);
+
+        let query = cn_dsl::credit_note
+            .filter(cn_dsl::tenant_id.eq(tenant_id))
+            .filter(cn_dsl::invoicing_entity_id.eq(invoicing_entity_id))
+            .filter(cn_dsl::status.ne(CreditNoteStatus::Draft))
+            .filter(cn_dsl::finalized_at.ge(start))
+            .filter(cn_dsl::finalized_at.lt(end))
+            .order((cn_dsl::finalized_at.asc(), cn_dsl::credit_note_number.asc()))
+            .select(CreditNoteRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing credit notes for export")
+            .into_db_result()
+    }
+
     pub async fn update_pdf_document_id(
         conn: &mut PgConn,
         id: CreditNoteId,
@@ -11,8 +11,8 @@ use common_domain::ids::{
     AliasOr, BaseId, ConnectorId, CustomerId, CustomerPaymentMethodId, TenantId,
 };
 use diesel::{
-    BoolExpressionMethods, ExpressionMethods, OptionalExtension, PgSortExpressionMethods,
-    PgTextExpressionMethods, QueryDsl, SelectableHelper, debug_query,
+    BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods, OptionalExtension,
+    PgSortExpressionMethods, PgTextExpressionMethods, QueryDsl, SelectableHelper, debug_query,
 };
 use error_stack::ResultExt;
 use itertools::Itertools;
@@ -396,6 +396,7 @@ impl CustomerRow {
                 invoicing_emails.eq(excluded(invoicing_emails)),
                 is_tax_exempt.eq(excluded(is_tax_exempt)),
                 custom_taxes.eq(excluded(custom_taxes)),
+                custom_attributes.eq(excluded(custom_attributes)),
                 vat_number_format_valid.eq(excluded(vat_number_format_valid)),
                 updated_at.eq(diesel::dsl::now),
             ));
@@ -547,6 +548,83 @@ impl CustomerRow {
             .attach("Error while checking for archived customers")
             .into_db_result()
     }
+
+    pub async fn set_parent(
+        conn: &mut PgConn,
+        id: CustomerId,
+        tenant_id: TenantId,
+        parent_customer_id: Option<CustomerId>,
+        pool_usage_with_parent: bool,
+    ) -> DbResult<CustomerRow> {
+        use crate::schema::customer::dsl as c_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::update(c_dsl::customer)
+            .filter(c_dsl::id.eq(id))
+            .filter(c_dsl::tenant_id.eq(tenant_id))
+            .set((
+                c_dsl::parent_customer_id.eq(parent_customer_id),
+                c_dsl::pool_usage_with_parent.eq(pool_usage_with_parent),
+                c_dsl::updated_at.eq(diesel::dsl::now),
+            ))
+            .returning(CustomerRow::as_returning());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_result(conn)
+            .await
+            .attach("Error while setting customer parent")
+            .into_db_result()
+    }
+
+    /// The children of a customer, including archived ones.
+    pub async fn list_children(
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        parent_customer_id: CustomerId,
+    ) -> DbResult<Vec<CustomerRow>> {
+        use crate::schema::customer::dsl as c_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = c_dsl::customer
+            .filter(c_dsl::tenant_id.eq(tenant_id))
+            .filter(c_dsl::parent_customer_id.eq(parent_customer_id))
+            .order(c_dsl::id.asc())
+            .select(CustomerRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while listing customer children")
+            .into_db_result()
+    }
+
+    /// Maps each customer of the batch that has a parent to that parent.
+    pub async fn find_parent_ids(
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        customer_ids: Vec<CustomerId>,
+    ) -> DbResult<Vec<(CustomerId, CustomerId)>> {
+        use crate::schema::customer::dsl as c_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = c_dsl::customer
+            .filter(c_dsl::tenant_id.eq(tenant_id))
+            .filter(c_dsl::id.eq_any(customer_ids))
+            .filter(c_dsl::parent_customer_id.is_not_null())
+            .select((c_dsl::id, c_dsl::parent_customer_id.assume_not_null()));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while finding customer parents")
+            .into_db_result()
+    }
 }
 
 impl CustomerRowPatch {
@@ -6,7 +6,9 @@ use crate::invoices::{
 
 use crate::{DbResult, PgConn};
 
-use crate::enums::{ConnectorProviderEnum, InvoicePaymentStatus, InvoiceStatusEnum};
+use crate::enums::{
+    ConnectorProviderEnum, InvoiceApprovalStatusEnum, InvoicePaymentStatus, InvoiceStatusEnum,
+};
 use crate::extend::connection_metadata;
 use crate::extend::cursor_pagination::{
     CursorPaginate, CursorPaginatedVec, CursorPaginationRequest,
@@ -16,8 +18,8 @@ use crate::extend::pagination::{Paginate, PaginatedVec, PaginationRequest};
 use crate::payments::PaymentTransactionRow;
 use chrono::NaiveDateTime;
 use common_domain::ids::{
-    AliasOr, BaseId, ConnectorId, CustomerId, InvoiceId, InvoicingEntityId, StoredDocumentId,
-    SubscriptionId, TenantId,
+    AliasOr, BaseId, ConnectorId, CouponId, CustomerId, InvoiceId, InvoicingEntityId,
+    StoredDocumentId, SubscriptionId, TenantId, UserId,
 };
 use diesel::dsl::IntervalDsl;
 use diesel::{
@@ -135,6 +137,37 @@ impl InvoiceRow {
             .into_db_result()
     }
 
+    /// Whether the coupon was redeemed on a one-off invoice of the customer that was not voided.
+    pub async fn customer_used_coupon(
+        conn: &mut PgConn,
+        param_tenant_id: TenantId,
+        param_customer_id: CustomerId,
+        param_coupon_id: CouponId,
+    ) -> DbResult<bool> {
+        use crate::schema::invoice::dsl as i_dsl;
+        use diesel::PgJsonbExpressionMethods;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::dsl::select(diesel::dsl::exists(
+            i_dsl::invoice
+                .filter(i_dsl::tenant_id.eq(param_tenant_id))
+                .filter(i_dsl::customer_id.eq(param_customer_id))
+                .filter(i_dsl::subscription_id.is_null())
+                .filter(i_dsl::status.ne(InvoiceStatusEnum::Void))
+                .filter(
+                    i_dsl::coupons.contains(serde_json::json!([{ "coupon_id": param_coupon_id }])),
+                ),
+        ));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_result(conn)
+            .await
+            .attach("Error while checking coupon usage of customer")
+            .into_db_result()
+    }
+
     pub async fn find_by_id(
         conn: &mut PgConn,
         param_tenant_id: TenantId,
@@ -468,6 +501,36 @@ impl InvoiceRow {
             .into_db_result()
     }
 
+    /// Issued (non-draft) invoices of an invoicing entity with an invoice date in `[from, to]`,
+    /// in ledger order. Used by the accounting export.
+    pub async fn list_for_export(
+        conn: &mut PgConn,
+        param_tenant_id: TenantId,
+        param_invoicing_entity_id: InvoicingEntityId,
+        param_from: chrono::NaiveDate,
+        param_to: chrono::NaiveDate,
+    ) -> DbResult<Vec<InvoiceRow>> {
+        use crate::schema::invoice::dsl as i_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = i_dsl::invoice
+            .filter(i_dsl::tenant_id.eq(param_tenant_id))
+            .filter(i_dsl::invoicing_entity_id.eq(param_invoicing_entity_id))
+            .filter(i_dsl::status.ne(InvoiceStatusEnum::Draft))
+            .filter(i_dsl::consolidated_into_invoice_id.is_null())
+            .filter(i_dsl::invoice_date.between(param_from, param_to))
+            .order((i_dsl::invoice_date.asc(), i_dsl::invoice_number.asc()))
+            .select(InvoiceRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing invoices for export")
+            .into_db_result()
+    }
+
     pub async fn list_detailed_by_ids(
         conn: &mut PgConn,
         param_invoice_ids: Vec<InvoiceId>,
@@ -556,6 +619,7 @@ impl InvoiceRow {
         new_invoice_number: String,
         payment_reference: String,
         coupons: serde_json::Value,
+        sub_line_presentations: serde_json::Value,
         finalized_at: NaiveDateTime,
     ) -> DbResult<usize> {
         use crate::schema::invoice::dsl as i_dsl;
@@ -577,6 +641,7 @@ impl InvoiceRow {
                 i_dsl::invoice_number.eq(new_invoice_number),
                 i_dsl::reference.eq(payment_reference),
                 i_dsl::coupons.eq(coupons),
+                i_dsl::sub_line_presentations.eq(Some(sub_line_presentations)),
             ));
 
         log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
@@ -882,6 +947,73 @@ impl InvoiceRow {
             .into_db_result()
     }
 
+    /// Puts a draft on hold until approved, clearing any previous decision.
+    pub async fn request_approval(
+        conn: &mut PgConn,
+        id: InvoiceId,
+        tenant_id: TenantId,
+    ) -> DbResult<()> {
+        use crate::schema::invoice::dsl as i_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::update(i_dsl::invoice)
+            .filter(i_dsl::id.eq(id))
+            .filter(i_dsl::tenant_id.eq(tenant_id))
+            .filter(i_dsl::status.eq(InvoiceStatusEnum::Draft))
+            .set((
+                i_dsl::approval_status.eq(Some(InvoiceApprovalStatusEnum::Pending)),
+                i_dsl::approval_requested_at.eq(diesel::dsl::now.nullable()),
+                i_dsl::approval_decided_at.eq(None::<NaiveDateTime>),
+                i_dsl::approval_decided_by.eq(None::<UserId>),
+                i_dsl::approval_comment.eq(None::<String>),
+                i_dsl::updated_at.eq(diesel::dsl::now),
+            ));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .map(|_| ())
+            .attach("Error while requesting invoice approval")
+            .into_db_result()
+    }
+
+    /// Records the decision on a draft pending approval. Returns false if it was not pending.
+    pub async fn decide_approval(
+        conn: &mut PgConn,
+        id: InvoiceId,
+        tenant_id: TenantId,
+        decision: InvoiceApprovalStatusEnum,
+        decided_by: UserId,
+        comment: Option<String>,
+    ) -> DbResult<bool> {
+        use crate::schema::invoice::dsl as i_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::update(i_dsl::invoice)
+            .filter(i_dsl::id.eq(id))
+            .filter(i_dsl::tenant_id.eq(tenant_id))
+            .filter(i_dsl::status.eq(InvoiceStatusEnum::Draft))
+            .filter(i_dsl::approval_status.eq(InvoiceApprovalStatusEnum::Pending))
+            .set((
+                i_dsl::approval_status.eq(Some(decision)),
+                i_dsl::approval_decided_at.eq(diesel::dsl::now.nullable()),
+                i_dsl::approval_decided_by.eq(Some(decided_by)),
+                i_dsl::approval_comment.eq(comment),
+                i_dsl::updated_at.eq(diesel::dsl::now),
+            ));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .map(|updated| updated > 0)
+            .attach("Error while recording invoice approval decision")
+            .into_db_result()
+    }
+
     pub async fn find_last_by_subscription_id(
         conn: &mut PgConn,
         param_tenant_id: TenantId,
@@ -1112,6 +1244,35 @@ impl InvoiceRow {
             .attach("Error while listing creditable period invoices")
             .into_db_result()
     }
+
+    /// Non-void `UsageThreshold` invoices issued since `since`, whose usage must be
+    /// deducted from the period invoice billing the same usage periods.
+    pub async fn list_usage_threshold_invoices(
+        conn: &mut PgConn,
+        param_tenant_id: TenantId,
+        param_subscription_id: SubscriptionId,
+        since: chrono::NaiveDate,
+    ) -> DbResult<Vec<InvoiceRow>> {
+        use crate::enums::InvoiceType;
+        use crate::schema::invoice::dsl as i_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = i_dsl::invoice
+            .filter(i_dsl::tenant_id.eq(param_tenant_id))
+            .filter(i_dsl::subscription_id.eq(param_subscription_id))
+            .filter(i_dsl::invoice_type.eq(InvoiceType::UsageThreshold))
+            .filter(i_dsl::invoice_date.ge(since))
+            .filter(i_dsl::status.ne(InvoiceStatusEnum::Void))
+            .select(InvoiceRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing usage threshold invoices")
+            .into_db_result()
+    }
 }
 
 impl InvoiceRowLinesPatch {
@@ -223,6 +223,103 @@ impl InvoicingEntityRow {
             .attach("Error while updating credit note number")
             .into_db_result()
     }
+
+    /// Takes the next number of a scoped sequence (see `invoice_number_sequence`), creating the
+    /// sequence at 1 on first use.
+    ///
+    /// The upsert locks the sequence row until the transaction ends, so concurrent finalizations
+    /// wait for each other and a rolled back transaction does not consume a number.
+    pub async fn allocate_sequence_number(
+        conn: &mut PgConn,
+        id: InvoicingEntityId,
+        tenant_id: TenantId,
+        series: &str,
+        scope: &str,
+    ) -> DbResult<i64> {
+        use crate::schema::invoice_number_sequence::dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::insert_into(dsl::invoice_number_sequence)
+            .values((
+                dsl::invoicing_entity_id.eq(id),
+                dsl::series.eq(series),
+                dsl::scope.eq(scope),
+                dsl::next_number.eq(2_i64),
+                dsl::tenant_id.eq(tenant_id),
+            ))
+            .on_conflict((dsl::invoicing_entity_id, dsl::series, dsl::scope))
+            .do_update()
+            .set(dsl::next_number.eq(dsl::next_number + 1))
+            .returning(dsl::next_number - 1);
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_result(conn)
+            .await
+            .attach("Error while allocating invoice number sequence")
+            .into_db_result()
+    }
+
+    /// Scoped sequences of a document series, as `(scope, next_number)`.
+    pub async fn list_sequences(
+        conn: &mut PgConn,
+        id: InvoicingEntityId,
+        tenant_id: TenantId,
+        series: &str,
+    ) -> DbResult<Vec<(String, i64)>> {
+        use crate::schema::invoice_number_sequence::dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = dsl::invoice_number_sequence
+            .filter(dsl::invoicing_entity_id.eq(id))
+            .filter(dsl::tenant_id.eq(tenant_id))
+            .filter(dsl::series.eq(series))
+            .select((dsl::scope, dsl::next_number));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while listing invoice number sequences")
+            .into_db_result()
+    }
+
+    /// Sets the number the next document of a scoped sequence receives, creating the sequence
+    /// if needed.
+    pub async fn set_sequence_next_number(
+        conn: &mut PgConn,
+        id: InvoicingEntityId,
+        tenant_id: TenantId,
+        series: &str,
+        scope: &str,
+        next_number: i64,
+    ) -> DbResult<()> {
+        use crate::schema::invoice_number_sequence::dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::insert_into(dsl::invoice_number_sequence)
+            .values((
+                dsl::invoicing_entity_id.eq(id),
+                dsl::series.eq(series),
+                dsl::scope.eq(scope),
+                dsl::next_number.eq(next_number),
+                dsl::tenant_id.eq(tenant_id),
+            ))
+            .on_conflict((dsl::invoicing_entity_id, dsl::series, dsl::scope))
+            .do_update()
+            .set(dsl::next_number.eq(next_number));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .map(|_| ())
+            .attach("Error while setting invoice number sequence")
+            .into_db_result()
+    }
 }
 
 impl InvoicingEntityProvidersRow {
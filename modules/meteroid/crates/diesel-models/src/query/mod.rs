@@ -34,6 +34,7 @@ pub mod plan_component_prices;
 pub mod plan_version_add_ons;
 pub mod plan_versions;
 pub mod plans;
+pub mod price_changes;
 pub mod price_components;
 pub mod prices;
 pub mod product_families;
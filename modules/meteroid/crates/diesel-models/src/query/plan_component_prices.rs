@@ -1,7 +1,7 @@
 use crate::errors::IntoDbResult;
 use crate::plan_component_prices::{PlanComponentPriceRow, PlanComponentPriceRowNew};
 use crate::{DbResult, PgConn};
-use common_domain::ids::PriceComponentId;
+use common_domain::ids::{PriceComponentId, PriceId, TenantId};
 use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, debug_query};
 use error_stack::ResultExt;
 
@@ -64,4 +64,35 @@ impl PlanComponentPriceRow {
             .map(|_| ())
             .into_db_result()
     }
+
+    /// Repoint every plan component using `old_price_id` to `new_price_id`.
+    ///
+    /// The link table carries no tenant, so the old price is scoped through `price.tenant_id`.
+    pub async fn replace_price_id(
+        conn: &mut PgConn,
+        old_price_id: PriceId,
+        new_price_id: PriceId,
+        tenant_id: TenantId,
+    ) -> DbResult<usize> {
+        use crate::schema::plan_component_price::dsl as pcp_dsl;
+        use crate::schema::price::dsl as p_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let tenant_price = p_dsl::price
+            .filter(p_dsl::id.eq(old_price_id))
+            .filter(p_dsl::tenant_id.eq(tenant_id))
+            .select(p_dsl::id);
+
+        let query = diesel::update(pcp_dsl::plan_component_price)
+            .filter(pcp_dsl::price_id.eq_any(tenant_price))
+            .set(pcp_dsl::price_id.eq(new_price_id));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while replacing plan component price")
+            .into_db_result()
+    }
 }
@@ -1,7 +1,7 @@
 use crate::errors::IntoDbResult;
 use crate::plan_version_add_ons::{PlanVersionAddOnRow, PlanVersionAddOnRowNew};
 use crate::{DbResult, PgConn};
-use common_domain::ids::{AddOnId, PlanVersionId, TenantId};
+use common_domain::ids::{AddOnId, PlanVersionId, PriceId, TenantId};
 use diesel::{ExpressionMethods, Insertable, IntoSql, QueryDsl, SelectableHelper, debug_query};
 use diesel_async::RunQueryDsl;
 use error_stack::ResultExt;
@@ -136,4 +136,28 @@ impl PlanVersionAddOnRow {
 
         Ok(())
     }
+
+    /// Repoint every plan version add-on priced at `old_price_id` to `new_price_id`.
+    pub async fn replace_price_id(
+        conn: &mut PgConn,
+        old_price_id: PriceId,
+        new_price_id: PriceId,
+        tenant_id: TenantId,
+    ) -> DbResult<usize> {
+        use crate::schema::plan_version_add_on::dsl as pva_dsl;
+
+        let query = diesel::update(pva_dsl::plan_version_add_on)
+            .filter(pva_dsl::price_id.eq(old_price_id))
+            .filter(pva_dsl::tenant_id.eq(tenant_id))
+            .set(pva_dsl::price_id.eq(new_price_id));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .tap_err(|e| log::error!("Error while replacing plan_version_add_on price: {e:?}"))
+            .attach("Error while replacing plan_version_add_on price")
+            .into_db_result()
+    }
 }
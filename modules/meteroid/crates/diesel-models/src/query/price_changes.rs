@@ -0,0 +1,152 @@
+use crate::enums::PriceChangeStatusEnum;
+use crate::errors::IntoDbResult;
+use crate::price_changes::{PriceChangeRow, PriceChangeRowNew};
+use crate::{DbResult, PgConn};
+use chrono::NaiveDate;
+use common_domain::ids::{PriceChangeId, ProductId, TenantId};
+use diesel::{ExpressionMethods, QueryDsl, SelectableHelper, debug_query};
+use diesel_async::RunQueryDsl;
+use error_stack::ResultExt;
+
+impl PriceChangeRowNew {
+    pub async fn insert(&self, conn: &mut PgConn) -> DbResult<PriceChangeRow> {
+        use crate::schema::price_change::dsl::price_change;
+
+        let query = diesel::insert_into(price_change).values(self);
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_result(conn)
+            .await
+            .attach("Error while inserting price change")
+            .into_db_result()
+    }
+}
+
+impl PriceChangeRow {
+    pub async fn find_by_id(
+        conn: &mut PgConn,
+        id: PriceChangeId,
+        tenant_id: TenantId,
+    ) -> DbResult<PriceChangeRow> {
+        use crate::schema::price_change::dsl as pc_dsl;
+
+        let query = pc_dsl::price_change
+            .filter(pc_dsl::id.eq(id))
+            .filter(pc_dsl::tenant_id.eq(tenant_id))
+            .select(PriceChangeRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .first(conn)
+            .await
+            .attach("Error while finding price change by id")
+            .into_db_result()
+    }
+
+    pub async fn find_by_id_for_update(
+        conn: &mut PgConn,
+        id: PriceChangeId,
+        tenant_id: TenantId,
+    ) -> DbResult<PriceChangeRow> {
+        use crate::schema::price_change::dsl as pc_dsl;
+
+        let query = pc_dsl::price_change
+            .filter(pc_dsl::id.eq(id))
+            .filter(pc_dsl::tenant_id.eq(tenant_id))
+            .for_update()
+            .select(PriceChangeRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .first(conn)
+            .await
+            .attach("Error while locking price change by id")
+            .into_db_result()
+    }
+
+    pub async fn list_by_product_id(
+        conn: &mut PgConn,
+        product_id: ProductId,
+        tenant_id: TenantId,
+    ) -> DbResult<Vec<PriceChangeRow>> {
+        use crate::schema::price_change::dsl as pc_dsl;
+
+        let query = pc_dsl::price_change
+            .filter(pc_dsl::product_id.eq(product_id))
+            .filter(pc_dsl::tenant_id.eq(tenant_id))
+            .order((pc_dsl::effective_date.desc(), pc_dsl::created_at.desc()))
+            .select(PriceChangeRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing price changes by product id")
+            .into_db_result()
+    }
+
+    /// Claim scheduled changes whose effective date has been reached, skipping rows
+    /// another worker already holds.
+    pub async fn claim_due(
+        conn: &mut PgConn,
+        as_of: NaiveDate,
+        limit: i64,
+    ) -> DbResult<Vec<PriceChangeRow>> {
+        use crate::schema::price_change::dsl as pc_dsl;
+
+        let query = pc_dsl::price_change
+            .filter(pc_dsl::status.eq(PriceChangeStatusEnum::Scheduled))
+            .filter(pc_dsl::effective_date.le(as_of))
+            .order(pc_dsl::effective_date.asc())
+            .limit(limit)
+            .for_update()
+            .skip_locked()
+            .select(PriceChangeRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while claiming due price changes")
+            .into_db_result()
+    }
+
+    pub async fn update_status(
+        conn: &mut PgConn,
+        id: PriceChangeId,
+        tenant_id: TenantId,
+        status: PriceChangeStatusEnum,
+    ) -> DbResult<PriceChangeRow> {
+        use crate::schema::price_change::dsl as pc_dsl;
+
+        let now = chrono::Utc::now().naive_utc();
+        let (applied_at, cancelled_at) = match status {
+            PriceChangeStatusEnum::Applied => (Some(now), None),
+            PriceChangeStatusEnum::Cancelled => (None, Some(now)),
+            PriceChangeStatusEnum::Scheduled => (None, None),
+        };
+
+        let query = diesel::update(pc_dsl::price_change)
+            .filter(pc_dsl::id.eq(id))
+            .filter(pc_dsl::tenant_id.eq(tenant_id))
+            .filter(pc_dsl::status.eq(PriceChangeStatusEnum::Scheduled))
+            .set((
+                pc_dsl::status.eq(status),
+                pc_dsl::applied_at.eq(applied_at),
+                pc_dsl::cancelled_at.eq(cancelled_at),
+            ));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_result(conn)
+            .await
+            .attach("Error while updating price change status")
+            .into_db_result()
+    }
+}
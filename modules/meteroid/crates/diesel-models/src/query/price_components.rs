@@ -86,6 +86,30 @@ impl PriceComponentRow {
             .into_db_result()
     }
 
+    pub async fn list_by_ids(
+        conn: &mut PgConn,
+        param_tenant_id: TenantId,
+        param_ids: &[PriceComponentId],
+    ) -> DbResult<Vec<PriceComponentRow>> {
+        use crate::schema::plan_version::dsl as pv_dsl;
+        use crate::schema::price_component::dsl as pc_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = pc_dsl::price_component
+            .inner_join(pv_dsl::plan_version)
+            .filter(pv_dsl::tenant_id.eq(param_tenant_id))
+            .filter(pc_dsl::id.eq_any(param_ids))
+            .select(PriceComponentRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while listing price components by ids")
+            .into_db_result()
+    }
+
     pub async fn insert(
         conn: &mut PgConn,
         price_component_param: PriceComponentRowNew,
@@ -282,6 +306,8 @@ impl PriceComponentRow {
                 dst_plan_version_id.into_sql::<diesel::sql_types::Uuid>(),
                 pc_dsl::product_id,
                 pc_dsl::billable_metric_id,
+                pc_dsl::sub_line_presentation,
+                pc_dsl::line_metadata,
             ))
             .insert_into(pc_dsl::price_component)
             .into_columns((
@@ -291,6 +317,8 @@ impl PriceComponentRow {
                 pc_dsl::plan_version_id,
                 pc_dsl::product_id,
                 pc_dsl::billable_metric_id,
+                pc_dsl::sub_line_presentation,
+                pc_dsl::line_metadata,
             ));
 
         log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
@@ -20,6 +20,25 @@ impl PriceRowNew {
             .into_db_result()
     }
 
+    /// Insert the price unless a row with the same id already exists.
+    /// Used to materialize pre-allocated price ids idempotently.
+    pub async fn insert_if_absent(&self, conn: &mut PgConn) -> DbResult<()> {
+        use crate::schema::price::dsl::price;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::insert_into(price)
+            .values(self)
+            .on_conflict_do_nothing();
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while inserting price if absent")
+            .into_db_result()
+            .map(|_| ())
+    }
+
     pub async fn insert_batch(conn: &mut PgConn, batch: &[PriceRowNew]) -> DbResult<Vec<PriceRow>> {
         use crate::schema::price::dsl::price;
         use diesel_async::RunQueryDsl;
@@ -156,6 +175,29 @@ impl PriceRow {
             .into_db_result()
     }
 
+    pub async fn update_variants(
+        conn: &mut PgConn,
+        id: PriceId,
+        tenant_id: TenantId,
+        variants: serde_json::Value,
+    ) -> DbResult<PriceRow> {
+        use crate::schema::price::dsl as p_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::update(p_dsl::price)
+            .filter(p_dsl::id.eq(id))
+            .filter(p_dsl::tenant_id.eq(tenant_id))
+            .set(p_dsl::variants.eq(variants));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_result(conn)
+            .await
+            .attach("Error while updating price variants")
+            .into_db_result()
+    }
+
     pub async fn archive(conn: &mut PgConn, id: PriceId, tenant_id: TenantId) -> DbResult<()> {
         use crate::schema::price::dsl as p_dsl;
         use diesel_async::RunQueryDsl;
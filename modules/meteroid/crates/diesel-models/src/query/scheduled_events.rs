@@ -99,8 +99,10 @@ impl ScheduledEventRow {
         let visible_types = vec![
             ScheduledEventTypeEnum::ApplyPlanChange,
             ScheduledEventTypeEnum::ApplyAmendment,
+            ScheduledEventTypeEnum::ApplyPriceChange,
             ScheduledEventTypeEnum::CancelSubscription,
             ScheduledEventTypeEnum::PauseSubscription,
+            ScheduledEventTypeEnum::ResumeSubscription,
             ScheduledEventTypeEnum::EndTrial,
         ];
 
@@ -473,6 +475,8 @@ impl ScheduledEventRow {
             ScheduledEventTypeEnum::PauseSubscription,
             ScheduledEventTypeEnum::CancelSubscription,
             ScheduledEventTypeEnum::EndTrial,
+            ScheduledEventTypeEnum::ApplyPriceChange,
+            ScheduledEventTypeEnum::ResumeSubscription,
         ];
 
         let query = diesel::update(scheduled_event)
@@ -494,6 +498,137 @@ impl ScheduledEventRow {
             .attach("Error while canceling pending subscription events")
             .into_db_result()
     }
+
+    /// Cancel the pending resumption of a paused subscription, if any.
+    pub async fn cancel_pending_resume_events(
+        conn: &mut PgConn,
+        subscription_id_param: SubscriptionId,
+        tenant_id_param: &TenantId,
+        reason: &str,
+    ) -> DbResult<usize> {
+        use crate::schema::scheduled_event::dsl::{
+            error, event_type, scheduled_event, status, subscription_id, tenant_id, updated_at,
+        };
+
+        let query = diesel::update(scheduled_event)
+            .filter(subscription_id.eq(subscription_id_param))
+            .filter(tenant_id.eq(tenant_id_param))
+            .filter(status.eq(ScheduledEventStatus::Pending))
+            .filter(event_type.eq(ScheduledEventTypeEnum::ResumeSubscription))
+            .set((
+                status.eq(ScheduledEventStatus::Canceled),
+                error.eq(Some(reason.to_string())),
+                updated_at.eq(Utc::now().naive_utc()),
+            ));
+
+        log::debug!("{}", diesel::debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while canceling pending resume events")
+            .into_db_result()
+    }
+
+    /// Cancel the pending cancellation of a subscription, if any.
+    pub async fn cancel_pending_cancellation_events(
+        conn: &mut PgConn,
+        subscription_id_param: SubscriptionId,
+        tenant_id_param: &TenantId,
+        reason: &str,
+    ) -> DbResult<usize> {
+        use crate::schema::scheduled_event::dsl::{
+            error, event_type, scheduled_event, status, subscription_id, tenant_id, updated_at,
+        };
+
+        let query = diesel::update(scheduled_event)
+            .filter(subscription_id.eq(subscription_id_param))
+            .filter(tenant_id.eq(tenant_id_param))
+            .filter(status.eq(ScheduledEventStatus::Pending))
+            .filter(event_type.eq(ScheduledEventTypeEnum::CancelSubscription))
+            .set((
+                status.eq(ScheduledEventStatus::Canceled),
+                error.eq(Some(reason.to_string())),
+                updated_at.eq(Utc::now().naive_utc()),
+            ));
+
+        log::debug!("{}", diesel::debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while canceling pending cancellation events")
+            .into_db_result()
+    }
+
+    /// Subscriptions that already have a (non-cancelled) price change event for the
+    /// given price change. `price_change_id` is matched against the event payload.
+    pub async fn list_subscription_ids_for_price_change(
+        conn: &mut PgConn,
+        tenant_id_param: &TenantId,
+        price_change_id: uuid::Uuid,
+    ) -> DbResult<Vec<SubscriptionId>> {
+        use crate::schema::scheduled_event::dsl::{
+            event_data, event_type, scheduled_event, status, subscription_id, tenant_id,
+        };
+
+        let query = scheduled_event
+            .filter(tenant_id.eq(tenant_id_param))
+            .filter(event_type.eq(ScheduledEventTypeEnum::ApplyPriceChange))
+            .filter(status.ne(ScheduledEventStatus::Canceled))
+            .filter(
+                event_data
+                    .retrieve_as_object("ApplyPriceChange")
+                    .retrieve_as_text("price_change_id")
+                    .eq(price_change_id.to_string()),
+            )
+            .select(subscription_id)
+            .distinct();
+
+        log::debug!("{}", diesel::debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while listing subscriptions for price change")
+            .into_db_result()
+    }
+
+    /// Cancel all pending events scheduled by a price change.
+    pub async fn cancel_pending_price_change_events(
+        conn: &mut PgConn,
+        tenant_id_param: &TenantId,
+        price_change_id: uuid::Uuid,
+        reason: &str,
+    ) -> DbResult<usize> {
+        use crate::schema::scheduled_event::dsl::{
+            error, event_data, event_type, scheduled_event, status, tenant_id, updated_at,
+        };
+
+        let query = diesel::update(scheduled_event)
+            .filter(tenant_id.eq(tenant_id_param))
+            .filter(event_type.eq(ScheduledEventTypeEnum::ApplyPriceChange))
+            .filter(status.eq(ScheduledEventStatus::Pending))
+            .filter(
+                event_data
+                    .retrieve_as_object("ApplyPriceChange")
+                    .retrieve_as_text("price_change_id")
+                    .eq(price_change_id.to_string()),
+            )
+            .set((
+                status.eq(ScheduledEventStatus::Canceled),
+                error.eq(Some(reason.to_string())),
+                updated_at.eq(Utc::now().naive_utc()),
+            ));
+
+        log::debug!("{}", diesel::debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while canceling pending price change events")
+            .into_db_result()
+    }
 }
 
 impl ScheduledEventRowNew {
@@ -157,6 +157,39 @@ impl SubscriptionComponentRow {
             .into_db_result()
     }
 
+    /// Active components priced at `price_id`, on subscriptions that are live
+    /// (active or trialing).
+    pub async fn list_live_by_price_id(
+        conn: &mut PgConn,
+        price_id: PriceId,
+        tenant_id: &TenantId,
+    ) -> DbResult<Vec<SubscriptionComponentRow>> {
+        use crate::enums::SubscriptionStatusEnum;
+        use crate::schema::subscription_component::dsl as sc_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let live_statuses = vec![
+            SubscriptionStatusEnum::TrialActive,
+            SubscriptionStatusEnum::Active,
+        ];
+
+        let query = sc_dsl::subscription_component
+            .inner_join(crate::schema::subscription::table)
+            .filter(sc_dsl::price_id.eq(price_id))
+            .filter(crate::schema::subscription::tenant_id.eq(tenant_id))
+            .filter(crate::schema::subscription::status.eq_any(live_statuses))
+            .filter(sc_dsl::effective_to.is_null())
+            .select(SubscriptionComponentRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while listing live subscription components by price id")
+            .into_db_result()
+    }
+
     pub async fn count_active_subscriptions_by_product_id(
         conn: &mut PgConn,
         product_id: &common_domain::ids::ProductId,
@@ -67,6 +67,36 @@ impl SubscriptionEventRow {
             .into_db_result()
     }
 
+    /// Events of an imported subscription that apply before `date` and were never logged to the
+    /// MRR movement log, e.g. its creation when the periods before the import were skipped.
+    pub async fn fetch_unlogged_imported_before_date(
+        conn: &mut PgConn,
+        subscription_uid: SubscriptionId,
+        date: NaiveDate,
+    ) -> DbResult<Vec<SubscriptionEventRow>> {
+        use crate::schema::subscription::dsl as s_dsl;
+        use crate::schema::subscription_event::dsl as se_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let imported_subscription = s_dsl::subscription
+            .filter(s_dsl::id.eq(subscription_uid))
+            .filter(s_dsl::imported_at.is_not_null())
+            .select(s_dsl::id);
+
+        let query = se_dsl::subscription_event
+            .filter(se_dsl::subscription_id.eq_any(imported_subscription))
+            .filter(se_dsl::applies_to.lt(date))
+            .filter(se_dsl::bi_mrr_movement_log_id.is_null());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while fetching unlogged imported subscription events")
+            .into_db_result()
+    }
+
     pub async fn fetch_by_subscription_id_and_event_type(
         conn: &mut PgConn,
         subscription_uid: SubscriptionId,
@@ -117,4 +147,33 @@ impl SubscriptionEventRow {
             .attach("Error while updating subscription event mrr movement log id")
             .into_db_result()
     }
+
+    /// Deletes events of the given type that apply from `from_date` and were not
+    /// yet logged as an MRR movement, e.g. the churn recorded for a scheduled
+    /// cancellation that gets undone.
+    pub async fn delete_unprocessed_by_event_type(
+        conn: &mut PgConn,
+        subscription_uid: SubscriptionId,
+        param_event_type: SubscriptionEventType,
+        from_date: NaiveDate,
+    ) -> DbResult<usize> {
+        use crate::schema::subscription_event::dsl::{
+            applies_to, bi_mrr_movement_log_id, event_type, subscription_event, subscription_id,
+        };
+        use diesel_async::RunQueryDsl;
+
+        let query = diesel::delete(subscription_event)
+            .filter(subscription_id.eq(subscription_uid))
+            .filter(event_type.eq(param_event_type))
+            .filter(applies_to.ge(from_date))
+            .filter(bi_mrr_movement_log_id.is_null());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while deleting subscription events")
+            .into_db_result()
+    }
 }
@@ -99,6 +99,30 @@ impl SubscriptionRow {
             .into_db_result()
     }
 
+    /// Get the invoice number prefix of the subscription (if any)
+    pub async fn get_invoice_number_prefix(
+        conn: &mut PgConn,
+        tenant_id_param: TenantId,
+        subscription_id_param: SubscriptionId,
+    ) -> DbResult<Option<String>> {
+        use crate::schema::subscription::dsl::{
+            id, invoice_number_prefix, subscription, tenant_id,
+        };
+
+        let query = subscription
+            .filter(id.eq(subscription_id_param))
+            .filter(tenant_id.eq(tenant_id_param))
+            .select(invoice_number_prefix);
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .first(conn)
+            .await
+            .attach("Error while fetching subscription invoice number prefix")
+            .into_db_result()
+    }
+
     pub async fn get_subscription_period_by_id(
         conn: &mut PgConn,
         tenant_id_param: &TenantId,
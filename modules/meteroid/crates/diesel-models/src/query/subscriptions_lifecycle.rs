@@ -1,16 +1,20 @@
 use crate::errors::IntoDbResult;
-use chrono::{Duration, NaiveDate, NaiveDateTime, Utc};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime, Utc};
 
 use crate::subscriptions::{
-    SubscriptionCycleErrorRowPatch, SubscriptionCycleRowPatch, SubscriptionRow,
+    SubscriptionCycleErrorRowPatch, SubscriptionCycleRowPatch, SubscriptionRenewalRowPatch,
+    SubscriptionRow,
 };
 use crate::{DbResult, PgConn};
 
 use diesel::{BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl, debug_query};
 use diesel_async::RunQueryDsl;
 
-use crate::enums::{CycleActionEnum, SubscriptionStatusEnum};
-use common_domain::ids::{PlanVersionId, SubscriptionId, TenantId};
+use crate::enums::{
+    CycleActionEnum, RenewalPolicyEnum, ScheduledEventStatus, ScheduledEventTypeEnum,
+    SubscriptionStatusEnum,
+};
+use common_domain::ids::{PlanId, PlanVersionId, SubscriptionId, TenantId};
 use error_stack::ResultExt;
 use uuid::Uuid;
 
@@ -203,6 +207,240 @@ impl SubscriptionRow {
         Ok(ids)
     }
 
+    /// Lists active subscriptions with an invoice threshold, ordered by id.
+    ///
+    /// Subscriptions whose period is already due are left to the cycle transitions,
+    /// which will bill the usage with the period invoice.
+    pub async fn list_with_invoice_threshold(
+        conn: &mut PgConn,
+        after: Option<SubscriptionId>,
+        limit: i64,
+    ) -> DbResult<Vec<(SubscriptionId, TenantId)>> {
+        use crate::schema::subscription::dsl;
+
+        let today = Utc::now().naive_utc().date();
+
+        let mut query = dsl::subscription
+            .select((dsl::id, dsl::tenant_id))
+            .filter(dsl::invoice_threshold.is_not_null())
+            .filter(dsl::status.eq(SubscriptionStatusEnum::Active))
+            .filter(dsl::current_period_end.gt(today))
+            .order_by(dsl::id.asc())
+            .limit(limit)
+            .into_boxed();
+
+        if let Some(after) = after {
+            query = query.filter(dsl::id.gt(after));
+        }
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing subscriptions with an invoice threshold")
+            .into_db_result()
+    }
+
+    /// Lists trialing subscriptions whose trial ends on or before `until` and that were not
+    /// notified yet for that trial end, ordered by id.
+    ///
+    /// A free trial ends with its trial period, a paid trial on its pending `EndTrial` event.
+    pub async fn list_trials_ending_before(
+        conn: &mut PgConn,
+        until: NaiveDate,
+        after: Option<SubscriptionId>,
+        limit: i64,
+    ) -> DbResult<Vec<(SubscriptionId, TenantId)>> {
+        use crate::schema::scheduled_event::dsl as se_dsl;
+        use crate::schema::subscription::dsl;
+        use diesel::PgExpressionMethods;
+        use diesel::sql_types::{Date, Nullable};
+
+        let today = Utc::now().naive_utc().date();
+
+        let free_trial_ending = dsl::next_cycle_action
+            .eq(CycleActionEnum::EndTrial)
+            .and(dsl::current_period_end.ge(today))
+            .and(dsl::current_period_end.le(until))
+            .and(dsl::notified_trial_end.is_distinct_from(dsl::current_period_end));
+
+        let paid_trial_ending = diesel::dsl::exists(
+            se_dsl::scheduled_event
+                .filter(se_dsl::subscription_id.eq(dsl::id))
+                .filter(se_dsl::event_type.eq(ScheduledEventTypeEnum::EndTrial))
+                .filter(se_dsl::status.eq(ScheduledEventStatus::Pending))
+                .filter(se_dsl::scheduled_time.ge(today.and_time(NaiveTime::MIN)))
+                .filter(
+                    se_dsl::scheduled_time.lt((until + Duration::days(1)).and_time(NaiveTime::MIN)),
+                )
+                .filter(dsl::notified_trial_end.is_distinct_from(
+                    diesel::dsl::sql::<Nullable<Date>>("scheduled_event.scheduled_time::date"),
+                ))
+                .select(diesel::dsl::sql::<diesel::sql_types::Integer>("1")),
+        );
+
+        let mut query = dsl::subscription
+            .select((dsl::id, dsl::tenant_id))
+            .filter(dsl::status.eq(SubscriptionStatusEnum::TrialActive))
+            .filter(free_trial_ending.or(paid_trial_ending))
+            .order_by(dsl::id.asc())
+            .limit(limit)
+            .into_boxed();
+
+        if let Some(after) = after {
+            query = query.filter(dsl::id.gt(after));
+        }
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing subscriptions with an ending trial")
+            .into_db_result()
+    }
+
+    /// Records the trial end the trial-will-end notification was sent for.
+    pub async fn set_notified_trial_end(
+        conn: &mut PgConn,
+        id: SubscriptionId,
+        tenant_id: TenantId,
+        trial_end: NaiveDate,
+    ) -> DbResult<()> {
+        use crate::schema::subscription::dsl;
+
+        let query = diesel::update(dsl::subscription)
+            .filter(dsl::id.eq(id))
+            .filter(dsl::tenant_id.eq(tenant_id))
+            .set(dsl::notified_trial_end.eq(trial_end));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while updating the notified trial end")
+            .into_db_result()?;
+
+        Ok(())
+    }
+
+    /// Lists renewing subscriptions whose term ends within their notice period and that were not
+    /// notified yet for that end of term, ordered by id.
+    pub async fn list_renewals_upcoming(
+        conn: &mut PgConn,
+        today: NaiveDate,
+        after: Option<SubscriptionId>,
+        limit: i64,
+    ) -> DbResult<Vec<(SubscriptionId, TenantId)>> {
+        use crate::schema::subscription::dsl;
+        use diesel::PgExpressionMethods;
+        use diesel::sql_types::{Bool, Date};
+
+        let mut query = dsl::subscription
+            .select((dsl::id, dsl::tenant_id))
+            .filter(dsl::status.eq_any(SubscriptionStatusEnum::not_terminal()))
+            .filter(dsl::renewal_policy.ne(RenewalPolicyEnum::Expire))
+            .filter(dsl::end_date.gt(today))
+            .filter(
+                diesel::dsl::sql::<Bool>(
+                    "subscription.end_date - subscription.renewal_notice_days <= ",
+                )
+                .bind::<Date, _>(today),
+            )
+            .filter(dsl::renewal_notified_for.is_distinct_from(dsl::end_date))
+            .order_by(dsl::id.asc())
+            .limit(limit)
+            .into_boxed();
+
+        if let Some(after) = after {
+            query = query.filter(dsl::id.gt(after));
+        }
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .load(conn)
+            .await
+            .attach("Error while listing subscriptions with an upcoming renewal")
+            .into_db_result()
+    }
+
+    /// Records the end of term the renewal-upcoming notification was sent for.
+    pub async fn set_renewal_notified_for(
+        conn: &mut PgConn,
+        id: SubscriptionId,
+        tenant_id: TenantId,
+        end_date: NaiveDate,
+    ) -> DbResult<()> {
+        use crate::schema::subscription::dsl;
+
+        let query = diesel::update(dsl::subscription)
+            .filter(dsl::id.eq(id))
+            .filter(dsl::tenant_id.eq(tenant_id))
+            .set(dsl::renewal_notified_for.eq(end_date));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while updating the notified renewal")
+            .into_db_result()?;
+
+        Ok(())
+    }
+
+    pub async fn update_trial_duration(
+        conn: &mut PgConn,
+        id: SubscriptionId,
+        tenant_id: TenantId,
+        trial_duration: i32,
+    ) -> DbResult<()> {
+        use crate::schema::subscription::dsl;
+
+        let query = diesel::update(dsl::subscription)
+            .filter(dsl::id.eq(id))
+            .filter(dsl::tenant_id.eq(tenant_id))
+            .set(dsl::trial_duration.eq(trial_duration));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while updating the trial duration")
+            .into_db_result()?;
+
+        Ok(())
+    }
+
+    /// Switches the trialing plan of a subscription, `None` falling back to the plan version's.
+    pub async fn update_trialing_plan(
+        conn: &mut PgConn,
+        id: SubscriptionId,
+        tenant_id: TenantId,
+        trialing_plan_id: Option<PlanId>,
+    ) -> DbResult<()> {
+        use crate::schema::subscription::dsl;
+
+        let query = diesel::update(dsl::subscription)
+            .filter(dsl::id.eq(id))
+            .filter(dsl::tenant_id.eq(tenant_id))
+            .set(dsl::trialing_plan_id.eq(trialing_plan_id));
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while updating the trialing plan")
+            .into_db_result()?;
+
+        Ok(())
+    }
+
     /// Fetches and locks a subscription for processing, re-validating that it's still due.
     ///
     /// Returns None if the subscription no longer meets the criteria (e.g., was modified
@@ -320,6 +558,27 @@ impl SubscriptionCycleRowPatch {
         Ok(())
     }
 }
+impl SubscriptionRenewalRowPatch {
+    pub async fn patch(&self, conn: &mut PgConn) -> DbResult<()> {
+        use crate::schema::subscription::dsl;
+
+        let query = diesel::update(dsl::subscription)
+            .filter(dsl::id.eq(&self.id))
+            .filter(dsl::tenant_id.eq(&self.tenant_id))
+            .set(self);
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .execute(conn)
+            .await
+            .attach("Error while updating subscription renewal terms")
+            .into_db_result()?;
+
+        Ok(())
+    }
+}
+
 impl SubscriptionCycleErrorRowPatch {
     pub async fn patch(&self, conn: &mut PgConn) -> DbResult<()> {
         use crate::schema::subscription::dsl;
@@ -177,6 +177,32 @@ impl UserRow {
             .into_db_result()
     }
 
+    pub async fn list_by_tenant_id(
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+    ) -> DbResult<Vec<UserWithRoleRow>> {
+        use crate::schema::organization_member::dsl as om_dsl;
+        use crate::schema::tenant::dsl as t_dsl;
+        use crate::schema::user::dsl as u_dsl;
+        use diesel_async::RunQueryDsl;
+
+        let query = u_dsl::user
+            .inner_join(om_dsl::organization_member.on(u_dsl::id.eq(om_dsl::user_id)))
+            .inner_join(t_dsl::tenant.on(om_dsl::organization_id.eq(t_dsl::organization_id)))
+            .filter(t_dsl::id.eq(tenant_id))
+            .filter(u_dsl::archived_at.is_null())
+            .order_by((om_dsl::role.asc(), u_dsl::email.asc()))
+            .select(UserWithRoleRow::as_select());
+
+        log::debug!("{}", debug_query::<diesel::pg::Pg, _>(&query));
+
+        query
+            .get_results(conn)
+            .await
+            .attach("Error while listing users by tenant")
+            .into_db_result()
+    }
+
     pub async fn any_exists(conn: &mut PgConn) -> DbResult<bool> {
         use crate::schema::user::dsl as u_dsl;
         use diesel_async::RunQueryDsl;
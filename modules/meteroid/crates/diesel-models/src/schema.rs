@@ -81,6 +81,10 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "FeeTypeEnum"))]
     pub struct FeeTypeEnum;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "InvoiceApprovalStatusEnum"))]
+    pub struct InvoiceApprovalStatusEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "InvoicePaymentStatus"))]
     pub struct InvoicePaymentStatus;
@@ -97,6 +101,10 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "MRRMovementType"))]
     pub struct MrrMovementType;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "NumberingResetEnum"))]
+    pub struct NumberingResetEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "OrganizationUserRole"))]
     pub struct OrganizationUserRole;
@@ -121,10 +129,22 @@ pub mod sql_types {
     #[diesel(postgres_type(name = "PlanTypeEnum"))]
     pub struct PlanTypeEnum;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "PriceChangeGrandfatheringEnum"))]
+    pub struct PriceChangeGrandfatheringEnum;
+
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "PriceChangeStatusEnum"))]
+    pub struct PriceChangeStatusEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "QuoteStatusEnum"))]
     pub struct QuoteStatusEnum;
 
+    #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
+    #[diesel(postgres_type(name = "RenewalPolicyEnum"))]
+    pub struct RenewalPolicyEnum;
+
     #[derive(diesel::query_builder::QueryId, diesel::sql_types::SqlType)]
     #[diesel(postgres_type(name = "ScheduledEventStatus"))]
     pub struct ScheduledEventStatus;
@@ -251,6 +271,7 @@ diesel::table! {
         error_message -> Nullable<Text>,
         error_output_key -> Nullable<Text>,
         input_file_name -> Nullable<Text>,
+        output_key -> Nullable<Text>,
     }
 }
 
@@ -555,6 +576,12 @@ diesel::table! {
         vat_number_validation_status -> Nullable<CustomerVatValidationStatusEnum>,
         vat_number_checked_at -> Nullable<Timestamp>,
         vat_number_vies_check -> Nullable<Jsonb>,
+        custom_attributes -> Jsonb,
+        parent_customer_id -> Nullable<Uuid>,
+        pool_usage_with_parent -> Bool,
+        invoice_language -> Nullable<Text>,
+        invoice_custom_fields -> Jsonb,
+        invoice_approval_required -> Bool,
     }
 }
 
@@ -697,6 +724,7 @@ diesel::table! {
     use super::sql_types::InvoiceStatusEnum;
     use super::sql_types::InvoiceType;
     use super::sql_types::InvoicePaymentStatus;
+    use super::sql_types::InvoiceApprovalStatusEnum;
 
     invoice (id) {
         id -> Uuid,
@@ -744,12 +772,30 @@ diesel::table! {
         invoicing_entity_id -> Uuid,
         parent_invoice_id -> Nullable<Uuid>,
         consolidated_into_invoice_id -> Nullable<Uuid>,
+        custom_fields -> Jsonb,
+        sub_line_presentations -> Nullable<Jsonb>,
+        approval_status -> Nullable<InvoiceApprovalStatusEnum>,
+        approval_requested_at -> Nullable<Timestamp>,
+        approval_decided_at -> Nullable<Timestamp>,
+        approval_decided_by -> Nullable<Uuid>,
+        approval_comment -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    invoice_number_sequence (invoicing_entity_id, series, scope) {
+        invoicing_entity_id -> Uuid,
+        series -> Text,
+        scope -> Text,
+        next_number -> Int8,
+        tenant_id -> Uuid,
     }
 }
 
 diesel::table! {
     use diesel::sql_types::*;
     use super::sql_types::TaxResolverEnum;
+    use super::sql_types::NumberingResetEnum;
 
     invoicing_entity (id) {
         id -> Uuid,
@@ -784,6 +830,13 @@ diesel::table! {
         require_billing_information -> Bool,
         portal_theme_mode -> Nullable<Text>,
         portal_roundness -> Nullable<Text>,
+        invoice_number_reset -> NumberingResetEnum,
+        credit_note_number_pattern -> Text,
+        credit_note_number_reset -> NumberingResetEnum,
+        invoice_translations -> Jsonb,
+        invoice_template -> Nullable<Text>,
+        invoice_approval_threshold -> Nullable<Int8>,
+        invoice_approver_ids -> Array<Nullable<Uuid>>,
     }
 }
 
@@ -954,6 +1007,30 @@ diesel::table! {
         created_at -> Timestamp,
         archived_at -> Nullable<Timestamp>,
         catalog -> Bool,
+        variants -> Jsonb,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use super::sql_types::PriceChangeGrandfatheringEnum;
+    use super::sql_types::PriceChangeStatusEnum;
+
+    price_change (id) {
+        id -> Uuid,
+        tenant_id -> Uuid,
+        product_id -> Uuid,
+        price_id -> Uuid,
+        new_price_id -> Uuid,
+        new_pricing -> Jsonb,
+        effective_date -> Date,
+        grandfathering -> PriceChangeGrandfatheringEnum,
+        renewing_after -> Nullable<Date>,
+        status -> PriceChangeStatusEnum,
+        created_by -> Uuid,
+        created_at -> Timestamp,
+        applied_at -> Nullable<Timestamp>,
+        cancelled_at -> Nullable<Timestamp>,
     }
 }
 
@@ -965,6 +1042,8 @@ diesel::table! {
         plan_version_id -> Uuid,
         product_id -> Nullable<Uuid>,
         billable_metric_id -> Nullable<Uuid>,
+        sub_line_presentation -> Jsonb,
+        line_metadata -> Jsonb,
     }
 }
 
@@ -1197,6 +1276,7 @@ diesel::table! {
     use super::sql_types::SubscriptionActivationConditionEnum;
     use super::sql_types::SubscriptionStatusEnum;
     use super::sql_types::CycleActionEnum;
+    use super::sql_types::RenewalPolicyEnum;
 
     subscription (id) {
         id -> Uuid,
@@ -1236,6 +1316,19 @@ diesel::table! {
         processing_started_at -> Nullable<Timestamp>,
         payment_methods_config -> Nullable<Jsonb>,
         imported_at -> Nullable<Timestamp>,
+        trialing_plan_id -> Nullable<Uuid>,
+        notified_trial_end -> Nullable<Date>,
+        renewal_policy -> RenewalPolicyEnum,
+        renewal_term_months -> Nullable<Int4>,
+        renewal_plan_version_id -> Nullable<Uuid>,
+        renewal_notice_days -> Nullable<Int4>,
+        renewal_opt_out_days -> Nullable<Int4>,
+        renewal_notified_for -> Nullable<Date>,
+        instance_id -> Nullable<Text>,
+        instance_usage_property -> Nullable<Text>,
+        instance_metadata -> Nullable<Jsonb>,
+        invoice_number_prefix -> Nullable<Text>,
+        invoice_custom_fields -> Jsonb,
     }
 }
 
@@ -1442,6 +1535,9 @@ diesel::joinable!(invoice -> customer (customer_id));
 diesel::joinable!(invoice -> invoicing_entity (invoicing_entity_id));
 diesel::joinable!(invoice -> plan_version (plan_version_id));
 diesel::joinable!(invoice -> tenant (tenant_id));
+diesel::joinable!(invoice -> user (approval_decided_by));
+diesel::joinable!(invoice_number_sequence -> invoicing_entity (invoicing_entity_id));
+diesel::joinable!(invoice_number_sequence -> tenant (tenant_id));
 diesel::joinable!(invoicing_entity -> bank_account (bank_account_id));
 diesel::joinable!(invoicing_entity -> tenant (tenant_id));
 diesel::joinable!(organization_invite -> organization (organization_id));
@@ -1464,6 +1560,9 @@ diesel::joinable!(plan_version_add_on -> price (price_id));
 diesel::joinable!(plan_version_add_on -> tenant (tenant_id));
 diesel::joinable!(price -> product (product_id));
 diesel::joinable!(price -> tenant (tenant_id));
+diesel::joinable!(price_change -> price (price_id));
+diesel::joinable!(price_change -> product (product_id));
+diesel::joinable!(price_change -> tenant (tenant_id));
 diesel::joinable!(price_component -> billable_metric (billable_metric_id));
 diesel::joinable!(price_component -> plan_version (plan_version_id));
 diesel::joinable!(price_component -> product (product_id));
@@ -1546,6 +1645,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     feature,
     historical_rates_from_usd,
     invoice,
+    invoice_number_sequence,
     invoicing_entity,
     oauth_verifier,
     organization,
@@ -1558,6 +1658,7 @@ diesel::allow_tables_to_appear_in_same_query!(
     plan_version,
     plan_version_add_on,
     price,
+    price_change,
     price_component,
     product,
     product_accounting,
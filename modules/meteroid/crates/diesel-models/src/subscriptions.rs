@@ -5,7 +5,8 @@ use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 
 use crate::enums::{
-    BillingPeriodEnum, CycleActionEnum, SubscriptionActivationConditionEnum, SubscriptionStatusEnum,
+    BillingPeriodEnum, CycleActionEnum, RenewalPolicyEnum, SubscriptionActivationConditionEnum,
+    SubscriptionStatusEnum,
 };
 use common_domain::ids::{
     CustomerId, InvoicingEntityId, PlanId, PlanVersionId, QuoteId, SubscriptionId, TenantId,
@@ -53,6 +54,19 @@ pub struct SubscriptionRow {
     pub processing_started_at: Option<NaiveDateTime>,
     pub payment_methods_config: Option<serde_json::Value>,
     pub imported_at: Option<NaiveDateTime>,
+    pub trialing_plan_id: Option<PlanId>,
+    pub notified_trial_end: Option<NaiveDate>,
+    pub renewal_policy: RenewalPolicyEnum,
+    pub renewal_term_months: Option<i32>,
+    pub renewal_plan_version_id: Option<PlanVersionId>,
+    pub renewal_notice_days: Option<i32>,
+    pub renewal_opt_out_days: Option<i32>,
+    pub renewal_notified_for: Option<NaiveDate>,
+    pub instance_id: Option<String>,
+    pub instance_usage_property: Option<String>,
+    pub instance_metadata: Option<serde_json::Value>,
+    pub invoice_number_prefix: Option<String>,
+    pub invoice_custom_fields: serde_json::Value,
 }
 
 #[derive(Insertable, Debug)]
@@ -89,6 +103,9 @@ pub struct SubscriptionRowNew {
     pub backdate_invoices: bool,
     pub payment_methods_config: Option<serde_json::Value>,
     pub imported_at: Option<NaiveDateTime>,
+    pub instance_id: Option<String>,
+    pub instance_usage_property: Option<String>,
+    pub instance_metadata: Option<serde_json::Value>,
 }
 
 pub struct CancelSubscriptionParams {
@@ -147,6 +164,20 @@ pub struct SubscriptionCycleRowPatch {
     pub billing_day_anchor: Option<i16>,
 }
 
+#[derive(AsChangeset)]
+#[diesel(table_name = crate::schema::subscription)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct SubscriptionRenewalRowPatch {
+    pub id: SubscriptionId,
+    pub tenant_id: TenantId,
+    pub end_date: Option<Option<NaiveDate>>,
+    pub renewal_policy: Option<RenewalPolicyEnum>,
+    pub renewal_term_months: Option<Option<i32>>,
+    pub renewal_plan_version_id: Option<Option<PlanVersionId>>,
+    pub renewal_notice_days: Option<Option<i32>>,
+    pub renewal_opt_out_days: Option<Option<i32>>,
+}
+
 #[derive(AsChangeset)]
 #[diesel(table_name = crate::schema::subscription)]
 #[diesel(check_for_backend(diesel::pg::Pg))]
@@ -169,4 +200,6 @@ pub struct SubscriptionRowPatch {
     pub invoice_memo: Option<Option<String>>,
     pub purchase_order: Option<Option<String>>,
     pub payment_methods_config: Option<Option<serde_json::Value>>,
+    pub invoice_number_prefix: Option<Option<String>>,
+    pub invoice_custom_fields: Option<serde_json::Value>,
 }
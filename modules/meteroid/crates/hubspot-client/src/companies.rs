@@ -39,6 +39,7 @@ pub struct NewCompany {
     pub name: String,
     pub billing_email: Option<String>,
     pub billing_address: Option<CompanyAddress>,
+    pub invoice_custom_fields: Option<String>,
 }
 
 impl From<NewCompany> for BatchUpsertItemRequest {
@@ -56,6 +57,7 @@ impl From<NewCompany> for BatchUpsertItemRequest {
                 "meteroid_customer_state": value.billing_address.as_ref().and_then(|v| v.state.as_ref()),
                 "meteroid_customer_street": value.billing_address.as_ref().and_then(|v| v.line1.as_ref()),
                 "meteroid_customer_postal_code": value.billing_address.as_ref().and_then(|v| v.zip_code.as_ref()),
+                "meteroid_invoice_custom_fields": value.invoice_custom_fields,
             }),
             associations: None,
         }
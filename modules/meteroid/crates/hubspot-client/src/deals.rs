@@ -44,6 +44,7 @@ pub struct NewDeal {
     pub subscription_currency: String,
     pub subscription_status: String,
     pub subscription_mrr_cents: u64,
+    pub invoice_custom_fields: Option<String>,
 }
 
 impl From<NewDeal> for BatchUpsertItemRequest {
@@ -62,6 +63,7 @@ impl From<NewDeal> for BatchUpsertItemRequest {
                 "meteroid_subscription_mrr_cents": value.subscription_mrr_cents,
                 "meteroid_subscription_id": value.subscription_id.to_string(),
                 "meteroid_customer_id": value.customer_id.to_string(),
+                "meteroid_invoice_custom_fields": value.invoice_custom_fields,
             }),
             // most of the time are ignored or fail silently in hubspot so managed by a separate API call
             associations: None,
@@ -53,6 +53,39 @@ pub trait PropertiesApi {
 
         Ok(())
     }
+
+    /// Creates the invoice custom fields properties, added after `create_meteroid_properties`
+    /// ran for the existing connections. Properties that already exist are left untouched.
+    async fn create_invoice_custom_fields_properties(
+        &self,
+        access_token: &SecretString,
+    ) -> Result<(), HubspotError> {
+        let (companies, deals) = tokio::join!(
+            self.batch_create_properties(
+                ObjectType::Companies,
+                vec![company_invoice_custom_fields_property()],
+                access_token
+            ),
+            self.batch_create_properties(
+                ObjectType::Deals,
+                vec![deal_invoice_custom_fields_property()],
+                access_token
+            )
+        );
+
+        for res in [companies, deals] {
+            match res {
+                Ok(_)
+                | Err(HubspotError::ClientError {
+                    status_code: Some(409),
+                    ..
+                }) => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -176,9 +209,23 @@ fn company_properties() -> Vec<NewProperty> {
             has_unique_value: false,
             hidden: false,
         },
+        company_invoice_custom_fields_property(),
     ]
 }
 
+fn company_invoice_custom_fields_property() -> NewProperty {
+    NewProperty {
+        name: CompanyProperty::MeteroidInvoiceCustomFields.to_string(),
+        description: Some("Custom fields set on the customer's invoices in Meteroid".to_string()),
+        label: "Meteroid invoice custom fields".to_string(),
+        type_: PropertyType::String,
+        field_type: PropertyFieldType::Text,
+        group_name: PropertyGroup::MeteroidInfo.to_string(),
+        has_unique_value: false,
+        hidden: false,
+    }
+}
+
 fn deal_properties() -> Vec<NewProperty> {
     vec![
         NewProperty {
@@ -261,9 +308,25 @@ fn deal_properties() -> Vec<NewProperty> {
             has_unique_value: false,
             hidden: false,
         },
+        deal_invoice_custom_fields_property(),
     ]
 }
 
+fn deal_invoice_custom_fields_property() -> NewProperty {
+    NewProperty {
+        name: DealProperty::MeteroidInvoiceCustomFields.to_string(),
+        description: Some(
+            "Custom fields set on the subscription's invoices in Meteroid".to_string(),
+        ),
+        label: "Meteroid invoice custom fields".to_string(),
+        type_: PropertyType::String,
+        field_type: PropertyFieldType::Text,
+        group_name: PropertyGroup::MeteroidInfo.to_string(),
+        has_unique_value: false,
+        hidden: false,
+    }
+}
+
 #[derive(strum::Display, Serialize, Deserialize)]
 pub enum ObjectType {
     #[strum(to_string = "companies")]
@@ -370,6 +433,8 @@ enum CompanyProperty {
     MeteroidCustomerStreet,
     #[strum(to_string = "meteroid_customer_postal_code")]
     MeteroidCustomerPostalCode,
+    #[strum(to_string = "meteroid_invoice_custom_fields")]
+    MeteroidInvoiceCustomFields,
 }
 
 #[derive(strum::Display)]
@@ -391,4 +456,6 @@ enum DealProperty {
     MeteroidSubscriptionStatus,
     #[strum(to_string = "meteroid_subscription_mrr_cents")]
     MeteroidSubscriptionMrrCents,
+    #[strum(to_string = "meteroid_invoice_custom_fields")]
+    MeteroidInvoiceCustomFields,
 }
@@ -18,6 +18,7 @@ fn generate_grpc_types(root: &Path) -> Result<(), Report<BuildError>> {
         "bankaccounts",
         "batchjobs",
         "billablemetrics",
+        "catalog",
         "connectors",
         "entitlements",
         "customers",
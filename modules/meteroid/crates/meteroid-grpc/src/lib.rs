@@ -57,6 +57,12 @@ pub mod meteroid {
             }
         }
 
+        pub mod catalog {
+            pub mod v1 {
+                tonic::include_proto!("meteroid.api.catalog.v1");
+            }
+        }
+
         pub mod connectors {
             pub mod v1 {
                 tonic::include_proto!("meteroid.api.connectors.v1");
@@ -200,17 +206,6 @@ pub mod meteroid {
         pub mod shared {
             pub mod v1 {
                 include_proto_serde!("meteroid.api.shared.v1");
-
-                impl BillingPeriod {
-                    pub fn months_value(&self) -> u32 {
-                        match self {
-                            BillingPeriod::Monthly => 1,
-                            BillingPeriod::Quarterly => 3,
-                            BillingPeriod::Semiannual => 6,
-                            BillingPeriod::Annual => 12,
-                        }
-                    }
-                }
             }
         }
     }
@@ -11,7 +11,7 @@ use meteroid_invoicing::{
 };
 use rust_decimal::Decimal;
 use rusty_money::{Money, iso};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::Path;
 use std::str::FromStr;
 use std::time::{Duration, Instant};
@@ -101,6 +101,8 @@ fn create_test_invoice() -> Invoice {
 
     Invoice {
         lang: "fr-FR".to_string(),
+        translation_overrides: HashMap::new(),
+        template: None,
         organization: Organization {
             name: "Acme Inc.".to_string(),
             logo_src: None,
@@ -145,6 +147,7 @@ fn create_test_invoice() -> Invoice {
             due_date,
             memo: Some("Thank you for your subscription!".to_string()),
             payment_url: Some("https://pay.example.com/inv-2025-001".to_string()),
+            usage_annex_url: None,
             flags: Flags {
                 show_payment_status: Some(true),
                 show_payment_info: Some(true),
@@ -156,6 +159,7 @@ fn create_test_invoice() -> Invoice {
             },
             discount: Money::from_major(0, eur),
             purchase_order: Some("Order #210".to_string()),
+            custom_fields: BTreeMap::new(),
             parent_invoice_number: None,
             parent_invoice_date: None,
         },
@@ -185,6 +189,7 @@ fn create_test_invoice() -> Invoice {
                     },
                 ],
                 group_label: None,
+                metadata: BTreeMap::new(),
             },
             InvoiceLine {
                 name: "Software License".to_string(),
@@ -198,6 +203,7 @@ fn create_test_invoice() -> Invoice {
                 is_prorated: false,
                 sub_lines: vec![],
                 group_label: None,
+                metadata: BTreeMap::new(),
             },
         ],
         coupons: vec![
@@ -213,6 +219,7 @@ fn create_test_invoice() -> Invoice {
             TaxBreakdownItem {
                 name: "VAT 20%".to_string(),
                 rate: Decimal::from_str("0.2").unwrap(),
+                taxable_amount: Money::from_major(1000, eur),
                 amount: Money::from_major(200, eur),
                 exemption_type: Some(TaxExemptionType::ReverseCharge)
             },
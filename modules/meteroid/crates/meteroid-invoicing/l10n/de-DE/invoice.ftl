@@ -0,0 +1,70 @@
+invoice-title = Rechnung
+invoice-number = Rechnung Nr.
+company-logo-alt = Firmenlogo
+issue-date = Rechnungsdatum
+amount-due = Fälliger Betrag
+due-date = Fälligkeitsdatum
+due-label = fällig am
+bill-from = Rechnungssteller
+bill-to = Rechnungsempfänger
+invoice-lines = Rechnungspositionen
+description = Beschreibung
+quantity = Menge
+unit-price = Einzelpreis
+prorated = anteilig
+tax-rate = Steuersatz
+tax = Steuer
+amount = Gesamt (netto)
+subtotal = Zwischensumme
+total-due = Gesamtbetrag
+legal-info = Rechtliche Hinweise
+vat-exempt-legal = Keine Umsatzsteuer ausgewiesen
+exchange-rate-info = Wechselkurs am {$date}:  {$equality} | Umgerechneter Gesamtbetrag = {$amount_converted}
+
+payment-status = Zahlungsstatus
+payment-status-paid = Bezahlt
+payment-status-partially-paid = Teilweise bezahlt
+payment-status-unpaid = Unbezahlt
+payment-method = Zahlungsart
+payment-date = Datum
+payment-amount = Betrag
+no-transactions = Keine Zahlungen erhalten
+payment-info-title = Zahlungsinformationen
+payment-terms-title = Zahlungsbedingungen
+payment-terms-text = Zahlbar innerhalb von {$days} Tagen
+tax-info-title = Steuerinformationen
+tax-included-text = Alle Preise sind in {$currency} angegeben und enthalten Steuern, sofern nicht anders angegeben.
+reverse-charge-label = Steuerschuldnerschaft des Leistungsempfängers
+tax-reverse-charge = Steuerschuldnerschaft des Leistungsempfängers (Reverse Charge)
+pay-online = Online bezahlen
+usage-annex = Die detaillierte Nutzung ist im Nutzungsanhang verfügbar
+seller-vat-id = USt-IdNr. des Verkäufers
+customer-vat-id = USt-IdNr. des Kunden
+vat-standard = USt. (Regelsatz)
+vat-reduced = USt. (ermäßigter Satz)
+vat-exempt-notice = Umsatzsteuerfreie Positionen sind nicht in der Steuerberechnung enthalten
+reverse-charge-notice = Steuerschuldnerschaft des Leistungsempfängers gemäß Art. 44 und 196 der Richtlinie 2006/112/EG. Der Empfänger schuldet die Umsatzsteuer.
+intra-eu-notice = Innergemeinschaftliche Lieferung - Art. 138 MwSt-Systemrichtlinie
+b2b-notice = Geschäft zwischen Unternehmen
+eu-vat-directive-notice = Diese Rechnung entspricht der MwSt-Systemrichtlinie 2006/112/EG
+company-registration = Handelsregisternummer
+discount = Rabatt
+purchase-order = Bestellnummer
+no-tax-applied = Keine Steuer berechnet
+cancels-and-replaces = Storniert und ersetzt die Rechnung {$invoice_number} vom {$invoice_date}
+
+# Credit Note specific translations
+credit-note-title = Gutschrift
+credit-note-number = Gutschrift Nr.
+related-invoice = Zugehörige Rechnung
+credit-to = Gutschrift an
+reason = Grund
+refunded = Erstattet
+credit-to-balance = Dem Guthaben gutgeschrieben
+debt-cancellation = Forderungsverzicht
+refunded-amount = Erstatteter Betrag
+credited-amount = Gutgeschriebener Betrag
+total-credit = Gutschrift gesamt
+related-invoice-value = {$invoice_number} vom {$invoice_date}
+credit-note-vat-directive-notice = Diese Gutschrift entspricht der MwSt-Systemrichtlinie 2006/112/EG
+credit-note-reverse-charge-notice = Steuerschuldnerschaft des Leistungsempfängers gemäß Art. 44 und 196 der Richtlinie 2006/112/EG. Der Empfänger der ursprünglichen Rechnung schuldet die Umsatzsteuer.
@@ -36,6 +36,7 @@ tax-included-text = All prices are in {$currency} and include tax unless specifi
 reverse-charge-label = Reverse charge
 tax-reverse-charge = Tax to be paid on reverse charge basis
 pay-online = Pay online
+usage-annex = Detailed usage is available in the usage annex
 seller-vat-id = Seller Tax ID
 customer-vat-id = Customer Tax ID
 vat-standard = VAT (Standard Rate)
@@ -0,0 +1,70 @@
+invoice-title = Factura
+invoice-number = Factura n.º
+company-logo-alt = Logotipo de la empresa
+issue-date = Fecha de emisión
+amount-due = Importe adeudado
+due-date = Fecha de vencimiento
+due-label = vence el
+bill-from = Emisor
+bill-to = Cliente
+invoice-lines = Conceptos
+description = Descripción
+quantity = Cantidad
+unit-price = Precio unitario
+prorated = prorrateado
+tax-rate = Tipo impositivo
+tax = Impuesto
+amount = Total (sin impuestos)
+subtotal = Subtotal
+total-due = Total a pagar
+legal-info = Información legal
+vat-exempt-legal = IVA no aplicable
+exchange-rate-info = Tipo de cambio a {$date}:  {$equality} | Importe total convertido = {$amount_converted}
+
+payment-status = Estado del pago
+payment-status-paid = Pagada
+payment-status-partially-paid = Pagada parcialmente
+payment-status-unpaid = Pendiente de pago
+payment-method = Método
+payment-date = Fecha
+payment-amount = Importe
+no-transactions = No se han recibido pagos
+payment-info-title = Información de pago
+payment-terms-title = Condiciones de pago
+payment-terms-text = Pago a realizar en un plazo de {$days} días
+tax-info-title = Información fiscal
+tax-included-text = Todos los precios están en {$currency} e incluyen impuestos salvo que se indique lo contrario.
+reverse-charge-label = Inversión del sujeto pasivo
+tax-reverse-charge = Impuesto a liquidar por inversión del sujeto pasivo
+pay-online = Pagar en línea
+usage-annex = El detalle del consumo está disponible en el anexo de uso
+seller-vat-id = NIF-IVA del vendedor
+customer-vat-id = NIF-IVA del cliente
+vat-standard = IVA (tipo general)
+vat-reduced = IVA (tipo reducido)
+vat-exempt-notice = Los conceptos exentos de IVA no se incluyen en el cálculo del impuesto
+reverse-charge-notice = Inversión del sujeto pasivo conforme a los artículos 44 y 196 de la Directiva 2006/112/CE. El destinatario es el sujeto pasivo del IVA.
+intra-eu-notice = Entrega intracomunitaria - Art. 138 Directiva IVA UE
+b2b-notice = Operación entre empresas
+eu-vat-directive-notice = Esta factura cumple con la Directiva IVA UE 2006/112/CE
+company-registration = Número de registro mercantil
+discount = Descuento
+purchase-order = Orden de compra
+no-tax-applied = Sin impuestos aplicados
+cancels-and-replaces = Anula y sustituye a la factura {$invoice_number} del {$invoice_date}
+
+# Credit Note specific translations
+credit-note-title = Factura rectificativa
+credit-note-number = Factura rectificativa n.º
+related-invoice = Factura relacionada
+credit-to = Abono a
+reason = Motivo
+refunded = Reembolsado
+credit-to-balance = Abonado al saldo
+debt-cancellation = Cancelación de deuda
+refunded-amount = Importe reembolsado
+credited-amount = Importe abonado
+total-credit = Total abonado
+related-invoice-value = {$invoice_number} del {$invoice_date}
+credit-note-vat-directive-notice = Esta factura rectificativa cumple con la Directiva IVA UE 2006/112/CE
+credit-note-reverse-charge-notice = Inversión del sujeto pasivo conforme a los artículos 44 y 196 de la Directiva 2006/112/CE. El destinatario de la factura original es el sujeto pasivo del IVA.
@@ -37,6 +37,7 @@ tax-included-text = Tous les prix sont en {$currency} et incluent la TVA sauf in
 tax-reverse-charge = TVA due selon le régime de l'autoliquidation
 reverse-charge-label = Autoliquidation
 pay-online = Payer en ligne
+usage-annex = Le détail de la consommation est disponible dans l'annexe d'utilisation
 seller-vat-id = N° TVA vendeur
 customer-vat-id = N° TVA client
 vat-standard = TVA (Taux normal)
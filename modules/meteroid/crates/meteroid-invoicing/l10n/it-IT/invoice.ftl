@@ -0,0 +1,70 @@
+invoice-title = Fattura
+invoice-number = Fattura n.
+company-logo-alt = Logo aziendale
+issue-date = Data di emissione
+amount-due = Importo dovuto
+due-date = Data di scadenza
+due-label = scadenza
+bill-from = Emittente
+bill-to = Cliente
+invoice-lines = Righe fattura
+description = Descrizione
+quantity = Quantità
+unit-price = Prezzo unitario
+prorated = pro rata
+tax-rate = Aliquota
+tax = Imposta
+amount = Totale (imponibile)
+subtotal = Subtotale
+total-due = Totale dovuto
+legal-info = Informazioni legali
+vat-exempt-legal = IVA non applicabile
+exchange-rate-info = Tasso di cambio al {$date}:  {$equality} | Importo totale convertito = {$amount_converted}
+
+payment-status = Stato del pagamento
+payment-status-paid = Pagata
+payment-status-partially-paid = Parzialmente pagata
+payment-status-unpaid = Non pagata
+payment-method = Metodo
+payment-date = Data
+payment-amount = Importo
+no-transactions = Nessun pagamento ricevuto
+payment-info-title = Informazioni di pagamento
+payment-terms-title = Termini di pagamento
+payment-terms-text = Pagamento da effettuare entro {$days} giorni
+tax-info-title = Informazioni fiscali
+tax-included-text = Tutti i prezzi sono in {$currency} e includono le imposte, salvo diversa indicazione.
+reverse-charge-label = Inversione contabile
+tax-reverse-charge = Imposta da assolvere con il meccanismo dell'inversione contabile
+pay-online = Paga online
+usage-annex = Il dettaglio dei consumi è disponibile nell'allegato di utilizzo
+seller-vat-id = Partita IVA del venditore
+customer-vat-id = Partita IVA del cliente
+vat-standard = IVA (aliquota ordinaria)
+vat-reduced = IVA (aliquota ridotta)
+vat-exempt-notice = Le voci esenti IVA non sono incluse nel calcolo dell'imposta
+reverse-charge-notice = Inversione contabile ai sensi degli articoli 44 e 196 della Direttiva 2006/112/CE. Il destinatario è debitore dell'IVA.
+intra-eu-notice = Cessione intracomunitaria - Art. 138 Direttiva IVA UE
+b2b-notice = Operazione tra imprese
+eu-vat-directive-notice = La presente fattura è conforme alla Direttiva IVA UE 2006/112/CE
+company-registration = Numero di iscrizione al registro delle imprese
+discount = Sconto
+purchase-order = Ordine di acquisto
+no-tax-applied = Nessuna imposta applicata
+cancels-and-replaces = Annulla e sostituisce la fattura {$invoice_number} del {$invoice_date}
+
+# Credit Note specific translations
+credit-note-title = Nota di credito
+credit-note-number = Nota di credito n.
+related-invoice = Fattura di riferimento
+credit-to = Accredito a
+reason = Motivo
+refunded = Rimborsato
+credit-to-balance = Accreditato sul saldo
+debt-cancellation = Annullamento del debito
+refunded-amount = Importo rimborsato
+credited-amount = Importo accreditato
+total-credit = Totale accreditato
+related-invoice-value = {$invoice_number} del {$invoice_date}
+credit-note-vat-directive-notice = La presente nota di credito è conforme alla Direttiva IVA UE 2006/112/CE
+credit-note-reverse-charge-notice = Inversione contabile ai sensi degli articoli 44 e 196 della Direttiva 2006/112/CE. Il destinatario della fattura originale è debitore dell'IVA.
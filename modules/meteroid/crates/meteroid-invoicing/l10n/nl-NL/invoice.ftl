@@ -0,0 +1,70 @@
+invoice-title = Factuur
+invoice-number = Factuurnr.
+company-logo-alt = Bedrijfslogo
+issue-date = Factuurdatum
+amount-due = Verschuldigd bedrag
+due-date = Vervaldatum
+due-label = te betalen op
+bill-from = Afzender
+bill-to = Factuuradres
+invoice-lines = Factuurregels
+description = Omschrijving
+quantity = Aantal
+unit-price = Prijs per eenheid
+prorated = naar rato
+tax-rate = Belastingtarief
+tax = Belasting
+amount = Totaal (excl. btw)
+subtotal = Subtotaal
+total-due = Totaal te betalen
+legal-info = Juridische informatie
+vat-exempt-legal = Btw niet van toepassing
+exchange-rate-info = Wisselkoers op {$date}:  {$equality} | Omgerekend totaalbedrag = {$amount_converted}
+
+payment-status = Betaalstatus
+payment-status-paid = Betaald
+payment-status-partially-paid = Gedeeltelijk betaald
+payment-status-unpaid = Onbetaald
+payment-method = Methode
+payment-date = Datum
+payment-amount = Bedrag
+no-transactions = Geen betalingen ontvangen
+payment-info-title = Betalingsinformatie
+payment-terms-title = Betalingsvoorwaarden
+payment-terms-text = Betaling binnen {$days} dagen
+tax-info-title = Belastinginformatie
+tax-included-text = Alle prijzen zijn in {$currency} en inclusief belasting, tenzij anders vermeld.
+reverse-charge-label = Btw verlegd
+tax-reverse-charge = Btw verschuldigd volgens de verleggingsregeling
+pay-online = Online betalen
+usage-annex = Het gedetailleerde verbruik staat in de verbruiksbijlage
+seller-vat-id = Btw-nummer verkoper
+customer-vat-id = Btw-nummer klant
+vat-standard = Btw (standaardtarief)
+vat-reduced = Btw (verlaagd tarief)
+vat-exempt-notice = Van btw vrijgestelde regels zijn niet opgenomen in de belastingberekening
+reverse-charge-notice = Btw verlegd op grond van artikelen 44 en 196 van Richtlijn 2006/112/EG. De afnemer is de btw verschuldigd.
+intra-eu-notice = Intracommunautaire levering - Art. 138 EU-btw-richtlijn
+b2b-notice = Transactie tussen ondernemingen
+eu-vat-directive-notice = Deze factuur voldoet aan EU-btw-richtlijn 2006/112/EG
+company-registration = KvK-nummer
+discount = Korting
+purchase-order = Inkooporder
+no-tax-applied = Geen belasting toegepast
+cancels-and-replaces = Annuleert en vervangt factuur {$invoice_number} van {$invoice_date}
+
+# Credit Note specific translations
+credit-note-title = Creditnota
+credit-note-number = Creditnotanr.
+related-invoice = Bijbehorende factuur
+credit-to = Gecrediteerd aan
+reason = Reden
+refunded = Terugbetaald
+credit-to-balance = Bijgeschreven op saldo
+debt-cancellation = Kwijtschelding
+refunded-amount = Terugbetaald bedrag
+credited-amount = Gecrediteerd bedrag
+total-credit = Totaal gecrediteerd
+related-invoice-value = {$invoice_number} van {$invoice_date}
+credit-note-vat-directive-notice = Deze creditnota voldoet aan EU-btw-richtlijn 2006/112/EG
+credit-note-reverse-charge-notice = Btw verlegd op grond van artikelen 44 en 196 van Richtlijn 2006/112/EG. De ontvanger van de oorspronkelijke factuur is de btw verschuldigd.
@@ -0,0 +1,70 @@
+invoice-title = Fatura
+invoice-number = Fatura n.º
+company-logo-alt = Logótipo da empresa
+issue-date = Data de emissão
+amount-due = Montante em dívida
+due-date = Data de vencimento
+due-label = vence a
+bill-from = Emitente
+bill-to = Cliente
+invoice-lines = Linhas da fatura
+description = Descrição
+quantity = Quantidade
+unit-price = Preço unitário
+prorated = proporcional
+tax-rate = Taxa de imposto
+tax = Imposto
+amount = Total (sem impostos)
+subtotal = Subtotal
+total-due = Total a pagar
+legal-info = Informações legais
+vat-exempt-legal = IVA não aplicável
+exchange-rate-info = Taxa de câmbio em {$date}:  {$equality} | Montante total convertido = {$amount_converted}
+
+payment-status = Estado do pagamento
+payment-status-paid = Paga
+payment-status-partially-paid = Parcialmente paga
+payment-status-unpaid = Por pagar
+payment-method = Método
+payment-date = Data
+payment-amount = Montante
+no-transactions = Nenhum pagamento recebido
+payment-info-title = Informações de pagamento
+payment-terms-title = Condições de pagamento
+payment-terms-text = Pagamento a efetuar no prazo de {$days} dias
+tax-info-title = Informações fiscais
+tax-included-text = Todos os preços estão em {$currency} e incluem impostos, salvo indicação em contrário.
+reverse-charge-label = Autoliquidação
+tax-reverse-charge = Imposto a liquidar pelo adquirente (autoliquidação)
+pay-online = Pagar online
+usage-annex = O detalhe do consumo está disponível no anexo de utilização
+seller-vat-id = NIF do vendedor
+customer-vat-id = NIF do cliente
+vat-standard = IVA (taxa normal)
+vat-reduced = IVA (taxa reduzida)
+vat-exempt-notice = Os itens isentos de IVA não estão incluídos no cálculo do imposto
+reverse-charge-notice = Autoliquidação nos termos dos artigos 44.º e 196.º da Diretiva 2006/112/CE. O destinatário é o devedor do IVA.
+intra-eu-notice = Transmissão intracomunitária - Art. 138.º Diretiva IVA UE
+b2b-notice = Transação entre empresas
+eu-vat-directive-notice = Esta fatura está em conformidade com a Diretiva IVA UE 2006/112/CE
+company-registration = Número de registo comercial
+discount = Desconto
+purchase-order = Nota de encomenda
+no-tax-applied = Nenhum imposto aplicado
+cancels-and-replaces = Anula e substitui a fatura {$invoice_number} de {$invoice_date}
+
+# Credit Note specific translations
+credit-note-title = Nota de crédito
+credit-note-number = Nota de crédito n.º
+related-invoice = Fatura relacionada
+credit-to = Crédito a
+reason = Motivo
+refunded = Reembolsado
+credit-to-balance = Creditado no saldo
+debt-cancellation = Anulação de dívida
+refunded-amount = Montante reembolsado
+credited-amount = Montante creditado
+total-credit = Total creditado
+related-invoice-value = {$invoice_number} de {$invoice_date}
+credit-note-vat-directive-notice = Esta nota de crédito está em conformidade com a Diretiva IVA UE 2006/112/CE
+credit-note-reverse-charge-notice = Autoliquidação nos termos dos artigos 44.º e 196.º da Diretiva 2006/112/CE. O destinatário da fatura original é o devedor do IVA.
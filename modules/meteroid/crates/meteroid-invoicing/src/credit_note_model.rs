@@ -3,6 +3,7 @@ use common_domain::country::CountryCode;
 use rust_decimal::Decimal;
 use rusty_money::iso;
 use rusty_money::iso::Currency;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Copy)]
 pub enum CreditType {
@@ -28,6 +29,8 @@ pub struct CreditNote {
     pub metadata: CreditNoteMetadata,
     pub lines: Vec<CreditNoteLine>,
     pub tax_breakdown: Vec<TaxBreakdownItem>,
+    /// Tenant-provided translations for `lang`, by message id (e.g. "bill-to").
+    pub translation_overrides: HashMap<String, String>,
 }
 
 pub struct CreditNoteMetadata {
@@ -118,6 +121,7 @@ pub enum TaxExemptionType {
 pub struct TaxBreakdownItem {
     pub name: String,
     pub rate: Decimal,
+    pub taxable_amount: Price,
     pub amount: Price,
     pub exemption_type: Option<TaxExemptionType>,
 }
@@ -3,7 +3,7 @@ use crate::credit_note_model::{
     TaxBreakdownItem, TaxExemptionType,
 };
 use crate::errors::{InvoicingError, InvoicingResult};
-use chrono::prelude::*;
+use crate::locale::{apply_overrides, format_date, resolve_locale};
 use derive_typst_intoval::{IntoDict, IntoValue};
 use fluent_static::MessageBundle;
 use rust_decimal::Decimal;
@@ -29,6 +29,11 @@ mod l10n {
         resources = [
             ("l10n/en-US/invoice.ftl", "en-US"),
             ("l10n/fr-FR/invoice.ftl", "fr-FR"),
+            ("l10n/de-DE/invoice.ftl", "de-DE"),
+            ("l10n/es-ES/invoice.ftl", "es-ES"),
+            ("l10n/it-IT/invoice.ftl", "it-IT"),
+            ("l10n/nl-NL/invoice.ftl", "nl-NL"),
+            ("l10n/pt-PT/invoice.ftl", "pt-PT"),
         ],
         default_language = "en-US")]
     pub struct CreditNoteL10n;
@@ -250,24 +255,26 @@ pub struct TypstCreditNoteContent {
 
 impl From<&CreditNote> for TypstCreditNoteContent {
     fn from(credit_note: &CreditNote) -> Self {
-        let lang = match credit_note.lang.as_str() {
-            "fr" | "fr-FR" => "fr-FR",
-            _ => "en-US",
-        };
+        let lang = resolve_locale(&credit_note.lang);
 
         let credit_note_l10n =
             &l10n::CreditNoteL10N::get(lang).unwrap_or(l10n::CreditNoteL10N::default());
 
+        let formatted_related_date = format_date(lang, &credit_note.metadata.related_invoice_date)
+            .unwrap_or_else(|_| {
+                credit_note
+                    .metadata
+                    .related_invoice_date
+                    .format("%Y-%m-%d")
+                    .to_string()
+            });
+
         let mut translations = typst::foundations::dict! {
             // Credit note specific translations
             "credit_note_title" => credit_note_l10n.credit_note_title().into_value(),
             "credit_note_number" => credit_note_l10n.credit_note_number().into_value(),
             "related_invoice" => credit_note_l10n.related_invoice().into_value(),
-            "related_invoice_value" => {
-                let formatted_related_date = format_date(lang, &credit_note.metadata.related_invoice_date)
-                    .unwrap_or_else(|_| credit_note.metadata.related_invoice_date.format("%Y-%m-%d").to_string());
-                credit_note_l10n.related_invoice_value(&credit_note.metadata.related_invoice_number, &formatted_related_date).into_value()
-            },
+            "related_invoice_value" => credit_note_l10n.related_invoice_value(&credit_note.metadata.related_invoice_number, &formatted_related_date).into_value(),
             "credit_to" => credit_note_l10n.credit_to().into_value(),
             "reason" => credit_note_l10n.reason().into_value(),
             "refunded" => credit_note_l10n.refunded().into_value(),
@@ -300,6 +307,15 @@ impl From<&CreditNote> for TypstCreditNoteContent {
             "credit_note_reverse_charge_notice" => credit_note_l10n.credit_note_reverse_charge_notice().into_value()
         };
 
+        let mut override_args = vec![
+            (
+                "invoice_number",
+                credit_note.metadata.related_invoice_number.clone(),
+            ),
+            ("invoice_date", formatted_related_date.clone()),
+            ("currency", credit_note.metadata.currency.name.to_string()),
+        ];
+
         if let Some(exchange_rate) = credit_note.organization.exchange_rate {
             let date = format_date(lang, &credit_note.metadata.issue_date).unwrap_or_else(|_| {
                 credit_note
@@ -327,8 +343,17 @@ impl From<&CreditNote> for TypstCreditNoteContent {
                     .exchange_rate_info(&date, &equality, &amount_converted)
                     .into_value(),
             );
+            override_args.push(("date", date));
+            override_args.push(("equality", equality));
+            override_args.push(("amount_converted", amount_converted));
         }
 
+        apply_overrides(
+            &mut translations,
+            &credit_note.translation_overrides,
+            &override_args,
+        );
+
         let currency_symbol = credit_note.metadata.currency.symbol();
 
         let formatted_issue_date = format_date(lang, &credit_note.metadata.issue_date)
@@ -393,14 +418,7 @@ impl From<&CreditNote> for TypstCreditNoteContent {
             customer: TypstCustomer::from_customer_with_lang(&credit_note.customer, lang),
             number: credit_note.metadata.number.clone(),
             related_invoice_number: credit_note.metadata.related_invoice_number.clone(),
-            related_invoice_date: format_date(lang, &credit_note.metadata.related_invoice_date)
-                .unwrap_or_else(|_| {
-                    credit_note
-                        .metadata
-                        .related_invoice_date
-                        .format("%Y-%m-%d")
-                        .to_string()
-                }),
+            related_invoice_date: formatted_related_date,
             issue_date: formatted_issue_date,
             subtotal,
             tax_amount,
@@ -427,14 +445,6 @@ impl From<&CreditNote> for TypstCreditNoteContent {
     }
 }
 
-#[inline]
-fn format_date(lang: &str, date: &chrono::NaiveDate) -> Result<String, InvoicingError> {
-    match lang {
-        "fr-FR" => Ok(date.format_localized("%e %B %Y", Locale::fr_FR).to_string()),
-        _ => Ok(date.format("%B %e, %Y").to_string()),
-    }
-}
-
 #[inline]
 fn format_currency_dec(amount: Decimal, currency: &iso::Currency) -> String {
     rusty_money::Money::from_decimal(amount, currency).to_string()
@@ -0,0 +1,650 @@
+//! Syntax-agnostic view of invoices and credit notes following the EN 16931 semantic model,
+//! shared by the Factur-X (CII) and Peppol BIS (UBL) generators.
+
+use crate::credit_note_model::{self as cn, CreditNote};
+use crate::errors::{InvoicingError, InvoicingResult};
+use crate::model::{self, Invoice};
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use rusty_money::FormattableCurrency;
+
+/// UN/ECE Rec 20 unit code for "one", used for every billed quantity
+pub(crate) const UNIT_CODE_ONE: &str = "C62";
+
+/// UNTDID 4461 payment means code for a SEPA credit transfer
+pub(crate) const PAYMENT_MEANS_SEPA_CREDIT_TRANSFER: &str = "58";
+
+/// UNTDID 1001 document type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DocumentKind {
+    Invoice,
+    CorrectedInvoice,
+    CreditNote,
+}
+
+impl DocumentKind {
+    pub(crate) fn type_code(self) -> &'static str {
+        match self {
+            DocumentKind::Invoice => "380",
+            DocumentKind::CreditNote => "381",
+            DocumentKind::CorrectedInvoice => "384",
+        }
+    }
+}
+
+/// EN 16931 VAT category (UNTDID 5305).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VatCategory {
+    Standard,
+    ZeroRated,
+    Exempt,
+    ReverseCharge,
+    NotSubject,
+}
+
+impl VatCategory {
+    fn from_rate(rate: Decimal) -> Self {
+        if rate.is_zero() {
+            VatCategory::ZeroRated
+        } else {
+            VatCategory::Standard
+        }
+    }
+
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            VatCategory::Standard => "S",
+            VatCategory::ZeroRated => "Z",
+            VatCategory::Exempt => "E",
+            VatCategory::ReverseCharge => "AE",
+            VatCategory::NotSubject => "O",
+        }
+    }
+
+    /// Exemption reason code (VATEX) and text, required by EN 16931 for exempted categories.
+    pub(crate) fn exemption(self) -> Option<(Option<&'static str>, &'static str)> {
+        match self {
+            VatCategory::Standard | VatCategory::ZeroRated => None,
+            VatCategory::Exempt => Some((None, "Exempt from VAT")),
+            VatCategory::ReverseCharge => Some((Some("VATEX-EU-AE"), "Reverse charge")),
+            VatCategory::NotSubject => Some((Some("VATEX-EU-O"), "Not subject to VAT")),
+        }
+    }
+
+    /// Supplies outside the scope of VAT carry no rate at all (BR-O-05).
+    pub(crate) fn has_rate(self) -> bool {
+        self != VatCategory::NotSubject
+    }
+}
+
+impl From<&model::TaxExemptionType> for VatCategory {
+    fn from(exemption_type: &model::TaxExemptionType) -> Self {
+        match exemption_type {
+            model::TaxExemptionType::ReverseCharge => VatCategory::ReverseCharge,
+            model::TaxExemptionType::TaxExempt => VatCategory::Exempt,
+            model::TaxExemptionType::NotRegistered => VatCategory::NotSubject,
+        }
+    }
+}
+
+impl From<&cn::TaxExemptionType> for VatCategory {
+    fn from(exemption_type: &cn::TaxExemptionType) -> Self {
+        match exemption_type {
+            cn::TaxExemptionType::ReverseCharge => VatCategory::ReverseCharge,
+            cn::TaxExemptionType::TaxExempt => VatCategory::Exempt,
+            cn::TaxExemptionType::NotRegistered => VatCategory::NotSubject,
+        }
+    }
+}
+
+/// Lines and amounts of the document sharing a VAT category and rate.
+pub(crate) struct VatGroup {
+    pub category: VatCategory,
+    /// Fraction (0.2 for 20%)
+    pub rate: Decimal,
+    /// Sum of the line net amounts
+    pub line_total: Decimal,
+    /// Taxable amount, after discounts and credits
+    pub basis: Decimal,
+    pub tax: Decimal,
+}
+
+impl VatGroup {
+    fn matches(&self, category: VatCategory, rate: Decimal) -> bool {
+        self.category == category && self.rate == rate
+    }
+
+    /// Document level allowance (positive) or charge (negative) bridging the line total and
+    /// the taxable amount of the group.
+    pub(crate) fn adjustment(&self) -> Decimal {
+        self.line_total - self.basis
+    }
+}
+
+pub(crate) struct PostalAddress<'a> {
+    pub line1: Option<&'a str>,
+    pub line2: Option<&'a str>,
+    pub city: Option<&'a str>,
+    pub zip_code: Option<&'a str>,
+    pub state: Option<&'a str>,
+    pub country: Option<&'a str>,
+}
+
+pub(crate) struct Party<'a> {
+    pub name: &'a str,
+    pub legal_number: Option<&'a str>,
+    pub address: PostalAddress<'a>,
+    pub email: Option<&'a str>,
+    pub tax_id: Option<&'a str>,
+}
+
+pub(crate) struct Line<'a> {
+    pub name: &'a str,
+    pub description: Option<&'a str>,
+    pub quantity: Decimal,
+    pub price: Decimal,
+    pub amount: Decimal,
+    pub category: VatCategory,
+    pub rate: Decimal,
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+pub(crate) struct BankTransfer<'a> {
+    pub iban: String,
+    pub bic: Option<&'a str>,
+}
+
+pub(crate) struct Totals {
+    pub line_total: Decimal,
+    pub allowance_total: Decimal,
+    pub charge_total: Decimal,
+    pub tax_basis_total: Decimal,
+    pub tax_total: Decimal,
+    pub grand_total: Decimal,
+    pub prepaid: Decimal,
+    pub due: Decimal,
+}
+
+/// An invoice or credit note, with positive amounts for what the buyer owes or is credited.
+pub(crate) struct Document<'a> {
+    pub kind: DocumentKind,
+    pub number: &'a str,
+    pub issue_date: NaiveDate,
+    pub due_date: Option<NaiveDate>,
+    pub payment_term: Option<u32>,
+    pub currency_code: &'static str,
+    pub exponent: u32,
+    pub note: Option<&'a str>,
+    pub purchase_order: Option<&'a str>,
+    /// Number and date of the invoice this document corrects or credits
+    pub preceding_invoice: Option<(&'a str, Option<NaiveDate>)>,
+    pub seller: Party<'a>,
+    pub buyer: Party<'a>,
+    pub lines: Vec<Line<'a>>,
+    pub vat_groups: Vec<VatGroup>,
+    pub totals: Totals,
+    pub bank_transfer: Option<BankTransfer<'a>>,
+    pub payment_reference: Option<&'a str>,
+}
+
+impl<'a> Document<'a> {
+    pub(crate) fn from_invoice(invoice: &'a Invoice) -> InvoicingResult<Self> {
+        let currency = invoice.metadata.currency;
+
+        let breakdown: Vec<VatGroup> = invoice
+            .tax_breakdown
+            .iter()
+            .map(|item| VatGroup {
+                category: item
+                    .exemption_type
+                    .as_ref()
+                    .map_or_else(|| VatCategory::from_rate(item.rate), VatCategory::from),
+                rate: item.rate,
+                line_total: Decimal::ZERO,
+                basis: *item.taxable_amount.amount(),
+                tax: *item.amount.amount(),
+            })
+            .collect();
+
+        let lines = invoice
+            .lines
+            .iter()
+            .map(|line| {
+                Line::new(
+                    &line.name,
+                    line.description.as_deref(),
+                    line.quantity,
+                    line.unit_price.as_ref().map(|p| *p.amount()),
+                    *line.subtotal.amount(),
+                    line_category(&breakdown, line.tax_rate),
+                    line.tax_rate,
+                    (line.start_date, line.end_date),
+                    currency.exponent(),
+                )
+            })
+            .collect();
+
+        let bank_detail = |key: &str| {
+            invoice
+                .bank_details
+                .as_ref()
+                .and_then(|d| d.get(key))
+                .map(String::as_str)
+        };
+        let prepaid = invoice.transactions.iter().map(|t| t.amount.amount()).sum();
+
+        Document::build(
+            Document {
+                kind: if invoice.metadata.parent_invoice_number.is_some() {
+                    DocumentKind::CorrectedInvoice
+                } else {
+                    DocumentKind::Invoice
+                },
+                number: &invoice.metadata.number,
+                issue_date: invoice.metadata.issue_date,
+                due_date: Some(invoice.metadata.due_date),
+                payment_term: Some(invoice.metadata.payment_term),
+                currency_code: currency.code(),
+                exponent: currency.exponent(),
+                note: invoice.metadata.memo.as_deref(),
+                purchase_order: invoice.metadata.purchase_order.as_deref(),
+                preceding_invoice: invoice
+                    .metadata
+                    .parent_invoice_number
+                    .as_deref()
+                    .map(|number| (number, invoice.metadata.parent_invoice_date)),
+                seller: Party {
+                    name: &invoice.organization.name,
+                    legal_number: invoice.organization.legal_number.as_deref(),
+                    address: invoice_address(&invoice.organization.address),
+                    email: invoice.organization.email.as_deref(),
+                    tax_id: invoice.organization.tax_id.as_deref(),
+                },
+                buyer: Party {
+                    name: &invoice.customer.name,
+                    legal_number: invoice.customer.legal_number.as_deref(),
+                    address: invoice_address(&invoice.customer.address),
+                    email: invoice.customer.email.as_deref(),
+                    tax_id: invoice.customer.tax_id.as_deref(),
+                },
+                lines,
+                vat_groups: breakdown,
+                totals: Totals::default(),
+                bank_transfer: bank_detail("IBAN").map(|iban| BankTransfer {
+                    iban: iban.replace(' ', ""),
+                    bic: bank_detail("BIC/SWIFT"),
+                }),
+                payment_reference: bank_detail("Payment Reference"),
+            },
+            *invoice.metadata.discount.amount(),
+            prepaid,
+        )
+    }
+
+    /// Credit note amounts are negative in the model, as rendered on the PDF.
+    pub(crate) fn from_credit_note(credit_note: &'a CreditNote) -> InvoicingResult<Self> {
+        let currency = credit_note.metadata.currency;
+
+        // The taxable amount of a credit note is tracked per rate, and repeated on every tax
+        // sharing that rate
+        let mut seen_rates = vec![];
+        let breakdown: Vec<VatGroup> = credit_note
+            .tax_breakdown
+            .iter()
+            .map(|item| {
+                let first_of_rate = !seen_rates.contains(&item.rate);
+                seen_rates.push(item.rate);
+                VatGroup {
+                    category: item
+                        .exemption_type
+                        .as_ref()
+                        .map_or_else(|| VatCategory::from_rate(item.rate), VatCategory::from),
+                    rate: item.rate,
+                    line_total: Decimal::ZERO,
+                    basis: if first_of_rate {
+                        -*item.taxable_amount.amount()
+                    } else {
+                        Decimal::ZERO
+                    },
+                    tax: -*item.amount.amount(),
+                }
+            })
+            .collect();
+
+        let lines = credit_note
+            .lines
+            .iter()
+            .map(|line| {
+                Line::new(
+                    &line.name,
+                    line.description.as_deref(),
+                    line.quantity.map(|q| q.abs()),
+                    line.unit_price.as_ref().map(|p| p.amount().abs()),
+                    -*line.subtotal.amount(),
+                    line_category(&breakdown, line.tax_rate),
+                    line.tax_rate,
+                    (line.start_date, line.end_date),
+                    currency.exponent(),
+                )
+            })
+            .collect();
+
+        Document::build(
+            Document {
+                kind: DocumentKind::CreditNote,
+                number: &credit_note.metadata.number,
+                issue_date: credit_note.metadata.issue_date,
+                due_date: None,
+                payment_term: None,
+                currency_code: currency.code(),
+                exponent: currency.exponent(),
+                note: credit_note
+                    .metadata
+                    .reason
+                    .as_deref()
+                    .or(credit_note.metadata.memo.as_deref()),
+                purchase_order: None,
+                preceding_invoice: Some((
+                    &credit_note.metadata.related_invoice_number,
+                    Some(credit_note.metadata.related_invoice_date),
+                )),
+                seller: Party {
+                    name: &credit_note.organization.name,
+                    legal_number: credit_note.organization.legal_number.as_deref(),
+                    address: credit_note_address(&credit_note.organization.address),
+                    email: credit_note.organization.email.as_deref(),
+                    tax_id: credit_note.organization.tax_id.as_deref(),
+                },
+                buyer: Party {
+                    name: &credit_note.customer.name,
+                    legal_number: credit_note.customer.legal_number.as_deref(),
+                    address: credit_note_address(&credit_note.customer.address),
+                    email: credit_note.customer.email.as_deref(),
+                    tax_id: credit_note.customer.tax_id.as_deref(),
+                },
+                lines,
+                vat_groups: breakdown,
+                totals: Totals::default(),
+                bank_transfer: None,
+                payment_reference: None,
+            },
+            Decimal::ZERO,
+            Decimal::ZERO,
+        )
+    }
+
+    /// Completes the VAT groups with the line amounts and computes the totals, so that they
+    /// satisfy the EN 16931 calculation rules (BR-CO-10 to BR-CO-16).
+    fn build(mut document: Self, discount: Decimal, prepaid: Decimal) -> InvoicingResult<Self> {
+        if document.lines.is_empty() {
+            return Err(InvoicingError::XmlGenerationError(
+                "An e-invoice requires at least one line".to_string(),
+            ));
+        }
+
+        let has_breakdown = !document.vat_groups.is_empty();
+        let groups = &mut document.vat_groups;
+
+        let mut merged: Vec<VatGroup> = vec![];
+        for group in groups.drain(..) {
+            match merged
+                .iter_mut()
+                .find(|g| g.matches(group.category, group.rate))
+            {
+                Some(existing) => {
+                    existing.basis += group.basis;
+                    existing.tax += group.tax;
+                }
+                None => merged.push(group),
+            }
+        }
+        *groups = merged;
+
+        for line in &document.lines {
+            match groups
+                .iter_mut()
+                .find(|g| g.matches(line.category, line.rate))
+            {
+                Some(group) => group.line_total += line.amount,
+                None => groups.push(VatGroup {
+                    category: line.category,
+                    rate: line.rate,
+                    line_total: line.amount,
+                    basis: line.amount,
+                    tax: Decimal::ZERO,
+                }),
+            }
+        }
+
+        // Without a tax breakdown, the discount cannot be split by rate and goes to the largest
+        // group
+        if !has_breakdown && let Some(group) = groups.iter_mut().max_by_key(|g| g.line_total) {
+            group.basis -= discount;
+        }
+
+        let line_total: Decimal = document.lines.iter().map(|l| l.amount).sum();
+        let allowance_total: Decimal = groups
+            .iter()
+            .map(VatGroup::adjustment)
+            .filter(|a| *a > Decimal::ZERO)
+            .sum();
+        let charge_total: Decimal = groups
+            .iter()
+            .map(VatGroup::adjustment)
+            .filter(|a| *a < Decimal::ZERO)
+            .map(|a| -a)
+            .sum();
+        let tax_basis_total = line_total - allowance_total + charge_total;
+        let tax_total: Decimal = groups.iter().map(|g| g.tax).sum();
+        let grand_total = tax_basis_total + tax_total;
+
+        document.totals = Totals {
+            line_total,
+            allowance_total,
+            charge_total,
+            tax_basis_total,
+            tax_total,
+            grand_total,
+            prepaid,
+            due: grand_total - prepaid,
+        };
+
+        Ok(document)
+    }
+
+    pub(crate) fn amount(&self, value: Decimal) -> String {
+        let mut value = value.round_dp(self.exponent);
+        value.rescale(self.exponent);
+        value.to_string()
+    }
+}
+
+impl Default for Totals {
+    fn default() -> Self {
+        Totals {
+            line_total: Decimal::ZERO,
+            allowance_total: Decimal::ZERO,
+            charge_total: Decimal::ZERO,
+            tax_basis_total: Decimal::ZERO,
+            tax_total: Decimal::ZERO,
+            grand_total: Decimal::ZERO,
+            prepaid: Decimal::ZERO,
+            due: Decimal::ZERO,
+        }
+    }
+}
+
+impl<'a> Line<'a> {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        name: &'a str,
+        description: Option<&'a str>,
+        quantity: Option<Decimal>,
+        unit_price: Option<Decimal>,
+        amount: Decimal,
+        category: VatCategory,
+        rate: Decimal,
+        (start_date, end_date): (NaiveDate, NaiveDate),
+        exponent: u32,
+    ) -> Self {
+        let rounded = |value: Decimal| value.round_dp(exponent);
+
+        // The net price must multiply back to the line amount and cannot be negative (BR-27),
+        // which prorated lines, tiered usage and credits do not satisfy: they are billed as a
+        // single unit.
+        let (quantity, price) = match (quantity, unit_price) {
+            (Some(quantity), Some(price))
+                if quantity.is_sign_positive()
+                    && price.is_sign_positive()
+                    && rounded(quantity * price) == rounded(amount) =>
+            {
+                (quantity, price)
+            }
+            _ if amount.is_sign_negative() => (Decimal::NEGATIVE_ONE, -amount),
+            _ => (Decimal::ONE, amount),
+        };
+
+        Line {
+            name,
+            description: description.filter(|d| !d.is_empty()),
+            quantity: quantity.normalize(),
+            price: price.normalize(),
+            amount,
+            category,
+            rate,
+            start_date,
+            end_date,
+        }
+    }
+}
+
+/// The category of a line is the one of the tax breakdown entry at the same rate.
+fn line_category(breakdown: &[VatGroup], rate: Decimal) -> VatCategory {
+    breakdown
+        .iter()
+        .find(|group| group.rate == rate)
+        .map_or_else(|| VatCategory::from_rate(rate), |group| group.category)
+}
+
+fn invoice_address(address: &model::Address) -> PostalAddress<'_> {
+    PostalAddress {
+        line1: address.line1.as_deref(),
+        line2: address.line2.as_deref(),
+        city: address.city.as_deref(),
+        zip_code: address.zip_code.as_deref(),
+        state: address.state.as_deref(),
+        country: address.country.as_ref().map(|c| c.code.as_str()),
+    }
+}
+
+fn credit_note_address(address: &cn::Address) -> PostalAddress<'_> {
+    PostalAddress {
+        line1: address.line1.as_deref(),
+        line2: address.line2.as_deref(),
+        city: address.city.as_deref(),
+        zip_code: address.zip_code.as_deref(),
+        state: address.state.as_deref(),
+        country: address.country.as_ref().map(|c| c.code.as_str()),
+    }
+}
+
+/// Rates are stored as fractions (0.2 for 20%)
+pub(crate) fn format_percent(rate: Decimal) -> String {
+    (rate * Decimal::ONE_HUNDRED).normalize().to_string()
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Minimal indented XML writer. Elements are written in the order their schema requires.
+#[derive(Default)]
+pub(crate) struct XmlWriter {
+    xml: String,
+    depth: usize,
+}
+
+impl XmlWriter {
+    pub(crate) fn new(root: &str, namespaces: &[(&str, &str)]) -> Self {
+        let mut writer = XmlWriter::default();
+        writer.raw(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+
+        let mut open = format!("<{root}");
+        for (prefix, uri) in namespaces {
+            if prefix.is_empty() {
+                open.push_str(&format!(r#" xmlns="{uri}""#));
+            } else {
+                open.push_str(&format!(r#" xmlns:{prefix}="{uri}""#));
+            }
+        }
+        open.push('>');
+        writer.raw(&open);
+        writer.depth += 1;
+        writer
+    }
+
+    pub(crate) fn finish(mut self, root: &str) -> String {
+        self.close(root);
+        self.xml
+    }
+
+    fn raw(&mut self, content: &str) {
+        self.xml.push_str(&"  ".repeat(self.depth));
+        self.xml.push_str(content);
+        self.xml.push('\n');
+    }
+
+    pub(crate) fn open(&mut self, tag: &str) {
+        self.raw(&format!("<{tag}>"));
+        self.depth += 1;
+    }
+
+    pub(crate) fn close(&mut self, tag: &str) {
+        self.depth -= 1;
+        self.raw(&format!("</{tag}>"));
+    }
+
+    pub(crate) fn empty(&mut self, tag: &str) {
+        self.raw(&format!("<{tag}/>"));
+    }
+
+    /// Empty values are skipped, as e-invoicing rules forbid empty elements.
+    pub(crate) fn text(&mut self, tag: &str, value: &str) {
+        if !value.is_empty() {
+            self.raw(&format!("<{tag}>{}</{tag}>", escape(value)));
+        }
+    }
+
+    pub(crate) fn opt_text(&mut self, tag: &str, value: Option<&str>) {
+        if let Some(value) = value {
+            self.text(tag, value);
+        }
+    }
+
+    pub(crate) fn text_with_attribute(
+        &mut self,
+        tag: &str,
+        (name, attribute): (&str, &str),
+        value: &str,
+    ) {
+        if !value.is_empty() {
+            self.raw(&format!(
+                r#"<{tag} {name}="{}">{}</{tag}>"#,
+                escape(attribute),
+                escape(value)
+            ));
+        }
+    }
+}
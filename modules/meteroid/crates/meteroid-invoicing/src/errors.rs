@@ -4,6 +4,8 @@ pub enum InvoicingError {
     I18nError(String),
     #[error("Failed to generate invoice document: {0}")]
     InvoiceGenerationError(String),
+    #[error("Invalid invoice template: {0}")]
+    TemplateError(String),
     #[error("Failed to generate PDF: {0}")]
     PdfGenerationError(String),
     #[error("Failed to generate SVG: {0}")]
@@ -1,8 +1,12 @@
 pub mod credit_note_model;
 pub mod credit_note_render;
+mod e_invoice;
 pub mod errors;
+pub mod locale;
 pub mod model;
 pub mod pdf;
 pub mod svg;
+pub mod template;
 pub mod typst_render;
 pub mod xml_facturx;
+pub mod xml_ubl;
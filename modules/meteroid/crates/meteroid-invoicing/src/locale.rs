@@ -0,0 +1,83 @@
+use crate::errors::InvoicingError;
+use chrono::{Locale, NaiveDate};
+use common_utils::lang::match_language;
+use std::collections::HashMap;
+use typst::foundations::{Dict, IntoValue};
+
+/// Locales shipped with translations in `l10n/`. The first one is the fallback.
+pub const SUPPORTED_LOCALES: [&str; 7] = [
+    "en-US", "fr-FR", "de-DE", "es-ES", "it-IT", "nl-NL", "pt-PT",
+];
+
+static MESSAGES: &str = include_str!("../l10n/en-US/invoice.ftl");
+
+/// Maps a language tag to a supported locale, matching on the primary language when the
+/// region differs ("de", "de-AT" -> "de-DE"). Unknown languages fall back to en-US.
+pub fn resolve_locale(lang: &str) -> &'static str {
+    match_language(SUPPORTED_LOCALES, lang, |locale| *locale).unwrap_or(SUPPORTED_LOCALES[0])
+}
+
+/// Ids of the invoice and credit note messages a tenant can translate (e.g. "bill-to").
+pub fn message_ids() -> impl Iterator<Item = &'static str> {
+    MESSAGES
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once(" = "))
+        .map(|(id, _)| id.trim())
+}
+
+pub fn is_message_id(id: &str) -> bool {
+    message_ids().any(|m| m == id)
+}
+
+/// Replaces the bundled translations with the ones provided by the tenant.
+/// Only the messages present in `translations` are overridden, as the others are not shown.
+/// Placeholders follow the syntax of the bundled messages (`{$days}` or `{ $days }`).
+pub(crate) fn apply_overrides(
+    translations: &mut Dict,
+    overrides: &HashMap<String, String>,
+    args: &[(&str, String)],
+) {
+    for (id, text) in overrides {
+        let key = id.replace('-', "_");
+        if translations.contains(&key) {
+            translations.insert(key.into(), fill_placeholders(text, args).into_value());
+        }
+    }
+}
+
+fn fill_placeholders(text: &str, args: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let Some(end) = placeholder.find('}') else {
+            rest = placeholder;
+            break;
+        };
+        let name = placeholder[1..end].trim().trim_start_matches('$');
+        match args.iter().find(|(arg, _)| *arg == name) {
+            Some((_, value)) => out.push_str(value),
+            None => out.push_str(&placeholder[..=end]),
+        }
+        rest = &placeholder[end + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+pub(crate) fn format_date(locale: &str, date: &NaiveDate) -> Result<String, InvoicingError> {
+    let formatted = match locale {
+        "fr-FR" => date.format_localized("%e %B %Y", Locale::fr_FR),
+        "de-DE" => date.format_localized("%e. %B %Y", Locale::de_DE),
+        "es-ES" => date.format_localized("%e de %B de %Y", Locale::es_ES),
+        "it-IT" => date.format_localized("%e %B %Y", Locale::it_IT),
+        "nl-NL" => date.format_localized("%e %B %Y", Locale::nl_NL),
+        "pt-PT" => date.format_localized("%e de %B de %Y", Locale::pt_PT),
+        _ => date.format("%B %e, %Y"),
+    };
+    Ok(formatted.to_string())
+}
@@ -3,7 +3,7 @@ use common_domain::country::CountryCode;
 use rust_decimal::Decimal;
 use rusty_money::iso;
 use rusty_money::iso::Currency;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone)]
 pub enum PaymentStatus {
@@ -33,6 +33,10 @@ pub struct Invoice {
     pub transactions: Vec<Transaction>,
     pub bank_details: Option<HashMap<String, String>>, // TODO here we need the BankAccount format, and we mak to kv in the Typst types
     pub tax_breakdown: Vec<TaxBreakdownItem>,
+    /// Tenant-provided translations for `lang`, by message id (e.g. "bill-to").
+    pub translation_overrides: HashMap<String, String>,
+    /// Typst source of the invoicing entity's template, rendered instead of the built-in one.
+    pub template: Option<String>,
 }
 
 pub enum TaxExemptionType {
@@ -44,6 +48,7 @@ pub enum TaxExemptionType {
 pub struct TaxBreakdownItem {
     pub name: String,
     pub rate: Decimal,
+    pub taxable_amount: Price,
     pub amount: Price,
     pub exemption_type: Option<TaxExemptionType>,
 }
@@ -92,8 +97,12 @@ pub struct InvoiceMetadata {
     pub due_date: chrono::NaiveDate,
     pub memo: Option<String>,
     pub payment_url: Option<String>,
+    /// Download link of the usage detail of the lines that are merged on the document.
+    pub usage_annex_url: Option<String>,
     pub flags: Flags,
     pub purchase_order: Option<String>,
+    /// Structured fields (e.g. cost center), listed with the invoice number and dates.
+    pub custom_fields: BTreeMap<String, String>,
     pub parent_invoice_number: Option<String>,
     pub parent_invoice_date: Option<chrono::NaiveDate>,
 }
@@ -125,6 +134,8 @@ pub struct InvoiceLine {
     /// Optional grouping heading (e.g. plan name) used to group lines on the rendered invoice,
     /// typically set for consolidated invoices that merge several subscriptions.
     pub group_label: Option<String>,
+    /// Structured fields of the line (e.g. project code), shown under its description.
+    pub metadata: BTreeMap<String, String>,
 }
 
 pub struct InvoiceSubLine {
@@ -3,13 +3,14 @@ use crate::credit_note_render::TypstCreditNoteRenderer;
 use crate::errors::{InvoicingError, InvoicingResult};
 use crate::model::Invoice;
 use crate::typst_render::TypstInvoiceRenderer;
+use crate::xml_facturx::{FacturXDocument, facturx_xmp_metadata, generate_facturx_xml};
 use async_trait::async_trait;
 use bytes::Bytes;
 use typst::foundations::Smart;
 use typst_layout::PagedDocument;
 use typst_pdf::{self, PdfOptions, PdfStandard, PdfStandards};
 
-fn generate_pdf_from_document(document: &PagedDocument) -> InvoicingResult<Bytes> {
+pub(crate) fn generate_pdf_from_document(document: &PagedDocument) -> InvoicingResult<Bytes> {
     let pdf_standard = [PdfStandard::A_3b]; // PDF/A-3b is required for e-invoicing
     let pdf_standards = PdfStandards::new(&pdf_standard).map_err(|_| {
         InvoicingError::PdfGenerationError("Failed to create PDF standards".to_string())
@@ -31,9 +32,97 @@ fn generate_pdf_from_document(document: &PagedDocument) -> InvoicingResult<Bytes
     Ok(Bytes::from(pdf))
 }
 
+/// Rewrites the XMP metadata stream of a PDF through an incremental update, the object
+/// keeping its number so the catalog needs no change. Typst offers no hook on the metadata
+/// it writes.
+fn update_xmp_metadata(
+    pdf: &[u8],
+    update: impl FnOnce(&str) -> InvoicingResult<String>,
+) -> InvoicingResult<Bytes> {
+    let malformed =
+        |what: &str| InvoicingError::PdfGenerationError(format!("Malformed PDF: {what}"));
+
+    let metadata_at = find(pdf, b"/Type/Metadata", 0)
+        .or_else(|| find(pdf, b"/Type /Metadata", 0))
+        .ok_or_else(|| malformed("no metadata"))?;
+    let object_at =
+        rfind(&pdf[..metadata_at], b" 0 obj").ok_or_else(|| malformed("metadata object"))?;
+    let number_at = pdf[..object_at]
+        .iter()
+        .rposition(|b| !b.is_ascii_digit())
+        .map_or(0, |i| i + 1);
+    let object_number = std::str::from_utf8(&pdf[number_at..object_at])
+        .ok()
+        .and_then(|n| n.parse::<u32>().ok())
+        .ok_or_else(|| malformed("metadata object number"))?;
+
+    let stream_start = find(pdf, b"stream\n", metadata_at)
+        .map(|i| i + b"stream\n".len())
+        .ok_or_else(|| malformed("metadata stream"))?;
+    let stream_end =
+        find(pdf, b"\nendstream", stream_start).ok_or_else(|| malformed("metadata stream"))?;
+    let xmp = std::str::from_utf8(&pdf[stream_start..stream_end])
+        .map_err(|_| malformed("metadata encoding"))?;
+
+    let trailer_at = rfind(pdf, b"trailer").ok_or_else(|| malformed("no trailer"))?;
+    let startxref_at =
+        find(pdf, b"startxref", trailer_at).ok_or_else(|| malformed("no startxref"))?;
+    let trailer = std::str::from_utf8(&pdf[trailer_at + b"trailer".len()..startxref_at])
+        .map_err(|_| malformed("trailer encoding"))?
+        .trim()
+        .strip_suffix(">>")
+        .ok_or_else(|| malformed("trailer dictionary"))?;
+    let previous_xref = std::str::from_utf8(&pdf[startxref_at + b"startxref".len()..])
+        .ok()
+        .and_then(|rest| rest.split_whitespace().next())
+        .and_then(|offset| offset.parse::<usize>().ok())
+        .ok_or_else(|| malformed("startxref offset"))?;
+
+    let xmp = update(xmp)?;
+
+    let mut out = pdf.to_vec();
+    if out.last() != Some(&b'\n') {
+        out.push(b'\n');
+    }
+    let object_offset = out.len();
+    out.extend_from_slice(
+        format!(
+            "{object_number} 0 obj\n<<\n  /Length {}\n  /Type /Metadata\n  /Subtype /XML\n>>\nstream\n",
+            xmp.len()
+        )
+        .as_bytes(),
+    );
+    out.extend_from_slice(xmp.as_bytes());
+    out.extend_from_slice(b"\nendstream\nendobj\n\n");
+
+    let xref_offset = out.len();
+    out.extend_from_slice(
+        format!(
+            "xref\n{object_number} 1\n{object_offset:010} 00000 n\r\ntrailer\n{trailer}  /Prev {previous_xref}\n>>\nstartxref\n{xref_offset}\n%%EOF"
+        )
+        .as_bytes(),
+    );
+
+    Ok(Bytes::from(out))
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|w| w == needle)
+        .map(|i| i + from)
+}
+
+fn rfind(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack.windows(needle.len()).rposition(|w| w == needle)
+}
+
 #[async_trait]
 pub trait PdfGenerator: Send + Sync {
     async fn generate_pdf(&self, invoice: &Invoice) -> InvoicingResult<Bytes>;
+    /// Generates a Factur-X hybrid invoice, whose PDF embeds the CII XML.
+    async fn generate_facturx_pdf(&self, invoice: &Invoice) -> InvoicingResult<FacturXDocument>;
 }
 
 pub struct TypstPdfGenerator {
@@ -54,6 +143,17 @@ impl PdfGenerator for TypstPdfGenerator {
 
         generate_pdf_from_document(&result)
     }
+
+    async fn generate_facturx_pdf(&self, invoice: &Invoice) -> InvoicingResult<FacturXDocument> {
+        let xml = generate_facturx_xml(invoice)?;
+        let result = self.renderer.render_facturx_invoice(invoice, &xml)?;
+        let pdf = generate_pdf_from_document(&result)?;
+
+        Ok(FacturXDocument {
+            pdf: update_xmp_metadata(&pdf, facturx_xmp_metadata)?,
+            xml: Bytes::from(xml),
+        })
+    }
 }
 
 #[async_trait]
@@ -0,0 +1,180 @@
+use crate::errors::{InvoicingError, InvoicingResult};
+use crate::model::{
+    Address, Coupon, Customer, Flags, Invoice, InvoiceLine, InvoiceMetadata, InvoiceSubLine,
+    Organization, PaymentStatus, TaxBreakdownItem, Transaction,
+};
+use crate::pdf::generate_pdf_from_document;
+use crate::typst_render::TypstInvoiceRenderer;
+use crate::xml_facturx::generate_facturx_xml;
+use chrono::NaiveDate;
+use common_domain::country::CountryCode;
+use rust_decimal::Decimal;
+use rusty_money::{Money, iso};
+use std::collections::{BTreeMap, HashMap};
+
+/// Maximum size of a tenant template, in bytes.
+pub const MAX_TEMPLATE_SIZE: usize = 256 * 1024;
+
+/// Checks a tenant template by rendering a sample invoice with it, the same way issued invoices
+/// are rendered (Factur-X PDF). Returns the Typst errors when it does not compile.
+///
+/// Templates get the same `sys.inputs` as the built-in `main.typ`, and can
+/// `#import "invoice.typ": invoice` to only restyle parts of the default layout.
+pub fn validate_invoice_template(template: &str) -> InvoicingResult<()> {
+    if template.trim().is_empty() {
+        return Err(InvoicingError::TemplateError(
+            "Template is empty".to_string(),
+        ));
+    }
+    if template.len() > MAX_TEMPLATE_SIZE {
+        return Err(InvoicingError::TemplateError(format!(
+            "Template exceeds {} KiB",
+            MAX_TEMPLATE_SIZE / 1024
+        )));
+    }
+
+    let renderer = TypstInvoiceRenderer::new()?;
+    let invoice = sample_invoice(template);
+    let xml = generate_facturx_xml(&invoice)?;
+    let document = renderer.render_facturx_invoice(&invoice, &xml)?;
+
+    generate_pdf_from_document(&document)
+        .map(|_| ())
+        .map_err(|e| InvoicingError::TemplateError(e.to_string()))
+}
+
+/// Invoice filling every input a template can use: sub-lines, coupons, payments,
+/// bank details and all the optional sections.
+fn sample_invoice(template: &str) -> Invoice {
+    let eur = iso::find("EUR").unwrap_or(iso::EUR);
+    let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap_or_default();
+    let rate = Decimal::new(20, 0);
+
+    let address = |line1: &str, city: &str| Address {
+        line1: Some(line1.to_string()),
+        line2: None,
+        city: Some(city.to_string()),
+        country: CountryCode::parse_as_opt("FR"),
+        state: None,
+        zip_code: Some("75001".to_string()),
+    };
+
+    Invoice {
+        lang: "en-US".to_string(),
+        organization: Organization {
+            logo_src: None,
+            name: "Acme SAS".to_string(),
+            legal_number: Some("123 456 789".to_string()),
+            address: address("1 rue de la Paix", "Paris"),
+            email: Some("billing@acme.com".to_string()),
+            tax_id: Some("FR12123456789".to_string()),
+            footer_info: Some("Thank you for your business".to_string()),
+            footer_legal: Some("SAS au capital de 10 000 €".to_string()),
+            accounting_currency: *eur,
+            exchange_rate: None,
+        },
+        customer: Customer {
+            name: "Globex SARL".to_string(),
+            legal_number: Some("987 654 321".to_string()),
+            address: address("2 avenue des Champs", "Lyon"),
+            email: Some("accounts@globex.com".to_string()),
+            tax_id: Some("FR98987654321".to_string()),
+        },
+        metadata: InvoiceMetadata {
+            number: "INV-000001".to_string(),
+            issue_date: date(3, 1),
+            payment_term: 30,
+            subtotal: Money::from_major(100, eur),
+            tax_amount: Money::from_major(18, eur),
+            total_amount: Money::from_major(108, eur),
+            discount: Money::from_major(10, eur),
+            currency: eur,
+            due_date: date(3, 31),
+            memo: Some("Sample invoice".to_string()),
+            payment_url: Some("https://example.com/pay".to_string()),
+            usage_annex_url: None,
+            flags: Flags {
+                show_payment_status: Some(true),
+                show_payment_info: Some(true),
+                show_terms: Some(true),
+                show_tax_info: Some(true),
+                show_legal_info: Some(true),
+                show_footer_custom_info: Some(true),
+                whitelabel: Some(false),
+            },
+            purchase_order: Some("PO-42".to_string()),
+            custom_fields: BTreeMap::from([("cost_center".to_string(), "CC-42".to_string())]),
+            parent_invoice_number: None,
+            parent_invoice_date: None,
+        },
+        lines: vec![
+            InvoiceLine {
+                name: "Pro plan".to_string(),
+                description: Some("Monthly subscription".to_string()),
+                subtotal: Money::from_major(60, eur),
+                quantity: Some(Decimal::ONE),
+                unit_price: Some(Money::from_major(60, eur)),
+                tax_rate: rate,
+                start_date: date(2, 1),
+                end_date: date(2, 28),
+                is_prorated: false,
+                sub_lines: vec![],
+                group_label: Some("Pro".to_string()),
+                metadata: BTreeMap::from([("project".to_string(), "Apollo".to_string())]),
+            },
+            InvoiceLine {
+                name: "API calls".to_string(),
+                description: None,
+                subtotal: Money::from_major(40, eur),
+                quantity: Some(Decimal::new(3000, 0)),
+                unit_price: None,
+                tax_rate: rate,
+                start_date: date(2, 1),
+                end_date: date(2, 28),
+                is_prorated: true,
+                sub_lines: vec![
+                    InvoiceSubLine {
+                        name: "First 1000".to_string(),
+                        total: Money::from_major(10, eur),
+                        quantity: Decimal::new(1000, 0),
+                        unit_price: Money::from_minor(1, eur),
+                    },
+                    InvoiceSubLine {
+                        name: "Above 1000".to_string(),
+                        total: Money::from_major(30, eur),
+                        quantity: Decimal::new(2000, 0),
+                        unit_price: Money::from_decimal(Decimal::new(15, 3), eur),
+                    },
+                ],
+                group_label: Some("Pro".to_string()),
+                metadata: BTreeMap::new(),
+            },
+        ],
+        coupons: vec![Coupon {
+            name: "WELCOME10".to_string(),
+            total: Money::from_major(10, eur),
+        }],
+        payment_status: Some(PaymentStatus::PartiallyPaid),
+        transactions: vec![Transaction {
+            method: "Card •••• 4242".to_string(),
+            date: date(3, 2),
+            amount: Money::from_major(50, eur),
+        }],
+        bank_details: Some(HashMap::from([
+            (
+                "IBAN".to_string(),
+                "FR76 3000 6000 0112 3456 7890 189".to_string(),
+            ),
+            ("BIC".to_string(), "AGRIFRPP".to_string()),
+        ])),
+        tax_breakdown: vec![TaxBreakdownItem {
+            name: "VAT 20%".to_string(),
+            rate,
+            taxable_amount: Money::from_major(90, eur),
+            amount: Money::from_major(18, eur),
+            exemption_type: None,
+        }],
+        translation_overrides: HashMap::new(),
+        template: Some(template.to_string()),
+    }
+}
@@ -1,22 +1,24 @@
 use crate::errors::{InvoicingError, InvoicingResult};
+use crate::locale::{apply_overrides, format_date, resolve_locale};
 use crate::model::{
     Address, Coupon, Customer, Invoice, InvoiceLine, InvoiceSubLine, Organization, PaymentStatus,
     TaxBreakdownItem, Transaction,
 };
-use chrono::prelude::*;
 use derive_typst_intoval::{IntoDict, IntoValue};
 use fluent_static::MessageBundle;
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
 use rusty_money::{FormattableCurrency, iso};
+use std::collections::BTreeMap;
 use typst::foundations::{Bytes, Dict, IntoValue};
 use typst::text::Font;
-use typst_as_lib::TypstEngine;
+use typst_as_lib::{TypstAsLibError, TypstEngine, TypstTemplateMainFile};
 use typst_layout::PagedDocument;
 
 static INVOICE_CORE: &str = include_str!("../templates/invoice.typ");
 static TEMPLATE_CORE: &str = include_str!("../templates/template.typ");
 static MAIN_TEMPLATE: &str = include_str!("../templates/main.typ");
+static CUSTOM_TEMPLATE: &str = "custom.typ";
 
 static INTER_VARIABLE_FONT: &[u8] = include_bytes!("../assets/fonts/Inter-Variable.ttf");
 static NOTO_SANS_HEBREW: &[u8] = include_bytes!("../assets/fonts/NotoSansHebrew-Regular.ttf");
@@ -39,6 +41,11 @@ mod l10n {
         resources = [
             ("l10n/en-US/invoice.ftl", "en-US"),
             ("l10n/fr-FR/invoice.ftl", "fr-FR"),
+            ("l10n/de-DE/invoice.ftl", "de-DE"),
+            ("l10n/es-ES/invoice.ftl", "es-ES"),
+            ("l10n/it-IT/invoice.ftl", "it-IT"),
+            ("l10n/nl-NL/invoice.ftl", "nl-NL"),
+            ("l10n/pt-PT/invoice.ftl", "pt-PT"),
         ],
         default_language = "en-US")]
     pub struct InvoiceL10n;
@@ -157,6 +164,7 @@ pub struct TypstInvoiceLine {
     pub is_prorated: bool,
     pub sub_lines: Vec<TypstInvoiceSubLine>,
     pub group_label: Option<String>,
+    pub metadata: Vec<TypstCustomField>,
 }
 
 impl From<&InvoiceLine> for TypstInvoiceLine {
@@ -175,6 +183,7 @@ impl From<&InvoiceLine> for TypstInvoiceLine {
             end_date,
             is_prorated: line.is_prorated,
             group_label: line.group_label.clone(),
+            metadata: TypstCustomField::from_map(&line.metadata),
             sub_lines: {
                 let mut sub_lines = Vec::with_capacity(line.sub_lines.len());
                 for sub_line in &line.sub_lines {
@@ -186,6 +195,24 @@ impl From<&InvoiceLine> for TypstInvoiceLine {
     }
 }
 
+#[derive(Debug, Clone, IntoValue, IntoDict)]
+pub struct TypstCustomField {
+    pub name: String,
+    pub value: String,
+}
+
+impl TypstCustomField {
+    fn from_map(fields: &BTreeMap<String, String>) -> Vec<Self> {
+        fields
+            .iter()
+            .map(|(name, value)| TypstCustomField {
+                name: name.clone(),
+                value: value.clone(),
+            })
+            .collect()
+    }
+}
+
 #[derive(Debug, Clone, IntoValue, IntoDict)]
 pub struct TypstInvoiceSubLine {
     pub name: String,
@@ -284,6 +311,7 @@ pub struct TypstInvoiceContent {
     pub lines: Vec<TypstInvoiceLine>,
     pub translations: Dict,
     pub pay_online_url: Option<String>,
+    pub usage_annex_url: Option<String>,
     pub payment_status: String,
     pub transactions: Vec<TypstTransaction>,
     pub payment_info: Option<Dict>,
@@ -298,16 +326,14 @@ pub struct TypstInvoiceContent {
     pub tax_breakdown: Vec<TypstTaxBreakdownItem>,
     pub discount: f64,
     pub purchase_order: Option<String>,
+    pub custom_fields: Vec<TypstCustomField>,
     pub parent_invoice_number: Option<String>,
     pub parent_invoice_date: Option<String>,
 }
 
 impl From<&Invoice> for TypstInvoiceContent {
     fn from(invoice: &Invoice) -> Self {
-        let lang = match invoice.lang.as_str() {
-            "fr" | "fr-FR" => "fr-FR",
-            _ => "en-US",
-        };
+        let lang = resolve_locale(&invoice.lang);
 
         let invoice_l10n = &l10n::InvoiceL10N::get(lang).unwrap_or(l10n::InvoiceL10N::default());
 
@@ -346,6 +372,7 @@ impl From<&Invoice> for TypstInvoiceContent {
             "tax_info_title" => invoice_l10n.tax_info_title().into_value(),
             "tax_reverse_charge" => invoice_l10n.tax_reverse_charge().into_value(),
             "pay_online" => invoice_l10n.pay_online().into_value(),
+            "usage_annex" => invoice_l10n.usage_annex().into_value(),
             "seller_vat_id" => invoice_l10n.seller_vat_id().into_value(),
             "customer_vat_id" => invoice_l10n.customer_vat_id().into_value(),
             "vat_standard" => invoice_l10n.vat_standard().into_value(),
@@ -364,6 +391,11 @@ impl From<&Invoice> for TypstInvoiceContent {
             "no_tax_applied" => invoice_l10n.no_tax_applied().into_value()
         };
 
+        let mut override_args = vec![
+            ("days", invoice.metadata.payment_term.to_string()),
+            ("currency", invoice.metadata.currency.name.to_string()),
+        ];
+
         if let (Some(parent_number), Some(parent_date)) = (
             &invoice.metadata.parent_invoice_number,
             &invoice.metadata.parent_invoice_date,
@@ -376,6 +408,8 @@ impl From<&Invoice> for TypstInvoiceContent {
                     .cancels_and_replaces(parent_number, &formatted_parent_date)
                     .into_value(),
             );
+            override_args.push(("invoice_number", parent_number.clone()));
+            override_args.push(("invoice_date", formatted_parent_date));
         }
 
         if let Some(exchange_rate) = invoice.organization.exchange_rate {
@@ -400,8 +434,17 @@ impl From<&Invoice> for TypstInvoiceContent {
                     .exchange_rate_info(&date, &equality, &amount_converted)
                     .into_value(),
             );
+            override_args.push(("date", date));
+            override_args.push(("equality", equality));
+            override_args.push(("amount_converted", amount_converted));
         }
 
+        apply_overrides(
+            &mut translations,
+            &invoice.translation_overrides,
+            &override_args,
+        );
+
         let currency_symbol = invoice.metadata.currency.symbol();
 
         let formatted_issue_date = format_date(lang, &invoice.metadata.issue_date)
@@ -476,6 +519,7 @@ impl From<&Invoice> for TypstInvoiceContent {
             lines,
             translations,
             pay_online_url: invoice.metadata.payment_url.clone(),
+            usage_annex_url: invoice.metadata.usage_annex_url.clone(),
             payment_status,
             transactions,
             coupons,
@@ -495,6 +539,7 @@ impl From<&Invoice> for TypstInvoiceContent {
             tax_breakdown,
             discount,
             purchase_order: invoice.metadata.purchase_order.clone(),
+            custom_fields: TypstCustomField::from_map(&invoice.metadata.custom_fields),
             parent_invoice_number: invoice.metadata.parent_invoice_number.clone(),
             parent_invoice_date: invoice.metadata.parent_invoice_date.map(|d| {
                 format_date(lang, &d).unwrap_or_else(|_| d.format("%Y-%m-%d").to_string())
@@ -503,14 +548,6 @@ impl From<&Invoice> for TypstInvoiceContent {
     }
 }
 
-#[inline]
-fn format_date(lang: &str, date: &NaiveDate) -> Result<String, InvoicingError> {
-    match lang {
-        "fr-FR" => Ok(date.format_localized("%e %B %Y", Locale::fr_FR).to_string()),
-        _ => Ok(date.format("%B %e, %Y").to_string()),
-    }
-}
-
 #[inline]
 fn format_currency_dec(amount: Decimal, currency: &iso::Currency) -> String {
     rusty_money::Money::from_decimal(amount, currency).to_string()
@@ -536,6 +573,7 @@ pub(crate) fn load_fonts() -> Result<Vec<Font>, InvoicingError> {
 
 pub struct TypstInvoiceRenderer {
     engine: TypstEngine,
+    fonts: Vec<Font>,
 }
 
 impl TypstInvoiceRenderer {
@@ -550,25 +588,82 @@ impl TypstInvoiceRenderer {
             ])
             .with_static_file_resolver([("wordmark.svg", WORDMARK_LOGO)])
             .with_static_file_resolver([("logo.png", LOGO)])
-            .fonts(fonts)
+            .fonts(fonts.clone())
             .build();
 
-        Ok(TypstInvoiceRenderer { engine })
+        Ok(TypstInvoiceRenderer { engine, fonts })
+    }
+
+    /// Engine compiling a tenant template as its main file. The built-in templates stay
+    /// importable, and nothing else can be read from the filesystem.
+    fn custom_engine(&self, template: &str) -> TypstEngine<TypstTemplateMainFile> {
+        TypstEngine::builder()
+            .main_file((CUSTOM_TEMPLATE, template))
+            .with_static_source_file_resolver([
+                ("invoice.typ", INVOICE_CORE),
+                ("template.typ", TEMPLATE_CORE),
+            ])
+            .with_static_file_resolver([("wordmark.svg", WORDMARK_LOGO)])
+            .with_static_file_resolver([("logo.png", LOGO)])
+            .fonts(self.fonts.clone())
+            .build()
     }
 
     pub fn render_invoice(&self, invoice: &Invoice) -> InvoicingResult<PagedDocument> {
-        let invoice_content = TypstInvoiceContent::from(invoice);
-
-        let result = self
-            .engine
-            .compile_with_input("main.typ", invoice_content.into_dict())
-            .output
-            .map_err(|e| {
-                InvoicingError::InvoiceGenerationError(format!(
-                    "Failed to compile Typst document: {e:?}"
-                ))
-            })?;
+        self.render(invoice, None)
+    }
+
+    /// Renders the invoice with its Factur-X XML attached, for PDF export.
+    pub fn render_facturx_invoice(
+        &self,
+        invoice: &Invoice,
+        facturx_xml: &str,
+    ) -> InvoicingResult<PagedDocument> {
+        self.render(invoice, Some(facturx_xml))
+    }
+
+    fn render(
+        &self,
+        invoice: &Invoice,
+        facturx_xml: Option<&str>,
+    ) -> InvoicingResult<PagedDocument> {
+        let mut inputs = TypstInvoiceContent::from(invoice).into_dict();
+        if let Some(xml) = facturx_xml {
+            inputs.insert(
+                "facturx_xml".into(),
+                Bytes::new(xml.as_bytes().to_vec()).into_value(),
+            );
+        }
+
+        let result = match invoice.template.as_deref() {
+            Some(template) => self
+                .custom_engine(template)
+                .compile_with_input(inputs)
+                .output
+                .map_err(|e| InvoicingError::TemplateError(describe_compile_error(e)))?,
+            None => self
+                .engine
+                .compile_with_input("main.typ", inputs)
+                .output
+                .map_err(|e| {
+                    InvoicingError::InvoiceGenerationError(format!(
+                        "Failed to compile Typst document: {e:?}"
+                    ))
+                })?,
+        };
 
         Ok(result)
     }
 }
+
+/// Compilation errors as shown to the tenant editing their template.
+fn describe_compile_error(error: TypstAsLibError) -> String {
+    match error {
+        TypstAsLibError::TypstSource(diagnostics) => diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.message.to_string())
+            .collect::<Vec<_>>()
+            .join("; "),
+        other => other.to_string(),
+    }
+}
@@ -1,13 +1,347 @@
-use crate::errors::InvoicingResult;
+//! Factur-X / ZUGFeRD e-invoices.
+//!
+//! The invoice is described as a UN/CEFACT Cross Industry Invoice (CII) following the EN 16931
+//! profile. The XML is embedded in the PDF/A-3 rendering of the invoice, which makes a hybrid
+//! document readable by both humans and accounting systems. The XMP metadata of the PDF
+//! identifies the embedded file and its profile, through the `fx` extension schema.
+
+use crate::e_invoice::{
+    Document, Line, PAYMENT_MEANS_SEPA_CREDIT_TRANSFER, Party, UNIT_CODE_ONE, XmlWriter,
+    format_percent,
+};
+use crate::errors::{InvoicingError, InvoicingResult};
 use crate::model::Invoice;
+use bytes::Bytes;
+use chrono::NaiveDate;
+
+/// A Factur-X invoice: the PDF/A-3 rendering with the XML attached, and the XML on its own.
+pub struct FacturXDocument {
+    pub pdf: Bytes,
+    pub xml: Bytes,
+}
+
+const EN16931_GUIDELINE: &str = "urn:cen.eu:en16931:2017";
+
+const ROOT: &str = "rsm:CrossIndustryInvoice";
+
+/// Name of the embedded XML, mandated by the specification.
+pub(crate) const FACTURX_FILE_NAME: &str = "factur-x.xml";
+
+const FACTURX_XMP_NAMESPACE: &str = "urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#";
+
+/// Description of the `fx` schema, required by PDF/A for any schema outside of the XMP
+/// specification.
+const FACTURX_XMP_EXTENSION_SCHEMA: &str = concat!(
+    r#"<rdf:li rdf:parseType="Resource" xmlns:pdfaSchema="http://www.aiim.org/pdfa/ns/schema#" xmlns:pdfaProperty="http://www.aiim.org/pdfa/ns/property#">"#,
+    "<pdfaSchema:schema>Factur-X PDFA Extension Schema</pdfaSchema:schema>",
+    "<pdfaSchema:namespaceURI>urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#</pdfaSchema:namespaceURI>",
+    "<pdfaSchema:prefix>fx</pdfaSchema:prefix>",
+    "<pdfaSchema:property><rdf:Seq>",
+    r#"<rdf:li rdf:parseType="Resource"><pdfaProperty:name>DocumentFileName</pdfaProperty:name><pdfaProperty:valueType>Text</pdfaProperty:valueType><pdfaProperty:category>external</pdfaProperty:category><pdfaProperty:description>The name of the embedded XML document</pdfaProperty:description></rdf:li>"#,
+    r#"<rdf:li rdf:parseType="Resource"><pdfaProperty:name>DocumentType</pdfaProperty:name><pdfaProperty:valueType>Text</pdfaProperty:valueType><pdfaProperty:category>external</pdfaProperty:category><pdfaProperty:description>The type of the hybrid document in capital letters, e.g. INVOICE or ORDER</pdfaProperty:description></rdf:li>"#,
+    r#"<rdf:li rdf:parseType="Resource"><pdfaProperty:name>Version</pdfaProperty:name><pdfaProperty:valueType>Text</pdfaProperty:valueType><pdfaProperty:category>external</pdfaProperty:category><pdfaProperty:description>The actual version of the standard applying to the embedded XML document</pdfaProperty:description></rdf:li>"#,
+    r#"<rdf:li rdf:parseType="Resource"><pdfaProperty:name>ConformanceLevel</pdfaProperty:name><pdfaProperty:valueType>Text</pdfaProperty:valueType><pdfaProperty:category>external</pdfaProperty:category><pdfaProperty:description>The conformance level of the embedded XML document</pdfaProperty:description></rdf:li>"#,
+    "</rdf:Seq></pdfaSchema:property>",
+    "</rdf:li>",
+);
+
+const XMP_EXTENSION_SCHEMAS_END: &str = "</rdf:Bag></pdfaExtension:schemas>";
+
+/// Adds the Factur-X properties to the XMP metadata of the PDF/A rendering, declaring the `fx`
+/// schema next to the extension schemas already described.
+pub(crate) fn facturx_xmp_metadata(xmp: &str) -> InvoicingResult<String> {
+    let rdf_end = xmp.rfind("</rdf:RDF>").ok_or_else(|| {
+        InvoicingError::PdfGenerationError("Missing RDF in the XMP metadata".to_string())
+    })?;
+
+    let mut properties = String::new();
+    if !xmp.contains(XMP_EXTENSION_SCHEMAS_END) {
+        properties.push_str(concat!(
+            r#"<rdf:Description rdf:about="" xmlns:pdfaExtension="http://www.aiim.org/pdfa/ns/extension/">"#,
+            "<pdfaExtension:schemas><rdf:Bag>",
+            "</rdf:Bag></pdfaExtension:schemas>",
+            "</rdf:Description>",
+        ));
+    }
+    properties.push_str(&format!(
+        concat!(
+            r#"<rdf:Description rdf:about="" xmlns:fx="{namespace}">"#,
+            "<fx:DocumentType>INVOICE</fx:DocumentType>",
+            "<fx:DocumentFileName>{file_name}</fx:DocumentFileName>",
+            "<fx:Version>1.0</fx:Version>",
+            "<fx:ConformanceLevel>EN 16931</fx:ConformanceLevel>",
+            "</rdf:Description>",
+        ),
+        namespace = FACTURX_XMP_NAMESPACE,
+        file_name = FACTURX_FILE_NAME,
+    ));
+
+    let mut xmp = format!("{}{}{}", &xmp[..rdf_end], properties, &xmp[rdf_end..]);
+    if let Some(schemas_end) = xmp.find(XMP_EXTENSION_SCHEMAS_END) {
+        xmp.insert_str(schemas_end, FACTURX_XMP_EXTENSION_SCHEMA);
+    }
+
+    Ok(xmp)
+}
+
+/// Generates the Factur-X XML (CII, EN 16931 profile) of the invoice.
+pub fn generate_facturx_xml(invoice: &Invoice) -> InvoicingResult<String> {
+    let doc = Document::from_invoice(invoice)?;
+    let amount = |value| doc.amount(value);
+    let totals = &doc.totals;
+
+    let mut w = XmlWriter::new(
+        ROOT,
+        &[
+            (
+                "rsm",
+                "urn:un:unece:uncefact:data:standard:CrossIndustryInvoice:100",
+            ),
+            (
+                "qdt",
+                "urn:un:unece:uncefact:data:standard:QualifiedDataType:100",
+            ),
+            (
+                "ram",
+                "urn:un:unece:uncefact:data:standard:ReusableAggregateBusinessInformationEntity:100",
+            ),
+            (
+                "udt",
+                "urn:un:unece:uncefact:data:standard:UnqualifiedDataType:100",
+            ),
+        ],
+    );
+
+    w.open("rsm:ExchangedDocumentContext");
+    w.open("ram:GuidelineSpecifiedDocumentContextParameter");
+    w.text("ram:ID", EN16931_GUIDELINE);
+    w.close("ram:GuidelineSpecifiedDocumentContextParameter");
+    w.close("rsm:ExchangedDocumentContext");
+
+    w.open("rsm:ExchangedDocument");
+    w.text("ram:ID", doc.number);
+    w.text("ram:TypeCode", doc.kind.type_code());
+    date(&mut w, "ram:IssueDateTime", "udt", doc.issue_date);
+    if let Some(note) = doc.note {
+        w.open("ram:IncludedNote");
+        w.text("ram:Content", note);
+        w.close("ram:IncludedNote");
+    }
+    w.close("rsm:ExchangedDocument");
+
+    w.open("rsm:SupplyChainTradeTransaction");
+
+    for (index, line) in doc.lines.iter().enumerate() {
+        write_line(&mut w, index + 1, line, &amount);
+    }
+
+    w.open("ram:ApplicableHeaderTradeAgreement");
+    w.open("ram:SellerTradeParty");
+    write_party(&mut w, &doc.seller);
+    w.close("ram:SellerTradeParty");
+    w.open("ram:BuyerTradeParty");
+    write_party(&mut w, &doc.buyer);
+    w.close("ram:BuyerTradeParty");
+    if let Some(purchase_order) = doc.purchase_order {
+        w.open("ram:BuyerOrderReferencedDocument");
+        w.text("ram:IssuerAssignedID", purchase_order);
+        w.close("ram:BuyerOrderReferencedDocument");
+    }
+    w.close("ram:ApplicableHeaderTradeAgreement");
+
+    w.empty("ram:ApplicableHeaderTradeDelivery");
+
+    w.open("ram:ApplicableHeaderTradeSettlement");
+    w.opt_text("ram:PaymentReference", doc.payment_reference);
+    w.text("ram:InvoiceCurrencyCode", doc.currency_code);
+    if let Some(bank_transfer) = &doc.bank_transfer {
+        w.open("ram:SpecifiedTradeSettlementPaymentMeans");
+        w.text("ram:TypeCode", PAYMENT_MEANS_SEPA_CREDIT_TRANSFER);
+        w.open("ram:PayeePartyCreditorFinancialAccount");
+        w.text("ram:IBANID", &bank_transfer.iban);
+        w.close("ram:PayeePartyCreditorFinancialAccount");
+        if let Some(bic) = bank_transfer.bic {
+            w.open("ram:PayeeSpecifiedCreditorFinancialInstitution");
+            w.text("ram:BICID", bic);
+            w.close("ram:PayeeSpecifiedCreditorFinancialInstitution");
+        }
+        w.close("ram:SpecifiedTradeSettlementPaymentMeans");
+    }
+    for group in &doc.vat_groups {
+        w.open("ram:ApplicableTradeTax");
+        w.text("ram:CalculatedAmount", &amount(group.tax));
+        w.text("ram:TypeCode", "VAT");
+        if let Some((_, reason)) = group.category.exemption() {
+            w.text("ram:ExemptionReason", reason);
+        }
+        w.text("ram:BasisAmount", &amount(group.basis));
+        w.text("ram:CategoryCode", group.category.code());
+        if let Some((Some(code), _)) = group.category.exemption() {
+            w.text("ram:ExemptionReasonCode", code);
+        }
+        if group.category.has_rate() {
+            w.text("ram:RateApplicablePercent", &format_percent(group.rate));
+        }
+        w.close("ram:ApplicableTradeTax");
+    }
+    for group in &doc.vat_groups {
+        let adjustment = group.adjustment();
+        if adjustment.is_zero() {
+            continue;
+        }
+        w.open("ram:SpecifiedTradeAllowanceCharge");
+        w.open("ram:ChargeIndicator");
+        w.text(
+            "udt:Indicator",
+            if adjustment.is_sign_negative() {
+                "true"
+            } else {
+                "false"
+            },
+        );
+        w.close("ram:ChargeIndicator");
+        w.text("ram:ActualAmount", &amount(adjustment.abs()));
+        w.text(
+            "ram:Reason",
+            if adjustment.is_sign_negative() {
+                "Adjustment"
+            } else {
+                "Discount"
+            },
+        );
+        w.open("ram:CategoryTradeTax");
+        w.text("ram:TypeCode", "VAT");
+        w.text("ram:CategoryCode", group.category.code());
+        if group.category.has_rate() {
+            w.text("ram:RateApplicablePercent", &format_percent(group.rate));
+        }
+        w.close("ram:CategoryTradeTax");
+        w.close("ram:SpecifiedTradeAllowanceCharge");
+    }
+    if let (Some(payment_term), Some(due_date)) = (doc.payment_term, doc.due_date) {
+        w.open("ram:SpecifiedTradePaymentTerms");
+        w.text("ram:Description", &format!("Net {payment_term} days"));
+        date(&mut w, "ram:DueDateDateTime", "udt", due_date);
+        w.close("ram:SpecifiedTradePaymentTerms");
+    }
+
+    w.open("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+    w.text("ram:LineTotalAmount", &amount(totals.line_total));
+    w.text("ram:ChargeTotalAmount", &amount(totals.charge_total));
+    w.text("ram:AllowanceTotalAmount", &amount(totals.allowance_total));
+    w.text("ram:TaxBasisTotalAmount", &amount(totals.tax_basis_total));
+    w.text_with_attribute(
+        "ram:TaxTotalAmount",
+        ("currencyID", doc.currency_code),
+        &amount(totals.tax_total),
+    );
+    w.text("ram:GrandTotalAmount", &amount(totals.grand_total));
+    w.text("ram:TotalPrepaidAmount", &amount(totals.prepaid));
+    w.text("ram:DuePayableAmount", &amount(totals.due));
+    w.close("ram:SpecifiedTradeSettlementHeaderMonetarySummation");
+
+    if let Some((parent_number, parent_date)) = doc.preceding_invoice {
+        w.open("ram:InvoiceReferencedDocument");
+        w.text("ram:IssuerAssignedID", parent_number);
+        if let Some(parent_date) = parent_date {
+            date(&mut w, "ram:FormattedIssueDateTime", "qdt", parent_date);
+        }
+        w.close("ram:InvoiceReferencedDocument");
+    }
+    w.close("ram:ApplicableHeaderTradeSettlement");
+
+    w.close("rsm:SupplyChainTradeTransaction");
+
+    Ok(w.finish(ROOT))
+}
+
+fn write_line(
+    w: &mut XmlWriter,
+    line_id: usize,
+    line: &Line,
+    amount: &impl Fn(rust_decimal::Decimal) -> String,
+) {
+    w.open("ram:IncludedSupplyChainTradeLineItem");
+
+    w.open("ram:AssociatedDocumentLineDocument");
+    w.text("ram:LineID", &line_id.to_string());
+    w.close("ram:AssociatedDocumentLineDocument");
+
+    w.open("ram:SpecifiedTradeProduct");
+    w.text("ram:Name", line.name);
+    w.opt_text("ram:Description", line.description);
+    w.close("ram:SpecifiedTradeProduct");
+
+    w.open("ram:SpecifiedLineTradeAgreement");
+    w.open("ram:NetPriceProductTradePrice");
+    w.text("ram:ChargeAmount", &line.price.to_string());
+    w.close("ram:NetPriceProductTradePrice");
+    w.close("ram:SpecifiedLineTradeAgreement");
+
+    w.open("ram:SpecifiedLineTradeDelivery");
+    w.text_with_attribute(
+        "ram:BilledQuantity",
+        ("unitCode", UNIT_CODE_ONE),
+        &line.quantity.to_string(),
+    );
+    w.close("ram:SpecifiedLineTradeDelivery");
+
+    w.open("ram:SpecifiedLineTradeSettlement");
+    w.open("ram:ApplicableTradeTax");
+    w.text("ram:TypeCode", "VAT");
+    w.text("ram:CategoryCode", line.category.code());
+    if line.category.has_rate() {
+        w.text("ram:RateApplicablePercent", &format_percent(line.rate));
+    }
+    w.close("ram:ApplicableTradeTax");
+    w.open("ram:BillingSpecifiedPeriod");
+    date(w, "ram:StartDateTime", "udt", line.start_date);
+    date(w, "ram:EndDateTime", "udt", line.end_date);
+    w.close("ram:BillingSpecifiedPeriod");
+    w.open("ram:SpecifiedTradeSettlementLineMonetarySummation");
+    w.text("ram:LineTotalAmount", &amount(line.amount));
+    w.close("ram:SpecifiedTradeSettlementLineMonetarySummation");
+    w.close("ram:SpecifiedLineTradeSettlement");
+
+    w.close("ram:IncludedSupplyChainTradeLineItem");
+}
+
+fn write_party(w: &mut XmlWriter, party: &Party) {
+    let address = &party.address;
 
-// Function to attach Factur-X XML to a PDF
-#[allow(unused)]
-pub fn generate_facturx_pdf(_pdf_data: &[u8], _invoice: &Invoice) -> InvoicingResult<Vec<u8>> {
-    todo!()
+    w.text("ram:Name", party.name);
+    if let Some(legal_number) = party.legal_number {
+        w.open("ram:SpecifiedLegalOrganization");
+        w.text("ram:ID", legal_number);
+        w.close("ram:SpecifiedLegalOrganization");
+    }
+    w.open("ram:PostalTradeAddress");
+    w.opt_text("ram:PostcodeCode", address.zip_code);
+    w.opt_text("ram:LineOne", address.line1);
+    w.opt_text("ram:LineTwo", address.line2);
+    w.opt_text("ram:CityName", address.city);
+    w.opt_text("ram:CountryID", address.country);
+    w.opt_text("ram:CountrySubDivisionName", address.state);
+    w.close("ram:PostalTradeAddress");
+    if let Some(email) = party.email {
+        w.open("ram:URIUniversalCommunication");
+        w.text_with_attribute("ram:URIID", ("schemeID", "EM"), email);
+        w.close("ram:URIUniversalCommunication");
+    }
+    if let Some(tax_id) = party.tax_id {
+        w.open("ram:SpecifiedTaxRegistration");
+        w.text_with_attribute("ram:ID", ("schemeID", "VA"), tax_id);
+        w.close("ram:SpecifiedTaxRegistration");
+    }
 }
 
-#[allow(unused)]
-fn generate_facturx_xml(_invoice: &Invoice) -> InvoicingResult<String> {
-    todo!()
+/// Date in the CCYYMMDD format (102), with the data type namespace the element requires.
+fn date(w: &mut XmlWriter, tag: &str, namespace: &str, date: NaiveDate) {
+    w.open(tag);
+    w.text_with_attribute(
+        &format!("{namespace}:DateTimeString"),
+        ("format", "102"),
+        &date.format("%Y%m%d").to_string(),
+    );
+    w.close(tag);
 }
@@ -0,0 +1,348 @@
+//! Peppol BIS Billing 3.0 e-invoices.
+//!
+//! Invoices and credit notes are described as UBL 2.1 documents following the Peppol BIS
+//! Billing 3.0 specification, the EN 16931 core invoice usage required on the Peppol network.
+
+use crate::credit_note_model::CreditNote;
+use crate::e_invoice::{
+    Document, DocumentKind, Line, PAYMENT_MEANS_SEPA_CREDIT_TRANSFER, Party, UNIT_CODE_ONE,
+    XmlWriter, format_percent,
+};
+use crate::errors::InvoicingResult;
+use crate::model::Invoice;
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+const CUSTOMIZATION_ID: &str =
+    "urn:cen.eu:en16931:2017#compliant#urn:fdc:peppol.eu:2017:poacc:billing:3.0";
+const PROFILE_ID: &str = "urn:fdc:peppol.eu:2017:poacc:billing:01:1.0";
+
+const NS_CAC: &str = "urn:oasis:names:specification:ubl:schema:xsd:CommonAggregateComponents-2";
+const NS_CBC: &str = "urn:oasis:names:specification:ubl:schema:xsd:CommonBasicComponents-2";
+
+/// Electronic Address Scheme (EAS) of the national identifiers registered on Peppol, which are
+/// preferred to the VAT number where commonly used.
+const LEGAL_NUMBER_SCHEMES: &[(&str, &str)] = &[
+    ("DK", "0184"),
+    ("FI", "0037"),
+    ("NO", "0192"),
+    ("SE", "0007"),
+];
+
+/// Electronic Address Scheme (EAS) of the VAT numbers, per country.
+const VAT_SCHEMES: &[(&str, &str)] = &[
+    ("AT", "9914"),
+    ("BE", "9925"),
+    ("BG", "9926"),
+    ("CY", "9928"),
+    ("CZ", "9929"),
+    ("DE", "9930"),
+    ("EE", "9931"),
+    ("ES", "9920"),
+    ("FR", "9957"),
+    ("GB", "9932"),
+    ("GR", "9933"),
+    ("HR", "9934"),
+    ("IE", "9935"),
+    ("LT", "9937"),
+    ("LU", "9938"),
+    ("LV", "9939"),
+    ("MT", "9943"),
+    ("NL", "9944"),
+    ("PL", "9945"),
+    ("PT", "9946"),
+    ("RO", "9947"),
+    ("SI", "9949"),
+    ("SK", "9950"),
+];
+
+/// Generates the Peppol BIS Billing 3.0 UBL invoice.
+pub fn generate_ubl_invoice(invoice: &Invoice) -> InvoicingResult<String> {
+    Ok(write_document(&Document::from_invoice(invoice)?))
+}
+
+/// Generates the Peppol BIS Billing 3.0 UBL credit note.
+pub fn generate_ubl_credit_note(credit_note: &CreditNote) -> InvoicingResult<String> {
+    Ok(write_document(&Document::from_credit_note(credit_note)?))
+}
+
+fn write_document(doc: &Document) -> String {
+    let is_credit_note = doc.kind == DocumentKind::CreditNote;
+    let (root, namespace) = if is_credit_note {
+        (
+            "CreditNote",
+            "urn:oasis:names:specification:ubl:schema:xsd:CreditNote-2",
+        )
+    } else {
+        (
+            "Invoice",
+            "urn:oasis:names:specification:ubl:schema:xsd:Invoice-2",
+        )
+    };
+    let amount = |value: Decimal| doc.amount(value);
+    let currency = ("currencyID", doc.currency_code);
+    let totals = &doc.totals;
+
+    let mut w = XmlWriter::new(root, &[("", namespace), ("cac", NS_CAC), ("cbc", NS_CBC)]);
+
+    w.text("cbc:CustomizationID", CUSTOMIZATION_ID);
+    w.text("cbc:ProfileID", PROFILE_ID);
+    w.text("cbc:ID", doc.number);
+    w.text("cbc:IssueDate", &iso_date(doc.issue_date));
+    if is_credit_note {
+        w.text("cbc:CreditNoteTypeCode", doc.kind.type_code());
+    } else {
+        if let Some(due_date) = doc.due_date {
+            w.text("cbc:DueDate", &iso_date(due_date));
+        }
+        w.text("cbc:InvoiceTypeCode", doc.kind.type_code());
+    }
+    w.opt_text("cbc:Note", doc.note);
+    w.text("cbc:DocumentCurrencyCode", doc.currency_code);
+    // Either a buyer reference or an order reference is required (PEPPOL-EN16931-R003)
+    w.text(
+        "cbc:BuyerReference",
+        doc.purchase_order.unwrap_or(doc.buyer.name),
+    );
+    if let Some(purchase_order) = doc.purchase_order {
+        w.open("cac:OrderReference");
+        w.text("cbc:ID", purchase_order);
+        w.close("cac:OrderReference");
+    }
+    if let Some((number, issue_date)) = doc.preceding_invoice {
+        w.open("cac:BillingReference");
+        w.open("cac:InvoiceDocumentReference");
+        w.text("cbc:ID", number);
+        if let Some(issue_date) = issue_date {
+            w.text("cbc:IssueDate", &iso_date(issue_date));
+        }
+        w.close("cac:InvoiceDocumentReference");
+        w.close("cac:BillingReference");
+    }
+
+    w.open("cac:AccountingSupplierParty");
+    write_party(&mut w, &doc.seller);
+    w.close("cac:AccountingSupplierParty");
+    w.open("cac:AccountingCustomerParty");
+    write_party(&mut w, &doc.buyer);
+    w.close("cac:AccountingCustomerParty");
+
+    if let Some(bank_transfer) = &doc.bank_transfer {
+        w.open("cac:PaymentMeans");
+        w.text("cbc:PaymentMeansCode", PAYMENT_MEANS_SEPA_CREDIT_TRANSFER);
+        w.opt_text("cbc:PaymentID", doc.payment_reference);
+        w.open("cac:PayeeFinancialAccount");
+        w.text("cbc:ID", &bank_transfer.iban);
+        if let Some(bic) = bank_transfer.bic {
+            w.open("cac:FinancialInstitutionBranch");
+            w.text("cbc:ID", bic);
+            w.close("cac:FinancialInstitutionBranch");
+        }
+        w.close("cac:PayeeFinancialAccount");
+        w.close("cac:PaymentMeans");
+    }
+    if let Some(payment_term) = doc.payment_term {
+        w.open("cac:PaymentTerms");
+        w.text("cbc:Note", &format!("Net {payment_term} days"));
+        w.close("cac:PaymentTerms");
+    }
+
+    for group in &doc.vat_groups {
+        let adjustment = group.adjustment();
+        if adjustment.is_zero() {
+            continue;
+        }
+        let is_charge = adjustment.is_sign_negative();
+        w.open("cac:AllowanceCharge");
+        w.text(
+            "cbc:ChargeIndicator",
+            if is_charge { "true" } else { "false" },
+        );
+        w.text(
+            "cbc:AllowanceChargeReason",
+            if is_charge { "Adjustment" } else { "Discount" },
+        );
+        w.text_with_attribute("cbc:Amount", currency, &amount(adjustment.abs()));
+        w.open("cac:TaxCategory");
+        w.text("cbc:ID", group.category.code());
+        if group.category.has_rate() {
+            w.text("cbc:Percent", &format_percent(group.rate));
+        }
+        write_tax_scheme(&mut w);
+        w.close("cac:TaxCategory");
+        w.close("cac:AllowanceCharge");
+    }
+
+    w.open("cac:TaxTotal");
+    w.text_with_attribute("cbc:TaxAmount", currency, &amount(totals.tax_total));
+    for group in &doc.vat_groups {
+        w.open("cac:TaxSubtotal");
+        w.text_with_attribute("cbc:TaxableAmount", currency, &amount(group.basis));
+        w.text_with_attribute("cbc:TaxAmount", currency, &amount(group.tax));
+        w.open("cac:TaxCategory");
+        w.text("cbc:ID", group.category.code());
+        if group.category.has_rate() {
+            w.text("cbc:Percent", &format_percent(group.rate));
+        }
+        if let Some((code, reason)) = group.category.exemption() {
+            w.opt_text("cbc:TaxExemptionReasonCode", code);
+            w.text("cbc:TaxExemptionReason", reason);
+        }
+        write_tax_scheme(&mut w);
+        w.close("cac:TaxCategory");
+        w.close("cac:TaxSubtotal");
+    }
+    w.close("cac:TaxTotal");
+
+    w.open("cac:LegalMonetaryTotal");
+    w.text_with_attribute(
+        "cbc:LineExtensionAmount",
+        currency,
+        &amount(totals.line_total),
+    );
+    w.text_with_attribute(
+        "cbc:TaxExclusiveAmount",
+        currency,
+        &amount(totals.tax_basis_total),
+    );
+    w.text_with_attribute(
+        "cbc:TaxInclusiveAmount",
+        currency,
+        &amount(totals.grand_total),
+    );
+    w.text_with_attribute(
+        "cbc:AllowanceTotalAmount",
+        currency,
+        &amount(totals.allowance_total),
+    );
+    w.text_with_attribute(
+        "cbc:ChargeTotalAmount",
+        currency,
+        &amount(totals.charge_total),
+    );
+    w.text_with_attribute("cbc:PrepaidAmount", currency, &amount(totals.prepaid));
+    w.text_with_attribute("cbc:PayableAmount", currency, &amount(totals.due));
+    w.close("cac:LegalMonetaryTotal");
+
+    for (index, line) in doc.lines.iter().enumerate() {
+        write_line(&mut w, doc, index + 1, line, is_credit_note);
+    }
+
+    w.finish(root)
+}
+
+fn write_line(w: &mut XmlWriter, doc: &Document, line_id: usize, line: &Line, credit: bool) {
+    let (tag, quantity_tag) = if credit {
+        ("cac:CreditNoteLine", "cbc:CreditedQuantity")
+    } else {
+        ("cac:InvoiceLine", "cbc:InvoicedQuantity")
+    };
+    let currency = ("currencyID", doc.currency_code);
+
+    w.open(tag);
+    w.text("cbc:ID", &line_id.to_string());
+    w.text_with_attribute(
+        quantity_tag,
+        ("unitCode", UNIT_CODE_ONE),
+        &line.quantity.to_string(),
+    );
+    w.text_with_attribute(
+        "cbc:LineExtensionAmount",
+        currency,
+        &doc.amount(line.amount),
+    );
+    w.open("cac:InvoicePeriod");
+    w.text("cbc:StartDate", &iso_date(line.start_date));
+    w.text("cbc:EndDate", &iso_date(line.end_date));
+    w.close("cac:InvoicePeriod");
+    w.open("cac:Item");
+    w.opt_text("cbc:Description", line.description);
+    w.text("cbc:Name", line.name);
+    w.open("cac:ClassifiedTaxCategory");
+    w.text("cbc:ID", line.category.code());
+    if line.category.has_rate() {
+        w.text("cbc:Percent", &format_percent(line.rate));
+    }
+    write_tax_scheme(w);
+    w.close("cac:ClassifiedTaxCategory");
+    w.close("cac:Item");
+    w.open("cac:Price");
+    w.text_with_attribute("cbc:PriceAmount", currency, &line.price.to_string());
+    w.close("cac:Price");
+    w.close(tag);
+}
+
+fn write_party(w: &mut XmlWriter, party: &Party) {
+    let address = &party.address;
+
+    w.open("cac:Party");
+    if let Some((scheme, endpoint)) = endpoint(party) {
+        w.text_with_attribute("cbc:EndpointID", ("schemeID", scheme), endpoint);
+    }
+    w.open("cac:PartyName");
+    w.text("cbc:Name", party.name);
+    w.close("cac:PartyName");
+    w.open("cac:PostalAddress");
+    w.opt_text("cbc:StreetName", address.line1);
+    w.opt_text("cbc:AdditionalStreetName", address.line2);
+    w.opt_text("cbc:CityName", address.city);
+    w.opt_text("cbc:PostalZone", address.zip_code);
+    w.opt_text("cbc:CountrySubentity", address.state);
+    if let Some(country) = address.country {
+        w.open("cac:Country");
+        w.text("cbc:IdentificationCode", country);
+        w.close("cac:Country");
+    }
+    w.close("cac:PostalAddress");
+    if let Some(tax_id) = party.tax_id {
+        w.open("cac:PartyTaxScheme");
+        w.text("cbc:CompanyID", tax_id);
+        write_tax_scheme(w);
+        w.close("cac:PartyTaxScheme");
+    }
+    w.open("cac:PartyLegalEntity");
+    w.text("cbc:RegistrationName", party.name);
+    w.opt_text("cbc:CompanyID", party.legal_number);
+    w.close("cac:PartyLegalEntity");
+    if let Some(email) = party.email {
+        w.open("cac:Contact");
+        w.text("cbc:ElectronicMail", email);
+        w.close("cac:Contact");
+    }
+    w.close("cac:Party");
+}
+
+/// Peppol electronic address of the party (PEPPOL-EN16931-R010/R020): a national identifier
+/// or VAT number registered on the network, or the email address otherwise.
+fn endpoint<'a>(party: &Party<'a>) -> Option<(&'static str, &'a str)> {
+    let scheme = |schemes: &[(&str, &'static str)]| {
+        party.address.country.and_then(|country| {
+            schemes
+                .iter()
+                .find(|(code, _)| *code == country)
+                .map(|(_, scheme)| *scheme)
+        })
+    };
+
+    party
+        .legal_number
+        .zip(scheme(LEGAL_NUMBER_SCHEMES))
+        .map(|(legal_number, scheme)| (scheme, legal_number))
+        .or_else(|| {
+            party
+                .tax_id
+                .zip(scheme(VAT_SCHEMES))
+                .map(|(tax_id, scheme)| (scheme, tax_id))
+        })
+        .or_else(|| party.email.map(|email| ("EM", email)))
+}
+
+fn write_tax_scheme(w: &mut XmlWriter) {
+    w.open("cac:TaxScheme");
+    w.text("cbc:ID", "VAT");
+    w.close("cac:TaxScheme");
+}
+
+fn iso_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
@@ -49,7 +49,8 @@
   // Format currency values
   let format_amount = (amount) => {
     let formatted = calc.round(amount, digits: 2)
-    if lang.starts-with("fr") {
+    // Languages that put the currency symbol after the amount
+    if ("fr", "de", "es", "it", "pt").any(l => lang.starts-with(l)) {
       str(formatted) + " " + currency_symbol
     } else {
       currency_symbol + " " + str(formatted)
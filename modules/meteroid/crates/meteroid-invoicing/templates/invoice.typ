@@ -22,6 +22,7 @@
   tax_breakdown,
   translations,
   pay_online_url: none,
+  usage_annex_url: none,      // Download link of the usage detail of merged lines
   payment_status: "unpaid",  // "paid", "partially_paid", or "unpaid"
   transactions: (),  // list of transactions
   payment_info: none,  // key-value pairs for payment information
@@ -33,8 +34,10 @@
   show_legal_info: true,     // Show legal information section
   show_footer_custom_info: true,   // Show footer custom information section
   whitelabel: false,         // Hide meteroid branding
+  custom_fields: (),               // (name, value) pairs listed with the invoice details
   parent_invoice_number: none,     // Set when this is a corrective invoice
   parent_invoice_date: none,       // Date of the original invoice
+  facturx_xml: none,               // Factur-X XML embedded in the PDF, as bytes
 ) = {
   // Define color palette with named variables
   let color = (
@@ -57,7 +60,8 @@
   // Format currency values
   let format_amount = (amount) => {
     let formatted = calc.round(amount, digits: 2)
-    if lang.starts-with("fr") {
+    // Languages that put the currency symbol after the amount
+    if ("fr", "de", "es", "it", "pt").any(l => lang.starts-with(l)) {
       str(formatted) + " " + currency_symbol
     } else {
       currency_symbol + " " + str(formatted)
@@ -110,6 +114,18 @@
   set text(font: inter, size: 9.5pt, fill: color.text)
   set heading(numbering: none)
 
+  // Hybrid e-invoice: the machine-readable XML mirrors the visual content.
+  // The file name is mandated by the Factur-X specification.
+  if facturx_xml != none {
+    pdf.attach(
+      "factur-x.xml",
+      facturx_xml,
+      relationship: "alternative",
+      mime-type: "text/xml",
+      description: "Factur-X invoice",
+    )
+  }
+
   // Start with clean header layout
   grid(
     columns: (3fr, 3fr),
@@ -148,6 +164,11 @@
       )
     },
 
+    ..custom_fields.map(field => (
+      [#text(fill: color.accent, weight: "medium", field.name)],
+      [#text(weight: "medium", field.value)],
+    )).flatten(),
+
     ..if organization.tax_id != none {
       (
         [#text(fill: color.accent, weight: "medium", translations.seller_vat_id)],
@@ -297,6 +318,10 @@
           ]
           #linebreak()
           #text(size: 8pt, fill: color.date_text, item.start_date + " → " + item.end_date)
+          #if item.at("metadata", default: ()).len() > 0 [
+            #linebreak()
+            #text(size: 8pt, fill: color.date_text, item.metadata.map(field => field.name + ": " + field.value).join(" · "))
+          ]
         ],
 
         align(center, text(weight: "regular", if item.quantity != none { str(item.quantity) } else { "" })),
@@ -353,6 +378,11 @@
     }
   }
 
+  if usage_annex_url != none {
+    v(6pt)
+    link(usage_annex_url, text(size: 8pt, fill: color.accent, underline(translations.usage_annex)))
+  }
+
   v(16pt)
 
   // Summary section aligned right with payment status on the left
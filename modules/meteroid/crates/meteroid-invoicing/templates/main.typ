@@ -22,6 +22,7 @@
   inputs.tax_breakdown,
   inputs.translations,
   pay_online_url: inputs.at("pay_online_url", default: none),
+  usage_annex_url: inputs.at("usage_annex_url", default: none),
   payment_status: inputs.at("payment_status", default: "unpaid"),
   transactions: inputs.at("transactions", default: ()),
   payment_info: inputs.at("payment_info", default: none),
@@ -31,6 +32,8 @@
   show_tax_info: inputs.at("show_tax_info", default: true),
   show_legal_info: inputs.at("show_legal_info", default: true),
   show_footer_custom_info: inputs.at("show_footer_custom_info", default: true),
+  custom_fields: inputs.at("custom_fields", default: ()),
   parent_invoice_number: inputs.at("parent_invoice_number", default: none),
-  parent_invoice_date: inputs.at("parent_invoice_date", default: none)
+  parent_invoice_date: inputs.at("parent_invoice_date", default: none),
+  facturx_xml: inputs.at("facturx_xml", default: none)
 )
@@ -0,0 +1,719 @@
+//! Peppol BIS Billing 3.0 validation of the UBL documents.
+//!
+//! The checks below are a port of the EN 16931 and Peppol schematron rules (CEN-EN16931-UBL.sch
+//! and PEPPOL-EN16931-UBL.sch) that apply to the documents we generate. Assertion messages carry
+//! the rule identifier, so a failure can be looked up in the official rule set.
+
+use chrono::NaiveDate;
+use common_domain::country::CountryCode;
+use meteroid_invoicing::credit_note_model::{
+    self as cn, CreditNote, CreditNoteMetadata, CreditType,
+};
+use meteroid_invoicing::model::{
+    Address, Customer, Flags, Invoice, InvoiceLine, InvoiceMetadata, Organization, PaymentStatus,
+    TaxBreakdownItem, TaxExemptionType, Transaction,
+};
+use meteroid_invoicing::xml_ubl::{generate_ubl_credit_note, generate_ubl_invoice};
+use roxmltree::{Document, Node};
+use rust_decimal::Decimal;
+use rusty_money::{Money, iso};
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+const CUSTOMIZATION_ID: &str =
+    "urn:cen.eu:en16931:2017#compliant#urn:fdc:peppol.eu:2017:poacc:billing:3.0";
+const PROFILE_ID: &str = "urn:fdc:peppol.eu:2017:poacc:billing:01:1.0";
+
+#[test]
+fn test_ubl_invoice_standard_rate() {
+    let invoice = create_invoice();
+
+    let xml = generate_ubl_invoice(&invoice).unwrap();
+    let doc = Document::parse(&xml).unwrap();
+    assert_bis_compliant(&doc);
+
+    let root = doc.root_element();
+    assert_eq!(root.tag_name().name(), "Invoice");
+    assert_eq!(text(root, "InvoiceTypeCode"), "380");
+    assert_eq!(text(root, "DueDate"), "2025-01-31");
+    assert_eq!(text(root, "BuyerReference"), "PO-42");
+    assert_eq!(
+        endpoint(root, "AccountingSupplierParty"),
+        ("9925", "BE0123456789")
+    );
+    assert_eq!(
+        endpoint(root, "AccountingCustomerParty"),
+        ("9944", "NL123456789B01")
+    );
+    assert_eq!(
+        text(root, "PaymentMeans/PayeeFinancialAccount/ID"),
+        "BE71096123456769"
+    );
+    assert_eq!(text(root, "PaymentMeans/PaymentID"), "+++090/9337/55493+++");
+    assert_eq!(text(root, "LegalMonetaryTotal/PayableAmount"), "242.00");
+}
+
+#[test]
+fn test_ubl_invoice_reverse_charge_with_discount() {
+    let eur = iso::find("EUR").unwrap();
+    let mut invoice = create_invoice();
+    invoice.metadata.discount = Money::from_major(20, eur);
+    for line in &mut invoice.lines {
+        line.tax_rate = Decimal::ZERO;
+    }
+    invoice.tax_breakdown = vec![TaxBreakdownItem {
+        name: "Reverse charge".to_string(),
+        rate: Decimal::ZERO,
+        taxable_amount: Money::from_major(180, eur),
+        amount: Money::from_major(0, eur),
+        exemption_type: Some(TaxExemptionType::ReverseCharge),
+    }];
+
+    let xml = generate_ubl_invoice(&invoice).unwrap();
+    let doc = Document::parse(&xml).unwrap();
+    assert_bis_compliant(&doc);
+
+    let root = doc.root_element();
+    let category = node(root, "TaxTotal/TaxSubtotal/TaxCategory");
+    assert_eq!(text(category, "ID"), "AE");
+    assert_eq!(text(category, "TaxExemptionReasonCode"), "VATEX-EU-AE");
+    assert_eq!(text(root, "AllowanceCharge/ChargeIndicator"), "false");
+    assert_eq!(text(root, "AllowanceCharge/Amount"), "20.00");
+    assert_eq!(text(root, "LegalMonetaryTotal/PayableAmount"), "180.00");
+}
+
+#[test]
+fn test_ubl_invoice_prorated_and_mixed_rates() {
+    let eur = iso::find("EUR").unwrap();
+    let mut invoice = create_invoice();
+    // Prorated: the unit price does not multiply back to the amount
+    invoice.lines[0].subtotal = Money::from_minor(6452, eur);
+    invoice.lines[0].is_prorated = true;
+    invoice.lines[1].tax_rate = Decimal::from_str("0.06").unwrap();
+    invoice.lines.push(InvoiceLine {
+        name: "Goodwill credit".to_string(),
+        description: None,
+        subtotal: Money::from_major(-10, eur),
+        quantity: Some(Decimal::ONE),
+        unit_price: Some(Money::from_major(-10, eur)),
+        tax_rate: Decimal::from_str("0.21").unwrap(),
+        start_date: invoice.lines[0].start_date,
+        end_date: invoice.lines[0].end_date,
+        is_prorated: false,
+        sub_lines: vec![],
+        group_label: None,
+        metadata: BTreeMap::new(),
+    });
+    invoice.tax_breakdown = vec![
+        TaxBreakdownItem {
+            name: "VAT 21%".to_string(),
+            rate: Decimal::from_str("0.21").unwrap(),
+            taxable_amount: Money::from_minor(5452, eur),
+            amount: Money::from_minor(1145, eur),
+            exemption_type: None,
+        },
+        TaxBreakdownItem {
+            name: "VAT 6%".to_string(),
+            rate: Decimal::from_str("0.06").unwrap(),
+            taxable_amount: Money::from_major(100, eur),
+            amount: Money::from_major(6, eur),
+            exemption_type: None,
+        },
+    ];
+    invoice.transactions = vec![Transaction {
+        method: "card".to_string(),
+        date: invoice.metadata.issue_date,
+        amount: Money::from_major(50, eur),
+    }];
+
+    let xml = generate_ubl_invoice(&invoice).unwrap();
+    let doc = Document::parse(&xml).unwrap();
+    assert_bis_compliant(&doc);
+
+    let root = doc.root_element();
+    assert_eq!(children(root, "TaxTotal/TaxSubtotal").len(), 2);
+    assert_eq!(text(root, "LegalMonetaryTotal/PrepaidAmount"), "50.00");
+    assert_eq!(text(root, "LegalMonetaryTotal/PayableAmount"), "121.97");
+}
+
+#[test]
+fn test_ubl_credit_note() {
+    let credit_note = create_credit_note();
+
+    let xml = generate_ubl_credit_note(&credit_note).unwrap();
+    let doc = Document::parse(&xml).unwrap();
+    assert_bis_compliant(&doc);
+
+    let root = doc.root_element();
+    assert_eq!(root.tag_name().name(), "CreditNote");
+    assert_eq!(text(root, "CreditNoteTypeCode"), "381");
+    assert_eq!(text(root, "Note"), "Billing error");
+    assert_eq!(
+        text(root, "BillingReference/InvoiceDocumentReference/ID"),
+        "INV-001"
+    );
+    // Credit note amounts are negative in the model, but positive in the UBL credit note
+    assert_eq!(text(root, "CreditNoteLine/CreditedQuantity"), "2");
+    assert_eq!(text(root, "CreditNoteLine/LineExtensionAmount"), "100.00");
+    assert_eq!(text(root, "LegalMonetaryTotal/PayableAmount"), "121.00");
+}
+
+#[test]
+fn test_ubl_endpoints_fall_back_to_email() {
+    let mut invoice = create_invoice();
+    invoice.organization.address.country = CountryCode::parse_as_opt("NO");
+    invoice.organization.legal_number = Some("987654321".to_string());
+    invoice.customer.address.country = CountryCode::parse_as_opt("US");
+    invoice.customer.tax_id = None;
+
+    let xml = generate_ubl_invoice(&invoice).unwrap();
+    let doc = Document::parse(&xml).unwrap();
+    assert_bis_compliant(&doc);
+
+    let root = doc.root_element();
+    assert_eq!(
+        endpoint(root, "AccountingSupplierParty"),
+        ("0192", "987654321")
+    );
+    assert_eq!(
+        endpoint(root, "AccountingCustomerParty"),
+        ("EM", "ap@buyer.example")
+    );
+}
+
+#[test]
+fn test_bis_rules_reject_inconsistent_totals() {
+    let xml = generate_ubl_invoice(&create_invoice()).unwrap().replace(
+        r#"<cbc:PayableAmount currencyID="EUR">242.00</cbc:PayableAmount>"#,
+        r#"<cbc:PayableAmount currencyID="EUR">240.00</cbc:PayableAmount>"#,
+    );
+    let doc = Document::parse(&xml).unwrap();
+
+    let result = std::panic::catch_unwind(|| assert_bis_compliant(&doc));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("[BR-CO-16]"), "{message}");
+}
+
+/// Checks the document against the BIS 3.0 rules, panicking on the first violation.
+fn assert_bis_compliant(doc: &Document) {
+    let root = doc.root_element();
+    let is_credit_note = root.tag_name().name() == "CreditNote";
+    let line_tag = if is_credit_note {
+        "CreditNoteLine"
+    } else {
+        "InvoiceLine"
+    };
+    let currency = text(root, "DocumentCurrencyCode");
+
+    // Document level
+    assert_eq!(text(root, "CustomizationID"), CUSTOMIZATION_ID, "[BR-01]");
+    assert_eq!(text(root, "ProfileID"), PROFILE_ID, "[PEPPOL-EN16931-R001]");
+    assert!(
+        has(root, "ID"),
+        "[BR-02] An invoice shall have an invoice number"
+    );
+    assert!(
+        has(root, "IssueDate"),
+        "[BR-03] An invoice shall have an issue date"
+    );
+    let type_code = if is_credit_note {
+        text(root, "CreditNoteTypeCode")
+    } else {
+        text(root, "InvoiceTypeCode")
+    };
+    assert!(
+        ["380", "381", "383", "384", "386", "389", "751"].contains(&type_code),
+        "[BR-04] [PEPPOL-EN16931-P0100] Invalid type code {type_code}"
+    );
+    assert_eq!(currency.len(), 3, "[BR-05] Invalid currency code");
+    assert!(
+        has(root, "BuyerReference") || has(root, "OrderReference/ID"),
+        "[PEPPOL-EN16931-R003] A buyer reference or purchase order reference must be provided"
+    );
+    for element in root.descendants().filter(Node::is_element) {
+        if element.children().any(|c| c.is_element()) {
+            continue;
+        }
+        assert!(
+            element.text().is_some_and(|t| !t.trim().is_empty()),
+            "[PEPPOL-EN16931-R008] Empty element {}",
+            element.tag_name().name()
+        );
+        if let Some(attribute) = element.attribute("currencyID") {
+            assert_eq!(
+                attribute,
+                currency,
+                "[PEPPOL-EN16931-R051] Currency of {} differs from the document currency",
+                element.tag_name().name()
+            );
+            assert!(
+                decimals(element.text().unwrap()) <= 2
+                    || element.tag_name().name() == "PriceAmount",
+                "[BR-DEC] {} has more than 2 decimals",
+                element.tag_name().name()
+            );
+        }
+    }
+
+    // Parties
+    for (party, rules) in [
+        (
+            "AccountingSupplierParty",
+            ["BR-06", "BR-08", "BR-09", "PEPPOL-EN16931-R020"],
+        ),
+        (
+            "AccountingCustomerParty",
+            ["BR-07", "BR-10", "BR-11", "PEPPOL-EN16931-R010"],
+        ),
+    ] {
+        let party = node(root, &format!("{party}/Party"));
+        assert!(
+            has(party, "PartyLegalEntity/RegistrationName"),
+            "[{}] Missing party name",
+            rules[0]
+        );
+        assert!(
+            has(party, "PostalAddress"),
+            "[{}] Missing postal address",
+            rules[1]
+        );
+        assert!(
+            has(party, "PostalAddress/Country/IdentificationCode"),
+            "[{}] Missing country code",
+            rules[2]
+        );
+        let endpoint = child(party, "EndpointID")
+            .unwrap_or_else(|| panic!("[{}] Missing electronic address", rules[3]));
+        assert!(
+            endpoint.attribute("schemeID").is_some(),
+            "[PEPPOL-EN16931-R020] Missing electronic address scheme"
+        );
+    }
+
+    // Lines
+    let lines = children(root, line_tag);
+    assert!(
+        !lines.is_empty(),
+        "[BR-16] An invoice shall have at least one line"
+    );
+    let quantity_tag = if is_credit_note {
+        "CreditedQuantity"
+    } else {
+        "InvoicedQuantity"
+    };
+    for line in &lines {
+        assert!(has(*line, "ID"), "[BR-21] Missing line identifier");
+        assert!(has(*line, quantity_tag), "[BR-22] Missing quantity");
+        assert!(
+            node(*line, quantity_tag).attribute("unitCode").is_some(),
+            "[BR-23] Missing unit of measure"
+        );
+        assert!(
+            has(*line, "LineExtensionAmount"),
+            "[BR-24] Missing line amount"
+        );
+        assert!(has(*line, "Item/Name"), "[BR-25] Missing item name");
+        let price = amount(*line, "Price/PriceAmount");
+        assert!(!price.is_sign_negative(), "[BR-27] Negative item net price");
+        let quantity = amount(*line, quantity_tag);
+        assert_eq!(
+            (quantity * price).round_dp(2),
+            amount(*line, "LineExtensionAmount"),
+            "[PEPPOL-EN16931-R120] Line amount must equal quantity times price"
+        );
+        let category = node(*line, "Item/ClassifiedTaxCategory");
+        assert!(has(category, "ID"), "[BR-CO-04] Missing line VAT category");
+        if text(category, "ID") == "O" {
+            assert!(
+                !has(category, "Percent"),
+                "[BR-O-05] Rate on a line not subject to VAT"
+            );
+        }
+    }
+
+    // Document level allowances and charges
+    let allowance_charges = children(root, "AllowanceCharge");
+    let sum_adjustments = |charge: bool| -> Decimal {
+        allowance_charges
+            .iter()
+            .filter(|a| (text(**a, "ChargeIndicator") == "true") == charge)
+            .map(|a| amount(*a, "Amount"))
+            .sum()
+    };
+    for allowance_charge in &allowance_charges {
+        assert!(
+            has(*allowance_charge, "AllowanceChargeReason")
+                || has(*allowance_charge, "AllowanceChargeReasonCode"),
+            "[BR-33] [BR-38] Missing allowance or charge reason"
+        );
+        assert!(
+            has(*allowance_charge, "TaxCategory/ID"),
+            "[BR-32] [BR-37] Missing allowance or charge VAT category"
+        );
+    }
+
+    // Totals
+    let totals = node(root, "LegalMonetaryTotal");
+    let line_total: Decimal = lines
+        .iter()
+        .map(|l| amount(*l, "LineExtensionAmount"))
+        .sum();
+    let allowance_total = amount(totals, "AllowanceTotalAmount");
+    let charge_total = amount(totals, "ChargeTotalAmount");
+    let tax_exclusive = amount(totals, "TaxExclusiveAmount");
+    let tax_inclusive = amount(totals, "TaxInclusiveAmount");
+    let prepaid = amount(totals, "PrepaidAmount");
+
+    assert_eq!(
+        amount(totals, "LineExtensionAmount"),
+        line_total,
+        "[BR-CO-10]"
+    );
+    assert_eq!(allowance_total, sum_adjustments(false), "[BR-CO-11]");
+    assert_eq!(charge_total, sum_adjustments(true), "[BR-CO-12]");
+    assert_eq!(
+        tax_exclusive,
+        line_total - allowance_total + charge_total,
+        "[BR-CO-13]"
+    );
+
+    let tax_totals: Vec<_> = children(root, "TaxTotal")
+        .into_iter()
+        .filter(|t| has(*t, "TaxSubtotal"))
+        .collect();
+    assert_eq!(tax_totals.len(), 1, "[PEPPOL-EN16931-R053]");
+    let tax_total = tax_totals[0];
+    let subtotals = children(tax_total, "TaxSubtotal");
+    assert_eq!(
+        amount(tax_total, "TaxAmount"),
+        subtotals.iter().map(|s| amount(*s, "TaxAmount")).sum(),
+        "[BR-CO-14]"
+    );
+    assert_eq!(
+        tax_inclusive,
+        tax_exclusive + amount(tax_total, "TaxAmount"),
+        "[BR-CO-15]"
+    );
+    assert_eq!(
+        amount(totals, "PayableAmount"),
+        tax_inclusive - prepaid,
+        "[BR-CO-16]"
+    );
+
+    // VAT breakdown
+    for subtotal in &subtotals {
+        let category = node(*subtotal, "TaxCategory");
+        let code = text(category, "ID");
+        let rate =
+            child(category, "Percent").map(|p| Decimal::from_str(p.text().unwrap()).unwrap());
+        let matches = |node: Node| {
+            text(node, "ID") == code
+                && child(node, "Percent").map(|p| Decimal::from_str(p.text().unwrap()).unwrap())
+                    == rate
+        };
+
+        let lines_amount: Decimal = lines
+            .iter()
+            .filter(|l| matches(node(**l, "Item/ClassifiedTaxCategory")))
+            .map(|l| amount(*l, "LineExtensionAmount"))
+            .sum();
+        let adjustments: Decimal = allowance_charges
+            .iter()
+            .filter(|a| matches(node(**a, "TaxCategory")))
+            .map(|a| {
+                let value = amount(*a, "Amount");
+                if text(*a, "ChargeIndicator") == "true" {
+                    value
+                } else {
+                    -value
+                }
+            })
+            .sum();
+        let taxable = amount(*subtotal, "TaxableAmount");
+        assert_eq!(
+            taxable,
+            lines_amount + adjustments,
+            "[BR-{code}-08] Taxable amount of category {code} does not match its lines"
+        );
+
+        match code {
+            "S" => {
+                let rate = rate.expect("[BR-S-05] Missing standard rate");
+                assert!(
+                    rate > Decimal::ZERO,
+                    "[BR-S-05] Standard rate must be positive"
+                );
+                assert_eq!(
+                    amount(*subtotal, "TaxAmount"),
+                    (taxable * rate / Decimal::ONE_HUNDRED).round_dp(2),
+                    "[BR-S-09]"
+                );
+            }
+            "Z" | "E" | "AE" => {
+                assert_eq!(rate, Some(Decimal::ZERO), "[BR-{code}-05] Rate must be 0");
+                assert!(
+                    amount(*subtotal, "TaxAmount").is_zero(),
+                    "[BR-{code}-09] VAT amount must be 0"
+                );
+            }
+            "O" => assert!(rate.is_none(), "[BR-O-05] Rate not allowed"),
+            _ => panic!("[BR-CL-10] Unknown VAT category {code}"),
+        }
+        if code != "S" && code != "Z" {
+            assert!(
+                has(category, "TaxExemptionReason") || has(category, "TaxExemptionReasonCode"),
+                "[BR-{code}-10] Missing VAT exemption reason"
+            );
+        }
+        if code == "AE" {
+            assert!(
+                has(
+                    root,
+                    "AccountingSupplierParty/Party/PartyTaxScheme/CompanyID"
+                ) && has(
+                    root,
+                    "AccountingCustomerParty/Party/PartyTaxScheme/CompanyID"
+                ),
+                "[BR-AE-02] Reverse charge requires the seller and buyer VAT identifiers"
+            );
+        }
+    }
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children()
+        .find(|n| n.is_element() && n.tag_name().name() == name)
+}
+
+fn children<'a, 'input>(node: Node<'a, 'input>, path: &str) -> Vec<Node<'a, 'input>> {
+    let (parent, name) = match path.rsplit_once('/') {
+        Some((parent, name)) => (self::node(node, parent), name),
+        None => (node, path),
+    };
+    parent
+        .children()
+        .filter(|n| n.is_element() && n.tag_name().name() == name)
+        .collect()
+}
+
+fn find<'a, 'input>(node: Node<'a, 'input>, path: &str) -> Option<Node<'a, 'input>> {
+    path.split('/').try_fold(node, child)
+}
+
+fn has(node: Node, path: &str) -> bool {
+    find(node, path).is_some()
+}
+
+fn node<'a, 'input>(node: Node<'a, 'input>, path: &str) -> Node<'a, 'input> {
+    find(node, path).unwrap_or_else(|| panic!("Missing element {path}"))
+}
+
+fn text<'a>(node: Node<'a, '_>, path: &str) -> &'a str {
+    self::node(node, path).text().unwrap_or_default()
+}
+
+fn amount(node: Node, path: &str) -> Decimal {
+    Decimal::from_str(text(node, path)).unwrap()
+}
+
+fn decimals(value: &str) -> usize {
+    value.split_once('.').map_or(0, |(_, d)| d.len())
+}
+
+fn endpoint<'a>(root: Node<'a, '_>, party: &str) -> (&'a str, &'a str) {
+    let endpoint = node(root, &format!("{party}/Party/EndpointID"));
+    (
+        endpoint.attribute("schemeID").unwrap(),
+        endpoint.text().unwrap(),
+    )
+}
+
+fn create_invoice() -> Invoice {
+    let eur = iso::find("EUR").unwrap();
+    let start_date = NaiveDate::from_ymd_opt(2024, 12, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+    Invoice {
+        lang: "en-US".to_string(),
+        translation_overrides: HashMap::new(),
+        template: None,
+        organization: Organization {
+            name: "Seller BV".to_string(),
+            logo_src: None,
+            legal_number: Some("0123456789".to_string()),
+            address: Address {
+                line1: Some("Rue de la Loi 16".to_string()),
+                line2: None,
+                city: Some("Brussels".to_string()),
+                country: CountryCode::parse_as_opt("BE"),
+                state: None,
+                zip_code: Some("1000".to_string()),
+            },
+            email: Some("billing@seller.example".to_string()),
+            tax_id: Some("BE0123456789".to_string()),
+            footer_info: None,
+            footer_legal: None,
+            accounting_currency: *eur,
+            exchange_rate: None,
+        },
+        customer: Customer {
+            name: "Buyer & Co".to_string(),
+            legal_number: None,
+            address: Address {
+                line1: Some("Damrak 1".to_string()),
+                line2: None,
+                city: Some("Amsterdam".to_string()),
+                country: CountryCode::parse_as_opt("NL"),
+                state: None,
+                zip_code: Some("1012 LG".to_string()),
+            },
+            email: Some("ap@buyer.example".to_string()),
+            tax_id: Some("NL123456789B01".to_string()),
+        },
+        metadata: InvoiceMetadata {
+            number: "INV-2025-001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            payment_term: 30,
+            subtotal: Money::from_major(200, eur),
+            tax_amount: Money::from_major(42, eur),
+            total_amount: Money::from_major(242, eur),
+            discount: Money::from_major(0, eur),
+            currency: eur,
+            due_date: NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            memo: None,
+            payment_url: None,
+            usage_annex_url: None,
+            flags: Flags::default(),
+            purchase_order: Some("PO-42".to_string()),
+            custom_fields: BTreeMap::new(),
+            parent_invoice_number: None,
+            parent_invoice_date: None,
+        },
+        lines: vec![
+            InvoiceLine {
+                name: "Seats".to_string(),
+                description: None,
+                subtotal: Money::from_major(100, eur),
+                quantity: Some(Decimal::from(10)),
+                unit_price: Some(Money::from_major(10, eur)),
+                tax_rate: Decimal::from_str("0.21").unwrap(),
+                start_date,
+                end_date,
+                is_prorated: false,
+                sub_lines: vec![],
+                group_label: None,
+                metadata: BTreeMap::new(),
+            },
+            InvoiceLine {
+                name: "Platform fee".to_string(),
+                description: Some(String::new()),
+                subtotal: Money::from_major(100, eur),
+                quantity: Some(Decimal::ONE),
+                unit_price: Some(Money::from_major(100, eur)),
+                tax_rate: Decimal::from_str("0.21").unwrap(),
+                start_date,
+                end_date,
+                is_prorated: false,
+                sub_lines: vec![],
+                group_label: None,
+                metadata: BTreeMap::new(),
+            },
+        ],
+        coupons: vec![],
+        payment_status: Some(PaymentStatus::Unpaid),
+        transactions: vec![],
+        bank_details: Some(HashMap::from([
+            ("IBAN".to_string(), "BE71 0961 2345 6769".to_string()),
+            ("BIC/SWIFT".to_string(), "GKCCBEBB".to_string()),
+            (
+                "Payment Reference".to_string(),
+                "+++090/9337/55493+++".to_string(),
+            ),
+        ])),
+        tax_breakdown: vec![TaxBreakdownItem {
+            name: "VAT 21%".to_string(),
+            rate: Decimal::from_str("0.21").unwrap(),
+            taxable_amount: Money::from_major(200, eur),
+            amount: Money::from_major(42, eur),
+            exemption_type: None,
+        }],
+    }
+}
+
+fn create_credit_note() -> CreditNote {
+    let eur = iso::find("EUR").unwrap();
+    let invoice = create_invoice();
+
+    CreditNote {
+        lang: "en-US".to_string(),
+        translation_overrides: HashMap::new(),
+        organization: cn::Organization {
+            name: invoice.organization.name,
+            logo_src: None,
+            legal_number: invoice.organization.legal_number,
+            address: cn::Address {
+                line1: invoice.organization.address.line1,
+                line2: None,
+                city: invoice.organization.address.city,
+                country: invoice.organization.address.country,
+                state: None,
+                zip_code: invoice.organization.address.zip_code,
+            },
+            email: invoice.organization.email,
+            tax_id: invoice.organization.tax_id,
+            footer_info: None,
+            footer_legal: None,
+            accounting_currency: *eur,
+            exchange_rate: None,
+        },
+        customer: cn::Customer {
+            name: invoice.customer.name,
+            legal_number: None,
+            address: cn::Address {
+                line1: invoice.customer.address.line1,
+                line2: None,
+                city: invoice.customer.address.city,
+                country: invoice.customer.address.country,
+                state: None,
+                zip_code: invoice.customer.address.zip_code,
+            },
+            email: invoice.customer.email,
+            tax_id: invoice.customer.tax_id,
+        },
+        metadata: CreditNoteMetadata {
+            number: "CN-001".to_string(),
+            issue_date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            related_invoice_number: "INV-001".to_string(),
+            related_invoice_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            subtotal: Money::from_major(-100, eur),
+            tax_amount: Money::from_major(-21, eur),
+            total_amount: Money::from_major(-121, eur),
+            currency: eur,
+            reason: Some("Billing error".to_string()),
+            memo: None,
+            credit_type: CreditType::CreditToBalance,
+            refunded_amount: Money::from_major(0, eur),
+            credited_amount: Money::from_major(121, eur),
+            flags: cn::Flags::default(),
+        },
+        lines: vec![cn::CreditNoteLine {
+            name: "Seats".to_string(),
+            description: None,
+            subtotal: Money::from_major(-100, eur),
+            quantity: Some(Decimal::from(2)),
+            unit_price: Some(Money::from_major(50, eur)),
+            tax_rate: Decimal::from_str("0.21").unwrap(),
+            start_date: NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(),
+            end_date: NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            is_prorated: false,
+            sub_lines: vec![],
+        }],
+        tax_breakdown: vec![cn::TaxBreakdownItem {
+            name: "VAT 21%".to_string(),
+            rate: Decimal::from_str("0.21").unwrap(),
+            taxable_amount: Money::from_major(-100, eur),
+            amount: Money::from_major(-21, eur),
+            exemption_type: None,
+        }],
+    }
+}
@@ -3,19 +3,26 @@ use common_domain::country::CountryCode;
 use meteroid_invoicing::credit_note_model::{
     self as cn, CreditNote, CreditNoteMetadata, CreditType,
 };
-use meteroid_invoicing::model::{Coupon, Flags, PaymentStatus, TaxBreakdownItem};
+use meteroid_invoicing::credit_note_render::TypstCreditNoteContent;
+use meteroid_invoicing::errors::InvoicingError;
+use meteroid_invoicing::locale::{SUPPORTED_LOCALES, resolve_locale};
+use meteroid_invoicing::model::{Coupon, Flags, PaymentStatus, TaxBreakdownItem, TaxExemptionType};
 use meteroid_invoicing::pdf::{CreditNotePdfGenerator, PdfGenerator, TypstCreditNotePdfGenerator};
+use meteroid_invoicing::template::validate_invoice_template;
+use meteroid_invoicing::xml_facturx::generate_facturx_xml;
 use meteroid_invoicing::{
     model::{
         Address, Customer, Invoice, InvoiceLine, InvoiceMetadata, InvoiceSubLine, Organization,
         Transaction,
     },
     pdf::TypstPdfGenerator,
+    typst_render::TypstInvoiceContent,
 };
 use rust_decimal::Decimal;
 use rusty_money::{Money, iso};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::str::FromStr;
+use typst::foundations::IntoValue;
 
 #[tokio::test]
 async fn test_typst_invoice_generation_validates_template() {
@@ -87,6 +94,255 @@ async fn test_typst_invoice_with_multiple_languages() {
     );
 }
 
+#[tokio::test]
+async fn test_typst_invoice_with_supported_locales() {
+    let generator = TypstPdfGenerator::new().expect("Failed to create TypstPdfGenerator");
+
+    for locale in SUPPORTED_LOCALES {
+        let mut invoice = create_minimal_invoice();
+        invoice.lang = locale.to_string();
+        let result = generator.generate_pdf(&invoice).await;
+        assert!(
+            result.is_ok(),
+            "Failed to generate {locale} PDF: {:?}",
+            result.err()
+        );
+    }
+}
+
+#[test]
+fn test_resolve_locale_matches_primary_language() {
+    assert_eq!(resolve_locale("de-DE"), "de-DE");
+    assert_eq!(resolve_locale("de-AT"), "de-DE");
+    assert_eq!(resolve_locale("pt"), "pt-PT");
+    assert_eq!(resolve_locale("nl-BE"), "nl-NL");
+    assert_eq!(resolve_locale("fr"), "fr-FR");
+    assert_eq!(resolve_locale("sv-SE"), "en-US");
+}
+
+#[test]
+fn test_invoice_translations_with_tenant_overrides() {
+    let mut invoice = create_minimal_invoice();
+    invoice.lang = "de-AT".to_string();
+    invoice.translation_overrides = HashMap::from([
+        ("bill-to".to_string(), "Kunde".to_string()),
+        (
+            "payment-terms-text".to_string(),
+            "Zahlbar netto { $days } Tage".to_string(),
+        ),
+        // not shown on invoices
+        ("credit-to".to_string(), "Gutschrift für".to_string()),
+    ]);
+
+    let content = TypstInvoiceContent::from(&invoice);
+    let translation = |key: &str| content.translations.get(key).cloned().ok();
+
+    assert_eq!(translation("invoice_title"), Some("Rechnung".into_value()));
+    assert_eq!(translation("bill_to"), Some("Kunde".into_value()));
+    assert_eq!(
+        translation("payment_terms_text"),
+        Some("Zahlbar netto 14 Tage".into_value())
+    );
+    assert_eq!(translation("credit_to"), None);
+    assert_eq!(content.issue_date, " 1. Januar 2025");
+    assert_eq!(
+        content.customer.address.country.as_deref(),
+        Some("Vereinigte Staaten")
+    );
+}
+
+#[tokio::test]
+async fn test_typst_invoice_with_custom_fields() {
+    let generator = TypstPdfGenerator::new().expect("Failed to create TypstPdfGenerator");
+    let mut invoice = create_minimal_invoice();
+    invoice.metadata.custom_fields = BTreeMap::from([
+        ("project".to_string(), "Apollo".to_string()),
+        ("cost_center".to_string(), "CC-42".to_string()),
+    ]);
+    invoice.lines[0].metadata = BTreeMap::from([("contract_id".to_string(), "CT-7".to_string())]);
+
+    let content = TypstInvoiceContent::from(&invoice);
+    let names: Vec<_> = content
+        .custom_fields
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["cost_center", "project"]);
+    assert_eq!(content.lines[0].metadata[0].value, "CT-7");
+
+    let result = generator.generate_pdf(&invoice).await;
+    assert!(
+        result.is_ok(),
+        "Failed to generate PDF with custom fields: {:?}",
+        result.err()
+    );
+}
+
+#[tokio::test]
+async fn test_facturx_invoice_embeds_cii_xml() {
+    let generator = TypstPdfGenerator::new().expect("Failed to create TypstPdfGenerator");
+    let invoice = create_minimal_invoice();
+
+    let result = generator.generate_facturx_pdf(&invoice).await;
+    assert!(
+        result.is_ok(),
+        "Failed to generate Factur-X PDF: {:?}",
+        result.err()
+    );
+
+    let document = result.unwrap();
+    assert!(
+        &document.pdf[0..4] == b"%PDF",
+        "Output should be a valid PDF file"
+    );
+
+    // The last metadata stream, written by the incremental update, identifies the XML
+    let pdf = String::from_utf8_lossy(&document.pdf);
+    let xmp_start = pdf.rfind("<?xpacket begin").expect("missing XMP metadata");
+    let xmp_end = pdf
+        .rfind("<?xpacket end=\"r\"?>")
+        .expect("missing XMP metadata");
+    let xmp = &pdf[xmp_start..xmp_end];
+    let xmp = xmp.trim_start_matches(|c| c != '>').trim_start_matches('>');
+    let xmp = roxmltree::Document::parse(xmp).expect("invalid XMP metadata");
+    let fx = "urn:factur-x:pdfa:CrossIndustryDocument:invoice:1p0#";
+    let fx_property = |name: &str| {
+        xmp.descendants()
+            .find(|n| n.has_tag_name((fx, name)))
+            .and_then(|n| n.text())
+            .map(str::to_string)
+    };
+    assert_eq!(fx_property("DocumentType").as_deref(), Some("INVOICE"));
+    assert_eq!(
+        fx_property("DocumentFileName").as_deref(),
+        Some("factur-x.xml")
+    );
+    assert_eq!(fx_property("Version").as_deref(), Some("1.0"));
+    assert_eq!(fx_property("ConformanceLevel").as_deref(), Some("EN 16931"));
+    assert!(
+        xmp.descendants()
+            .any(|n| n.has_tag_name("prefix") && n.text() == Some("fx")),
+        "the fx schema should be described as a PDF/A extension schema"
+    );
+    assert!(
+        pdf.contains("/Prev "),
+        "the metadata should be updated incrementally"
+    );
+
+    let xml = String::from_utf8(document.xml.to_vec()).unwrap();
+    assert!(xml.contains("<ram:ID>urn:cen.eu:en16931:2017</ram:ID>"));
+    assert!(xml.contains("<ram:ID>TEST-001</ram:ID>"));
+    assert!(xml.contains("<ram:TypeCode>380</ram:TypeCode>"));
+    assert!(xml.contains("<ram:IssuerAssignedID>some order</ram:IssuerAssignedID>"));
+    assert!(xml.contains("<ram:CategoryCode>S</ram:CategoryCode>"));
+    assert!(xml.contains("<ram:LineTotalAmount>100.00</ram:LineTotalAmount>"));
+    assert!(xml.contains("<ram:TaxBasisTotalAmount>100.00</ram:TaxBasisTotalAmount>"));
+    assert!(xml.contains(r#"<ram:TaxTotalAmount currencyID="EUR">20.00</ram:TaxTotalAmount>"#));
+    assert!(xml.contains("<ram:GrandTotalAmount>120.00</ram:GrandTotalAmount>"));
+    assert!(xml.contains("<ram:DuePayableAmount>120.00</ram:DuePayableAmount>"));
+}
+
+#[test]
+fn test_facturx_xml_reverse_charge_with_discount() {
+    let eur = iso::find("EUR").unwrap();
+    let mut invoice = create_minimal_invoice();
+    invoice.customer.name = "Müller & Söhne".to_string();
+    invoice.customer.tax_id = Some("DE123456789".to_string());
+    invoice.metadata.discount = Money::from_major(10, eur);
+    invoice.lines[0].tax_rate = Decimal::ZERO;
+    invoice.tax_breakdown = vec![TaxBreakdownItem {
+        name: "Reverse charge".to_string(),
+        rate: Decimal::ZERO,
+        taxable_amount: Money::from_major(90, eur),
+        amount: Money::from_major(0, eur),
+        exemption_type: Some(TaxExemptionType::ReverseCharge),
+    }];
+
+    let xml = generate_facturx_xml(&invoice).unwrap();
+
+    assert!(xml.contains("<ram:Name>Müller &amp; Söhne</ram:Name>"));
+    assert!(xml.contains(r#"<ram:ID schemeID="VA">DE123456789</ram:ID>"#));
+    assert!(xml.contains("<ram:CategoryCode>AE</ram:CategoryCode>"));
+    assert!(xml.contains("<ram:ExemptionReason>Reverse charge</ram:ExemptionReason>"));
+    assert!(xml.contains("<ram:ExemptionReasonCode>VATEX-EU-AE</ram:ExemptionReasonCode>"));
+    // The discount is a document level allowance on the reverse charge category
+    assert!(xml.contains("<udt:Indicator>false</udt:Indicator>"));
+    assert!(xml.contains("<ram:ActualAmount>10.00</ram:ActualAmount>"));
+    assert!(xml.contains("<ram:AllowanceTotalAmount>10.00</ram:AllowanceTotalAmount>"));
+    assert!(xml.contains("<ram:BasisAmount>90.00</ram:BasisAmount>"));
+    assert!(xml.contains("<ram:TaxBasisTotalAmount>90.00</ram:TaxBasisTotalAmount>"));
+    assert!(xml.contains("<ram:GrandTotalAmount>90.00</ram:GrandTotalAmount>"));
+}
+
+static CUSTOM_TEMPLATE: &str = r#"
+#import sys: inputs
+
+#set document(title: inputs.number, date: datetime.today())
+#set page(paper: "a4", margin: 2cm)
+#set text(font: "Inter", size: 9pt)
+
+= #inputs.translations.invoice_title #inputs.number
+
+#table(
+  columns: (1fr, auto),
+  ..inputs.lines.map(line => (line.name, str(line.subtotal))).flatten()
+)
+
+Late payment penalties: three times the legal interest rate, plus a 40 EUR recovery fee.
+"#;
+
+#[tokio::test]
+async fn test_typst_invoice_with_custom_template() {
+    let generator = TypstPdfGenerator::new().expect("Failed to create TypstPdfGenerator");
+    let mut invoice = create_minimal_invoice();
+    invoice.template = Some(CUSTOM_TEMPLATE.to_string());
+
+    let result = generator.generate_pdf(&invoice).await;
+    assert!(
+        result.is_ok(),
+        "Failed to generate PDF with a custom template: {:?}",
+        result.err()
+    );
+    assert!(&result.unwrap()[0..4] == b"%PDF");
+
+    assert_eq!(validate_invoice_template(CUSTOM_TEMPLATE), Ok(()));
+}
+
+#[test]
+fn test_custom_template_reusing_default_layout() {
+    let template = format!(
+        "{}\n#place(bottom, [Bank details are printed on the back.])",
+        include_str!("../templates/main.typ")
+    );
+
+    assert_eq!(validate_invoice_template(&template), Ok(()));
+}
+
+#[tokio::test]
+async fn test_invalid_custom_template_is_rejected() {
+    let invalid = "#import sys: inputs\n= #inputs.unknown_field";
+
+    let error = validate_invoice_template(invalid).unwrap_err();
+    assert!(
+        matches!(&error, InvoicingError::TemplateError(message) if message.contains("unknown_field")),
+        "Unexpected error: {error:?}"
+    );
+
+    let generator = TypstPdfGenerator::new().expect("Failed to create TypstPdfGenerator");
+    let mut invoice = create_minimal_invoice();
+    invoice.template = Some(invalid.to_string());
+    assert!(matches!(
+        generator.generate_pdf(&invoice).await,
+        Err(InvoicingError::TemplateError(_))
+    ));
+
+    // templates only see the built-in sources and assets
+    assert!(validate_invoice_template("#read(\"/etc/hostname\")").is_err());
+    assert!(validate_invoice_template("  ").is_err());
+    // PDF/A export requires a document date
+    assert!(validate_invoice_template("Invoice").is_err());
+}
+
 fn create_minimal_invoice() -> Invoice {
     let issue_date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
     let due_date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
@@ -97,6 +353,8 @@ fn create_minimal_invoice() -> Invoice {
 
     Invoice {
         lang: "en-US".to_string(),
+        translation_overrides: HashMap::new(),
+        template: None,
         organization: Organization {
             name: "Test Company".to_string(),
             logo_src: None,
@@ -141,6 +399,7 @@ fn create_minimal_invoice() -> Invoice {
             due_date,
             memo: None,
             payment_url: None,
+            usage_annex_url: None,
             flags: Flags {
                 show_payment_status: Some(true),
                 show_payment_info: Some(false),
@@ -152,6 +411,7 @@ fn create_minimal_invoice() -> Invoice {
             },
             discount: Money::from_major(0, eur),
             purchase_order: Some("some order".to_string()),
+            custom_fields: BTreeMap::new(),
             parent_invoice_number: None,
             parent_invoice_date: None,
         },
@@ -167,6 +427,7 @@ fn create_minimal_invoice() -> Invoice {
             is_prorated: false,
             sub_lines: vec![],
             group_label: None,
+            metadata: BTreeMap::new(),
         }],
         coupons: vec![],
         payment_status: Some(PaymentStatus::Unpaid),
@@ -175,6 +436,7 @@ fn create_minimal_invoice() -> Invoice {
         tax_breakdown: vec![TaxBreakdownItem {
             name: "VAT 20%".to_string(),
             rate: Decimal::from_str("20.0").unwrap(),
+            taxable_amount: Money::from_major(100, eur),
             amount: Money::from_major(20, eur),
             exemption_type: None,
         }],
@@ -199,6 +461,8 @@ fn create_full_invoice() -> Invoice {
 
     Invoice {
         lang: "en-US".to_string(),
+        translation_overrides: HashMap::new(),
+        template: None,
         organization: Organization {
             name: "Full Test Corp".to_string(),
             logo_src: None,
@@ -243,6 +507,9 @@ fn create_full_invoice() -> Invoice {
             due_date,
             memo: Some("Thank you for your business!".to_string()),
             payment_url: Some("https://pay.example.com/invoice/full-001".to_string()),
+            usage_annex_url: Some(
+                "https://example.com/files/v1/invoice/usage/full-001".to_string(),
+            ),
             flags: Flags {
                 show_payment_status: Some(true),
                 show_payment_info: Some(true),
@@ -254,6 +521,10 @@ fn create_full_invoice() -> Invoice {
             },
             discount: Money::from_major(0, eur),
             purchase_order: Some("some order".to_string()),
+            custom_fields: BTreeMap::from([
+                ("contract_id".to_string(), "CT-2025-001".to_string()),
+                ("cost_center".to_string(), "CC-42".to_string()),
+            ]),
             parent_invoice_number: None,
             parent_invoice_date: None,
         },
@@ -283,6 +554,7 @@ fn create_full_invoice() -> Invoice {
                     },
                 ],
                 group_label: None,
+                metadata: BTreeMap::from([("project".to_string(), "Apollo".to_string())]),
             },
             InvoiceLine {
                 name: "Support Package".to_string(),
@@ -296,6 +568,7 @@ fn create_full_invoice() -> Invoice {
                 is_prorated: false,
                 sub_lines: vec![],
                 group_label: None,
+                metadata: BTreeMap::new(),
             },
         ],
         coupons: vec![Coupon {
@@ -312,6 +585,7 @@ fn create_full_invoice() -> Invoice {
         tax_breakdown: vec![TaxBreakdownItem {
             name: "VAT 20%".to_string(),
             rate: Decimal::from_str("20.0").unwrap(),
+            taxable_amount: Money::from_major(1000, eur),
             amount: Money::from_major(200, eur),
             exemption_type: None,
         }],
@@ -409,6 +683,28 @@ async fn test_typst_credit_note_with_multiple_languages() {
     );
 }
 
+#[test]
+fn test_credit_note_translations_with_tenant_overrides() {
+    let mut credit_note = create_minimal_credit_note();
+    credit_note.lang = "es-ES".to_string();
+    credit_note.translation_overrides = HashMap::from([(
+        "related-invoice-value".to_string(),
+        "Factura {$invoice_number}".to_string(),
+    )]);
+
+    let content = TypstCreditNoteContent::from(&credit_note);
+    let translation = |key: &str| content.translations.get(key).cloned().ok();
+
+    assert_eq!(
+        translation("credit_note_title"),
+        Some("Factura rectificativa".into_value())
+    );
+    assert_eq!(
+        translation("related_invoice_value"),
+        Some(format!("Factura {}", credit_note.metadata.related_invoice_number).into_value())
+    );
+}
+
 #[tokio::test]
 async fn test_typst_credit_note_debt_cancellation_type() {
     let generator =
@@ -476,6 +772,7 @@ fn create_minimal_credit_note() -> CreditNote {
 
     CreditNote {
         lang: "en-US".to_string(),
+        translation_overrides: HashMap::new(),
         organization: cn::Organization {
             name: "Test Company".to_string(),
             logo_src: None,
@@ -545,6 +842,7 @@ fn create_minimal_credit_note() -> CreditNote {
         tax_breakdown: vec![cn::TaxBreakdownItem {
             name: "VAT 20%".to_string(),
             rate: Decimal::from_str("20.0").unwrap(),
+            taxable_amount: Money::from_major(100, eur),
             amount: Money::from_major(20, eur),
             exemption_type: None,
         }],
@@ -560,6 +858,7 @@ fn create_full_credit_note() -> CreditNote {
 
     CreditNote {
         lang: "en-US".to_string(),
+        translation_overrides: HashMap::new(),
         organization: cn::Organization {
             name: "Full Test Corp".to_string(),
             logo_src: None,
@@ -656,6 +955,7 @@ fn create_full_credit_note() -> CreditNote {
         tax_breakdown: vec![cn::TaxBreakdownItem {
             name: "VAT 20%".to_string(),
             rate: Decimal::from_str("20.0").unwrap(),
+            taxable_amount: Money::from_major(500, eur),
             amount: Money::from_major(100, eur),
             exemption_type: None,
         }],
@@ -0,0 +1,36 @@
+invoice-ready-title = Ihre Rechnung von {$company} ist verfügbar
+invoice-ready-heading = Ihre Rechnung ist verfügbar
+invoice-paid-title = Ihre Zahlungsbestätigung von {$company}
+invoice-paid-heading = Vielen Dank für Ihre Zahlung!
+invoice-label-subscription = Ihr Abonnement {$plan}
+invoice-label-services = Rechnung für Leistungen
+invoice-paid-label-subscription = Ihre Rechnung für {$plan} wurde erfolgreich bezahlt.
+invoice-paid-label-services = Die Rechnung für Leistungen wurde erfolgreich bezahlt.
+payment-thanks = Vielen Dank für Ihre Zahlung
+invoice-number = Rechnung Nr.
+invoice-date = Rechnungsdatum
+due-date = Fällig am
+amount-due = Fälliger Betrag
+amount-paid = Bezahlter Betrag
+view-and-pay = Ansehen und bezahlen
+
+quote-ready-title = Angebot {$number} von {$company}
+quote-ready-heading = Sie haben ein Angebot erhalten
+quote-number = Angebot Nr.
+valid-until = Gültig bis
+view-and-sign-quote = Angebot ansehen und unterzeichnen
+
+plan = Tarif
+trial-will-end-title = Ihre Testphase für {$plan} endet am {$date}
+trial-will-end-heading = Ihre Testphase endet bald
+trial-will-end-text = Ihre Testphase für {$plan} bei {$company} endet am {$date}.
+trial-ends-on = Testphase endet am
+trial-questions = Wenden Sie sich vor diesem Datum an {$company}, wenn Sie Fragen zu Ihrem Abonnement haben.
+
+renewal-upcoming-title = Ihr Abonnement {$plan} verlängert sich am {$date}
+renewal-upcoming-heading = Ihr Abonnement verlängert sich bald
+renewal-upcoming-text = Ihr Abonnement für {$plan} bei {$company} verlängert sich am {$date}.
+renews-on = Verlängerung am
+opt-out-before = Kündigung bis
+renewal-opt-out = Wenn Sie nicht verlängern möchten, wenden Sie sich vor Ablauf der Kündigungsfrist an {$company}.
+renewal-questions = Wenden Sie sich an {$company}, wenn Sie Fragen zu Ihrem Abonnement haben.
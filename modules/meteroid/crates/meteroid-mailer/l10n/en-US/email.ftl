@@ -0,0 +1,36 @@
+invoice-ready-title = Your {$company} invoice is ready
+invoice-ready-heading = Your invoice is ready
+invoice-paid-title = Your {$company} receipt
+invoice-paid-heading = Thank you for your payment !
+invoice-label-subscription = Your {$plan} subscription
+invoice-label-services = Invoice for services
+invoice-paid-label-subscription = Your {$plan} invoice was paid successfully.
+invoice-paid-label-services = Invoice for services was paid successfully.
+payment-thanks = Thank you for your payment
+invoice-number = Invoice n°
+invoice-date = Invoice date
+due-date = Due at
+amount-due = Amount due
+amount-paid = Amount paid
+view-and-pay = View and pay
+
+quote-ready-title = Quote {$number} from {$company}
+quote-ready-heading = You've received a quote
+quote-number = Quote n°
+valid-until = Valid until
+view-and-sign-quote = View and sign quote
+
+plan = Plan
+trial-will-end-title = Your {$plan} trial ends on {$date}
+trial-will-end-heading = Your trial is ending soon
+trial-will-end-text = Your trial of {$plan} with {$company} ends on {$date}.
+trial-ends-on = Trial ends on
+trial-questions = Reach out to {$company} before that date if you have any questions about your subscription.
+
+renewal-upcoming-title = Your {$plan} subscription renews on {$date}
+renewal-upcoming-heading = Your subscription renews soon
+renewal-upcoming-text = Your subscription to {$plan} with {$company} renews on {$date}.
+renews-on = Renews on
+opt-out-before = Opt out before
+renewal-opt-out = If you do not wish to renew, reach out to {$company} before the opt-out deadline.
+renewal-questions = Reach out to {$company} if you have any questions about your subscription.
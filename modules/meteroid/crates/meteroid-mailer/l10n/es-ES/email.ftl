@@ -0,0 +1,36 @@
+invoice-ready-title = Tu factura de {$company} está disponible
+invoice-ready-heading = Tu factura está disponible
+invoice-paid-title = Tu recibo de {$company}
+invoice-paid-heading = ¡Gracias por tu pago!
+invoice-label-subscription = Tu suscripción {$plan}
+invoice-label-services = Factura de servicios
+invoice-paid-label-subscription = Tu factura de {$plan} se ha pagado correctamente.
+invoice-paid-label-services = La factura de servicios se ha pagado correctamente.
+payment-thanks = Gracias por tu pago
+invoice-number = Factura n.º
+invoice-date = Fecha de la factura
+due-date = Vence el
+amount-due = Importe adeudado
+amount-paid = Importe pagado
+view-and-pay = Ver y pagar
+
+quote-ready-title = Presupuesto {$number} de {$company}
+quote-ready-heading = Has recibido un presupuesto
+quote-number = Presupuesto n.º
+valid-until = Válido hasta
+view-and-sign-quote = Ver y firmar el presupuesto
+
+plan = Plan
+trial-will-end-title = Tu prueba de {$plan} termina el {$date}
+trial-will-end-heading = Tu prueba termina pronto
+trial-will-end-text = Tu prueba de {$plan} con {$company} termina el {$date}.
+trial-ends-on = La prueba termina el
+trial-questions = Ponte en contacto con {$company} antes de esa fecha si tienes alguna pregunta sobre tu suscripción.
+
+renewal-upcoming-title = Tu suscripción {$plan} se renueva el {$date}
+renewal-upcoming-heading = Tu suscripción se renueva pronto
+renewal-upcoming-text = Tu suscripción a {$plan} con {$company} se renueva el {$date}.
+renews-on = Se renueva el
+opt-out-before = Cancelar antes del
+renewal-opt-out = Si no deseas renovar, ponte en contacto con {$company} antes de la fecha límite de cancelación.
+renewal-questions = Ponte en contacto con {$company} si tienes alguna pregunta sobre tu suscripción.
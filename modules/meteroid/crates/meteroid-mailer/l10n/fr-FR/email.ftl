@@ -0,0 +1,36 @@
+invoice-ready-title = Votre facture {$company} est disponible
+invoice-ready-heading = Votre facture est disponible
+invoice-paid-title = Votre reçu {$company}
+invoice-paid-heading = Merci pour votre paiement !
+invoice-label-subscription = Votre abonnement {$plan}
+invoice-label-services = Facture de prestations
+invoice-paid-label-subscription = Votre facture {$plan} a bien été payée.
+invoice-paid-label-services = La facture de prestations a bien été payée.
+payment-thanks = Merci pour votre paiement
+invoice-number = Facture n°
+invoice-date = Date de facture
+due-date = Échéance
+amount-due = Montant dû
+amount-paid = Montant payé
+view-and-pay = Consulter et payer
+
+quote-ready-title = Devis {$number} de {$company}
+quote-ready-heading = Vous avez reçu un devis
+quote-number = Devis n°
+valid-until = Valable jusqu'au
+view-and-sign-quote = Consulter et signer le devis
+
+plan = Offre
+trial-will-end-title = Votre essai {$plan} se termine le {$date}
+trial-will-end-heading = Votre essai se termine bientôt
+trial-will-end-text = Votre essai de {$plan} avec {$company} se termine le {$date}.
+trial-ends-on = Fin de l'essai
+trial-questions = Contactez {$company} avant cette date pour toute question sur votre abonnement.
+
+renewal-upcoming-title = Votre abonnement {$plan} est renouvelé le {$date}
+renewal-upcoming-heading = Votre abonnement est bientôt renouvelé
+renewal-upcoming-text = Votre abonnement à {$plan} avec {$company} est renouvelé le {$date}.
+renews-on = Renouvellement le
+opt-out-before = Résiliation avant le
+renewal-opt-out = Si vous ne souhaitez pas renouveler, contactez {$company} avant la date limite de résiliation.
+renewal-questions = Contactez {$company} pour toute question sur votre abonnement.
@@ -0,0 +1,36 @@
+invoice-ready-title = La tua fattura {$company} è disponibile
+invoice-ready-heading = La tua fattura è disponibile
+invoice-paid-title = La tua ricevuta {$company}
+invoice-paid-heading = Grazie per il tuo pagamento!
+invoice-label-subscription = Il tuo abbonamento {$plan}
+invoice-label-services = Fattura per servizi
+invoice-paid-label-subscription = La tua fattura {$plan} è stata pagata correttamente.
+invoice-paid-label-services = La fattura per servizi è stata pagata correttamente.
+payment-thanks = Grazie per il tuo pagamento
+invoice-number = Fattura n.
+invoice-date = Data fattura
+due-date = Scadenza
+amount-due = Importo dovuto
+amount-paid = Importo pagato
+view-and-pay = Visualizza e paga
+
+quote-ready-title = Preventivo {$number} da {$company}
+quote-ready-heading = Hai ricevuto un preventivo
+quote-number = Preventivo n.
+valid-until = Valido fino al
+view-and-sign-quote = Visualizza e firma il preventivo
+
+plan = Piano
+trial-will-end-title = La tua prova di {$plan} termina il {$date}
+trial-will-end-heading = La tua prova sta per terminare
+trial-will-end-text = La tua prova di {$plan} con {$company} termina il {$date}.
+trial-ends-on = Fine della prova
+trial-questions = Contatta {$company} prima di tale data per qualsiasi domanda sul tuo abbonamento.
+
+renewal-upcoming-title = Il tuo abbonamento {$plan} si rinnova il {$date}
+renewal-upcoming-heading = Il tuo abbonamento si rinnova a breve
+renewal-upcoming-text = Il tuo abbonamento a {$plan} con {$company} si rinnova il {$date}.
+renews-on = Rinnovo il
+opt-out-before = Disdetta entro il
+renewal-opt-out = Se non desideri rinnovare, contatta {$company} prima del termine di disdetta.
+renewal-questions = Contatta {$company} per qualsiasi domanda sul tuo abbonamento.
@@ -0,0 +1,36 @@
+invoice-ready-title = Je factuur van {$company} staat klaar
+invoice-ready-heading = Je factuur staat klaar
+invoice-paid-title = Je betalingsbewijs van {$company}
+invoice-paid-heading = Bedankt voor je betaling!
+invoice-label-subscription = Je {$plan}-abonnement
+invoice-label-services = Factuur voor diensten
+invoice-paid-label-subscription = Je factuur voor {$plan} is succesvol betaald.
+invoice-paid-label-services = De factuur voor diensten is succesvol betaald.
+payment-thanks = Bedankt voor je betaling
+invoice-number = Factuurnr.
+invoice-date = Factuurdatum
+due-date = Vervaldatum
+amount-due = Verschuldigd bedrag
+amount-paid = Betaald bedrag
+view-and-pay = Bekijken en betalen
+
+quote-ready-title = Offerte {$number} van {$company}
+quote-ready-heading = Je hebt een offerte ontvangen
+quote-number = Offertenr.
+valid-until = Geldig tot
+view-and-sign-quote = Offerte bekijken en ondertekenen
+
+plan = Abonnement
+trial-will-end-title = Je proefperiode van {$plan} eindigt op {$date}
+trial-will-end-heading = Je proefperiode eindigt binnenkort
+trial-will-end-text = Je proefperiode van {$plan} bij {$company} eindigt op {$date}.
+trial-ends-on = Proefperiode eindigt op
+trial-questions = Neem voor die datum contact op met {$company} als je vragen hebt over je abonnement.
+
+renewal-upcoming-title = Je {$plan}-abonnement wordt verlengd op {$date}
+renewal-upcoming-heading = Je abonnement wordt binnenkort verlengd
+renewal-upcoming-text = Je abonnement op {$plan} bij {$company} wordt verlengd op {$date}.
+renews-on = Verlenging op
+opt-out-before = Opzeggen voor
+renewal-opt-out = Als je niet wilt verlengen, neem dan voor de opzegtermijn contact op met {$company}.
+renewal-questions = Neem contact op met {$company} als je vragen hebt over je abonnement.
@@ -0,0 +1,36 @@
+invoice-ready-title = A sua fatura {$company} está disponível
+invoice-ready-heading = A sua fatura está disponível
+invoice-paid-title = O seu recibo {$company}
+invoice-paid-heading = Obrigado pelo seu pagamento!
+invoice-label-subscription = A sua subscrição {$plan}
+invoice-label-services = Fatura de serviços
+invoice-paid-label-subscription = A sua fatura {$plan} foi paga com sucesso.
+invoice-paid-label-services = A fatura de serviços foi paga com sucesso.
+payment-thanks = Obrigado pelo seu pagamento
+invoice-number = Fatura n.º
+invoice-date = Data da fatura
+due-date = Vence a
+amount-due = Montante em dívida
+amount-paid = Montante pago
+view-and-pay = Ver e pagar
+
+quote-ready-title = Orçamento {$number} de {$company}
+quote-ready-heading = Recebeu um orçamento
+quote-number = Orçamento n.º
+valid-until = Válido até
+view-and-sign-quote = Ver e assinar o orçamento
+
+plan = Plano
+trial-will-end-title = O seu período experimental de {$plan} termina a {$date}
+trial-will-end-heading = O seu período experimental termina em breve
+trial-will-end-text = O seu período experimental de {$plan} com {$company} termina a {$date}.
+trial-ends-on = Fim do período experimental
+trial-questions = Contacte {$company} antes dessa data se tiver alguma questão sobre a sua subscrição.
+
+renewal-upcoming-title = A sua subscrição {$plan} é renovada a {$date}
+renewal-upcoming-heading = A sua subscrição será renovada em breve
+renewal-upcoming-text = A sua subscrição de {$plan} com {$company} é renovada a {$date}.
+renews-on = Renovação a
+opt-out-before = Cancelar antes de
+renewal-opt-out = Se não pretender renovar, contacte {$company} antes do prazo de cancelamento.
+renewal-questions = Contacte {$company} se tiver alguma questão sobre a sua subscrição.
@@ -0,0 +1,63 @@
+use common_utils::lang::{match_language, primary_language};
+use fluent_static::{LanguageAware, MessageBundle};
+
+#[allow(clippy::all)]
+mod bundle {
+    use fluent_static::message_bundle;
+
+    #[message_bundle(
+        resources = [
+            ("l10n/en-US/email.ftl", "en-US"),
+            ("l10n/fr-FR/email.ftl", "fr-FR"),
+            ("l10n/de-DE/email.ftl", "de-DE"),
+            ("l10n/es-ES/email.ftl", "es-ES"),
+            ("l10n/it-IT/email.ftl", "it-IT"),
+            ("l10n/nl-NL/email.ftl", "nl-NL"),
+            ("l10n/pt-PT/email.ftl", "pt-PT"),
+        ],
+        default_language = "en-US")]
+    pub struct EmailL10n;
+}
+
+pub(crate) use bundle::EmailL10N;
+
+/// Translations for a language tag, matching on the primary language when the region
+/// differs ("de-AT" -> "de-DE"). Unknown languages fall back to en-US.
+pub(crate) fn translations(lang: &str) -> EmailL10N {
+    let mut bundle = match_language(EmailL10N::supported_language_ids(), lang, |id| **id)
+        .and_then(|id| EmailL10N::get(id))
+        .unwrap_or_default();
+
+    // the messages end up in subjects and plain html, bidi isolation marks would only be noise
+    bundle.set_use_isolating(false);
+    bundle
+}
+
+/// Value of the `lang` attribute of the email document ("de-AT" -> "de").
+pub(crate) fn html_lang(lang: &str) -> String {
+    let t = translations(lang);
+    primary_language(t.language_id()).to_string()
+}
+
+/// Headline of the invoice ready email, e.g. "Your Pro subscription".
+pub fn invoice_ready_label(lang: &str, plan_name: Option<&str>) -> String {
+    let t = translations(lang);
+    match plan_name {
+        Some(plan) => t.invoice_label_subscription(plan).to_string(),
+        None => t.invoice_label_services().to_string(),
+    }
+}
+
+/// Headline of the receipt email sent once an invoice is paid.
+pub fn invoice_paid_label(lang: &str, plan_name: Option<&str>) -> String {
+    let t = translations(lang);
+    match plan_name {
+        Some(plan) => t.invoice_paid_label_subscription(plan).to_string(),
+        None => t.invoice_paid_label_services().to_string(),
+    }
+}
+
+/// Headline of the receipt email sent with the invoice when it was paid at checkout.
+pub fn payment_thanks_label(lang: &str) -> String {
+    translations(lang).payment_thanks().to_string()
+}
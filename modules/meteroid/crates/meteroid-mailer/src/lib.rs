@@ -1,5 +1,6 @@
 pub mod config;
 pub mod errors;
+pub mod l10n;
 pub mod model;
 pub mod service;
 mod template;
@@ -146,6 +146,8 @@ pub struct InvoiceReady {
     pub currency: String,
     pub payment_url: String,
     pub company_name: String,
+    /// Language tag of the customer (e.g. "de-DE"). Unsupported languages fall back to english.
+    pub lang: String,
     pub logo_url: Option<String>,
     pub recipients: Vec<EmailRecipient>,
     pub attachment: EmailAttachment,
@@ -161,6 +163,7 @@ pub struct InvoicePaid {
     pub amount_paid: i64,
     pub currency: String,
     pub company_name: String,
+    pub lang: String,
     pub logo_url: Option<String>,
     pub recipients: Vec<EmailRecipient>,
     pub attachments: Vec<EmailAttachment>,
@@ -174,6 +177,7 @@ pub struct QuoteReady {
     pub quote_number: String,
     pub expires_at: Option<NaiveDate>,
     pub company_name: String,
+    pub lang: String,
     pub logo_url: Option<String>,
     pub recipients: Vec<EmailRecipient>,
     pub portal_url: String,
@@ -181,6 +185,40 @@ pub struct QuoteReady {
     pub account: String,
 }
 
+#[derive(Clone)]
+pub struct TrialWillEnd {
+    pub plan_name: String,
+    pub trial_end_date: NaiveDate,
+    pub company_name: String,
+    pub lang: String,
+    pub logo_url: Option<String>,
+    pub recipients: Vec<EmailRecipient>,
+    pub account: String,
+}
+
+#[derive(Clone)]
+pub struct RenewalUpcoming {
+    pub plan_name: String,
+    pub renewal_date: NaiveDate,
+    pub opt_out_deadline: Option<NaiveDate>,
+    pub company_name: String,
+    pub lang: String,
+    pub logo_url: Option<String>,
+    pub recipients: Vec<EmailRecipient>,
+    pub account: String,
+}
+
+#[derive(Clone)]
+pub struct InvoiceApprovalRequested {
+    pub tenant_name: String,
+    pub invoice_number: String,
+    pub customer_name: String,
+    pub total: i64,
+    pub currency: String,
+    pub invoice_url: String,
+    pub recipients: Vec<EmailRecipient>,
+}
+
 #[derive(Clone)]
 pub struct OrgInvite {
     pub org_name: String,
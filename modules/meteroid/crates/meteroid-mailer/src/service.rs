@@ -1,12 +1,13 @@
 use crate::config::MailerConfig;
 use crate::errors::MailerServiceError;
 use crate::model::{
-    Email, EmailValidationLink, InvoicePaid, InvoiceReady, OrgInvite, QuoteReady, RenderedEmail,
-    ResetPasswordLink,
+    Email, EmailValidationLink, InvoiceApprovalRequested, InvoicePaid, InvoiceReady, OrgInvite,
+    QuoteReady, RenderedEmail, RenewalUpcoming, ResetPasswordLink, TrialWillEnd,
 };
 use crate::template::{
-    EmailValidationLinkTemplate, InvoicePaidTemplate, InvoiceReadyTemplate, OrgInviteTemplate,
-    QuoteReadyTemplate, ResetPasswordLinkTemplate,
+    EmailValidationLinkTemplate, InvoiceApprovalRequestedTemplate, InvoicePaidTemplate,
+    InvoiceReadyTemplate, OrgInviteTemplate, QuoteReadyTemplate, RenewalUpcomingTemplate,
+    ResetPasswordLinkTemplate, TrialWillEndTemplate,
 };
 use async_trait::async_trait;
 use error_stack::Report;
@@ -49,7 +50,22 @@ pub trait MailerService: Send + Sync {
         data: QuoteReady,
     ) -> Result<RenderedEmail, Report<MailerServiceError>>;
 
+    async fn send_trial_will_end(
+        &self,
+        data: TrialWillEnd,
+    ) -> Result<RenderedEmail, Report<MailerServiceError>>;
+
+    async fn send_renewal_upcoming(
+        &self,
+        data: RenewalUpcoming,
+    ) -> Result<RenderedEmail, Report<MailerServiceError>>;
+
     async fn send_org_invite(&self, data: OrgInvite) -> Result<(), Report<MailerServiceError>>;
+
+    async fn send_invoice_approval_requested(
+        &self,
+        data: InvoiceApprovalRequested,
+    ) -> Result<(), Report<MailerServiceError>>;
 }
 
 pub struct LettreMailerService<T: AsyncTransport> {
@@ -227,6 +243,68 @@ where
         })
     }
 
+    async fn send_trial_will_end(
+        &self,
+        data: TrialWillEnd,
+    ) -> Result<RenderedEmail, Report<MailerServiceError>> {
+        let tpl = TrialWillEndTemplate::from(data.clone()).tpl;
+        let subject = tpl.title.clone();
+        let from = format!("{} <noreply@meteroid.com>", data.company_name);
+        let reply_to = Some("Meteroid <support@meteroid.com>".to_string());
+        let body_html = tpl.render_once().map_err(|e| Report::new(e.into()))?;
+        let recipients = data.recipients.clone();
+
+        let email = Email {
+            from: from.clone(),
+            reply_to: reply_to.clone(),
+            to: recipients.clone(),
+            subject: subject.clone(),
+            body_html: body_html.clone(),
+            attachments: vec![],
+        };
+        let delivered = self.send(email).await?;
+        Ok(RenderedEmail {
+            subject,
+            from,
+            reply_to,
+            recipients,
+            body_html,
+            attachment_filenames: vec![],
+            delivered,
+        })
+    }
+
+    async fn send_renewal_upcoming(
+        &self,
+        data: RenewalUpcoming,
+    ) -> Result<RenderedEmail, Report<MailerServiceError>> {
+        let tpl = RenewalUpcomingTemplate::from(data.clone()).tpl;
+        let subject = tpl.title.clone();
+        let from = format!("{} <noreply@meteroid.com>", data.company_name);
+        let reply_to = Some("Meteroid <support@meteroid.com>".to_string());
+        let body_html = tpl.render_once().map_err(|e| Report::new(e.into()))?;
+        let recipients = data.recipients.clone();
+
+        let email = Email {
+            from: from.clone(),
+            reply_to: reply_to.clone(),
+            to: recipients.clone(),
+            subject: subject.clone(),
+            body_html: body_html.clone(),
+            attachments: vec![],
+        };
+        let delivered = self.send(email).await?;
+        Ok(RenderedEmail {
+            subject,
+            from,
+            reply_to,
+            recipients,
+            body_html,
+            attachment_filenames: vec![],
+            delivered,
+        })
+    }
+
     async fn send_org_invite(&self, data: OrgInvite) -> Result<(), Report<MailerServiceError>> {
         let tpl = OrgInviteTemplate::from(data.clone()).tpl;
         let body_html = tpl.render_once().map_err(|e| Report::new(e.into()))?;
@@ -240,6 +318,24 @@ where
         };
         self.send(email).await.map(|_| ())
     }
+
+    async fn send_invoice_approval_requested(
+        &self,
+        data: InvoiceApprovalRequested,
+    ) -> Result<(), Report<MailerServiceError>> {
+        let tpl = InvoiceApprovalRequestedTemplate::from(data.clone()).tpl;
+        let subject = tpl.title.clone();
+        let body_html = tpl.render_once().map_err(|e| Report::new(e.into()))?;
+        let email = Email {
+            from: self.config.from.clone(),
+            reply_to: Some("No Reply <no-reply@meteroid.com>".into()),
+            to: data.recipients,
+            subject,
+            body_html,
+            attachments: vec![],
+        };
+        self.send(email).await.map(|_| ())
+    }
 }
 
 pub fn mailer_service(cfg: MailerConfig) -> Arc<dyn MailerService> {
@@ -1,5 +1,7 @@
+use crate::l10n::{EmailL10N, html_lang, translations};
 use crate::model::{
-    EmailValidationLink, InvoicePaid, InvoiceReady, OrgInvite, QuoteReady, ResetPasswordLink,
+    EmailValidationLink, InvoiceApprovalRequested, InvoicePaid, InvoiceReady, OrgInvite,
+    QuoteReady, RenewalUpcoming, ResetPasswordLink, TrialWillEnd,
 };
 use sailfish::TemplateSimple;
 use secrecy::ExposeSecret;
@@ -124,6 +126,7 @@ impl FooterTemplate {
 #[derive(TemplateSimple)]
 #[template(path = "invoice_ready.stpl")]
 pub struct InvoiceReadyContent {
+    pub t: EmailL10N,
     pub invoice_number: String,
     pub invoice_date: String,
     pub invoice_due_date: String,
@@ -143,7 +146,12 @@ impl From<InvoiceReady> for InvoiceReadyTemplate {
             logo_url: data.logo_url,
         };
         let footer = FooterTemplate::new();
+        let t = translations(&data.lang);
+        let title = t
+            .invoice_ready_title(header.company_name.as_str())
+            .to_string();
         let content = InvoiceReadyContent {
+            t,
             invoice_number: data.invoice_number,
             invoice_date: format_date(data.invoice_date),
             invoice_due_date: format_date(data.invoice_due_date),
@@ -153,8 +161,8 @@ impl From<InvoiceReady> for InvoiceReadyTemplate {
         };
         InvoiceReadyTemplate {
             tpl: LayoutTemplate {
-                lang: "en".to_string(),
-                title: format!("Your {} invoice is ready", header.company_name),
+                lang: html_lang(&data.lang),
+                title,
                 header,
                 footer,
                 content,
@@ -166,6 +174,7 @@ impl From<InvoiceReady> for InvoiceReadyTemplate {
 #[derive(TemplateSimple)]
 #[template(path = "invoice_receipt.stpl")]
 pub struct InvoicePaidContent {
+    pub t: EmailL10N,
     pub invoice_number: String,
     pub invoice_date: String,
     pub invoice_due_date: String,
@@ -184,7 +193,12 @@ impl From<InvoicePaid> for InvoicePaidTemplate {
             logo_url: data.logo_url,
         };
         let footer = FooterTemplate::new();
+        let t = translations(&data.lang);
+        let title = t
+            .invoice_paid_title(header.company_name.as_str())
+            .to_string();
         let content = InvoicePaidContent {
+            t,
             invoice_number: data.invoice_number,
             invoice_date: format_date(data.invoice_date),
             invoice_due_date: format_date(data.invoice_due_date),
@@ -193,8 +207,8 @@ impl From<InvoicePaid> for InvoicePaidTemplate {
         };
         InvoicePaidTemplate {
             tpl: LayoutTemplate {
-                lang: "en".to_string(),
-                title: format!("Your {} receipt", header.company_name),
+                lang: html_lang(&data.lang),
+                title,
                 header,
                 footer,
                 content,
@@ -206,6 +220,7 @@ impl From<InvoicePaid> for InvoicePaidTemplate {
 #[derive(TemplateSimple)]
 #[template(path = "quote_ready.stpl")]
 pub struct QuoteReadyContent {
+    pub t: EmailL10N,
     pub quote_number: String,
     pub expires_at: Option<String>,
     pub portal_url: String,
@@ -223,7 +238,12 @@ impl From<QuoteReady> for QuoteReadyTemplate {
             logo_url: data.logo_url,
         };
         let footer = FooterTemplate::new();
+        let t = translations(&data.lang);
+        let title = t
+            .quote_ready_title(data.quote_number.as_str(), data.company_name.as_str())
+            .to_string();
         let content = QuoteReadyContent {
+            t,
             quote_number: data.quote_number,
             expires_at: data.expires_at.map(format_date),
             portal_url: data.portal_url,
@@ -231,8 +251,98 @@ impl From<QuoteReady> for QuoteReadyTemplate {
         };
         QuoteReadyTemplate {
             tpl: LayoutTemplate {
-                lang: "en".to_string(),
-                title: format!("Quote {} from {}", content.quote_number, data.company_name),
+                lang: html_lang(&data.lang),
+                title,
+                header,
+                footer,
+                content,
+            },
+        }
+    }
+}
+
+#[derive(TemplateSimple)]
+#[template(path = "trial_will_end.stpl")]
+pub struct TrialWillEndContent {
+    pub t: EmailL10N,
+    pub company_name: String,
+    pub plan_name: String,
+    pub trial_end_date: String,
+}
+
+pub struct TrialWillEndTemplate {
+    pub tpl: LayoutTemplate<TrialWillEndContent>,
+}
+
+impl From<TrialWillEnd> for TrialWillEndTemplate {
+    fn from(data: TrialWillEnd) -> Self {
+        let header = HeaderTemplate {
+            company_name: data.company_name.clone(),
+            logo_url: data.logo_url,
+        };
+        let footer = FooterTemplate::new();
+        let content = TrialWillEndContent {
+            t: translations(&data.lang),
+            company_name: data.company_name,
+            plan_name: data.plan_name,
+            trial_end_date: format_date(data.trial_end_date),
+        };
+        TrialWillEndTemplate {
+            tpl: LayoutTemplate {
+                lang: html_lang(&data.lang),
+                title: content
+                    .t
+                    .trial_will_end_title(
+                        content.plan_name.as_str(),
+                        content.trial_end_date.as_str(),
+                    )
+                    .to_string(),
+                header,
+                footer,
+                content,
+            },
+        }
+    }
+}
+
+#[derive(TemplateSimple)]
+#[template(path = "renewal_upcoming.stpl")]
+pub struct RenewalUpcomingContent {
+    pub t: EmailL10N,
+    pub company_name: String,
+    pub plan_name: String,
+    pub renewal_date: String,
+    pub opt_out_deadline: Option<String>,
+}
+
+pub struct RenewalUpcomingTemplate {
+    pub tpl: LayoutTemplate<RenewalUpcomingContent>,
+}
+
+impl From<RenewalUpcoming> for RenewalUpcomingTemplate {
+    fn from(data: RenewalUpcoming) -> Self {
+        let header = HeaderTemplate {
+            company_name: data.company_name.clone(),
+            logo_url: data.logo_url,
+        };
+        let footer = FooterTemplate::new();
+        let content = RenewalUpcomingContent {
+            t: translations(&data.lang),
+            company_name: data.company_name,
+            plan_name: data.plan_name,
+            renewal_date: format_date(data.renewal_date),
+            opt_out_deadline: data.opt_out_deadline.map(format_date),
+        };
+        RenewalUpcomingTemplate {
+            tpl: LayoutTemplate {
+                lang: html_lang(&data.lang),
+                title: content
+                    .t
+                    .renewal_upcoming_title(
+                        content.plan_name.as_str(),
+                        content.renewal_date.as_str(),
+                    )
+                    .to_string(),
                 header,
                 footer,
                 content,
@@ -281,10 +391,59 @@ impl From<OrgInvite> for OrgInviteTemplate {
     }
 }
 
+#[derive(TemplateSimple)]
+#[template(path = "invoice_approval_requested.stpl")]
+pub struct InvoiceApprovalRequestedContent {
+    pub tenant_name: String,
+    pub invoice_number: String,
+    pub customer_name: String,
+    pub total: String,
+    pub invoice_url: String,
+}
+
+pub struct InvoiceApprovalRequestedTemplate {
+    pub tpl: LayoutTemplate<InvoiceApprovalRequestedContent>,
+}
+
+impl From<InvoiceApprovalRequested> for InvoiceApprovalRequestedTemplate {
+    fn from(data: InvoiceApprovalRequested) -> Self {
+        let header = HeaderTemplate {
+            company_name: data.tenant_name.clone(),
+            logo_url: Some(METEROID_WORDMARK_URL.to_string()),
+        };
+        let footer = FooterTemplate::new();
+        let content = InvoiceApprovalRequestedContent {
+            tenant_name: data.tenant_name,
+            invoice_number: data.invoice_number,
+            customer_name: data.customer_name,
+            total: format_currency(data.total, &data.currency),
+            invoice_url: data.invoice_url,
+        };
+        InvoiceApprovalRequestedTemplate {
+            tpl: LayoutTemplate {
+                lang: "en".to_string(),
+                title: format!(
+                    "Invoice {} is awaiting your approval",
+                    content.invoice_number
+                ),
+                header,
+                footer,
+                content,
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::FooterTemplate;
-    use chrono::Datelike;
+    use super::{
+        FooterTemplate, InvoiceApprovalRequestedTemplate, InvoiceReadyTemplate,
+        TrialWillEndTemplate,
+    };
+    use crate::model::{
+        EmailAttachment, EmailAttachmentType, InvoiceApprovalRequested, InvoiceReady, TrialWillEnd,
+    };
+    use chrono::{Datelike, NaiveDate};
     use sailfish::TemplateSimple;
 
     #[test]
@@ -297,4 +456,75 @@ mod tests {
             "footer should contain '{expected}'"
         );
     }
+
+    #[test]
+    fn test_invoice_ready_follows_customer_language() {
+        let data = InvoiceReady {
+            invoice_number: "INV-001".to_string(),
+            invoice_date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            invoice_due_date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            label: crate::l10n::invoice_ready_label("de-AT", Some("Pro")),
+            amount_due: 1000,
+            currency: "EUR".to_string(),
+            payment_url: "https://example.com/pay".to_string(),
+            company_name: "Acme".to_string(),
+            lang: "de-AT".to_string(),
+            logo_url: None,
+            recipients: vec![],
+            attachment: EmailAttachment {
+                filename: "invoice.pdf".to_string(),
+                content: vec![],
+                type_: EmailAttachmentType::Pdf,
+            },
+            account: "acme".to_string(),
+        };
+
+        let tpl = InvoiceReadyTemplate::from(data).tpl;
+        assert_eq!(tpl.title, "Ihre Rechnung von Acme ist verfügbar");
+
+        let html = tpl.render_once().unwrap();
+        assert!(html.contains(r#"<html lang="de">"#));
+        assert!(html.contains("Ihr Abonnement Pro"));
+        assert!(html.contains("Ansehen und bezahlen"));
+    }
+
+    #[test]
+    fn test_unsupported_language_falls_back_to_english() {
+        let data = TrialWillEnd {
+            plan_name: "Pro".to_string(),
+            trial_end_date: NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            company_name: "Acme".to_string(),
+            lang: "ja-JP".to_string(),
+            logo_url: None,
+            recipients: vec![],
+            account: "acme".to_string(),
+        };
+
+        let tpl = TrialWillEndTemplate::from(data).tpl;
+        assert_eq!(tpl.title, "Your Pro trial ends on 2025-03-01");
+
+        let html = tpl.render_once().unwrap();
+        assert!(html.contains(r#"<html lang="en">"#));
+        assert!(html.contains("Your trial of Pro with Acme ends on 2025-03-01."));
+    }
+
+    #[test]
+    fn test_invoice_approval_requested_links_to_invoice() {
+        let data = InvoiceApprovalRequested {
+            tenant_name: "Production".to_string(),
+            invoice_number: "INV-042".to_string(),
+            customer_name: "Globex".to_string(),
+            total: 12_500_000,
+            currency: "USD".to_string(),
+            invoice_url: "https://app.example.com/acme/prod/invoices/inv_1".to_string(),
+            recipients: vec![],
+        };
+
+        let tpl = InvoiceApprovalRequestedTemplate::from(data).tpl;
+        assert_eq!(tpl.title, "Invoice INV-042 is awaiting your approval");
+
+        let html = tpl.render_once().unwrap();
+        assert!(html.contains("https://app.example.com/acme/prod/invoices/inv_1"));
+        assert!(html.contains("Globex"));
+    }
 }
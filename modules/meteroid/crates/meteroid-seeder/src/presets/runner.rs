@@ -215,6 +215,10 @@ pub async fn run_preset(
             custom_taxes: vec![],
             is_tax_exempt: false,
             connected_account_id: None,
+            custom_attributes: Default::default(),
+            invoice_language: None,
+            invoice_custom_fields: Default::default(),
+            invoice_approval_required: false,
         });
     }
 
@@ -264,7 +268,8 @@ pub async fn run_preset(
             purchase_order: None,
             backdate_invoices: true,
             skip_checkout_session: false,
-            skip_past_invoices: false,
+            past_period_billing: store_domain::PastPeriodBilling::Invoice,
+            instance: None,
         };
 
         subscriptions_to_create.push(store_domain::CreateSubscription {
@@ -212,6 +212,7 @@ impl PriceComponent {
                     cadence,
                     currency: currency.to_string(),
                     pricing,
+                    variants: vec![],
                 })
             })
             .collect();
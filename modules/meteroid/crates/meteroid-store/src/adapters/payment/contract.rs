@@ -186,6 +186,12 @@ fn make_test_customer(connector: &Connector) -> crate::domain::Customer {
         vat_number_vies_check: None,
         connected_account_id: None,
         conn_meta: None,
+        custom_attributes: Default::default(),
+        parent_customer_id: None,
+        pool_usage_with_parent: false,
+        invoice_language: None,
+        invoice_custom_fields: Default::default(),
+        invoice_approval_required: false,
     }
 }
 
@@ -0,0 +1,343 @@
+use chrono::NaiveDateTime;
+use error_stack::Report;
+use serde::{Deserialize, Serialize};
+
+use super::coupons::CouponDiscount;
+use super::entitlements::EntitlementValue;
+use super::enums::{BillingPeriodEnum, BillingType, PlanTypeEnum};
+use super::price_components::{DowngradePolicy, UpgradePolicy};
+use super::prices::{PriceVariant, Pricing, UsageModel};
+use crate::errors::StoreError;
+
+/// Declarative description of a tenant's catalog.
+///
+/// Entries are keyed by name (product families, products, add-ons, plans) or code (features,
+/// coupons, billable metrics) instead of ids, so the same document can be applied to several
+/// tenants. Applying a document creates or updates the entries it lists; entries missing from
+/// the document are left untouched unless the document is applied with pruning.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogDocument {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<CatalogFeature>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub product_families: Vec<CatalogProductFamily>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coupons: Vec<CatalogCoupon>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatalogFormat {
+    Yaml,
+    Json,
+}
+
+impl CatalogDocument {
+    pub fn parse(input: &str, format: CatalogFormat) -> Result<Self, Report<StoreError>> {
+        let parsed = match format {
+            CatalogFormat::Yaml => serde_yaml::from_str(input).map_err(|e| e.to_string()),
+            CatalogFormat::Json => serde_json::from_str(input).map_err(|e| e.to_string()),
+        };
+
+        parsed.map_err(|e| {
+            Report::new(StoreError::InvalidArgument(format!(
+                "Invalid catalog document: {e}"
+            )))
+        })
+    }
+
+    pub fn render(&self, format: CatalogFormat) -> Result<String, Report<StoreError>> {
+        match format {
+            CatalogFormat::Yaml => serde_yaml::to_string(self).map_err(|e| {
+                Report::new(StoreError::InvalidArgument(format!(
+                    "Failed to render catalog: {e}"
+                )))
+            }),
+            CatalogFormat::Json => serde_json::to_string_pretty(self).map_err(|e| {
+                Report::new(StoreError::SerdeError(
+                    "Failed to render catalog".to_string(),
+                    e,
+                ))
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogFeature {
+    pub code: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Billable metric code of a metered feature. Boolean feature when absent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metric: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogProductFamily {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub products: Vec<CatalogProduct>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_ons: Vec<CatalogAddOn>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plans: Vec<CatalogPlan>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogProduct {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub fee: CatalogFeeStructure,
+}
+
+/// `FeeStructure` with billable metrics referenced by code.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum CatalogFeeStructure {
+    Rate {},
+    Slot {
+        unit_name: String,
+        upgrade_policy: UpgradePolicy,
+        downgrade_policy: DowngradePolicy,
+    },
+    Capacity {
+        metric: String,
+    },
+    Usage {
+        metric: String,
+        model: UsageModel,
+    },
+    ExtraRecurring {
+        billing_type: BillingType,
+    },
+    OneTime {},
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogAddOn {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Name of a product of the same family.
+    pub product: String,
+    pub currency: String,
+    pub cadence: BillingPeriodEnum,
+    pub pricing: Pricing,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<PriceVariant>,
+    #[serde(default)]
+    pub self_serviceable: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_instances_per_subscription: Option<i32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<CatalogEntitlement>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogPlan {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub plan_type: PlanTypeEnum,
+    pub currency: String,
+    #[serde(default)]
+    pub net_terms: i32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period_start_day: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trial: Option<CatalogTrial>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub components: Vec<CatalogComponent>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub add_ons: Vec<CatalogPlanAddOn>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entitlements: Vec<CatalogEntitlement>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogTrial {
+    pub duration_days: u32,
+    #[serde(default)]
+    pub is_free: bool,
+    /// Name of the plan of the same family resolved during the trial.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trialing_plan: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogComponent {
+    pub name: String,
+    /// Name of a product of the same family.
+    pub product: String,
+    pub prices: Vec<CatalogPrice>,
+}
+
+/// A price of a plan component, in the plan currency.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogPrice {
+    pub cadence: BillingPeriodEnum,
+    pub pricing: Pricing,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<PriceVariant>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogPlanAddOn {
+    /// Name of an add-on of the same family.
+    pub add_on: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub self_serviceable: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_instances_per_subscription: Option<i32>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogEntitlement {
+    /// Feature code.
+    pub feature: String,
+    pub value: EntitlementValue,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct CatalogCoupon {
+    pub code: String,
+    pub description: String,
+    pub discount: CouponDiscount,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<NaiveDateTime>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redemption_limit: Option<i32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recurring_value: Option<i32>,
+    #[serde(default)]
+    pub reusable: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatalogEntity {
+    Feature,
+    ProductFamily,
+    Product,
+    AddOn,
+    Plan,
+    Coupon,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CatalogAction {
+    Create,
+    Update,
+    Unchanged,
+    /// The entry differs on a field that cannot be changed in place. Nothing is applied while
+    /// the document contains conflicts.
+    Conflict,
+    /// The entry is missing from a document applied with pruning.
+    Archive,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CatalogChange {
+    pub entity: CatalogEntity,
+    /// Document key of the entry, prefixed by its product family for family-scoped entries.
+    pub key: String,
+    pub action: CatalogAction,
+    /// Fields that differ for updates, or the reason of a conflict.
+    pub details: Vec<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CatalogDiff {
+    pub changes: Vec<CatalogChange>,
+}
+
+impl CatalogDiff {
+    pub fn has_conflicts(&self) -> bool {
+        self.changes
+            .iter()
+            .any(|c| c.action == CatalogAction::Conflict)
+    }
+
+    pub fn has_changes(&self) -> bool {
+        self.changes.iter().any(|c| {
+            matches!(
+                c.action,
+                CatalogAction::Create | CatalogAction::Update | CatalogAction::Archive
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    const YAML: &str = r#"
+features:
+  - code: seats
+    name: Seats
+product_families:
+  - name: Default
+    products:
+      - name: Platform fee
+        fee:
+          type: Rate
+    plans:
+      - name: Pro
+        plan_type: Standard
+        currency: EUR
+        net_terms: 30
+        components:
+          - name: Platform fee
+            product: Platform fee
+            prices:
+              - cadence: Monthly
+                pricing:
+                  type: Rate
+                  rate: "49.00"
+        entitlements:
+          - feature: seats
+            value:
+              type: boolean
+              enabled: true
+"#;
+
+    #[test]
+    fn parses_yaml_and_round_trips_through_json() {
+        let doc = CatalogDocument::parse(YAML, CatalogFormat::Yaml).unwrap();
+
+        let plan = &doc.product_families[0].plans[0];
+        assert_eq!(plan.plan_type, PlanTypeEnum::Standard);
+        assert_eq!(
+            plan.components[0].prices[0].pricing,
+            Pricing::Rate { rate: dec!(49) }
+        );
+
+        let json = doc.render(CatalogFormat::Json).unwrap();
+        let reparsed = CatalogDocument::parse(&json, CatalogFormat::Json).unwrap();
+        assert_eq!(doc, reparsed);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        let err = CatalogDocument::parse("plans: []", CatalogFormat::Yaml).unwrap_err();
+        assert!(matches!(
+            err.current_context(),
+            StoreError::InvalidArgument(_)
+        ));
+    }
+}
@@ -9,7 +9,9 @@ use rust_decimal::Decimal;
 
 use crate::domain::subscription_add_ons::CreateSubscriptionAddOns;
 use crate::domain::subscription_components::CreateSubscriptionComponents;
-use crate::domain::subscriptions::{CreateSubscription, PaymentMethodsConfig, SubscriptionNew};
+use crate::domain::subscriptions::{
+    CreateSubscription, PastPeriodBilling, PaymentMethodsConfig, SubscriptionNew,
+};
 use crate::domain::{
     CreateSubscriptionCoupon, CreateSubscriptionCoupons, SubscriptionActivationCondition,
 };
@@ -115,7 +117,8 @@ impl CheckoutSession {
             purchase_order: self.purchase_order.clone(),
             backdate_invoices: false,
             skip_checkout_session: true,
-            skip_past_invoices: false,
+            past_period_billing: PastPeriodBilling::Invoice,
+            instance: None,
         }
     }
 
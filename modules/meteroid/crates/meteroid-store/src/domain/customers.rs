@@ -3,6 +3,7 @@ use crate::domain::enums::PaymentMethodTypeEnum;
 use crate::errors::StoreError;
 use crate::errors::StoreErrorReport;
 use crate::json_value_serde;
+use crate::utils::custom_fields::CustomFields;
 use chrono::NaiveDateTime;
 use common_domain::country::CountryCode;
 use common_domain::ids::{
@@ -16,6 +17,7 @@ use error_stack::Report;
 use o2o::o2o;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -73,6 +75,22 @@ pub struct Customer {
     /// Evidence returned by VIES with the last definitive answer.
     #[from(~.and_then(|v| serde_json::from_value(v).ok()))]
     pub vat_number_vies_check: Option<meteroid_tax::ViesCheckData>,
+    /// Free-form key/value attributes, e.g. used to select price variants.
+    #[from(serde_json::from_value(~).unwrap_or_default())]
+    pub custom_attributes: BTreeMap<String, String>,
+    /// Parent account billed for this customer's subscriptions.
+    pub parent_customer_id: Option<CustomerId>,
+    /// Whether this customer's usage is pooled with the rest of the hierarchy for tiering.
+    pub pool_usage_with_parent: bool,
+    /// Language tag (e.g. "de-DE") of the invoices and emails sent to this customer.
+    /// Defaults to the language of the invoicing entity's country.
+    pub invoice_language: Option<String>,
+    /// Custom fields set on the invoices of this customer, unless overridden by the subscription.
+    #[from(serde_json::from_value(~).unwrap_or_default())]
+    pub invoice_custom_fields: CustomFields,
+    /// Whether the invoices of this customer wait for approval before being finalized,
+    /// whatever their amount.
+    pub invoice_approval_required: bool,
 }
 
 impl Customer {
@@ -122,6 +140,10 @@ pub struct CustomerNew {
     pub custom_taxes: Vec<CustomerCustomTax>,
     pub is_tax_exempt: bool,
     pub connected_account_id: Option<ConnectedAccountId>,
+    pub custom_attributes: BTreeMap<String, String>,
+    pub invoice_language: Option<String>,
+    pub invoice_custom_fields: CustomFields,
+    pub invoice_approval_required: bool,
 }
 
 impl CustomerNew {
@@ -204,6 +226,18 @@ impl TryInto<CustomerRowNew> for CustomerNewWrapper {
             vat_number_format_valid: self.vat_number_format_valid,
             connected_account_id: self.inner.connected_account_id,
             vat_number_validation_status,
+            custom_attributes: serde_json::to_value(&self.inner.custom_attributes).map_err(
+                |e| StoreError::SerdeError("Failed to serialize custom_attributes".to_string(), e),
+            )?,
+            invoice_language: self.inner.invoice_language,
+            invoice_custom_fields: serde_json::to_value(&self.inner.invoice_custom_fields)
+                .map_err(|e| {
+                    StoreError::SerdeError(
+                        "Failed to serialize invoice_custom_fields".to_string(),
+                        e,
+                    )
+                })?,
+            invoice_approval_required: self.inner.invoice_approval_required,
         })
     }
 }
@@ -234,6 +268,16 @@ pub struct CustomerPatch {
     pub current_payment_method_id: Option<Option<CustomerPaymentMethodId>>,
     pub is_tax_exempt: Option<bool>,
     pub connected_account_id: Option<Option<ConnectedAccountId>>,
+    #[map(~.map(|v| serde_json::to_value(&v)).transpose().map_err(| e | {
+    StoreError::SerdeError("Failed to serialize custom_attributes".to_string(), e)
+    })?)]
+    pub custom_attributes: Option<BTreeMap<String, String>>,
+    pub invoice_language: Option<Option<String>>,
+    #[map(~.map(|v| serde_json::to_value(&v)).transpose().map_err(| e | {
+    StoreError::SerdeError("Failed to serialize invoice_custom_fields".to_string(), e)
+    })?)]
+    pub invoice_custom_fields: Option<CustomFields>,
+    pub invoice_approval_required: Option<bool>,
 }
 
 impl CustomerPatch {
@@ -315,6 +359,10 @@ pub struct CustomerUpdate {
     pub vat_number: Option<String>,
     pub custom_taxes: Vec<CustomerCustomTax>,
     pub is_tax_exempt: bool,
+    pub custom_attributes: BTreeMap<String, String>,
+    pub invoice_language: Option<String>,
+    pub invoice_custom_fields: CustomFields,
+    pub invoice_approval_required: bool,
 }
 
 impl CustomerUpdate {
@@ -373,6 +421,12 @@ mod tests {
             vat_number_validation_status: None,
             vat_number_checked_at: None,
             vat_number_vies_check: None,
+            custom_attributes: BTreeMap::new(),
+            parent_customer_id: None,
+            pool_usage_with_parent: false,
+            invoice_language: None,
+            invoice_custom_fields: BTreeMap::new(),
+            invoice_approval_required: false,
         }
     }
 
@@ -8,6 +8,7 @@ use uuid::Uuid;
 
 pub use common_domain::actor::{Actor, ActorType};
 
+use crate::domain::SubscriptionStatusEnum;
 use crate::domain::outbox_event::OutboxEvent;
 
 #[derive(Debug, Clone, Copy, Display, EnumString, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -173,6 +174,15 @@ pub enum ActivityType {
     #[strum(serialize = "invoice.consolidated")]
     #[serde(rename = "invoice.consolidated")]
     InvoiceConsolidated,
+    #[strum(serialize = "invoice.approval_requested")]
+    #[serde(rename = "invoice.approval_requested")]
+    InvoiceApprovalRequested,
+    #[strum(serialize = "invoice.approved")]
+    #[serde(rename = "invoice.approved")]
+    InvoiceApproved,
+    #[strum(serialize = "invoice.rejected")]
+    #[serde(rename = "invoice.rejected")]
+    InvoiceRejected,
 
     #[strum(serialize = "credit_note.created")]
     #[serde(rename = "credit_note.created")]
@@ -267,6 +277,18 @@ pub enum ActivityType {
     #[strum(serialize = "subscription.paused")]
     #[serde(rename = "subscription.paused")]
     SubscriptionPaused,
+    #[strum(serialize = "subscription.pause_scheduled")]
+    #[serde(rename = "subscription.pause_scheduled")]
+    SubscriptionPauseScheduled,
+    #[strum(serialize = "subscription.resumed")]
+    #[serde(rename = "subscription.resumed")]
+    SubscriptionResumed,
+    #[strum(serialize = "subscription.resume_scheduled")]
+    #[serde(rename = "subscription.resume_scheduled")]
+    SubscriptionResumeScheduled,
+    #[strum(serialize = "subscription.reactivated")]
+    #[serde(rename = "subscription.reactivated")]
+    SubscriptionReactivated,
     #[strum(serialize = "subscription.cancellation_scheduled")]
     #[serde(rename = "subscription.cancellation_scheduled")]
     SubscriptionCancellationScheduled,
@@ -285,6 +307,45 @@ pub enum ActivityType {
     #[strum(serialize = "subscription.plan_changed")]
     #[serde(rename = "subscription.plan_changed")]
     SubscriptionPlanChanged,
+    #[strum(serialize = "subscription.billing_anchor_changed")]
+    #[serde(rename = "subscription.billing_anchor_changed")]
+    SubscriptionBillingAnchorChanged,
+    #[strum(serialize = "subscription.trial_extended")]
+    #[serde(rename = "subscription.trial_extended")]
+    SubscriptionTrialExtended,
+    #[strum(serialize = "subscription.trial_ended_early")]
+    #[serde(rename = "subscription.trial_ended_early")]
+    SubscriptionTrialEndedEarly,
+    #[strum(serialize = "subscription.trialing_plan_changed")]
+    #[serde(rename = "subscription.trialing_plan_changed")]
+    SubscriptionTrialingPlanChanged,
+    #[strum(serialize = "subscription.trial_will_end")]
+    #[serde(rename = "subscription.trial_will_end")]
+    SubscriptionTrialWillEnd,
+    #[strum(serialize = "subscription.trial_ended")]
+    #[serde(rename = "subscription.trial_ended")]
+    SubscriptionTrialEnded,
+    #[strum(serialize = "subscription.suspended")]
+    #[serde(rename = "subscription.suspended")]
+    SubscriptionSuspended,
+    #[strum(serialize = "subscription.status_changed")]
+    #[serde(rename = "subscription.status_changed")]
+    SubscriptionStatusChanged,
+    #[strum(serialize = "subscription.renewed")]
+    #[serde(rename = "subscription.renewed")]
+    SubscriptionRenewed,
+    #[strum(serialize = "subscription.renewal_terms_updated")]
+    #[serde(rename = "subscription.renewal_terms_updated")]
+    SubscriptionRenewalTermsUpdated,
+    #[strum(serialize = "subscription.renewal_opted_out")]
+    #[serde(rename = "subscription.renewal_opted_out")]
+    SubscriptionRenewalOptedOut,
+    #[strum(serialize = "subscription.renewal_upcoming")]
+    #[serde(rename = "subscription.renewal_upcoming")]
+    SubscriptionRenewalUpcoming,
+    #[strum(serialize = "subscription.term_renewed")]
+    #[serde(rename = "subscription.term_renewed")]
+    SubscriptionTermRenewed,
     #[strum(serialize = "subscription.amendment_scheduled")]
     #[serde(rename = "subscription.amendment_scheduled")]
     SubscriptionAmendmentScheduled,
@@ -509,6 +570,133 @@ impl From<&OutboxEvent> for Option<Activity> {
                 e.subscription_id.as_uuid(),
             )
             .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionPaused(e) => Activity::new(
+                ActivityType::SubscriptionPaused,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionResumed(e) => Activity::new(
+                ActivityType::SubscriptionResumed,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionReactivated(e) => Activity::new(
+                ActivityType::SubscriptionReactivated,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionTrialWillEnd(e) => Activity::new(
+                ActivityType::SubscriptionTrialWillEnd,
+                EntityType::Subscription,
+                e.subscription.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.subscription.customer_id)
+            .with_metadata(serde_json::json!({
+                "trial_end_date": e.trial_end_date.to_string(),
+            })),
+            OutboxEvent::SubscriptionTrialEnded(e) => Activity::new(
+                ActivityType::SubscriptionTrialEnded,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionCancellationScheduled(e) => {
+                let mut a = Activity::new(
+                    ActivityType::SubscriptionCancellationScheduled,
+                    EntityType::Subscription,
+                    e.subscription.subscription_id.as_uuid(),
+                )
+                .agg_customer(e.subscription.customer_id);
+                if let Some(reason) = &e.reason {
+                    a = a.with_metadata(serde_json::json!({ "reason": reason }));
+                }
+                a
+            }
+            OutboxEvent::SubscriptionCancelled(e) => Activity::new(
+                ActivityType::SubscriptionCancelled,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionPlanChanged(e) => Activity::new(
+                ActivityType::SubscriptionPlanChanged,
+                EntityType::Subscription,
+                e.subscription.subscription_id.as_uuid(),
+            )
+            .with_metadata(serde_json::json!({
+                "new_plan_version_id": e.subscription.plan_version_id.to_string(),
+                "previous_plan_version_id": e.previous_plan_version_id.to_string(),
+                "change_date": e.change_date.to_string(),
+            }))
+            .agg_customer(e.subscription.customer_id),
+            OutboxEvent::SubscriptionAmended(e) => Activity::new(
+                ActivityType::SubscriptionAmended,
+                EntityType::Subscription,
+                e.subscription.subscription_id.as_uuid(),
+            )
+            .with_metadata(serde_json::json!({
+                "effective_date": e.effective_date.to_string(),
+                "components_closed": e.components_closed,
+                "components_added": e.components_added,
+                "addons_closed": e.addons_closed,
+                "addons_added": e.addons_added,
+                "adjustment_invoice_id": e.adjustment_invoice_id.map(|id| id.to_string()),
+            }))
+            .agg_customer(e.subscription.customer_id),
+            OutboxEvent::SubscriptionSuspended(e) => Activity::new(
+                ActivityType::SubscriptionSuspended,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionStatusChanged(e) => {
+                // Transitions with a dedicated event already have their own row in the feed.
+                if has_dedicated_activity(&e.previous_status, &e.subscription.status) {
+                    return None;
+                }
+                Activity::new(
+                    ActivityType::SubscriptionStatusChanged,
+                    EntityType::Subscription,
+                    e.subscription.subscription_id.as_uuid(),
+                )
+                .with_metadata(serde_json::json!({
+                    "previous_status": e.previous_status.as_screaming_snake_case(),
+                    "status": e.subscription.status.as_screaming_snake_case(),
+                }))
+                .agg_customer(e.subscription.customer_id)
+            }
+            OutboxEvent::SubscriptionRenewed(e) => Activity::new(
+                ActivityType::SubscriptionRenewed,
+                EntityType::Subscription,
+                e.subscription_id.as_uuid(),
+            )
+            .agg_customer(e.customer_id),
+            OutboxEvent::SubscriptionRenewalUpcoming(e) => Activity::new(
+                ActivityType::SubscriptionRenewalUpcoming,
+                EntityType::Subscription,
+                e.subscription.subscription_id.as_uuid(),
+            )
+            .with_metadata(serde_json::json!({
+                "renewal_date": e.renewal_date.to_string(),
+                "renewal_policy": e.renewal_policy,
+                "renewal_plan_version_id": e.renewal_plan_version_id.map(|id| id.to_string()),
+                "opt_out_deadline": e.opt_out_deadline.map(|d| d.to_string()),
+            }))
+            .agg_customer(e.subscription.customer_id),
+            OutboxEvent::SubscriptionTermRenewed(e) => Activity::new(
+                ActivityType::SubscriptionTermRenewed,
+                EntityType::Subscription,
+                e.subscription.subscription_id.as_uuid(),
+            )
+            .with_metadata(serde_json::json!({
+                "previous_end_date": e.previous_end_date.to_string(),
+                "end_date": e.subscription.end_date.map(|d| d.to_string()),
+                "renewal_policy": e.renewal_policy,
+            }))
+            .agg_customer(e.subscription.customer_id),
             OutboxEvent::QuoteAccepted(e) => Activity::new(
                 ActivityType::QuoteAccepted,
                 EntityType::Quote,
@@ -607,6 +795,20 @@ impl From<&OutboxEvent> for Option<Activity> {
     }
 }
 
+/// Whether a status transition is already covered by its own subscription event
+/// (cancelled, paused, resumed, reactivated, suspended or trial ended).
+fn has_dedicated_activity(
+    previous: &SubscriptionStatusEnum,
+    status: &SubscriptionStatusEnum,
+) -> bool {
+    use SubscriptionStatusEnum as S;
+    matches!(status, S::Cancelled | S::Paused | S::Suspended)
+        || matches!(
+            (previous, status),
+            (S::TrialActive, _) | (S::Paused, S::Active) | (S::Cancelled, S::Active)
+        )
+}
+
 #[derive(Debug, Clone, o2o)]
 #[from_owned(EntityActivityRow)]
 pub struct EntityActivity {
@@ -1,5 +1,6 @@
 use diesel_models::enums as diesel_enums;
 use o2o::o2o;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 use strum::{Display, EnumString};
@@ -25,9 +26,41 @@ pub enum BillingMetricAggregateEnum {
     CountDistinct,
 }
 
+/// Length of a billing period. Month-based periods follow the subscription's billing
+/// day anchor; day-based periods have a fixed length and ignore it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PeriodLength {
+    Days(u32),
+    Months(u32),
+}
+
+impl PeriodLength {
+    /// Nominal length in days, used to order periods and to prorate across cadences.
+    pub fn nominal_days(&self) -> u32 {
+        match self {
+            PeriodLength::Days(days) => *days,
+            PeriodLength::Months(12) => 365,
+            PeriodLength::Months(6) => 182,
+            PeriodLength::Months(3) => 91,
+            PeriodLength::Months(months) => months * 30,
+        }
+    }
+
+    /// Converts an amount billed once per period into its monthly equivalent.
+    /// Day-based periods use the average month length (365 / 12 days).
+    pub fn to_monthly(&self, amount: Decimal) -> Decimal {
+        match self {
+            PeriodLength::Months(months) => amount / Decimal::from(*months),
+            PeriodLength::Days(days) => amount * Decimal::from(365) / Decimal::from(12 * days),
+        }
+    }
+}
+
 #[derive(o2o, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Eq, Default)]
 #[map_owned(diesel_enums::BillingPeriodEnum)]
 pub enum BillingPeriodEnum {
+    Daily,
+    Weekly,
     #[default]
     Monthly,
     Quarterly,
@@ -36,17 +69,21 @@ pub enum BillingPeriodEnum {
 }
 
 impl BillingPeriodEnum {
-    pub fn as_months(&self) -> u32 {
+    pub fn length(&self) -> PeriodLength {
         match self {
-            BillingPeriodEnum::Monthly => 1,
-            BillingPeriodEnum::Quarterly => 3,
-            BillingPeriodEnum::Semiannual => 6,
-            BillingPeriodEnum::Annual => 12,
+            BillingPeriodEnum::Daily => PeriodLength::Days(1),
+            BillingPeriodEnum::Weekly => PeriodLength::Days(7),
+            BillingPeriodEnum::Monthly => PeriodLength::Months(1),
+            BillingPeriodEnum::Quarterly => PeriodLength::Months(3),
+            BillingPeriodEnum::Semiannual => PeriodLength::Months(6),
+            BillingPeriodEnum::Annual => PeriodLength::Months(12),
         }
     }
 
     pub fn as_subscription_billing_period(&self) -> SubscriptionFeeBillingPeriod {
         match self {
+            BillingPeriodEnum::Daily => SubscriptionFeeBillingPeriod::Daily,
+            BillingPeriodEnum::Weekly => SubscriptionFeeBillingPeriod::Weekly,
             BillingPeriodEnum::Monthly => SubscriptionFeeBillingPeriod::Monthly,
             BillingPeriodEnum::Quarterly => SubscriptionFeeBillingPeriod::Quarterly,
             BillingPeriodEnum::Semiannual => SubscriptionFeeBillingPeriod::Semiannual,
@@ -63,7 +100,9 @@ impl PartialOrd for BillingPeriodEnum {
 
 impl Ord for BillingPeriodEnum {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.as_months().cmp(&other.as_months())
+        self.length()
+            .nominal_days()
+            .cmp(&other.length().nominal_days())
     }
 }
 
@@ -103,6 +142,15 @@ pub enum InvoiceStatusEnum {
     Uncollectible,
 }
 
+/// Outcome of the approval gate on a draft invoice. Unset when no approval was ever requested.
+#[derive(o2o, Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[map_owned(diesel_enums::InvoiceApprovalStatusEnum)]
+pub enum InvoiceApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
 #[derive(o2o, Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
 #[map_owned(diesel_enums::InvoicePaymentStatus)]
 pub enum InvoicePaymentStatus {
@@ -120,7 +168,7 @@ pub enum InvoiceType {
     Recurring,
     OneOff,
     Adjustment,
-    // Imported,
+    Imported,
     UsageThreshold,
 }
 
@@ -214,6 +262,14 @@ pub enum PlanTypeEnum {
     Custom,
 }
 
+#[derive(o2o, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[map_owned(diesel_enums::PriceChangeStatusEnum)]
+pub enum PriceChangeStatusEnum {
+    Scheduled,
+    Applied,
+    Cancelled,
+}
+
 #[derive(o2o, Serialize, Deserialize, Debug, Clone)]
 #[map_owned(diesel_enums::UnitConversionRoundingEnum)]
 pub enum UnitConversionRoundingEnum {
@@ -250,12 +306,15 @@ pub enum SubscriptionEventType {
     Cancelled,
     Reactivated,
     Updated,
+    Renewed,
 }
 
 #[derive(o2o, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[map_owned(diesel_enums::SubscriptionFeeBillingPeriod)]
 pub enum SubscriptionFeeBillingPeriod {
     OneTime,
+    Daily,
+    Weekly,
     Monthly,
     Quarterly,
     Semiannual,
@@ -263,19 +322,16 @@ pub enum SubscriptionFeeBillingPeriod {
 }
 
 impl SubscriptionFeeBillingPeriod {
-    pub fn as_months(&self) -> i32 {
-        match self {
-            SubscriptionFeeBillingPeriod::OneTime => i32::MAX, // month_elapsed % OneTime.as_months() will only be 0 if month_elapsed is 0
-            SubscriptionFeeBillingPeriod::Monthly => 1,
-            SubscriptionFeeBillingPeriod::Quarterly => 3,
-            SubscriptionFeeBillingPeriod::Semiannual => 6,
-            SubscriptionFeeBillingPeriod::Annual => 12,
-        }
+    /// Recurring period length, `None` for one-time fees.
+    pub fn length(&self) -> Option<PeriodLength> {
+        self.as_billing_period_opt().map(|p| p.length())
     }
 
     pub fn as_billing_period_opt(&self) -> Option<BillingPeriodEnum> {
         match self {
             SubscriptionFeeBillingPeriod::OneTime => None,
+            SubscriptionFeeBillingPeriod::Daily => Some(BillingPeriodEnum::Daily),
+            SubscriptionFeeBillingPeriod::Weekly => Some(BillingPeriodEnum::Weekly),
             SubscriptionFeeBillingPeriod::Monthly => Some(BillingPeriodEnum::Monthly),
             SubscriptionFeeBillingPeriod::Quarterly => Some(BillingPeriodEnum::Quarterly),
             SubscriptionFeeBillingPeriod::Semiannual => Some(BillingPeriodEnum::Semiannual),
@@ -284,7 +340,7 @@ impl SubscriptionFeeBillingPeriod {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub enum BillingType {
     Advance,
     Arrears,
@@ -348,6 +404,42 @@ impl SubscriptionStatusEnum {
     }
 }
 
+/// What happens when a fixed-term subscription reaches its end date.
+#[derive(o2o, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[map_owned(diesel_enums::RenewalPolicyEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum RenewalPolicyEnum {
+    /// The subscription completes at the end of its term.
+    #[default]
+    Expire,
+    /// A new term of the same length starts.
+    RenewSameTerm,
+    /// The subscription keeps renewing one month at a time.
+    RenewMonthToMonth,
+    /// A new term starts on another plan version, then renews for the same term.
+    RenewToPlanVersion,
+}
+
+impl RenewalPolicyEnum {
+    pub fn renews(&self) -> bool {
+        !matches!(self, RenewalPolicyEnum::Expire)
+    }
+}
+
+/// When the numbering sequence of invoices or credit notes starts over.
+#[derive(o2o, Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[map_owned(diesel_enums::NumberingResetEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum NumberingResetEnum {
+    /// A single sequence for the invoicing entity.
+    #[default]
+    Never,
+    /// A sequence per calendar year of the document date.
+    Yearly,
+    /// A sequence per calendar month of the document date.
+    Monthly,
+}
+
 #[derive(o2o, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
 #[map_owned(diesel_enums::ScheduledEventTypeEnum)]
 pub enum ScheduledEventTypeEnum {
@@ -358,6 +450,8 @@ pub enum ScheduledEventTypeEnum {
     PauseSubscription,
     EndTrial,
     ApplyAmendment,
+    ApplyPriceChange,
+    ResumeSubscription,
 }
 
 #[derive(o2o, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -405,6 +499,7 @@ pub enum BatchJobTypeEnum {
     CustomerCsvImport,
     SubscriptionCsvImport,
     SubscriptionPlanMigration,
+    InvoiceExport,
 }
 
 #[derive(o2o, Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
@@ -1,4 +1,5 @@
 use crate::domain::coupons::CouponDiscount;
+use crate::utils::custom_fields::CustomFields;
 use common_domain::ids::{
     AppliedCouponId, BillableMetricId, CouponId, PriceComponentId, ProductId, SubscriptionAddOnId,
     SubscriptionPriceComponentId,
@@ -6,12 +7,13 @@ use common_domain::ids::{
 use rust_decimal::Decimal;
 use rust_decimal::prelude::Zero;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(PartialEq, Eq, Debug, Deserialize, Serialize, Clone)]
 pub struct CouponLineItem {
     pub coupon_id: CouponId,
-    pub applied_coupon_id: AppliedCouponId,
+    /// None for a coupon applied directly to a one-off invoice, outside of any subscription
+    pub applied_coupon_id: Option<AppliedCouponId>,
     pub name: String,
     pub code: String,
     pub value: i64,
@@ -65,6 +67,11 @@ pub struct LineItem {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub group_by_dimensions: Option<HashMap<String, String>>,
+
+    /// Structured fields of the line (e.g. contract id), from the price component and subscription
+    /// instance metadata.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub metadata: CustomFields,
 }
 
 #[derive(PartialEq, Debug, Deserialize, Serialize, Eq, Clone)]
@@ -1,14 +1,16 @@
-use super::enums::{InvoicePaymentStatus, InvoiceStatusEnum, InvoiceType};
+use super::enums::{InvoiceApprovalStatus, InvoicePaymentStatus, InvoiceStatusEnum, InvoiceType};
 use crate::domain::connectors::ConnectionMeta;
 use crate::domain::invoice_lines::LineItem;
 use crate::domain::payment_transactions::PaymentTransaction;
+use crate::domain::price_components::SubLinePresentation;
 use crate::domain::{Address, CouponLineItem, Customer, InvoicingEntity, PlanVersionOverview};
 use crate::errors::{StoreError, StoreErrorReport};
 use crate::services::CustomerDetailsUpdate;
+use crate::utils::custom_fields::CustomFields;
 use chrono::{NaiveDate, NaiveDateTime, Utc};
 use common_domain::ids::{
-    BaseId, CustomerId, InvoiceId, InvoicingEntityId, PlanVersionId, StoredDocumentId,
-    SubscriptionId, TenantId,
+    BaseId, CustomerId, InvoiceId, InvoicingEntityId, PlanVersionId, PriceComponentId, PriceId,
+    ProductId, StoredDocumentId, SubscriptionId, TenantId, UserId,
 };
 use diesel_models::invoices::DetailedInvoiceRow;
 use diesel_models::invoices::InvoiceRow;
@@ -20,6 +22,7 @@ use error_stack::Report;
 use o2o::o2o;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, o2o, PartialEq, Eq)]
 #[try_from_owned(InvoiceRow, StoreErrorReport)]
@@ -90,6 +93,25 @@ pub struct Invoice {
     /// Set on a per-subscription draft that has been merged into a consolidated invoice.
     /// Such a draft is superseded: it is never finalized or charged on its own.
     pub consolidated_into_invoice_id: Option<InvoiceId>,
+    /// Structured fields (cost center, project code...), from the customer and subscription
+    /// defaults. Editable while the invoice is a draft.
+    #[from(serde_json::from_value(~).map_err(| e | {
+    StoreError::SerdeError("Failed to deserialize custom_fields".to_string(), e)
+    }) ?)]
+    pub custom_fields: CustomFields,
+    /// Sub-line presentation of the components, snapshotted at finalization so that documents
+    /// keep their layout. `None` on drafts, presented with the current one.
+    #[from(~.map(serde_json::from_value).transpose().map_err(| e | {
+    StoreError::SerdeError("Failed to deserialize sub_line_presentations".to_string(), e)
+    }) ?)]
+    pub sub_line_presentations: Option<HashMap<PriceComponentId, SubLinePresentation>>,
+    /// Set once the draft was held for approval before finalization.
+    #[from(~.map(Into::into))]
+    pub approval_status: Option<InvoiceApprovalStatus>,
+    pub approval_requested_at: Option<NaiveDateTime>,
+    pub approval_decided_at: Option<NaiveDateTime>,
+    pub approval_decided_by: Option<UserId>,
+    pub approval_comment: Option<String>,
 }
 
 impl Invoice {
@@ -113,6 +135,16 @@ impl Invoice {
         }
         Ok(())
     }
+
+    /// A draft held for approval keeps the amounts submitted to the approvers until decided.
+    pub fn ensure_not_awaiting_approval(&self, operation: &str) -> Result<(), StoreError> {
+        if self.approval_status == Some(InvoiceApprovalStatus::Pending) {
+            return Err(StoreError::InvalidArgument(format!(
+                "Cannot {operation} an invoice awaiting approval, approve or reject it first"
+            )));
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -189,6 +221,10 @@ pub struct InvoiceNew {
     pub invoicing_entity_id: InvoicingEntityId,
     pub parent_invoice_id: Option<InvoiceId>,
     pub consolidated_into_invoice_id: Option<InvoiceId>,
+    #[into(serde_json::to_value(& ~).map_err(| e | {
+    StoreError::SerdeError("Failed to serialize custom_fields".to_string(), e)
+    }) ?)]
+    pub custom_fields: CustomFields,
 }
 
 #[derive(Debug, o2o)]
@@ -221,6 +257,9 @@ pub struct InlineCustomer {
     pub vat_number: Option<String>,
     pub billing_address: Option<Address>,
     pub snapshot_at: NaiveDateTime,
+    /// Language the documents of the invoice are issued in, if the customer has a preference.
+    #[serde(default)]
+    pub invoice_language: Option<String>,
 }
 
 impl From<Customer> for InlineCustomer {
@@ -233,6 +272,7 @@ impl From<Customer> for InlineCustomer {
             vat_number: value.vat_number.clone(),
             billing_address: value.billing_address.clone(),
             snapshot_at: chrono::Utc::now().naive_utc(),
+            invoice_language: value.invoice_language.clone(),
         }
     }
 }
@@ -359,6 +399,13 @@ impl From<InvoiceNew> for Invoice {
             invoicing_entity_id: value.invoicing_entity_id,
             parent_invoice_id: value.parent_invoice_id,
             consolidated_into_invoice_id: value.consolidated_into_invoice_id,
+            custom_fields: value.custom_fields,
+            sub_line_presentations: None,
+            approval_status: None,
+            approval_requested_at: None,
+            approval_decided_at: None,
+            approval_decided_by: None,
+            approval_comment: None,
         }
     }
 }
@@ -372,6 +419,7 @@ pub struct UpdateInvoiceParams {
     pub discount: Option<String>,
     pub customer_details: Option<CustomerDetailsUpdate>,
     pub invoicing_entity_id: Option<InvoicingEntityId>,
+    pub custom_fields: Option<CustomFields>,
 }
 
 pub struct UpdateLineItemParams {
@@ -384,4 +432,42 @@ pub struct UpdateLineItemParams {
     pub tax_rate: Decimal,
     pub description: Option<String>,
     pub sub_lines: Vec<crate::domain::invoice_lines::SubLineItem>,
+    /// None keeps the metadata of the existing line.
+    pub metadata: Option<CustomFields>,
+}
+
+/// A draft one-off invoice, outside of any subscription (created through the API).
+pub struct OneOffInvoiceNew {
+    pub customer_id: CustomerId,
+    /// Defaults to the customer currency
+    pub currency: Option<String>,
+    /// Defaults to today
+    pub invoice_date: Option<NaiveDate>,
+    /// Defaults to the net terms of the invoicing entity
+    pub due_date: Option<NaiveDate>,
+    pub memo: Option<String>,
+    pub reference: Option<String>,
+    pub purchase_order: Option<String>,
+    pub line_items: Vec<OneOffLineItemNew>,
+    pub coupon_codes: Vec<String>,
+    /// Merged over the customer defaults
+    pub custom_fields: Option<CustomFields>,
+}
+
+pub struct OneOffLineItemNew {
+    pub item: OneOffItem,
+    pub quantity: Decimal,
+    pub description: Option<String>,
+    pub metadata: CustomFields,
+}
+
+pub enum OneOffItem {
+    /// A catalog price, billed at the pricing that applies to the customer. Named after its product.
+    Price(PriceId),
+    /// A free-form line, optionally attached to a product for its tax settings.
+    Custom {
+        name: String,
+        unit_price: Decimal,
+        product_id: Option<ProductId>,
+    },
 }
@@ -2,15 +2,20 @@ use secrecy::SecretString;
 
 use crate::StoreResult;
 use crate::domain::connectors::{Connector, ConnectorMeta};
+use crate::domain::enums::NumberingResetEnum;
 use crate::domain::{Address, BankAccount, TaxResolverEnum};
+use crate::errors::StoreError;
+use crate::services::utils::NumberPattern;
+use crate::utils::locale::{InvoiceTranslations, validate_invoice_translations};
 use common_domain::country::CountryCode;
 use common_domain::ids::{
-    BankAccountId, ConnectorId, InvoicingEntityId, StoredDocumentId, TenantId,
+    BankAccountId, ConnectorId, InvoicingEntityId, StoredDocumentId, TenantId, UserId,
 };
 use diesel_models::invoicing_entities::{
     InvoicingEntityProvidersRow, InvoicingEntityRow, InvoicingEntityRowPatch,
     InvoicingEntityRowProvidersPatch,
 };
+use error_stack::Report;
 use o2o::o2o;
 use serde::{Deserialize, Serialize};
 
@@ -60,9 +65,47 @@ pub struct InvoicingEntity {
     pub portal_theme_mode: Option<String>,
     /// Customer-portal control roundness ("Sharp" | "Modern" | "Rounded").
     pub portal_roundness: Option<String>,
+    #[map(~.into())]
+    pub invoice_number_reset: NumberingResetEnum,
+    pub credit_note_number_pattern: String,
+    #[map(~.into())]
+    pub credit_note_number_reset: NumberingResetEnum,
+    /// Tenant overrides of the invoice and credit note messages, by language tag.
+    #[from(serde_json::from_value(~).unwrap_or_default())]
+    #[into(serde_json::to_value(&~).unwrap_or_default())]
+    #[serde(default)]
+    pub invoice_translations: InvoiceTranslations,
+    /// Typst source replacing the built-in invoice template, validated by the API on upload.
+    #[serde(default)]
+    pub invoice_template: Option<String>,
+    /// Invoices totalling at least this amount (in cents of the accounting currency)
+    /// need approval before they can be finalized.
+    #[serde(default)]
+    pub invoice_approval_threshold: Option<i64>,
+    /// Users allowed to approve held invoices. When empty, any organization admin can.
+    #[from(~.into_iter().flatten().collect())]
+    #[into(~.into_iter().map(Some).collect())]
+    #[serde(default)]
+    pub invoice_approver_ids: Vec<UserId>,
 }
 
 impl InvoicingEntity {
+    /// Checks that the invoice and credit note number patterns are valid for their reset.
+    pub fn validate_number_patterns(&self) -> StoreResult<()> {
+        NumberPattern::validate(&self.invoice_number_pattern, self.invoice_number_reset)
+            .and_then(|_| {
+                NumberPattern::validate(
+                    &self.credit_note_number_pattern,
+                    self.credit_note_number_reset,
+                )
+            })
+            .map_err(|e| Report::new(StoreError::InvalidArgument(e)))
+    }
+
+    pub fn validate_invoice_translations(&self) -> StoreResult<()> {
+        validate_invoice_translations(&self.invoice_translations)
+    }
+
     pub fn address(&self) -> Address {
         Address {
             line1: self.address_line1.clone(),
@@ -99,6 +142,10 @@ pub struct InvoicingEntityNew {
     pub require_billing_information: bool,
     pub portal_theme_mode: Option<String>,
     pub portal_roundness: Option<String>,
+    pub invoice_number_reset: Option<NumberingResetEnum>,
+    pub credit_note_number_pattern: Option<String>,
+    pub credit_note_number_reset: Option<NumberingResetEnum>,
+    pub invoice_translations: InvoiceTranslations,
 }
 
 #[derive(Clone, Debug, o2o, Default)]
@@ -128,6 +175,17 @@ pub struct InvoicingEntityPatch {
     pub require_billing_information: Option<bool>,
     pub portal_theme_mode: Option<Option<String>>,
     pub portal_roundness: Option<Option<String>>,
+    #[map(~.map(|x| x.into()))]
+    pub invoice_number_reset: Option<NumberingResetEnum>,
+    pub credit_note_number_pattern: Option<String>,
+    #[map(~.map(|x| x.into()))]
+    pub credit_note_number_reset: Option<NumberingResetEnum>,
+    #[into(~.map(|t| serde_json::to_value(&t).unwrap_or_default()))]
+    pub invoice_translations: Option<InvoiceTranslations>,
+    pub invoice_template: Option<Option<String>>,
+    pub invoice_approval_threshold: Option<Option<i64>>,
+    #[into(~.map(|ids| ids.into_iter().map(Some).collect()))]
+    pub invoice_approver_ids: Option<Vec<UserId>>,
 }
 
 #[derive(Clone, Debug, o2o, Default)]
@@ -84,6 +84,23 @@ impl From<Period> for UsagePeriod {
     }
 }
 
+/// Restricts a usage query to the events whose `property` equals `value`. An empty `value`
+/// matches the events without the property.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct UsageFilter {
+    pub property: String,
+    pub value: String,
+}
+
+impl UsageFilter {
+    pub fn without(property: &str) -> Self {
+        UsageFilter {
+            property: property.to_string(),
+            value: String::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ComponentPeriods {
     pub arrear: Option<Period>,
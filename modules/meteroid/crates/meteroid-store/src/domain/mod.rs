@@ -35,6 +35,7 @@ pub mod customers;
 pub mod invoices;
 pub mod plans;
 
+pub mod price_changes;
 pub mod price_components;
 pub mod prices;
 pub mod tenants;
@@ -45,6 +46,7 @@ pub mod api_tokens;
 pub mod bank_accounts;
 pub mod batch_jobs;
 pub mod billable_metrics;
+pub mod catalog;
 pub mod checkout_sessions;
 pub mod connectors;
 pub mod coupons;
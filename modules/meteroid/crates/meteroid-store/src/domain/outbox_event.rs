@@ -6,10 +6,12 @@ use crate::domain::enums::{BillingPeriodEnum, InvoiceStatusEnum, PlanStatusEnum,
 use crate::domain::pgmq::{PgmqMessage, PgmqMessageNew};
 use crate::domain::{
     Address, BillableMetric, BillingMetricAggregateEnum, CreditNote, Customer, Invoice,
-    PaymentStatusEnum, PaymentTransaction, PaymentTypeEnum, Quote, SegmentationMatrix,
-    ShippingAddress, Subscription, SubscriptionStatusEnum, UnitConversionRoundingEnum,
+    PaymentStatusEnum, PaymentTransaction, PaymentTypeEnum, Quote, RenewalPolicyEnum,
+    SegmentationMatrix, ShippingAddress, Subscription, SubscriptionStatusEnum,
+    UnitConversionRoundingEnum,
 };
 use crate::errors::{StoreError, StoreErrorReport};
+use crate::utils::custom_fields::CustomFields;
 use crate::{StoreResult, json_value_serde};
 use chrono::{NaiveDate, NaiveDateTime};
 use common_domain::ids::{
@@ -24,6 +26,7 @@ use error_stack::Report;
 use o2o::o2o;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
 use strum::Display;
 use uuid::Uuid;
 
@@ -45,6 +48,20 @@ pub enum OutboxEvent {
     CreditNoteFinalized(Box<CreditNoteEvent>),
     CreditNoteVoided(Box<CreditNoteEvent>),
     SubscriptionCreated(Box<SubscriptionEvent>),
+    SubscriptionPaused(Box<SubscriptionEvent>),
+    SubscriptionResumed(Box<SubscriptionEvent>),
+    SubscriptionReactivated(Box<SubscriptionEvent>),
+    SubscriptionTrialWillEnd(Box<SubscriptionTrialWillEndEvent>),
+    SubscriptionTrialEnded(Box<SubscriptionEvent>),
+    SubscriptionCancellationScheduled(Box<SubscriptionCancellationScheduledEvent>),
+    SubscriptionCancelled(Box<SubscriptionEvent>),
+    SubscriptionPlanChanged(Box<SubscriptionPlanChangedEvent>),
+    SubscriptionAmended(Box<SubscriptionAmendedEvent>),
+    SubscriptionSuspended(Box<SubscriptionEvent>),
+    SubscriptionStatusChanged(Box<SubscriptionStatusChangedEvent>),
+    SubscriptionRenewed(Box<SubscriptionEvent>),
+    SubscriptionRenewalUpcoming(Box<SubscriptionRenewalUpcomingEvent>),
+    SubscriptionTermRenewed(Box<SubscriptionTermRenewedEvent>),
     PaymentTransactionSaved(Box<PaymentTransactionEvent>),
     QuoteAccepted(Box<QuoteAcceptedEvent>),
     QuoteConverted(Box<QuoteConvertedEvent>),
@@ -79,6 +96,20 @@ pub enum EventType {
     CreditNoteFinalized,
     CreditNoteVoided,
     SubscriptionCreated,
+    SubscriptionPaused,
+    SubscriptionResumed,
+    SubscriptionReactivated,
+    SubscriptionTrialWillEnd,
+    SubscriptionTrialEnded,
+    SubscriptionCancellationScheduled,
+    SubscriptionCancelled,
+    SubscriptionPlanChanged,
+    SubscriptionAmended,
+    SubscriptionSuspended,
+    SubscriptionStatusChanged,
+    SubscriptionRenewed,
+    SubscriptionRenewalUpcoming,
+    SubscriptionTermRenewed,
     PaymentTransactionReceived,
     QuoteAccepted,
     QuoteConverted,
@@ -115,7 +146,21 @@ impl OutboxEvent {
             OutboxEvent::CreditNoteCreated(event) => event.id,
             OutboxEvent::CreditNoteFinalized(event) => event.id,
             OutboxEvent::CreditNoteVoided(event) => event.id,
-            OutboxEvent::SubscriptionCreated(event) => event.id,
+            OutboxEvent::SubscriptionCreated(event)
+            | OutboxEvent::SubscriptionPaused(event)
+            | OutboxEvent::SubscriptionResumed(event)
+            | OutboxEvent::SubscriptionReactivated(event)
+            | OutboxEvent::SubscriptionTrialEnded(event)
+            | OutboxEvent::SubscriptionCancelled(event)
+            | OutboxEvent::SubscriptionSuspended(event)
+            | OutboxEvent::SubscriptionRenewed(event) => event.id,
+            OutboxEvent::SubscriptionTrialWillEnd(event) => event.subscription.id,
+            OutboxEvent::SubscriptionCancellationScheduled(event) => event.subscription.id,
+            OutboxEvent::SubscriptionPlanChanged(event) => event.subscription.id,
+            OutboxEvent::SubscriptionAmended(event) => event.subscription.id,
+            OutboxEvent::SubscriptionStatusChanged(event) => event.subscription.id,
+            OutboxEvent::SubscriptionRenewalUpcoming(event) => event.subscription.id,
+            OutboxEvent::SubscriptionTermRenewed(event) => event.subscription.id,
             OutboxEvent::PaymentTransactionSaved(event) => event.id,
             OutboxEvent::QuoteAccepted(event) => event.id,
             OutboxEvent::QuoteConverted(event) => event.id,
@@ -150,7 +195,21 @@ impl OutboxEvent {
             OutboxEvent::CreditNoteCreated(event) => event.tenant_id,
             OutboxEvent::CreditNoteFinalized(event) => event.tenant_id,
             OutboxEvent::CreditNoteVoided(event) => event.tenant_id,
-            OutboxEvent::SubscriptionCreated(event) => event.tenant_id,
+            OutboxEvent::SubscriptionCreated(event)
+            | OutboxEvent::SubscriptionPaused(event)
+            | OutboxEvent::SubscriptionResumed(event)
+            | OutboxEvent::SubscriptionReactivated(event)
+            | OutboxEvent::SubscriptionTrialEnded(event)
+            | OutboxEvent::SubscriptionCancelled(event)
+            | OutboxEvent::SubscriptionSuspended(event)
+            | OutboxEvent::SubscriptionRenewed(event) => event.tenant_id,
+            OutboxEvent::SubscriptionTrialWillEnd(event) => event.subscription.tenant_id,
+            OutboxEvent::SubscriptionCancellationScheduled(event) => event.subscription.tenant_id,
+            OutboxEvent::SubscriptionPlanChanged(event) => event.subscription.tenant_id,
+            OutboxEvent::SubscriptionAmended(event) => event.subscription.tenant_id,
+            OutboxEvent::SubscriptionStatusChanged(event) => event.subscription.tenant_id,
+            OutboxEvent::SubscriptionRenewalUpcoming(event) => event.subscription.tenant_id,
+            OutboxEvent::SubscriptionTermRenewed(event) => event.subscription.tenant_id,
             OutboxEvent::PaymentTransactionSaved(event) => event.tenant_id,
             OutboxEvent::QuoteAccepted(event) => event.tenant_id,
             OutboxEvent::QuoteConverted(event) => event.tenant_id,
@@ -185,7 +244,33 @@ impl OutboxEvent {
             OutboxEvent::CreditNoteCreated(event) => event.credit_note_id.as_uuid(),
             OutboxEvent::CreditNoteFinalized(event) => event.credit_note_id.as_uuid(),
             OutboxEvent::CreditNoteVoided(event) => event.credit_note_id.as_uuid(),
-            OutboxEvent::SubscriptionCreated(event) => event.subscription_id.as_uuid(),
+            OutboxEvent::SubscriptionCreated(event)
+            | OutboxEvent::SubscriptionPaused(event)
+            | OutboxEvent::SubscriptionResumed(event)
+            | OutboxEvent::SubscriptionReactivated(event)
+            | OutboxEvent::SubscriptionTrialEnded(event)
+            | OutboxEvent::SubscriptionCancelled(event)
+            | OutboxEvent::SubscriptionSuspended(event)
+            | OutboxEvent::SubscriptionRenewed(event) => event.subscription_id.as_uuid(),
+            OutboxEvent::SubscriptionTrialWillEnd(event) => {
+                event.subscription.subscription_id.as_uuid()
+            }
+            OutboxEvent::SubscriptionCancellationScheduled(event) => {
+                event.subscription.subscription_id.as_uuid()
+            }
+            OutboxEvent::SubscriptionPlanChanged(event) => {
+                event.subscription.subscription_id.as_uuid()
+            }
+            OutboxEvent::SubscriptionAmended(event) => event.subscription.subscription_id.as_uuid(),
+            OutboxEvent::SubscriptionStatusChanged(event) => {
+                event.subscription.subscription_id.as_uuid()
+            }
+            OutboxEvent::SubscriptionRenewalUpcoming(event) => {
+                event.subscription.subscription_id.as_uuid()
+            }
+            OutboxEvent::SubscriptionTermRenewed(event) => {
+                event.subscription.subscription_id.as_uuid()
+            }
             OutboxEvent::PaymentTransactionSaved(event) => event.payment_transaction_id.as_uuid(),
             OutboxEvent::QuoteAccepted(event) => event.quote_id.as_uuid(),
             OutboxEvent::QuoteConverted(event) => event.quote_id.as_uuid(),
@@ -220,7 +305,21 @@ impl OutboxEvent {
             OutboxEvent::CreditNoteCreated(_) => "CreditNote".to_string(),
             OutboxEvent::CreditNoteFinalized(_) => "CreditNote".to_string(),
             OutboxEvent::CreditNoteVoided(_) => "CreditNote".to_string(),
-            OutboxEvent::SubscriptionCreated(_) => "Subscription".to_string(),
+            OutboxEvent::SubscriptionCreated(_)
+            | OutboxEvent::SubscriptionPaused(_)
+            | OutboxEvent::SubscriptionResumed(_)
+            | OutboxEvent::SubscriptionReactivated(_)
+            | OutboxEvent::SubscriptionTrialWillEnd(_)
+            | OutboxEvent::SubscriptionTrialEnded(_)
+            | OutboxEvent::SubscriptionCancellationScheduled(_)
+            | OutboxEvent::SubscriptionCancelled(_)
+            | OutboxEvent::SubscriptionPlanChanged(_)
+            | OutboxEvent::SubscriptionAmended(_)
+            | OutboxEvent::SubscriptionSuspended(_)
+            | OutboxEvent::SubscriptionStatusChanged(_)
+            | OutboxEvent::SubscriptionRenewed(_)
+            | OutboxEvent::SubscriptionRenewalUpcoming(_)
+            | OutboxEvent::SubscriptionTermRenewed(_) => "Subscription".to_string(),
             OutboxEvent::PaymentTransactionSaved(_) => "PaymentTransaction".to_string(),
             OutboxEvent::QuoteAccepted(_) => "Quote".to_string(),
             OutboxEvent::QuoteConverted(_) => "Quote".to_string(),
@@ -258,6 +357,22 @@ impl OutboxEvent {
             OutboxEvent::CreditNoteFinalized(_) => EventType::CreditNoteFinalized,
             OutboxEvent::CreditNoteVoided(_) => EventType::CreditNoteVoided,
             OutboxEvent::SubscriptionCreated(_) => EventType::SubscriptionCreated,
+            OutboxEvent::SubscriptionPaused(_) => EventType::SubscriptionPaused,
+            OutboxEvent::SubscriptionResumed(_) => EventType::SubscriptionResumed,
+            OutboxEvent::SubscriptionReactivated(_) => EventType::SubscriptionReactivated,
+            OutboxEvent::SubscriptionTrialWillEnd(_) => EventType::SubscriptionTrialWillEnd,
+            OutboxEvent::SubscriptionTrialEnded(_) => EventType::SubscriptionTrialEnded,
+            OutboxEvent::SubscriptionCancellationScheduled(_) => {
+                EventType::SubscriptionCancellationScheduled
+            }
+            OutboxEvent::SubscriptionCancelled(_) => EventType::SubscriptionCancelled,
+            OutboxEvent::SubscriptionPlanChanged(_) => EventType::SubscriptionPlanChanged,
+            OutboxEvent::SubscriptionAmended(_) => EventType::SubscriptionAmended,
+            OutboxEvent::SubscriptionSuspended(_) => EventType::SubscriptionSuspended,
+            OutboxEvent::SubscriptionStatusChanged(_) => EventType::SubscriptionStatusChanged,
+            OutboxEvent::SubscriptionRenewed(_) => EventType::SubscriptionRenewed,
+            OutboxEvent::SubscriptionRenewalUpcoming(_) => EventType::SubscriptionRenewalUpcoming,
+            OutboxEvent::SubscriptionTermRenewed(_) => EventType::SubscriptionTermRenewed,
             OutboxEvent::PaymentTransactionSaved(_) => EventType::PaymentTransactionReceived,
             OutboxEvent::QuoteAccepted(_) => EventType::QuoteAccepted,
             OutboxEvent::QuoteConverted(_) => EventType::QuoteConverted,
@@ -330,6 +445,64 @@ impl OutboxEvent {
         OutboxEvent::SubscriptionCreated(Box::new(event))
     }
 
+    pub fn subscription_paused(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionPaused(Box::new(event))
+    }
+
+    pub fn subscription_resumed(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionResumed(Box::new(event))
+    }
+
+    pub fn subscription_reactivated(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionReactivated(Box::new(event))
+    }
+
+    pub fn subscription_trial_will_end(event: SubscriptionTrialWillEndEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionTrialWillEnd(Box::new(event))
+    }
+
+    pub fn subscription_trial_ended(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionTrialEnded(Box::new(event))
+    }
+
+    pub fn subscription_cancellation_scheduled(
+        event: SubscriptionCancellationScheduledEvent,
+    ) -> OutboxEvent {
+        OutboxEvent::SubscriptionCancellationScheduled(Box::new(event))
+    }
+
+    pub fn subscription_cancelled(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionCancelled(Box::new(event))
+    }
+
+    pub fn subscription_plan_changed(event: SubscriptionPlanChangedEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionPlanChanged(Box::new(event))
+    }
+
+    pub fn subscription_amended(event: SubscriptionAmendedEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionAmended(Box::new(event))
+    }
+
+    pub fn subscription_suspended(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionSuspended(Box::new(event))
+    }
+
+    pub fn subscription_status_changed(event: SubscriptionStatusChangedEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionStatusChanged(Box::new(event))
+    }
+
+    pub fn subscription_renewed(event: SubscriptionEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionRenewed(Box::new(event))
+    }
+
+    pub fn subscription_renewal_upcoming(event: SubscriptionRenewalUpcomingEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionRenewalUpcoming(Box::new(event))
+    }
+
+    pub fn subscription_term_renewed(event: SubscriptionTermRenewedEvent) -> OutboxEvent {
+        OutboxEvent::SubscriptionTermRenewed(Box::new(event))
+    }
+
     pub fn payment_transaction_saved(event: PaymentTransactionEvent) -> OutboxEvent {
         OutboxEvent::PaymentTransactionSaved(Box::new(event))
     }
@@ -442,6 +615,8 @@ pub struct CustomerEvent {
     pub shipping_address: Option<ShippingAddress>,
     pub vat_number: Option<String>,
     pub conn_meta: Option<ConnectionMeta>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub invoice_custom_fields: CustomFields,
 }
 
 impl CustomerEvent {
@@ -507,6 +682,78 @@ pub struct SubscriptionEvent {
     pub mrr_cents: u64,
     pub period: BillingPeriodEnum,
     pub status: SubscriptionStatusEnum,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub invoice_custom_fields: CustomFields,
+}
+
+/// Sent a few days before the trial of a subscription ends.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionTrialWillEndEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub trial_end_date: NaiveDate,
+}
+
+/// Sent when a cancellation is scheduled; the subscription stays live until `effective_date`.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionCancellationScheduledEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub effective_date: NaiveDate,
+    pub reason: Option<String>,
+}
+
+/// Sent once a plan change is applied; `subscription` carries the new plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionPlanChangedEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub previous_plan_version_id: PlanVersionId,
+    pub change_date: NaiveDate,
+}
+
+/// Sent once the components or add-ons of a subscription are amended.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionAmendedEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub effective_date: NaiveDate,
+    pub components_closed: usize,
+    pub components_added: usize,
+    pub addons_closed: usize,
+    pub addons_added: usize,
+    pub adjustment_invoice_id: Option<InvoiceId>,
+}
+
+/// Sent on every status transition of a subscription, alongside the dedicated event if any.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionStatusChangedEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub previous_status: SubscriptionStatusEnum,
+}
+
+/// Sent within the notice period before the term of a renewing subscription ends.
+#[skip_serializing_none]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionRenewalUpcomingEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub renewal_date: NaiveDate,
+    pub renewal_policy: RenewalPolicyEnum,
+    pub renewal_plan_version_id: Option<PlanVersionId>,
+    pub opt_out_deadline: Option<NaiveDate>,
+}
+
+/// Sent when a fixed-term subscription renews for a new term; `subscription` carries the new end date.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscriptionTermRenewedEvent {
+    #[serde(flatten)]
+    pub subscription: SubscriptionEvent,
+    pub previous_end_date: NaiveDate,
+    pub renewal_policy: RenewalPolicyEnum,
 }
 
 #[skip_serializing_none]
@@ -536,6 +783,9 @@ pub struct InvoiceEvent {
     pub parent_invoice_id: Option<InvoiceId>,
     /// Set on a child draft merged into a consolidated invoice; references that parent.
     pub consolidated_into_invoice_id: Option<InvoiceId>,
+    #[map(@.custom_fields.clone())]
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_fields: CustomFields,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -313,6 +313,10 @@ pub enum SendEmailRequest {
         amount_due: i64,
         currency: String,
         company_name: String,
+        /// Language tag of the email, resolved from the customer when the email is requested.
+        /// `#[serde(default)]` so messages enqueued before localization fall back to english.
+        #[serde(default)]
+        lang: Option<String>,
         logo_attachment_id: Option<StoredDocumentId>,
         invoicing_emails: Vec<String>,
         invoice_pdf_id: StoredDocumentId,
@@ -337,6 +341,8 @@ pub enum SendEmailRequest {
         amount_paid: i64,
         currency: String,
         company_name: String,
+        #[serde(default)]
+        lang: Option<String>,
         logo_attachment_id: Option<StoredDocumentId>,
         invoicing_emails: Vec<String>,
         invoice_pdf_id: StoredDocumentId,
@@ -369,6 +375,8 @@ pub enum SendEmailRequest {
         quote_number: String,
         expires_at: Option<NaiveDate>,
         company_name: String,
+        #[serde(default)]
+        lang: Option<String>,
         logo_attachment_id: Option<StoredDocumentId>,
         /// Recipients to send the quote to
         recipient_emails: Vec<String>,
@@ -379,6 +387,50 @@ pub enum SendEmailRequest {
         /// Currency of the quote
         currency: String,
     },
+
+    /// Reminder sent to the customer a few days before the end of their trial
+    TrialWillEnd {
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        customer_id: CustomerId,
+        invoicing_entity_id: InvoicingEntityId,
+        plan_name: String,
+        trial_end_date: NaiveDate,
+        company_name: String,
+        #[serde(default)]
+        lang: Option<String>,
+        logo_attachment_id: Option<StoredDocumentId>,
+        recipient_emails: Vec<String>,
+    },
+
+    /// Notice sent to the customer before the term of their subscription renews
+    RenewalUpcoming {
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        customer_id: CustomerId,
+        invoicing_entity_id: InvoicingEntityId,
+        plan_name: String,
+        renewal_date: NaiveDate,
+        opt_out_deadline: Option<NaiveDate>,
+        company_name: String,
+        #[serde(default)]
+        lang: Option<String>,
+        logo_attachment_id: Option<StoredDocumentId>,
+        recipient_emails: Vec<String>,
+    },
+
+    /// Internal notice to the designated approvers of a draft held before finalization
+    InvoiceApprovalRequested {
+        tenant_id: TenantId,
+        invoice_id: InvoiceId,
+        tenant_name: String,
+        invoice_number: String,
+        customer_name: String,
+        total: i64,
+        currency: String,
+        invoice_url: String,
+        recipient_emails: Vec<String>,
+    },
 }
 impl SendEmailRequest {
     pub fn tenant_id(&self) -> TenantId {
@@ -387,7 +439,10 @@ impl SendEmailRequest {
             | SendEmailRequest::InvoicePaid { tenant_id, .. }
             | SendEmailRequest::PaymentReminder { tenant_id, .. }
             | SendEmailRequest::PaymentRejected { tenant_id, .. }
-            | SendEmailRequest::QuoteReady { tenant_id, .. } => *tenant_id,
+            | SendEmailRequest::QuoteReady { tenant_id, .. }
+            | SendEmailRequest::TrialWillEnd { tenant_id, .. }
+            | SendEmailRequest::RenewalUpcoming { tenant_id, .. }
+            | SendEmailRequest::InvoiceApprovalRequested { tenant_id, .. } => *tenant_id,
         }
     }
 }
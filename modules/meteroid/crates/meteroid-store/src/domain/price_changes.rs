@@ -0,0 +1,166 @@
+use chrono::{NaiveDate, NaiveDateTime};
+use common_domain::ids::{CustomerId, PriceChangeId, PriceId, SubscriptionId};
+use diesel_models::enums::PriceChangeGrandfatheringEnum;
+use diesel_models::price_changes::PriceChangeRow;
+use error_stack::Report;
+use uuid::Uuid;
+
+use crate::domain::enums::PriceChangeStatusEnum;
+use crate::domain::prices::{AffectedPlan, Price, Pricing};
+use crate::errors::StoreError;
+
+/// Which existing subscriptions follow a scheduled price change.
+/// The catalog always switches to the new price on the effective date.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PriceChangeGrandfathering {
+    /// Every live subscription moves to the new price at its first renewal on or
+    /// after the effective date.
+    AllSubscriptions,
+    /// Existing subscriptions keep the current price; only new subscriptions get
+    /// the new one.
+    NewSubscriptionsOnly,
+    /// Existing subscriptions move at their first renewal on or after this date,
+    /// giving current customers a longer notice than the catalog change.
+    RenewingAfter(NaiveDate),
+}
+
+impl PriceChangeGrandfathering {
+    /// Date from which existing subscriptions switch at renewal, or `None` when they
+    /// are grandfathered.
+    pub fn migration_cutoff(&self, effective_date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::AllSubscriptions => Some(effective_date),
+            Self::NewSubscriptionsOnly => None,
+            Self::RenewingAfter(date) => Some(*date),
+        }
+    }
+
+    pub(crate) fn to_row(&self) -> (PriceChangeGrandfatheringEnum, Option<NaiveDate>) {
+        match self {
+            Self::AllSubscriptions => (PriceChangeGrandfatheringEnum::AllSubscriptions, None),
+            Self::NewSubscriptionsOnly => {
+                (PriceChangeGrandfatheringEnum::NewSubscriptionsOnly, None)
+            }
+            Self::RenewingAfter(date) => {
+                (PriceChangeGrandfatheringEnum::RenewingAfter, Some(*date))
+            }
+        }
+    }
+
+    pub(crate) fn from_row(
+        grandfathering: PriceChangeGrandfatheringEnum,
+        renewing_after: Option<NaiveDate>,
+    ) -> Result<Self, Report<StoreError>> {
+        match (grandfathering, renewing_after) {
+            (PriceChangeGrandfatheringEnum::AllSubscriptions, _) => Ok(Self::AllSubscriptions),
+            (PriceChangeGrandfatheringEnum::NewSubscriptionsOnly, _) => {
+                Ok(Self::NewSubscriptionsOnly)
+            }
+            (PriceChangeGrandfatheringEnum::RenewingAfter, Some(date)) => {
+                Ok(Self::RenewingAfter(date))
+            }
+            (PriceChangeGrandfatheringEnum::RenewingAfter, None) => {
+                Err(Report::new(StoreError::InvalidArgument(
+                    "RenewingAfter price change is missing its date".to_string(),
+                )))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PriceChangeNew {
+    pub price_id: PriceId,
+    pub new_pricing: Pricing,
+    pub effective_date: NaiveDate,
+    pub grandfathering: PriceChangeGrandfathering,
+}
+
+#[derive(Clone, Debug)]
+pub struct PriceChange {
+    pub id: PriceChangeId,
+    pub current_price: Price,
+    /// The price that replaces `current_price`. Its row only exists once the change
+    /// has started applying, but the id is stable from scheduling onwards.
+    pub new_price: Price,
+    pub effective_date: NaiveDate,
+    pub grandfathering: PriceChangeGrandfathering,
+    pub status: PriceChangeStatusEnum,
+    pub created_by: Uuid,
+    pub created_at: NaiveDateTime,
+    pub applied_at: Option<NaiveDateTime>,
+    pub cancelled_at: Option<NaiveDateTime>,
+}
+
+impl PriceChange {
+    pub(crate) fn from_row(
+        row: PriceChangeRow,
+        current_price: Price,
+    ) -> Result<Self, Report<StoreError>> {
+        let new_pricing: Pricing = serde_json::from_value(row.new_pricing).map_err(|e| {
+            Report::new(StoreError::SerdeError(
+                "Failed to deserialize Pricing".to_string(),
+                e,
+            ))
+        })?;
+
+        let new_price = Price {
+            id: row.new_price_id,
+            pricing: new_pricing,
+            created_at: row.applied_at.unwrap_or(row.created_at),
+            archived_at: None,
+            ..current_price.clone()
+        };
+
+        Ok(PriceChange {
+            id: row.id,
+            current_price,
+            new_price,
+            effective_date: row.effective_date,
+            grandfathering: PriceChangeGrandfathering::from_row(
+                row.grandfathering,
+                row.renewing_after,
+            )?,
+            status: row.status.into(),
+            created_by: row.created_by,
+            created_at: row.created_at,
+            applied_at: row.applied_at,
+            cancelled_at: row.cancelled_at,
+        })
+    }
+}
+
+/// MRR impact of a price change on a single subscription.
+#[derive(Clone, Debug)]
+pub struct SubscriptionPriceChangeImpact {
+    pub subscription_id: SubscriptionId,
+    pub customer_id: CustomerId,
+    pub customer_name: String,
+    pub plan_name: String,
+    /// Renewal date at which the subscription switches to the new price.
+    pub apply_date: NaiveDate,
+    pub current_mrr_cents: i64,
+    pub new_mrr_cents: i64,
+}
+
+impl SubscriptionPriceChangeImpact {
+    pub fn mrr_delta_cents(&self) -> i64 {
+        self.new_mrr_cents - self.current_mrr_cents
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PriceChangePreview {
+    pub currency: String,
+    pub affected_plans: Vec<AffectedPlan>,
+    pub subscriptions: Vec<SubscriptionPriceChangeImpact>,
+}
+
+impl PriceChangePreview {
+    pub fn total_mrr_delta_cents(&self) -> i64 {
+        self.subscriptions
+            .iter()
+            .map(SubscriptionPriceChangeImpact::mrr_delta_cents)
+            .sum()
+    }
+}
@@ -4,10 +4,11 @@ use uuid::Uuid;
 // TODO duplicate as well
 use super::enums::{BillingPeriodEnum, BillingType, FeeTypeEnum, SubscriptionFeeBillingPeriod};
 
-use crate::domain::prices::{self, FeeStructure, LegacyPricingData, Pricing};
+use crate::domain::prices::{self, FeeStructure, LegacyPricingData, PriceVariant, Pricing};
 use crate::domain::{Price, Product, SubscriptionFee};
 use crate::errors::{StoreError, StoreErrorReport};
 use crate::json_value_serde;
+use crate::utils::custom_fields::{CustomFields, validate_custom_fields};
 use common_domain::ids::{
     BaseId, BillableMetricId, PlanVersionId, PriceComponentId, PriceId, ProductId,
 };
@@ -57,6 +58,8 @@ pub struct PriceInput {
     pub cadence: BillingPeriodEnum,
     pub currency: String,
     pub pricing: Pricing,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<PriceVariant>,
 }
 
 /// High-level component definition used by `insert_plan` and the seeder.
@@ -67,18 +70,61 @@ pub struct PriceComponentNewInternal {
     pub prices: Vec<PriceEntry>,
 }
 
+/// How the usage lines of a component are shown on invoice documents, the portal and the
+/// upcoming invoice. Stored invoice lines keep the full detail whatever the mode.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum SubLinePresentation {
+    /// One line per group, with all its sub-lines.
+    #[default]
+    Detailed,
+    /// A single line without sub-lines; the detail goes to the usage annex.
+    Collapsed,
+    /// The `limit` largest groups, the others merged into an "Other" line.
+    TopGroups { limit: u32 },
+    /// A single line with the sub-lines (tiers, packages...) summed across groups.
+    TiersOnly,
+}
+
+json_value_serde!(SubLinePresentation);
+
+/// Serializes the presentation of a component being saved, rejecting a `TopGroups` without groups.
+pub(crate) fn sub_line_presentation_to_json(
+    presentation: SubLinePresentation,
+) -> Result<serde_json::Value, StoreErrorReport> {
+    if presentation == (SubLinePresentation::TopGroups { limit: 0 }) {
+        return Err(StoreError::InvalidArgument(
+            "The top groups presentation needs a limit of at least 1".to_string(),
+        )
+        .into());
+    }
+    presentation.try_into()
+}
+
+pub(crate) fn line_metadata_to_json(
+    line_metadata: &CustomFields,
+) -> Result<serde_json::Value, StoreErrorReport> {
+    serde_json::to_value(line_metadata).map_err(|e| {
+        StoreError::SerdeError("Failed to serialize line_metadata".to_string(), e).into()
+    })
+}
+
 /// Low-level row builder for inserting a PriceComponentRow.
 #[derive(Debug, Clone)]
 pub struct PriceComponentNew {
     pub name: String,
     pub product_id: Option<ProductId>,
     pub plan_version_id: PlanVersionId,
+    pub sub_line_presentation: SubLinePresentation,
+    pub line_metadata: CustomFields,
 }
 
 impl TryInto<PriceComponentRowNew> for PriceComponentNew {
     type Error = StoreErrorReport;
 
     fn try_into(self) -> Result<PriceComponentRowNew, Self::Error> {
+        validate_custom_fields(&self.line_metadata)?;
+
         Ok(PriceComponentRowNew {
             id: PriceComponentId::new(),
             plan_version_id: self.plan_version_id,
@@ -86,6 +132,8 @@ impl TryInto<PriceComponentRowNew> for PriceComponentNew {
             legacy_fee: None,
             product_id: self.product_id,
             billable_metric_id: None,
+            sub_line_presentation: sub_line_presentation_to_json(self.sub_line_presentation)?,
+            line_metadata: line_metadata_to_json(&self.line_metadata)?,
         })
     }
 }
@@ -101,6 +149,9 @@ pub struct PriceComponent {
     /// V1 pricing data extracted from Row legacy_fee. No database IDs.
     /// None for v2 components.
     pub legacy_pricing: Option<LegacyPricingData>,
+    pub sub_line_presentation: SubLinePresentation,
+    /// Metadata set on the invoice lines of the component, under the subscription instance one.
+    pub line_metadata: CustomFields,
 }
 
 impl TryInto<PriceComponent> for PriceComponentRow {
@@ -113,6 +164,8 @@ impl TryInto<PriceComponent> for PriceComponentRow {
             product_id: self.product_id,
             prices: Vec::new(),
             legacy_pricing: None,
+            sub_line_presentation: self.sub_line_presentation.try_into()?,
+            line_metadata: serde_json::from_value(self.line_metadata).unwrap_or_default(),
         })
     }
 }
@@ -257,7 +310,7 @@ fn select_legacy_pricing_entry<'a>(
 }
 
 //
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub enum UsagePricingModel {
     PerUnit {
         rate: rust_decimal::Decimal,
@@ -265,10 +318,14 @@ pub enum UsagePricingModel {
     Tiered {
         tiers: Vec<TierRow>,
         block_size: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tier_group: Option<TierGroup>,
     },
     Volume {
         tiers: Vec<TierRow>,
         block_size: Option<u64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tier_group: Option<TierGroup>,
     },
     Package {
         block_size: u64,
@@ -279,20 +336,39 @@ pub enum UsagePricingModel {
     },
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+impl UsagePricingModel {
+    pub fn tier_group(&self) -> Option<&TierGroup> {
+        match self {
+            UsagePricingModel::Tiered { tier_group, .. }
+            | UsagePricingModel::Volume { tier_group, .. } => tier_group.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct MatrixRow {
     pub dimension1: MatrixDimension,
     pub dimension2: Option<MatrixDimension>,
     pub per_unit_price: rust_decimal::Decimal,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct MatrixDimension {
     pub key: String,
     pub value: String,
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+/// Pools the usage of every usage fee of a subscription sharing the same `key`: tiers are
+/// evaluated on the combined quantity, and each fee is billed its share of the result.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TierGroup {
+    pub key: String,
+    /// Pooled units contributed by one unit of this fee's metric.
+    pub units_per_quantity: rust_decimal::Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 pub struct TierRow {
     pub first_unit: u64,
     // last unit is implicit.
@@ -363,12 +439,12 @@ pub struct CapacityThreshold {
     pub per_unit_overage: rust_decimal::Decimal,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UpgradePolicy {
     Prorated,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DowngradePolicy {
     RemoveAtEndOfPeriod,
 }
@@ -417,6 +493,7 @@ golden!(FeeType, {
                 flat_cap: None,
             }],
             block_size: Some(10),
+            tier_group: None,
         },
         cadence: BillingPeriodEnum::Monthly,
     },
@@ -430,6 +507,7 @@ golden!(FeeType, {
                 flat_cap: None,
             }],
             block_size: Some(10),
+            tier_group: None,
         },
         cadence: BillingPeriodEnum::Monthly,
     },
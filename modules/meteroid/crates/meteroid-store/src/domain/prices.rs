@@ -1,4 +1,5 @@
 use chrono::NaiveDateTime;
+use common_domain::country::CountryCode;
 use common_domain::ids::{BillableMetricId, PriceId, ProductId, TenantId};
 use diesel_models::prices::PriceRow;
 use error_stack::Report;
@@ -6,13 +7,14 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::customers::Customer;
 use super::enums::{BillingPeriodEnum, BillingType, FeeTypeEnum, SubscriptionFeeBillingPeriod};
 use super::price_components::{DowngradePolicy, FeeType, UpgradePolicy, UsagePricingModel};
 use super::subscription_components::SubscriptionFee;
 use crate::errors::StoreError;
 
 // structural billing config stored on Product.fee_structure
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(tag = "type")]
 pub enum FeeStructure {
     Rate {},
@@ -34,7 +36,20 @@ pub enum FeeStructure {
     OneTime {},
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug)]
+impl FeeStructure {
+    pub fn fee_type(&self) -> FeeTypeEnum {
+        match self {
+            FeeStructure::Rate {} => FeeTypeEnum::Rate,
+            FeeStructure::Slot { .. } => FeeTypeEnum::Slot,
+            FeeStructure::Capacity { .. } => FeeTypeEnum::Capacity,
+            FeeStructure::Usage { .. } => FeeTypeEnum::Usage,
+            FeeStructure::ExtraRecurring { .. } => FeeTypeEnum::ExtraRecurring,
+            FeeStructure::OneTime {} => FeeTypeEnum::OneTime,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(tag = "type")]
 pub enum UsageModel {
     PerUnit,
@@ -57,7 +72,7 @@ impl From<&UsagePricingModel> for UsageModel {
 }
 
 // monetary config stored on Price.pricing
-#[derive(Serialize, Deserialize, Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(tag = "type")]
 pub enum Pricing {
     Rate {
@@ -97,6 +112,99 @@ pub struct Price {
     pub created_at: NaiveDateTime,
     pub archived_at: Option<NaiveDateTime>,
     pub catalog: bool,
+    pub variants: Vec<PriceVariant>,
+}
+
+impl Price {
+    /// Pricing billed to `customer`: the first variant whose condition matches, or the base
+    /// pricing.
+    pub fn pricing_for(&self, customer: &Customer) -> &Pricing {
+        self.variants
+            .iter()
+            .find(|v| v.condition.matches(customer))
+            .map_or(&self.pricing, |v| &v.pricing)
+    }
+}
+
+/// Alternative pricing of a price for the customers matching `condition`, for instance
+/// purchasing-power-parity or regional pricing. Variants are resolved when a subscription is
+/// created or previewed, the resulting fee is then stored on the subscription like any other.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct PriceVariant {
+    pub condition: PriceCondition,
+    pub pricing: Pricing,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum PriceCondition {
+    /// The country of the customer billing address is one of `countries`.
+    BillingCountry { countries: Vec<CountryCode> },
+    /// The customer custom attribute `key` is set to one of `values`.
+    CustomerAttribute { key: String, values: Vec<String> },
+}
+
+impl PriceCondition {
+    pub fn matches(&self, customer: &Customer) -> bool {
+        match self {
+            PriceCondition::BillingCountry { countries } => customer
+                .billing_address
+                .as_ref()
+                .and_then(|a| a.country.as_ref())
+                .is_some_and(|country| countries.contains(country)),
+            PriceCondition::CustomerAttribute { key, values } => customer
+                .custom_attributes
+                .get(key)
+                .is_some_and(|value| values.contains(value)),
+        }
+    }
+}
+
+/// Variants must keep the pricing model of the base price, so that they resolve against the
+/// same product fee structure.
+pub fn validate_price_variants(
+    pricing: &Pricing,
+    variants: &[PriceVariant],
+) -> Result<(), StoreError> {
+    for variant in variants {
+        let empty = match &variant.condition {
+            PriceCondition::BillingCountry { countries } => countries.is_empty(),
+            PriceCondition::CustomerAttribute { key, values } => {
+                key.trim().is_empty() || values.is_empty()
+            }
+        };
+        if empty {
+            return Err(StoreError::InvalidArgument(
+                "Price variant condition cannot be empty".to_string(),
+            ));
+        }
+
+        let same_model = match (pricing, &variant.pricing) {
+            (Pricing::Usage(base), Pricing::Usage(other)) => {
+                UsageModel::from(base) == UsageModel::from(other)
+            }
+            (base, other) => std::mem::discriminant(base) == std::mem::discriminant(other),
+        };
+        if !same_model {
+            return Err(StoreError::InvalidArgument(
+                "Price variant pricing must use the same model as the price".to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn price_variants_to_json(
+    pricing: &Pricing,
+    variants: &[PriceVariant],
+) -> Result<serde_json::Value, Report<StoreError>> {
+    validate_price_variants(pricing, variants).map_err(Report::new)?;
+    serde_json::to_value(variants).map_err(|e| {
+        Report::new(StoreError::SerdeError(
+            "Failed to serialize price variants".to_string(),
+            e,
+        ))
+    })
 }
 
 impl TryFrom<PriceRow> for Price {
@@ -120,6 +228,12 @@ impl TryFrom<PriceRow> for Price {
             created_at: row.created_at,
             archived_at: row.archived_at,
             catalog: row.catalog,
+            variants: serde_json::from_value(row.variants).map_err(|e| {
+                Report::new(StoreError::SerdeError(
+                    "Failed to deserialize price variants".to_string(),
+                    e,
+                ))
+            })?,
         })
     }
 }
@@ -798,6 +912,7 @@ mod tests {
                     flat_cap: None,
                 }],
                 block_size: None,
+                tier_group: None,
             },
             cadence: BillingPeriodEnum::Monthly,
         };
@@ -899,6 +1014,7 @@ mod tests {
                     flat_cap: None,
                 }],
                 block_size: Some(100),
+                tier_group: None,
             }),
             Pricing::Usage(UsagePricingModel::Matrix {
                 rates: vec![MatrixRow {
@@ -1070,4 +1186,54 @@ mod tests {
             _ => panic!("Expected Slot"),
         }
     }
+
+    #[test]
+    fn test_price_variants_keep_pricing_model() {
+        use std::str::FromStr;
+
+        let pricing = Pricing::Rate { rate: dec!(49) };
+        let variant = PriceVariant {
+            condition: PriceCondition::BillingCountry {
+                countries: vec![CountryCode::from_str("IN").unwrap()],
+            },
+            pricing: Pricing::Rate { rate: dec!(19) },
+        };
+        assert!(validate_price_variants(&pricing, std::slice::from_ref(&variant)).is_ok());
+
+        let other_model = PriceVariant {
+            pricing: Pricing::Slot {
+                unit_rate: dec!(5),
+                min_slots: None,
+                max_slots: None,
+            },
+            ..variant.clone()
+        };
+        assert!(validate_price_variants(&pricing, &[other_model]).is_err());
+
+        let empty_condition = PriceVariant {
+            condition: PriceCondition::CustomerAttribute {
+                key: "region".to_string(),
+                values: vec![],
+            },
+            ..variant
+        };
+        assert!(validate_price_variants(&pricing, &[empty_condition]).is_err());
+    }
+
+    #[test]
+    fn test_price_variant_serde() {
+        let json = serde_json::json!([{
+            "condition": {"type": "CustomerAttribute", "key": "tier", "values": ["edu"]},
+            "pricing": {"type": "Rate", "rate": "9.00"}
+        }]);
+        let variants: Vec<PriceVariant> = serde_json::from_value(json).unwrap();
+        assert_eq!(
+            variants[0].condition,
+            PriceCondition::CustomerAttribute {
+                key: "tier".to_string(),
+                values: vec!["edu".to_string()],
+            }
+        );
+        assert_eq!(variants[0].pricing, Pricing::Rate { rate: dec!(9) });
+    }
 }
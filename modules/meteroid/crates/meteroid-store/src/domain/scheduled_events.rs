@@ -3,11 +3,12 @@ use crate::domain::enums::{
     ScheduledEventStatus, ScheduledEventTypeEnum, SubscriptionFeeBillingPeriod,
 };
 use crate::domain::subscription_components::SubscriptionFee;
+use crate::domain::subscriptions::{PauseBilling, ResumeBillingAnchor};
 use crate::errors::StoreErrorReport;
 use crate::json_value_serde;
-use chrono::NaiveDateTime;
+use chrono::{NaiveDate, NaiveDateTime};
 use common_domain::ids::{
-    AddOnId, BaseId, InvoiceId, PlanVersionId, PriceComponentId, PriceId, ProductId,
+    AddOnId, BaseId, InvoiceId, PlanVersionId, PriceChangeId, PriceComponentId, PriceId, ProductId,
     ScheduledEventId, SubscriptionAddOnId, SubscriptionId, SubscriptionPriceComponentId, TenantId,
 };
 use diesel_models::scheduled_events::ScheduledEventRow;
@@ -63,7 +64,19 @@ pub enum ScheduledEventData {
     CancelSubscription {
         reason: Option<String>,
     },
-    PauseSubscription,
+    PauseSubscription {
+        #[serde(default)]
+        billing: PauseBilling,
+        /// Auto-resume date, scheduled as a `ResumeSubscription` event once paused.
+        #[serde(default)]
+        resume_on: Option<NaiveDate>,
+        #[serde(default)]
+        resume_anchor: ResumeBillingAnchor,
+    },
+    ResumeSubscription {
+        #[serde(default)]
+        anchor: ResumeBillingAnchor,
+    },
     FinalizeInvoice {
         invoice_id: InvoiceId,
     },
@@ -87,6 +100,12 @@ pub enum ScheduledEventData {
         addon_close: Vec<SubscriptionAddOnId>,
         addon_insert: Vec<ResolvedAddOnInsert>,
     },
+    /// Move the subscription's components priced by a scheduled price change onto
+    /// the new price. Components are resolved when the event fires, so slot counts
+    /// and intervening amendments are taken into account.
+    ApplyPriceChange {
+        price_change_id: PriceChangeId,
+    },
 }
 
 /// A fully-resolved subscription component to insert when an amendment is applied.
@@ -138,11 +157,13 @@ impl ScheduledEventData {
         match self {
             Self::CancelSubscription { .. } => ScheduledEventTypeEnum::CancelSubscription,
             Self::PauseSubscription { .. } => ScheduledEventTypeEnum::PauseSubscription,
+            Self::ResumeSubscription { .. } => ScheduledEventTypeEnum::ResumeSubscription,
             Self::FinalizeInvoice { .. } => ScheduledEventTypeEnum::FinalizeInvoice,
             Self::RetryPayment { .. } => ScheduledEventTypeEnum::RetryPayment,
             Self::ApplyPlanChange { .. } => ScheduledEventTypeEnum::ApplyPlanChange,
             Self::EndTrial => ScheduledEventTypeEnum::EndTrial,
             Self::ApplyAmendment { .. } => ScheduledEventTypeEnum::ApplyAmendment,
+            Self::ApplyPriceChange { .. } => ScheduledEventTypeEnum::ApplyPriceChange,
         }
     }
 }
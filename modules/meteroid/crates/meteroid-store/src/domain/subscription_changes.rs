@@ -66,6 +66,23 @@ pub struct ImmediatePlanChangeResult {
     pub effective_date: NaiveDate,
 }
 
+/// Outcome of moving a subscription's billing day: the current period becomes a stub
+/// ending on the first occurrence of the new billing day.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BillingAnchorChangePreview {
+    pub billing_day_anchor: u16,
+    pub current_period_start: NaiveDate,
+    pub current_period_end: NaiveDate,
+    pub new_period_end: NaiveDate,
+    pub proration: ProrationSummary,
+}
+
+#[derive(Debug, Clone)]
+pub struct BillingAnchorChangeResult {
+    pub adjustment_invoice_id: Option<InvoiceId>,
+    pub new_period_end: NaiveDate,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchedComponent {
     pub product_id: ProductId,
@@ -1,11 +1,12 @@
-use chrono::{NaiveDate, NaiveDateTime};
+use chrono::{Days, NaiveDate, NaiveDateTime};
 use common_domain::ids::BankAccountId;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 use crate::domain::connectors::ConnectionMeta;
 use crate::domain::entitlements::{Entitlement, EntitlementSpec};
 use crate::domain::enums::{
-    BillingPeriodEnum, ScheduledEventTypeEnum, SubscriptionActivationCondition,
+    BillingPeriodEnum, RenewalPolicyEnum, ScheduledEventTypeEnum, SubscriptionActivationCondition,
 };
 use crate::domain::subscription_add_ons::{
     CreateSubscriptionAddOns, SubscriptionAddOn, SubscriptionAddOnNewInternal,
@@ -14,10 +15,11 @@ use crate::domain::subscription_components::SubscriptionComponentNewInternal;
 use crate::domain::{
     AppliedCouponDetailed, BillableMetric, CreateSubscriptionComponents, CreateSubscriptionCoupons,
     Customer, InvoicingEntity, PlanForSubscription, Schedule, SubscriptionComponent,
-    SubscriptionStatusEnum,
+    SubscriptionStatusEnum, UsageFilter,
 };
 use crate::errors::{StoreError, StoreErrorReport};
 use crate::services::PaymentSetupResult;
+use crate::utils::custom_fields::CustomFields;
 use common_domain::ids::CouponId;
 use common_domain::ids::{
     CustomerId, InvoicingEntityId, PlanId, PlanVersionId, QuoteId, SubscriptionId, TenantId,
@@ -199,6 +201,91 @@ pub struct Subscription {
     // Quote to subscription linking
     pub quote_id: Option<QuoteId>,
     pub payment_methods_config: Option<PaymentMethodsConfig>,
+    /// Trialing plan switched mid-trial, in place of the plan version's one.
+    pub trialing_plan_id: Option<PlanId>,
+    // Fixed-term renewal, applied at end_date
+    pub renewal_policy: RenewalPolicyEnum,
+    pub renewal_term_months: Option<u32>,
+    pub renewal_plan_version_id: Option<PlanVersionId>,
+    pub renewal_notice_days: Option<u32>,
+    pub renewal_opt_out_days: Option<u32>,
+    /// Instance of the plan, when the customer holds several subscriptions to it.
+    pub instance: Option<SubscriptionInstance>,
+    /// Substituted to {prefix} in invoice numbers, with its own numbering sequence.
+    pub invoice_number_prefix: Option<String>,
+    /// Custom fields set on the invoices of this subscription, over the customer ones.
+    pub invoice_custom_fields: CustomFields,
+}
+
+impl Subscription {
+    /// Last day renewal can be opted out of, if the subscription has a term that renews:
+    /// `renewal_opt_out_days` before the end of the term, or the day before it by default.
+    pub fn renewal_opt_out_deadline(&self) -> Option<NaiveDate> {
+        if !self.renewal_policy.renews() {
+            return None;
+        }
+        let days = self.renewal_opt_out_days.unwrap_or(0).max(1);
+        self.end_date?.checked_sub_days(Days::new(days as u64))
+    }
+
+    /// Usage of an instance is restricted to the events carrying its identifier. Other
+    /// subscriptions leave out the events tagged with an instance through the default property.
+    pub fn usage_filter(&self) -> UsageFilter {
+        match &self.instance {
+            Some(instance) => instance.usage_filter(),
+            None => UsageFilter::without(SubscriptionInstance::DEFAULT_USAGE_PROPERTY),
+        }
+    }
+}
+
+/// One of several independent subscriptions of a customer to the same plan, e.g. one per project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SubscriptionInstance {
+    /// Identifies the instance among the customer's subscriptions, and in its usage events.
+    pub id: String,
+    /// Event property carrying the instance identifier. Defaults to `instance_id`.
+    pub usage_property: Option<String>,
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl SubscriptionInstance {
+    pub const DEFAULT_USAGE_PROPERTY: &'static str = "instance_id";
+
+    pub fn usage_filter(&self) -> UsageFilter {
+        UsageFilter {
+            property: self
+                .usage_property
+                .clone()
+                .unwrap_or_else(|| Self::DEFAULT_USAGE_PROPERTY.to_string()),
+            value: self.id.clone(),
+        }
+    }
+
+    fn from_row(row: &SubscriptionRow) -> Option<Self> {
+        row.instance_id.as_ref().map(|id| SubscriptionInstance {
+            id: id.clone(),
+            usage_property: row.instance_usage_property.clone(),
+            metadata: row
+                .instance_metadata
+                .clone()
+                .and_then(|m| serde_json::from_value(m).ok())
+                .unwrap_or_default(),
+        })
+    }
+}
+
+/// Renewal behaviour of a fixed-term subscription, set on top of its end date.
+#[derive(Debug, Clone)]
+pub struct RenewalTerms {
+    pub policy: RenewalPolicyEnum,
+    /// Length of each renewed term. Defaults to the length of the current term.
+    pub term_months: Option<u32>,
+    /// Plan version the subscription renews onto, for `RenewToPlanVersion`.
+    pub plan_version_id: Option<PlanVersionId>,
+    /// Days before the end of the term the renewal-upcoming notification is sent.
+    pub notice_days: Option<u32>,
+    /// Days before the end of the term renewal can still be opted out of.
+    pub opt_out_days: Option<u32>,
 }
 
 pub enum CyclePosition {
@@ -206,10 +293,34 @@ pub enum CyclePosition {
     End,
 }
 
+/// What keeps being invoiced while a subscription is paused.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PauseBilling {
+    /// Billing cycles stop until the subscription is resumed.
+    #[default]
+    NoBilling,
+    /// Cycles continue so fixed fees keep being billed. Usage is still charged as reported.
+    KeepFixedFees,
+}
+
+/// How the billing anchor is handled when a paused subscription resumes.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResumeBillingAnchor {
+    /// Keep the original billing day; the first period after resumption is prorated.
+    #[default]
+    Keep,
+    /// Re-anchor billing on the resume date, starting a full period.
+    ResetToResumeDate,
+}
+
 impl TryFrom<SubscriptionForDisplayRow> for Subscription {
     type Error = StoreErrorReport;
 
     fn try_from(val: SubscriptionForDisplayRow) -> Result<Self, Self::Error> {
+        let instance = SubscriptionInstance::from_row(&val.subscription);
+
         Ok(Subscription {
             id: val.subscription.id,
             customer_id: val.subscription.customer_id,
@@ -264,6 +375,16 @@ impl TryFrom<SubscriptionForDisplayRow> for Subscription {
                         e,
                     )
                 })?,
+            trialing_plan_id: val.subscription.trialing_plan_id,
+            renewal_policy: val.subscription.renewal_policy.into(),
+            renewal_term_months: val.subscription.renewal_term_months.map(|x| x as u32),
+            renewal_plan_version_id: val.subscription.renewal_plan_version_id,
+            renewal_notice_days: val.subscription.renewal_notice_days.map(|x| x as u32),
+            renewal_opt_out_days: val.subscription.renewal_opt_out_days.map(|x| x as u32),
+            instance,
+            invoice_number_prefix: val.subscription.invoice_number_prefix,
+            invoice_custom_fields: serde_json::from_value(val.subscription.invoice_custom_fields)
+                .unwrap_or_default(),
         })
     }
 }
@@ -303,9 +424,40 @@ pub struct SubscriptionNew {
     /// When true, prevents checkout session creation even with OnCheckout activation.
     /// Used when creating subscriptions from checkout completion (SelfServe flow).
     pub skip_checkout_session: bool,
-    /// When true with a past start_date, skip creating invoices for past cycles.
-    /// Used for migrations where past billing was handled externally.
-    pub skip_past_invoices: bool,
+    /// How periods before today are handled when start_date is in the past.
+    pub past_period_billing: PastPeriodBilling,
+    /// Set when the customer subscribes to the same plan several times.
+    pub instance: Option<SubscriptionInstance>,
+}
+
+/// What to do with the periods of a backdated subscription that elapsed before its creation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PastPeriodBilling {
+    /// Invoice every past period, as if the subscription had been running all along.
+    #[default]
+    Invoice,
+    /// Record past periods as imported invoices, already paid in the previous billing system.
+    /// Used for migrations, so that revenue and MRR history are preserved.
+    ImportAsPaid,
+    /// Do not record past periods. Used for migrations where past billing was handled externally.
+    Skip,
+}
+
+impl PastPeriodBilling {
+    /// Migration modes position the subscription at its current period instead of billing
+    /// from the start date.
+    pub fn is_migration(&self) -> bool {
+        !matches!(self, PastPeriodBilling::Invoice)
+    }
+
+    /// An explicit mode takes precedence over the legacy `skip_past_invoices` flag.
+    pub fn resolve(mode: Option<PastPeriodBilling>, skip_past_invoices: bool) -> Self {
+        match mode {
+            Some(mode) => mode,
+            None if skip_past_invoices => PastPeriodBilling::Skip,
+            None => PastPeriodBilling::Invoice,
+        }
+    }
 }
 
 pub struct SubscriptionNewEnriched<'a> {
@@ -327,7 +479,7 @@ pub struct SubscriptionNewEnriched<'a> {
     pub quote_id: Option<QuoteId>,
     /// Effective trial duration: uses request override if provided, otherwise plan's trial_duration_days
     pub effective_trial_duration: Option<u32>,
-    /// Set to now() when skip_past_invoices is true, to track migrated subscriptions
+    /// Set to now() for migrated subscriptions (past periods imported or skipped)
     pub imported_at: Option<NaiveDateTime>,
 }
 
@@ -351,6 +503,15 @@ impl SubscriptionNewEnriched<'_> {
                 StoreError::SerdeError("Failed to serialize payment_methods_config".to_string(), e)
             })?;
 
+        let instance_metadata = sub
+            .instance
+            .as_ref()
+            .map(|instance| serde_json::to_value(&instance.metadata))
+            .transpose()
+            .map_err(|e| {
+                StoreError::SerdeError("Failed to serialize instance_metadata".to_string(), e)
+            })?;
+
         Ok(SubscriptionRowNew {
             id: self.subscription_id,
             trial_duration: self.effective_trial_duration.map(|x| x as i32),
@@ -383,6 +544,9 @@ impl SubscriptionNewEnriched<'_> {
             backdate_invoices: sub.backdate_invoices,
             payment_methods_config,
             imported_at: self.imported_at,
+            instance_id: sub.instance.as_ref().map(|i| i.id.clone()),
+            instance_usage_property: sub.instance.as_ref().and_then(|i| i.usage_property.clone()),
+            instance_metadata,
         })
     }
 }
@@ -441,6 +605,8 @@ pub struct SubscriptionDetails {
     pub subscription: Subscription,
     pub invoicing_entity: InvoicingEntity,
     pub customer: Customer,
+    /// Parent account billed for the subscription, when the customer belongs to a hierarchy
+    pub payer: Option<Customer>,
     pub schedules: Vec<Schedule>,
     pub price_components: Vec<SubscriptionComponent>,
     pub add_ons: Vec<SubscriptionAddOn>,
@@ -452,6 +618,13 @@ pub struct SubscriptionDetails {
     pub entitlements: Vec<Entitlement>,
 }
 
+impl SubscriptionDetails {
+    /// The customer invoiced and charged for the subscription.
+    pub fn billed_customer(&self) -> &Customer {
+        self.payer.as_ref().unwrap_or(&self.customer)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SubscriptionPatch {
     pub id: SubscriptionId,
@@ -462,6 +635,8 @@ pub struct SubscriptionPatch {
     pub purchase_order: Option<Option<String>>,
     /// None = no change, Some(None) = reset to inherit, Some(Some(config)) = set config
     pub payment_methods_config: Option<Option<PaymentMethodsConfig>>,
+    pub invoice_number_prefix: Option<Option<String>>,
+    pub invoice_custom_fields: Option<CustomFields>,
 }
 
 golden::golden!(PaymentMethodsConfig, {
@@ -416,6 +416,10 @@ impl AddOnInterface for Store {
                                 pricing: pricing_json,
                                 tenant_id,
                                 catalog: true,
+                                variants: crate::domain::prices::price_variants_to_json(
+                                    &input.pricing,
+                                    &input.variants,
+                                )?,
                             }
                             .insert(conn)
                             .await
@@ -134,6 +134,12 @@ pub trait BatchJobsInterface {
 
     async fn set_error_output_key(&self, job_id: BatchJobId, key: String) -> StoreResult<()>;
 
+    async fn set_output_key(&self, job_id: BatchJobId, key: String) -> StoreResult<()>;
+
+    /// Records an input generated while chunking (e.g. a snapshot of the items of an export),
+    /// so the chunks and the error CSV read the same items.
+    async fn set_input_source_key(&self, job_id: BatchJobId, key: String) -> StoreResult<()>;
+
     async fn list_failed_item_indices(
         &self,
         job_id: BatchJobId,
@@ -620,6 +626,22 @@ impl BatchJobsInterface for Store {
         Ok(())
     }
 
+    async fn set_output_key(&self, job_id: BatchJobId, key: String) -> StoreResult<()> {
+        let mut conn = self.get_conn().await?;
+        BatchJobRow::set_output_key(&mut conn, job_id, key)
+            .await
+            .map_err(|err| StoreError::DatabaseError(err.error))?;
+        Ok(())
+    }
+
+    async fn set_input_source_key(&self, job_id: BatchJobId, key: String) -> StoreResult<()> {
+        let mut conn = self.get_conn().await?;
+        BatchJobRow::set_input_source_key(&mut conn, job_id, key)
+            .await
+            .map_err(|err| StoreError::DatabaseError(err.error))?;
+        Ok(())
+    }
+
     async fn list_failed_item_indices(
         &self,
         job_id: BatchJobId,
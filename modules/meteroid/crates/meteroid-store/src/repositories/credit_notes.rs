@@ -9,9 +9,12 @@ use crate::domain::{
 };
 use crate::errors::StoreError;
 use crate::repositories::customer_balance::{CustomerBalance, convert_currency};
+use crate::services::utils::{NumberContext, NumberSeries, next_document_number};
 use crate::store::Store;
 use chrono::NaiveDateTime;
-use common_domain::ids::{CreditNoteId, CustomerId, InvoiceId, StoredDocumentId, TenantId};
+use common_domain::ids::{
+    CreditNoteId, CustomerId, InvoiceId, InvoicingEntityId, StoredDocumentId, TenantId,
+};
 use common_utils::decimals::{ToSubunit, ToUnit};
 use diesel_models::PgConn;
 use diesel_models::credit_notes::CreditNoteRow;
@@ -19,6 +22,7 @@ use diesel_models::customers::CustomerRow;
 use diesel_models::invoices::InvoiceRow;
 use diesel_models::invoicing_entities::InvoicingEntityRow;
 use diesel_models::payments::PaymentTransactionRow;
+use diesel_models::subscriptions::SubscriptionRow;
 use error_stack::{Report, bail};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::ToPrimitive;
@@ -164,6 +168,15 @@ pub trait CreditNoteInterface {
         credit_note_ids: Vec<CreditNoteId>,
     ) -> StoreResult<Vec<CreditNote>>;
 
+    /// Issued credit notes of an invoicing entity finalized within `[from, to]`, for accounting exports.
+    async fn list_credit_notes_for_export(
+        &self,
+        tenant_id: TenantId,
+        invoicing_entity_id: InvoicingEntityId,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> StoreResult<Vec<CreditNote>>;
+
     async fn save_credit_note_pdf_document(
         &self,
         credit_note_id: CreditNoteId,
@@ -310,6 +323,7 @@ fn negate_line_items(
                         metric_id: item.metric_id,
                         description: item.description.clone(),
                         group_by_dimensions: item.group_by_dimensions.clone(),
+                        metadata: item.metadata.clone(),
                     };
                 }
 
@@ -384,6 +398,7 @@ fn negate_line_items(
                     metric_id: item.metric_id,
                     description: item.description.clone(),
                     group_by_dimensions: item.group_by_dimensions.clone(),
+                    metadata: item.metadata.clone(),
                 }
             } else {
                 // Full credit: negate the amounts, keep original quantity and positive
@@ -420,6 +435,7 @@ fn negate_line_items(
                     metric_id: item.metric_id,
                     description: item.description.clone(),
                     group_by_dimensions: item.group_by_dimensions.clone(),
+                    metadata: item.metadata.clone(),
                 }
             }
         })
@@ -687,6 +703,43 @@ pub(crate) async fn create_user_credit_note_tx(
     .await
 }
 
+/// Allocates the number of a credit note of the invoice, locking the invoicing entity until the
+/// end of the transaction for sequential numbering.
+async fn next_credit_note_number(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    invoice: &Invoice,
+) -> StoreResult<String> {
+    let invoicing_entity = InvoicingEntityRow::select_for_update_by_id_and_tenant(
+        conn,
+        invoice.invoicing_entity_id,
+        tenant_id,
+    )
+    .await
+    .map_err(Into::<Report<StoreError>>::into)?;
+
+    let prefix = match invoice.subscription_id {
+        Some(subscription_id) => {
+            SubscriptionRow::get_invoice_number_prefix(conn, tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+        }
+        None => None,
+    };
+
+    next_document_number(
+        conn,
+        &invoicing_entity,
+        NumberSeries::CreditNote,
+        &NumberContext {
+            date: chrono::Utc::now().date_naive(),
+            customer_alias: invoice.customer_details.alias.as_deref(),
+            prefix: prefix.as_deref(),
+        },
+    )
+    .await
+}
+
 pub(crate) async fn finalize_credit_note_tx(
     store: &Store,
     conn: &mut PgConn,
@@ -702,30 +755,17 @@ pub(crate) async fn finalize_credit_note_tx(
         ));
     }
 
-    let invoicing_entity = InvoicingEntityRow::select_for_update_by_id_and_tenant(
-        conn,
-        credit_note_row.invoicing_entity_id,
-        tenant_id,
-    )
-    .await
-    .map_err(Into::<Report<StoreError>>::into)?;
+    let invoice: Invoice = InvoiceRow::find_by_id(conn, tenant_id, credit_note_row.invoice_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?
+        .try_into()?;
 
-    let credit_note_number_value = invoicing_entity.next_credit_note_number;
-    let credit_note_number = format!("CN-{:06}", credit_note_number_value);
+    let credit_note_number = next_credit_note_number(conn, tenant_id, &invoice).await?;
 
     CreditNoteRow::finalize_with_number(conn, credit_note_id, tenant_id, &credit_note_number)
         .await
         .map_err(Into::<Report<StoreError>>::into)?;
 
-    InvoicingEntityRow::update_credit_note_number(
-        conn,
-        credit_note_row.invoicing_entity_id,
-        tenant_id,
-        credit_note_number_value,
-    )
-    .await
-    .map_err(Into::<Report<StoreError>>::into)?;
-
     if credit_note_row.credited_amount_cents > 0 {
         let customer = CustomerRow::find_by_id(conn, &credit_note_row.customer_id, &tenant_id)
             .await
@@ -1041,23 +1081,12 @@ pub(crate) async fn create_credit_note_tx(
     let tax_breakdown = compute_tax_breakdown(&negated_line_items);
 
     // 8. Get credit note number - only assign real number when finalizing
-    let (credit_note_number, credit_note_number_value) =
-        if params.status == crate::domain::enums::CreditNoteStatus::Finalized {
-            // Lock invoicing entity for sequential numbering
-            let invoicing_entity = InvoicingEntityRow::select_for_update_by_id_and_tenant(
-                conn,
-                invoice.invoicing_entity_id,
-                tenant_id,
-            )
-            .await
-            .map_err(Into::<Report<StoreError>>::into)?;
-
-            let number_value = invoicing_entity.next_credit_note_number;
-            (format!("CN-{:06}", number_value), Some(number_value))
-        } else {
-            // Draft credit notes use placeholder
-            ("draft".to_string(), None)
-        };
+    let credit_note_number = if params.status == crate::domain::enums::CreditNoteStatus::Finalized {
+        next_credit_note_number(conn, tenant_id, &invoice).await?
+    } else {
+        // Draft credit notes use placeholder
+        "draft".to_string()
+    };
 
     // 9. Determine credit/refund amounts based on credit type
     // These are positive values representing how much is credited/refunded
@@ -1179,18 +1208,6 @@ pub(crate) async fn create_credit_note_tx(
         .record_outbox_batch_tx(conn, tenant_id, actor, events)
         .await?;
 
-    // 15. Update the credit note number in the invoicing entity (only when finalizing)
-    if let Some(number_value) = credit_note_number_value {
-        InvoicingEntityRow::update_credit_note_number(
-            conn,
-            invoice.invoicing_entity_id,
-            tenant_id,
-            number_value,
-        )
-        .await
-        .map_err(Into::<Report<StoreError>>::into)?;
-    }
-
     Ok(credit_note)
 }
 
@@ -1455,6 +1472,25 @@ impl CreditNoteInterface for Store {
             .collect::<Result<Vec<_>, _>>()
     }
 
+    async fn list_credit_notes_for_export(
+        &self,
+        tenant_id: TenantId,
+        invoicing_entity_id: InvoicingEntityId,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> StoreResult<Vec<CreditNote>> {
+        let mut conn = self.get_conn().await?;
+
+        let rows =
+            CreditNoteRow::list_for_export(&mut conn, tenant_id, invoicing_entity_id, from, to)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+
+        rows.into_iter()
+            .map(std::convert::TryInto::try_into)
+            .collect::<Result<Vec<_>, _>>()
+    }
+
     async fn save_credit_note_pdf_document(
         &self,
         credit_note_id: CreditNoteId,
@@ -1527,6 +1563,7 @@ mod tests {
             metric_id: None,
             description: None,
             group_by_dimensions: None,
+            metadata: Default::default(),
         }
     }
 
@@ -254,10 +254,12 @@ impl CustomerPaymentMethodsInterface for Store {
             PaymentMethodsConfig::Online {
                 config: online_config,
             } => {
+                // Child customers are charged against the payment method of their parent
+                let payer_id = context.parent_customer_id.unwrap_or(context.customer_id);
                 self.resolve_online_payment_method(
                     &mut conn,
                     &tenant_id,
-                    &context.customer_id,
+                    &payer_id,
                     context.card_provider_id,
                     context.direct_debit_provider_id,
                     online_config.as_ref(),
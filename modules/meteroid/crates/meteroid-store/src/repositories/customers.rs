@@ -18,6 +18,8 @@ use crate::repositories::invoicing_entities::{
 };
 use crate::repositories::pgmq::PgmqInterface;
 use crate::store::{PgConn, Store};
+use crate::utils::custom_fields::validate_custom_fields;
+use crate::utils::locale::validate_language_tag;
 use common_domain::ids::{AliasOr, BaseId, ConnectorId, CustomerId, TenantId};
 use common_eventbus::Event;
 use diesel_models::customers::{CustomerRow, CustomerRowNew, CustomerRowPatch, CustomerRowUpdate};
@@ -44,6 +46,57 @@ fn validate_customer_currency(
     Ok(())
 }
 
+fn validate_customer_language(language: Option<&str>) -> StoreResult<()> {
+    language.map_or(Ok(()), validate_language_tag)
+}
+
+/// Hierarchies are one level deep and stay within an invoicing entity, so that the
+/// invoices of a child can be issued by the same entity to its parent.
+async fn validate_customer_parent(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    customer: &CustomerRow,
+    parent_id: CustomerId,
+) -> StoreResult<()> {
+    if parent_id == customer.id {
+        bail!(StoreError::InvalidArgument(
+            "A customer cannot be its own parent".to_string()
+        ));
+    }
+
+    // Locked so that the parent cannot be archived or attached to another parent meanwhile
+    let parent = CustomerRow::select_for_update(conn, parent_id, tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+    if parent.archived_at.is_some() {
+        bail!(StoreError::InvalidArgument(
+            "The parent customer is archived".to_string()
+        ));
+    }
+    if parent.parent_customer_id.is_some() {
+        bail!(StoreError::InvalidArgument(
+            "The parent customer is itself a child of another customer".to_string()
+        ));
+    }
+    if parent.invoicing_entity_id != customer.invoicing_entity_id {
+        bail!(StoreError::InvalidArgument(
+            "The parent customer must belong to the same invoicing entity".to_string()
+        ));
+    }
+
+    let children = CustomerRow::list_children(conn, tenant_id, customer.id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+    if !children.is_empty() {
+        bail!(StoreError::InvalidArgument(
+            "A customer with child customers cannot have a parent".to_string()
+        ));
+    }
+
+    Ok(())
+}
+
 #[with_conn_delegate]
 #[async_trait::async_trait]
 pub trait CustomersInterface {
@@ -159,6 +212,17 @@ pub trait CustomersInterface {
         id_or_alias: AliasOr<CustomerId>,
     ) -> StoreResult<()>;
 
+    /// Attaches a customer to a parent account that is invoiced and charged for its
+    /// subscriptions, or detaches it when `parent_customer_id` is None.
+    async fn set_customer_parent(
+        &self,
+        actor: Actor,
+        tenant_id: TenantId,
+        customer_id: CustomerId,
+        parent_customer_id: Option<CustomerId>,
+        pool_usage_with_parent: bool,
+    ) -> StoreResult<Customer>;
+
     async fn patch_customer_conn_meta(
         &self,
         tenant_id: TenantId,
@@ -347,6 +411,8 @@ impl CustomersInterface for Store {
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
         validate_customer_currency(&customer.currency, &tenant.available_currencies)?;
+        validate_customer_language(customer.invoice_language.as_deref())?;
+        validate_custom_fields(&customer.invoice_custom_fields)?;
 
         if let Some(_ca_id) = customer.connected_account_id {
             // enterprise placeholder
@@ -529,6 +595,12 @@ impl CustomersInterface for Store {
                 .map_err(Into::<Report<StoreError>>::into)?;
             validate_customer_currency(currency, &tenant.available_currencies)?;
         }
+        if let Some(ref language) = customer.invoice_language {
+            validate_customer_language(language.as_deref())?;
+        }
+        if let Some(ref fields) = customer.invoice_custom_fields {
+            validate_custom_fields(fields)?;
+        }
 
         let is_valid_vat_number_format = customer.is_valid_vat_number_format();
         // A patch that touches vat_number resets external validation for the new value.
@@ -622,6 +694,8 @@ impl CustomersInterface for Store {
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
         validate_customer_currency(&customer.currency, &tenant.available_currencies)?;
+        validate_customer_language(customer.invoice_language.as_deref())?;
+        validate_custom_fields(&customer.invoice_custom_fields)?;
 
         let by_id_or_alias =
             CustomerRow::find_by_id_or_alias(&mut conn, tenant_id, customer.id_or_alias.clone())
@@ -632,6 +706,20 @@ impl CustomersInterface for Store {
             .get_invoicing_entity(tenant_id, Some(customer.invoicing_entity_id))
             .await?;
 
+        if invoicing_entity.id != by_id_or_alias.invoicing_entity_id {
+            let in_hierarchy = by_id_or_alias.parent_customer_id.is_some()
+                || !CustomerRow::list_children(&mut conn, tenant_id, by_id_or_alias.id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?
+                    .is_empty();
+            if in_hierarchy {
+                bail!(StoreError::InvalidArgument(
+                    "Cannot change the invoicing entity of a customer belonging to a hierarchy"
+                        .to_string()
+                ));
+            }
+        }
+
         let vat_number_format_valid = customer.is_valid_vat_number_format();
 
         // Reset external validation only when the number actually changed; otherwise
@@ -680,6 +768,19 @@ impl CustomersInterface for Store {
             vat_number_validation_status,
             vat_number_checked_at,
             vat_number_vies_check,
+            custom_attributes: serde_json::to_value(&customer.custom_attributes).map_err(|e| {
+                StoreError::SerdeError("Failed to serialize custom_attributes".to_string(), e)
+            })?,
+            invoice_language: customer.invoice_language,
+            invoice_custom_fields: serde_json::to_value(&customer.invoice_custom_fields).map_err(
+                |e| {
+                    StoreError::SerdeError(
+                        "Failed to serialize invoice_custom_fields".to_string(),
+                        e,
+                    )
+                },
+            )?,
+            invoice_approval_required: customer.invoice_approval_required,
         };
 
         let updated = self
@@ -766,6 +867,20 @@ impl CustomersInterface for Store {
         self.transaction(|conn| {
             let actor = &actor;
             async move {
+                // Locked so that no child can be attached concurrently
+                CustomerRow::select_for_update(conn, customer_id, tenant_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                let children = CustomerRow::list_children(conn, tenant_id, customer_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+                if children.iter().any(|c| c.archived_at.is_none()) {
+                    bail!(StoreError::InvalidArgument(
+                        "Cannot archive a customer with child customers. Detach or archive them before archiving.".to_string()
+                    ));
+                }
+
                 CustomerRow::archive(conn, customer_id, tenant_id)
                     .await
                     .map_err(Into::<Report<StoreError>>::into)?;
@@ -824,6 +939,56 @@ impl CustomersInterface for Store {
         .await
     }
 
+    async fn set_customer_parent(
+        &self,
+        actor: Actor,
+        tenant_id: TenantId,
+        customer_id: CustomerId,
+        parent_customer_id: Option<CustomerId>,
+        pool_usage_with_parent: bool,
+    ) -> StoreResult<Customer> {
+        let updated = self
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    let customer = CustomerRow::find_by_id(conn, &customer_id, &tenant_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?;
+
+                    if let Some(parent_id) = parent_customer_id {
+                        validate_customer_parent(conn, tenant_id, &customer, parent_id).await?;
+                    }
+
+                    let updated: Customer = CustomerRow::set_parent(
+                        conn,
+                        customer.id,
+                        tenant_id,
+                        parent_customer_id,
+                        parent_customer_id.is_some() && pool_usage_with_parent,
+                    )
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?
+                    .try_into()?;
+
+                    let outbox_events = vec![OutboxEvent::customer_updated(updated.clone().into())];
+                    self.internal
+                        .record_outbox_batch_tx(conn, tenant_id, actor, outbox_events)
+                        .await?;
+
+                    Ok(updated)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        let _ = self
+            .eventbus
+            .publish(Event::customer_updated(actor, updated.id, tenant_id))
+            .await;
+
+        Ok(updated)
+    }
+
     async fn patch_customer_conn_meta(
         &self,
         tenant_id: TenantId,
@@ -1044,9 +1209,11 @@ impl Store {
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
 
-        // Validate all currencies upfront
+        // Validate all currencies and languages upfront
         for customer in &batch {
             validate_customer_currency(&customer.currency, &tenant.available_currencies)?;
+            validate_customer_language(customer.invoice_language.as_deref())?;
+            validate_custom_fields(&customer.invoice_custom_fields)?;
         }
 
         let invoicing_entities = self.list_invoicing_entities(tenant_id).await?;
@@ -1108,7 +1275,10 @@ impl Store {
         let mut failures = Vec::new();
 
         for (idx, c) in batch.into_iter().enumerate() {
-            if let Err(e) = validate_customer_currency(&c.currency, &tenant.available_currencies) {
+            if let Err(e) = validate_customer_currency(&c.currency, &tenant.available_currencies)
+                .and_then(|_| validate_customer_language(c.invoice_language.as_deref()))
+                .and_then(|_| validate_custom_fields(&c.invoice_custom_fields))
+            {
                 failures.push((idx, e.current_context().to_string()));
                 continue;
             }
@@ -18,8 +18,8 @@ use crate::repositories::pgmq::PgmqInterface;
 use crate::store::Store;
 use crate::{StoreResult, domain};
 use common_domain::ids::{
-    AliasOr, BaseId, ConnectorId, CustomerId, EventId, InvoiceId, StoredDocumentId, SubscriptionId,
-    TenantId,
+    AliasOr, BaseId, ConnectorId, CustomerId, EventId, InvoiceId, InvoicingEntityId,
+    StoredDocumentId, SubscriptionId, TenantId,
 };
 use diesel_models::PgConn;
 use diesel_models::customer_balance_txs::CustomerBalancePendingTxRow;
@@ -108,6 +108,15 @@ pub trait InvoiceInterface {
 
     async fn list_invoices_by_ids(&self, ids: Vec<InvoiceId>) -> StoreResult<Vec<Invoice>>;
 
+    /// Issued invoices of an invoicing entity dated within `[from, to]`, for accounting exports.
+    async fn list_invoices_for_export(
+        &self,
+        tenant_id: TenantId,
+        invoicing_entity_id: InvoicingEntityId,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> StoreResult<Vec<Invoice>>;
+
     async fn list_detailed_invoices_by_ids(
         &self,
         ids: Vec<InvoiceId>,
@@ -371,6 +380,26 @@ impl InvoiceInterface for Store {
             .collect::<Result<Vec<_>, _>>()
     }
 
+    async fn list_invoices_for_export(
+        &self,
+        tenant_id: TenantId,
+        invoicing_entity_id: InvoicingEntityId,
+        from: chrono::NaiveDate,
+        to: chrono::NaiveDate,
+    ) -> StoreResult<Vec<Invoice>> {
+        let mut conn = self.get_conn().await?;
+
+        let invoices =
+            InvoiceRow::list_for_export(&mut conn, tenant_id, invoicing_entity_id, from, to)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+
+        invoices
+            .into_iter()
+            .map(std::convert::TryInto::try_into)
+            .collect::<Result<Vec<_>, _>>()
+    }
+
     async fn list_detailed_invoices_by_ids(
         &self,
         ids: Vec<InvoiceId>,
@@ -654,6 +683,7 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
     log::info!("Processing MRR logs for invoice {}", inserted.id);
     if inserted.invoice_type == InvoiceType::Recurring
         || inserted.invoice_type == InvoiceType::Adjustment
+        || inserted.invoice_type == InvoiceType::Imported
     {
         // A consolidated invoice has no subscription_id; its members already logged their MRR.
         let subscription_id = match inserted.subscription_id {
@@ -661,7 +691,7 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
             None => return Ok(()),
         };
 
-        let subscription_events = diesel_models::subscription_events::SubscriptionEventRow::fetch_by_subscription_id_and_date(
+        let mut subscription_events = diesel_models::subscription_events::SubscriptionEventRow::fetch_by_subscription_id_and_date(
             conn,
             subscription_id,
             inserted.invoice_date,
@@ -669,6 +699,19 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
 
+        // A migrated subscription may have events (its creation at the original start_date) that
+        // predate its first invoice in Meteroid. They are logged at their own date with this
+        // invoice, so that the MRR history goes back to the original start.
+        subscription_events.extend(
+            diesel_models::subscription_events::SubscriptionEventRow::fetch_unlogged_imported_before_date(
+                conn,
+                subscription_id,
+                inserted.invoice_date,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?,
+        );
+
         let mut mrr_logs = vec![];
 
         for event in subscription_events {
@@ -691,7 +734,7 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
                 }
                 SubscriptionEventType::Cancelled => MrrMovementType::Churn,
                 SubscriptionEventType::Reactivated => MrrMovementType::Reactivation,
-                SubscriptionEventType::Updated => {
+                SubscriptionEventType::Updated | SubscriptionEventType::Renewed => {
                     if mrr_delta > 0 {
                         MrrMovementType::Expansion
                     } else {
@@ -708,6 +751,7 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
                 SubscriptionEventType::Cancelled => "Subscription cancelled",
                 SubscriptionEventType::Reactivated => "Subscription reactivated",
                 SubscriptionEventType::Updated => "Subscription updated",
+                SubscriptionEventType::Renewed => "Subscription renewed",
             };
 
             if let Some(plan_version_id) = inserted.plan_version_id {
@@ -717,32 +761,40 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
                     movement_type: movement_type.clone(),
                     net_mrr_change: mrr_delta,
                     currency: inserted.currency.clone(),
-                    applies_to: inserted.invoice_date,
+                    applies_to: event.applies_to,
                     invoice_id: inserted.id,
                     credit_note_id: None,
                     plan_version_id,
                     tenant_id: inserted.tenant_id,
                 };
 
-                mrr_logs.push((new_log, movement_type));
+                mrr_logs.push((new_log, movement_type, event.id));
             }
         }
 
-        let mrr_delta_cents: i64 = mrr_logs.iter().map(|(l, _)| l.net_mrr_change).sum();
-
-        // Get historical rate for USD conversion
-        let rates =
-            get_historical_rate_from_usd_by_date_cached(conn, inserted.invoice_date).await?;
+        let mrr_delta_cents: i64 = mrr_logs.iter().map(|(l, _, _)| l.net_mrr_change).sum();
 
         // Insert MRR movement logs
-        let logs_to_insert: Vec<_> = mrr_logs.iter().map(|(l, _)| (*l).clone()).collect();
+        let logs_to_insert: Vec<_> = mrr_logs.iter().map(|(l, _, _)| (*l).clone()).collect();
         diesel_models::bi::BiMrrMovementLogRow::insert_movement_log_batch(conn, logs_to_insert)
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
 
+        // Link the events to their movement log, so they are not logged again
+        for (log, _, event_id) in &mrr_logs {
+            diesel_models::subscription_events::SubscriptionEventRow::update_mrr_movement_log_id(
+                conn, *event_id, log.id,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+        }
+
         // Aggregate into bi_delta_mrr_daily (replaces trigger)
-        if let Some(rates) = rates {
-            for (log, movement_type) in &mrr_logs {
+        for (log, movement_type, _) in &mrr_logs {
+            // Get historical rate for USD conversion
+            let rates = get_historical_rate_from_usd_by_date_cached(conn, log.applies_to).await?;
+
+            if let Some(rates) = rates {
                 let rate = rates.rates.get(&log.currency).copied().unwrap_or(1.0);
                 // Convert to USD with full Decimal precision (stored as NUMERIC(20,4))
                 let mrr_change_usd = if rate != 0.0 {
@@ -767,12 +819,12 @@ async fn process_mrr(inserted: &Invoice, conn: &mut PgConn) -> StoreResult<()> {
                 )
                 .await
                 .map_err(Into::<Report<StoreError>>::into)?;
+            } else {
+                log::warn!(
+                    "No historical rates found for date {}, MRR USD values will be zero",
+                    log.applies_to
+                );
             }
-        } else {
-            log::warn!(
-                "No historical rates found for date {}, MRR USD values will be zero",
-                inserted.invoice_date
-            );
         }
 
         SubscriptionRow::update_subscription_mrr_delta(conn, subscription_id, mrr_delta_cents)
@@ -796,12 +848,53 @@ pub async fn insert_invoice_tx(
 async fn insert_invoice_batch_tx(
     store: &Store,
     tx: &mut PgConn,
-    invoice: Vec<InvoiceNew>,
+    mut invoice: Vec<InvoiceNew>,
 ) -> StoreResult<Vec<Invoice>> {
-    // Check if any customers are archived before creating invoices
     use diesel_models::customers::CustomerRow;
     use itertools::Itertools;
 
+    // Subscription invoices of child customers are addressed to their parent
+    let subscriber_ids: Vec<CustomerId> = invoice
+        .iter()
+        .filter(|inv| inv.subscription_id.is_some())
+        .map(|inv| inv.customer_id)
+        .unique()
+        .collect();
+
+    if let Some(first) = invoice.first()
+        && !subscriber_ids.is_empty()
+    {
+        let tenant_id = first.tenant_id;
+        let parents: HashMap<CustomerId, CustomerId> =
+            CustomerRow::find_parent_ids(tx, tenant_id, subscriber_ids)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .into_iter()
+                .collect();
+
+        let mut payers: HashMap<CustomerId, domain::Customer> = HashMap::new();
+        for parent_id in parents.values().unique() {
+            let payer: domain::Customer = CustomerRow::find_by_id(tx, parent_id, &tenant_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?;
+            payers.insert(*parent_id, payer);
+        }
+
+        for inv in invoice
+            .iter_mut()
+            .filter(|inv| inv.subscription_id.is_some())
+        {
+            if let Some(payer) = parents.get(&inv.customer_id).and_then(|p| payers.get(p)) {
+                inv.customer_id = payer.id;
+                if inv.customer_details.id != payer.id {
+                    inv.customer_details = payer.clone().into();
+                }
+            }
+        }
+    }
+
+    // Check if any customers are archived before creating invoices
     let customer_ids: Vec<CustomerId> =
         invoice.iter().map(|inv| inv.customer_id).unique().collect();
 
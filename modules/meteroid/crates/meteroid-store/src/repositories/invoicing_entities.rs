@@ -8,6 +8,7 @@ use crate::domain::{
 };
 use crate::errors::StoreError;
 use crate::repositories::connectors::ConnectorsInterface;
+use crate::services::utils::{NumberSeries, carry_over_number_sequences};
 use crate::store::{PgConn, Store, StoreInternal};
 use common_domain::country::CountryCode;
 use common_domain::ids::{BaseId, InvoicingEntityId, OrganizationId, TenantId};
@@ -17,7 +18,8 @@ use diesel_models::invoicing_entities::{
 };
 use diesel_models::organizations::OrganizationRow;
 use diesel_models::tenants::TenantRow;
-use error_stack::Report;
+use diesel_models::users::UserRow;
+use error_stack::{Report, bail};
 use meteroid_store_macros::with_conn_delegate;
 use scoped_futures::ScopedFutureExt;
 
@@ -174,15 +176,64 @@ impl InvoicingEntityInterface for Store {
             }
         }
 
+        if let Some(Some(threshold)) = row.invoice_approval_threshold
+            && threshold < 0
+        {
+            bail!(StoreError::InvalidArgument(
+                "invoice approval threshold cannot be negative".to_string()
+            ));
+        }
+        if let Some(approver_ids) = &row.invoice_approver_ids {
+            let members = UserRow::list_by_tenant_id(&mut conn, tenant_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+            if let Some(unknown) = approver_ids
+                .iter()
+                .flatten()
+                .find(|id| !members.iter().any(|m| m.id == **id))
+            {
+                bail!(StoreError::InvalidArgument(format!(
+                    "approver {unknown} is not a member of this organization"
+                )));
+            }
+        }
+
         let res: InvoicingEntityRow = self
             .transaction(|conn| {
                 let actor = &actor;
                 async move {
+                    // locked as for numbering, the sequences being carried over a reset change
+                    let previous = InvoicingEntityRow::select_for_update_by_id_and_tenant(
+                        conn, entity_id, tenant_id,
+                    )
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
                     let res = row
                         .patch_invoicing_entity(conn, tenant_id)
                         .await
                         .map_err(Into::<Report<StoreError>>::into)?;
 
+                    // validated once patched, as the pattern and its reset can change separately
+                    let patched = InvoicingEntity::from(res.clone());
+                    patched.validate_number_patterns()?;
+                    patched.validate_invoice_translations()?;
+
+                    let today = chrono::Utc::now().date_naive();
+                    for series in NumberSeries::ALL {
+                        let reset_changed = match series {
+                            NumberSeries::Invoice => {
+                                previous.invoice_number_reset != res.invoice_number_reset
+                            }
+                            NumberSeries::CreditNote => {
+                                previous.credit_note_number_reset != res.credit_note_number_reset
+                            }
+                        };
+                        if reset_changed {
+                            carry_over_number_sequences(conn, &res, series, today).await?;
+                        }
+                    }
+
                     let activity = Activity::new(
                         ActivityType::EntityUpdated,
                         EntityType::InvoicingEntity,
@@ -344,8 +395,22 @@ impl StoreInternal {
             require_billing_information: invoicing_entity.require_billing_information,
             portal_theme_mode: invoicing_entity.portal_theme_mode.clone(),
             portal_roundness: invoicing_entity.portal_roundness.clone(),
+            invoice_number_reset: invoicing_entity.invoice_number_reset.unwrap_or_default(),
+            credit_note_number_pattern: invoicing_entity
+                .credit_note_number_pattern
+                .unwrap_or("CN-{number:6}".to_string()),
+            credit_note_number_reset: invoicing_entity
+                .credit_note_number_reset
+                .unwrap_or_default(),
+            invoice_translations: invoicing_entity.invoice_translations,
+            invoice_template: None,
+            invoice_approval_threshold: None,
+            invoice_approver_ids: vec![],
         };
 
+        entity.validate_number_patterns()?;
+        entity.validate_invoice_translations()?;
+
         let row: InvoicingEntityRow = entity.into();
 
         let invoicing_entity_row = row
@@ -3,7 +3,7 @@ use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput,
 use crate::store::{PgConn, Store};
 
 use crate::domain::entitlements::Entitlement;
-use crate::domain::price_components::FeeType;
+use crate::domain::price_components::{FeeType, SubLinePresentation};
 use crate::domain::prices::{
     LegacyPricingData, extract_fee_structure, extract_legacy_pricing, extract_pricing,
 };
@@ -16,6 +16,7 @@ use crate::domain::{
 use crate::errors::StoreError;
 use crate::repositories::entitlements::insert_entitlement_specs;
 use crate::repositories::price_components::resolve_component_internal;
+use crate::utils::custom_fields::CustomFields;
 use common_domain::ids::{
     BaseId, PlanId, PlanVersionId, PriceComponentId, ProductFamilyId, ProductId, TenantId,
 };
@@ -402,6 +403,8 @@ impl PlansInterface for Store {
                                 plan_version_id: inserted_plan_version_new.id,
                                 name: p.name.clone(),
                                 product_id: Some(product_id),
+                                sub_line_presentation: SubLinePresentation::default(),
+                                line_metadata: CustomFields::new(),
                             }
                             .try_into()?;
 
@@ -853,6 +856,7 @@ impl PlansInterface for Store {
                                     })?,
                                     tenant_id: auth_tenant_id,
                                     catalog: true,
+                                    variants: serde_json::Value::Array(vec![]),
                                 }
                                 .insert(conn)
                                 .await
@@ -1383,6 +1387,7 @@ impl PlansInterface for Store {
         publish: bool,
     ) -> StoreResult<FullPlan> {
         let mut conn = self.get_conn().await?;
+        let version_entitlements = version.entitlements.clone();
 
         // Load plan metadata (version-agnostic) to get draft_version_id and product_family_id
         let plan_overview: PlanOverview =
@@ -1498,6 +1503,8 @@ impl PlansInterface for Store {
                                 plan_version_id: inserted_version.id,
                                 name: p.name.clone(),
                                 product_id: Some(product_id),
+                                sub_line_presentation: SubLinePresentation::default(),
+                                line_metadata: CustomFields::new(),
                             }
                             .try_into()?;
 
@@ -1519,6 +1526,16 @@ impl PlansInterface for Store {
                         }
                     }
 
+                    if !version_entitlements.is_empty() {
+                        insert_entitlement_specs(
+                            conn,
+                            version_entitlements.clone(),
+                            EntitlementEntityId::PlanVersion(inserted_version.id),
+                            tenant_id,
+                        )
+                        .await?;
+                    }
+
                     // Attach add-ons
                     for add_on in &add_on_attachments {
                         let row_new: diesel_models::plan_version_add_ons::PlanVersionAddOnRowNew =
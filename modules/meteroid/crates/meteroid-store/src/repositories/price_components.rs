@@ -2,10 +2,12 @@ use crate::StoreResult;
 use crate::domain::Price;
 use crate::domain::price_components::{
     PriceComponent, PriceComponentNew, PriceComponentNewInternal, PriceEntry, ProductRef,
+    SubLinePresentation, line_metadata_to_json, sub_line_presentation_to_json,
 };
-use crate::domain::prices::{LegacyPricingData, extract_legacy_pricing};
+use crate::domain::prices::{LegacyPricingData, extract_legacy_pricing, price_variants_to_json};
 use crate::errors::StoreError;
 use crate::store::{PgConn, Store};
+use crate::utils::custom_fields::{CustomFields, validate_custom_fields};
 use common_domain::ids::{
     BaseId, PlanVersionId, PriceComponentId, PriceId, ProductFamilyId, ProductId, TenantId,
 };
@@ -115,6 +117,7 @@ pub async fn resolve_component_internal(
                     pricing: pricing_json,
                     tenant_id,
                     catalog,
+                    variants: price_variants_to_json(&input.pricing, &input.variants)?,
                 }
                 .insert(conn)
                 .await
@@ -141,6 +144,13 @@ pub trait PriceComponentInterface {
         id: PriceComponentId,
     ) -> StoreResult<PriceComponent>;
 
+    /// Sub-line presentation of the given components, for those that are not detailed.
+    async fn list_sub_line_presentations(
+        &self,
+        tenant_id: TenantId,
+        ids: Vec<PriceComponentId>,
+    ) -> StoreResult<HashMap<PriceComponentId, SubLinePresentation>>;
+
     async fn create_price_component(
         &self,
         price_component: PriceComponentNew,
@@ -186,11 +196,14 @@ pub trait PriceComponentInterface {
     /// Create a price component from high-level ProductRef + PriceEntry.
     /// Resolves the product (creates if New) and prices (creates if New),
     /// then creates the component row and join rows.
+    #[allow(clippy::too_many_arguments)]
     async fn create_price_component_from_ref(
         &self,
         name: String,
         product_ref: ProductRef,
         price_entries: Vec<PriceEntry>,
+        sub_line_presentation: SubLinePresentation,
+        line_metadata: CustomFields,
         plan_version_id: PlanVersionId,
         tenant_id: TenantId,
     ) -> StoreResult<PriceComponent>;
@@ -297,6 +310,20 @@ impl PriceComponentInterface for Store {
             .and_then(TryInto::try_into)
     }
 
+    async fn list_sub_line_presentations(
+        &self,
+        tenant_id: TenantId,
+        ids: Vec<PriceComponentId>,
+    ) -> StoreResult<HashMap<PriceComponentId, SubLinePresentation>> {
+        if ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let mut conn = self.get_conn().await?;
+
+        list_sub_line_presentations_tx(&mut conn, tenant_id, &ids).await
+    }
+
     async fn create_price_component(
         &self,
         price_component: PriceComponentNew,
@@ -343,6 +370,7 @@ impl PriceComponentInterface for Store {
                     pricing: pricing_json,
                     tenant_id,
                     catalog: true,
+                    variants: price_variants_to_json(&pi.pricing, &pi.variants)?,
                 })
             })
             .collect::<Result<Vec<_>, Report<StoreError>>>()?;
@@ -432,6 +460,8 @@ impl PriceComponentInterface for Store {
         tenant_id: TenantId,
         plan_version_id: PlanVersionId,
     ) -> StoreResult<Option<PriceComponent>> {
+        validate_custom_fields(&price_component.line_metadata)?;
+
         let mut conn = self.get_conn().await?;
         let price_component_row = PriceComponentRow {
             id: price_component.id,
@@ -440,6 +470,10 @@ impl PriceComponentInterface for Store {
             product_id: price_component.product_id,
             legacy_fee: None,
             billable_metric_id: None,
+            sub_line_presentation: sub_line_presentation_to_json(
+                price_component.sub_line_presentation,
+            )?,
+            line_metadata: line_metadata_to_json(&price_component.line_metadata)?,
         };
         let updated = price_component_row
             .update(&mut conn, tenant_id)
@@ -482,6 +516,8 @@ impl PriceComponentInterface for Store {
             ))
         })?;
 
+        validate_custom_fields(&price_component.line_metadata)?;
+
         let component_id = price_component.id;
         let pc_row = PriceComponentRow {
             id: component_id,
@@ -490,6 +526,10 @@ impl PriceComponentInterface for Store {
             product_id: price_component.product_id,
             legacy_fee: None,
             billable_metric_id: None,
+            sub_line_presentation: sub_line_presentation_to_json(
+                price_component.sub_line_presentation,
+            )?,
+            line_metadata: line_metadata_to_json(&price_component.line_metadata)?,
         };
 
         let price_rows_new: Vec<PriceRowNew> = prices
@@ -509,6 +549,7 @@ impl PriceComponentInterface for Store {
                     pricing: pricing_json,
                     tenant_id,
                     catalog: true,
+                    variants: price_variants_to_json(&pi.pricing, &pi.variants)?,
                 })
             })
             .collect::<Result<Vec<_>, Report<StoreError>>>()?;
@@ -579,11 +620,14 @@ impl PriceComponentInterface for Store {
         .await
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn create_price_component_from_ref(
         &self,
         name: String,
         product_ref: ProductRef,
         price_entries: Vec<PriceEntry>,
+        sub_line_presentation: SubLinePresentation,
+        line_metadata: CustomFields,
         plan_version_id: PlanVersionId,
         tenant_id: TenantId,
     ) -> StoreResult<PriceComponent> {
@@ -592,6 +636,9 @@ impl PriceComponentInterface for Store {
             product_ref,
             prices: price_entries,
         };
+        let sub_line_presentation = sub_line_presentation_to_json(sub_line_presentation)?;
+        validate_custom_fields(&line_metadata)?;
+        let line_metadata = line_metadata_to_json(&line_metadata)?;
 
         self.transaction(|conn| {
             async move {
@@ -622,6 +669,8 @@ impl PriceComponentInterface for Store {
                     plan_version_id,
                     product_id: Some(product_id),
                     billable_metric_id: None,
+                    sub_line_presentation,
+                    line_metadata,
                 };
                 let inserted = component_row_new
                     .insert(conn)
@@ -659,3 +708,22 @@ impl PriceComponentInterface for Store {
         .await
     }
 }
+
+pub(crate) async fn list_sub_line_presentations_tx(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    ids: &[PriceComponentId],
+) -> StoreResult<HashMap<PriceComponentId, SubLinePresentation>> {
+    let rows = PriceComponentRow::list_by_ids(conn, tenant_id, ids)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+    let mut presentations = HashMap::new();
+    for row in rows {
+        let presentation: SubLinePresentation = row.sub_line_presentation.try_into()?;
+        if presentation != SubLinePresentation::Detailed {
+            presentations.insert(row.id, presentation);
+        }
+    }
+    Ok(presentations)
+}
@@ -1,10 +1,17 @@
 use crate::StoreResult;
 use crate::domain::Price;
+use crate::domain::prices::{PriceVariant, Pricing, price_variants_to_json};
 use crate::errors::StoreError;
 use crate::store::Store;
-use common_domain::ids::{PriceId, ProductId, TenantId};
-use diesel_models::prices::PriceRow;
+use common_domain::ids::{BaseId, PriceId, ProductId, TenantId};
+use diesel_models::add_ons::AddOnRow;
+use diesel_models::enums::PriceChangeStatusEnum;
+use diesel_models::plan_component_prices::PlanComponentPriceRow;
+use diesel_models::plan_version_add_ons::PlanVersionAddOnRow;
+use diesel_models::price_changes::PriceChangeRow;
+use diesel_models::prices::{PriceRow, PriceRowNew};
 use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
 
 #[async_trait::async_trait]
 pub trait PriceInterface {
@@ -18,6 +25,16 @@ pub trait PriceInterface {
         ids: &[PriceId],
         tenant_id: TenantId,
     ) -> StoreResult<Vec<Price>>;
+    /// Replaces the variants of a price. Prices referenced by subscriptions are never mutated:
+    /// a new price carrying the variants replaces the current one in the catalog, which is
+    /// archived. Existing subscriptions stay on the archived price and are billed as before,
+    /// the variants only apply to new subscriptions and plan changes.
+    async fn update_price_variants(
+        &self,
+        price_id: PriceId,
+        tenant_id: TenantId,
+        variants: Vec<PriceVariant>,
+    ) -> StoreResult<Price>;
 }
 
 #[async_trait::async_trait]
@@ -49,4 +66,83 @@ impl PriceInterface for Store {
 
         rows.into_iter().map(Price::try_from).collect()
     }
+
+    async fn update_price_variants(
+        &self,
+        price_id: PriceId,
+        tenant_id: TenantId,
+        variants: Vec<PriceVariant>,
+    ) -> StoreResult<Price> {
+        self.transaction(|conn| {
+            async move {
+                let current = PriceRow::find_by_id_and_tenant_id(conn, price_id, tenant_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                if current.archived_at.is_some() {
+                    return Err(Report::new(StoreError::InvalidArgument(
+                        "Cannot update the variants of an archived price".to_string(),
+                    )));
+                }
+
+                // A scheduled change copies the variants of the price it replaces and swaps
+                // that price out of the catalog, it would silently undo the update.
+                let pending_change =
+                    PriceChangeRow::list_by_product_id(conn, current.product_id, tenant_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?
+                        .into_iter()
+                        .any(|c| {
+                            c.price_id == price_id && c.status == PriceChangeStatusEnum::Scheduled
+                        });
+                if pending_change {
+                    return Err(Report::new(StoreError::InvalidArgument(
+                        "Cannot update the variants of a price with a scheduled price change"
+                            .to_string(),
+                    )));
+                }
+
+                let pricing: Pricing =
+                    serde_json::from_value(current.pricing.clone()).map_err(|e| {
+                        Report::new(StoreError::SerdeError(
+                            "Failed to deserialize Pricing".to_string(),
+                            e,
+                        ))
+                    })?;
+                let variants = price_variants_to_json(&pricing, &variants)?;
+
+                let new_price = PriceRowNew {
+                    id: PriceId::new(),
+                    product_id: current.product_id,
+                    cadence: current.cadence,
+                    currency: current.currency,
+                    pricing: current.pricing,
+                    tenant_id,
+                    catalog: current.catalog,
+                    variants,
+                }
+                .insert(conn)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+
+                PlanComponentPriceRow::replace_price_id(conn, price_id, new_price.id, tenant_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+                PlanVersionAddOnRow::replace_price_id(conn, price_id, new_price.id, tenant_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+                AddOnRow::replace_price_id(conn, price_id, new_price.id, tenant_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                PriceRow::archive(conn, price_id, tenant_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                new_price.try_into()
+            }
+            .scope_boxed()
+        })
+        .await
+    }
 }
@@ -15,6 +15,7 @@ use crate::errors::{StoreError, StoreErrorReport};
 use crate::jwt_claims::{ResourceAccess, generate_portal_token};
 use crate::repositories::pgmq::PgmqInterface;
 use crate::store::Store;
+use crate::utils::locale::document_language;
 use common_domain::ids::{
     BaseId, CustomerId, EntitlementEntityId, QuoteId, QuotePriceComponentId, StoredDocumentId,
     TenantId, UserId,
@@ -313,6 +314,7 @@ impl QuotesInterface for Store {
                 let resolved = resolve_fee_from_maps(
                     row.price_id,
                     row.product_id,
+                    row.legacy_fee.as_ref(),
                     &prices_by_id,
                     &products_by_id,
                 );
@@ -341,6 +343,7 @@ impl QuotesInterface for Store {
                 let resolved = resolve_fee_from_maps(
                     row.price_id,
                     row.product_id,
+                    row.legacy_fee.as_ref(),
                     &prices_by_id,
                     &products_by_id,
                 );
@@ -1064,6 +1067,11 @@ impl QuotesInterface for Store {
                     .into());
                 }
 
+                let lang = document_language(
+                    customer.invoice_language.as_deref(),
+                    &invoicing_entity.country,
+                );
+
                 // Generate one email request per recipient, each with their own JWT token
                 let mut email_messages = Vec::new();
                 for recipient in &recipients {
@@ -1087,6 +1095,7 @@ impl QuotesInterface for Store {
                         quote_number: quote.quote_number.clone(),
                         expires_at: quote.expires_at.map(|dt| dt.date()),
                         company_name: invoicing_entity.legal_name.clone(),
+                        lang: Some(lang.clone()),
                         logo_attachment_id: invoicing_entity.logo_attachment_id,
                         recipient_emails: vec![recipient.email.clone()],
                         portal_url,
@@ -2,7 +2,10 @@ use crate::StoreResult;
 use crate::domain::enums::SubscriptionFeeBillingPeriod;
 use crate::domain::prices::{Price, fee_type_billing_period, resolve_subscription_fee};
 use crate::domain::products::Product;
-use crate::domain::subscriptions::{AmendmentSummary, PaymentMethodsConfig, PendingScheduledEvent};
+use crate::domain::subscriptions::{
+    AmendmentSummary, PauseBilling, PaymentMethodsConfig, PendingScheduledEvent,
+    ResumeBillingAnchor,
+};
 use crate::domain::{
     BillableMetric, ConnectorProviderEnum, Customer, InvoicingEntity, PaginatedVec,
     PaginationRequest, Schedule, Subscription, SubscriptionComponent, SubscriptionComponentNew,
@@ -18,6 +21,7 @@ use std::collections::HashMap;
 use crate::errors::StoreError;
 use crate::services::validate_charge_automatically_with_provider_ids;
 use crate::store::Store;
+use crate::utils::custom_fields::validate_custom_fields;
 use error_stack::{Report, bail};
 use itertools::Itertools;
 
@@ -56,6 +60,20 @@ pub enum CancellationEffectiveAt {
     Date(NaiveDate),
 }
 
+pub enum PauseEffectiveAt {
+    EndOfBillingPeriod,
+    Date(NaiveDate),
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PauseParams {
+    pub billing: PauseBilling,
+    /// Automatically resume the subscription on that date.
+    pub resume_on: Option<NaiveDate>,
+    /// Anchor applied by the automatic resumption.
+    pub resume_anchor: ResumeBillingAnchor,
+}
+
 #[with_conn_delegate]
 pub trait SubscriptionInterface {
     #[delegated]
@@ -178,6 +196,8 @@ impl SubscriptionInterface for Store {
                 .map_err(Into::<Report<StoreError>>::into)?
                 .try_into()?;
 
+        let payer = find_payer(conn, tenant_id, &customer).await?;
+
         let invoicing_entity: InvoicingEntity =
             InvoicingEntityRow::get_invoicing_entity_by_id_and_tenant(
                 conn,
@@ -191,6 +211,7 @@ impl SubscriptionInterface for Store {
         Ok(SubscriptionDetails {
             subscription,
             customer,
+            payer,
             invoicing_entity,
             schedules: vec![],
             price_components: vec![],
@@ -286,6 +307,7 @@ impl SubscriptionInterface for Store {
                 let resolved = resolve_fee_from_maps(
                     row.price_id,
                     row.product_id,
+                    row.legacy_fee.as_ref(),
                     &prices_by_id,
                     &products_by_id,
                 );
@@ -316,6 +338,7 @@ impl SubscriptionInterface for Store {
                 let resolved = resolve_fee_from_maps(
                     row.price_id,
                     row.product_id,
+                    row.legacy_fee.as_ref(),
                     &prices_by_id,
                     &products_by_id,
                 );
@@ -408,6 +431,8 @@ impl SubscriptionInterface for Store {
                 .map_err(Into::<Report<StoreError>>::into)?
                 .try_into()?;
 
+        let payer = find_payer(conn, tenant_id, &customer).await?;
+
         let invoicing_entity: InvoicingEntity =
             InvoicingEntityRow::get_invoicing_entity_by_id_and_tenant(
                 conn,
@@ -428,21 +453,23 @@ impl SubscriptionInterface for Store {
             if let Some(version) = plan_with_version.version {
                 if let Some(duration_days) = version.trial_duration_days {
                     if duration_days > 0 {
+                        // A trialing plan switched mid-trial takes precedence over the plan's
+                        let trialing_plan_id =
+                            subscription.trialing_plan_id.or(version.trialing_plan_id);
                         // If there's a trialing_plan_id, fetch its name
-                        let trialing_plan_name =
-                            if let Some(trialing_plan_id) = &version.trialing_plan_id {
-                                PlanRow::get_overview_by_id(conn, *trialing_plan_id, tenant_id)
-                                    .await
-                                    .ok()
-                                    .map(|p| p.name)
-                            } else {
-                                None
-                            };
+                        let trialing_plan_name = if let Some(trialing_plan_id) = &trialing_plan_id {
+                            PlanRow::get_overview_by_id(conn, *trialing_plan_id, tenant_id)
+                                .await
+                                .ok()
+                                .map(|p| p.name)
+                        } else {
+                            None
+                        };
 
                         Some(TrialConfig {
                             duration_days: duration_days as u32,
                             is_free: version.trial_is_free,
-                            trialing_plan_id: version.trialing_plan_id,
+                            trialing_plan_id,
                             trialing_plan_name,
                         })
                     } else {
@@ -564,6 +591,7 @@ impl SubscriptionInterface for Store {
             schedules,
             checkout_url,
             customer,
+            payer,
             invoicing_entity,
             trial_config,
             pending_events,
@@ -805,6 +833,10 @@ impl SubscriptionInterface for Store {
             ));
         }
 
+        if let Some(fields) = &patch.invoice_custom_fields {
+            validate_custom_fields(fields)?;
+        }
+
         let effective_charge_automatically = patch
             .charge_automatically
             .unwrap_or(existing.subscription.charge_automatically);
@@ -847,6 +879,17 @@ impl SubscriptionInterface for Store {
             net_terms: patch.net_terms.map(|n| n as i32),
             invoice_memo: patch.invoice_memo,
             purchase_order: patch.purchase_order,
+            invoice_number_prefix: patch.invoice_number_prefix,
+            invoice_custom_fields: patch
+                .invoice_custom_fields
+                .map(|fields| serde_json::to_value(&fields))
+                .transpose()
+                .map_err(|e| {
+                    StoreError::SerdeError(
+                        "Failed to serialize invoice_custom_fields".to_string(),
+                        e,
+                    )
+                })?,
             payment_methods_config: patch
                 .payment_methods_config
                 .map(|opt| {
@@ -934,17 +977,40 @@ pub(crate) async fn fetch_prices_and_products(
     Ok((prices_by_id, products_by_id))
 }
 
+/// Resolves the fee of a price-backed row from the live price. For prices with variants, the
+/// fee snapshotted on the row is kept instead: it was resolved against the customer when the
+/// row was created, while the live price only holds the base pricing.
 pub(crate) fn resolve_fee_from_maps(
     price_id: Option<PriceId>,
     product_id: Option<ProductId>,
+    snapshot: Option<&serde_json::Value>,
     prices_by_id: &HashMap<PriceId, Price>,
     products_by_id: &HashMap<ProductId, Product>,
 ) -> Option<(SubscriptionFeeBillingPeriod, crate::domain::SubscriptionFee)> {
     let price = prices_by_id.get(&price_id?)?;
     let product = products_by_id.get(&product_id?)?;
     let fee_structure = &product.fee_structure;
-    let fee = resolve_subscription_fee(fee_structure, &price.pricing, None).ok()?;
+    let fee = match snapshot {
+        Some(snapshot) if !price.variants.is_empty() => snapshot.clone().try_into().ok()?,
+        _ => resolve_subscription_fee(fee_structure, &price.pricing, None).ok()?,
+    };
     let period = fee_type_billing_period(fee_structure)
         .unwrap_or_else(|| price.cadence.as_subscription_billing_period());
     Some((period, fee))
 }
+
+/// Loads the parent account billed in place of `customer`, if any.
+pub(crate) async fn find_payer(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    customer: &Customer,
+) -> StoreResult<Option<Customer>> {
+    match customer.parent_customer_id {
+        Some(parent_id) => CustomerRow::find_by_id(conn, &parent_id, &tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?
+            .try_into()
+            .map(Some),
+        None => Ok(None),
+    }
+}
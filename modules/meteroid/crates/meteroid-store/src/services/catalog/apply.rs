@@ -0,0 +1,1186 @@
+use std::collections::{HashMap, HashSet};
+
+use common_domain::ids::{
+    AddOnId, CouponId, EntitlementEntityId, FeatureId, PlanId, ProductFamilyId, ProductId, TenantId,
+};
+use error_stack::Report;
+
+use crate::StoreResult;
+use crate::domain::add_ons::AddOnPatch;
+use crate::domain::catalog::{
+    CatalogAction, CatalogAddOn, CatalogChange, CatalogCoupon, CatalogDiff, CatalogDocument,
+    CatalogEntitlement, CatalogEntity, CatalogFeature, CatalogPlan, CatalogProduct,
+    CatalogProductFamily,
+};
+use crate::domain::coupons::{CouponNew, CouponPatch, CouponStatusPatch};
+use crate::domain::entitlements::{
+    EntitlementNew, EntitlementSpec, EntitlementUpdate, FeatureNew, FeatureType, FeatureUpdate,
+};
+use crate::domain::entity_activity::Actor;
+use crate::domain::enums::{FeatureStatusEnum, PlanStatusEnum};
+use crate::domain::plan_version_add_ons::PlanVersionAddOnNew;
+use crate::domain::price_components::{
+    PriceComponentNewInternal, PriceEntry, PriceInput, ProductRef,
+};
+use crate::domain::{
+    FullPlanNew, PlanNew, PlanTrial, PlanVersionNewInternal, ProductFamilyNew, ProductNew,
+};
+use crate::errors::StoreError;
+use crate::repositories::add_ons::AddOnInterface;
+use crate::repositories::coupons::CouponInterface;
+use crate::repositories::plan_version_add_ons::PlanVersionAddOnInterface;
+use crate::repositories::products::{ProductInterface, ProductUpdate};
+use crate::repositories::{EntitlementsInterface, PlansInterface, ProductFamilyInterface};
+
+use super::{CatalogState, CurrentPlan, Services};
+
+/// Lists the named fields that differ between two values of the same type.
+macro_rules! changed_fields {
+    ($current:expr, $desired:expr, [$($field:ident),+ $(,)?]) => {{
+        let mut changed: Vec<String> = Vec::new();
+        $(
+            if $current.$field != $desired.$field {
+                changed.push(stringify!($field).to_string());
+            }
+        )+
+        changed
+    }};
+}
+
+/// Ids known for the document keys. `None` marks an entry that is declared in the document
+/// but does not exist yet, which only happens when computing a diff.
+type KnownIds<T> = HashMap<String, Option<T>>;
+
+/// An existing entry that a pruning apply archives.
+enum Removal {
+    Plan(PlanId),
+    AddOn(AddOnId),
+    Product(ProductId),
+    Feature(FeatureId),
+    Coupon(CouponId),
+}
+
+struct Reconciler<'a> {
+    services: &'a Services,
+    tenant_id: TenantId,
+    /// `None` computes the diff without writing anything.
+    actor: Option<&'a Actor>,
+    /// Archives the entries missing from the document.
+    prune: bool,
+    state: CatalogState,
+    features: KnownIds<FeatureId>,
+    changes: Vec<CatalogChange>,
+}
+
+impl Services {
+    pub(crate) async fn diff_catalog(
+        &self,
+        tenant_id: TenantId,
+        document: &CatalogDocument,
+        prune: bool,
+    ) -> StoreResult<CatalogDiff> {
+        validate_document(document)?;
+        let state = self.load_catalog_state(tenant_id).await?;
+        Reconciler::new(self, tenant_id, None, prune, state)
+            .run(document)
+            .await
+    }
+
+    /// Creates and updates the catalog entries of the document. Plans are never modified in
+    /// place: every change produces a new draft version, to be reviewed and published as usual.
+    ///
+    /// With `prune`, the plans, add-ons and products of the families listed in the document,
+    /// and the features and coupons, that the document does not list are archived. Product
+    /// families themselves are never archived, and families missing from the document are left
+    /// untouched, so a document describing a single family cannot archive another one.
+    ///
+    /// Nothing is written when the document contains conflicts. Entries are applied one by one,
+    /// so after a failure re-applying the same document completes the remaining changes.
+    pub(crate) async fn apply_catalog(
+        &self,
+        tenant_id: TenantId,
+        actor: Actor,
+        document: &CatalogDocument,
+        prune: bool,
+    ) -> StoreResult<CatalogDiff> {
+        let diff = self.diff_catalog(tenant_id, document, prune).await?;
+
+        if diff.has_conflicts() {
+            let conflicts = diff
+                .changes
+                .iter()
+                .filter(|c| c.action == CatalogAction::Conflict)
+                .map(|c| format!("{}: {}", c.key, c.details.join(", ")))
+                .collect::<Vec<_>>();
+            return Err(Report::new(StoreError::InvalidArgument(format!(
+                "Catalog has conflicts: {}",
+                conflicts.join("; ")
+            ))));
+        }
+
+        if !diff.has_changes() {
+            return Ok(diff);
+        }
+
+        let state = self.load_catalog_state(tenant_id).await?;
+        Reconciler::new(self, tenant_id, Some(&actor), prune, state)
+            .run(document)
+            .await
+    }
+}
+
+fn validate_document(document: &CatalogDocument) -> StoreResult<()> {
+    fn unique<'a>(what: &str, keys: impl Iterator<Item = &'a String>) -> StoreResult<()> {
+        let mut seen = HashSet::new();
+        for key in keys {
+            if !seen.insert(key) {
+                return Err(Report::new(StoreError::InvalidArgument(format!(
+                    "Duplicate {what} '{key}' in catalog"
+                ))));
+            }
+        }
+        Ok(())
+    }
+
+    unique("feature", document.features.iter().map(|f| &f.code))?;
+    unique("coupon", document.coupons.iter().map(|c| &c.code))?;
+    unique(
+        "product family",
+        document.product_families.iter().map(|f| &f.name),
+    )?;
+    unique(
+        "add-on",
+        document
+            .product_families
+            .iter()
+            .flat_map(|f| f.add_ons.iter().map(|a| &a.name)),
+    )?;
+    for family in &document.product_families {
+        unique("product", family.products.iter().map(|p| &p.name))?;
+        unique("plan", family.plans.iter().map(|p| &p.name))?;
+    }
+    Ok(())
+}
+
+fn sorted_entitlements(entitlements: &[CatalogEntitlement]) -> Vec<CatalogEntitlement> {
+    let mut sorted = entitlements.to_vec();
+    sorted.sort_by(|a, b| a.feature.cmp(&b.feature));
+    sorted
+}
+
+fn normalized_plan(plan: &CatalogPlan) -> CatalogPlan {
+    let mut plan = plan.clone();
+    plan.add_ons.sort_by(|a, b| a.add_on.cmp(&b.add_on));
+    plan.entitlements = sorted_entitlements(&plan.entitlements);
+    plan
+}
+
+fn require<T>(id: Option<T>, key: &str) -> StoreResult<T> {
+    id.ok_or_else(|| {
+        Report::new(StoreError::InvalidArgument(format!(
+            "Catalog entry '{key}' was not created"
+        )))
+    })
+}
+
+impl<'a> Reconciler<'a> {
+    fn new(
+        services: &'a Services,
+        tenant_id: TenantId,
+        actor: Option<&'a Actor>,
+        prune: bool,
+        state: CatalogState,
+    ) -> Self {
+        let features = state
+            .features
+            .iter()
+            .map(|f| (f.code.clone(), Some(f.id)))
+            .collect();
+
+        Self {
+            services,
+            tenant_id,
+            actor,
+            prune,
+            state,
+            features,
+            changes: vec![],
+        }
+    }
+
+    fn record(
+        &mut self,
+        entity: CatalogEntity,
+        key: String,
+        action: CatalogAction,
+        details: Vec<String>,
+    ) {
+        self.changes.push(CatalogChange {
+            entity,
+            key,
+            action,
+            details,
+        });
+    }
+
+    async fn run(mut self, document: &CatalogDocument) -> StoreResult<CatalogDiff> {
+        for feature in &document.features {
+            self.reconcile_feature(feature).await?;
+        }
+
+        for family in &document.product_families {
+            self.reconcile_family(family).await?;
+        }
+
+        for coupon in &document.coupons {
+            self.reconcile_coupon(coupon).await?;
+        }
+
+        if self.prune {
+            for (entity, key, removal) in self.removals(document) {
+                if let Some(actor) = self.actor {
+                    self.archive(actor, removal).await?;
+                }
+                self.record(entity, key, CatalogAction::Archive, vec![]);
+            }
+        }
+
+        Ok(CatalogDiff {
+            changes: self.changes,
+        })
+    }
+
+    /// Existing entries missing from the document, dependents first: plans before the add-ons
+    /// and products they reference.
+    fn removals(&self, document: &CatalogDocument) -> Vec<(CatalogEntity, String, Removal)> {
+        let mut plans = vec![];
+        let mut add_ons = vec![];
+        let mut products = vec![];
+
+        for family in &document.product_families {
+            let Some(family_id) = self
+                .state
+                .families
+                .iter()
+                .find(|f| f.name == family.name)
+                .map(|f| f.id)
+            else {
+                continue;
+            };
+            let key = |name: &str| format!("{}/{}", family.name, name);
+
+            for plan in self.state.plans.get(&family_id).into_iter().flatten() {
+                let plan = &plan.full.plan;
+                if !family.plans.iter().any(|p| p.name == plan.name) {
+                    plans.push((CatalogEntity::Plan, key(&plan.name), Removal::Plan(plan.id)));
+                }
+            }
+
+            for add_on in &self.state.add_ons {
+                if self.state.product_family_of(add_on.product_id) == Some(family_id)
+                    && !family.add_ons.iter().any(|a| a.name == add_on.name)
+                {
+                    add_ons.push((
+                        CatalogEntity::AddOn,
+                        key(&add_on.name),
+                        Removal::AddOn(add_on.id),
+                    ));
+                }
+            }
+
+            for product in self.state.products.get(&family_id).into_iter().flatten() {
+                if !family.products.iter().any(|p| p.name == product.name) {
+                    products.push((
+                        CatalogEntity::Product,
+                        key(&product.name),
+                        Removal::Product(product.id),
+                    ));
+                }
+            }
+        }
+
+        let features = self
+            .state
+            .features
+            .iter()
+            .filter(|f| !document.features.iter().any(|d| d.code == f.code))
+            .map(|f| {
+                (
+                    CatalogEntity::Feature,
+                    f.code.clone(),
+                    Removal::Feature(f.id),
+                )
+            });
+
+        let coupons = self
+            .state
+            .coupons
+            .iter()
+            .filter(|c| !document.coupons.iter().any(|d| d.code == c.code))
+            .map(|c| (CatalogEntity::Coupon, c.code.clone(), Removal::Coupon(c.id)));
+
+        plans
+            .into_iter()
+            .chain(add_ons)
+            .chain(products)
+            .chain(features)
+            .chain(coupons)
+            .collect()
+    }
+
+    async fn archive(&self, actor: &Actor, removal: Removal) -> StoreResult<()> {
+        let store = &self.services.store;
+        match removal {
+            Removal::Plan(id) => store.archive_plan(actor.clone(), id, self.tenant_id).await,
+            Removal::AddOn(id) => {
+                store
+                    .archive_add_on(actor.clone(), id, self.tenant_id)
+                    .await
+            }
+            Removal::Product(id) => store
+                .archive_product(actor.clone(), id, self.tenant_id)
+                .await
+                .map(|_| ()),
+            Removal::Feature(id) => {
+                store
+                    .set_feature_status(id, self.tenant_id, FeatureStatusEnum::Archived)
+                    .await
+            }
+            Removal::Coupon(id) => store
+                .update_coupon_status(
+                    actor.clone(),
+                    CouponStatusPatch {
+                        id,
+                        tenant_id: self.tenant_id,
+                        archived_at: Some(Some(chrono::Utc::now().naive_utc())),
+                        disabled: None,
+                    },
+                )
+                .await
+                .map(|_| ()),
+        }
+    }
+
+    fn missing_features(&self, entitlements: &[CatalogEntitlement]) -> Vec<String> {
+        entitlements
+            .iter()
+            .filter(|e| !self.features.contains_key(&e.feature))
+            .map(|e| format!("unknown feature '{}'", e.feature))
+            .collect()
+    }
+
+    fn entitlement_specs(
+        &self,
+        entitlements: &[CatalogEntitlement],
+    ) -> StoreResult<Vec<EntitlementSpec>> {
+        entitlements
+            .iter()
+            .map(|e| {
+                let feature_id =
+                    require(self.features.get(&e.feature).copied().flatten(), &e.feature)?;
+                Ok(EntitlementSpec {
+                    feature_id,
+                    value: e.value.clone(),
+                })
+            })
+            .collect()
+    }
+
+    async fn reconcile_feature(&mut self, desired: &CatalogFeature) -> StoreResult<()> {
+        let key = desired.code.clone();
+
+        if let Some(metric) = &desired.metric
+            && !self.state.metric_ids.contains_key(metric)
+        {
+            self.record(
+                CatalogEntity::Feature,
+                key,
+                CatalogAction::Conflict,
+                vec![format!("unknown billable metric '{metric}'")],
+            );
+            return Ok(());
+        }
+
+        let existing = self
+            .state
+            .features
+            .iter()
+            .find(|f| f.code == desired.code)
+            .cloned();
+
+        let Some(existing) = existing else {
+            let mut id = None;
+            if self.actor.is_some() {
+                let feature_type = match &desired.metric {
+                    Some(metric) => FeatureType::metered(self.state.metric_ids[metric]),
+                    None => FeatureType::Boolean,
+                };
+                let created = self
+                    .services
+                    .store
+                    .create_feature(FeatureNew {
+                        tenant_id: self.tenant_id,
+                        product_id: None,
+                        name: desired.name.clone(),
+                        code: desired.code.clone(),
+                        description: desired.description.clone(),
+                        feature_type,
+                        entitlement: None,
+                    })
+                    .await?;
+                id = Some(created.id);
+            }
+            self.features.insert(key.clone(), id);
+            self.record(CatalogEntity::Feature, key, CatalogAction::Create, vec![]);
+            return Ok(());
+        };
+
+        let current = self.state.feature_to_catalog(&existing);
+        if current.metric != desired.metric {
+            self.record(
+                CatalogEntity::Feature,
+                key,
+                CatalogAction::Conflict,
+                vec!["the metric of a feature cannot be changed".to_string()],
+            );
+            return Ok(());
+        }
+
+        let changed = changed_fields!(current, desired, [name, description]);
+        if changed.is_empty() {
+            self.record(
+                CatalogEntity::Feature,
+                key,
+                CatalogAction::Unchanged,
+                vec![],
+            );
+            return Ok(());
+        }
+
+        if self.actor.is_some() {
+            self.services
+                .store
+                .update_feature(
+                    existing.id,
+                    self.tenant_id,
+                    FeatureUpdate {
+                        name: Some(desired.name.clone()),
+                        description: Some(desired.description.clone()),
+                        product_id: None,
+                    },
+                )
+                .await?;
+        }
+        self.record(CatalogEntity::Feature, key, CatalogAction::Update, changed);
+        Ok(())
+    }
+
+    async fn reconcile_family(&mut self, family: &CatalogProductFamily) -> StoreResult<()> {
+        let existing_id = self
+            .state
+            .families
+            .iter()
+            .find(|f| f.name == family.name)
+            .map(|f| f.id);
+
+        let family_id = match existing_id {
+            Some(id) => {
+                self.record(
+                    CatalogEntity::ProductFamily,
+                    family.name.clone(),
+                    CatalogAction::Unchanged,
+                    vec![],
+                );
+                Some(id)
+            }
+            None => {
+                let mut id = None;
+                if let Some(actor) = self.actor {
+                    let created = self
+                        .services
+                        .store
+                        .insert_product_family(
+                            ProductFamilyNew {
+                                name: family.name.clone(),
+                                tenant_id: self.tenant_id,
+                            },
+                            Some(actor.clone()),
+                        )
+                        .await?;
+                    id = Some(created.id);
+                }
+                self.record(
+                    CatalogEntity::ProductFamily,
+                    family.name.clone(),
+                    CatalogAction::Create,
+                    vec![],
+                );
+                id
+            }
+        };
+
+        let mut products: KnownIds<ProductId> = family_id
+            .and_then(|id| self.state.products.get(&id))
+            .into_iter()
+            .flatten()
+            .map(|p| (p.name.clone(), Some(p.id)))
+            .collect();
+        for product in &family.products {
+            let id = self.reconcile_product(family, family_id, product).await?;
+            products.insert(product.name.clone(), id);
+        }
+
+        // only add-ons of this family can be attached to its plans
+        let mut add_ons: KnownIds<AddOnId> = self
+            .state
+            .add_ons
+            .iter()
+            .filter(|a| {
+                family_id.is_some() && self.state.product_family_of(a.product_id) == family_id
+            })
+            .map(|a| (a.name.clone(), Some(a.id)))
+            .collect();
+        for add_on in &family.add_ons {
+            let id = self
+                .reconcile_add_on(family, family_id, &products, add_on)
+                .await?;
+            add_ons.insert(add_on.name.clone(), id);
+        }
+
+        let mut plans: KnownIds<PlanId> = family_id
+            .and_then(|id| self.state.plans.get(&id))
+            .into_iter()
+            .flatten()
+            .map(|p| (p.full.plan.name.clone(), Some(p.full.plan.id)))
+            .collect();
+        for plan in &family.plans {
+            plans.entry(plan.name.clone()).or_insert(None);
+        }
+        for plan in &family.plans {
+            let id = self
+                .reconcile_plan(family, family_id, &products, &add_ons, &plans, plan)
+                .await?;
+            plans.insert(plan.name.clone(), id);
+        }
+
+        Ok(())
+    }
+
+    async fn reconcile_product(
+        &mut self,
+        family: &CatalogProductFamily,
+        family_id: Option<ProductFamilyId>,
+        desired: &CatalogProduct,
+    ) -> StoreResult<Option<ProductId>> {
+        let key = format!("{}/{}", family.name, desired.name);
+
+        let fee_structure = match self.state.fee_from_catalog(&desired.fee) {
+            Ok(fee_structure) => fee_structure,
+            Err(reason) => {
+                self.record(
+                    CatalogEntity::Product,
+                    key,
+                    CatalogAction::Conflict,
+                    vec![reason],
+                );
+                return Ok(None);
+            }
+        };
+
+        let existing = family_id
+            .and_then(|id| self.state.products.get(&id))
+            .into_iter()
+            .flatten()
+            .find(|p| p.name == desired.name)
+            .cloned();
+
+        let Some(existing) = existing else {
+            let mut id = None;
+            if let Some(actor) = self.actor {
+                let created = self
+                    .services
+                    .store
+                    .create_product(
+                        actor.clone(),
+                        ProductNew {
+                            name: desired.name.clone(),
+                            description: desired.description.clone(),
+                            tenant_id: self.tenant_id,
+                            family_id: require(family_id, &family.name)?,
+                            fee_type: fee_structure.fee_type(),
+                            fee_structure,
+                            catalog: true,
+                        },
+                    )
+                    .await?;
+                id = Some(created.id);
+            }
+            self.record(CatalogEntity::Product, key, CatalogAction::Create, vec![]);
+            return Ok(id);
+        };
+
+        if existing.fee_type != fee_structure.fee_type() {
+            self.record(
+                CatalogEntity::Product,
+                key,
+                CatalogAction::Conflict,
+                vec!["the fee type of a product cannot be changed".to_string()],
+            );
+            return Ok(Some(existing.id));
+        }
+
+        let current = self.state.product_to_catalog(&existing)?;
+        let changed = changed_fields!(current, desired, [description, fee]);
+        if changed.is_empty() {
+            self.record(
+                CatalogEntity::Product,
+                key,
+                CatalogAction::Unchanged,
+                vec![],
+            );
+            return Ok(Some(existing.id));
+        }
+
+        if let Some(actor) = self.actor {
+            self.services
+                .store
+                .update_product(
+                    actor.clone(),
+                    ProductUpdate {
+                        id: existing.id,
+                        tenant_id: self.tenant_id,
+                        name: None,
+                        description: Some(desired.description.clone()),
+                        fee_type: None,
+                        fee_structure: Some(fee_structure),
+                    },
+                )
+                .await?;
+        }
+        self.record(CatalogEntity::Product, key, CatalogAction::Update, changed);
+        Ok(Some(existing.id))
+    }
+
+    async fn reconcile_add_on(
+        &mut self,
+        family: &CatalogProductFamily,
+        family_id: Option<ProductFamilyId>,
+        products: &KnownIds<ProductId>,
+        desired: &CatalogAddOn,
+    ) -> StoreResult<Option<AddOnId>> {
+        let key = format!("{}/{}", family.name, desired.name);
+
+        let mut conflicts = self.missing_features(&desired.entitlements);
+        if !products.contains_key(&desired.product) {
+            conflicts.push(format!("unknown product '{}'", desired.product));
+        }
+
+        let existing = self
+            .state
+            .add_ons
+            .iter()
+            .find(|a| a.name == desired.name)
+            .cloned();
+
+        let current = existing
+            .as_ref()
+            .and_then(|a| self.state.add_on_to_catalog(a));
+        if let Some(current) = &current {
+            if current.product != desired.product {
+                conflicts.push("the product of an add-on cannot be changed".to_string());
+            }
+            if current.currency != desired.currency {
+                conflicts.push("the currency of an add-on cannot be changed".to_string());
+            }
+        }
+
+        if !conflicts.is_empty() {
+            self.record(
+                CatalogEntity::AddOn,
+                key,
+                CatalogAction::Conflict,
+                conflicts,
+            );
+            return Ok(existing.map(|a| a.id));
+        }
+
+        let price_entry = PriceEntry::New(PriceInput {
+            cadence: desired.cadence,
+            currency: desired.currency.clone(),
+            pricing: desired.pricing.clone(),
+            variants: desired.variants.clone(),
+        });
+
+        let (Some(existing), Some(current)) = (existing, current) else {
+            let mut id = None;
+            if self.actor.is_some() {
+                let product_id = require(
+                    products.get(&desired.product).copied().flatten(),
+                    &desired.product,
+                )?;
+                let created = self
+                    .services
+                    .store
+                    .create_add_on_from_ref(
+                        desired.name.clone(),
+                        ProductRef::Existing(product_id),
+                        price_entry,
+                        desired.description.clone(),
+                        desired.self_serviceable,
+                        desired.max_instances_per_subscription,
+                        self.tenant_id,
+                        require(family_id, &family.name)?,
+                        self.entitlement_specs(&desired.entitlements)?,
+                    )
+                    .await?;
+                id = Some(created.id);
+            }
+            self.record(CatalogEntity::AddOn, key, CatalogAction::Create, vec![]);
+            return Ok(id);
+        };
+
+        let desired_sorted = CatalogAddOn {
+            entitlements: sorted_entitlements(&desired.entitlements),
+            ..desired.clone()
+        };
+        let changed = changed_fields!(
+            current,
+            desired_sorted,
+            [
+                description,
+                cadence,
+                pricing,
+                variants,
+                self_serviceable,
+                max_instances_per_subscription,
+                entitlements,
+            ]
+        );
+        if changed.is_empty() {
+            self.record(CatalogEntity::AddOn, key, CatalogAction::Unchanged, vec![]);
+            return Ok(Some(existing.id));
+        }
+
+        if let Some(actor) = self.actor {
+            let price_changed = current.cadence != desired.cadence
+                || current.pricing != desired.pricing
+                || current.variants != desired.variants;
+            self.services
+                .store
+                .update_add_on(
+                    actor.clone(),
+                    AddOnPatch {
+                        id: existing.id,
+                        tenant_id: self.tenant_id,
+                        name: None,
+                        description: Some(desired.description.clone()),
+                        self_serviceable: Some(desired.self_serviceable),
+                        max_instances_per_subscription: Some(
+                            desired.max_instances_per_subscription,
+                        ),
+                    },
+                    price_changed.then_some(price_entry),
+                )
+                .await?;
+
+            if current.entitlements != desired_sorted.entitlements {
+                self.sync_add_on_entitlements(
+                    existing.id,
+                    &existing.entitlements,
+                    &desired.entitlements,
+                )
+                .await?;
+            }
+        }
+        self.record(CatalogEntity::AddOn, key, CatalogAction::Update, changed);
+        Ok(Some(existing.id))
+    }
+
+    async fn sync_add_on_entitlements(
+        &self,
+        add_on_id: AddOnId,
+        current: &[crate::domain::entitlements::Entitlement],
+        desired: &[CatalogEntitlement],
+    ) -> StoreResult<()> {
+        let store = &self.services.store;
+        let desired = self.entitlement_specs(desired)?;
+
+        for spec in &desired {
+            match current.iter().find(|e| e.feature_id == spec.feature_id) {
+                Some(entitlement) if entitlement.value == spec.value => {}
+                Some(entitlement) => {
+                    store
+                        .update_entitlement(
+                            entitlement.id,
+                            self.tenant_id,
+                            EntitlementUpdate {
+                                value: Some(spec.value.clone()),
+                            },
+                        )
+                        .await?;
+                }
+                None => {
+                    store
+                        .create_entitlement(EntitlementNew {
+                            tenant_id: self.tenant_id,
+                            feature_id: spec.feature_id,
+                            entity: EntitlementEntityId::AddOn(add_on_id),
+                            value: spec.value.clone(),
+                        })
+                        .await?;
+                }
+            }
+        }
+
+        for entitlement in current {
+            if !desired
+                .iter()
+                .any(|s| s.feature_id == entitlement.feature_id)
+            {
+                store
+                    .delete_entitlement(entitlement.id, self.tenant_id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn reconcile_plan(
+        &mut self,
+        family: &CatalogProductFamily,
+        family_id: Option<ProductFamilyId>,
+        products: &KnownIds<ProductId>,
+        add_ons: &KnownIds<AddOnId>,
+        plans: &KnownIds<PlanId>,
+        desired: &CatalogPlan,
+    ) -> StoreResult<Option<PlanId>> {
+        let key = format!("{}/{}", family.name, desired.name);
+
+        let mut conflicts = self.missing_features(&desired.entitlements);
+        for component in &desired.components {
+            if !products.contains_key(&component.product) {
+                conflicts.push(format!(
+                    "component '{}' uses unknown product '{}'",
+                    component.name, component.product
+                ));
+            }
+            if component.prices.is_empty() {
+                conflicts.push(format!("component '{}' has no price", component.name));
+            }
+        }
+        for add_on in &desired.add_ons {
+            if !add_ons.contains_key(&add_on.add_on) {
+                conflicts.push(format!("unknown add-on '{}'", add_on.add_on));
+            }
+        }
+        if let Some(trialing_plan) = desired
+            .trial
+            .as_ref()
+            .and_then(|t| t.trialing_plan.as_ref())
+            && !plans.contains_key(trialing_plan)
+        {
+            conflicts.push(format!("unknown trialing plan '{trialing_plan}'"));
+        }
+
+        let current = family_id
+            .and_then(|id| self.state.plans.get(&id))
+            .into_iter()
+            .flatten()
+            .find(|p| p.full.plan.name == desired.name)
+            .map(|p| {
+                (
+                    p.full.plan.id,
+                    normalized_plan(&self.state.plan_to_catalog(p)),
+                )
+            });
+
+        if let Some((_, current)) = &current
+            && current.plan_type != desired.plan_type
+        {
+            conflicts.push("the type of a plan cannot be changed".to_string());
+        }
+
+        if !conflicts.is_empty() {
+            self.record(CatalogEntity::Plan, key, CatalogAction::Conflict, conflicts);
+            return Ok(current.map(|(id, _)| id));
+        }
+
+        let Some((plan_id, current)) = current else {
+            let mut id = None;
+            if let Some(actor) = self.actor {
+                let family_id = require(family_id, &family.name)?;
+                let version = self.plan_version(desired, plans)?;
+                let components = self.plan_components(desired, products, None)?;
+                let created = self
+                    .services
+                    .store
+                    .insert_plan(
+                        actor.clone(),
+                        FullPlanNew {
+                            plan: PlanNew {
+                                name: desired.name.clone(),
+                                description: desired.description.clone(),
+                                tenant_id: self.tenant_id,
+                                product_family_id: family_id,
+                                plan_type: desired.plan_type.clone(),
+                                status: PlanStatusEnum::Draft,
+                            },
+                            version,
+                            price_components: components,
+                        },
+                    )
+                    .await?;
+                for add_on in self.plan_add_ons(desired, add_ons)? {
+                    self.services
+                        .store
+                        .attach_add_on_to_plan_version(PlanVersionAddOnNew {
+                            plan_version_id: created.version.id,
+                            ..add_on
+                        })
+                        .await?;
+                }
+                id = Some(created.plan.id);
+            }
+            self.record(CatalogEntity::Plan, key, CatalogAction::Create, vec![]);
+            return Ok(id);
+        };
+
+        let desired_normalized = normalized_plan(desired);
+        let changed = changed_fields!(
+            current,
+            desired_normalized,
+            [
+                description,
+                currency,
+                net_terms,
+                period_start_day,
+                trial,
+                components,
+                add_ons,
+                entitlements,
+            ]
+        );
+        if changed.is_empty() {
+            self.record(CatalogEntity::Plan, key, CatalogAction::Unchanged, vec![]);
+            return Ok(Some(plan_id));
+        }
+
+        if let Some(actor) = self.actor {
+            let current_plan = self
+                .state
+                .plans
+                .values()
+                .flatten()
+                .find(|p| p.full.plan.id == plan_id);
+            let version = self.plan_version(desired, plans)?;
+            let components = self.plan_components(desired, products, current_plan)?;
+            let attachments = self.plan_add_ons(desired, add_ons)?;
+            self.services
+                .store
+                .replace_plan_version(
+                    actor.clone(),
+                    plan_id,
+                    self.tenant_id,
+                    desired.name.clone(),
+                    desired.description.clone(),
+                    version,
+                    components,
+                    attachments,
+                    false,
+                )
+                .await?;
+        }
+        self.record(CatalogEntity::Plan, key, CatalogAction::Update, changed);
+        Ok(Some(plan_id))
+    }
+
+    fn plan_version(
+        &self,
+        desired: &CatalogPlan,
+        plans: &KnownIds<PlanId>,
+    ) -> StoreResult<PlanVersionNewInternal> {
+        let trial = desired
+            .trial
+            .as_ref()
+            .map(|t| {
+                let trialing_plan_id = t
+                    .trialing_plan
+                    .as_ref()
+                    .map(|name| require(plans.get(name).copied().flatten(), name))
+                    .transpose()?;
+                Ok::<_, Report<StoreError>>(PlanTrial {
+                    duration_days: t.duration_days,
+                    trialing_plan_id,
+                    trial_is_free: t.is_free,
+                })
+            })
+            .transpose()?;
+
+        Ok(PlanVersionNewInternal {
+            is_draft_version: true,
+            period_start_day: desired.period_start_day,
+            net_terms: desired.net_terms,
+            currency: Some(desired.currency.clone()),
+            billing_cycles: None,
+            trial,
+            entitlements: self.entitlement_specs(&desired.entitlements)?,
+        })
+    }
+
+    /// Reuses the prices of the current version that are identical, so that unchanged
+    /// components keep their price ids across versions.
+    fn plan_components(
+        &self,
+        desired: &CatalogPlan,
+        products: &KnownIds<ProductId>,
+        current: Option<&CurrentPlan>,
+    ) -> StoreResult<Vec<PriceComponentNewInternal>> {
+        let current_prices: Vec<_> = current
+            .into_iter()
+            .flat_map(|p| p.full.price_components.iter())
+            .flat_map(|c| c.prices.iter())
+            .collect();
+
+        desired
+            .components
+            .iter()
+            .map(|component| {
+                let product_id = require(
+                    products.get(&component.product).copied().flatten(),
+                    &component.product,
+                )?;
+
+                let prices = component
+                    .prices
+                    .iter()
+                    .map(|price| {
+                        current_prices
+                            .iter()
+                            .find(|p| {
+                                p.product_id == product_id
+                                    && p.cadence == price.cadence
+                                    && p.currency == desired.currency
+                                    && p.pricing == price.pricing
+                                    && p.variants == price.variants
+                            })
+                            .map(|p| PriceEntry::Existing(p.id))
+                            .unwrap_or_else(|| {
+                                PriceEntry::New(PriceInput {
+                                    cadence: price.cadence,
+                                    currency: desired.currency.clone(),
+                                    pricing: price.pricing.clone(),
+                                    variants: price.variants.clone(),
+                                })
+                            })
+                    })
+                    .collect();
+
+                Ok(PriceComponentNewInternal {
+                    name: component.name.clone(),
+                    product_ref: ProductRef::Existing(product_id),
+                    prices,
+                })
+            })
+            .collect()
+    }
+
+    fn plan_add_ons(
+        &self,
+        desired: &CatalogPlan,
+        add_ons: &KnownIds<AddOnId>,
+    ) -> StoreResult<Vec<PlanVersionAddOnNew>> {
+        desired
+            .add_ons
+            .iter()
+            .map(|a| {
+                Ok(PlanVersionAddOnNew {
+                    plan_version_id: Default::default(), // set by the store
+                    add_on_id: require(add_ons.get(&a.add_on).copied().flatten(), &a.add_on)?,
+                    price_id: None,
+                    self_serviceable: a.self_serviceable,
+                    max_instances_per_subscription: a.max_instances_per_subscription,
+                    tenant_id: self.tenant_id,
+                })
+            })
+            .collect()
+    }
+
+    async fn reconcile_coupon(&mut self, desired: &CatalogCoupon) -> StoreResult<()> {
+        let key = desired.code.clone();
+
+        let existing = self
+            .state
+            .coupons
+            .iter()
+            .find(|c| c.code == desired.code)
+            .cloned();
+
+        let Some(existing) = existing else {
+            if let Some(actor) = self.actor {
+                self.services
+                    .store
+                    .create_coupon(
+                        actor.clone(),
+                        CouponNew {
+                            code: desired.code.clone(),
+                            description: desired.description.clone(),
+                            tenant_id: self.tenant_id,
+                            discount: desired.discount.clone(),
+                            expires_at: desired.expires_at,
+                            redemption_limit: desired.redemption_limit,
+                            recurring_value: desired.recurring_value,
+                            reusable: desired.reusable,
+                            plan_ids: vec![],
+                        },
+                    )
+                    .await?;
+            }
+            self.record(CatalogEntity::Coupon, key, CatalogAction::Create, vec![]);
+            return Ok(());
+        };
+
+        let current = CatalogState::coupon_to_catalog(&existing);
+        let immutable = changed_fields!(
+            current,
+            desired,
+            [expires_at, redemption_limit, recurring_value, reusable]
+        );
+        if !immutable.is_empty() {
+            self.record(
+                CatalogEntity::Coupon,
+                key,
+                CatalogAction::Conflict,
+                immutable
+                    .into_iter()
+                    .map(|f| format!("{f} cannot be changed"))
+                    .collect(),
+            );
+            return Ok(());
+        }
+
+        let changed = changed_fields!(current, desired, [description, discount]);
+        if changed.is_empty() {
+            self.record(CatalogEntity::Coupon, key, CatalogAction::Unchanged, vec![]);
+            return Ok(());
+        }
+
+        if let Some(actor) = self.actor {
+            self.services
+                .store
+                .update_coupon(
+                    actor.clone(),
+                    CouponPatch {
+                        id: existing.id,
+                        tenant_id: self.tenant_id,
+                        description: Some(desired.description.clone()),
+                        discount: Some(desired.discount.clone()),
+                        plan_ids: None,
+                    },
+                )
+                .await?;
+        }
+        self.record(CatalogEntity::Coupon, key, CatalogAction::Update, changed);
+        Ok(())
+    }
+}
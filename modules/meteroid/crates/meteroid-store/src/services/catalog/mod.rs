@@ -0,0 +1,460 @@
+use std::collections::HashMap;
+
+use common_domain::ids::{
+    BillableMetricId, FeatureId, PlanId, ProductFamilyId, ProductId, TenantId,
+};
+use error_stack::Report;
+
+use crate::StoreResult;
+use crate::domain::add_ons::AddOn;
+use crate::domain::catalog::{
+    CatalogAddOn, CatalogComponent, CatalogCoupon, CatalogDocument, CatalogEntitlement,
+    CatalogFeature, CatalogFeeStructure, CatalogPlan, CatalogPlanAddOn, CatalogPrice,
+    CatalogProduct, CatalogProductFamily, CatalogTrial,
+};
+use crate::domain::coupons::{Coupon, CouponFilter};
+use crate::domain::entitlements::{Entitlement, Feature, FeatureType};
+use crate::domain::enums::{FeatureStatusEnum, PlanStatusEnum};
+use crate::domain::plan_version_add_ons::PlanVersionAddOn;
+use crate::domain::prices::FeeStructure;
+use crate::domain::{
+    FullPlan, PaginationRequest, PlanFilters, PlanVersionFilter, Product, ProductFamily,
+};
+use crate::errors::StoreError;
+use crate::repositories::add_ons::AddOnInterface;
+use crate::repositories::billable_metrics::BillableMetricInterface;
+use crate::repositories::coupons::CouponInterface;
+use crate::repositories::plan_version_add_ons::PlanVersionAddOnInterface;
+use crate::repositories::products::ProductInterface;
+use crate::repositories::{EntitlementsInterface, PlansInterface, ProductFamilyInterface};
+
+use super::Services;
+
+mod apply;
+
+fn all_items() -> PaginationRequest {
+    PaginationRequest {
+        per_page: Some(u32::MAX),
+        page: 0,
+    }
+}
+
+/// Latest version of a plan: the draft when there is one, the published version otherwise.
+/// Catalog documents are compared against it, so re-applying a document is a no-op.
+struct CurrentPlan {
+    full: FullPlan,
+    add_ons: Vec<PlanVersionAddOn>,
+}
+
+/// Non-archived catalog of a tenant, with the lookups needed to translate ids to document keys.
+struct CatalogState {
+    families: Vec<ProductFamily>,
+    products: HashMap<ProductFamilyId, Vec<Product>>,
+    metric_codes: HashMap<BillableMetricId, String>,
+    metric_ids: HashMap<String, BillableMetricId>,
+    features: Vec<Feature>,
+    add_ons: Vec<AddOn>,
+    plans: HashMap<ProductFamilyId, Vec<CurrentPlan>>,
+    coupons: Vec<Coupon>,
+}
+
+impl CatalogState {
+    fn product_name(&self, id: ProductId) -> Option<&str> {
+        self.products
+            .values()
+            .flatten()
+            .find(|p| p.id == id)
+            .map(|p| p.name.as_str())
+    }
+
+    fn product_family_of(&self, id: ProductId) -> Option<ProductFamilyId> {
+        self.products
+            .iter()
+            .find(|(_, products)| products.iter().any(|p| p.id == id))
+            .map(|(family_id, _)| *family_id)
+    }
+
+    fn feature_code(&self, id: FeatureId) -> Option<&str> {
+        self.features
+            .iter()
+            .find(|f| f.id == id)
+            .map(|f| f.code.as_str())
+    }
+
+    fn plan_name(&self, id: PlanId) -> Option<&str> {
+        self.plans
+            .values()
+            .flatten()
+            .find(|p| p.full.plan.id == id)
+            .map(|p| p.full.plan.name.as_str())
+    }
+
+    fn fee_to_catalog(&self, fee: &FeeStructure) -> StoreResult<CatalogFeeStructure> {
+        let metric_code = |id: &BillableMetricId| {
+            self.metric_codes.get(id).cloned().ok_or_else(|| {
+                Report::new(StoreError::ValueNotFound(format!(
+                    "Billable metric {id} is archived or missing"
+                )))
+            })
+        };
+
+        Ok(match fee {
+            FeeStructure::Rate {} => CatalogFeeStructure::Rate {},
+            FeeStructure::Slot {
+                unit_name,
+                upgrade_policy,
+                downgrade_policy,
+            } => CatalogFeeStructure::Slot {
+                unit_name: unit_name.clone(),
+                upgrade_policy: upgrade_policy.clone(),
+                downgrade_policy: downgrade_policy.clone(),
+            },
+            FeeStructure::Capacity { metric_id } => CatalogFeeStructure::Capacity {
+                metric: metric_code(metric_id)?,
+            },
+            FeeStructure::Usage { metric_id, model } => CatalogFeeStructure::Usage {
+                metric: metric_code(metric_id)?,
+                model: model.clone(),
+            },
+            FeeStructure::ExtraRecurring { billing_type } => CatalogFeeStructure::ExtraRecurring {
+                billing_type: billing_type.clone(),
+            },
+            FeeStructure::OneTime {} => CatalogFeeStructure::OneTime {},
+        })
+    }
+
+    /// Resolves metric codes. The error is the conflict reported for the entry.
+    fn fee_from_catalog(&self, fee: &CatalogFeeStructure) -> Result<FeeStructure, String> {
+        let metric_id = |code: &String| {
+            self.metric_ids
+                .get(code)
+                .copied()
+                .ok_or_else(|| format!("unknown billable metric '{code}'"))
+        };
+
+        Ok(match fee {
+            CatalogFeeStructure::Rate {} => FeeStructure::Rate {},
+            CatalogFeeStructure::Slot {
+                unit_name,
+                upgrade_policy,
+                downgrade_policy,
+            } => FeeStructure::Slot {
+                unit_name: unit_name.clone(),
+                upgrade_policy: upgrade_policy.clone(),
+                downgrade_policy: downgrade_policy.clone(),
+            },
+            CatalogFeeStructure::Capacity { metric } => FeeStructure::Capacity {
+                metric_id: metric_id(metric)?,
+            },
+            CatalogFeeStructure::Usage { metric, model } => FeeStructure::Usage {
+                metric_id: metric_id(metric)?,
+                model: model.clone(),
+            },
+            CatalogFeeStructure::ExtraRecurring { billing_type } => FeeStructure::ExtraRecurring {
+                billing_type: billing_type.clone(),
+            },
+            CatalogFeeStructure::OneTime {} => FeeStructure::OneTime {},
+        })
+    }
+
+    fn feature_to_catalog(&self, feature: &Feature) -> CatalogFeature {
+        CatalogFeature {
+            code: feature.code.clone(),
+            name: feature.name.clone(),
+            description: feature.description.clone(),
+            metric: match &feature.feature_type {
+                FeatureType::Boolean => None,
+                FeatureType::Metered { metric_id } => Some(
+                    self.metric_codes
+                        .get(metric_id)
+                        .cloned()
+                        .unwrap_or_else(|| metric_id.to_string()),
+                ),
+            },
+        }
+    }
+
+    fn entitlements_to_catalog(&self, entitlements: &[Entitlement]) -> Vec<CatalogEntitlement> {
+        let mut res: Vec<CatalogEntitlement> = entitlements
+            .iter()
+            .filter_map(|e| {
+                self.feature_code(e.feature_id)
+                    .map(|code| CatalogEntitlement {
+                        feature: code.to_string(),
+                        value: e.value.clone(),
+                    })
+            })
+            .collect();
+        res.sort_by(|a, b| a.feature.cmp(&b.feature));
+        res
+    }
+
+    fn product_to_catalog(&self, product: &Product) -> StoreResult<CatalogProduct> {
+        Ok(CatalogProduct {
+            name: product.name.clone(),
+            description: product.description.clone(),
+            fee: self.fee_to_catalog(&product.fee_structure)?,
+        })
+    }
+
+    fn add_on_to_catalog(&self, add_on: &AddOn) -> Option<CatalogAddOn> {
+        let price = add_on.price.as_ref()?;
+        Some(CatalogAddOn {
+            name: add_on.name.clone(),
+            description: add_on.description.clone(),
+            product: self.product_name(add_on.product_id)?.to_string(),
+            currency: price.currency.clone(),
+            cadence: price.cadence,
+            pricing: price.pricing.clone(),
+            variants: price.variants.clone(),
+            self_serviceable: add_on.self_serviceable,
+            max_instances_per_subscription: add_on.max_instances_per_subscription,
+            entitlements: self.entitlements_to_catalog(&add_on.entitlements),
+        })
+    }
+
+    fn plan_to_catalog(&self, current: &CurrentPlan) -> CatalogPlan {
+        let FullPlan {
+            plan,
+            version,
+            price_components,
+            products,
+            ..
+        } = &current.full;
+
+        let components = price_components
+            .iter()
+            .filter_map(|c| {
+                // v1 components without a product cannot be described by name
+                let product = products.get(&c.product_id?)?;
+                Some(CatalogComponent {
+                    name: c.name.clone(),
+                    product: product.name.clone(),
+                    prices: c
+                        .prices
+                        .iter()
+                        .map(|p| CatalogPrice {
+                            cadence: p.cadence,
+                            pricing: p.pricing.clone(),
+                            variants: p.variants.clone(),
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+
+        let mut add_ons: Vec<CatalogPlanAddOn> = current
+            .add_ons
+            .iter()
+            .filter_map(|a| {
+                let add_on = self.add_ons.iter().find(|x| x.id == a.add_on_id)?;
+                Some(CatalogPlanAddOn {
+                    add_on: add_on.name.clone(),
+                    self_serviceable: a.self_serviceable,
+                    max_instances_per_subscription: a.max_instances_per_subscription,
+                })
+            })
+            .collect();
+        add_ons.sort_by(|a, b| a.add_on.cmp(&b.add_on));
+
+        CatalogPlan {
+            name: plan.name.clone(),
+            description: plan.description.clone(),
+            plan_type: plan.plan_type.clone(),
+            currency: version.currency.clone(),
+            net_terms: version.net_terms,
+            period_start_day: version.period_start_day,
+            trial: version.trial_duration_days.map(|days| CatalogTrial {
+                duration_days: days.max(0) as u32,
+                is_free: version.trial_is_free,
+                trialing_plan: version
+                    .trialing_plan_id
+                    .and_then(|id| self.plan_name(id))
+                    .map(str::to_string),
+            }),
+            components,
+            add_ons,
+            entitlements: self.entitlements_to_catalog(&version.entitlements),
+        }
+    }
+
+    fn coupon_to_catalog(coupon: &Coupon) -> CatalogCoupon {
+        CatalogCoupon {
+            code: coupon.code.clone(),
+            description: coupon.description.clone(),
+            discount: coupon.discount.clone(),
+            expires_at: coupon.expires_at,
+            redemption_limit: coupon.redemption_limit,
+            recurring_value: coupon.recurring_value,
+            reusable: coupon.reusable,
+        }
+    }
+
+    fn to_document(&self) -> StoreResult<CatalogDocument> {
+        let mut features: Vec<CatalogFeature> = self
+            .features
+            .iter()
+            .map(|f| self.feature_to_catalog(f))
+            .collect();
+        features.sort_by(|a, b| a.code.cmp(&b.code));
+
+        let mut product_families = Vec::with_capacity(self.families.len());
+        for family in &self.families {
+            let mut products = self
+                .products
+                .get(&family.id)
+                .into_iter()
+                .flatten()
+                .map(|p| self.product_to_catalog(p))
+                .collect::<StoreResult<Vec<_>>>()?;
+            products.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let mut add_ons: Vec<CatalogAddOn> = self
+                .add_ons
+                .iter()
+                .filter(|a| self.product_family_of(a.product_id) == Some(family.id))
+                .filter_map(|a| self.add_on_to_catalog(a))
+                .collect();
+            add_ons.sort_by(|a, b| a.name.cmp(&b.name));
+
+            let mut plans: Vec<CatalogPlan> = self
+                .plans
+                .get(&family.id)
+                .into_iter()
+                .flatten()
+                .map(|p| self.plan_to_catalog(p))
+                .collect();
+            plans.sort_by(|a, b| a.name.cmp(&b.name));
+
+            product_families.push(CatalogProductFamily {
+                name: family.name.clone(),
+                products,
+                add_ons,
+                plans,
+            });
+        }
+        product_families.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let mut coupons: Vec<CatalogCoupon> =
+            self.coupons.iter().map(Self::coupon_to_catalog).collect();
+        coupons.sort_by(|a, b| a.code.cmp(&b.code));
+
+        Ok(CatalogDocument {
+            features,
+            product_families,
+            coupons,
+        })
+    }
+}
+
+impl Services {
+    async fn load_catalog_state(&self, tenant_id: TenantId) -> StoreResult<CatalogState> {
+        let families = self
+            .store
+            .list_product_families(tenant_id, all_items(), None, None)
+            .await?
+            .items
+            .into_iter()
+            .filter(|f| f.archived_at.is_none())
+            .collect::<Vec<_>>();
+
+        let mut products = HashMap::new();
+        let mut plans = HashMap::new();
+        for family in &families {
+            let family_products = self
+                .store
+                .list_products(tenant_id, Some(family.id), true, all_items(), None)
+                .await?
+                .items
+                .into_iter()
+                .filter(|p| p.archived_at.is_none())
+                .collect::<Vec<_>>();
+            products.insert(family.id, family_products);
+
+            let overviews = self
+                .store
+                .list_plans(
+                    tenant_id,
+                    Some(family.id),
+                    PlanFilters {
+                        search: None,
+                        filter_status: vec![],
+                        filter_type: vec![],
+                        filter_currency: None,
+                    },
+                    all_items(),
+                    None,
+                )
+                .await?
+                .items;
+
+            let mut family_plans = Vec::with_capacity(overviews.len());
+            for overview in overviews {
+                if overview.status == PlanStatusEnum::Archived {
+                    continue;
+                }
+                let filter = if overview.has_draft_version {
+                    PlanVersionFilter::Draft
+                } else {
+                    PlanVersionFilter::Active
+                };
+                let full = self
+                    .store
+                    .get_full_plan(overview.id, tenant_id, filter)
+                    .await?;
+                let add_ons = self
+                    .store
+                    .list_plan_version_add_ons(full.version.id, tenant_id)
+                    .await?;
+                family_plans.push(CurrentPlan { full, add_ons });
+            }
+            plans.insert(family.id, family_plans);
+        }
+
+        let metrics = self.store.list_active_billable_metrics(tenant_id).await?;
+        let metric_codes = metrics.iter().map(|m| (m.id, m.code.clone())).collect();
+        let metric_ids = metrics.into_iter().map(|m| (m.code, m.id)).collect();
+
+        let features = self
+            .store
+            .list_features(
+                tenant_id,
+                all_items(),
+                Some(vec![FeatureStatusEnum::Active, FeatureStatusEnum::Disabled]),
+                None,
+                None,
+            )
+            .await?
+            .items;
+
+        let add_ons = self
+            .store
+            .list_add_ons(tenant_id, None, all_items(), None, None, false, None)
+            .await?
+            .items;
+
+        let coupons = self
+            .store
+            .list_coupons(tenant_id, all_items(), None, CouponFilter::ALL, None)
+            .await?
+            .items
+            .into_iter()
+            .filter(|c| c.archived_at.is_none())
+            .collect();
+
+        Ok(CatalogState {
+            families,
+            products,
+            metric_codes,
+            metric_ids,
+            features,
+            add_ons,
+            plans,
+            coupons,
+        })
+    }
+
+    pub(crate) async fn export_catalog(&self, tenant_id: TenantId) -> StoreResult<CatalogDocument> {
+        self.load_catalog_state(tenant_id).await?.to_document()
+    }
+}
@@ -4,17 +4,19 @@ use crate::domain::subscription_add_ons::SubscriptionAddOn;
 use crate::domain::subscription_coupons::AppliedCoupon;
 use crate::domain::{
     AppliedCouponDetailed, BillingPeriodEnum, CheckoutSession, CheckoutType, CreateSubscription,
-    CreateSubscriptionAddOns, CreateSubscriptionComponents, InvoicingEntity, PriceComponent,
-    Subscription, SubscriptionActivationCondition, SubscriptionComponent, SubscriptionDetails,
-    SubscriptionStatusEnum, TrialConfig,
+    CreateSubscriptionAddOns, CreateSubscriptionComponents, Customer, InvoicingEntity,
+    PriceComponent, Subscription, SubscriptionActivationCondition, SubscriptionComponent,
+    SubscriptionDetails, SubscriptionStatusEnum, TrialConfig,
 };
 use crate::errors::StoreError;
 use crate::repositories::add_ons::AddOnInterface;
 use crate::repositories::customers::CustomersInterfaceAuto;
 use crate::repositories::plans::PlansInterface;
 use crate::repositories::price_components::PriceComponentInterface;
+use crate::repositories::subscriptions::find_payer;
 use crate::services::Services;
 use crate::services::invoice_lines::invoice_lines::ComputedInvoiceContent;
+use crate::services::subscriptions::effective_plan::{localize_price_components, localize_prices};
 use crate::store::PgConn;
 use crate::utils::periods::calculate_advance_period_range;
 use chrono::{Datelike, Duration, Utc};
@@ -57,7 +59,7 @@ impl Services {
             ))
         })?;
 
-        let price_components = self
+        let mut price_components = self
             .store
             .list_price_components(session.plan_version_id, tenant_id)
             .await?;
@@ -67,6 +69,9 @@ impl Services {
             .find_customer_by_id(session.customer_id, tenant_id)
             .await?;
 
+        // Localized pricing: price variants matching the customer replace the base pricing
+        localize_price_components(&mut price_components, &customer);
+
         let invoicing_entity_providers = InvoicingEntityProvidersRow::resolve_providers_by_id(
             conn,
             customer.invoicing_entity_id,
@@ -117,7 +122,7 @@ impl Services {
         )?;
 
         let subscription_add_ons = self
-            .build_preview_add_ons(conn, tenant_id, session.add_ons.as_ref())
+            .build_preview_add_ons(conn, tenant_id, &customer, session.add_ons.as_ref())
             .await?;
 
         let billing_period = self.extract_billing_period_from_components_and_add_ons(
@@ -224,6 +229,15 @@ impl Services {
             next_retry: None,
             quote_id: None,
             payment_methods_config: session.payment_methods_config.clone(),
+            trialing_plan_id: None,
+            renewal_policy: Default::default(),
+            renewal_term_months: None,
+            renewal_plan_version_id: None,
+            renewal_notice_days: None,
+            renewal_opt_out_days: None,
+            instance: None,
+            invoice_number_prefix: None,
+            invoice_custom_fields: Default::default(),
         };
 
         let mut applied_coupons = Vec::new();
@@ -261,10 +275,13 @@ impl Services {
             }
         }
 
+        let payer = find_payer(conn, tenant_id, &customer).await?;
+
         Ok(SubscriptionDetails {
             subscription: virtual_subscription,
             invoicing_entity,
             customer,
+            payer,
             schedules: Vec::new(),
             price_components: subscription_components,
             add_ons: subscription_add_ons,
@@ -301,7 +318,7 @@ impl Services {
             ))
         })?;
 
-        let price_components = self
+        let mut price_components = self
             .store
             .list_price_components(sub.plan_version_id, tenant_id)
             .await?;
@@ -311,6 +328,9 @@ impl Services {
             .find_customer_by_id(sub.customer_id, tenant_id)
             .await?;
 
+        // Localized pricing: price variants matching the customer replace the base pricing
+        localize_price_components(&mut price_components, &customer);
+
         let invoicing_entity_providers = InvoicingEntityProvidersRow::resolve_providers_by_id(
             conn,
             customer.invoicing_entity_id,
@@ -357,7 +377,7 @@ impl Services {
         )?;
 
         let subscription_add_ons = self
-            .build_preview_add_ons(conn, tenant_id, create.add_ons.as_ref())
+            .build_preview_add_ons(conn, tenant_id, &customer, create.add_ons.as_ref())
             .await?;
 
         let billing_period = self.extract_billing_period_from_components_and_add_ons(
@@ -440,6 +460,15 @@ impl Services {
             next_retry: None,
             quote_id: None,
             payment_methods_config: sub.payment_methods_config.clone(),
+            trialing_plan_id: None,
+            renewal_policy: Default::default(),
+            renewal_term_months: None,
+            renewal_plan_version_id: None,
+            renewal_notice_days: None,
+            renewal_opt_out_days: None,
+            instance: None,
+            invoice_number_prefix: None,
+            invoice_custom_fields: Default::default(),
         };
 
         let mut applied_coupons = Vec::new();
@@ -454,10 +483,13 @@ impl Services {
             }
         }
 
+        let payer = find_payer(conn, tenant_id, &customer).await?;
+
         let details = SubscriptionDetails {
             subscription: virtual_subscription,
             invoicing_entity,
             customer,
+            payer,
             schedules: Vec::new(),
             price_components: subscription_components,
             add_ons: subscription_add_ons,
@@ -723,6 +755,7 @@ impl Services {
         &self,
         conn: &mut PgConn,
         tenant_id: TenantId,
+        customer: &Customer,
         add_ons: Option<&CreateSubscriptionAddOns>,
     ) -> StoreResult<Vec<SubscriptionAddOn>> {
         let Some(create_add_ons) = add_ons else {
@@ -758,7 +791,7 @@ impl Services {
 
         let ao_price_ids: Vec<common_domain::ids::PriceId> =
             add_ons.iter().map(|a| a.price_id).collect();
-        let ao_prices_map: HashMap<common_domain::ids::PriceId, crate::domain::prices::Price> =
+        let mut ao_prices_map: HashMap<common_domain::ids::PriceId, crate::domain::prices::Price> =
             if ao_price_ids.is_empty() {
                 HashMap::new()
             } else {
@@ -772,6 +805,7 @@ impl Services {
                     })
                     .collect::<Result<HashMap<_, _>, _>>()?
             };
+        localize_prices(ao_prices_map.values_mut(), customer);
 
         let mut result = Vec::new();
 
@@ -1,5 +1,5 @@
 use crate::StoreResult;
-use crate::domain::{BillableMetric, UsagePeriod};
+use crate::domain::{BillableMetric, UsageFilter, UsagePeriod};
 use crate::errors::StoreError;
 use chrono::{NaiveDate, NaiveDateTime};
 use common_domain::ids::{BillableMetricId, CustomerId, TenantId};
@@ -76,12 +76,14 @@ pub struct IngestEventsResult {
 
 #[async_trait::async_trait]
 pub trait UsageClient: Send + Sync {
+    /// If `filter` is Some, only the events matching it are aggregated.
     async fn fetch_usage(
         &self,
         tenant_id: &TenantId,
         customer_id: &CustomerId,
         metric: &BillableMetric,
         period: UsagePeriod,
+        filter: Option<&UsageFilter>,
     ) -> StoreResult<UsageData>;
 
     async fn fetch_total_usage(
@@ -98,6 +100,7 @@ pub trait UsageClient: Send + Sync {
         customer_id: &CustomerId,
         metric: &BillableMetric,
         period: UsagePeriod,
+        filter: Option<&UsageFilter>,
     ) -> StoreResult<WindowedUsageData>;
 
     /// Fetch aggregated usage for a metric over a period.
@@ -141,13 +144,14 @@ impl UsageClient for MockUsageClient {
         _customer_id: &CustomerId,
         metric: &BillableMetric,
         period: UsagePeriod,
+        filter: Option<&UsageFilter>,
     ) -> StoreResult<UsageData> {
         let params = MockUsageDataParams {
             metric_id: metric.id,
             period_start: period.start,
             period_end: period.end,
         };
-        let usage_data = self
+        let mut usage_data = self
             .data
             .get(&params)
             .cloned()
@@ -155,6 +159,17 @@ impl UsageClient for MockUsageClient {
                 data: vec![],
                 period: period.clone(),
             });
+        // Mocked events carry their properties as dimensions. Like the metering service,
+        // filtered properties are not grouped by.
+        if let Some(filter) = filter {
+            usage_data.data.retain_mut(|usage| {
+                usage
+                    .dimensions
+                    .remove(&filter.property)
+                    .unwrap_or_default()
+                    == filter.value
+            });
+        }
         Ok(usage_data)
     }
 
@@ -183,6 +198,7 @@ impl UsageClient for MockUsageClient {
         _customer_id: &CustomerId,
         _metric: &BillableMetric,
         period: UsagePeriod,
+        _filter: Option<&UsageFilter>,
     ) -> StoreResult<WindowedUsageData> {
         Ok(WindowedUsageData {
             data: vec![],
@@ -54,6 +54,7 @@ impl Services {
                         tax_rate: Decimal::ZERO,
                         tax_details: vec![],
                         group_by_dimensions: None,
+                        metadata: Default::default(),
                     }];
 
                     let invoice = self
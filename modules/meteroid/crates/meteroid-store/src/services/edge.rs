@@ -15,11 +15,11 @@ use crate::domain::subscriptions::PaymentMethodsConfig;
 use crate::domain::{
     CheckoutSession, CreateSubscription, CreateSubscriptionFromQuote, CreatedSubscription,
     Customer, CustomerBuyCredits, DetailedInvoice, Invoice, InvoicingEntity,
-    InvoicingEntityProviderSensitive, SetupIntent, Subscription, SubscriptionDetails,
-    UpdateInvoiceParams,
+    InvoicingEntityProviderSensitive, OneOffInvoiceNew, RenewalTerms, ResumeBillingAnchor,
+    SetupIntent, Subscription, SubscriptionDetails, UpdateInvoiceParams,
 };
 use crate::errors::{StoreError, StoreErrorReport};
-use crate::repositories::subscriptions::CancellationEffectiveAt;
+use crate::repositories::subscriptions::{CancellationEffectiveAt, PauseEffectiveAt, PauseParams};
 use crate::repositories::{InvoiceInterface, SubscriptionInterface};
 use crate::services::CycleTransitionResult;
 use crate::services::clients::usage::WindowedUsageData;
@@ -32,7 +32,7 @@ use chrono::Datelike;
 use chrono::{NaiveDate, Utc};
 use common_domain::ids::{
     AppliedCouponId, BaseId, CheckoutSessionId, CustomerConnectionId, CustomerId,
-    CustomerPaymentMethodId, InvoiceId, PlanVersionId, SubscriptionId, TenantId,
+    CustomerPaymentMethodId, InvoiceId, PlanId, PlanVersionId, SubscriptionId, TenantId,
 };
 use diesel_models::applied_coupons::AppliedCouponRowNew;
 use diesel_models::checkout_sessions::CheckoutSessionRow;
@@ -290,6 +290,7 @@ impl ServicesEdge {
             )
             .into());
         }
+        invoice.ensure_not_awaiting_approval("refresh")?;
 
         self.services
             .refresh_invoice_data(
@@ -940,6 +941,43 @@ impl ServicesEdge {
             .await
     }
 
+    pub async fn pause_subscription(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        effective_at: PauseEffectiveAt,
+        params: PauseParams,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .pause_subscription(actor, subscription_id, tenant_id, effective_at, params)
+            .await
+    }
+
+    pub async fn resume_subscription(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        resume_on: Option<NaiveDate>,
+        anchor: ResumeBillingAnchor,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .resume_subscription(actor, subscription_id, tenant_id, resume_on, anchor)
+            .await
+    }
+
+    pub async fn reactivate_subscription(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .reactivate_subscription(actor, subscription_id, tenant_id)
+            .await
+    }
+
     pub async fn schedule_plan_change(
         &self,
         typed_actor: Actor,
@@ -978,6 +1016,87 @@ impl ServicesEdge {
             .await
     }
 
+    pub async fn preview_billing_anchor_change(
+        &self,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        billing_day_anchor: u16,
+    ) -> StoreResult<crate::domain::subscription_changes::BillingAnchorChangePreview> {
+        self.services
+            .preview_billing_anchor_change(subscription_id, tenant_id, billing_day_anchor)
+            .await
+    }
+
+    pub async fn change_billing_anchor(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        billing_day_anchor: u16,
+    ) -> StoreResult<crate::domain::subscription_changes::BillingAnchorChangeResult> {
+        self.services
+            .change_billing_anchor(actor, subscription_id, tenant_id, billing_day_anchor)
+            .await
+    }
+
+    pub async fn extend_trial(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        trial_end: NaiveDate,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .extend_trial(actor, subscription_id, tenant_id, trial_end)
+            .await
+    }
+
+    pub async fn end_trial_now(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .end_trial_now(actor, subscription_id, tenant_id)
+            .await
+    }
+
+    pub async fn change_trialing_plan(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        trialing_plan_id: Option<PlanId>,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .change_trialing_plan(actor, subscription_id, tenant_id, trialing_plan_id)
+            .await
+    }
+
+    pub async fn set_renewal_terms(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        terms: RenewalTerms,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .set_renewal_terms(actor, subscription_id, tenant_id, terms)
+            .await
+    }
+
+    pub async fn opt_out_of_renewal(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+    ) -> StoreResult<Subscription> {
+        self.services
+            .opt_out_of_renewal(actor, subscription_id, tenant_id)
+            .await
+    }
+
     /// Compute the invoice content for a plan change checkout preview.
     /// Mirrors the exact amount computation from `complete_checkout_plan_change_tx`.
     pub async fn compute_plan_change_checkout_invoice(
@@ -1021,7 +1140,7 @@ impl ServicesEdge {
                     &mut conn,
                     tenant_id,
                     &prepared.subscription_details.subscription,
-                    &prepared.subscription_details.customer,
+                    prepared.subscription_details.billed_customer(),
                     &prepared.proration,
                 )
                 .await?
@@ -1174,6 +1293,97 @@ impl ServicesEdge {
             .await
     }
 
+    pub async fn preview_price_change(
+        &self,
+        tenant_id: TenantId,
+        change: &crate::domain::price_changes::PriceChangeNew,
+    ) -> StoreResult<crate::domain::price_changes::PriceChangePreview> {
+        self.services.preview_price_change(tenant_id, change).await
+    }
+
+    pub async fn schedule_price_change(
+        &self,
+        tenant_id: TenantId,
+        change: crate::domain::price_changes::PriceChangeNew,
+        actor: uuid::Uuid,
+    ) -> StoreResult<crate::domain::price_changes::PriceChange> {
+        self.services
+            .schedule_price_change(tenant_id, change, actor)
+            .await
+    }
+
+    pub async fn list_price_changes(
+        &self,
+        tenant_id: TenantId,
+        product_id: common_domain::ids::ProductId,
+    ) -> StoreResult<Vec<crate::domain::price_changes::PriceChange>> {
+        self.services
+            .list_price_changes(tenant_id, product_id)
+            .await
+    }
+
+    pub async fn cancel_price_change(
+        &self,
+        tenant_id: TenantId,
+        id: common_domain::ids::PriceChangeId,
+    ) -> StoreResult<crate::domain::price_changes::PriceChange> {
+        self.services.cancel_price_change(tenant_id, id).await
+    }
+
+    pub async fn apply_due_price_changes(&self) -> StoreResult<usize> {
+        self.services.apply_due_price_changes().await
+    }
+
+    pub async fn process_usage_thresholds(&self) -> StoreResult<usize> {
+        self.services.process_usage_thresholds().await
+    }
+
+    pub async fn process_trial_will_end_notifications(&self) -> StoreResult<usize> {
+        self.services.process_trial_will_end_notifications().await
+    }
+
+    pub async fn process_renewal_upcoming_notifications(&self) -> StoreResult<usize> {
+        self.services.process_renewal_upcoming_notifications().await
+    }
+
+    pub async fn invoice_usage_threshold(
+        &self,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+    ) -> StoreResult<Option<DetailedInvoice>> {
+        self.services
+            .invoice_usage_threshold(tenant_id, subscription_id)
+            .await
+    }
+
+    pub async fn export_catalog(
+        &self,
+        tenant_id: TenantId,
+    ) -> StoreResult<crate::domain::catalog::CatalogDocument> {
+        self.services.export_catalog(tenant_id).await
+    }
+
+    pub async fn diff_catalog(
+        &self,
+        tenant_id: TenantId,
+        document: &crate::domain::catalog::CatalogDocument,
+        prune: bool,
+    ) -> StoreResult<crate::domain::catalog::CatalogDiff> {
+        self.services.diff_catalog(tenant_id, document, prune).await
+    }
+
+    pub async fn apply_catalog(
+        &self,
+        tenant_id: TenantId,
+        actor: Actor,
+        document: &crate::domain::catalog::CatalogDocument,
+        prune: bool,
+    ) -> StoreResult<crate::domain::catalog::CatalogDiff> {
+        self.services
+            .apply_catalog(tenant_id, actor, document, prune)
+            .await
+    }
+
     pub async fn on_invoice_accounting_pdf_generated(
         &self,
         event: InvoicePdfGeneratedEvent,
@@ -1246,6 +1456,38 @@ impl ServicesEdge {
             .await
     }
 
+    pub async fn approve_invoice(
+        &self,
+        actor: Actor,
+        invoice_id: InvoiceId,
+        tenant_id: TenantId,
+        comment: Option<String>,
+    ) -> StoreResult<DetailedInvoice> {
+        self.services
+            .approve_invoice(actor, invoice_id, tenant_id, comment)
+            .await
+    }
+
+    pub async fn reject_invoice(
+        &self,
+        actor: Actor,
+        invoice_id: InvoiceId,
+        tenant_id: TenantId,
+        comment: String,
+    ) -> StoreResult<DetailedInvoice> {
+        self.services
+            .reject_invoice(actor, invoice_id, tenant_id, comment)
+            .await
+    }
+
+    pub async fn create_oneoff_invoice(
+        &self,
+        tenant_id: TenantId,
+        params: OneOffInvoiceNew,
+    ) -> StoreResult<DetailedInvoice> {
+        self.services.create_oneoff_invoice(tenant_id, params).await
+    }
+
     pub async fn create_corrected_invoice_from(
         &self,
         tenant_id: TenantId,
@@ -2974,14 +3216,7 @@ impl ServicesEdge {
                     .store
                     .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
                     .await?;
-                let customer = diesel_models::customers::CustomerRow::find_by_id(
-                    conn,
-                    &subscription.subscription.customer_id,
-                    &tenant_id,
-                )
-                .await
-                .map_err(Into::<Report<StoreError>>::into)?;
-                let customer: crate::domain::Customer = customer.try_into()?;
+                let customer = subscription.billed_customer().clone();
 
                 let draft = self
                     .services
@@ -3081,7 +3316,7 @@ impl ServicesEdge {
                         conn,
                         tenant_id,
                         &prepared.subscription_details.subscription,
-                        &prepared.subscription_details.customer,
+                        prepared.subscription_details.billed_customer(),
                         &prepared.proration,
                     )
                     .await?;
@@ -3101,7 +3336,7 @@ impl ServicesEdge {
                     .create_adjustment_invoice_from_content(
                         conn,
                         &prepared.subscription_details.subscription,
-                        &prepared.subscription_details.customer,
+                        prepared.subscription_details.billed_customer(),
                         &prepared.proration,
                         content,
                     )
@@ -3243,7 +3478,7 @@ impl ServicesEdge {
                             conn,
                             tenant_id,
                             &prepared.subscription_details.subscription,
-                            &prepared.subscription_details.customer,
+                            prepared.subscription_details.billed_customer(),
                             &prepared.proration,
                         )
                         .await?;
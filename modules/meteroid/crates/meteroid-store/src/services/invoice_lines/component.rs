@@ -17,7 +17,11 @@ use crate::repositories::subscriptions::SubscriptionSlotsInterface;
 use crate::services::Services;
 use crate::services::clients::usage::{GroupedUsageData, UsageData};
 use crate::store::PgConn;
-use common_domain::ids::{BillableMetricId, SubscriptionAddOnId, SubscriptionPriceComponentId};
+use crate::utils::custom_fields::CustomFields;
+use common_domain::ids::{
+    BillableMetricId, CustomerId, PriceComponentId, SubscriptionAddOnId,
+    SubscriptionPriceComponentId,
+};
 use common_utils::decimals::ToSubunit;
 use common_utils::integers::{ToNonNegativeU64, only_positive, only_positive_decimal};
 use error_stack::{Report, ResultExt};
@@ -71,6 +75,8 @@ impl Services {
         invoice_date: &NaiveDate,
         precision: u8,
         existing_lines: &HashMap<ExistingLineKey, &LineItem>,
+        component_metadata: &HashMap<PriceComponentId, CustomFields>,
+        tier_pool: Option<Decimal>,
     ) -> StoreResult<Vec<LineItem>> {
         let is_first_period = periods.arrear.is_none();
 
@@ -399,29 +405,63 @@ impl Services {
 
                                         lines.push(line);
                                     }
-                                    UsagePricingModel::Tiered { tiers, block_size } => {
-                                        let mut line = fees::compute_tier_price(
-                                            usage_units,
-                                            tiers,
-                                            arrear_period.clone(),
-                                            precision,
-                                            *metric_id,
-                                            block_size,
-                                        )?;
+                                    UsagePricingModel::Tiered {
+                                        tiers,
+                                        block_size,
+                                        tier_group,
+                                    } => {
+                                        let mut line = match tier_group.as_ref().zip(tier_pool) {
+                                            Some((group, pooled_units)) => {
+                                                fees::compute_pooled_tier_price(
+                                                    usage_units,
+                                                    usage_units * group.units_per_quantity,
+                                                    pooled_units,
+                                                    tiers,
+                                                    arrear_period.clone(),
+                                                    precision,
+                                                    *metric_id,
+                                                )?
+                                            }
+                                            None => fees::compute_tier_price(
+                                                usage_units,
+                                                tiers,
+                                                arrear_period.clone(),
+                                                precision,
+                                                *metric_id,
+                                                block_size,
+                                            )?,
+                                        };
 
                                         line.group_by_dimensions =
                                             Some(grouped_usage.dimensions.clone());
                                         lines.push(line);
                                     }
-                                    UsagePricingModel::Volume { tiers, block_size } => {
-                                        let mut line = fees::compute_volume_price(
-                                            usage_units,
-                                            tiers,
-                                            arrear_period.clone(),
-                                            precision,
-                                            *metric_id,
-                                            block_size,
-                                        )?;
+                                    UsagePricingModel::Volume {
+                                        tiers,
+                                        block_size,
+                                        tier_group,
+                                    } => {
+                                        let mut line = match tier_group.as_ref().zip(tier_pool) {
+                                            Some((group, pooled_units)) => {
+                                                fees::compute_pooled_volume_price(
+                                                    usage_units,
+                                                    usage_units * group.units_per_quantity,
+                                                    pooled_units,
+                                                    tiers,
+                                                    arrear_period.clone(),
+                                                    precision,
+                                                    *metric_id,
+                                                )?
+                                            }
+                                            None => fees::compute_volume_price(
+                                                usage_units,
+                                                tiers,
+                                                arrear_period.clone(),
+                                                precision,
+                                                *metric_id,
+                                                block_size,
+                                            )?,
+                                        };
 
                                         line.group_by_dimensions =
                                             Some(grouped_usage.dimensions.clone());
@@ -468,6 +508,18 @@ impl Services {
                 }
             }
         }
+        let instance = subscription_details.subscription.instance.as_ref();
+        let instance_id = instance.map(|instance| instance.id.clone());
+        // The metadata of the price component, overridden key by key by the plan instance one
+        let mut line_metadata = component
+            .price_component_id()
+            .and_then(|id| component_metadata.get(&id))
+            .cloned()
+            .unwrap_or_default();
+        if let Some(instance) = instance {
+            line_metadata.extend(instance.metadata.clone());
+        }
+
         Ok(lines
             .into_iter()
             .map(|line| {
@@ -518,8 +570,15 @@ impl Services {
                     sub_add_on_id: component.sub_add_on_id(),
                     product_id: component.product_id(),
                     metric_id: line.metric_id,
-                    description: existing_line.and_then(|el| el.description.clone()),
+                    // Lines of a plan instance are told apart by its identifier
+                    description: existing_line
+                        .and_then(|el| el.description.clone())
+                        .or_else(|| instance_id.clone()),
                     group_by_dimensions: line.group_by_dimensions,
+                    // Edited on the draft, else carried over from the component and plan instance
+                    metadata: existing_line
+                        .map(|el| el.metadata.clone())
+                        .unwrap_or_else(|| line_metadata.clone()),
 
                     amount_subtotal: line.total as i64,
 
@@ -534,11 +593,30 @@ impl Services {
             .collect())
     }
 
-    async fn fetch_usage(
+    pub(super) async fn fetch_usage(
         &self,
         period: Period,
         metric_id: BillableMetricId,
         subscription_details: &SubscriptionDetails,
+    ) -> StoreResult<UsageData> {
+        self.fetch_customer_usage(
+            period,
+            metric_id,
+            subscription_details.subscription.customer_id,
+            subscription_details,
+        )
+        .await
+    }
+
+    /// Usage of a metric of the subscription, as recorded for `customer_id`. The instance
+    /// filter of the subscription only applies to its own customer: the usage of the other
+    /// customers of its hierarchy is pooled as a whole.
+    pub(super) async fn fetch_customer_usage(
+        &self,
+        period: Period,
+        metric_id: BillableMetricId,
+        customer_id: CustomerId,
+        subscription_details: &SubscriptionDetails,
     ) -> StoreResult<UsageData> {
         let metric = subscription_details
             .metrics
@@ -548,13 +626,17 @@ impl Services {
                 "metric with id {metric_id}"
             )))?;
 
+        let filter = (customer_id == subscription_details.subscription.customer_id)
+            .then(|| subscription_details.subscription.usage_filter());
+
         let usage = self
             .usage_client
             .fetch_usage(
                 &subscription_details.subscription.tenant_id,
-                &subscription_details.subscription.customer_id,
+                &customer_id,
                 metric,
                 period.into(),
+                filter.as_ref(),
             )
             .await?;
 
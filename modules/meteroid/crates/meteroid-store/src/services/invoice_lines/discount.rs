@@ -1,6 +1,6 @@
-use crate::domain::coupons::{AppliedCouponsDiscount, CouponDiscount};
+use crate::domain::coupons::{AppliedCouponsDiscount, Coupon, CouponDiscount};
 use crate::domain::{AppliedCouponDetailed, CouponLineItem, LineItem};
-use common_domain::ids::BaseId;
+use common_domain::ids::{AppliedCouponId, BaseId};
 use common_utils::decimals::ToSubunit;
 use common_utils::integers::ToNonNegativeU64;
 use itertools::Itertools;
@@ -66,19 +66,54 @@ pub fn calculate_coupons_discount(
     subtotal: i64,
     invoice_currency: &str,
     coupons: &[AppliedCouponDetailed],
+) -> AppliedCouponsDiscount {
+    let applicable_coupons = coupons
+        .iter()
+        .filter(|x| x.is_invoice_applicable())
+        .map(|x| {
+            (
+                &x.coupon,
+                Some(x.applied_coupon.id),
+                x.applied_coupon.applied_amount.unwrap_or(Decimal::ZERO),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    apply_coupons(subtotal, invoice_currency, applicable_coupons)
+}
+
+/// Discount of catalog coupons applied directly to a one-off invoice. They follow the same
+/// precedence as subscription coupons; a fixed amount is not consumed across invoices.
+pub fn calculate_oneoff_coupons_discount(
+    subtotal: i64,
+    invoice_currency: &str,
+    coupons: &[Coupon],
+) -> AppliedCouponsDiscount {
+    let applicable_coupons = coupons
+        .iter()
+        .map(|coupon| (coupon, None, Decimal::ZERO))
+        .collect::<Vec<_>>();
+
+    apply_coupons(subtotal, invoice_currency, applicable_coupons)
+}
+
+/// Applies the coupons (with the amount already consumed for fixed ones) to the subtotal.
+fn apply_coupons(
+    subtotal: i64,
+    invoice_currency: &str,
+    coupons: Vec<(&Coupon, Option<AppliedCouponId>, Decimal)>,
 ) -> AppliedCouponsDiscount {
     // Customer-friendly precedence: percentages first, then fixed amounts.
     // Tiebreak on the underlying coupon.id so preview and invoice paths agree
     // (applied_coupon.id is regenerated per preview and would diverge).
-    let applicable_coupons: Vec<&AppliedCouponDetailed> = coupons
-        .iter()
-        .filter(|x| x.is_invoice_applicable())
-        .sorted_by_key(|x| {
-            let type_precedence = match x.coupon.discount {
+    let applicable_coupons = coupons
+        .into_iter()
+        .sorted_by_key(|(coupon, _, _)| {
+            let type_precedence = match coupon.discount {
                 CouponDiscount::Percentage(_) => 0u8,
                 CouponDiscount::Fixed { .. } => 1u8,
             };
-            (type_precedence, x.coupon.id.as_uuid())
+            (type_precedence, coupon.id.as_uuid())
         })
         .collect::<Vec<_>>();
 
@@ -86,11 +121,11 @@ pub fn calculate_coupons_discount(
 
     let mut subtotal_subunits = Decimal::from(subtotal);
 
-    for applicable_coupon in applicable_coupons {
+    for (coupon, applied_coupon_id, consumed_amount) in applicable_coupons {
         if subtotal_subunits <= Decimal::ONE {
             break;
         }
-        let discount = match &applicable_coupon.coupon.discount {
+        let discount = match &coupon.discount {
             CouponDiscount::Percentage(percentage) => {
                 (subtotal_subunits * percentage / Decimal::ONE_HUNDRED).min(subtotal_subunits)
             }
@@ -102,11 +137,6 @@ pub fn calculate_coupons_discount(
                 // todo domain should use Currency type instead of string
                 let cur = rusty_money::iso::find(currency).unwrap_or(rusty_money::iso::USD);
 
-                let consumed_amount = &applicable_coupon
-                    .applied_coupon
-                    .applied_amount
-                    .unwrap_or(Decimal::ZERO);
-
                 let discount_subunits = (amount - consumed_amount)
                     .to_subunit_opt(cur.exponent as u8)
                     .unwrap_or(0);
@@ -119,12 +149,12 @@ pub fn calculate_coupons_discount(
 
         let discount = discount.to_i64().unwrap_or(0);
         applied_coupons_items.push(CouponLineItem {
-            coupon_id: applicable_coupon.coupon.id,
-            applied_coupon_id: applicable_coupon.applied_coupon.id,
-            name: format!("Coupon ({})", applicable_coupon.coupon.code), // TODO allow defining a name in coupon
-            code: applicable_coupon.coupon.code.clone(),
+            coupon_id: coupon.id,
+            applied_coupon_id,
+            name: format!("Coupon ({})", coupon.code), // TODO allow defining a name in coupon
+            code: coupon.code.clone(),
             value: discount,
-            discount: applicable_coupon.coupon.discount.clone(),
+            discount: coupon.discount.clone(),
         });
     }
 
@@ -263,6 +293,28 @@ mod tests {
         assert_eq!(r1.discount_subunit, 6000);
     }
 
+    #[test]
+    fn oneoff_coupons_have_no_applied_coupon() {
+        let pct = applied_coupon("PCT", CouponDiscount::Percentage(Decimal::from(10)));
+        let fix = applied_coupon(
+            "FIX",
+            CouponDiscount::Fixed {
+                currency: "USD".to_string(),
+                amount: Decimal::from(50),
+            },
+        );
+
+        let r = calculate_oneoff_coupons_discount(10_000, "USD", &[fix.coupon, pct.coupon]);
+
+        assert_eq!(r.discount_subunit, 6000);
+        assert_eq!(r.applied_coupons[0].code, "PCT");
+        assert!(
+            r.applied_coupons
+                .iter()
+                .all(|c| c.applied_coupon_id.is_none())
+        );
+    }
+
     fn new_line_item(amount_subtotal: i64) -> LineItem {
         LineItem {
             local_id: "test".to_string(),
@@ -285,6 +337,7 @@ mod tests {
             description: None,
             amount_total: amount_subtotal,
             group_by_dimensions: None,
+            metadata: Default::default(),
             tax_details: vec![],
         }
     }
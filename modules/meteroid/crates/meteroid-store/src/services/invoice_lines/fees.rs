@@ -123,7 +123,50 @@ pub fn compute_volume_price(
     _block_size: &Option<u64>,
 ) -> StoreResult<InvoiceLineInner> {
     let charge = volume_charge(usage_units, tiers);
-    let amount = charge.as_ref().map_or(Decimal::ZERO, |c| c.amount);
+    volume_line(
+        usage_units,
+        usage_units,
+        charge,
+        Decimal::ONE,
+        period,
+        precision,
+        metric_id,
+    )
+}
+
+/// Volume pricing evaluated on the pooled quantity of a tier group. The line is billed the
+/// share of the pooled amount matching its `line_units` (usage converted to pooled units).
+pub fn compute_pooled_volume_price(
+    usage_units: Decimal,
+    line_units: Decimal,
+    pooled_units: Decimal,
+    tiers: &[TierRow],
+    period: Period,
+    precision: u8,
+    metric_id: BillableMetricId,
+) -> StoreResult<InvoiceLineInner> {
+    let charge = volume_charge(pooled_units, tiers);
+    volume_line(
+        usage_units,
+        line_units,
+        charge,
+        pool_share(line_units, pooled_units),
+        period,
+        precision,
+        metric_id,
+    )
+}
+
+fn volume_line(
+    usage_units: Decimal,
+    line_units: Decimal,
+    charge: Option<VolumeCharge>,
+    share: Decimal,
+    period: Period,
+    precision: u8,
+    metric_id: BillableMetricId,
+) -> StoreResult<InvoiceLineInner> {
+    let amount = charge.as_ref().map_or(Decimal::ZERO, |c| c.amount) * share;
     let unit_price = charge.as_ref().map_or(Decimal::ZERO, |c| c.unit_price);
     let attributes = charge.as_ref().map(|c| SubLineAttributes::Volume {
         first_unit: c.first_unit,
@@ -148,7 +191,7 @@ pub fn compute_volume_price(
             local_id: LocalId::no_prefix(),
             name: "Volume".to_string(),
             total,
-            quantity: usage_units,
+            quantity: line_units,
             unit_price,
             attributes,
         }],
@@ -166,12 +209,52 @@ pub fn compute_tier_price(
     _block_size: &Option<u64>,
 ) -> StoreResult<InvoiceLineInner> {
     let charges = tiered_charges(usage_units, tiers);
+    tier_line(
+        usage_units,
+        &charges,
+        Decimal::ONE,
+        period,
+        precision,
+        metric_id,
+    )
+}
+
+/// Graduated tiers evaluated on the pooled quantity of a tier group. Each tier's charge is
+/// split pro rata, the line being billed the share matching its `line_units`.
+pub fn compute_pooled_tier_price(
+    usage_units: Decimal,
+    line_units: Decimal,
+    pooled_units: Decimal,
+    tiers: &[TierRow],
+    period: Period,
+    precision: u8,
+    metric_id: BillableMetricId,
+) -> StoreResult<InvoiceLineInner> {
+    let charges = tiered_charges(pooled_units, tiers);
+    tier_line(
+        usage_units,
+        &charges,
+        pool_share(line_units, pooled_units),
+        period,
+        precision,
+        metric_id,
+    )
+}
 
+fn tier_line(
+    usage_units: Decimal,
+    charges: &[TierCharge],
+    share: Decimal,
+    period: Period,
+    precision: u8,
+    metric_id: BillableMetricId,
+) -> StoreResult<InvoiceLineInner> {
     let mut subtotal = Decimal::ZERO;
     let mut sub_lines = Vec::with_capacity(charges.len());
 
-    for charge in &charges {
-        subtotal += charge.amount;
+    for charge in charges {
+        let amount = charge.amount * share;
+        subtotal += amount;
         sub_lines.push(SubLineItem {
             local_id: LocalId::no_prefix(),
             name: format!(
@@ -179,12 +262,11 @@ pub fn compute_tier_price(
                 charge.first_unit,
                 charge.last_unit.map_or("∞".to_string(), |s| s.to_string())
             ),
-            total: charge
-                .amount
+            total: amount
                 .to_subunit_opt(precision)
                 .ok_or(Report::new(StoreError::InvalidDecimal))
                 .attach("Failed to convert subline total to subunit")?,
-            quantity: charge.units,
+            quantity: charge.units * share,
             unit_price: charge.unit_price,
             attributes: Some(SubLineAttributes::Tiered {
                 first_unit: charge.first_unit,
@@ -212,6 +294,14 @@ pub fn compute_tier_price(
     })
 }
 
+/// Fraction of a pooled amount billed to a line contributing `line_units`.
+fn pool_share(line_units: Decimal, pooled_units: Decimal) -> Decimal {
+    if pooled_units <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    (line_units / pooled_units).min(Decimal::ONE)
+}
+
 /// Prices a usage quantity under a pricing model, reusing the invoicing math so
 /// the result matches what would be billed. Returns `None` for Matrix, which
 /// needs per-dimension quantities. Display/estimation only.
@@ -305,7 +395,8 @@ mod tests {
             compute_usage_price(
                 &UsagePricingModel::Tiered {
                     tiers: tiers.to_vec(),
-                    block_size: None
+                    block_size: None,
+                    tier_group: None,
                 },
                 dec!(150),
                 "USD"
@@ -366,7 +457,8 @@ mod tests {
             compute_usage_price(
                 &UsagePricingModel::Volume {
                     tiers: tiers.to_vec(),
-                    block_size: None
+                    block_size: None,
+                    tier_group: None,
                 },
                 dec!(150),
                 "USD"
@@ -376,6 +468,68 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pooled_tiered_allocates_by_share() {
+        let tiers = [
+            tier(0, dec!(1.0), None, None),
+            tier(100, dec!(0.5), None, None),
+        ];
+        // pool of 300 units: 100*1 + 200*0.5 = 200, split 120/180
+        let api_calls = compute_pooled_tier_price(
+            dec!(1200),
+            dec!(120),
+            dec!(300),
+            &tiers,
+            period(),
+            2,
+            BillableMetricId::new(),
+        )
+        .unwrap();
+        let storage = compute_pooled_tier_price(
+            dec!(18),
+            dec!(180),
+            dec!(300),
+            &tiers,
+            period(),
+            2,
+            BillableMetricId::new(),
+        )
+        .unwrap();
+
+        assert_eq!(line_total(&api_calls), dec!(80));
+        assert_eq!(line_total(&storage), dec!(120));
+        assert_eq!(api_calls.quantity, Some(dec!(1200)));
+        assert_eq!(
+            api_calls
+                .sublines
+                .iter()
+                .map(|s| s.quantity)
+                .collect::<Vec<_>>(),
+            vec![dec!(40), dec!(80)]
+        );
+    }
+
+    #[test]
+    fn pooled_volume_uses_pooled_tier() {
+        let tiers = [
+            tier(0, dec!(1.0), None, None),
+            tier(100, dec!(0.5), None, None),
+        ];
+        // 60 units alone would land in the first tier; the pool of 150 reaches the second
+        let line = compute_pooled_volume_price(
+            dec!(60),
+            dec!(60),
+            dec!(150),
+            &tiers,
+            period(),
+            2,
+            BillableMetricId::new(),
+        )
+        .unwrap();
+        assert_eq!(line_total(&line), dec!(30));
+        assert_eq!(line.sublines[0].unit_price, dec!(0.5));
+    }
+
     #[test]
     fn per_unit_and_package() {
         let per_unit = UsagePricingModel::PerUnit { rate: dec!(0.001) };
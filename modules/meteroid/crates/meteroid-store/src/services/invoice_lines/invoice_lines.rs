@@ -6,7 +6,9 @@ use crate::domain::{
     SubscriptionFeeInterface, TaxBreakdownItem, TaxResolverEnum, VatNumberValidationStatus,
 };
 use chrono::NaiveDate;
-use common_domain::ids::{PriceComponentId, SubscriptionAddOnId, SubscriptionPriceComponentId};
+use common_domain::ids::{
+    CustomerId, PriceComponentId, SubscriptionAddOnId, SubscriptionPriceComponentId,
+};
 use diesel_models::subscription_add_ons::SubscriptionAddOnRow;
 use diesel_models::subscription_components::SubscriptionComponentRow;
 use itertools::Itertools;
@@ -21,11 +23,16 @@ use crate::services::Services;
 use crate::services::invoice_lines::component::ExistingLineKey;
 use crate::services::invoice_lines::discount::calculate_coupons_discount;
 use crate::store::PgConn;
+use crate::utils::custom_fields::CustomFields;
 use crate::utils::periods::calculate_component_period_for_invoice_date;
 use common_utils::integers::ToNonNegativeU64;
 use diesel_models::billable_metrics::BillableMetricRow;
+use diesel_models::customers::CustomerRow;
+use diesel_models::invoices::InvoiceRow;
+use diesel_models::price_components::PriceComponentRow;
 use error_stack::{Report, ResultExt};
 use meteroid_tax::{ManualTaxEngine, MeteroidTaxEngine, TaxDetails, TaxEngine};
+use rust_decimal::Decimal;
 
 impl Services {}
 
@@ -67,8 +74,11 @@ impl Services {
             let total = invoice.total as u64;
             let balance_in_invoice_currency = convert_currency(
                 conn,
-                subscription_details.customer.balance_value_cents.max(0),
-                &subscription_details.customer.currency,
+                subscription_details
+                    .billed_customer()
+                    .balance_value_cents
+                    .max(0),
+                &subscription_details.billed_customer().currency,
                 &subscription_details.subscription.currency,
             )
             .await?;
@@ -348,6 +358,15 @@ impl Services {
         // (multiple sub_component_ids for the same price_component_id)
         apply_temporal_date_range_to_names(&mut invoice_lines);
 
+        // Usage billed early by usage threshold invoices is only charged for the remainder
+        self.deduct_threshold_invoiced_usage(
+            conn,
+            subscription_details,
+            &mut invoice_lines,
+            currency,
+        )
+        .await?;
+
         let subtotal = invoice_lines
             .iter()
             .fold(0, |acc, x| acc + x.amount_subtotal);
@@ -366,7 +385,7 @@ impl Services {
             .process_invoice_lines_taxes(
                 invoice_lines,
                 &subscription_details.invoicing_entity,
-                &subscription_details.customer,
+                subscription_details.billed_customer(),
                 subscription_details.subscription.currency.clone(),
                 &invoice_date,
             )
@@ -386,8 +405,11 @@ impl Services {
         let total = subtotal_with_discounts + tax_amount;
         let balance_in_invoice_currency = convert_currency(
             conn,
-            subscription_details.customer.balance_value_cents.max(0),
-            &subscription_details.customer.currency,
+            subscription_details
+                .billed_customer()
+                .balance_value_cents
+                .max(0),
+            &subscription_details.billed_customer().currency,
             &subscription_details.subscription.currency,
         )
         .await?;
@@ -722,6 +744,47 @@ impl Services {
         Ok((updated_invoice_lines, breakdown))
     }
 
+    /// Deducts the usage already billed by `UsageThreshold` invoices from the matching usage lines.
+    async fn deduct_threshold_invoiced_usage(
+        &self,
+        conn: &mut PgConn,
+        subscription_details: &SubscriptionDetails,
+        invoice_lines: &mut [LineItem],
+        currency: &Currency,
+    ) -> StoreResult<()> {
+        let Some(since) = invoice_lines
+            .iter()
+            .filter(|line| line.metric_id.is_some())
+            .map(|line| line.start_date)
+            .min()
+        else {
+            return Ok(());
+        };
+
+        let threshold_invoices = InvoiceRow::list_usage_threshold_invoices(
+            conn,
+            subscription_details.subscription.tenant_id,
+            subscription_details.subscription.id,
+            since,
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+        let mut billed: HashMap<(ExistingLineKey, NaiveDate), i64> = HashMap::new();
+        for row in threshold_invoices {
+            let invoice: Invoice = row.try_into()?;
+            for line in &invoice.line_items {
+                if let Some(key) = ExistingLineKey::from_line_item(line) {
+                    *billed.entry((key, line.start_date)).or_default() += line.amount_subtotal;
+                }
+            }
+        }
+
+        deduct_billed_usage(invoice_lines, &billed, currency);
+
+        Ok(())
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn process_fee_records<T: SubscriptionFeeInterface>(
         &self,
@@ -734,6 +797,9 @@ impl Services {
         currency: &Currency,
         existing_lines: &HashMap<ExistingLineKey, &LineItem>,
     ) -> StoreResult<Vec<LineItem>> {
+        let component_metadata =
+            component_line_metadata(conn, subscription_details, fee_records).await?;
+
         // One-time fees are billed exactly once and follow a different rule than the
         // cadence-based components below: `applies_this_period` only admits one-time
         // fees on cycle 0, which would silently drop a one-time fee added by a later
@@ -747,9 +813,6 @@ impl Services {
             .into_iter()
             .into_group_map_by(|c| c.period_ref());
 
-        // TODO case when invoiced early via threshold (that's for usage-based only)
-        // can be quite easy => we need some last_invoice_threshold date in the subscription, to reduce the usage periods if that date is within the period
-
         let component_period_components: Vec<(ComponentPeriods, Vec<&T>)> = component_groups
             .into_iter()
             .filter_map(|(billing_period, components)| {
@@ -775,10 +838,25 @@ impl Services {
             })
             .collect();
 
+        let tier_pools = self
+            .compute_tier_pools(conn, subscription_details, &component_period_components)
+            .await?;
+
         // we can now compute all the components for each period
         let mut invoice_lines = Vec::new();
         for (period, components) in component_period_components {
             for component in components {
+                let tier_pool = match component.fee_ref() {
+                    SubscriptionFee::Usage { model, .. } => model
+                        .tier_group()
+                        .zip(period.arrear.as_ref())
+                        .and_then(|(group, arrear)| {
+                            tier_pools.get(&(group.key.clone(), arrear.clone()))
+                        })
+                        .copied(),
+                    _ => None,
+                };
+
                 // Apply temporal bounds to arrear period for usage-based billing split
                 let adjusted_period = restrict_arrear_period_by_temporal_bounds(
                     period.clone(),
@@ -795,6 +873,8 @@ impl Services {
                         &invoice_date,
                         currency.precision,
                         existing_lines,
+                        &component_metadata,
+                        tier_pool,
                     )
                     .await?;
 
@@ -837,6 +917,8 @@ impl Services {
                     &invoice_date,
                     currency.precision,
                     existing_lines,
+                    &component_metadata,
+                    None,
                 )
                 .await?;
 
@@ -845,6 +927,136 @@ impl Services {
 
         Ok(invoice_lines)
     }
+
+    /// Combined usage of the usage fees sharing a tier group, in pooled units, keyed by group
+    /// and arrear period. Fees only pool with fees billed over the same period.
+    async fn compute_tier_pools<T: SubscriptionFeeInterface>(
+        &self,
+        conn: &mut PgConn,
+        subscription_details: &SubscriptionDetails,
+        component_period_components: &[(ComponentPeriods, Vec<&T>)],
+    ) -> StoreResult<HashMap<(String, Period), Decimal>> {
+        let mut pools: HashMap<(String, Period), Decimal> = HashMap::new();
+        let pool_members = usage_pool_members(conn, &subscription_details.customer).await?;
+
+        for (period, components) in component_period_components {
+            let Some(arrear) = &period.arrear else {
+                continue;
+            };
+
+            for component in components {
+                let SubscriptionFee::Usage { metric_id, model } = component.fee_ref() else {
+                    continue;
+                };
+                let Some(group) = model.tier_group() else {
+                    continue;
+                };
+
+                let adjusted_period = restrict_arrear_period_by_temporal_bounds(
+                    period.clone(),
+                    component.effective_from(),
+                    component.effective_to(),
+                );
+                let Some(usage_period) = adjusted_period.arrear else {
+                    continue;
+                };
+
+                let usage = self
+                    .fetch_usage(usage_period.clone(), *metric_id, subscription_details)
+                    .await?;
+                let mut units: Decimal = usage
+                    .data
+                    .iter()
+                    .map(|grouped| grouped.value)
+                    .filter(|value| *value > Decimal::ZERO)
+                    .sum();
+
+                // The usage of the rest of the hierarchy counts towards the same tiers
+                for member_id in &pool_members {
+                    let usage = self
+                        .fetch_customer_usage(
+                            usage_period.clone(),
+                            *metric_id,
+                            *member_id,
+                            subscription_details,
+                        )
+                        .await?;
+                    units += usage
+                        .data
+                        .iter()
+                        .map(|grouped| grouped.value)
+                        .filter(|value| *value > Decimal::ZERO)
+                        .sum::<Decimal>();
+                }
+
+                *pools
+                    .entry((group.key.clone(), arrear.clone()))
+                    .or_default() += units * group.units_per_quantity;
+            }
+        }
+
+        Ok(pools)
+    }
+}
+
+/// Line metadata of the price components the fees come from, for the components having some.
+async fn component_line_metadata<T: SubscriptionFeeInterface>(
+    conn: &mut PgConn,
+    subscription_details: &SubscriptionDetails,
+    fee_records: &[T],
+) -> StoreResult<HashMap<PriceComponentId, CustomFields>> {
+    let ids: Vec<PriceComponentId> = fee_records
+        .iter()
+        .filter_map(|c| c.price_component_id())
+        .unique()
+        .collect();
+    if ids.is_empty() {
+        return Ok(HashMap::new());
+    }
+
+    let rows =
+        PriceComponentRow::list_by_ids(conn, subscription_details.subscription.tenant_id, &ids)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+    Ok(rows
+        .into_iter()
+        .filter_map(|row| {
+            let metadata: CustomFields = serde_json::from_value(row.line_metadata).ok()?;
+            (!metadata.is_empty()).then_some((row.id, metadata))
+        })
+        .collect())
+}
+
+/// Other customers of the hierarchy whose usage is pooled with `customer` for tiering.
+/// A hierarchy pools the usage of the parent and of the children that opted in; a child
+/// that did not opt in is tiered on its own usage only.
+async fn usage_pool_members(
+    conn: &mut PgConn,
+    customer: &Customer,
+) -> StoreResult<Vec<CustomerId>> {
+    let root_id = match customer.parent_customer_id {
+        Some(parent_id) if customer.pool_usage_with_parent => parent_id,
+        Some(_) => return Ok(vec![]),
+        None => customer.id,
+    };
+
+    let children = CustomerRow::list_children(conn, customer.tenant_id, root_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+    if children.is_empty() {
+        return Ok(vec![]);
+    }
+
+    Ok(std::iter::once(root_id)
+        .chain(
+            children
+                .into_iter()
+                .filter(|child| child.pool_usage_with_parent)
+                .map(|child| child.id),
+        )
+        .filter(|id| *id != customer.id)
+        .collect())
 }
 
 /// Restrict the arrear period of a ComponentPeriods based on a component's temporal bounds.
@@ -931,9 +1143,40 @@ fn apply_temporal_date_range_to_names(lines: &mut [LineItem]) {
     }
 }
 
+/// Reduces usage lines by the amount already billed for the same usage and period.
+/// The per-unit breakdown no longer adds up to the remainder, so it is dropped.
+fn deduct_billed_usage(
+    lines: &mut [LineItem],
+    billed: &HashMap<(ExistingLineKey, NaiveDate), i64>,
+    currency: &Currency,
+) {
+    for line in lines.iter_mut() {
+        let Some(key) = ExistingLineKey::from_line_item(line) else {
+            continue;
+        };
+        let already_billed = billed.get(&(key, line.start_date)).copied().unwrap_or(0);
+        if already_billed <= 0 {
+            continue;
+        }
+
+        let remaining = (line.amount_subtotal - already_billed).max(0);
+        line.amount_subtotal = remaining;
+        line.taxable_amount = remaining;
+        line.amount_total = remaining;
+        line.unit_price = None;
+        line.sub_lines.clear();
+        line.description = Some(format!(
+            "{} {} already invoiced on usage threshold",
+            Decimal::new(already_billed, u32::from(currency.precision)),
+            currency.code
+        ));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use common_domain::ids::{BaseId, BillableMetricId};
 
     fn date(y: i32, m: u32, d: u32) -> NaiveDate {
         NaiveDate::from_ymd_opt(y, m, d).unwrap()
@@ -1012,4 +1255,67 @@ mod tests {
 
         assert!(periods.arrear.is_none());
     }
+
+    fn usage_line(amount: i64, start: NaiveDate) -> LineItem {
+        LineItem {
+            local_id: "line".to_string(),
+            name: "Bandwidth".to_string(),
+            amount_subtotal: amount,
+            tax_rate: Decimal::ZERO,
+            taxable_amount: amount,
+            tax_amount: 0,
+            amount_total: amount,
+            tax_details: vec![],
+            quantity: Some(Decimal::from(amount)),
+            unit_price: Some(Decimal::new(1, 2)),
+            start_date: start,
+            end_date: date(2025, 2, 1),
+            sub_lines: vec![],
+            is_prorated: false,
+            price_component_id: None,
+            sub_component_id: Some(SubscriptionPriceComponentId::new()),
+            sub_add_on_id: None,
+            product_id: None,
+            metric_id: Some(BillableMetricId::new()),
+            description: None,
+            group_by_dimensions: None,
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn threshold_billed_usage_is_deducted_from_matching_period() {
+        let eur = Currencies::resolve_currency("EUR").unwrap();
+        let line = usage_line(5000, date(2025, 1, 1));
+        let key = ExistingLineKey::from_line_item(&line).unwrap();
+        let billed = HashMap::from([((key, date(2025, 1, 1)), 3000)]);
+
+        let mut lines = vec![line.clone(), usage_line(5000, date(2024, 12, 1))];
+        lines[1].sub_component_id = line.sub_component_id;
+        lines[1].metric_id = line.metric_id;
+        deduct_billed_usage(&mut lines, &billed, eur);
+
+        assert_eq!(lines[0].amount_subtotal, 2000);
+        assert_eq!(lines[0].amount_total, 2000);
+        assert_eq!(lines[0].unit_price, None);
+        assert_eq!(
+            lines[0].description.as_deref(),
+            Some("30.00 EUR already invoiced on usage threshold")
+        );
+        // Another usage period is left untouched
+        assert_eq!(lines[1].amount_subtotal, 5000);
+        assert!(lines[1].description.is_none());
+    }
+
+    #[test]
+    fn threshold_billed_usage_never_goes_negative() {
+        let eur = Currencies::resolve_currency("EUR").unwrap();
+        let mut lines = vec![usage_line(1000, date(2025, 1, 1))];
+        let key = ExistingLineKey::from_line_item(&lines[0]).unwrap();
+        let billed = HashMap::from([((key, date(2025, 1, 1)), 3000)]);
+
+        deduct_billed_usage(&mut lines, &billed, eur);
+
+        assert_eq!(lines[0].amount_subtotal, 0);
+    }
 }
@@ -1,6 +1,7 @@
 mod component;
 #[allow(clippy::module_inception)]
 pub mod invoice_lines;
+pub mod presentation;
 
 pub mod discount;
 pub mod fees;
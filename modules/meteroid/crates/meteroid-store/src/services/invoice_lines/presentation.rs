@@ -0,0 +1,426 @@
+use crate::domain::Invoice;
+use crate::domain::invoice_lines::{LineItem, SubLineItem, TaxDetail};
+use crate::domain::price_components::SubLinePresentation;
+use crate::repositories::price_components::PriceComponentInterface;
+use crate::{Store, StoreResult};
+use chrono::NaiveDate;
+use common_domain::ids::{PriceComponentId, TenantId};
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+const OTHER_GROUPS_NAME: &str = "Other";
+
+/// Invoice lines as shown on documents, the portal and the upcoming invoice.
+#[derive(Debug, Clone, Default)]
+pub struct PresentedLines {
+    pub lines: Vec<LineItem>,
+    /// Original lines that were merged in the presentation, for the usage annex.
+    pub annex: Vec<LineItem>,
+}
+
+/// Applies the sub-line presentation of the price components to their lines.
+///
+/// Lines are merged, never dropped, so the presented lines sum up to the invoice. Only lines
+/// of the same component, period and tax rate are merged, so that each presented line keeps
+/// a single tax rate. Components without an entry in `presentations` stay detailed.
+pub fn present_line_items(
+    lines: &[LineItem],
+    presentations: &HashMap<PriceComponentId, SubLinePresentation>,
+) -> PresentedLines {
+    enum Slot<'a> {
+        Line(&'a LineItem),
+        Bucket(usize),
+    }
+
+    type BucketKey = (PriceComponentId, NaiveDate, NaiveDate, Decimal);
+
+    let mut slots = Vec::with_capacity(lines.len());
+    let mut buckets: Vec<(&SubLinePresentation, Vec<&LineItem>)> = Vec::new();
+    let mut bucket_index: HashMap<BucketKey, usize> = HashMap::new();
+
+    for line in lines {
+        let presentation = line
+            .price_component_id
+            .and_then(|id| presentations.get(&id).map(|p| (id, p)));
+
+        match presentation {
+            Some((id, presentation)) if *presentation != SubLinePresentation::Detailed => {
+                let key = (id, line.start_date, line.end_date, line.tax_rate);
+                match bucket_index.get(&key) {
+                    Some(idx) => buckets[*idx].1.push(line),
+                    None => {
+                        bucket_index.insert(key, buckets.len());
+                        slots.push(Slot::Bucket(buckets.len()));
+                        buckets.push((presentation, vec![line]));
+                    }
+                }
+            }
+            _ => slots.push(Slot::Line(line)),
+        }
+    }
+
+    let mut presented = PresentedLines::default();
+    for slot in slots {
+        match slot {
+            Slot::Line(line) => presented.lines.push(line.clone()),
+            Slot::Bucket(idx) => {
+                let (presentation, bucket) = &buckets[idx];
+                present_bucket(presentation, bucket, &mut presented);
+            }
+        }
+    }
+    presented
+}
+
+/// Presents the lines of an invoice. Finalized invoices keep the presentation snapshotted at
+/// finalization, so that they still match their stored documents; drafts use the current one.
+pub async fn present_invoice(store: &Store, invoice: &Invoice) -> StoreResult<PresentedLines> {
+    match &invoice.sub_line_presentations {
+        Some(presentations) => Ok(present_line_items(&invoice.line_items, presentations)),
+        None => present_invoice_lines(store, invoice.tenant_id, &invoice.line_items).await,
+    }
+}
+
+/// Presents the lines with the current sub-line presentation of their price components,
+/// for drafts and previews (e.g. the upcoming invoice).
+pub async fn present_invoice_lines(
+    store: &Store,
+    tenant_id: TenantId,
+    lines: &[LineItem],
+) -> StoreResult<PresentedLines> {
+    let component_ids = lines
+        .iter()
+        .filter_map(|l| l.price_component_id)
+        .unique()
+        .collect();
+
+    let presentations = store
+        .list_sub_line_presentations(tenant_id, component_ids)
+        .await?;
+
+    Ok(present_line_items(lines, &presentations))
+}
+
+fn present_bucket(
+    presentation: &SubLinePresentation,
+    bucket: &[&LineItem],
+    presented: &mut PresentedLines,
+) {
+    match presentation {
+        SubLinePresentation::Detailed => {
+            presented.lines.extend(bucket.iter().map(|l| (*l).clone()));
+        }
+        SubLinePresentation::Collapsed => {
+            if bucket.len() == 1 && bucket[0].sub_lines.is_empty() {
+                presented.lines.push(bucket[0].clone());
+                return;
+            }
+            presented
+                .lines
+                .push(merge_lines(base_name(bucket[0]), bucket, Vec::new()));
+            presented.annex.extend(bucket.iter().map(|l| (*l).clone()));
+        }
+        SubLinePresentation::TopGroups { limit } => {
+            let limit = *limit as usize;
+            // merging a single group into "Other" would not save anything
+            if bucket.len() <= limit + 1 {
+                presented.lines.extend(bucket.iter().map(|l| (*l).clone()));
+                return;
+            }
+
+            let mut by_amount: Vec<usize> = (0..bucket.len()).collect();
+            by_amount.sort_by_key(|idx| std::cmp::Reverse(bucket[*idx].amount_subtotal));
+            let (top, others) = by_amount.split_at_mut(limit);
+            top.sort_unstable();
+            others.sort_unstable();
+            let others: Vec<&LineItem> = others.iter().map(|idx| bucket[*idx]).collect();
+
+            presented
+                .lines
+                .extend(top.iter().map(|idx| bucket[*idx].clone()));
+            let name = format!("{} ({OTHER_GROUPS_NAME})", base_name(bucket[0]));
+            presented.lines.push(merge_lines(name, &others, Vec::new()));
+            presented.annex.extend(others.into_iter().cloned());
+        }
+        SubLinePresentation::TiersOnly => {
+            if bucket.len() == 1 {
+                presented.lines.push(bucket[0].clone());
+                return;
+            }
+            let sub_lines = sum_sub_lines_by_name(bucket);
+            presented
+                .lines
+                .push(merge_lines(base_name(bucket[0]), bucket, sub_lines));
+            presented.annex.extend(bucket.iter().map(|l| (*l).clone()));
+        }
+    }
+}
+
+/// Name of the line without the group values appended when the line was computed.
+fn base_name(line: &LineItem) -> String {
+    let Some(dimensions) = line.group_by_dimensions.as_ref().filter(|d| !d.is_empty()) else {
+        return line.name.clone();
+    };
+
+    let mut sorted: Vec<_> = dimensions.iter().collect();
+    sorted.sort_by_key(|(k, _)| *k);
+    let values: Vec<&str> = sorted.into_iter().map(|(_, v)| v.as_str()).collect();
+    let suffix = format!(" ({})", values.join(", "));
+
+    line.name
+        .strip_suffix(&suffix)
+        .unwrap_or(&line.name)
+        .to_string()
+}
+
+fn merge_lines(name: String, lines: &[&LineItem], sub_lines: Vec<SubLineItem>) -> LineItem {
+    let first = lines[0];
+
+    let mut tax_details: Vec<TaxDetail> = Vec::new();
+    for detail in lines.iter().flat_map(|l| &l.tax_details) {
+        match tax_details
+            .iter_mut()
+            .find(|d| d.tax_name == detail.tax_name && d.tax_rate == detail.tax_rate)
+        {
+            Some(existing) => existing.tax_amount += detail.tax_amount,
+            None => tax_details.push(detail.clone()),
+        }
+    }
+
+    LineItem {
+        local_id: first.local_id.clone(),
+        name,
+        amount_subtotal: lines.iter().map(|l| l.amount_subtotal).sum(),
+        tax_rate: first.tax_rate,
+        taxable_amount: lines.iter().map(|l| l.taxable_amount).sum(),
+        tax_amount: lines.iter().map(|l| l.tax_amount).sum(),
+        amount_total: lines.iter().map(|l| l.amount_total).sum(),
+        tax_details,
+        quantity: lines.iter().map(|l| l.quantity).sum(),
+        unit_price: first
+            .unit_price
+            .filter(|price| lines.iter().all(|l| l.unit_price == Some(*price))),
+        start_date: first.start_date,
+        end_date: first.end_date,
+        sub_lines,
+        is_prorated: lines.iter().any(|l| l.is_prorated),
+        price_component_id: first.price_component_id,
+        sub_component_id: first.sub_component_id,
+        sub_add_on_id: first.sub_add_on_id,
+        product_id: first.product_id,
+        metric_id: first.metric_id,
+        description: first.description.clone(),
+        group_by_dimensions: None,
+        metadata: first.metadata.clone(),
+    }
+}
+
+fn sum_sub_lines_by_name(lines: &[&LineItem]) -> Vec<SubLineItem> {
+    let mut sub_lines: Vec<SubLineItem> = Vec::new();
+    for sub_line in lines.iter().flat_map(|l| &l.sub_lines) {
+        match sub_lines.iter_mut().find(|s| s.name == sub_line.name) {
+            Some(existing) => {
+                existing.total += sub_line.total;
+                existing.quantity += sub_line.quantity;
+            }
+            None => sub_lines.push(sub_line.clone()),
+        }
+    }
+    sub_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_domain::ids::BaseId;
+    use rust_decimal_macros::dec;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 1, day).unwrap()
+    }
+
+    fn line(
+        component: Option<PriceComponentId>,
+        region: Option<&str>,
+        amount: i64,
+        sub_lines: Vec<SubLineItem>,
+    ) -> LineItem {
+        let (name, group_by_dimensions) = match region {
+            Some(region) => (
+                format!("API calls ({region})"),
+                Some(HashMap::from([("region".to_string(), region.to_string())])),
+            ),
+            None => ("API calls".to_string(), None),
+        };
+        LineItem {
+            local_id: name.clone(),
+            name,
+            amount_subtotal: amount,
+            tax_rate: dec!(0.2),
+            taxable_amount: amount,
+            tax_amount: amount / 5,
+            amount_total: amount + amount / 5,
+            tax_details: vec![TaxDetail {
+                tax_rate: dec!(0.2),
+                tax_name: "VAT".to_string(),
+                tax_amount: amount / 5,
+            }],
+            quantity: Some(Decimal::from(amount / 10)),
+            unit_price: Some(dec!(0.1)),
+            start_date: date(1),
+            end_date: date(31),
+            sub_lines,
+            is_prorated: false,
+            price_component_id: component,
+            sub_component_id: None,
+            sub_add_on_id: None,
+            product_id: None,
+            metric_id: None,
+            description: None,
+            group_by_dimensions,
+            metadata: Default::default(),
+        }
+    }
+
+    fn sub_line(name: &str, total: i64, quantity: Decimal) -> SubLineItem {
+        SubLineItem {
+            local_id: name.to_string(),
+            name: name.to_string(),
+            total,
+            quantity,
+            unit_price: dec!(0.1),
+            attributes: None,
+        }
+    }
+
+    fn grouped_lines(component: PriceComponentId) -> Vec<LineItem> {
+        vec![
+            line(None, None, 5_000, vec![]),
+            line(Some(component), Some("eu"), 1_000, vec![]),
+            line(Some(component), Some("us"), 4_000, vec![]),
+            line(Some(component), Some("apac"), 300, vec![]),
+            line(Some(component), Some("latam"), 200, vec![]),
+        ]
+    }
+
+    fn total(lines: &[LineItem]) -> i64 {
+        lines.iter().map(|l| l.amount_total).sum()
+    }
+
+    #[test]
+    fn test_detailed_keeps_lines() {
+        let component = PriceComponentId::new();
+        let lines = grouped_lines(component);
+
+        let presented = present_line_items(&lines, &HashMap::new());
+
+        assert_eq!(presented.lines, lines);
+        assert!(presented.annex.is_empty());
+    }
+
+    #[test]
+    fn test_collapsed_merges_groups_into_annex() {
+        let component = PriceComponentId::new();
+        let lines = grouped_lines(component);
+        let presentations = HashMap::from([(component, SubLinePresentation::Collapsed)]);
+
+        let presented = present_line_items(&lines, &presentations);
+
+        assert_eq!(presented.lines.len(), 2);
+        let collapsed = &presented.lines[1];
+        assert_eq!(collapsed.name, "API calls");
+        assert_eq!(collapsed.amount_subtotal, 5_500);
+        assert_eq!(collapsed.quantity, Some(dec!(550)));
+        assert_eq!(collapsed.unit_price, Some(dec!(0.1)));
+        assert_eq!(collapsed.tax_details.len(), 1);
+        assert_eq!(collapsed.tax_details[0].tax_amount, 1_100);
+        assert_eq!(collapsed.group_by_dimensions, None);
+        assert_eq!(total(&presented.lines), total(&lines));
+        assert_eq!(presented.annex, lines[1..].to_vec());
+    }
+
+    #[test]
+    fn test_top_groups_merges_smallest_into_other() {
+        let component = PriceComponentId::new();
+        let lines = grouped_lines(component);
+        let presentations =
+            HashMap::from([(component, SubLinePresentation::TopGroups { limit: 2 })]);
+
+        let presented = present_line_items(&lines, &presentations);
+
+        let names: Vec<&str> = presented.lines.iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "API calls",
+                "API calls (eu)",
+                "API calls (us)",
+                "API calls (Other)"
+            ]
+        );
+        assert_eq!(presented.lines[3].amount_subtotal, 500);
+        assert_eq!(total(&presented.lines), total(&lines));
+        assert_eq!(presented.annex.len(), 2);
+
+        // a single remaining group is shown as is
+        let presentations =
+            HashMap::from([(component, SubLinePresentation::TopGroups { limit: 3 })]);
+        let presented = present_line_items(&lines, &presentations);
+        assert_eq!(presented.lines, lines);
+    }
+
+    #[test]
+    fn test_tiers_only_sums_sub_lines() {
+        let component = PriceComponentId::new();
+        let lines = vec![
+            line(
+                Some(component),
+                Some("eu"),
+                1_500,
+                vec![
+                    sub_line("Tier 1", 1_000, dec!(100)),
+                    sub_line("Tier 2", 500, dec!(50)),
+                ],
+            ),
+            line(
+                Some(component),
+                Some("us"),
+                1_000,
+                vec![sub_line("Tier 1", 1_000, dec!(100))],
+            ),
+        ];
+        let presentations = HashMap::from([(component, SubLinePresentation::TiersOnly)]);
+
+        let presented = present_line_items(&lines, &presentations);
+
+        assert_eq!(presented.lines.len(), 1);
+        let merged = &presented.lines[0];
+        assert_eq!(merged.name, "API calls");
+        assert_eq!(merged.amount_subtotal, 2_500);
+        assert_eq!(
+            merged
+                .sub_lines
+                .iter()
+                .map(|s| (s.name.as_str(), s.total, s.quantity))
+                .collect::<Vec<_>>(),
+            vec![("Tier 1", 2_000, dec!(200)), ("Tier 2", 500, dec!(50))]
+        );
+    }
+
+    #[test]
+    fn test_lines_of_other_periods_or_rates_are_not_merged() {
+        let component = PriceComponentId::new();
+        let mut lines = grouped_lines(component);
+        lines[2].start_date = date(15);
+        lines[3].tax_rate = dec!(0.1);
+        let presentations = HashMap::from([(component, SubLinePresentation::Collapsed)]);
+
+        let presented = present_line_items(&lines, &presentations);
+
+        // eu + latam are merged, us and apac stay on their own
+        assert_eq!(presented.lines.len(), 4);
+        assert_eq!(presented.lines[1].amount_subtotal, 1_200);
+        assert_eq!(total(&presented.lines), total(&lines));
+    }
+}
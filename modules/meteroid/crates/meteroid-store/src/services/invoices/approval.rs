@@ -0,0 +1,464 @@
+use crate::StoreResult;
+use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::enums::InvoiceApprovalStatus;
+use crate::domain::pgmq::{PgmqMessageNew, PgmqQueue, SendEmailRequest};
+use crate::domain::{DetailedInvoice, Invoice, SubscriptionDetails};
+use crate::errors::StoreError;
+use crate::repositories::customer_balance::convert_currency;
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::repositories::pgmq::PgmqInterface;
+use crate::services::Services;
+use crate::store::PgConn;
+use common_domain::ids::{BaseId, InvoiceId, TenantId, UserId};
+use diesel_models::customers::CustomerRow;
+use diesel_models::enums::OrganizationUserRole;
+use diesel_models::invoices::InvoiceRow;
+use diesel_models::invoicing_entities::InvoicingEntityRow;
+use diesel_models::organizations::OrganizationRow;
+use diesel_models::tenants::TenantRow;
+use diesel_models::users::{UserRow, UserWithRoleRow};
+use error_stack::{Report, bail};
+use scoped_futures::ScopedFutureExt;
+
+impl Services {
+    /// Finalizes the draft, unless it needs an approval that was not granted yet.
+    ///
+    /// A draft needs approval when its customer is flagged for it, or when its total reaches the
+    /// invoicing entity's threshold. It is then held as a draft: the approval is requested (once)
+    /// and `approve_invoice` finalizes it later.
+    pub(in crate::services) async fn finalize_or_hold_tx(
+        &self,
+        conn: &mut PgConn,
+        actor: &Actor,
+        id: InvoiceId,
+        tenant_id: TenantId,
+        refresh_invoice_lines: bool,
+        subscription_details_for_refresh: &Option<SubscriptionDetails>,
+    ) -> StoreResult<DetailedInvoice> {
+        if self
+            .hold_for_approval_tx(conn, actor, id, tenant_id, refresh_invoice_lines)
+            .await?
+        {
+            return InvoiceRow::find_detailed_by_id(conn, tenant_id, id)
+                .await
+                .map_err(Into::into)
+                .and_then(std::convert::TryInto::try_into);
+        }
+
+        self.finalize_invoice_tx(
+            conn,
+            actor,
+            id,
+            tenant_id,
+            refresh_invoice_lines,
+            subscription_details_for_refresh,
+        )
+        .await
+    }
+
+    /// Returns true when the draft must wait for an approval, requesting it if not pending yet.
+    async fn hold_for_approval_tx(
+        &self,
+        conn: &mut PgConn,
+        actor: &Actor,
+        id: InvoiceId,
+        tenant_id: TenantId,
+        refresh_invoice_lines: bool,
+    ) -> StoreResult<bool> {
+        let invoice_lock = InvoiceRow::select_for_update_by_id(conn, tenant_id, id).await?;
+        let invoice: Invoice = invoice_lock.invoice.try_into()?;
+
+        // finalize_invoice_tx handles non-drafts and consolidated children
+        if !invoice.can_edit() || invoice.is_consolidated_child() {
+            return Ok(false);
+        }
+        match invoice.approval_status {
+            Some(InvoiceApprovalStatus::Approved) => return Ok(false),
+            Some(InvoiceApprovalStatus::Pending) => return Ok(true),
+            Some(InvoiceApprovalStatus::Rejected) | None => {}
+        }
+
+        let invoicing_entity = InvoicingEntityRow::get_invoicing_entity_by_id_and_tenant(
+            conn,
+            invoice.invoicing_entity_id,
+            tenant_id,
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+        let customer = CustomerRow::find_by_id(conn, &invoice.customer_id, &tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        if !customer.invoice_approval_required
+            && invoicing_entity.invoice_approval_threshold.is_none()
+        {
+            return Ok(false);
+        }
+
+        // The approver must see the amounts that will be finalized, so capture the usage
+        // accrued during the grace period before comparing against the threshold.
+        let invoice: Invoice = if refresh_invoice_lines {
+            self.refresh_invoice_data(conn, id, tenant_id, &None, true)
+                .await?;
+            InvoiceRow::find_by_id(conn, tenant_id, id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?
+        } else {
+            invoice
+        };
+
+        let total_in_accounting_currency = match invoicing_entity.invoice_approval_threshold {
+            Some(_) => {
+                convert_currency(
+                    conn,
+                    invoice.total,
+                    &invoice.currency,
+                    &invoicing_entity.accounting_currency,
+                )
+                .await?
+            }
+            None => invoice.total,
+        };
+
+        if !approval_required(
+            customer.invoice_approval_required,
+            invoicing_entity.invoice_approval_threshold,
+            total_in_accounting_currency,
+        ) {
+            return Ok(false);
+        }
+
+        InvoiceRow::request_approval(conn, id, tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        let mut activity = Activity::new(
+            ActivityType::InvoiceApprovalRequested,
+            EntityType::Invoice,
+            id.as_uuid(),
+        )
+        .agg_customer(invoice.customer_id)
+        .with_metadata(serde_json::json!({
+            "total": invoice.total,
+            "currency": invoice.currency,
+            "threshold": invoicing_entity.invoice_approval_threshold,
+            "customer_approval_required": customer.invoice_approval_required,
+        }));
+        if let Some(subscription_id) = invoice.subscription_id {
+            activity = activity.agg_subscription(subscription_id);
+        }
+        self.store
+            .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+            .await?;
+
+        let approvers = list_approvers_tx(conn, tenant_id, &invoicing_entity).await?;
+
+        let tenant = TenantRow::find_by_id(conn, tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+        let organization = OrganizationRow::get_by_id(conn, tenant.organization_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        let email_msg: PgmqMessageNew = SendEmailRequest::InvoiceApprovalRequested {
+            tenant_id,
+            invoice_id: id,
+            tenant_name: tenant.name,
+            invoice_number: invoice.invoice_number.clone(),
+            customer_name: invoice.customer_details.name.clone(),
+            total: invoice.total,
+            currency: invoice.currency.clone(),
+            invoice_url: format!(
+                "{}/{}/{}/invoices/{}",
+                self.store.settings.public_url.trim_end_matches('/'),
+                organization.slug,
+                tenant.slug,
+                id.as_base62()
+            ),
+            recipient_emails: approvers.into_iter().map(|user| user.email).collect(),
+        }
+        .try_into()?;
+        self.store
+            .pgmq_send_batch_tx(conn, PgmqQueue::SendEmailRequest, vec![email_msg])
+            .await?;
+
+        Ok(true)
+    }
+
+    /// Approves a draft held for approval and finalizes it as approved, without refreshing it.
+    pub async fn approve_invoice(
+        &self,
+        actor: Actor,
+        id: InvoiceId,
+        tenant_id: TenantId,
+        comment: Option<String>,
+    ) -> StoreResult<DetailedInvoice> {
+        self.store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    self.decide_approval_tx(
+                        conn,
+                        actor,
+                        id,
+                        tenant_id,
+                        ApprovalDecision::Approve,
+                        comment,
+                    )
+                    .await?;
+
+                    // The approved amounts are the ones finalized: no refresh.
+                    self.finalize_invoice_tx(conn, actor, id, tenant_id, false, &None)
+                        .await
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    /// Rejects a draft held for approval. It stays a draft; finalizing it again resubmits it.
+    pub async fn reject_invoice(
+        &self,
+        actor: Actor,
+        id: InvoiceId,
+        tenant_id: TenantId,
+        comment: String,
+    ) -> StoreResult<DetailedInvoice> {
+        if comment.trim().is_empty() {
+            bail!(StoreError::InvalidArgument(
+                "A comment is required to reject an invoice".to_string()
+            ));
+        }
+
+        self.store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    self.decide_approval_tx(
+                        conn,
+                        actor,
+                        id,
+                        tenant_id,
+                        ApprovalDecision::Reject,
+                        Some(comment),
+                    )
+                    .await?;
+
+                    InvoiceRow::find_detailed_by_id(conn, tenant_id, id)
+                        .await
+                        .map_err(Into::into)
+                        .and_then(std::convert::TryInto::try_into)
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    async fn decide_approval_tx(
+        &self,
+        conn: &mut PgConn,
+        actor: &Actor,
+        id: InvoiceId,
+        tenant_id: TenantId,
+        decision: ApprovalDecision,
+        comment: Option<String>,
+    ) -> StoreResult<()> {
+        let Actor::User { id: user_id } = actor else {
+            bail!(StoreError::Forbidden(
+                "Invoices can only be approved or rejected by a user".to_string()
+            ));
+        };
+
+        let invoice_lock = InvoiceRow::select_for_update_by_id(conn, tenant_id, id).await?;
+        let invoice: Invoice = invoice_lock.invoice.try_into()?;
+
+        if !invoice.can_edit() || invoice.approval_status != Some(InvoiceApprovalStatus::Pending) {
+            bail!(StoreError::InvalidArgument(
+                "Invoice is not awaiting approval".to_string()
+            ));
+        }
+
+        let invoicing_entity = InvoicingEntityRow::get_invoicing_entity_by_id_and_tenant(
+            conn,
+            invoice.invoicing_entity_id,
+            tenant_id,
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+        let approvers = list_approvers_tx(conn, tenant_id, &invoicing_entity).await?;
+        if !approvers.iter().any(|approver| approver.id == *user_id) {
+            bail!(StoreError::Forbidden(
+                "You are not an approver of this invoicing entity".to_string()
+            ));
+        }
+
+        let comment = comment.filter(|c| !c.trim().is_empty());
+        let decided = InvoiceRow::decide_approval(
+            conn,
+            id,
+            tenant_id,
+            decision.status().into(),
+            *user_id,
+            comment.clone(),
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+        if !decided {
+            bail!(StoreError::InvalidArgument(
+                "Invoice is not awaiting approval".to_string()
+            ));
+        }
+
+        let mut activity =
+            Activity::new(decision.activity_type(), EntityType::Invoice, id.as_uuid())
+                .agg_customer(invoice.customer_id)
+                .with_metadata(serde_json::json!({
+                    "comment": comment,
+                    "total": invoice.total,
+                    "currency": invoice.currency,
+                }));
+        if let Some(subscription_id) = invoice.subscription_id {
+            activity = activity.agg_subscription(subscription_id);
+        }
+        self.store
+            .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ApprovalDecision {
+    Approve,
+    Reject,
+}
+
+impl ApprovalDecision {
+    fn status(self) -> InvoiceApprovalStatus {
+        match self {
+            ApprovalDecision::Approve => InvoiceApprovalStatus::Approved,
+            ApprovalDecision::Reject => InvoiceApprovalStatus::Rejected,
+        }
+    }
+
+    fn activity_type(self) -> ActivityType {
+        match self {
+            ApprovalDecision::Approve => ActivityType::InvoiceApproved,
+            ApprovalDecision::Reject => ActivityType::InvoiceRejected,
+        }
+    }
+}
+
+/// The members allowed to approve the invoices of the invoicing entity.
+async fn list_approvers_tx(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    invoicing_entity: &InvoicingEntityRow,
+) -> StoreResult<Vec<UserWithRoleRow>> {
+    let approver_ids: Vec<UserId> = invoicing_entity
+        .invoice_approver_ids
+        .iter()
+        .flatten()
+        .copied()
+        .collect();
+    let members = UserRow::list_by_tenant_id(conn, tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+    let member_ids: Vec<UserId> = members.iter().map(|user| user.id).collect();
+
+    Ok(members
+        .into_iter()
+        .filter(|user| can_approve(&approver_ids, &member_ids, user.id, &user.role))
+        .collect())
+}
+
+/// A draft needs approval when its customer requires it, or when its total (in the invoicing
+/// entity's accounting currency) reaches the entity threshold.
+fn approval_required(
+    customer_approval_required: bool,
+    threshold: Option<i64>,
+    total_in_accounting_currency: i64,
+) -> bool {
+    customer_approval_required || threshold.is_some_and(|t| total_in_accounting_currency >= t)
+}
+
+/// The designated approvers of the invoicing entity, or any organization admin when none of
+/// them is still a member.
+fn can_approve(
+    approver_ids: &[UserId],
+    member_ids: &[UserId],
+    user_id: UserId,
+    role: &OrganizationUserRole,
+) -> bool {
+    if approver_ids.iter().any(|id| member_ids.contains(id)) {
+        approver_ids.contains(&user_id)
+    } else {
+        *role == OrganizationUserRole::Admin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn approval_required_by_customer_or_threshold() {
+        assert!(!approval_required(false, None, 1_000_000));
+        assert!(approval_required(true, None, 0));
+        assert!(approval_required(false, Some(100_000), 100_000));
+        assert!(!approval_required(false, Some(100_000), 99_999));
+    }
+
+    #[test]
+    fn admins_approve_unless_approvers_are_designated() {
+        let approver = UserId::new();
+        let other = UserId::new();
+        let members = [approver, other];
+
+        assert!(can_approve(
+            &[],
+            &members,
+            other,
+            &OrganizationUserRole::Admin
+        ));
+        assert!(!can_approve(
+            &[],
+            &members,
+            other,
+            &OrganizationUserRole::Member
+        ));
+        assert!(can_approve(
+            &[approver],
+            &members,
+            approver,
+            &OrganizationUserRole::Member
+        ));
+        assert!(!can_approve(
+            &[approver],
+            &members,
+            other,
+            &OrganizationUserRole::Admin
+        ));
+    }
+
+    #[test]
+    fn admins_approve_when_no_designated_approver_is_a_member() {
+        let former_member = UserId::new();
+        let admin = UserId::new();
+
+        assert!(can_approve(
+            &[former_member],
+            &[admin],
+            admin,
+            &OrganizationUserRole::Admin
+        ));
+        assert!(!can_approve(
+            &[former_member],
+            &[admin],
+            former_member,
+            &OrganizationUserRole::Member
+        ));
+    }
+}
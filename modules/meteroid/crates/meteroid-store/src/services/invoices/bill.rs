@@ -14,7 +14,6 @@ use common_domain::ids::{
 };
 use diesel_models::customer_connection::CustomerConnectionDetailsRow;
 use diesel_models::customer_payment_methods::CustomerPaymentMethodRow;
-use diesel_models::customers::CustomerRow;
 use diesel_models::enums::{ConnectorProviderEnum, PaymentMethodTypeEnum};
 use diesel_models::invoices::InvoiceRow;
 use diesel_models::invoicing_entities::InvoicingEntityRow;
@@ -129,11 +128,7 @@ impl Services {
             .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
             .await?;
 
-        let customer =
-            CustomerRow::find_by_id(conn, &subscription.subscription.customer_id, &tenant_id)
-                .await
-                .map_err(Into::into)
-                .and_then(TryInto::try_into)?;
+        let customer = subscription.billed_customer().clone();
 
         self.bill_subscription_with_data_tx(conn, tenant_id, subscription, customer, mode)
             .await
@@ -386,8 +381,8 @@ impl Services {
                     return self.as_detailed_invoice(draft_invoice, customer).map(Some);
                 }
 
-                // else we finalize immediately and trigger payment
-                self.finalize_invoice_tx(
+                // else we finalize immediately and trigger payment, unless it awaits approval
+                self.finalize_or_hold_tx(
                     conn,
                     &Actor::System,
                     draft_invoice.id,
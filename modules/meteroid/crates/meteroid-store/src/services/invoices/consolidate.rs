@@ -33,6 +33,9 @@ impl Services {
     /// The per-subscription drafts are retained as consolidated children (linked via
     /// `consolidated_into_invoice_id`) so their MRR movements and per-subscription idempotency
     /// stay intact; only the consolidated parent is finalized/charged/rendered.
+    ///
+    /// The finalized invoice is held as a draft instead when it needs an approval, which then
+    /// finalizes it.
     pub(in crate::services) async fn consolidate_and_finalize(
         &self,
         conn: &mut PgConn,
@@ -59,7 +62,7 @@ impl Services {
             || trigger.subscription_id.is_none()
         {
             return self
-                .finalize_invoice_tx(
+                .finalize_or_hold_tx(
                     conn,
                     &Actor::System,
                     trigger_invoice_id,
@@ -81,7 +84,7 @@ impl Services {
         .map_err(Into::<Report<StoreError>>::into)?;
         if !invoicing_entity.consolidate_recurring_invoices {
             return self
-                .finalize_invoice_tx(
+                .finalize_or_hold_tx(
                     conn,
                     &Actor::System,
                     trigger_invoice_id,
@@ -167,7 +170,7 @@ impl Services {
         // A single member: nothing to merge, finalize on its own (existing behavior).
         if members.len() <= 1 {
             return self
-                .finalize_invoice_tx(
+                .finalize_or_hold_tx(
                     conn,
                     &Actor::System,
                     trigger_invoice_id,
@@ -245,6 +248,14 @@ impl Services {
         let due_at = (trigger.invoice_date + chrono::Duration::days(i64::from(trigger.net_terms)))
             .and_time(NaiveTime::MIN);
 
+        // Only the custom fields all members agree on apply to the whole parent.
+        let mut custom_fields = trigger.custom_fields.clone();
+        custom_fields.retain(|key, value| {
+            refreshed
+                .iter()
+                .all(|m| m.custom_fields.get(key) == Some(value))
+        });
+
         let invoice_new = InvoiceNew {
             status: InvoiceStatusEnum::Draft,
             tenant_id,
@@ -281,6 +292,7 @@ impl Services {
             invoicing_entity_id: trigger.invoicing_entity_id,
             parent_invoice_id: None,
             consolidated_into_invoice_id: None,
+            custom_fields,
         };
 
         let parent = insert_invoice_tx(&self.store, conn, invoice_new).await?;
@@ -319,7 +331,7 @@ impl Services {
 
         // refresh=false: content is already combined and fresh, so no per-subscription recompute
         // (which would need a subscription_id) happens.
-        self.finalize_invoice_tx(conn, &Actor::System, parent.id, tenant_id, false, &None)
+        self.finalize_or_hold_tx(conn, &Actor::System, parent.id, tenant_id, false, &None)
             .await
             .map(|_| ())
     }
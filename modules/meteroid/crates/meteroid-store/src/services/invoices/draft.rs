@@ -16,6 +16,7 @@ use crate::repositories::invoicing_entities::{
 use crate::services::Services;
 use crate::services::invoice_lines::invoice_lines::ComputedInvoiceContent;
 use crate::store::PgConn;
+use crate::utils::custom_fields::resolve_invoice_custom_fields;
 use chrono::{NaiveDate, NaiveTime, Utc};
 use common_domain::ids::{
     BaseId, InvoiceId, PriceId, ProductId, SubscriptionAddOnId, SubscriptionId, TenantId,
@@ -101,6 +102,7 @@ fn proration_lines_to_invoice_lines(
                 metric_id: None,
                 description: None,
                 group_by_dimensions: None,
+                metadata: Default::default(),
             }
         })
         .collect()
@@ -157,9 +159,14 @@ impl Services {
         // Draft invoice uses "draft" as invoice number
         let invoice_number = "draft";
 
+        let custom_fields = resolve_invoice_custom_fields(
+            &customer.invoice_custom_fields,
+            Some(&subscription.invoice_custom_fields),
+        );
+
         let invoice_new = InvoiceNew {
             tenant_id: subscription.tenant_id,
-            customer_id: subscription.customer_id,
+            customer_id: customer.id,
             subscription_id: Some(subscription.id),
             plan_version_id: Some(subscription.plan_version_id),
             invoice_type: InvoiceType::Recurring,
@@ -193,6 +200,7 @@ impl Services {
             invoicing_entity_id: subscription.invoicing_entity_id,
             parent_invoice_id: None,
             consolidated_into_invoice_id: None,
+            custom_fields,
         };
 
         let inserted_invoice = insert_invoice_tx(&self.store, conn, invoice_new).await?;
@@ -275,6 +283,7 @@ impl Services {
                 email: customer.billing_email.clone(),
                 alias: customer.alias.clone(),
                 snapshot_at: chrono::Utc::now().naive_utc(),
+                invoice_language: customer.invoice_language.clone(),
             },
             seller_details: InlineInvoicingEntity {
                 id: invoicing_entity.id,
@@ -292,6 +301,7 @@ impl Services {
             invoicing_entity_id: invoicing_entity.id,
             parent_invoice_id,
             consolidated_into_invoice_id: None,
+            custom_fields: customer.invoice_custom_fields.clone(),
         };
 
         let inserted_invoice = insert_invoice_tx(&self.store, conn, invoice_new).await?;
@@ -426,6 +436,7 @@ impl Services {
             invoicing_entity_id: parent.invoicing_entity_id,
             parent_invoice_id: Some(parent.id),
             consolidated_into_invoice_id: None,
+            custom_fields: parent.custom_fields.clone(),
         };
 
         insert_invoice_tx(&self.store, conn, invoice_new).await
@@ -632,7 +643,7 @@ impl Services {
 
         let invoice_new = InvoiceNew {
             tenant_id: subscription.tenant_id,
-            customer_id: subscription.customer_id,
+            customer_id: customer.id,
             subscription_id: Some(subscription.id),
             plan_version_id: Some(subscription.plan_version_id),
             invoice_type: InvoiceType::Adjustment,
@@ -670,6 +681,10 @@ impl Services {
             invoicing_entity_id: subscription.invoicing_entity_id,
             parent_invoice_id: None,
             consolidated_into_invoice_id: None,
+            custom_fields: resolve_invoice_custom_fields(
+                &customer.invoice_custom_fields,
+                Some(&subscription.invoice_custom_fields),
+            ),
         };
 
         let inserted_invoice = insert_invoice_tx(&self.store, conn, invoice_new).await?;
@@ -711,14 +726,7 @@ impl Services {
             .await?;
         let original_subscription = addon_details.subscription.clone();
 
-        let customer: Customer = diesel_models::customers::CustomerRow::find_by_id(
-            conn,
-            &original_subscription.customer_id,
-            &tenant_id,
-        )
-        .await
-        .map_err(Into::<Report<StoreError>>::into)?
-        .try_into()?;
+        let customer = addon_details.billed_customer().clone();
 
         addon_details.subscription.billing_start_date = Some(now);
         addon_details.subscription.cycle_index = Some(0);
@@ -4,18 +4,21 @@ use crate::domain::outbox_event::OutboxEvent;
 use crate::domain::{CouponLineItem, DetailedInvoice, Invoice, SubscriptionDetails};
 use crate::errors::StoreError;
 use crate::repositories::customer_balance::{CustomerBalance, convert_currency};
+use crate::repositories::price_components::list_sub_line_presentations_tx;
 use crate::services::Services;
-use crate::services::utils::format_invoice_number;
+use crate::services::utils::{NumberContext, NumberSeries, next_document_number};
 use chrono::NaiveTime;
-use common_domain::ids::{AppliedCouponId, InvoiceId, TenantId};
+use common_domain::ids::{AppliedCouponId, InvoiceId, PriceComponentId, TenantId};
 use common_eventbus::Event;
 use common_utils::decimals::ToUnit;
 use diesel_models::applied_coupons::{AppliedCouponDetailedRow, AppliedCouponRow};
 use diesel_models::customers::CustomerRow;
 use diesel_models::invoices::{InvoiceRow, InvoiceRowLinesPatch};
 use diesel_models::invoicing_entities::InvoicingEntityRow;
+use diesel_models::subscriptions::SubscriptionRow;
 use diesel_models::{DbResult, PgConn};
 use error_stack::Report;
+use itertools::Itertools;
 use scoped_futures::ScopedFutureExt;
 
 impl Services {
@@ -28,7 +31,7 @@ impl Services {
         self.store
             .transaction(|conn| {
                 let actor = &actor;
-                self.finalize_invoice_tx(conn, actor, id, tenant_id, false, &None)
+                self.finalize_or_hold_tx(conn, actor, id, tenant_id, false, &None)
                     .scope_boxed()
             })
             .await
@@ -168,11 +171,26 @@ impl Services {
         .await
         .map_err(Into::<Report<StoreError>>::into)?;
 
-        let new_invoice_number = format_invoice_number(
-            invoicing_entity.next_invoice_number,
-            invoicing_entity.invoice_number_pattern,
-            invoice.invoice_date,
-        );
+        let prefix = match invoice.subscription_id {
+            Some(subscription_id) => {
+                SubscriptionRow::get_invoice_number_prefix(tx, invoice.tenant_id, subscription_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?
+            }
+            None => None,
+        };
+
+        let new_invoice_number = next_document_number(
+            tx,
+            &invoicing_entity,
+            NumberSeries::Invoice,
+            &NumberContext {
+                date: invoice.invoice_date,
+                customer_alias: invoice.customer_details.alias.as_deref(),
+                prefix: prefix.as_deref(),
+            },
+        )
+        .await?;
 
         let payment_reference = new_invoice_number
             .chars()
@@ -184,6 +202,20 @@ impl Services {
         let applied_coupons_json = serde_json::to_value(&applied_coupons_amounts)
             .map_err(|e| StoreError::SerdeError("Failed to serialize coupons".to_string(), e))?;
 
+        // Documents keep the presentation of the components at finalization
+        let component_ids: Vec<PriceComponentId> = invoice
+            .line_items
+            .iter()
+            .filter_map(|l| l.price_component_id)
+            .unique()
+            .collect();
+        let sub_line_presentations =
+            list_sub_line_presentations_tx(tx, tenant_id, &component_ids).await?;
+        let sub_line_presentations_json =
+            serde_json::to_value(&sub_line_presentations).map_err(|e| {
+                StoreError::SerdeError("Failed to serialize sub_line_presentations".to_string(), e)
+            })?;
+
         // Use invoice_date for finalized_at when backdating (for seeded subscriptions),
         // otherwise use current time
         let finalized_at = if backdate_invoices {
@@ -201,20 +233,12 @@ impl Services {
             new_invoice_number,
             payment_reference,
             applied_coupons_json,
+            sub_line_presentations_json,
             finalized_at,
         )
         .await
         .map_err(Into::<Report<StoreError>>::into)?;
 
-        InvoicingEntityRow::update_invoicing_entity_number(
-            tx,
-            invoice.invoicing_entity_id,
-            invoice.tenant_id,
-            invoicing_entity.next_invoice_number,
-        )
-        .await
-        .map_err(Into::<Report<StoreError>>::into)?;
-
         let final_invoice: DetailedInvoice =
             InvoiceRow::find_detailed_by_id(tx, invoice.tenant_id, invoice.id)
                 .await
@@ -249,7 +273,7 @@ async fn refresh_applied_coupons(
 ) -> DbResult<Vec<AppliedCouponId>> {
     let applied_coupons_ids: Vec<AppliedCouponId> = applied_coupons_amounts
         .iter()
-        .map(|c| c.applied_coupon_id)
+        .filter_map(|c| c.applied_coupon_id)
         .collect();
 
     let applied_coupons_detailed =
@@ -265,7 +289,7 @@ async fn refresh_applied_coupons(
 
             applied_coupons_amounts
                 .iter()
-                .find(|x| x.applied_coupon_id == applied_coupon_detailed.applied_coupon.id)
+                .find(|x| x.applied_coupon_id == Some(applied_coupon_detailed.applied_coupon.id))
                 .map(|x| x.value.to_unit(cur.exponent as u8))
         } else {
             None
@@ -0,0 +1,188 @@
+use crate::StoreResult;
+use crate::domain::pgmq::{BiAggregationEvent, BiInvoiceFinalizedEvent, PgmqMessageNew, PgmqQueue};
+use crate::domain::{
+    Invoice, InvoiceNew, InvoicePaymentStatus, InvoiceStatusEnum, InvoiceType,
+    SubscriptionStatusEnum,
+};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::repositories::invoices::insert_invoice_tx;
+use crate::repositories::pgmq::PgmqInterface;
+use crate::services::Services;
+use crate::store::PgConn;
+use crate::utils::custom_fields::resolve_invoice_custom_fields;
+use crate::utils::periods::calculate_advance_period_range;
+use chrono::{Days, NaiveTime};
+use common_domain::ids::{BaseId, SubscriptionId, TenantId};
+use diesel_models::enums::InvoicePaymentStatus as DbInvoicePaymentStatus;
+use diesel_models::invoices::InvoiceRow;
+use error_stack::{Report, ResultExt};
+
+impl Services {
+    /// Records every period of a migrated subscription, up to its current one, as an imported
+    /// invoice already paid in the previous billing system.
+    ///
+    /// The subscription must already be positioned at its current period (migration mode).
+    /// Imported invoices are finalized and paid at their period start. They are never sent nor
+    /// collected, but they count towards the revenue and MRR history.
+    pub(in crate::services) async fn import_past_periods_tx(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+    ) -> StoreResult<Vec<Invoice>> {
+        let mut details = self
+            .store
+            .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
+            .await?;
+
+        // A subscription still in trial has no paid period yet
+        if !matches!(
+            details.subscription.status,
+            SubscriptionStatusEnum::Active | SubscriptionStatusEnum::Completed
+        ) {
+            return Ok(vec![]);
+        }
+
+        let (Some(billing_start_date), Some(last_cycle)) = (
+            details.subscription.billing_start_date,
+            details.subscription.cycle_index,
+        ) else {
+            return Ok(vec![]);
+        };
+
+        // Billing starts after a free trial
+        let effective_billing_start =
+            match (&details.trial_config, details.subscription.trial_duration) {
+                (Some(trial), Some(days)) if trial.is_free => billing_start_date
+                    .checked_add_days(Days::new(u64::from(days)))
+                    .ok_or(Report::new(StoreError::InvalidArgument(
+                        "Invalid trial duration".to_string(),
+                    )))?,
+                _ => billing_start_date,
+            };
+
+        let billing_period = details.subscription.period;
+        let billing_day_anchor = u32::from(details.subscription.billing_day_anchor);
+        let customer = details.billed_customer().clone();
+
+        let mut period = calculate_advance_period_range(
+            effective_billing_start,
+            billing_day_anchor,
+            true,
+            &billing_period,
+        );
+        let mut imported = vec![];
+
+        for cycle_index in 0..=last_cycle {
+            let invoice_date = period.start;
+
+            details.subscription.cycle_index = Some(cycle_index);
+            details.subscription.current_period_start = period.start;
+            details.subscription.current_period_end = Some(period.end);
+
+            let content = self
+                .compute_invoice(conn, &invoice_date, &details, None, None)
+                .await
+                .change_context(StoreError::InvoiceComputationError)?;
+
+            if !content.invoice_lines.is_empty() {
+                let subscription = &details.subscription;
+                let finalized_at = invoice_date.and_time(NaiveTime::MIN);
+
+                let invoice_new = InvoiceNew {
+                    tenant_id,
+                    customer_id: customer.id,
+                    subscription_id: Some(subscription.id),
+                    plan_version_id: Some(subscription.plan_version_id),
+                    invoice_type: InvoiceType::Imported,
+                    currency: subscription.currency.clone(),
+                    line_items: content.invoice_lines,
+                    coupons: content.applied_coupons,
+                    data_updated_at: None,
+                    status: InvoiceStatusEnum::Finalized,
+                    invoice_date,
+                    finalized_at: Some(finalized_at),
+                    total: content.total,
+                    // Settled in the previous billing system
+                    amount_due: 0,
+                    applied_credits: 0,
+                    net_terms: subscription.net_terms as i32,
+                    subtotal: content.subtotal,
+                    subtotal_recurring: content.subtotal_recurring,
+                    reference: None,
+                    purchase_order: subscription.purchase_order.clone(),
+                    memo: subscription.invoice_memo.clone(),
+                    due_at: Some(finalized_at),
+                    plan_name: Some(subscription.plan_name.clone()),
+                    invoice_number: format!(
+                        "IMP-{}-{}",
+                        subscription.id.as_base62(),
+                        cycle_index + 1
+                    ),
+                    customer_details: customer.clone().into(),
+                    seller_details: details.invoicing_entity.clone().into(),
+                    auto_advance: false,
+                    payment_status: InvoicePaymentStatus::Paid,
+                    discount: content.discount,
+                    tax_breakdown: content.tax_breakdown,
+                    tax_amount: content.tax_amount,
+                    manual: false,
+                    invoicing_entity_id: subscription.invoicing_entity_id,
+                    parent_invoice_id: None,
+                    consolidated_into_invoice_id: None,
+                    custom_fields: resolve_invoice_custom_fields(
+                        &customer.invoice_custom_fields,
+                        Some(&subscription.invoice_custom_fields),
+                    ),
+                };
+
+                let invoice = insert_invoice_tx(&self.store, conn, invoice_new).await?;
+
+                InvoiceRow::apply_payment_status(
+                    conn,
+                    invoice.id,
+                    tenant_id,
+                    DbInvoicePaymentStatus::Paid,
+                    Some(finalized_at),
+                )
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+
+                // Revenue is recorded directly: the finalization outbox event would also
+                // generate, send and sync the invoice.
+                let revenue: PgmqMessageNew =
+                    BiAggregationEvent::InvoiceFinalized(Box::new(BiInvoiceFinalizedEvent {
+                        tenant_id,
+                        customer_id: invoice.customer_id,
+                        plan_version_id: invoice.plan_version_id,
+                        currency: invoice.currency.clone(),
+                        amount_due: invoice.total,
+                        finalized_at,
+                    }))
+                    .try_into()?;
+                self.store
+                    .pgmq_send_batch_tx(conn, PgmqQueue::BiAggregation, vec![revenue])
+                    .await?;
+
+                imported.push(invoice);
+            }
+
+            // Same alignment as the renewals of the cycle worker
+            period = calculate_advance_period_range(
+                period.end,
+                billing_day_anchor,
+                true,
+                &billing_period,
+            );
+        }
+
+        log::info!(
+            "Imported {} past invoices for subscription {}",
+            imported.len(),
+            subscription_id
+        );
+
+        Ok(imported)
+    }
+}
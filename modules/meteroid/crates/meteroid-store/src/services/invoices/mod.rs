@@ -1,9 +1,13 @@
+mod approval;
 mod bill;
 mod consolidate;
 mod draft;
 mod finalize;
+mod import;
 mod issue;
+mod oneoff;
 mod refresh;
+mod threshold;
 mod upcoming;
 mod update;
 
@@ -0,0 +1,477 @@
+use crate::StoreResult;
+use crate::domain::prices::Pricing;
+use crate::domain::{
+    Coupon, Customer, DetailedInvoice, Invoice, InvoiceNew, InvoicePaymentStatus,
+    InvoiceStatusEnum, InvoiceType, InvoicingEntity, LineItem, OneOffInvoiceNew, OneOffItem, Price,
+    Product,
+};
+use crate::errors::StoreError;
+use crate::repositories::InvoiceInterface;
+use crate::repositories::coupons::CouponInterface;
+use crate::repositories::invoices::insert_invoice_tx;
+use crate::services::Services;
+use crate::services::invoice_lines::discount::calculate_oneoff_coupons_discount;
+use crate::store::PgConn;
+use crate::utils::custom_fields::{resolve_invoice_custom_fields, validate_custom_fields};
+use crate::utils::local_id::{IdType, LocalId};
+use chrono::{NaiveTime, Utc};
+use common_domain::ids::{PriceId, ProductId, TenantId};
+use common_utils::decimals::ToSubunit;
+use diesel_models::coupons::CouponRow;
+use diesel_models::customers::CustomerRow;
+use diesel_models::invoices::InvoiceRow;
+use diesel_models::invoicing_entities::InvoicingEntityRow;
+use diesel_models::prices::PriceRow;
+use diesel_models::products::ProductRow;
+use error_stack::{Report, ResultExt, bail};
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use scoped_futures::ScopedFutureExt;
+use std::collections::HashMap;
+
+/// Unit price of a catalog price billed once. Usage prices depend on metered quantities and
+/// cannot be billed this way.
+fn oneoff_unit_price(pricing: &Pricing) -> Option<Decimal> {
+    match pricing {
+        Pricing::Rate { rate } | Pricing::Capacity { rate, .. } => Some(*rate),
+        Pricing::Slot { unit_rate, .. } => Some(*unit_rate),
+        Pricing::ExtraRecurring { unit_price, .. } | Pricing::OneTime { unit_price, .. } => {
+            Some(*unit_price)
+        }
+        Pricing::Usage(_) => None,
+    }
+}
+
+/// Coupons applicable to a one-off invoice: catalog coupons valid for its currency and not
+/// restricted to specific plans.
+fn validate_oneoff_coupons(
+    codes: &[String],
+    coupons: Vec<Coupon>,
+    currency: &str,
+) -> StoreResult<Vec<Coupon>> {
+    if let Some(missing) = codes
+        .iter()
+        .find(|code| !coupons.iter().any(|c| &c.code == *code))
+    {
+        bail!(StoreError::ValueNotFound(format!(
+            "Coupon code '{missing}' not found"
+        )));
+    }
+
+    for coupon in &coupons {
+        coupon
+            .validate_for_use_with_message(currency)
+            .map_err(|msg| Report::new(StoreError::InvalidArgument(msg)))?;
+
+        if !coupon.plan_ids.is_empty() {
+            bail!(StoreError::InvalidArgument(format!(
+                "Coupon '{}' is restricted to specific plans and cannot be applied to a one-off invoice",
+                coupon.code
+            )));
+        }
+    }
+
+    Ok(coupons)
+}
+
+impl Services {
+    pub(in crate::services) async fn create_oneoff_invoice(
+        &self,
+        tenant_id: TenantId,
+        params: OneOffInvoiceNew,
+    ) -> StoreResult<DetailedInvoice> {
+        let invoice = self
+            .store
+            .transaction(|conn| {
+                async move { self.create_oneoff_invoice_tx(conn, tenant_id, params).await }
+                    .scope_boxed()
+            })
+            .await?;
+
+        self.store
+            .get_detailed_invoice_by_id(tenant_id, invoice.id)
+            .await
+    }
+
+    async fn create_oneoff_invoice_tx(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        params: OneOffInvoiceNew,
+    ) -> StoreResult<Invoice> {
+        if params.line_items.is_empty() {
+            bail!(StoreError::InvalidArgument(
+                "A one-off invoice requires at least one line item".to_string()
+            ));
+        }
+
+        let customer: Customer = CustomerRow::find_by_id(conn, &params.customer_id, &tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)
+            .and_then(TryInto::try_into)?;
+
+        let currency = params.currency.unwrap_or_else(|| customer.currency.clone());
+        let precision = rusty_money::iso::find(&currency)
+            .ok_or_else(|| {
+                Report::new(StoreError::InvalidArgument(format!(
+                    "Invalid currency: {currency}"
+                )))
+            })?
+            .exponent as u8;
+
+        let invoice_date = params
+            .invoice_date
+            .unwrap_or_else(|| Utc::now().date_naive());
+
+        let price_ids = params
+            .line_items
+            .iter()
+            .filter_map(|line| match &line.item {
+                OneOffItem::Price(price_id) => Some(*price_id),
+                OneOffItem::Custom { .. } => None,
+            })
+            .unique()
+            .collect::<Vec<_>>();
+
+        let prices_by_id: HashMap<PriceId, Price> =
+            PriceRow::list_by_ids(conn, &price_ids, tenant_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .into_iter()
+                .map(|row| Price::try_from(row).map(|price| (price.id, price)))
+                .collect::<StoreResult<_>>()?;
+
+        let product_ids = params
+            .line_items
+            .iter()
+            .filter_map(|line| match &line.item {
+                OneOffItem::Price(price_id) => prices_by_id.get(price_id).map(|p| p.product_id),
+                OneOffItem::Custom { product_id, .. } => *product_id,
+            })
+            .unique()
+            .collect::<Vec<_>>();
+
+        let products_by_id: HashMap<ProductId, Product> =
+            ProductRow::list_by_ids(conn, &product_ids, tenant_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .into_iter()
+                .map(|row| Product::try_from(row).map(|product| (product.id, product)))
+                .collect::<StoreResult<_>>()?;
+
+        let mut invoice_lines = Vec::with_capacity(params.line_items.len());
+        for line in params.line_items {
+            if line.quantity <= Decimal::ZERO {
+                bail!(StoreError::InvalidArgument(
+                    "Line item quantity must be positive".to_string()
+                ));
+            }
+            validate_custom_fields(&line.metadata)?;
+
+            let (name, unit_price, product_id) = match line.item {
+                OneOffItem::Price(price_id) => {
+                    let price = prices_by_id.get(&price_id).ok_or_else(|| {
+                        Report::new(StoreError::ValueNotFound(format!(
+                            "Price {price_id} not found"
+                        )))
+                    })?;
+                    if price.currency != currency {
+                        bail!(StoreError::InvalidArgument(format!(
+                            "Price {price_id} is in {}, the invoice is in {currency}",
+                            price.currency
+                        )));
+                    }
+                    let unit_price =
+                        oneoff_unit_price(price.pricing_for(&customer)).ok_or_else(|| {
+                            Report::new(StoreError::InvalidArgument(format!(
+                                "Price {price_id} is a usage price and cannot be billed on a one-off invoice"
+                            )))
+                        })?;
+                    let product = products_by_id.get(&price.product_id).ok_or_else(|| {
+                        Report::new(StoreError::ValueNotFound(format!(
+                            "Product {} not found",
+                            price.product_id
+                        )))
+                    })?;
+                    (product.name.clone(), unit_price, Some(product.id))
+                }
+                OneOffItem::Custom {
+                    name,
+                    unit_price,
+                    product_id,
+                } => {
+                    if unit_price < Decimal::ZERO {
+                        bail!(StoreError::InvalidArgument(
+                            "Line item unit price cannot be negative".to_string()
+                        ));
+                    }
+                    if let Some(product_id) = product_id
+                        && !products_by_id.contains_key(&product_id)
+                    {
+                        bail!(StoreError::ValueNotFound(format!(
+                            "Product {product_id} not found"
+                        )));
+                    }
+                    (name, unit_price, product_id)
+                }
+            };
+
+            let amount_subtotal = (line.quantity * unit_price)
+                .to_subunit_opt(precision)
+                .ok_or_else(|| {
+                    Report::new(StoreError::InvalidArgument(format!(
+                        "The amount of line '{name}' is too large"
+                    )))
+                })?;
+
+            invoice_lines.push(LineItem {
+                local_id: LocalId::generate_for(IdType::Other),
+                name,
+                amount_subtotal,
+                taxable_amount: amount_subtotal,
+                // handled by compute_oneoff_invoice
+                tax_rate: Decimal::ZERO,
+                tax_details: vec![],
+                tax_amount: 0,
+                amount_total: amount_subtotal,
+                quantity: Some(line.quantity),
+                unit_price: Some(unit_price),
+                start_date: invoice_date,
+                end_date: invoice_date,
+                sub_lines: vec![],
+                is_prorated: false,
+                price_component_id: None,
+                sub_component_id: None,
+                sub_add_on_id: None,
+                product_id,
+                metric_id: None,
+                description: line.description,
+                group_by_dimensions: None,
+                metadata: line.metadata,
+            });
+        }
+
+        let coupon_codes = params.coupon_codes.into_iter().unique().collect::<Vec<_>>();
+        let coupons = if coupon_codes.is_empty() {
+            vec![]
+        } else {
+            let found = self
+                .store
+                .list_coupons_by_codes_tx(conn, tenant_id, &coupon_codes)
+                .await?;
+            let coupons = validate_oneoff_coupons(&coupon_codes, found, &currency)?;
+            self.redeem_oneoff_coupons(conn, tenant_id, &customer, &currency, &coupons)
+                .await?;
+            coupons
+        };
+
+        let subtotal = invoice_lines.iter().map(|l| l.amount_subtotal).sum();
+        let coupons_discount = calculate_oneoff_coupons_discount(subtotal, &currency, &coupons);
+
+        let invoicing_entity: InvoicingEntity =
+            InvoicingEntityRow::get_invoicing_entity_by_id_and_tenant(
+                conn,
+                customer.invoicing_entity_id,
+                tenant_id,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?
+            .into();
+
+        let (due_at, net_terms) = match params.due_date {
+            Some(due_date) if due_date < invoice_date => {
+                bail!(StoreError::InvalidArgument(
+                    "The due date cannot be before the invoice date".to_string()
+                ));
+            }
+            Some(due_date) => (due_date, (due_date - invoice_date).num_days() as i32),
+            None => (
+                invoice_date + chrono::Duration::days(i64::from(invoicing_entity.net_terms)),
+                invoicing_entity.net_terms,
+            ),
+        };
+
+        let custom_fields = resolve_invoice_custom_fields(
+            &customer.invoice_custom_fields,
+            params.custom_fields.as_ref(),
+        );
+        validate_custom_fields(&custom_fields)?;
+
+        let invoice_content = self
+            .compute_oneoff_invoice(
+                conn,
+                &invoice_date,
+                invoice_lines,
+                &invoicing_entity,
+                &customer,
+                currency.clone(),
+                Some(coupons_discount.discount_subunit.max(0) as u64),
+                None,
+            )
+            .await
+            .change_context(StoreError::InvoiceComputationError)?;
+
+        let invoice_new = InvoiceNew {
+            tenant_id,
+            customer_id: customer.id,
+            subscription_id: None,
+            plan_version_id: None,
+            invoice_type: InvoiceType::OneOff,
+            currency,
+            line_items: invoice_content.invoice_lines,
+            coupons: coupons_discount.applied_coupons,
+            data_updated_at: None,
+            status: InvoiceStatusEnum::Draft,
+            invoice_date,
+            finalized_at: None,
+            total: invoice_content.total,
+            amount_due: invoice_content.amount_due,
+            applied_credits: invoice_content.applied_credits,
+            net_terms,
+            subtotal: invoice_content.subtotal,
+            subtotal_recurring: 0,
+            reference: params.reference,
+            purchase_order: params.purchase_order,
+            memo: params.memo,
+            due_at: Some(due_at.and_time(NaiveTime::MIN)),
+            plan_name: None,
+            invoice_number: "draft".to_string(),
+            customer_details: customer.into(),
+            seller_details: invoicing_entity.clone().into(),
+            // finalized explicitly by the caller
+            auto_advance: false,
+            payment_status: InvoicePaymentStatus::Unpaid,
+            discount: invoice_content.discount,
+            tax_breakdown: invoice_content.tax_breakdown,
+            tax_amount: invoice_content.tax_amount,
+            manual: true,
+            invoicing_entity_id: invoicing_entity.id,
+            parent_invoice_id: None,
+            consolidated_into_invoice_id: None,
+            custom_fields,
+        };
+
+        insert_invoice_tx(&self.store, conn, invoice_new).await
+    }
+
+    /// Counts the redemption of the coupons, as when applied to a subscription. The limits are
+    /// checked again with the coupons locked, so that concurrent invoices cannot exceed them.
+    async fn redeem_oneoff_coupons(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        customer: &Customer,
+        currency: &str,
+        coupons: &[Coupon],
+    ) -> StoreResult<()> {
+        let coupon_ids = coupons.iter().map(|c| c.id).collect::<Vec<_>>();
+
+        let locked = self
+            .lock_and_validate_coupons_for_checkout(
+                conn,
+                tenant_id,
+                customer.id,
+                &coupon_ids,
+                currency,
+            )
+            .await?;
+
+        // one-off invoices have no applied coupon, their redemptions are on the invoices
+        for coupon in locked.iter().filter(|c| !c.reusable) {
+            let used = InvoiceRow::customer_used_coupon(conn, tenant_id, customer.id, coupon.id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+            if used {
+                bail!(StoreError::InvalidArgument(format!(
+                    "Coupon {} is not reusable and has already been used by this customer",
+                    coupon.code
+                )));
+            }
+        }
+
+        CouponRow::update_last_redemption_at(conn, &coupon_ids, Utc::now().naive_utc())
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+        for coupon_id in coupon_ids {
+            CouponRow::inc_redemption_count(conn, coupon_id, 1)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::coupons::CouponDiscount;
+    use crate::domain::price_components::UsagePricingModel;
+    use common_domain::ids::{BaseId, CouponId, PlanId};
+    use rust_decimal_macros::dec;
+
+    fn coupon(code: &str, plan_ids: Vec<PlanId>) -> Coupon {
+        let now = Utc::now().naive_utc();
+        Coupon {
+            id: CouponId::new(),
+            code: code.to_string(),
+            description: String::new(),
+            tenant_id: TenantId::new(),
+            discount: CouponDiscount::Percentage(dec!(10)),
+            expires_at: None,
+            redemption_limit: None,
+            recurring_value: None,
+            reusable: true,
+            disabled: false,
+            created_at: now,
+            updated_at: now,
+            last_redemption_at: None,
+            archived_at: None,
+            redemption_count: 0,
+            plan_ids,
+        }
+    }
+
+    #[test]
+    fn unit_price_of_fixed_pricings() {
+        assert_eq!(
+            oneoff_unit_price(&Pricing::Rate { rate: dec!(49.5) }),
+            Some(dec!(49.5))
+        );
+        assert_eq!(
+            oneoff_unit_price(&Pricing::OneTime {
+                unit_price: dec!(12),
+                quantity: 3,
+            }),
+            Some(dec!(12))
+        );
+        assert_eq!(
+            oneoff_unit_price(&Pricing::Usage(UsagePricingModel::PerUnit {
+                rate: dec!(1)
+            })),
+            None
+        );
+    }
+
+    #[test]
+    fn coupons_must_exist_and_not_be_plan_restricted() {
+        let codes = vec!["A".to_string(), "B".to_string()];
+
+        let missing = validate_oneoff_coupons(&codes, vec![coupon("A", vec![])], "EUR");
+        assert!(missing.is_err());
+
+        let restricted = validate_oneoff_coupons(
+            &codes,
+            vec![coupon("A", vec![]), coupon("B", vec![PlanId::new()])],
+            "EUR",
+        );
+        assert!(restricted.is_err());
+
+        let valid = validate_oneoff_coupons(
+            &codes,
+            vec![coupon("A", vec![]), coupon("B", vec![])],
+            "EUR",
+        );
+        assert_eq!(valid.unwrap().len(), 2);
+    }
+}
@@ -74,11 +74,14 @@ impl Services {
 
             let subscription_details = match subscription_details_cache {
                 Some(details) => {
-                    if customer_balance == details.customer.balance_value_cents {
+                    if customer_balance == details.billed_customer().balance_value_cents {
                         details
                     } else {
                         res = details.clone();
-                        res.customer.balance_value_cents = customer_balance;
+                        res.payer
+                            .as_mut()
+                            .unwrap_or(&mut res.customer)
+                            .balance_value_cents = customer_balance;
                         &res
                     }
                 }
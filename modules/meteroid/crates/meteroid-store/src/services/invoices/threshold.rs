@@ -0,0 +1,204 @@
+use crate::StoreResult;
+use crate::constants::Currencies;
+use crate::domain::entity_activity::Actor;
+use crate::domain::{
+    DetailedInvoice, InvoiceNew, InvoicePaymentStatus, InvoiceStatusEnum, InvoiceType,
+    SubscriptionDetails,
+};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::repositories::invoices::insert_invoice_tx;
+use crate::services::Services;
+use crate::store::PgConn;
+use crate::utils::custom_fields::resolve_invoice_custom_fields;
+use chrono::{NaiveTime, Utc};
+use common_domain::ids::{SubscriptionId, TenantId};
+use common_utils::decimals::ToSubunit;
+use diesel_models::subscriptions::SubscriptionRow;
+use error_stack::{Report, ResultExt};
+use scoped_futures::ScopedFutureExt;
+
+const THRESHOLD_BATCH_SIZE: i64 = 100;
+
+impl Services {
+    /// Issues a `UsageThreshold` invoice for every active subscription whose usage not yet
+    /// invoiced in the current period reached its invoice threshold.
+    /// Returns the number of invoices issued.
+    pub(in crate::services) async fn process_usage_thresholds(&self) -> StoreResult<usize> {
+        let mut issued = 0;
+        let mut after = None;
+
+        loop {
+            let mut conn = self.store.get_conn().await?;
+            let batch = SubscriptionRow::list_with_invoice_threshold(
+                &mut conn,
+                after,
+                THRESHOLD_BATCH_SIZE,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+            drop(conn);
+
+            let Some((last_id, _)) = batch.last() else {
+                break;
+            };
+            after = Some(*last_id);
+
+            for (subscription_id, tenant_id) in batch {
+                // One failing subscription (e.g. usage unavailable) must not block the others
+                match self
+                    .invoice_usage_threshold(tenant_id, subscription_id)
+                    .await
+                {
+                    Ok(Some(_)) => issued += 1,
+                    Ok(None) => {}
+                    Err(err) => log::warn!(
+                        "Failed to check the usage threshold of subscription {subscription_id}: {err:?}"
+                    ),
+                }
+            }
+        }
+
+        Ok(issued)
+    }
+
+    /// Invoices the usage accrued in the current period if it reached the subscription's
+    /// invoice threshold. The period invoice later deducts what was billed here.
+    pub(in crate::services) async fn invoice_usage_threshold(
+        &self,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+    ) -> StoreResult<Option<DetailedInvoice>> {
+        self.store
+            .transaction(|conn| {
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?;
+
+                    let details = self
+                        .store
+                        .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
+                        .await?;
+
+                    self.invoice_usage_threshold_tx(conn, details).await
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    async fn invoice_usage_threshold_tx(
+        &self,
+        conn: &mut PgConn,
+        details: SubscriptionDetails,
+    ) -> StoreResult<Option<DetailedInvoice>> {
+        let subscription = &details.subscription;
+
+        let Some(threshold) = subscription.invoice_threshold else {
+            return Ok(None);
+        };
+
+        let currency = Currencies::resolve_currency(&subscription.currency).ok_or(Report::new(
+            StoreError::ValueNotFound(format!("Currency {} not found", subscription.currency)),
+        ))?;
+        let threshold = threshold
+            .to_subunit_opt(currency.precision)
+            .ok_or(Report::new(StoreError::InvalidDecimal))
+            .attach("Invalid invoice threshold")?;
+
+        // Already net of the previous threshold invoices of the period
+        let upcoming = self.compute_upcoming_invoice(conn, &details).await?;
+
+        let usage_lines: Vec<_> = upcoming
+            .invoice_lines
+            .into_iter()
+            .filter(|line| line.metric_id.is_some())
+            .filter(|line| line.start_date >= subscription.current_period_start)
+            .filter(|line| line.amount_subtotal > 0)
+            .collect();
+
+        let accrued: i64 = usage_lines.iter().map(|line| line.amount_subtotal).sum();
+        if usage_lines.is_empty() || accrued < threshold {
+            return Ok(None);
+        }
+
+        let invoice_date = Utc::now().naive_utc().date();
+
+        let content = self
+            .compute_oneoff_invoice(
+                conn,
+                &invoice_date,
+                usage_lines,
+                &details.invoicing_entity,
+                details.billed_customer(),
+                subscription.currency.clone(),
+                None,
+                None,
+            )
+            .await
+            .change_context(StoreError::InvoiceComputationError)?;
+
+        let due_date = (invoice_date + chrono::Duration::days(i64::from(subscription.net_terms)))
+            .and_time(NaiveTime::MIN);
+
+        let invoice_new = InvoiceNew {
+            tenant_id: subscription.tenant_id,
+            customer_id: details.billed_customer().id,
+            subscription_id: Some(subscription.id),
+            plan_version_id: Some(subscription.plan_version_id),
+            invoice_type: InvoiceType::UsageThreshold,
+            currency: subscription.currency.clone(),
+            line_items: content.invoice_lines,
+            coupons: vec![],
+            data_updated_at: None,
+            status: InvoiceStatusEnum::Draft,
+            invoice_date,
+            finalized_at: None,
+            total: content.total,
+            amount_due: content.amount_due,
+            applied_credits: content.applied_credits,
+            net_terms: subscription.net_terms as i32,
+            subtotal: content.subtotal,
+            subtotal_recurring: 0,
+            reference: None,
+            purchase_order: subscription.purchase_order.clone(),
+            memo: subscription.invoice_memo.clone(),
+            due_at: Some(due_date),
+            plan_name: Some(subscription.plan_name.clone()),
+            invoice_number: "draft".to_string(),
+            customer_details: details.billed_customer().clone().into(),
+            seller_details: details.invoicing_entity.clone().into(),
+            auto_advance: subscription.auto_advance_invoices,
+            payment_status: InvoicePaymentStatus::Unpaid,
+            discount: 0,
+            tax_breakdown: content.tax_breakdown,
+            tax_amount: content.tax_amount,
+            manual: false,
+            invoicing_entity_id: subscription.invoicing_entity_id,
+            parent_invoice_id: None,
+            consolidated_into_invoice_id: None,
+            custom_fields: resolve_invoice_custom_fields(
+                &details.billed_customer().invoice_custom_fields,
+                Some(&subscription.invoice_custom_fields),
+            ),
+        };
+
+        let invoice = insert_invoice_tx(&self.store, conn, invoice_new).await?;
+
+        // Finalized right away: the usage is now billed, a refresh would recompute the
+        // invoice as a full period invoice.
+        let invoice = self
+            .finalize_invoice_tx(
+                conn,
+                &Actor::System,
+                invoice.id,
+                subscription.tenant_id,
+                false,
+                &Some(details),
+            )
+            .await?;
+
+        Ok(Some(invoice))
+    }
+}
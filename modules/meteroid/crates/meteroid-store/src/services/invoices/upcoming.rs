@@ -73,6 +73,7 @@ impl Services {
                 &subscription_details.subscription.customer_id,
                 &metric,
                 period.into(),
+                Some(&subscription_details.subscription.usage_filter()),
             )
             .await
     }
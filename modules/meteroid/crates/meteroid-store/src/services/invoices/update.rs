@@ -6,6 +6,7 @@ use crate::repositories::customers::CustomersInterfaceAuto;
 use crate::repositories::invoices::compute_tax_breakdown;
 use crate::repositories::invoicing_entities::InvoicingEntityInterfaceAuto;
 use crate::services::Services;
+use crate::utils::custom_fields::validate_custom_fields;
 use crate::utils::local_id::{IdType, LocalId};
 use chrono::NaiveTime;
 use common_domain::ids::{InvoiceId, TenantId};
@@ -57,6 +58,7 @@ impl Services {
             ));
         }
         invoice.ensure_not_consolidated_child("edit")?;
+        invoice.ensure_not_awaiting_approval("edit")?;
 
         let updated_invoice = self
             .prepare_invoice_update(invoice, tenant_id, params)
@@ -81,6 +83,7 @@ impl Services {
             ));
         }
         invoice.ensure_not_consolidated_child("edit")?;
+        invoice.ensure_not_awaiting_approval("edit")?;
 
         // Editing amounts under a live charge would desync the invoice from the money the
         // provider is already moving: on settlement the invoice lands PartiallyPaid or
@@ -109,6 +112,9 @@ impl Services {
             memo: invoice.memo.clone(),
             reference: invoice.reference.clone(),
             purchase_order: invoice.purchase_order.clone(),
+            custom_fields: serde_json::to_value(&invoice.custom_fields).map_err(|e| {
+                StoreError::SerdeError("Failed to serialize custom_fields".to_string(), e)
+            })?,
             due_at: invoice.due_at,
             invoicing_entity_id: invoice.invoicing_entity_id,
             subtotal: invoice.subtotal,
@@ -141,6 +147,15 @@ impl Services {
         let currency = rusty_money::iso::find(&invoice.currency)
             .ok_or_else(|| StoreError::InvalidArgument("Invalid currency".into()))?;
 
+        if let Some(ref custom_fields) = params.custom_fields {
+            validate_custom_fields(custom_fields)?;
+        }
+        if let Some(line_items) = &params.line_items {
+            for metadata in line_items.iter().filter_map(|l| l.metadata.as_ref()) {
+                validate_custom_fields(metadata)?;
+            }
+        }
+
         if let Some(customer_update) = params.customer_details {
             invoice.customer_details = match customer_update {
                 CustomerDetailsUpdate::RefreshFromCustomer => {
@@ -242,6 +257,11 @@ impl Services {
                     metric_id: existing_line.and_then(|l| l.metric_id),
                     description: line_params.description.clone(),
                     group_by_dimensions: existing_line.and_then(|l| l.group_by_dimensions.clone()),
+                    metadata: line_params.metadata.clone().unwrap_or_else(|| {
+                        existing_line
+                            .map(|l| l.metadata.clone())
+                            .unwrap_or_default()
+                    }),
                 };
 
                 lines.push(item);
@@ -306,6 +326,9 @@ impl Services {
         if let Some(purchase_order) = params.purchase_order {
             invoice.purchase_order = purchase_order;
         }
+        if let Some(custom_fields) = params.custom_fields {
+            invoice.custom_fields = custom_fields;
+        }
         if let Some(due_date) = params.due_date {
             invoice.due_at = due_date.map(|d| d.and_time(NaiveTime::MIN));
         }
@@ -1,17 +1,23 @@
 use crate::StoreResult;
 use crate::domain::ScheduledEventTypeEnum;
+use crate::domain::Subscription;
 use crate::domain::customer_payment_methods::ResolvedPaymentMethod;
+use crate::domain::entity_activity::{Actor, AuditInput};
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionAmendedEvent};
 use crate::domain::pgmq::{PaymentRequestEvent, PgmqMessageNew, PgmqQueue};
 use crate::domain::scheduled_events::{ScheduledEvent, ScheduledEventData};
 use crate::domain::slot_transactions::SlotTransactionNewInternal;
 use crate::errors::StoreError;
 use crate::repositories::SubscriptionInterface;
 use crate::repositories::customer_payment_methods::CustomerPaymentMethodsInterface;
+use crate::repositories::entity_activity::EntityActivityInterface;
 use crate::repositories::pgmq::PgmqInterface;
+use crate::repositories::subscriptions::PauseParams;
 use crate::services::Services;
 use crate::store::PgConn;
 use crate::utils::errors::format_error_chain;
 use chrono::{Duration, NaiveDateTime, Utc};
+use common_domain::ids::{SubscriptionId, TenantId};
 use diesel_models::enums::{InvoiceStatusEnum, SubscriptionEventType, SubscriptionStatusEnum};
 use diesel_models::invoices::InvoiceRow;
 use diesel_models::payments::PaymentTransactionRow;
@@ -170,10 +176,16 @@ impl Services {
             ScheduledEventTypeEnum::PauseSubscription => {
                 self.process_pause_subscription(conn, &event).await
             }
+            ScheduledEventTypeEnum::ResumeSubscription => {
+                self.process_resume_subscription(conn, &event).await
+            }
             ScheduledEventTypeEnum::EndTrial => self.process_end_trial(conn, &event).await,
             ScheduledEventTypeEnum::ApplyAmendment => {
                 self.process_apply_amendment(conn, &event).await
             }
+            ScheduledEventTypeEnum::ApplyPriceChange => {
+                self.process_apply_price_change(conn, &event).await
+            }
         }
     }
     /// Determine if event should be retried
@@ -617,6 +629,17 @@ impl Services {
                     .count(),
                 mrr_delta,
             );
+
+            self.record_plan_change_tx(
+                conn,
+                event.tenant_id,
+                &Actor::System,
+                event.subscription_id,
+                sub.subscription.plan_version_id,
+                sub.subscription.status.into(),
+                event.scheduled_time.date(),
+            )
+            .await?;
         } else {
             log::error!(
                 "Unexpected event data for type ApplyPlanChange: {:?}, event_id={}",
@@ -638,8 +661,6 @@ impl Services {
         use crate::domain::subscription_components::{
             SubscriptionComponentNew, SubscriptionComponentNewInternal,
         };
-        use crate::services::subscriptions::plan_change::calculate_components_mrr_with_slots;
-        use crate::services::subscriptions::utils::calculate_mrr;
         use diesel_models::subscription_add_ons::{SubscriptionAddOnRow, SubscriptionAddOnRowNew};
         use diesel_models::subscription_components::{
             SubscriptionComponentRow, SubscriptionComponentRowNew,
@@ -795,58 +816,227 @@ impl Services {
                 .await
                 .map_err(Into::<error_stack::Report<StoreError>>::into)?;
 
-            // Recalculate MRR.
-            let sub_details = self
-                .store
-                .get_subscription_details_with_conn(conn, event.tenant_id, event.subscription_id)
+            let mrr_delta = self
+                .recalculate_subscription_mrr(conn, event.tenant_id, event.subscription_id)
                 .await?;
 
-            let precision = crate::constants::Currencies::resolve_currency_precision(
-                &sub_details.subscription.currency,
-            )
-            .unwrap_or(2);
+            log::info!(
+                "Applied amendment for subscription {}: closed_components={}, added_components={}, closed_addons={}, added_addons={}, mrr_delta={}",
+                event.subscription_id,
+                component_close.len(),
+                component_insert.len(),
+                addon_close.len(),
+                addon_insert.len(),
+                mrr_delta,
+            );
 
-            let component_mrr: i64 = calculate_components_mrr_with_slots(
+            let subscription: Subscription = SubscriptionRow::get_subscription_by_id(
                 conn,
-                event.tenant_id,
+                &event.tenant_id,
                 event.subscription_id,
-                &sub_details.price_components,
-                precision,
             )
-            .await?;
+            .await
+            .map_err(Into::<error_stack::Report<StoreError>>::into)?
+            .try_into()?;
+            self.store
+                .record_tx(
+                    conn,
+                    event.tenant_id,
+                    &Actor::System,
+                    AuditInput::Outbox(OutboxEvent::subscription_amended(
+                        SubscriptionAmendedEvent {
+                            subscription: subscription.into(),
+                            effective_date: apply_date,
+                            components_closed: component_close.len(),
+                            components_added: component_insert.len(),
+                            addons_closed: addon_close.len(),
+                            addons_added: addon_insert.len(),
+                            adjustment_invoice_id: None,
+                        },
+                    )),
+                )
+                .await?;
+        } else {
+            log::error!(
+                "Unexpected event data for type ApplyAmendment: {:?}, event_id={}",
+                event.event_data,
+                event.id
+            );
+        }
+        Ok(())
+    }
 
-            let add_on_mrr: i64 = sub_details
-                .add_ons
-                .iter()
-                .map(|a| calculate_mrr(&a.fee, &a.period, precision) * a.quantity as i64)
-                .sum();
+    async fn process_apply_price_change(
+        &self,
+        conn: &mut PgConn,
+        event: &ScheduledEvent,
+    ) -> StoreResult<()> {
+        use crate::domain::subscription_components::{
+            SubscriptionComponentNew, SubscriptionComponentNewInternal,
+        };
+        use crate::services::prices::price_changes::ensure_new_price;
+        use diesel_models::enums::PriceChangeStatusEnum;
+        use diesel_models::price_changes::PriceChangeRow;
+        use diesel_models::products::ProductRow;
+        use diesel_models::subscription_components::{
+            SubscriptionComponentRow, SubscriptionComponentRowNew,
+        };
+        use diesel_models::subscriptions::SubscriptionRow;
 
-            let new_mrr = component_mrr + add_on_mrr;
-            let old_mrr = sub_details.subscription.mrr_cents as i64;
-            let mrr_delta = new_mrr - old_mrr;
+        if let ScheduledEventData::ApplyPriceChange { price_change_id } = &event.event_data {
+            let change = PriceChangeRow::find_by_id(conn, *price_change_id, event.tenant_id)
+                .await
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
 
-            if mrr_delta != 0 {
-                SubscriptionRow::update_subscription_mrr_delta(
+            if change.status == PriceChangeStatusEnum::Cancelled {
+                log::info!(
+                    "Skipping cancelled price change {} for subscription {}",
+                    change.id,
+                    event.subscription_id
+                );
+                return Ok(());
+            }
+
+            SubscriptionRow::lock_subscription_for_update(conn, event.subscription_id)
+                .await
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+
+            let sub = SubscriptionRow::get_subscription_by_id(
+                conn,
+                &event.tenant_id,
+                event.subscription_id,
+            )
+            .await
+            .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+
+            match sub.subscription.status {
+                SubscriptionStatusEnum::Active | SubscriptionStatusEnum::TrialActive => {}
+                other => {
+                    log::warn!(
+                        "Skipping price change {} for subscription {} — status is {:?}",
+                        change.id,
+                        event.subscription_id,
+                        other,
+                    );
+                    return Ok(());
+                }
+            }
+
+            let components: Vec<SubscriptionComponentRow> =
+                SubscriptionComponentRow::list_subscription_components_by_subscription(
                     conn,
-                    event.subscription_id,
-                    mrr_delta,
+                    &event.tenant_id,
+                    &event.subscription_id,
                 )
                 .await
-                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?
+                .into_iter()
+                .filter(|c| c.price_id == Some(change.price_id))
+                .collect();
+
+            if components.is_empty() {
+                log::info!(
+                    "Subscription {} no longer uses price {}, nothing to apply for price change {}",
+                    event.subscription_id,
+                    change.price_id,
+                    change.id
+                );
+                return Ok(());
             }
 
+            ensure_new_price(conn, &change).await?;
+
+            let product_id = components
+                .iter()
+                .find_map(|c| c.product_id)
+                .ok_or_else(|| {
+                    StoreError::InvalidArgument(
+                        "Priced subscription component has no product".to_string(),
+                    )
+                })?;
+            let product: crate::domain::Product =
+                ProductRow::find_by_id_and_tenant_id(conn, product_id, event.tenant_id)
+                    .await
+                    .map_err(Into::<error_stack::Report<StoreError>>::into)?
+                    .try_into()?;
+            let new_pricing: crate::domain::prices::Pricing =
+                serde_json::from_value(change.new_pricing.clone()).map_err(|e| {
+                    StoreError::SerdeError("Failed to deserialize Pricing".to_string(), e)
+                })?;
+            let fee = crate::domain::prices::resolve_subscription_fee(
+                &product.fee_structure,
+                &new_pricing,
+                None,
+            )?;
+
+            let apply_date = event.scheduled_time.date();
+            let closed: Vec<_> = components.iter().map(|c| c.id).collect();
+            SubscriptionComponentRow::close_components(conn, &closed, apply_date)
+                .await
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+
+            let component_rows: Vec<SubscriptionComponentRowNew> = components
+                .iter()
+                .map(|c| {
+                    let mut row: SubscriptionComponentRowNew = SubscriptionComponentNew {
+                        subscription_id: event.subscription_id,
+                        internal: SubscriptionComponentNewInternal {
+                            price_component_id: c.price_component_id,
+                            product_id: c.product_id,
+                            name: c.name.clone(),
+                            period: c.period.clone().into(),
+                            fee: fee.clone(),
+                            is_override: false,
+                            price_id: Some(change.new_price_id),
+                            effective_from: apply_date,
+                        },
+                    }
+                    .try_into()?;
+                    // Keep the lineage so the switch reads as the same component on invoices.
+                    row.lineage_id = Some(c.lineage_id.unwrap_or(c.id));
+                    Ok::<_, error_stack::Report<StoreError>>(row)
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let refs: Vec<&SubscriptionComponentRowNew> = component_rows.iter().collect();
+            SubscriptionComponentRow::insert_subscription_component_batch(conn, refs)
+                .await
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+
+            let sub_event = diesel_models::subscription_events::SubscriptionEventRow {
+                id: uuid::Uuid::now_v7(),
+                subscription_id: event.subscription_id,
+                event_type: SubscriptionEventType::Switch,
+                details: Some(serde_json::json!({
+                    "kind": "price_change",
+                    "price_change_id": change.id,
+                    "old_price_id": change.price_id,
+                    "new_price_id": change.new_price_id,
+                })),
+                created_at: chrono::Utc::now().naive_utc(),
+                mrr_delta: None,
+                bi_mrr_movement_log_id: None,
+                applies_to: apply_date,
+            };
+            sub_event
+                .insert(conn)
+                .await
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+
+            let mrr_delta = self
+                .recalculate_subscription_mrr(conn, event.tenant_id, event.subscription_id)
+                .await?;
+
             log::info!(
-                "Applied amendment for subscription {}: closed_components={}, added_components={}, closed_addons={}, added_addons={}, mrr_delta={}",
+                "Applied price change {} for subscription {}: components={}, mrr_delta={}",
+                change.id,
                 event.subscription_id,
-                component_close.len(),
-                component_insert.len(),
-                addon_close.len(),
-                addon_insert.len(),
+                component_rows.len(),
                 mrr_delta,
             );
         } else {
             log::error!(
-                "Unexpected event data for type ApplyAmendment: {:?}, event_id={}",
+                "Unexpected event data for type ApplyPriceChange: {:?}, event_id={}",
                 event.event_data,
                 event.id
             );
@@ -854,18 +1044,94 @@ impl Services {
         Ok(())
     }
 
+    /// Recomputes the subscription MRR from its current components and add-ons and
+    /// stores the difference. Returns the applied delta.
+    async fn recalculate_subscription_mrr(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+    ) -> StoreResult<i64> {
+        use diesel_models::subscriptions::SubscriptionRow;
+
+        let (new_mrr, old_mrr) = self
+            .calculate_subscription_mrr(conn, tenant_id, subscription_id)
+            .await?;
+        let mrr_delta = new_mrr - old_mrr;
+
+        if mrr_delta != 0 {
+            SubscriptionRow::update_subscription_mrr_delta(conn, subscription_id, mrr_delta)
+                .await
+                .map_err(Into::<error_stack::Report<StoreError>>::into)?;
+        }
+
+        Ok(mrr_delta)
+    }
+
+    /// MRR of the subscription computed from its current components and add-ons, along
+    /// with the MRR currently stored on it.
+    pub(in crate::services) async fn calculate_subscription_mrr(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+    ) -> StoreResult<(i64, i64)> {
+        use crate::services::subscriptions::plan_change::calculate_components_mrr_with_slots;
+        use crate::services::subscriptions::utils::calculate_mrr;
+
+        let sub_details = self
+            .store
+            .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
+            .await?;
+
+        let precision = crate::constants::Currencies::resolve_currency_precision(
+            &sub_details.subscription.currency,
+        )
+        .unwrap_or(2);
+
+        let component_mrr: i64 = calculate_components_mrr_with_slots(
+            conn,
+            tenant_id,
+            subscription_id,
+            &sub_details.price_components,
+            precision,
+        )
+        .await?;
+
+        let add_on_mrr: i64 = sub_details
+            .add_ons
+            .iter()
+            .map(|a| calculate_mrr(&a.fee, &a.period, precision) * a.quantity as i64)
+            .sum();
+
+        Ok((
+            component_mrr + add_on_mrr,
+            sub_details.subscription.mrr_cents as i64,
+        ))
+    }
+
     async fn process_pause_subscription(
         &self,
         conn: &mut PgConn,
         event: &ScheduledEvent,
     ) -> StoreResult<()> {
-        if let ScheduledEventData::PauseSubscription = &event.event_data {
-            self.terminate_subscription(
+        if let ScheduledEventData::PauseSubscription {
+            billing,
+            resume_on,
+            resume_anchor,
+        } = &event.event_data
+        {
+            self.apply_pause(
                 conn,
+                &Actor::System,
                 event.tenant_id,
                 event.subscription_id,
                 event.scheduled_time.date(),
-                SubscriptionStatusEnum::Paused,
+                &PauseParams {
+                    billing: *billing,
+                    resume_on: *resume_on,
+                    resume_anchor: *resume_anchor,
+                },
             )
             .await?;
         } else {
@@ -879,6 +1145,52 @@ impl Services {
         Ok(())
     }
 
+    async fn process_resume_subscription(
+        &self,
+        conn: &mut PgConn,
+        event: &ScheduledEvent,
+    ) -> StoreResult<()> {
+        use diesel_models::subscriptions::SubscriptionRow;
+
+        if let ScheduledEventData::ResumeSubscription { anchor } = &event.event_data {
+            let subscription = SubscriptionRow::get_subscription_by_id(
+                conn,
+                &event.tenant_id,
+                event.subscription_id,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+            // Resumed manually (or terminated) in the meantime
+            if subscription.subscription.status != SubscriptionStatusEnum::Paused {
+                log::info!(
+                    "Subscription {} is no longer paused, skipping resume event {}",
+                    event.subscription_id,
+                    event.id
+                );
+                return Ok(());
+            }
+
+            self.apply_resume(
+                conn,
+                &Actor::System,
+                event.tenant_id,
+                event.subscription_id,
+                event.scheduled_time.date(),
+                *anchor,
+            )
+            .await?;
+        } else {
+            log::error!(
+                "Unexpected event data for type ResumeSubscription: {:?}, event_id={}",
+                event.event_data,
+                event.id
+            );
+        }
+
+        Ok(())
+    }
+
     /// Process EndTrial event for paid trials.
     /// This transitions the subscription from TrialActive to Active.
     /// Billing continues normally via RenewSubscription - this just handles the status change.
@@ -917,6 +1229,14 @@ impl Services {
                 };
                 patch.patch(conn).await?;
 
+                self.record_trial_ended_tx(
+                    conn,
+                    event.tenant_id,
+                    &Actor::System,
+                    event.subscription_id,
+                )
+                .await?;
+
                 log::info!(
                     "Paid trial ended for subscription {}, transitioned to Active",
                     event.subscription_id.as_base62()
@@ -1,5 +1,10 @@
 use crate::StoreResult;
+use crate::domain::entity_activity::{Actor, AuditInput};
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionTermRenewedEvent};
+use crate::domain::scheduled_events::ScheduledEventData;
+use crate::domain::{PauseBilling, Subscription};
 use crate::errors::{StoreError, StoreErrorContainer};
+use crate::repositories::entity_activity::EntityActivityInterface;
 use crate::services::{InvoiceBillingMode, Services};
 use crate::store::PgConn;
 use crate::utils::errors::format_error_chain;
@@ -182,7 +187,7 @@ impl Services {
 
     /// Executes the current scheduled action for a subscription.
     /// (activate, renew, end trial, end subscription, etc.)
-    async fn process_cycle_transition(
+    pub(in crate::services) async fn process_cycle_transition(
         &self,
         conn: &mut PgConn,
         subscription: &SubscriptionRow,
@@ -198,6 +203,14 @@ impl Services {
             return Ok(());
         };
 
+        // A fixed term reaching its end date renews if its policy says so, and billing goes on.
+        let term_renewal = match next_action {
+            CycleActionEnum::EndSubscription => {
+                self.prepare_term_renewal_tx(conn, subscription).await?
+            }
+            _ => None,
+        };
+
         let mut next_cycle = match next_action {
             CycleActionEnum::ActivateSubscription => self.activate_subscription(subscription)?,
             CycleActionEnum::RenewSubscription => self.renew_subscription(subscription)?,
@@ -205,10 +218,17 @@ impl Services {
                 // Trial ends - determine next state based on plan type and payment method
                 self.end_trial(conn, subscription).await?
             }
+            CycleActionEnum::EndSubscription if term_renewal.is_some() => {
+                self.renew_subscription(subscription)?
+            }
             CycleActionEnum::EndSubscription => self.end_subscription(subscription)?,
         };
 
-        if let Some(end_date) = subscription.end_date.filter(|end_date| {
+        let end_date = term_renewal
+            .as_ref()
+            .map(|renewal| renewal.new_end_date)
+            .or(subscription.end_date);
+        if let Some(end_date) = end_date.filter(|end_date| {
             next_cycle
                 .new_period_end
                 .is_some_and(|new_period_end| end_date <= &new_period_end)
@@ -233,6 +253,7 @@ impl Services {
                     ScheduledEventTypeEnum::CancelSubscription,
                     ScheduledEventTypeEnum::ApplyPlanChange,
                     ScheduledEventTypeEnum::ApplyAmendment,
+                    ScheduledEventTypeEnum::ApplyPriceChange,
                     ScheduledEventTypeEnum::PauseSubscription,
                 ],
                 next_cycle.new_period_start,
@@ -245,19 +266,33 @@ impl Services {
                     subscription.id,
                     event
                 );
-                // Non-terminal events (plan change, amendment) apply at period end and then
+                // Non-terminal events (plan change, amendment, price change) apply at period end and then
                 // billing continues; terminal events (cancel, pause) stop the cycle.
-                let is_non_terminal = matches!(
-                    event.event_type,
-                    ScheduledEventTypeEnum::ApplyPlanChange
-                        | ScheduledEventTypeEnum::ApplyAmendment
+                // A pause that keeps billing fixed fees only flips the status, so cycles go on.
+                let is_paused_with_billing = matches!(
+                    ScheduledEventData::try_from(&event.event_data),
+                    Ok(ScheduledEventData::PauseSubscription {
+                        billing: PauseBilling::KeepFixedFees,
+                        ..
+                    })
                 );
+                let is_non_terminal = is_paused_with_billing
+                    || matches!(
+                        event.event_type,
+                        ScheduledEventTypeEnum::ApplyPlanChange
+                            | ScheduledEventTypeEnum::ApplyAmendment
+                            | ScheduledEventTypeEnum::ApplyPriceChange
+                    );
                 let event_id = event.id;
 
                 ScheduledEventRow::mark_as_processing(conn, &[event_id]).await?;
                 self.process_event_batch(conn, vec![event]).await?;
                 ScheduledEventRow::mark_as_completed(conn, &[event_id]).await?;
 
+                if is_paused_with_billing {
+                    next_cycle.status = SubscriptionStatusEnum::Paused;
+                }
+
                 if !is_non_terminal {
                     // Terminal events (cancel, pause): clear processing claim and stop.
                     SubscriptionCycleRowPatch {
@@ -278,7 +313,7 @@ impl Services {
 
                     return Ok(());
                 }
-                // Plan change / amendment: fall through to advance period and bill at new prices.
+                // Plan change / amendment / price change / billed pause: fall through to advance period and bill.
             }
         }
 
@@ -299,9 +334,20 @@ impl Services {
                 // (we're still in cycle 0, just now with billing)
                 subscription.cycle_index
             }
+            // A renewed term starts a new billing period
+            CycleActionEnum::EndSubscription if term_renewal.is_some() => {
+                subscription.cycle_index.map(|i| i + 1).or(Some(0))
+            }
             _ => subscription.cycle_index,
         };
 
+        // Read again, as the events processed above may have changed the status already.
+        let previous_status =
+            SubscriptionRow::get_subscription_by_id(conn, &subscription.tenant_id, subscription.id)
+                .await?
+                .subscription
+                .status;
+
         let patch = SubscriptionCycleRowPatch {
             id: subscription.id,
             tenant_id: subscription.tenant_id,
@@ -319,6 +365,11 @@ impl Services {
 
         patch.patch(conn).await?;
 
+        if let Some(renewal) = &term_renewal {
+            self.apply_term_renewal_tx(conn, subscription, renewal)
+                .await?;
+        }
+
         if next_cycle.should_bill {
             self.bill_subscription_tx(
                 conn,
@@ -329,7 +380,51 @@ impl Services {
             .await?;
         }
 
-        Ok(())
+        let updated: Subscription =
+            SubscriptionRow::get_subscription_by_id(conn, &subscription.tenant_id, subscription.id)
+                .await?
+                .try_into()?;
+
+        let mut events = match next_action {
+            CycleActionEnum::RenewSubscription => {
+                vec![OutboxEvent::subscription_renewed(updated.clone().into())]
+            }
+            CycleActionEnum::EndTrial => vec![OutboxEvent::subscription_trial_ended(
+                updated.clone().into(),
+            )],
+            CycleActionEnum::EndSubscription if term_renewal.is_some() => {
+                vec![OutboxEvent::subscription_renewed(updated.clone().into())]
+            }
+            CycleActionEnum::ActivateSubscription | CycleActionEnum::EndSubscription => vec![],
+        };
+        if let Some(renewal) = &term_renewal {
+            events.push(OutboxEvent::subscription_term_renewed(
+                SubscriptionTermRenewedEvent {
+                    subscription: updated.clone().into(),
+                    previous_end_date: renewal.previous_end_date,
+                    renewal_policy: renewal.policy,
+                },
+            ));
+        }
+        for event in events {
+            self.store
+                .record_tx(
+                    conn,
+                    subscription.tenant_id,
+                    &Actor::System,
+                    AuditInput::Outbox(event),
+                )
+                .await?;
+        }
+
+        self.record_status_change_tx(
+            conn,
+            subscription.tenant_id,
+            &Actor::System,
+            &updated,
+            previous_status.into(),
+        )
+        .await
     }
 
     fn activate_subscription(&self, subscription: &SubscriptionRow) -> StoreResult<NextCycle> {
@@ -459,8 +554,16 @@ impl Services {
             &(subscription.period.clone().into()),
         );
 
+        // Subscriptions paused while keeping their fixed fees billed renew as usual,
+        // but stay paused until resumed.
+        let status = if subscription.status == SubscriptionStatusEnum::Paused {
+            SubscriptionStatusEnum::Paused
+        } else {
+            SubscriptionStatusEnum::Active
+        };
+
         Ok(NextCycle {
-            status: SubscriptionStatusEnum::Active,
+            status,
             next_cycle_action: Some(CycleActionEnum::RenewSubscription),
             new_period_start,
             new_period_end: Some(period.end),
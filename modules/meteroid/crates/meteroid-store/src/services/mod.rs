@@ -16,6 +16,7 @@ use std::sync::Arc;
 // mod billing_worker;
 pub mod utils;
 
+mod catalog;
 mod checkout_completion;
 mod checkout_preview;
 pub mod clients;
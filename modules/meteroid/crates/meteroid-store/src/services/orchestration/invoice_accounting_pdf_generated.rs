@@ -12,6 +12,7 @@ use crate::repositories::payment_transactions::PaymentTransactionInterface;
 use crate::repositories::pgmq::PgmqInterface;
 use crate::repositories::{InvoiceInterface, SubscriptionInterface};
 use crate::services::Services;
+use crate::utils::locale::document_language;
 use chrono::Utc;
 use common_domain::ids::TenantId;
 use diesel_models::invoices::InvoiceRow;
@@ -64,6 +65,10 @@ impl Services {
                     .await?;
 
                 if let Some(receipt) = receipt {
+                    let lang = document_language(
+                        customer.invoice_language.as_deref(),
+                        &invoicing_entity.country,
+                    );
                     let email_msg: PgmqMessageNew = SendEmailRequest::InvoicePaid {
                         tenant_id,
                         invoice_id: invoice.id,
@@ -71,10 +76,11 @@ impl Services {
                         invoicing_entity_id: invoicing_entity.id,
                         invoice_date: invoice.invoice_date,
                         invoice_due_date: invoice.due_at.map_or(invoice.invoice_date, |d| d.date()),
-                        label: "Thank you for your payment".to_string(),
+                        label: meteroid_mailer::l10n::payment_thanks_label(&lang),
                         amount_paid: receipt.amount,
                         currency: invoice.currency,
                         company_name: invoice.seller_details.legal_name.clone(),
+                        lang: Some(lang),
                         logo_attachment_id: invoicing_entity.logo_attachment_id,
                         invoicing_emails: customer.invoicing_emails,
                         invoice_pdf_id: event.pdf_id,
@@ -160,11 +166,11 @@ impl Services {
         customer: Customer,
         invoicing_entity: InvoicingEntity,
     ) -> StoreResult<()> {
-        let label = invoice
-            .plan_name
-            .as_ref()
-            .map(|plan| format!("Your {} subscription", plan))
-            .unwrap_or_else(|| "Invoice for services".to_string());
+        let lang = document_language(
+            customer.invoice_language.as_deref(),
+            &invoicing_entity.country,
+        );
+        let label = meteroid_mailer::l10n::invoice_ready_label(&lang, invoice.plan_name.as_deref());
 
         let evt: PgmqMessageNew = SendEmailRequest::InvoiceReady {
             tenant_id: invoice.tenant_id,
@@ -176,6 +182,7 @@ impl Services {
             label,
             currency: invoice.currency,
             company_name: invoice.seller_details.legal_name.clone(),
+            lang: Some(lang),
             logo_attachment_id: invoicing_entity.logo_attachment_id,
             invoicing_emails: customer.invoicing_emails,
             invoice_pdf_id: event.pdf_id,
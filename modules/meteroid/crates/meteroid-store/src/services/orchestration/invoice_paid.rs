@@ -8,6 +8,7 @@ use crate::repositories::invoicing_entities::InvoicingEntityInterfaceAuto;
 use crate::repositories::payment_transactions::PaymentTransactionInterface;
 use crate::repositories::pgmq::PgmqInterface;
 use crate::services::Services;
+use crate::utils::locale::document_language;
 use crate::utils::periods::calculate_advance_period_range;
 use common_domain::ids::TenantId;
 use diesel_models::enums::CycleActionEnum;
@@ -76,11 +77,11 @@ impl Services {
             .get_invoicing_entity(tenant_id, Some(customer.invoicing_entity_id))
             .await?;
 
-        let label = invoice
-            .plan_name
-            .as_ref()
-            .map(|plan| format!("Your {} invoice was paid successfully.", plan))
-            .unwrap_or_else(|| "Invoice for services was paid successfully.".to_string());
+        let lang = document_language(
+            customer.invoice_language.as_deref(),
+            &invoicing_entity.country,
+        );
+        let label = meteroid_mailer::l10n::invoice_paid_label(&lang, invoice.plan_name.as_deref());
 
         let email_msg: PgmqMessageNew = SendEmailRequest::InvoicePaid {
             tenant_id,
@@ -93,6 +94,7 @@ impl Services {
             amount_paid: receipt.amount,
             currency: invoice.currency,
             company_name: invoice.seller_details.legal_name.clone(),
+            lang: Some(lang),
             logo_attachment_id: invoicing_entity.logo_attachment_id,
             invoicing_emails: customer.invoicing_emails,
             invoice_pdf_id,
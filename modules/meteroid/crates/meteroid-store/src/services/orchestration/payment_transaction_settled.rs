@@ -406,7 +406,6 @@ impl Services {
         use crate::domain::checkout_sessions::CheckoutType;
         use crate::services::InvoiceBillingMode;
         use crate::services::checkout_completion::DirectChargeResult;
-        use diesel_models::customers::CustomerRow;
 
         self.store
             .transaction(|conn| {
@@ -645,14 +644,7 @@ impl Services {
                                 )
                                 .await?;
 
-                            let customer: crate::domain::Customer = CustomerRow::find_by_id(
-                                conn,
-                                &subscription.subscription.customer_id,
-                                &event.tenant_id,
-                            )
-                            .await
-                            .map_err(Into::into)
-                            .and_then(TryInto::try_into)?;
+                            let customer = subscription.billed_customer().clone();
 
                             self.bill_subscription_with_data_tx(
                                 conn,
@@ -780,7 +772,7 @@ impl Services {
                                             conn,
                                             event.tenant_id,
                                             &prepared.subscription_details.subscription,
-                                            &prepared.subscription_details.customer,
+                                            prepared.subscription_details.billed_customer(),
                                             &prepared.proration,
                                         )
                                         .await?;
@@ -253,6 +253,10 @@ impl Services {
             is_tax_exempt: None,
             custom_taxes: None,
             connected_account_id: None,
+            custom_attributes: None,
+            invoice_language: None,
+            invoice_custom_fields: None,
+            invoice_approval_required: None,
         };
         self.store
             .patch_customer(Actor::System, tenant_id, patch)
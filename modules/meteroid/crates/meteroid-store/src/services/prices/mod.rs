@@ -16,6 +16,8 @@ use crate::errors::StoreError;
 
 use super::Services;
 
+pub(crate) mod price_changes;
+
 fn dimension_key_matches(row: &MatrixRow, key: &MatrixDimensionKey) -> bool {
     let d1_match =
         row.dimension1.key == key.dimension1.key && row.dimension1.value == key.dimension1.value;
@@ -27,6 +29,23 @@ fn dimension_key_matches(row: &MatrixRow, key: &MatrixDimensionKey) -> bool {
     d1_match && d2_match
 }
 
+/// Groups `(plan name, version)` pairs into one entry per plan.
+fn affected_plans(plan_info: Vec<(String, i32)>) -> Vec<AffectedPlan> {
+    let mut plan_map: std::collections::BTreeMap<String, Vec<i32>> =
+        std::collections::BTreeMap::new();
+    for (name, version) in plan_info {
+        plan_map.entry(name).or_default().push(version);
+    }
+
+    plan_map
+        .into_iter()
+        .map(|(plan_name, versions)| AffectedPlan {
+            plan_name,
+            versions,
+        })
+        .collect()
+}
+
 fn validate_matrix_product(fee_structure: &serde_json::Value) -> StoreResult<()> {
     let fs: FeeStructure = serde_json::from_value(fee_structure.clone()).map_err(|e| {
         Report::new(StoreError::SerdeError(
@@ -207,26 +226,12 @@ impl Services {
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
 
-        let mut plan_map: std::collections::BTreeMap<String, Vec<i32>> =
-            std::collections::BTreeMap::new();
-        for (name, version) in plan_info {
-            plan_map.entry(name).or_default().push(version);
-        }
-
-        let affected_plans: Vec<AffectedPlan> = plan_map
-            .into_iter()
-            .map(|(plan_name, versions)| AffectedPlan {
-                plan_name,
-                versions,
-            })
-            .collect();
-
         Ok(MatrixUpdatePreview {
             affected_prices_count,
             affected_subscriptions_count: affected_subscriptions_count as usize,
             rows_to_add,
             rows_to_remove,
-            affected_plans,
+            affected_plans: affected_plans(plan_info),
         })
     }
 }
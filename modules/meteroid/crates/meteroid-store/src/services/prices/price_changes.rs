@@ -0,0 +1,590 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{NaiveDate, Utc};
+use common_domain::ids::{
+    BaseId, CustomerId, PriceChangeId, PriceId, ProductId, SubscriptionId, TenantId,
+};
+use diesel_models::add_ons::AddOnRow;
+use diesel_models::customers::CustomerRow;
+use diesel_models::enums::PriceChangeStatusEnum;
+use diesel_models::plan_component_prices::PlanComponentPriceRow;
+use diesel_models::plan_version_add_ons::PlanVersionAddOnRow;
+use diesel_models::plans::PlanRow;
+use diesel_models::price_changes::{PriceChangeRow, PriceChangeRowNew};
+use diesel_models::prices::{PriceRow, PriceRowNew};
+use diesel_models::products::ProductRow;
+use diesel_models::scheduled_events::ScheduledEventRow;
+use diesel_models::subscription_components::SubscriptionComponentRow;
+use diesel_models::subscriptions::{SubscriptionForDisplayRow, SubscriptionRow};
+use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+use crate::StoreResult;
+use crate::domain::enums::BillingPeriodEnum;
+use crate::domain::price_changes::{
+    PriceChange, PriceChangeGrandfathering, PriceChangeNew, PriceChangePreview,
+    SubscriptionPriceChangeImpact,
+};
+use crate::domain::prices::{FeeStructure, Price, Pricing, resolve_subscription_fee};
+use crate::domain::scheduled_events::{ScheduledEventData, ScheduledEventNew};
+use crate::domain::subscription_components::SubscriptionComponent;
+use crate::domain::{Customer, Product, SubscriptionFee};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::services::subscriptions::plan_change::calculate_components_mrr_with_slots;
+use crate::store::PgConn;
+use crate::utils::periods::first_renewal_on_or_after;
+
+use super::{Services, affected_plans};
+
+/// Changes applied per worker tick; each one runs in its own transaction.
+const APPLY_BATCH_SIZE: usize = 20;
+
+const PRICE_CHANGE_EVENT_SOURCE: &str = "price_change";
+
+/// A live subscription still billed on the price being changed, with the renewal at
+/// which it moves to the new price.
+struct PlannedMigration {
+    subscription: SubscriptionForDisplayRow,
+    components: Vec<SubscriptionComponentRow>,
+    apply_date: NaiveDate,
+}
+
+/// Resolved fee of a component billed on `price_id`, for MRR computation.
+fn component_with_fee(
+    row: &SubscriptionComponentRow,
+    fee: &SubscriptionFee,
+) -> SubscriptionComponent {
+    SubscriptionComponent {
+        id: row.id,
+        price_component_id: row.price_component_id,
+        product_id: row.product_id,
+        subscription_id: row.subscription_id,
+        name: row.name.clone(),
+        period: row.period.clone().into(),
+        fee: fee.clone(),
+        price_id: row.price_id,
+        effective_from: row.effective_from,
+        effective_to: row.effective_to,
+        lineage_id: row.lineage_id,
+        added_by_amendment: row.added_by_amendment,
+    }
+}
+
+fn resolve_fee(fee_structure: &FeeStructure, pricing: &Pricing) -> StoreResult<SubscriptionFee> {
+    resolve_subscription_fee(fee_structure, pricing, None).map_err(|e| {
+        Report::new(StoreError::InvalidArgument(format!(
+            "Pricing does not match the product fee structure: {e}"
+        )))
+    })
+}
+
+async fn load_product(
+    conn: &mut PgConn,
+    product_id: ProductId,
+    tenant_id: TenantId,
+) -> StoreResult<Product> {
+    ProductRow::find_by_id_and_tenant_id(conn, product_id, tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?
+        .try_into()
+}
+
+async fn load_price(
+    conn: &mut PgConn,
+    price_id: PriceId,
+    tenant_id: TenantId,
+) -> StoreResult<Price> {
+    PriceRow::find_by_id_and_tenant_id(conn, price_id, tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?
+        .try_into()
+}
+
+/// Validates a price change request and returns the current price and its product.
+async fn validate_price_change(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    change: &PriceChangeNew,
+) -> StoreResult<(Price, Product)> {
+    let today = Utc::now().date_naive();
+    if change.effective_date <= today {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Price change effective date must be in the future".to_string(),
+        )));
+    }
+
+    if let PriceChangeGrandfathering::RenewingAfter(date) = &change.grandfathering
+        && *date < change.effective_date
+    {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Grandfathering date cannot be before the effective date".to_string(),
+        )));
+    }
+
+    let price = load_price(conn, change.price_id, tenant_id).await?;
+    if price.archived_at.is_some() {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Cannot schedule a change on an archived price".to_string(),
+        )));
+    }
+
+    let product = load_product(conn, price.product_id, tenant_id).await?;
+    resolve_fee(&product.fee_structure, &change.new_pricing)?;
+
+    Ok((price, product))
+}
+
+/// Live subscriptions on `price_id` that migrate at their first renewal on or after
+/// `cutoff`. Subscriptions ending before that renewal are left alone, as are the ones of
+/// customers billed on a price variant: a change only replaces the base pricing.
+async fn plan_migrations(
+    conn: &mut PgConn,
+    tenant_id: TenantId,
+    price_id: PriceId,
+    cutoff: NaiveDate,
+) -> StoreResult<Vec<PlannedMigration>> {
+    let rows = SubscriptionComponentRow::list_live_by_price_id(conn, price_id, &tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+    let mut by_subscription: HashMap<SubscriptionId, Vec<SubscriptionComponentRow>> =
+        HashMap::new();
+    for row in rows {
+        by_subscription
+            .entry(row.subscription_id)
+            .or_default()
+            .push(row);
+    }
+
+    if by_subscription.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let ids: Vec<SubscriptionId> = by_subscription.keys().copied().collect();
+    let subscriptions = SubscriptionRow::list_subscriptions_by_ids(conn, &tenant_id, &ids)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+    let price = load_price(conn, price_id, tenant_id).await?;
+    let mut on_variant = HashSet::new();
+    if !price.variants.is_empty() {
+        let customer_ids: Vec<CustomerId> = subscriptions
+            .iter()
+            .map(|s| s.subscription.customer_id)
+            .collect();
+        let rows = CustomerRow::list_by_ids(conn, &tenant_id, customer_ids)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+        for row in rows {
+            let customer: Customer = row.try_into()?;
+            if price
+                .variants
+                .iter()
+                .any(|v| v.condition.matches(&customer))
+            {
+                on_variant.insert(customer.id);
+            }
+        }
+    }
+
+    let mut planned = Vec::with_capacity(subscriptions.len());
+    for subscription in subscriptions {
+        let sub = &subscription.subscription;
+        if on_variant.contains(&sub.customer_id) {
+            continue;
+        }
+        let Some(current_period_end) = sub.current_period_end else {
+            continue;
+        };
+
+        let billing_period: BillingPeriodEnum = sub.period.clone().into();
+        let apply_date = first_renewal_on_or_after(
+            current_period_end,
+            cutoff,
+            sub.billing_day_anchor as u32,
+            &billing_period,
+        );
+
+        if sub.end_date.is_some_and(|end| end <= apply_date) {
+            continue;
+        }
+
+        let components = by_subscription.remove(&sub.id).unwrap_or_default();
+        planned.push(PlannedMigration {
+            subscription,
+            components,
+            apply_date,
+        });
+    }
+
+    Ok(planned)
+}
+
+/// Inserts the replacement price row if it does not exist yet. The row is only created
+/// once the change starts applying, so it does not surface in the catalog before the
+/// effective date.
+pub(crate) async fn ensure_new_price(
+    conn: &mut PgConn,
+    change: &PriceChangeRow,
+) -> StoreResult<()> {
+    let current = PriceRow::find_by_id_and_tenant_id(conn, change.price_id, change.tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+    PriceRowNew {
+        id: change.new_price_id,
+        product_id: current.product_id,
+        cadence: current.cadence,
+        currency: current.currency,
+        pricing: change.new_pricing.clone(),
+        tenant_id: change.tenant_id,
+        catalog: current.catalog,
+        variants: current.variants,
+    }
+    .insert_if_absent(conn)
+    .await
+    .map_err(Into::<Report<StoreError>>::into)
+}
+
+impl Services {
+    pub(crate) async fn preview_price_change(
+        &self,
+        tenant_id: TenantId,
+        change: &PriceChangeNew,
+    ) -> StoreResult<PriceChangePreview> {
+        let mut conn = self.store.get_conn().await?;
+
+        let (price, product) = validate_price_change(&mut conn, tenant_id, change).await?;
+
+        let plan_info = PlanRow::list_plan_info_by_price_ids(&mut conn, &[price.id])
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        let Some(cutoff) = change
+            .grandfathering
+            .migration_cutoff(change.effective_date)
+        else {
+            return Ok(PriceChangePreview {
+                currency: price.currency,
+                affected_plans: affected_plans(plan_info),
+                subscriptions: vec![],
+            });
+        };
+
+        let current_fee = resolve_fee(&product.fee_structure, &price.pricing)?;
+        let new_fee = resolve_fee(&product.fee_structure, &change.new_pricing)?;
+
+        let precision =
+            crate::constants::Currencies::resolve_currency_precision(&price.currency).unwrap_or(2);
+
+        let mut subscriptions = Vec::new();
+        for planned in plan_migrations(&mut conn, tenant_id, price.id, cutoff).await? {
+            let subscription_id = planned.subscription.subscription.id;
+
+            let current: Vec<SubscriptionComponent> = planned
+                .components
+                .iter()
+                .map(|c| component_with_fee(c, &current_fee))
+                .collect();
+            let updated: Vec<SubscriptionComponent> = planned
+                .components
+                .iter()
+                .map(|c| component_with_fee(c, &new_fee))
+                .collect();
+
+            let current_mrr_cents = calculate_components_mrr_with_slots(
+                &mut conn,
+                tenant_id,
+                subscription_id,
+                &current,
+                precision,
+            )
+            .await?;
+            let new_mrr_cents = calculate_components_mrr_with_slots(
+                &mut conn,
+                tenant_id,
+                subscription_id,
+                &updated,
+                precision,
+            )
+            .await?;
+
+            subscriptions.push(SubscriptionPriceChangeImpact {
+                subscription_id,
+                customer_id: planned.subscription.customer_id,
+                customer_name: planned.subscription.customer_name,
+                plan_name: planned.subscription.plan_name,
+                apply_date: planned.apply_date,
+                current_mrr_cents,
+                new_mrr_cents,
+            });
+        }
+
+        subscriptions.sort_by_key(|s| s.apply_date);
+
+        Ok(PriceChangePreview {
+            currency: price.currency,
+            affected_plans: affected_plans(plan_info),
+            subscriptions,
+        })
+    }
+
+    pub(crate) async fn schedule_price_change(
+        &self,
+        tenant_id: TenantId,
+        change: PriceChangeNew,
+        actor: Uuid,
+    ) -> StoreResult<PriceChange> {
+        self.store
+            .transaction(|conn| {
+                async move {
+                    let (price, _) = validate_price_change(conn, tenant_id, &change).await?;
+
+                    let new_pricing = serde_json::to_value(&change.new_pricing).map_err(|e| {
+                        Report::new(StoreError::SerdeError(
+                            "Failed to serialize Pricing".to_string(),
+                            e,
+                        ))
+                    })?;
+                    let (grandfathering, renewing_after) = change.grandfathering.to_row();
+
+                    let row = PriceChangeRowNew {
+                        id: PriceChangeId::new(),
+                        tenant_id,
+                        product_id: price.product_id,
+                        price_id: price.id,
+                        new_price_id: PriceId::new(),
+                        new_pricing,
+                        effective_date: change.effective_date,
+                        grandfathering,
+                        renewing_after,
+                        created_by: actor,
+                    }
+                    .insert(conn)
+                    .await
+                    .map_err(|e| {
+                        let report: Report<StoreError> = e.into();
+                        match report.current_context() {
+                            StoreError::DuplicateValue { .. } => {
+                                report.change_context(StoreError::InvalidArgument(
+                                    "A price change is already scheduled for this price"
+                                        .to_string(),
+                                ))
+                            }
+                            _ => report,
+                        }
+                    })?;
+
+                    self.schedule_price_change_events(conn, &row).await?;
+
+                    PriceChange::from_row(row, price)
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    pub(crate) async fn list_price_changes(
+        &self,
+        tenant_id: TenantId,
+        product_id: ProductId,
+    ) -> StoreResult<Vec<PriceChange>> {
+        let mut conn = self.store.get_conn().await?;
+
+        let rows = PriceChangeRow::list_by_product_id(&mut conn, product_id, tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        let price_ids: Vec<PriceId> = rows.iter().map(|r| r.price_id).collect();
+        let prices: HashMap<PriceId, Price> =
+            PriceRow::list_by_ids(&mut conn, &price_ids, tenant_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .into_iter()
+                .map(|row| Price::try_from(row).map(|p| (p.id, p)))
+                .collect::<Result<_, _>>()?;
+
+        rows.into_iter()
+            .map(|row| {
+                let price = prices.get(&row.price_id).cloned().ok_or_else(|| {
+                    Report::new(StoreError::ValueNotFound(format!(
+                        "Price {} not found",
+                        row.price_id
+                    )))
+                })?;
+                PriceChange::from_row(row, price)
+            })
+            .collect()
+    }
+
+    pub(crate) async fn cancel_price_change(
+        &self,
+        tenant_id: TenantId,
+        id: PriceChangeId,
+    ) -> StoreResult<PriceChange> {
+        self.store
+            .transaction(|conn| {
+                async move {
+                    let row = PriceChangeRow::find_by_id_for_update(conn, id, tenant_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?;
+
+                    if row.status != PriceChangeStatusEnum::Scheduled {
+                        return Err(Report::new(StoreError::InvalidArgument(format!(
+                            "Price change is {:?} and can no longer be cancelled",
+                            row.status
+                        ))));
+                    }
+
+                    let row = PriceChangeRow::update_status(
+                        conn,
+                        id,
+                        tenant_id,
+                        PriceChangeStatusEnum::Cancelled,
+                    )
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                    ScheduledEventRow::cancel_pending_price_change_events(
+                        conn,
+                        &tenant_id,
+                        id.as_uuid(),
+                        "Price change cancelled",
+                    )
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                    let price = load_price(conn, row.price_id, tenant_id).await?;
+                    PriceChange::from_row(row, price)
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    /// Switches the catalog to the new price for every change whose effective date has
+    /// been reached. Returns the number of changes applied.
+    pub async fn apply_due_price_changes(&self) -> StoreResult<usize> {
+        let today = Utc::now().date_naive();
+        let mut applied = 0;
+
+        while applied < APPLY_BATCH_SIZE {
+            let claimed = self
+                .store
+                .transaction(|conn| {
+                    async move {
+                        let Some(row) = PriceChangeRow::claim_due(conn, today, 1)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?
+                            .into_iter()
+                            .next()
+                        else {
+                            return Ok(false);
+                        };
+
+                        self.apply_price_change_to_catalog(conn, &row).await?;
+                        Ok(true)
+                    }
+                    .scope_boxed()
+                })
+                .await?;
+
+            if !claimed {
+                break;
+            }
+            applied += 1;
+        }
+
+        Ok(applied)
+    }
+
+    async fn apply_price_change_to_catalog(
+        &self,
+        conn: &mut PgConn,
+        row: &PriceChangeRow,
+    ) -> StoreResult<()> {
+        ensure_new_price(conn, row).await?;
+
+        PlanComponentPriceRow::replace_price_id(
+            conn,
+            row.price_id,
+            row.new_price_id,
+            row.tenant_id,
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+        PlanVersionAddOnRow::replace_price_id(conn, row.price_id, row.new_price_id, row.tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+        AddOnRow::replace_price_id(conn, row.price_id, row.new_price_id, row.tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        PriceRow::archive(conn, row.price_id, row.tenant_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        // Subscriptions created since scheduling are still on the old price.
+        self.schedule_price_change_events(conn, row).await?;
+
+        PriceChangeRow::update_status(conn, row.id, row.tenant_id, PriceChangeStatusEnum::Applied)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
+        log::info!(
+            "Applied price change {} to catalog: price {} replaced by {}",
+            row.id,
+            row.price_id,
+            row.new_price_id
+        );
+
+        Ok(())
+    }
+
+    /// Schedules an `ApplyPriceChange` event at the migration renewal of every live
+    /// subscription on the old price that does not have one yet.
+    async fn schedule_price_change_events(
+        &self,
+        conn: &mut PgConn,
+        row: &PriceChangeRow,
+    ) -> StoreResult<()> {
+        let grandfathering =
+            PriceChangeGrandfathering::from_row(row.grandfathering.clone(), row.renewing_after)?;
+        let Some(cutoff) = grandfathering.migration_cutoff(row.effective_date) else {
+            return Ok(());
+        };
+
+        let already_scheduled = ScheduledEventRow::list_subscription_ids_for_price_change(
+            conn,
+            &row.tenant_id,
+            row.id.as_uuid(),
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+        let events: Vec<ScheduledEventNew> =
+            plan_migrations(conn, row.tenant_id, row.price_id, cutoff)
+                .await?
+                .into_iter()
+                .filter(|p| !already_scheduled.contains(&p.subscription.subscription.id))
+                .map(|p| ScheduledEventNew {
+                    subscription_id: p.subscription.subscription.id,
+                    tenant_id: row.tenant_id,
+                    scheduled_time: p.apply_date.and_time(chrono::NaiveTime::MIN),
+                    event_data: ScheduledEventData::ApplyPriceChange {
+                        price_change_id: row.id,
+                    },
+                    source: PRICE_CHANGE_EVENT_SOURCE.to_string(),
+                    created_by_customer: false,
+                })
+                .collect();
+
+        if !events.is_empty() {
+            self.store.schedule_events(conn, events).await?;
+        }
+
+        Ok(())
+    }
+}
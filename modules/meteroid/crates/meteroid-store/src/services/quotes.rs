@@ -5,7 +5,9 @@ use crate::domain::enums::QuoteStatusEnum;
 use crate::domain::quotes::DetailedQuote;
 use crate::domain::subscription_add_ons::SubscriptionAddOnNewInternal;
 use crate::domain::subscription_components::SubscriptionComponentNewInternal;
-use crate::domain::{CreateSubscriptionFromQuote, CreatedSubscription, SubscriptionNew};
+use crate::domain::{
+    CreateSubscriptionFromQuote, CreatedSubscription, PastPeriodBilling, SubscriptionNew,
+};
 use crate::errors::StoreError;
 use crate::services::ServicesEdge;
 use common_domain::ids::{QuoteId, TenantId};
@@ -89,7 +91,8 @@ fn build_subscription_from_quote(
         purchase_order: quote.purchase_order.clone(),
         backdate_invoices: false,
         skip_checkout_session: false,
-        skip_past_invoices: false,
+        past_period_billing: PastPeriodBilling::Invoice,
+        instance: None,
     };
 
     let components: Vec<SubscriptionComponentNewInternal> = detailed_quote
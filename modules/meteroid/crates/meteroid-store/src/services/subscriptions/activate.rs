@@ -1,5 +1,6 @@
 use crate::StoreResult;
 use crate::domain::Subscription;
+use crate::domain::entity_activity::Actor;
 use crate::domain::enums::{BillingPeriodEnum, SubscriptionActivationCondition};
 use crate::domain::scheduled_events::ScheduledEventNew;
 use crate::errors::StoreError;
@@ -30,8 +31,7 @@ impl Services {
         tenant_id: TenantId,
         subscription_id: SubscriptionId,
     ) -> StoreResult<Subscription> {
-        let db_subscription = self
-            .store
+        self.store
             .transaction(|conn| {
                 async move {
                     SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
@@ -118,20 +118,26 @@ impl Services {
                     .await?;
 
                     // Fetch and return updated subscription
-                    let updated =
+                    let updated: Subscription =
                         SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
                             .await
-                            .map_err(Into::<Report<StoreError>>::into)?;
+                            .map_err(Into::<Report<StoreError>>::into)?
+                            .try_into()?;
+
+                    self.record_status_change_tx(
+                        conn,
+                        tenant_id,
+                        &Actor::System,
+                        &updated,
+                        subscription.subscription.status,
+                    )
+                    .await?;
 
                     Ok(updated)
                 }
                 .scope_boxed()
             })
-            .await?;
-
-        let subscription: Subscription = db_subscription.try_into()?;
-
-        Ok(subscription)
+            .await
     }
 
     /// Activates a subscription after payment has been confirmed.
@@ -153,6 +159,13 @@ impl Services {
     ) -> Result<(), Report<StoreError>> {
         let has_trial = params.trial_duration.is_some_and(|d| d > 0);
 
+        let previous_status =
+            SubscriptionRow::get_subscription_by_id(conn, tenant_id, *subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .subscription
+                .status;
+
         let (status, current_period_start, current_period_end, next_cycle_action) =
             if has_trial && params.is_paid_trial {
                 // Paid trial: use normal billing cycle, trial end handled via scheduled event
@@ -236,6 +249,19 @@ impl Services {
                 .map_err(Into::<Report<StoreError>>::into)?;
         }
 
-        Ok(())
+        let subscription: Subscription =
+            SubscriptionRow::get_subscription_by_id(conn, tenant_id, *subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?;
+
+        self.record_status_change_tx(
+            conn,
+            *tenant_id,
+            &Actor::System,
+            &subscription,
+            previous_status.into(),
+        )
+        .await
     }
 }
@@ -5,6 +5,7 @@ use crate::domain::enums::SubscriptionFeeBillingPeriod;
 use crate::domain::enums::{CreditType, InvoicePaymentStatus, InvoiceStatusEnum};
 use crate::domain::invoice_lines::LineItem;
 use crate::domain::invoices::Invoice;
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionAmendedEvent};
 use crate::domain::price_components::{PriceComponentNewInternal, PriceEntry, ProductRef};
 use crate::domain::prices::FeeStructure;
 use crate::domain::scheduled_events::{
@@ -22,7 +23,7 @@ use crate::domain::subscription_components::{
     SubscriptionComponent, SubscriptionComponentNew, SubscriptionComponentNewInternal,
     SubscriptionFee,
 };
-use crate::domain::{Product, SubscriptionDetails};
+use crate::domain::{Product, Subscription, SubscriptionDetails};
 use crate::errors::StoreError;
 use crate::repositories::SubscriptionInterface;
 use crate::repositories::credit_notes::{
@@ -278,7 +279,7 @@ impl Services {
                             &mut conn,
                             tenant_id,
                             &sub_details.subscription,
-                            &sub_details.customer,
+                            sub_details.billed_customer(),
                             &charge_proration,
                         )
                         .await?
@@ -460,7 +461,7 @@ impl Services {
                                 conn,
                                 tenant_id,
                                 &sub_details.subscription,
-                                &sub_details.customer,
+                                sub_details.billed_customer(),
                                 &charge_proration,
                             )
                             .await?;
@@ -517,22 +518,28 @@ impl Services {
                     )
                     .await?;
 
-                    let activity = Activity::new(
-                        ActivityType::SubscriptionAmended,
-                        EntityType::Subscription,
-                        subscription_id.as_uuid(),
-                    )
-                    .with_metadata(amendment_metadata(
-                        &resolved.component_close,
-                        &component_inserts,
-                        &resolved.addon_close,
-                        &addon_inserts,
-                        change_date,
-                        adjustment_invoice_id,
-                    ))
-                    .agg_customer(sub_details.customer.id);
+                    let subscription: Subscription =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?
+                            .try_into()?;
                     self.store
-                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .record_tx(
+                            conn,
+                            tenant_id,
+                            actor,
+                            AuditInput::Outbox(OutboxEvent::subscription_amended(
+                                SubscriptionAmendedEvent {
+                                    subscription: subscription.into(),
+                                    effective_date: change_date,
+                                    components_closed: resolved.component_close.len(),
+                                    components_added: component_inserts.len(),
+                                    addons_closed: resolved.addon_close.len(),
+                                    addons_added: addon_inserts.len(),
+                                    adjustment_invoice_id,
+                                },
+                            )),
+                        )
                         .await?;
 
                     Ok(ImmediateAmendmentResult {
@@ -772,6 +779,7 @@ impl Services {
                 metric_id: orig.metric_id,
                 description: None,
                 group_by_dimensions: None,
+                metadata: Default::default(),
             });
         }
         if lines.is_empty() {
@@ -2212,6 +2220,7 @@ mod tests {
                 metric_id: None,
                 description: None,
                 group_by_dimensions: None,
+                metadata: Default::default(),
             }
         }
 
@@ -0,0 +1,379 @@
+use crate::StoreResult;
+use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::enums::{BillingPeriodEnum, PeriodLength};
+use crate::domain::subscription_changes::{
+    BillingAnchorChangePreview, BillingAnchorChangeResult, ProrationResult, ProrationSummary,
+    RemovedComponent,
+};
+use crate::domain::subscription_components::SubscriptionFee;
+use crate::domain::{SubscriptionDetails, SubscriptionStatusEnum};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::services::Services;
+use crate::services::subscriptions::proration::calculate_anchor_change_proration;
+use crate::services::subscriptions::utils::scale_fee;
+use crate::store::PgConn;
+use crate::utils::periods::{calculate_advance_period_range, subtract_periods};
+use chrono::NaiveDate;
+use common_domain::ids::{BaseId, SubscriptionId, TenantId};
+use diesel_models::slot_transactions::SlotTransactionRow;
+use diesel_models::subscriptions::{SubscriptionCycleRowPatch, SubscriptionRow};
+use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
+
+/// Validated anchor change: the current period `[period_start, period_end)` becomes
+/// the stub `[period_start, new_period_end)`.
+struct PreparedAnchorChange {
+    subscription_details: SubscriptionDetails,
+    billing_day_anchor: u16,
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    new_period_end: NaiveDate,
+    proration: ProrationResult,
+    days_remaining: u32,
+    days_in_period: u32,
+}
+
+impl PreparedAnchorChange {
+    fn summary(&self) -> ProrationSummary {
+        let credits_total_cents: i64 = self
+            .proration
+            .lines
+            .iter()
+            .filter(|l| l.is_credit)
+            .map(|l| l.amount_cents)
+            .sum();
+
+        ProrationSummary {
+            credits_total_cents,
+            charges_total_cents: self
+                .proration
+                .lines
+                .iter()
+                .filter(|l| !l.is_credit)
+                .map(|l| l.amount_cents)
+                .sum(),
+            net_amount_cents: self.proration.net_amount_cents,
+            // No overrides involved, nothing to net
+            net_credit_cents: credits_total_cents,
+            arrears_charge_cents: 0,
+            proration_factor: self.proration.proration_factor,
+            days_remaining: self.days_remaining,
+            days_in_period: self.days_in_period,
+        }
+    }
+}
+
+impl Services {
+    pub(in crate::services) async fn preview_billing_anchor_change(
+        &self,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        billing_day_anchor: u16,
+    ) -> StoreResult<BillingAnchorChangePreview> {
+        let mut conn = self.store.get_conn().await?;
+        let today = chrono::Utc::now().naive_utc().date();
+
+        let prepared = self
+            .prepare_billing_anchor_change(
+                &mut conn,
+                tenant_id,
+                subscription_id,
+                billing_day_anchor,
+                today,
+            )
+            .await?;
+
+        Ok(BillingAnchorChangePreview {
+            billing_day_anchor: prepared.billing_day_anchor,
+            current_period_start: prepared.period_start,
+            current_period_end: prepared.period_end,
+            new_period_end: prepared.new_period_end,
+            proration: prepared.summary(),
+        })
+    }
+
+    /// Moves the billing day of an active subscription. The current period is cut
+    /// short or extended up to the first occurrence of the new billing day, and the
+    /// difference is settled right away on an adjustment invoice.
+    pub(in crate::services) async fn change_billing_anchor(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        billing_day_anchor: u16,
+    ) -> StoreResult<BillingAnchorChangeResult> {
+        let today = chrono::Utc::now().naive_utc().date();
+
+        self.store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let prepared = self
+                        .prepare_billing_anchor_change(
+                            conn,
+                            tenant_id,
+                            subscription_id,
+                            billing_day_anchor,
+                            today,
+                        )
+                        .await?;
+                    let details = &prepared.subscription_details;
+
+                    let adjustment_invoice_id = if prepared.proration.net_amount_cents != 0 {
+                        let invoice = self
+                            .create_adjustment_invoice(
+                                conn,
+                                tenant_id,
+                                &details.subscription,
+                                details.billed_customer(),
+                                &prepared.proration,
+                            )
+                            .await?;
+                        if let Some(inv) = &invoice {
+                            self.finalize_invoice_tx(
+                                conn,
+                                &Actor::System,
+                                inv.id,
+                                tenant_id,
+                                false,
+                                &None,
+                            )
+                            .await?;
+                        }
+                        invoice.map(|inv| inv.id)
+                    } else {
+                        None
+                    };
+
+                    // Billing restarts from the current period start, so the stub is billed
+                    // in arrears as a single period and the next advance period is a full one.
+                    SubscriptionCycleRowPatch {
+                        id: subscription_id,
+                        tenant_id,
+                        status: None,
+                        cycle_index: None,
+                        next_cycle_action: None,
+                        current_period_start: None,
+                        current_period_end: Some(Some(prepared.new_period_end)),
+                        pending_checkout: None,
+                        processing_started_at: None,
+                        billing_start_date: Some(prepared.period_start),
+                        billing_day_anchor: Some(prepared.billing_day_anchor as i16),
+                    }
+                    .patch(conn)
+                    .await?;
+
+                    let activity = Activity::new(
+                        ActivityType::SubscriptionBillingAnchorChanged,
+                        EntityType::Subscription,
+                        subscription_id.as_uuid(),
+                    )
+                    .with_metadata(serde_json::json!({
+                        "previous_billing_day_anchor": details.subscription.billing_day_anchor,
+                        "new_billing_day_anchor": prepared.billing_day_anchor,
+                        "new_period_end": prepared.new_period_end.to_string(),
+                    }))
+                    .agg_customer(details.customer.id);
+                    self.store
+                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .await?;
+
+                    log::info!(
+                        "Changed billing anchor of subscription {} to {}: period [{}, {}], net_proration={}",
+                        subscription_id,
+                        prepared.billing_day_anchor,
+                        prepared.period_start,
+                        prepared.new_period_end,
+                        prepared.proration.net_amount_cents,
+                    );
+
+                    Ok(BillingAnchorChangeResult {
+                        adjustment_invoice_id,
+                        new_period_end: prepared.new_period_end,
+                    })
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    async fn prepare_billing_anchor_change(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        billing_day_anchor: u16,
+        change_date: NaiveDate,
+    ) -> StoreResult<PreparedAnchorChange> {
+        let subscription_details = self
+            .store
+            .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
+            .await?;
+        let subscription = &subscription_details.subscription;
+
+        if subscription.status != SubscriptionStatusEnum::Active {
+            return Err(Report::new(StoreError::InvalidArgument(
+                "Only active subscriptions can change their billing day".to_string(),
+            )));
+        }
+
+        if !(1..=31).contains(&billing_day_anchor) {
+            return Err(Report::new(StoreError::InvalidArgument(format!(
+                "Invalid billing day {billing_day_anchor}, expected a day between 1 and 31"
+            ))));
+        }
+
+        if billing_day_anchor == subscription.billing_day_anchor {
+            return Err(Report::new(StoreError::InvalidArgument(
+                "The subscription is already billed on that day".to_string(),
+            )));
+        }
+
+        if !matches!(subscription.period.length(), PeriodLength::Months(_)) {
+            return Err(Report::new(StoreError::InvalidArgument(
+                "Daily and weekly subscriptions have no billing day".to_string(),
+            )));
+        }
+
+        if !subscription_details.pending_events.is_empty() {
+            return Err(Report::new(StoreError::InvalidArgument(
+                "Cancel the scheduled changes of the subscription before changing its billing day"
+                    .to_string(),
+            )));
+        }
+
+        let period_start = subscription.current_period_start;
+        let period_end = subscription.current_period_end.ok_or_else(|| {
+            Report::new(StoreError::InvalidArgument(
+                "Subscription has no current_period_end".to_string(),
+            ))
+        })?;
+
+        // The stub ends on the first occurrence of the new billing day, whatever the cadence
+        let new_period_end = calculate_advance_period_range(
+            period_start,
+            u32::from(billing_day_anchor),
+            true,
+            &BillingPeriodEnum::Monthly,
+        )
+        .end;
+
+        if new_period_end <= change_date {
+            return Err(Report::new(StoreError::InvalidArgument(format!(
+                "Billing day {billing_day_anchor} has already passed in the current period, change it after the next renewal on {period_end}"
+            ))));
+        }
+
+        if subscription
+            .end_date
+            .is_some_and(|end_date| new_period_end > end_date)
+        {
+            return Err(Report::new(StoreError::InvalidArgument(
+                "The new billing period would end after the subscription end date".to_string(),
+            )));
+        }
+
+        let new_full_period_start = subtract_periods(
+            new_period_end,
+            1,
+            subscription.period.length(),
+            u32::from(billing_day_anchor),
+        )
+        .ok_or_else(|| {
+            Report::new(StoreError::InvalidArgument(
+                "Failed to compute the new billing period".to_string(),
+            ))
+        })?;
+
+        let mut components: Vec<RemovedComponent> = subscription_details
+            .price_components
+            .iter()
+            .filter(|c| {
+                c.effective_from <= change_date && c.effective_to.is_none_or(|to| to > change_date)
+            })
+            .map(|c| RemovedComponent {
+                name: c.name.clone(),
+                current_fee: c.fee.clone(),
+                current_period: c.period,
+                net_key: None,
+            })
+            .chain(
+                subscription_details
+                    .add_ons
+                    .iter()
+                    .filter(|a| {
+                        a.effective_from <= change_date
+                            && a.effective_to.is_none_or(|to| to > change_date)
+                    })
+                    .map(|a| RemovedComponent {
+                        name: a.name.clone(),
+                        current_fee: scale_fee(&a.fee, a.quantity),
+                        current_period: a.period,
+                        net_key: None,
+                    }),
+            )
+            .collect();
+
+        // Seat-based fees carry their initial count: bill the seats currently active
+        for component in &mut components {
+            if let SubscriptionFee::Slot {
+                unit,
+                initial_slots,
+                ..
+            } = &mut component.current_fee
+            {
+                *initial_slots = SlotTransactionRow::fetch_by_subscription_id_and_unit_locked(
+                    conn,
+                    tenant_id,
+                    subscription_id,
+                    unit.clone(),
+                    None,
+                )
+                .await
+                .map(|r| r.current_active_slots as u32)
+                .unwrap_or(0);
+            }
+        }
+
+        let precision =
+            crate::constants::Currencies::resolve_currency_precision(&subscription.currency)
+                .unwrap_or(2);
+
+        let proration = calculate_anchor_change_proration(
+            &components,
+            period_start,
+            period_end,
+            new_period_end,
+            new_full_period_start,
+            change_date,
+            precision,
+        );
+
+        let (days_remaining, days_in_period) = if new_period_end <= period_end {
+            (
+                (period_end - new_period_end).num_days() as u32,
+                (period_end - period_start).num_days() as u32,
+            )
+        } else {
+            (
+                (new_period_end - period_end).num_days() as u32,
+                (new_period_end - new_full_period_start).num_days() as u32,
+            )
+        };
+
+        Ok(PreparedAnchorChange {
+            subscription_details,
+            billing_day_anchor,
+            period_start,
+            period_end,
+            new_period_end,
+            proration,
+            days_remaining,
+            days_in_period,
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use crate::StoreResult;
-use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::entity_activity::{Actor, AuditInput};
 use crate::domain::enums::SubscriptionEventType;
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionCancellationScheduledEvent};
 use crate::domain::{Subscription, SubscriptionDetails};
 use crate::errors::StoreError;
 use chrono::{NaiveDate, NaiveTime};
@@ -17,7 +18,7 @@ use diesel_models::subscriptions::SubscriptionRow;
 // and even within it's probably still unsafe no ? Ex: creating components against a wrong subscription within a different tenant
 use crate::domain::scheduled_events::{ScheduledEventData, ScheduledEventNew};
 use crate::services::Services;
-use common_domain::ids::{BaseId, SubscriptionId, TenantId};
+use common_domain::ids::{SubscriptionId, TenantId};
 use diesel_models::scheduled_events::ScheduledEventRow;
 
 impl Services {
@@ -29,8 +30,7 @@ impl Services {
         reason: Option<String>,
         effective_at: CancellationEffectiveAt,
     ) -> StoreResult<Subscription> {
-        let db_subscription = self
-            .store
+        self.store
             .transaction(|conn| {
                 let actor = &actor;
                 let reason = reason.clone();
@@ -39,7 +39,6 @@ impl Services {
                         .store
                         .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
                         .await?;
-                    let customer_id = subscription.subscription.customer_id;
                     let reason_for_audit = reason.clone();
 
                     // Cancel all pending lifecycle events before scheduling the new cancellation
@@ -75,10 +74,11 @@ impl Services {
                         )
                         .await?;
 
-                    let res =
+                    let res: Subscription =
                         SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
                             .await
-                            .map_err(Into::<Report<StoreError>>::into)?;
+                            .map_err(Into::<Report<StoreError>>::into)?
+                            .try_into()?;
 
                     let mrr = subscription.subscription.mrr_cents;
 
@@ -98,28 +98,25 @@ impl Services {
                         .await
                         .map_err(Into::<Report<StoreError>>::into)?;
 
-                    let activity = {
-                        let mut a = Activity::new(
-                            ActivityType::SubscriptionCancellationScheduled,
-                            EntityType::Subscription,
-                            subscription_id.as_uuid(),
-                        )
-                        .agg_customer(customer_id);
-                        if let Some(reason) = reason_for_audit {
-                            a = a.with_metadata(serde_json::json!({ "reason": reason }));
-                        }
-                        a
-                    };
                     self.store
-                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .record_tx(
+                            conn,
+                            tenant_id,
+                            actor,
+                            AuditInput::Outbox(OutboxEvent::subscription_cancellation_scheduled(
+                                SubscriptionCancellationScheduledEvent {
+                                    subscription: res.clone().into(),
+                                    effective_date: billing_end_date,
+                                    reason: reason_for_audit,
+                                },
+                            )),
+                        )
                         .await?;
 
                     Ok(res)
                 }
                 .scope_boxed()
             })
-            .await?;
-
-        db_subscription.try_into()
+            .await
     }
 }
@@ -1,6 +1,6 @@
 use crate::StoreResult;
-use crate::domain::SubscriptionStatusEnum;
 use crate::domain::subscription_trial::{EffectivePlanInfo, EffectivePlanSource};
+use crate::domain::{Customer, Price, PriceComponent, SubscriptionStatusEnum};
 use crate::services::Services;
 use crate::store::PgConn;
 use common_domain::ids::TenantId;
@@ -12,7 +12,8 @@ impl Services {
     /// Resolves the effective plan for a subscription based on its trial status.
     ///
     /// The effective plan determines which plan's features/pricing should apply:
-    /// - During active trial with `trialing_plan_id` set: use the trialing plan
+    /// - During active trial with `trialing_plan_id` set: use the trialing plan, the one
+    ///   switched on the subscription if any, else the plan version's
     /// - Otherwise: use the subscription's original plan
     ///
     /// Note: The subscription always keeps its original `plan_version_id` - this method
@@ -36,7 +37,9 @@ impl Services {
 
         // Only during TrialActive do we potentially use a different plan
         if status == SubscriptionStatusEnum::TrialActive
-            && let Some(trialing_plan_id) = plan_version.trialing_plan_id
+            && let Some(trialing_plan_id) = subscription
+                .trialing_plan_id
+                .or(plan_version.trialing_plan_id)
             && let Ok(trialing_plan_with_version) = PlanRow::get_with_version_by_id(
                 conn,
                 trialing_plan_id,
@@ -76,3 +79,27 @@ impl Services {
             .await
     }
 }
+
+/// Resolves the price variants of the plan components for `customer`: each price keeps its id,
+/// with the matching variant pricing in place of the base pricing. Applied before computing
+/// subscription fees, so that subscription creation, checkout and plan changes bill the
+/// localized price.
+pub(in crate::services) fn localize_price_components(
+    components: &mut [PriceComponent],
+    customer: &Customer,
+) {
+    localize_prices(
+        components.iter_mut().flat_map(|c| c.prices.iter_mut()),
+        customer,
+    );
+}
+
+pub(in crate::services) fn localize_prices<'a>(
+    prices: impl IntoIterator<Item = &'a mut Price>,
+    customer: &Customer,
+) {
+    for price in prices {
+        price.pricing = price.pricing_for(customer).clone();
+        price.variants.clear();
+    }
+}
@@ -6,6 +6,7 @@ use crate::domain::{
     PlanForSubscription, Price, PriceComponent, Product,
 };
 use crate::errors::StoreError;
+use crate::services::subscriptions::effective_plan::{localize_price_components, localize_prices};
 use crate::store::PgConn;
 use crate::{StoreResult, services::Services};
 use common_domain::ids::{
@@ -80,6 +81,9 @@ pub struct SubscriptionCreationContext {
     pub price_components_by_plan_version: HashMap<PlanVersionId, Vec<PriceComponent>>,
     pub products_by_id: HashMap<ProductId, Product>,
     pub addon_prices_by_id: HashMap<PriceId, Price>,
+    /// Plan components and add-on prices of the customers matching a price variant, with the
+    /// variant pricing in place of the base pricing. Other customers use the maps above.
+    pub localized_pricing: HashMap<CustomerId, LocalizedPricing>,
     pub all_add_ons: Vec<AddOn>,
     pub all_coupons: Vec<Coupon>,
     pub invoicing_entity_providers: Vec<InvoicingEntityProviderSensitive>,
@@ -87,7 +91,73 @@ pub struct SubscriptionCreationContext {
     pub resolved_custom_components: Vec<ResolvedCustomComponents>,
 }
 
+#[derive(Debug)]
+pub struct LocalizedPricing {
+    pub price_components_by_plan_version: HashMap<PlanVersionId, Vec<PriceComponent>>,
+    pub addon_prices_by_id: HashMap<PriceId, Price>,
+}
+
+impl LocalizedPricing {
+    /// Localizes the batch pricing once per customer, only for the customers that match a
+    /// variant: everybody else is billed the base pricing as loaded.
+    fn for_customers(
+        customers: &[Customer],
+        price_components: &HashMap<PlanVersionId, Vec<PriceComponent>>,
+        addon_prices: &HashMap<PriceId, Price>,
+    ) -> HashMap<CustomerId, LocalizedPricing> {
+        let prices_with_variants: Vec<&Price> = price_components
+            .values()
+            .flatten()
+            .flat_map(|c| c.prices.iter())
+            .chain(addon_prices.values())
+            .filter(|p| !p.variants.is_empty())
+            .collect();
+
+        customers
+            .iter()
+            .filter(|customer| {
+                prices_with_variants
+                    .iter()
+                    .any(|p| p.variants.iter().any(|v| v.condition.matches(customer)))
+            })
+            .map(|customer| {
+                let mut price_components_by_plan_version = price_components.clone();
+                for components in price_components_by_plan_version.values_mut() {
+                    localize_price_components(components, customer);
+                }
+                let mut addon_prices_by_id = addon_prices.clone();
+                localize_prices(addon_prices_by_id.values_mut(), customer);
+
+                (
+                    customer.id,
+                    LocalizedPricing {
+                        price_components_by_plan_version,
+                        addon_prices_by_id,
+                    },
+                )
+            })
+            .collect()
+    }
+}
+
 impl SubscriptionCreationContext {
+    pub(crate) fn price_components_for(
+        &self,
+        customer: &Customer,
+    ) -> &HashMap<PlanVersionId, Vec<PriceComponent>> {
+        self.localized_pricing
+            .get(&customer.id)
+            .map_or(&self.price_components_by_plan_version, |l| {
+                &l.price_components_by_plan_version
+            })
+    }
+
+    pub(crate) fn addon_prices_for(&self, customer: &Customer) -> &HashMap<PriceId, Price> {
+        self.localized_pricing
+            .get(&customer.id)
+            .map_or(&self.addon_prices_by_id, |l| &l.addon_prices_by_id)
+    }
+
     pub(crate) fn get_invoicing_entity_providers_for_customer(
         &self,
         customer: &Customer,
@@ -167,12 +237,16 @@ impl Services {
             .resolve_custom_components(conn, batch, &price_components, &products_by_id, tenant_id)
             .await?;
 
+        let localized_pricing =
+            LocalizedPricing::for_customers(&customers, &price_components, &addon_prices_by_id);
+
         Ok(SubscriptionCreationContext {
             customers,
             plans,
             price_components_by_plan_version: price_components,
             products_by_id,
             addon_prices_by_id,
+            localized_pricing,
             all_add_ons: add_ons,
             all_coupons: coupons,
             invoicing_entity_providers: invoicing_entities,
@@ -421,6 +495,7 @@ impl Services {
             price_components_by_plan_version: HashMap::new(), // Not needed for quote conversion
             products_by_id: HashMap::new(),                   // Not needed for quote conversion
             addon_prices_by_id: HashMap::new(),               // Not needed for quote conversion
+            localized_pricing: HashMap::new(),                // Not needed for quote conversion
             all_add_ons: vec![],                              // Not needed for quote conversion
             all_coupons: coupons,
             invoicing_entity_providers: invoicing_entities,
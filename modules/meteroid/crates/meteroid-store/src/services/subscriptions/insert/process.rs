@@ -9,10 +9,11 @@ use crate::domain::scheduled_events::ScheduledEventNew;
 use crate::domain::slot_transactions::{SlotTransaction, SlotTransactionNewInternal};
 use crate::domain::{
     CreateSubscription, CreateSubscriptionAddOns, CreateSubscriptionComponents,
-    CreateSubscriptionFromQuote, CreatedSubscription, Customer, PaymentMethodsConfig,
-    SlotTransactionStatusEnum, SubscriptionActivationCondition, SubscriptionAddOnNew,
-    SubscriptionAddOnNewInternal, SubscriptionComponentNew, SubscriptionComponentNewInternal,
-    SubscriptionNew, SubscriptionNewEnriched, SubscriptionStatusEnum,
+    CreateSubscriptionFromQuote, CreatedSubscription, Customer, PastPeriodBilling,
+    PaymentMethodsConfig, SlotTransactionStatusEnum, SubscriptionActivationCondition,
+    SubscriptionAddOnNew, SubscriptionAddOnNewInternal, SubscriptionComponentNew,
+    SubscriptionComponentNewInternal, SubscriptionNew, SubscriptionNewEnriched,
+    SubscriptionStatusEnum,
 };
 use crate::errors::{StoreError, StoreErrorReport};
 use crate::jwt_claims::{ResourceAccess, generate_portal_token};
@@ -21,6 +22,7 @@ use crate::services::subscriptions::utils::{
     PendingMaterialization, apply_coupons, apply_coupons_without_validation, calculate_mrr,
     extract_billing_period, process_create_subscription_add_ons,
     process_create_subscription_components, process_create_subscription_coupons,
+    validate_billing_periods,
 };
 use crate::store::PgConn;
 use crate::utils::periods::{
@@ -68,8 +70,8 @@ pub struct ProcessedSubscription {
     /// When true, skip checkout session creation even if pending_checkout is true.
     /// Used for subscriptions created from checkout completion (SelfServe flow).
     skip_checkout_session: bool,
-    /// When true, skip billing for this subscription (migration mode).
-    skip_past_invoices: bool,
+    /// Migration mode: past periods are imported or skipped instead of billed.
+    past_period_billing: PastPeriodBilling,
     entitlements: Vec<crate::domain::entitlements::EntitlementSpec>,
 }
 
@@ -131,10 +133,16 @@ impl Services {
                 .ok_or(Report::new(StoreError::InsertError))
                 .attach("Missing resolved custom components for batch index")?;
 
-            let (components, pending_materializations) =
-                self.process_components(price_components, subscription, context, resolved, plan)?;
+            let (components, pending_materializations) = self.process_components(
+                price_components,
+                subscription,
+                context,
+                resolved,
+                customer,
+                plan,
+            )?;
             let (subscription_add_ons, pending_addon_materializations) =
-                self.process_add_ons(add_ons, subscription, context, plan)?;
+                self.process_add_ons(add_ons, subscription, context, customer, plan)?;
 
             let slot_transactions = process_slot_transactions(
                 &components,
@@ -240,6 +248,7 @@ impl Services {
         tenant_id: TenantId,
         quote_id: Option<QuoteId>,
     ) -> StoreResult<ProcessedSubscription> {
+        validate_billing_periods(&sub.components, &sub.add_ons)?;
         let period = extract_billing_period(&sub.components, &sub.add_ons);
 
         let plan = context
@@ -268,19 +277,22 @@ impl Services {
             )));
         }
 
-        // Validate: skip_past_invoices only makes sense with a past start_date
-        if sub.subscription.skip_past_invoices && sub.subscription.start_date >= now.date() {
+        let migration_mode = sub.subscription.past_period_billing.is_migration();
+
+        // Validate: importing or skipping past periods only makes sense with a past start_date
+        if migration_mode && sub.subscription.start_date >= now.date() {
             return Err(Report::new(StoreError::InvalidArgument(
-                "skip_past_invoices requires a past start_date".to_string(),
+                "Importing or skipping past periods requires a past start_date".to_string(),
             )));
         }
 
-        // Validate: skip_past_invoices only works with OnStart activation
-        if sub.subscription.skip_past_invoices
+        // Validate: migration mode only works with OnStart activation
+        if migration_mode
             && sub.subscription.activation_condition != SubscriptionActivationCondition::OnStart
         {
             return Err(Report::new(StoreError::InvalidArgument(
-                "skip_past_invoices requires activation_condition to be OnStart".to_string(),
+                "Importing or skipping past periods requires activation_condition to be OnStart"
+                    .to_string(),
             )));
         }
 
@@ -336,8 +348,8 @@ impl Services {
 
         if sub.subscription.activation_condition == SubscriptionActivationCondition::OnStart {
             if sub.subscription.start_date <= now.date() {
-                // Migration mode: skip past billing, set up subscription at current point
-                if sub.subscription.skip_past_invoices {
+                // Migration mode: no past billing, set up subscription at current point
+                if migration_mode {
                     imported_at = Some(now);
 
                     // Calculate effective billing start (post-trial for free trials)
@@ -368,8 +380,8 @@ impl Services {
                         // end_date is already in the past: complete the subscription immediately.
                         // Checked before trial state — a subscription past its end_date is always
                         // Completed, regardless of whether a trial would otherwise still be active.
-                        // No cycle worker involvement needed — billing was intentionally skipped
-                        // (skip_past_invoices = true), and there is no current period to charge.
+                        // No cycle worker involvement needed — past periods are imported or skipped
+                        // (migration mode), and there is no current period to charge.
                         let last_period = find_period_containing_date(
                             effective_billing_start,
                             end_date,
@@ -619,7 +631,7 @@ impl Services {
             pending_materializations: sub.pending_materializations.clone(),
             pending_addon_materializations: sub.pending_addon_materializations.clone(),
             skip_checkout_session: sub.subscription.skip_checkout_session,
-            skip_past_invoices: sub.subscription.skip_past_invoices,
+            past_period_billing: sub.subscription.past_period_billing,
             entitlements: sub.entitlements.clone(),
         })
     }
@@ -630,6 +642,7 @@ impl Services {
         subscription: &SubscriptionNew,
         context: &SubscriptionCreationContext,
         resolved: &super::context::ResolvedCustomComponents,
+        customer: &Customer,
         plan: &crate::domain::PlanForSubscription,
     ) -> Result<
         (
@@ -644,7 +657,7 @@ impl Services {
 
         process_create_subscription_components(
             components,
-            &context.price_components_by_plan_version,
+            context.price_components_for(customer),
             subscription,
             &context.products_by_id,
             resolved,
@@ -659,6 +672,7 @@ impl Services {
         add_ons: &Option<CreateSubscriptionAddOns>,
         subscription: &SubscriptionNew,
         context: &SubscriptionCreationContext,
+        customer: &Customer,
         plan: &crate::domain::PlanForSubscription,
     ) -> Result<
         (
@@ -675,7 +689,7 @@ impl Services {
             add_ons,
             &context.all_add_ons,
             &context.products_by_id,
-            &context.addon_prices_by_id,
+            context.addon_prices_for(customer),
             plan.product_family_id,
             &plan.currency,
             effective_from,
@@ -1044,8 +1058,12 @@ impl Services {
                         // Skip billing for migration mode subscriptions.
                         // The subscription is set up with current_period_end = today,
                         // so the cycle processing worker will pick it up and generate
-                        // the invoice for the next period.
-                        if proc.skip_past_invoices {
+                        // the invoice for the next period. Elapsed periods are recorded
+                        // as already-paid imported invoices when requested.
+                        if proc.past_period_billing.is_migration() {
+                            if proc.past_period_billing == PastPeriodBilling::ImportAsPaid {
+                                self.import_past_periods_tx(conn, tenant_id, sub.id).await?;
+                            }
                             continue;
                         }
 
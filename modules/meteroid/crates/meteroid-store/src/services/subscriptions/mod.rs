@@ -1,14 +1,20 @@
 pub(in crate::services) mod activate;
 pub(in crate::services) mod amendment;
+pub(in crate::services) mod billing_anchor;
 pub(in crate::services) mod cancel;
-mod effective_plan;
+pub(in crate::services) mod effective_plan;
 
 pub(in crate::services) mod insert;
+pub(in crate::services) mod pause;
 pub mod payment_resolution;
 pub(in crate::services) mod plan_change;
 pub mod proration;
+pub(in crate::services) mod reactivate;
+pub(in crate::services) mod renewal;
 pub(crate) mod slots;
+mod status;
 mod terminate;
+pub(in crate::services) mod trial;
 pub mod utils;
 
 pub use activate::PaymentActivationParams;
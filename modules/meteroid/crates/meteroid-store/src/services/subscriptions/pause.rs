@@ -0,0 +1,467 @@
+use crate::StoreResult;
+use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::outbox_event::OutboxEvent;
+use crate::domain::scheduled_events::{ScheduledEventData, ScheduledEventNew};
+use crate::domain::{PauseBilling, ResumeBillingAnchor, Subscription};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::repositories::subscriptions::{PauseEffectiveAt, PauseParams};
+use crate::services::{InvoiceBillingMode, Services};
+use crate::store::PgConn;
+use crate::utils::periods::calculate_advance_period_range;
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use common_domain::ids::{BaseId, SubscriptionId, TenantId};
+use diesel_models::enums::{CycleActionEnum, SubscriptionStatusEnum};
+use diesel_models::scheduled_events::ScheduledEventRow;
+use diesel_models::subscriptions::{SubscriptionCycleRowPatch, SubscriptionRow};
+use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
+
+impl Services {
+    /// Pauses an active subscription, now or at a later date (typically the end of
+    /// the current period), optionally scheduling its automatic resumption.
+    pub(in crate::services) async fn pause_subscription(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        effective_at: PauseEffectiveAt,
+        params: PauseParams,
+    ) -> StoreResult<Subscription> {
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?;
+
+                    if row.subscription.status != SubscriptionStatusEnum::Active {
+                        return Err(Report::new(StoreError::InvalidArgument(
+                            "Only active subscriptions can be paused".to_string(),
+                        )));
+                    }
+
+                    let pause_date = match effective_at {
+                        PauseEffectiveAt::EndOfBillingPeriod => {
+                            row.subscription.current_period_end.ok_or_else(|| {
+                                Report::new(StoreError::InvalidArgument(
+                                    "Subscription has no current billing period".to_string(),
+                                ))
+                            })?
+                        }
+                        PauseEffectiveAt::Date(date) => date,
+                    };
+
+                    validate_pause(&params, pause_date)?;
+
+                    // A pause replaces any pending plan change, pause or cancellation
+                    ScheduledEventRow::cancel_pending_lifecycle_events(
+                        conn,
+                        subscription_id,
+                        &tenant_id,
+                        "Replaced by subscription pause",
+                    )
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?;
+
+                    let today = chrono::Utc::now().naive_utc().date();
+
+                    if pause_date <= today {
+                        self.apply_pause(
+                            conn,
+                            actor,
+                            tenant_id,
+                            subscription_id,
+                            pause_date,
+                            &params,
+                        )
+                        .await?;
+                    } else {
+                        self.store
+                            .schedule_events(
+                                conn,
+                                vec![ScheduledEventNew {
+                                    subscription_id,
+                                    tenant_id,
+                                    scheduled_time: pause_date.and_time(NaiveTime::MIN),
+                                    event_data: ScheduledEventData::PauseSubscription {
+                                        billing: params.billing,
+                                        resume_on: params.resume_on,
+                                        resume_anchor: params.resume_anchor,
+                                    },
+                                    source: "edge".to_string(),
+                                    created_by_customer: false,
+                                }],
+                            )
+                            .await?;
+
+                        let activity = Activity::new(
+                            ActivityType::SubscriptionPauseScheduled,
+                            EntityType::Subscription,
+                            subscription_id.as_uuid(),
+                        )
+                        .agg_customer(row.subscription.customer_id)
+                        .with_metadata(serde_json::json!({
+                            "pause_date": pause_date,
+                            "billing": params.billing,
+                            "resume_on": params.resume_on,
+                        }));
+                        self.store
+                            .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                            .await?;
+                    }
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Resumes a paused subscription on `resume_on` (today if unset). A future date
+    /// schedules the resumption, replacing any pending one.
+    pub(in crate::services) async fn resume_subscription(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        resume_on: Option<NaiveDate>,
+        anchor: ResumeBillingAnchor,
+    ) -> StoreResult<Subscription> {
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?;
+
+                    if row.subscription.status != SubscriptionStatusEnum::Paused {
+                        return Err(Report::new(StoreError::InvalidArgument(
+                            "Only paused subscriptions can be resumed".to_string(),
+                        )));
+                    }
+
+                    let today = chrono::Utc::now().naive_utc().date();
+                    let resume_date = resume_on.unwrap_or(today);
+
+                    if resume_date > today {
+                        ScheduledEventRow::cancel_pending_resume_events(
+                            conn,
+                            subscription_id,
+                            &tenant_id,
+                            "Replaced by a new resumption",
+                        )
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?;
+
+                        self.store
+                            .schedule_events(
+                                conn,
+                                vec![ScheduledEventNew {
+                                    subscription_id,
+                                    tenant_id,
+                                    scheduled_time: resume_date.and_time(NaiveTime::MIN),
+                                    event_data: ScheduledEventData::ResumeSubscription { anchor },
+                                    source: "edge".to_string(),
+                                    created_by_customer: false,
+                                }],
+                            )
+                            .await?;
+
+                        let activity = Activity::new(
+                            ActivityType::SubscriptionResumeScheduled,
+                            EntityType::Subscription,
+                            subscription_id.as_uuid(),
+                        )
+                        .agg_customer(row.subscription.customer_id)
+                        .with_metadata(serde_json::json!({ "resume_on": resume_date }));
+                        self.store
+                            .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                            .await?;
+                    } else {
+                        self.apply_resume(
+                            conn,
+                            actor,
+                            tenant_id,
+                            subscription_id,
+                            resume_date,
+                            anchor,
+                        )
+                        .await?;
+                    }
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Puts the subscription in `Paused` as of `date`.
+    ///
+    /// Without billing, the cycles stop: the subscription is terminated as paused,
+    /// which bills what is owed up to `date`. When fixed fees keep being billed, only
+    /// the status changes and renewals go on.
+    pub(in crate::services) async fn apply_pause(
+        &self,
+        conn: &mut PgConn,
+        actor: &Actor,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        date: NaiveDate,
+        params: &PauseParams,
+    ) -> StoreResult<()> {
+        let previous_status =
+            SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .subscription
+                .status;
+
+        match params.billing {
+            PauseBilling::NoBilling => {
+                self.terminate_subscription(
+                    conn,
+                    tenant_id,
+                    subscription_id,
+                    date,
+                    SubscriptionStatusEnum::Paused,
+                )
+                .await?;
+            }
+            PauseBilling::KeepFixedFees => {
+                SubscriptionCycleRowPatch {
+                    id: subscription_id,
+                    tenant_id,
+                    status: Some(SubscriptionStatusEnum::Paused),
+                    next_cycle_action: None,
+                    current_period_start: None,
+                    current_period_end: None,
+                    cycle_index: None,
+                    pending_checkout: None,
+                    processing_started_at: None,
+                    billing_start_date: None,
+                    billing_day_anchor: None,
+                }
+                .patch(conn)
+                .await?;
+            }
+        }
+
+        if let Some(resume_on) = params.resume_on {
+            self.store
+                .schedule_events(
+                    conn,
+                    vec![ScheduledEventNew {
+                        subscription_id,
+                        tenant_id,
+                        scheduled_time: resume_on.and_time(NaiveTime::MIN),
+                        event_data: ScheduledEventData::ResumeSubscription {
+                            anchor: params.resume_anchor,
+                        },
+                        source: "system".to_string(),
+                        created_by_customer: false,
+                    }],
+                )
+                .await?;
+        }
+
+        let subscription: Subscription =
+            SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?;
+
+        self.store
+            .record_tx(
+                conn,
+                tenant_id,
+                actor,
+                AuditInput::Outbox(OutboxEvent::subscription_paused(
+                    subscription.clone().into(),
+                )),
+            )
+            .await?;
+
+        self.record_status_change_tx(
+            conn,
+            tenant_id,
+            actor,
+            &subscription,
+            previous_status.into(),
+        )
+        .await
+    }
+
+    /// Moves a paused subscription back to `Active` as of `date`.
+    ///
+    /// If its cycles were stopped, a new period starts on `date`, either prorated up
+    /// to the existing billing anchor or re-anchored on `date`, and is billed right away.
+    pub(in crate::services) async fn apply_resume(
+        &self,
+        conn: &mut PgConn,
+        actor: &Actor,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        date: NaiveDate,
+        anchor: ResumeBillingAnchor,
+    ) -> StoreResult<()> {
+        let row = SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?
+            .subscription;
+
+        if row.status != SubscriptionStatusEnum::Paused {
+            return Err(Report::new(StoreError::InvalidArgument(
+                "Only paused subscriptions can be resumed".to_string(),
+            )));
+        }
+
+        ScheduledEventRow::cancel_pending_resume_events(
+            conn,
+            subscription_id,
+            &tenant_id,
+            "Subscription resumed",
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+        if row.next_cycle_action.is_some() {
+            // Paused while still billing fixed fees: the cycles never stopped.
+            if anchor == ResumeBillingAnchor::ResetToResumeDate {
+                return Err(Report::new(StoreError::InvalidArgument(
+                    "Cannot re-anchor a subscription that kept billing while paused".to_string(),
+                )));
+            }
+
+            SubscriptionCycleRowPatch {
+                id: subscription_id,
+                tenant_id,
+                status: Some(SubscriptionStatusEnum::Active),
+                next_cycle_action: None,
+                current_period_start: None,
+                current_period_end: None,
+                cycle_index: None,
+                pending_checkout: None,
+                processing_started_at: None,
+                billing_start_date: None,
+                billing_day_anchor: None,
+            }
+            .patch(conn)
+            .await?;
+        } else {
+            if date < row.current_period_start {
+                return Err(Report::new(StoreError::InvalidArgument(format!(
+                    "Resume date must be on or after the pause date {}",
+                    row.current_period_start
+                ))));
+            }
+
+            let billing_day = match anchor {
+                ResumeBillingAnchor::Keep => row.billing_day_anchor as u32,
+                ResumeBillingAnchor::ResetToResumeDate => date.day(),
+            };
+            self.restart_cycles(conn, tenant_id, &row, date, billing_day)
+                .await?;
+        }
+
+        let subscription: Subscription =
+            SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?;
+
+        self.store
+            .record_tx(
+                conn,
+                tenant_id,
+                actor,
+                AuditInput::Outbox(OutboxEvent::subscription_resumed(
+                    subscription.clone().into(),
+                )),
+            )
+            .await?;
+
+        self.record_status_change_tx(conn, tenant_id, actor, &subscription, row.status.into())
+            .await
+    }
+
+    /// Starts a new period on `date` for a subscription whose cycles were stopped,
+    /// prorated up to `billing_day`, and bills it right away. Returns whether an invoice
+    /// was issued.
+    pub(in crate::services) async fn restart_cycles(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        row: &SubscriptionRow,
+        date: NaiveDate,
+        billing_day: u32,
+    ) -> StoreResult<bool> {
+        let period =
+            calculate_advance_period_range(date, billing_day, true, &row.period.clone().into());
+
+        // Setting the billing start to the restart date makes the invoice computation
+        // treat this period as a fresh start (prorated, nothing in arrears).
+        SubscriptionCycleRowPatch {
+            id: row.id,
+            tenant_id,
+            status: Some(SubscriptionStatusEnum::Active),
+            next_cycle_action: Some(Some(CycleActionEnum::RenewSubscription)),
+            current_period_start: Some(date),
+            current_period_end: Some(Some(period.end)),
+            cycle_index: Some(row.cycle_index.map_or(0, |i| i + 1)),
+            pending_checkout: None,
+            processing_started_at: Some(None),
+            billing_start_date: Some(date),
+            billing_day_anchor: Some(billing_day as i16),
+        }
+        .patch(conn)
+        .await?;
+
+        let invoice = self
+            .bill_subscription_tx(
+                conn,
+                tenant_id,
+                row.id,
+                InvoiceBillingMode::AwaitGracePeriodIfApplicable,
+            )
+            .await?;
+
+        Ok(invoice.is_some())
+    }
+}
+
+fn validate_pause(params: &PauseParams, pause_date: NaiveDate) -> StoreResult<()> {
+    if let Some(resume_on) = params.resume_on
+        && resume_on <= pause_date
+    {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Resume date must be after the pause date".to_string(),
+        )));
+    }
+
+    if params.billing == PauseBilling::KeepFixedFees
+        && params.resume_anchor == ResumeBillingAnchor::ResetToResumeDate
+    {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Cannot re-anchor a subscription that keeps billing while paused".to_string(),
+        )));
+    }
+
+    Ok(())
+}
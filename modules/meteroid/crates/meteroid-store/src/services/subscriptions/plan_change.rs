@@ -1,7 +1,7 @@
 use crate::StoreResult;
-use crate::domain::SubscriptionStatusEnum;
 use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
 use crate::domain::enums::SubscriptionFeeBillingPeriod;
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionPlanChangedEvent};
 use crate::domain::price_components::resolve_legacy_subscription_fee;
 use crate::domain::prices::{
     LegacyPricingData, Price, Pricing, extract_legacy_pricing, fee_type_billing_period,
@@ -19,10 +19,12 @@ use crate::domain::subscription_components::{
     ComponentParameterization, ComponentParameters, SubscriptionComponent,
     SubscriptionComponentNew, SubscriptionComponentNewInternal, SubscriptionFee,
 };
+use crate::domain::{Subscription, SubscriptionStatusEnum};
 use crate::errors::StoreError;
 use crate::repositories::SubscriptionInterface;
 use crate::repositories::entity_activity::EntityActivityInterface;
 use crate::services::Services;
+use crate::services::subscriptions::effective_plan::localize_prices;
 use crate::services::subscriptions::proration::{calculate_proration, detect_change_direction};
 use crate::services::subscriptions::utils::calculate_mrr;
 use crate::store::PgConn;
@@ -46,6 +48,8 @@ use diesel_models::subscription_components::{
 };
 use diesel_models::subscriptions::SubscriptionRow;
 use error_stack::Report;
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
 use scoped_futures::ScopedFutureExt;
 use std::collections::HashMap;
 
@@ -121,7 +125,7 @@ async fn load_validated_plan_change_context(
         ))));
     }
 
-    let target_components = load_target_components_with_prices(
+    let mut target_components = load_target_components_with_prices(
         conn,
         tenant_id,
         new_plan_version_id,
@@ -129,6 +133,12 @@ async fn load_validated_plan_change_context(
     )
     .await?;
 
+    for component in &mut target_components {
+        if let TargetPricing::V2 { prices, .. } = &mut component.pricing {
+            localize_prices(prices.iter_mut(), &subscription_details.customer);
+        }
+    }
+
     let products = load_products_for_components(
         conn,
         tenant_id,
@@ -243,6 +253,38 @@ impl Services {
             .await
     }
 
+    /// Builds the `ApplyPlanChange` data moving a subscription onto `new_plan_version_id`,
+    /// with the target components parameterized by default.
+    pub(in crate::services) async fn build_default_plan_change_tx(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        new_plan_version_id: PlanVersionId,
+    ) -> StoreResult<ScheduledEventData> {
+        let ctx = load_validated_plan_change_context(
+            conn,
+            &self.store,
+            tenant_id,
+            subscription_id,
+            new_plan_version_id,
+        )
+        .await?;
+
+        let component_mappings = build_component_mappings(
+            &ctx.subscription_details.price_components,
+            &ctx.target_components,
+            &ctx.products,
+            &[],
+        )?;
+
+        Ok(ScheduledEventData::ApplyPlanChange {
+            source_plan_version_id: Some(ctx.subscription_details.subscription.plan_version_id),
+            new_plan_version_id,
+            component_mappings,
+        })
+    }
+
     pub(in crate::services) async fn preview_plan_change(
         &self,
         subscription_id: SubscriptionId,
@@ -463,7 +505,9 @@ impl Services {
                         diesel_models::enums::ScheduledEventTypeEnum::ApplyPlanChange
                             | diesel_models::enums::ScheduledEventTypeEnum::CancelSubscription
                             | diesel_models::enums::ScheduledEventTypeEnum::PauseSubscription
+                            | diesel_models::enums::ScheduledEventTypeEnum::ResumeSubscription
                             | diesel_models::enums::ScheduledEventTypeEnum::ApplyAmendment
+                            | diesel_models::enums::ScheduledEventTypeEnum::ApplyPriceChange
                     ) {
                         return Err(Report::new(StoreError::InvalidArgument(
                             "This event type cannot be cancelled".to_string(),
@@ -479,7 +523,7 @@ impl Services {
                         )));
                     }
 
-                    // PauseSubscription has no dedicated "undo" variant; skip the audit row.
+                    // Pause, resume and price-change events have no dedicated "undo" variant; skip the audit row.
                     let activity_type = match event.event_type {
                         diesel_models::enums::ScheduledEventTypeEnum::ApplyPlanChange => {
                             Some(ActivityType::SubscriptionPlanChangeCancelled)
@@ -497,6 +541,20 @@ impl Services {
                         .await
                         .map_err(Into::<Report<StoreError>>::into)?;
 
+                    // Drop the churn recorded when the cancellation was scheduled
+                    if event.event_type
+                        == diesel_models::enums::ScheduledEventTypeEnum::CancelSubscription
+                    {
+                        diesel_models::subscription_events::SubscriptionEventRow::delete_unprocessed_by_event_type(
+                            conn,
+                            subscription_id,
+                            diesel_models::enums::SubscriptionEventType::Cancelled,
+                            event.scheduled_time.date(),
+                        )
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?;
+                    }
+
                     if let Some(activity_type) = activity_type {
                         let customer_id =
                             SubscriptionRow::get_customer_id(conn, &tenant_id, subscription_id)
@@ -565,7 +623,6 @@ impl Services {
                             change_date,
                         )
                         .await?;
-                    let customer_id = prepared.subscription_details.customer.id;
 
                     let is_free_trial = prepared.is_free_trial();
 
@@ -577,7 +634,7 @@ impl Services {
                                 conn,
                                 tenant_id,
                                 &prepared.subscription_details.subscription,
-                                &prepared.subscription_details.customer,
+                                prepared.subscription_details.billed_customer(),
                                 &prepared.proration,
                             )
                             .await?;
@@ -624,19 +681,17 @@ impl Services {
                         None
                     };
 
-                    let activity = Activity::new(
-                        ActivityType::SubscriptionPlanChanged,
-                        EntityType::Subscription,
-                        subscription_id.as_uuid(),
+                    let previous = &prepared.subscription_details.subscription;
+                    self.record_plan_change_tx(
+                        conn,
+                        tenant_id,
+                        actor,
+                        subscription_id,
+                        previous.plan_version_id,
+                        previous.status.clone(),
+                        change_date,
                     )
-                    .with_metadata(serde_json::json!({
-                        "new_plan_version_id": new_plan_version_id.to_string(),
-                        "change_date": change_date.to_string(),
-                    }))
-                    .agg_customer(customer_id);
-                    self.store
-                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
-                        .await?;
+                    .await?;
 
                     Ok(ImmediatePlanChangeResult {
                         adjustment_invoice_id,
@@ -1075,6 +1130,59 @@ impl Services {
 
         Ok(())
     }
+
+    /// Records the outbox events of an applied plan change: `SubscriptionPlanChanged`, then
+    /// `SubscriptionTrialEnded` and the status change when it moved the subscription out of trial.
+    #[allow(clippy::too_many_arguments)]
+    pub(in crate::services) async fn record_plan_change_tx(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        actor: &Actor,
+        subscription_id: SubscriptionId,
+        previous_plan_version_id: PlanVersionId,
+        previous_status: SubscriptionStatusEnum,
+        change_date: NaiveDate,
+    ) -> StoreResult<()> {
+        let subscription: Subscription =
+            SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?;
+
+        self.store
+            .record_tx(
+                conn,
+                tenant_id,
+                actor,
+                AuditInput::Outbox(OutboxEvent::subscription_plan_changed(
+                    SubscriptionPlanChangedEvent {
+                        subscription: subscription.clone().into(),
+                        previous_plan_version_id,
+                        change_date,
+                    },
+                )),
+            )
+            .await?;
+
+        if previous_status == SubscriptionStatusEnum::TrialActive
+            && subscription.status != SubscriptionStatusEnum::TrialActive
+        {
+            self.store
+                .record_tx(
+                    conn,
+                    tenant_id,
+                    actor,
+                    AuditInput::Outbox(OutboxEvent::subscription_trial_ended(
+                        subscription.clone().into(),
+                    )),
+                )
+                .await?;
+        }
+
+        self.record_status_change_tx(conn, tenant_id, actor, &subscription, previous_status)
+            .await
+    }
 }
 
 /// Intermediate struct holding validated and computed data from the prepare phase.
@@ -1192,6 +1300,7 @@ impl PreparedPlanChange {
             trial_config: None,
             invoicing_entity: self.subscription_details.invoicing_entity.clone(),
             customer: self.subscription_details.customer.clone(),
+            payer: self.subscription_details.payer.clone(),
             schedules: Vec::new(),
             applied_coupons: self.subscription_details.applied_coupons.clone(),
             metrics: self.subscription_details.metrics.clone(),
@@ -1729,9 +1838,11 @@ pub(crate) async fn calculate_components_mrr_with_slots(
             } => {
                 let count = slot_counts.get(unit).copied().unwrap_or(0);
                 let rate_cents = unit_rate.to_subunit_opt(precision).unwrap_or(0);
-                let period_months = i64::from(c.period.as_months());
-                if period_months > 0 {
-                    total_mrr += (count * rate_cents) / period_months;
+                if let Some(length) = c.period.length() {
+                    total_mrr += length
+                        .to_monthly(Decimal::from(count * rate_cents))
+                        .to_i64()
+                        .unwrap_or(0);
                 }
             }
             _ => {
@@ -73,18 +73,6 @@ pub(crate) fn fee_instance_count(fee: &SubscriptionFee) -> rust_decimal::Decimal
     }
 }
 
-/// Nominal length, in days, of a billing period. Used to prorate components
-/// whose billing cadence differs from the subscription's current period.
-fn nominal_period_days(period: &SubscriptionFeeBillingPeriod) -> f64 {
-    match period {
-        SubscriptionFeeBillingPeriod::Monthly => 30.0,
-        SubscriptionFeeBillingPeriod::Quarterly => 91.0,
-        SubscriptionFeeBillingPeriod::Semiannual => 182.0,
-        SubscriptionFeeBillingPeriod::Annual => 365.0,
-        SubscriptionFeeBillingPeriod::OneTime => 0.0,
-    }
-}
-
 /// Proration factor for a single component.
 ///
 /// When the component bills on the same cadence as the subscription's current
@@ -99,10 +87,9 @@ fn component_proration_factor(
     days_in_period: f64,
     base_factor: f64,
 ) -> f64 {
-    let nominal = nominal_period_days(period);
-    if nominal <= 0.0 {
+    let Some(nominal) = period.length().map(|l| f64::from(l.nominal_days())) else {
         return base_factor;
-    }
+    };
     // Treat the component as aligned with the subscription period when the
     // current period length is within 25% of the component's nominal length.
     if (days_in_period - nominal).abs() <= nominal * 0.25 {
@@ -331,6 +318,69 @@ pub fn calculate_proration(
     }
 }
 
+/// Proration for moving the billing anchor: the current period `[period_start,
+/// period_end)` becomes a stub ending on `new_period_end`.
+///
+/// A shorter period credits the advance-billed components for the days cut off,
+/// against the current period. A longer one charges the extra days, against the
+/// full period ending on `new_period_end` (starting on `new_full_period_start`),
+/// as those days belong to the new cadence. One-time fees are never prorated.
+pub fn calculate_anchor_change_proration(
+    components: &[RemovedComponent],
+    period_start: NaiveDate,
+    period_end: NaiveDate,
+    new_period_end: NaiveDate,
+    new_full_period_start: NaiveDate,
+    change_date: NaiveDate,
+    precision: u8,
+) -> ProrationResult {
+    let recurring: Vec<RemovedComponent> = components
+        .iter()
+        .filter(|c| !matches!(c.current_fee, SubscriptionFee::OneTime { .. }))
+        .cloned()
+        .collect();
+
+    let result = if new_period_end <= period_end {
+        calculate_proration(
+            &[],
+            &[],
+            &recurring,
+            period_start,
+            period_end,
+            new_period_end,
+            precision,
+        )
+    } else {
+        let extended: Vec<AddedComponent> = recurring
+            .into_iter()
+            .map(|c| AddedComponent {
+                name: c.name,
+                fee: c.current_fee,
+                period: c.current_period,
+                net_key: None,
+                billed_component_id: None,
+                billed_add_on_id: None,
+                instance_quantity: None,
+            })
+            .collect();
+        calculate_proration(
+            &[],
+            &extended,
+            &[],
+            new_full_period_start,
+            new_period_end,
+            period_end,
+            precision,
+        )
+    };
+
+    ProrationResult {
+        change_date,
+        period_end: new_period_end,
+        ..result
+    }
+}
+
 /// Net override credit/charge pairs that share a `net_key` into a single line,
 /// so the adjustment invoice taxes the delta rather than a gross charge beside
 /// an untaxed credit. Lines without a key (genuine adds/removes) pass through.
@@ -943,6 +993,84 @@ mod tests {
         assert_eq!(line.unit_price, None);
     }
 
+    fn billed(name: &str, fee: SubscriptionFee) -> RemovedComponent {
+        RemovedComponent {
+            name: name.to_string(),
+            current_fee: fee,
+            current_period: monthly(),
+            net_key: None,
+        }
+    }
+
+    #[test]
+    fn test_anchor_change_shortening_period_credits_cut_off_days() {
+        // Period Jan 1 → Jan 31 (30 days), anchor moved to the 21st: 10 days cut off
+        let result = calculate_anchor_change_proration(
+            &[billed("Basic", rate_fee(300)), billed("Usage", usage_fee())],
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 21).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 21).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            2,
+        );
+
+        assert_eq!(result.lines.len(), 1);
+        assert!(result.lines[0].is_credit);
+        // -(30000 * 10/30)
+        assert_eq!(result.net_amount_cents, -10000);
+        assert_eq!(
+            result.change_date,
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap()
+        );
+        assert_eq!(
+            result.period_end,
+            NaiveDate::from_ymd_opt(2025, 1, 21).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_anchor_change_extending_period_charges_extra_days() {
+        // Period Feb 1 → Mar 1, anchor moved to the 11th: Mar 1 → Mar 11 is charged
+        // against the new full period Feb 11 → Mar 11 (28 days)
+        let result = calculate_anchor_change_proration(
+            &[billed("Seats", slot_fee(2, 140))],
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 11).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 11).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 5).unwrap(),
+            2,
+        );
+
+        assert_eq!(result.lines.len(), 1);
+        assert!(!result.lines[0].is_credit);
+        // 28000 * 10/28
+        assert_eq!(result.net_amount_cents, 10000);
+    }
+
+    #[test]
+    fn test_anchor_change_skips_one_time_fees() {
+        let result = calculate_anchor_change_proration(
+            &[billed(
+                "Setup",
+                SubscriptionFee::OneTime {
+                    rate: Decimal::new(500, 0),
+                    quantity: 1,
+                },
+            )],
+            NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 3, 11).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 11).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 2, 5).unwrap(),
+            2,
+        );
+
+        assert!(result.lines.is_empty());
+        assert_eq!(result.net_amount_cents, 0);
+    }
+
     fn line(name: &str, amount: i64, is_credit: bool, net_key: Option<&str>) -> ProrationLineItem {
         ProrationLineItem {
             name: name.to_string(),
@@ -0,0 +1,175 @@
+use crate::StoreResult;
+use crate::domain::Subscription;
+use crate::domain::entity_activity::{Actor, AuditInput};
+use crate::domain::enums::SubscriptionEventType;
+use crate::domain::outbox_event::OutboxEvent;
+use crate::errors::StoreError;
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::services::Services;
+use common_domain::ids::{SubscriptionId, TenantId};
+use diesel_models::enums::SubscriptionStatusEnum;
+use diesel_models::scheduled_events::ScheduledEventRow;
+use diesel_models::subscription_events::SubscriptionEventRow;
+use diesel_models::subscriptions::SubscriptionRow;
+use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
+use uuid::Uuid;
+
+/// How long after its termination a cancelled subscription can still be reactivated.
+pub const REACTIVATION_WINDOW_DAYS: i64 = 30;
+
+impl Services {
+    /// Reverts a cancellation.
+    ///
+    /// A pending cancellation is simply dropped. A subscription cancelled less than
+    /// [`REACTIVATION_WINDOW_DAYS`] ago is restarted today on its billing anchor,
+    /// keeping its history and coupons.
+    pub(in crate::services) async fn reactivate_subscription(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+    ) -> StoreResult<Subscription> {
+        self.store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?
+                            .subscription;
+
+                    let today = chrono::Utc::now().naive_utc().date();
+                    let previous_status = row.status.clone();
+
+                    match row.status {
+                        SubscriptionStatusEnum::Cancelled => {
+                            let cancelled_on = row.current_period_start;
+                            if (today - cancelled_on).num_days() > REACTIVATION_WINDOW_DAYS {
+                                return Err(Report::new(StoreError::InvalidArgument(format!(
+                                    "Subscriptions can only be reactivated within {REACTIVATION_WINDOW_DAYS} days of their cancellation"
+                                ))));
+                            }
+
+                            // The churn brought the stored MRR down to zero, the subscription
+                            // is back at the MRR of its current components and add-ons.
+                            let (mrr, current_mrr) = self
+                                .calculate_subscription_mrr(conn, tenant_id, subscription_id)
+                                .await?;
+
+                            // Picked up by the MRR processing of the invoice issued below
+                            SubscriptionEventRow {
+                                id: Uuid::now_v7(),
+                                subscription_id,
+                                event_type: SubscriptionEventType::Reactivated.into(),
+                                details: None,
+                                created_at: chrono::Utc::now().naive_utc(),
+                                mrr_delta: Some(mrr - current_mrr),
+                                bi_mrr_movement_log_id: None,
+                                applies_to: today,
+                            }
+                            .insert(conn)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?;
+
+                            let invoiced = self
+                                .restart_cycles(
+                                    conn,
+                                    tenant_id,
+                                    &row,
+                                    today,
+                                    row.billing_day_anchor as u32,
+                                )
+                                .await?;
+
+                            if !invoiced {
+                                self.create_mrr_log_without_invoice(
+                                    conn,
+                                    tenant_id,
+                                    subscription_id,
+                                    SubscriptionEventType::Reactivated.into(),
+                                    today,
+                                )
+                                .await?;
+                            }
+                        }
+                        status if SubscriptionStatusEnum::not_terminal().contains(&status) => {
+                            let pending =
+                                ScheduledEventRow::list_pending_cancellations_for_subscriptions(
+                                    conn,
+                                    &[subscription_id],
+                                    &tenant_id,
+                                )
+                                .await
+                                .map_err(Into::<Report<StoreError>>::into)?;
+
+                            let Some((_, cancellation_time)) = pending.first() else {
+                                return Err(Report::new(StoreError::InvalidArgument(
+                                    "Subscription has no scheduled cancellation".to_string(),
+                                )));
+                            };
+
+                            ScheduledEventRow::cancel_pending_cancellation_events(
+                                conn,
+                                subscription_id,
+                                &tenant_id,
+                                "Subscription reactivated",
+                            )
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?;
+
+                            // The churn recorded when the cancellation was scheduled never happened
+                            SubscriptionEventRow::delete_unprocessed_by_event_type(
+                                conn,
+                                subscription_id,
+                                SubscriptionEventType::Cancelled.into(),
+                                cancellation_time.date(),
+                            )
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?;
+                        }
+                        _ => {
+                            return Err(Report::new(StoreError::InvalidArgument(
+                                "Only cancelled subscriptions or subscriptions with a scheduled cancellation can be reactivated".to_string(),
+                            )));
+                        }
+                    }
+
+                    let subscription: Subscription =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await
+                            .map_err(Into::<Report<StoreError>>::into)?
+                            .try_into()?;
+
+                    self.store
+                        .record_tx(
+                            conn,
+                            tenant_id,
+                            actor,
+                            AuditInput::Outbox(OutboxEvent::subscription_reactivated(
+                                subscription.clone().into(),
+                            )),
+                        )
+                        .await?;
+
+                    self.record_status_change_tx(
+                        conn,
+                        tenant_id,
+                        actor,
+                        &subscription,
+                        previous_status.into(),
+                    )
+                    .await?;
+
+                    Ok(subscription)
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+}
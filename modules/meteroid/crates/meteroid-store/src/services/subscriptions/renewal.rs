@@ -0,0 +1,557 @@
+use crate::StoreResult;
+use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionRenewalUpcomingEvent};
+use crate::domain::pgmq::{PgmqMessageNew, PgmqQueue, SendEmailRequest};
+use crate::domain::scheduled_events::ScheduledEventNew;
+use crate::domain::{RenewalPolicyEnum, RenewalTerms, Subscription};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::repositories::pgmq::PgmqInterface;
+use crate::services::Services;
+use crate::store::PgConn;
+use crate::utils::locale::document_language;
+use chrono::{Datelike, Months, NaiveDate, NaiveTime};
+use common_domain::ids::{BaseId, SubscriptionId, TenantId};
+use diesel_models::enums::{ScheduledEventTypeEnum, SubscriptionEventType, SubscriptionStatusEnum};
+use diesel_models::plans::PlanRow;
+use diesel_models::scheduled_events::ScheduledEventRow;
+use diesel_models::subscription_events::SubscriptionEventRow;
+use diesel_models::subscriptions::{SubscriptionRenewalRowPatch, SubscriptionRow};
+use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
+
+const RENEWAL_NOTIFICATION_BATCH_SIZE: i64 = 100;
+
+/// A term renewal decided when a fixed-term subscription reaches its end date, applied once
+/// the cycle transition goes through.
+pub(in crate::services) struct TermRenewal {
+    pub previous_end_date: NaiveDate,
+    pub new_end_date: NaiveDate,
+    pub policy: RenewalPolicyEnum,
+}
+
+impl Services {
+    /// Sets what happens when the term of a fixed-term subscription ends.
+    pub(in crate::services) async fn set_renewal_terms(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        terms: RenewalTerms,
+    ) -> StoreResult<Subscription> {
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                let terms = &terms;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .subscription;
+
+                    if !SubscriptionStatusEnum::not_terminal().contains(&row.status) {
+                        return Err(Report::new(StoreError::InvalidArgument(format!(
+                            "Cannot set renewal terms of a subscription in {:?} status",
+                            row.status
+                        ))));
+                    }
+
+                    let end_date = row.end_date.ok_or_else(|| {
+                        Report::new(StoreError::InvalidArgument(
+                            "Renewal terms require a subscription end date".to_string(),
+                        ))
+                    })?;
+
+                    if let (Some(notice_days), Some(opt_out_days)) =
+                        (terms.notice_days, terms.opt_out_days)
+                        && notice_days < opt_out_days
+                    {
+                        return Err(Report::new(StoreError::InvalidArgument(
+                            "The renewal notice must be sent before the opt-out cutoff".to_string(),
+                        )));
+                    }
+
+                    let term_months = match terms.policy {
+                        RenewalPolicyEnum::RenewSameTerm
+                        | RenewalPolicyEnum::RenewToPlanVersion => {
+                            let term_start = row.billing_start_date.unwrap_or(row.start_date);
+                            let term_months = terms
+                                .term_months
+                                .unwrap_or_else(|| whole_months_between(term_start, end_date));
+                            if term_months == 0 {
+                                return Err(Report::new(StoreError::InvalidArgument(
+                                    "The renewal term must be at least a month".to_string(),
+                                )));
+                            }
+                            Some(term_months)
+                        }
+                        RenewalPolicyEnum::Expire | RenewalPolicyEnum::RenewMonthToMonth => None,
+                    };
+
+                    let plan_version_id = match terms.policy {
+                        RenewalPolicyEnum::RenewToPlanVersion => {
+                            let plan_version_id = terms.plan_version_id.ok_or_else(|| {
+                                Report::new(StoreError::InvalidArgument(
+                                    "A plan version is required to renew onto".to_string(),
+                                ))
+                            })?;
+                            validate_renewal_plan_version(conn, &row, plan_version_id).await?;
+                            Some(plan_version_id)
+                        }
+                        _ => None,
+                    };
+
+                    SubscriptionRenewalRowPatch {
+                        id: subscription_id,
+                        tenant_id,
+                        end_date: None,
+                        renewal_policy: Some(terms.policy.into()),
+                        renewal_term_months: Some(term_months.map(|m| m as i32)),
+                        renewal_plan_version_id: Some(plan_version_id),
+                        renewal_notice_days: Some(terms.notice_days.map(|d| d as i32)),
+                        renewal_opt_out_days: Some(terms.opt_out_days.map(|d| d as i32)),
+                    }
+                    .patch(conn)
+                    .await?;
+
+                    let activity = Activity::new(
+                        ActivityType::SubscriptionRenewalTermsUpdated,
+                        EntityType::Subscription,
+                        subscription_id.as_uuid(),
+                    )
+                    .agg_customer(row.customer_id)
+                    .with_metadata(serde_json::json!({
+                        "renewal_policy": terms.policy,
+                        "term_months": term_months,
+                        "plan_version_id": plan_version_id.map(|id| id.as_base62()),
+                        "notice_days": terms.notice_days,
+                        "opt_out_days": terms.opt_out_days,
+                    }));
+                    self.store
+                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .await?;
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Lets the subscription expire at the end of its term instead of renewing, as long as
+    /// the opt-out cutoff has not passed.
+    pub(in crate::services) async fn opt_out_of_renewal(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+    ) -> StoreResult<Subscription> {
+        let today = chrono::Utc::now().naive_utc().date();
+
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let subscription: Subscription =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .try_into()?;
+
+                    let deadline = subscription.renewal_opt_out_deadline().ok_or_else(|| {
+                        Report::new(StoreError::InvalidArgument(
+                            "The subscription does not renew".to_string(),
+                        ))
+                    })?;
+
+                    if today > deadline {
+                        return Err(Report::new(StoreError::InvalidArgument(format!(
+                            "Renewal could only be opted out of until {deadline}"
+                        ))));
+                    }
+
+                    SubscriptionRenewalRowPatch {
+                        id: subscription_id,
+                        tenant_id,
+                        end_date: None,
+                        renewal_policy: Some(RenewalPolicyEnum::Expire.into()),
+                        renewal_term_months: Some(None),
+                        renewal_plan_version_id: Some(None),
+                        renewal_notice_days: None,
+                        renewal_opt_out_days: None,
+                    }
+                    .patch(conn)
+                    .await?;
+
+                    let activity = Activity::new(
+                        ActivityType::SubscriptionRenewalOptedOut,
+                        EntityType::Subscription,
+                        subscription_id.as_uuid(),
+                    )
+                    .agg_customer(subscription.customer_id)
+                    .with_metadata(serde_json::json!({
+                        "previous_renewal_policy": subscription.renewal_policy,
+                        "end_date": subscription.end_date.map(|d| d.to_string()),
+                    }));
+                    self.store
+                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .await?;
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Decides whether a subscription reaching its end date renews, and for how long.
+    ///
+    /// Renewing onto another plan version schedules the plan change on the end date, so that
+    /// it is applied by the same cycle transition. A lifecycle event already scheduled on
+    /// that date (cancellation, pause or explicit plan change) takes precedence.
+    pub(in crate::services) async fn prepare_term_renewal_tx(
+        &self,
+        conn: &mut PgConn,
+        subscription: &SubscriptionRow,
+    ) -> StoreResult<Option<TermRenewal>> {
+        let policy: RenewalPolicyEnum = subscription.renewal_policy.into();
+        let Some(previous_end_date) = subscription.end_date else {
+            return Ok(None);
+        };
+
+        let term_months = match policy {
+            RenewalPolicyEnum::Expire => return Ok(None),
+            RenewalPolicyEnum::RenewMonthToMonth => Some(1),
+            RenewalPolicyEnum::RenewSameTerm | RenewalPolicyEnum::RenewToPlanVersion => {
+                subscription.renewal_term_months.map(|m| m as u32)
+            }
+        };
+        let Some(new_end_date) = term_months
+            .filter(|m| *m > 0)
+            .and_then(|m| previous_end_date.checked_add_months(Months::new(m)))
+        else {
+            log::warn!(
+                "Subscription {} renews without a valid term, letting it expire",
+                subscription.id
+            );
+            return Ok(None);
+        };
+
+        if policy == RenewalPolicyEnum::RenewToPlanVersion
+            && let Some(plan_version_id) = subscription.renewal_plan_version_id
+        {
+            let renewal_date = subscription.current_period_end.unwrap_or(previous_end_date);
+
+            let pending = ScheduledEventRow::get_event_by_types_and_date_for_update(
+                conn,
+                subscription.id,
+                &subscription.tenant_id,
+                vec![
+                    ScheduledEventTypeEnum::CancelSubscription,
+                    ScheduledEventTypeEnum::ApplyPlanChange,
+                    ScheduledEventTypeEnum::PauseSubscription,
+                ],
+                renewal_date,
+            )
+            .await?;
+
+            if pending.is_none() && plan_version_id != subscription.plan_version_id {
+                let event_data = self
+                    .build_default_plan_change_tx(
+                        conn,
+                        subscription.tenant_id,
+                        subscription.id,
+                        plan_version_id,
+                    )
+                    .await?;
+
+                self.store
+                    .schedule_events(
+                        conn,
+                        vec![ScheduledEventNew {
+                            subscription_id: subscription.id,
+                            tenant_id: subscription.tenant_id,
+                            scheduled_time: renewal_date.and_time(NaiveTime::MIN),
+                            event_data,
+                            source: "renewal".to_string(),
+                            created_by_customer: false,
+                        }],
+                    )
+                    .await?;
+            }
+        }
+
+        Ok(Some(TermRenewal {
+            previous_end_date,
+            new_end_date,
+            policy,
+        }))
+    }
+
+    /// Moves the end date to the end of the new term, recorded as a renewal event of the
+    /// subscription. After renewing onto another plan version, further terms renew as is.
+    pub(in crate::services) async fn apply_term_renewal_tx(
+        &self,
+        conn: &mut PgConn,
+        subscription: &SubscriptionRow,
+        renewal: &TermRenewal,
+    ) -> StoreResult<()> {
+        let switched_plan = renewal.policy == RenewalPolicyEnum::RenewToPlanVersion;
+
+        SubscriptionRenewalRowPatch {
+            id: subscription.id,
+            tenant_id: subscription.tenant_id,
+            end_date: Some(Some(renewal.new_end_date)),
+            renewal_policy: switched_plan.then_some(RenewalPolicyEnum::RenewSameTerm.into()),
+            renewal_term_months: None,
+            renewal_plan_version_id: switched_plan.then_some(None),
+            renewal_notice_days: None,
+            renewal_opt_out_days: None,
+        }
+        .patch(conn)
+        .await?;
+
+        SubscriptionEventRow {
+            id: uuid::Uuid::now_v7(),
+            subscription_id: subscription.id,
+            event_type: SubscriptionEventType::Renewed,
+            details: Some(serde_json::json!({
+                "policy": renewal.policy,
+                "previous_end_date": renewal.previous_end_date,
+                "end_date": renewal.new_end_date,
+            })),
+            created_at: chrono::Utc::now().naive_utc(),
+            mrr_delta: None,
+            bi_mrr_movement_log_id: None,
+            applies_to: renewal.previous_end_date,
+        }
+        .insert(conn)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+        Ok(())
+    }
+
+    /// Notifies the renewing subscriptions that entered their notice period, once per end
+    /// of term.
+    pub(in crate::services) async fn process_renewal_upcoming_notifications(
+        &self,
+    ) -> StoreResult<usize> {
+        let today = chrono::Utc::now().naive_utc().date();
+
+        let mut notified = 0;
+        let mut after = None;
+
+        loop {
+            let mut conn = self.store.get_conn().await?;
+            let batch = SubscriptionRow::list_renewals_upcoming(
+                &mut conn,
+                today,
+                after,
+                RENEWAL_NOTIFICATION_BATCH_SIZE,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+            drop(conn);
+
+            let Some((last_id, _)) = batch.last() else {
+                break;
+            };
+            after = Some(*last_id);
+
+            for (subscription_id, tenant_id) in batch {
+                match self
+                    .notify_renewal_upcoming(tenant_id, subscription_id, today)
+                    .await
+                {
+                    Ok(true) => notified += 1,
+                    Ok(false) => {}
+                    Err(err) => log::warn!(
+                        "Failed to notify the renewal of subscription {subscription_id}: {err:?}"
+                    ),
+                }
+            }
+        }
+
+        Ok(notified)
+    }
+
+    /// Emits the `SubscriptionRenewalUpcoming` event and emails the customer if the term ends
+    /// within the notice period and was not notified yet. Returns whether a notification was sent.
+    async fn notify_renewal_upcoming(
+        &self,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        today: NaiveDate,
+    ) -> StoreResult<bool> {
+        self.store
+            .transaction(|conn| {
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .subscription;
+
+                    let (Some(end_date), Some(notice_days)) =
+                        (row.end_date, row.renewal_notice_days)
+                    else {
+                        return Ok(false);
+                    };
+                    let policy: RenewalPolicyEnum = row.renewal_policy.into();
+                    if !policy.renews()
+                        || end_date <= today
+                        || end_date - chrono::Duration::days(notice_days as i64) > today
+                        || row.renewal_notified_for == Some(end_date)
+                    {
+                        return Ok(false);
+                    }
+
+                    let details = self
+                        .store
+                        .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
+                        .await?;
+                    let opt_out_deadline = details.subscription.renewal_opt_out_deadline();
+
+                    let event = SubscriptionRenewalUpcomingEvent {
+                        subscription: details.subscription.clone().into(),
+                        renewal_date: end_date,
+                        renewal_policy: policy,
+                        renewal_plan_version_id: details.subscription.renewal_plan_version_id,
+                        opt_out_deadline,
+                    };
+                    self.store
+                        .record_tx(
+                            conn,
+                            tenant_id,
+                            &Actor::System,
+                            AuditInput::Outbox(OutboxEvent::subscription_renewal_upcoming(event)),
+                        )
+                        .await?;
+
+                    let customer = details.billed_customer();
+                    let recipient_emails = if customer.invoicing_emails.is_empty() {
+                        customer.billing_email.iter().cloned().collect()
+                    } else {
+                        customer.invoicing_emails.clone()
+                    };
+
+                    let email_msg: PgmqMessageNew = SendEmailRequest::RenewalUpcoming {
+                        tenant_id,
+                        subscription_id,
+                        customer_id: customer.id,
+                        invoicing_entity_id: details.invoicing_entity.id,
+                        plan_name: details.subscription.plan_name.clone(),
+                        renewal_date: end_date,
+                        opt_out_deadline,
+                        company_name: details.invoicing_entity.legal_name.clone(),
+                        lang: Some(document_language(
+                            customer.invoice_language.as_deref(),
+                            &details.invoicing_entity.country,
+                        )),
+                        logo_attachment_id: details.invoicing_entity.logo_attachment_id,
+                        recipient_emails,
+                    }
+                    .try_into()?;
+                    self.store
+                        .pgmq_send_batch_tx(conn, PgmqQueue::SendEmailRequest, vec![email_msg])
+                        .await?;
+
+                    SubscriptionRow::set_renewal_notified_for(
+                        conn,
+                        subscription_id,
+                        tenant_id,
+                        end_date,
+                    )
+                    .await?;
+
+                    Ok(true)
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+}
+
+/// Checks that a subscription can renew onto `plan_version_id`. The component mapping itself
+/// is resolved at renewal, against the components the subscription has by then.
+async fn validate_renewal_plan_version(
+    conn: &mut PgConn,
+    row: &SubscriptionRow,
+    plan_version_id: common_domain::ids::PlanVersionId,
+) -> StoreResult<()> {
+    let plan = PlanRow::get_with_version(conn, plan_version_id, row.tenant_id)
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?;
+
+    let version = plan.version.ok_or_else(|| {
+        Report::new(StoreError::ValueNotFound(
+            "Renewal plan version not found".to_string(),
+        ))
+    })?;
+
+    if version.is_draft_version {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Cannot renew onto a draft plan version".to_string(),
+        )));
+    }
+
+    if version.currency != row.currency {
+        return Err(Report::new(StoreError::InvalidArgument(format!(
+            "Currency mismatch: subscription uses {} but the renewal plan uses {}",
+            row.currency, version.currency
+        ))));
+    }
+
+    Ok(())
+}
+
+/// Number of whole months from `start` to `end`.
+fn whole_months_between(start: NaiveDate, end: NaiveDate) -> u32 {
+    let months = (end.year() - start.year()) * 12 + end.month() as i32 - start.month() as i32;
+    let months = months.max(0) as u32;
+
+    match start.checked_add_months(Months::new(months)) {
+        Some(date) if date > end => months.saturating_sub(1),
+        _ => months,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn whole_months_between_counts_complete_months() {
+        assert_eq!(whole_months_between(date(2025, 1, 1), date(2026, 1, 1)), 12);
+        assert_eq!(
+            whole_months_between(date(2025, 1, 15), date(2025, 4, 14)),
+            2
+        );
+        assert_eq!(
+            whole_months_between(date(2025, 1, 31), date(2025, 2, 28)),
+            1
+        );
+        assert_eq!(whole_months_between(date(2025, 1, 1), date(2025, 1, 20)), 0);
+        assert_eq!(whole_months_between(date(2025, 3, 1), date(2025, 1, 1)), 0);
+    }
+}
@@ -22,6 +22,7 @@ use crate::repositories::subscriptions::{
 use crate::services::Services;
 use crate::services::invoice_lines::invoice_lines::ComputedInvoiceContent;
 use crate::store::PgConn;
+use crate::utils::custom_fields::resolve_invoice_custom_fields;
 use crate::utils::local_id::LocalId;
 use chrono::{NaiveDateTime, NaiveTime};
 use common_domain::ids::{InvoiceId, PriceComponentId, SubscriptionId, TenantId};
@@ -502,6 +503,7 @@ impl Services {
             tax_details: vec![],
             sub_lines: vec![],
             group_by_dimensions: None,
+            metadata: Default::default(),
         };
 
         let customer = self
@@ -533,6 +535,11 @@ impl Services {
                 .and_time(NaiveTime::MIN),
         );
 
+        let custom_fields = resolve_invoice_custom_fields(
+            &customer.invoice_custom_fields,
+            Some(&subscription.invoice_custom_fields),
+        );
+
         let invoice_new = InvoiceNew {
             tenant_id,
             customer_id: subscription.customer_id,
@@ -569,6 +576,7 @@ impl Services {
             invoicing_entity_id: subscription.invoicing_entity_id,
             parent_invoice_id: None,
             consolidated_into_invoice_id: None,
+            custom_fields,
         };
 
         let draft_invoice = insert_invoice_tx(&self.store, conn, invoice_new).await?;
@@ -0,0 +1,48 @@
+use crate::StoreResult;
+use crate::domain::entity_activity::{Actor, AuditInput};
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionStatusChangedEvent};
+use crate::domain::{Subscription, SubscriptionStatusEnum};
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::services::Services;
+use crate::store::PgConn;
+use common_domain::ids::TenantId;
+
+impl Services {
+    /// Records `SubscriptionStatusChanged` when `subscription` left `previous_status`,
+    /// followed by `SubscriptionSuspended` when it is now suspended.
+    ///
+    /// Called after the more specific lifecycle event (cancelled, paused, trial ended...)
+    /// so that consumers can follow every status transition from a single event type.
+    pub(in crate::services) async fn record_status_change_tx(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        actor: &Actor,
+        subscription: &Subscription,
+        previous_status: SubscriptionStatusEnum,
+    ) -> StoreResult<()> {
+        if subscription.status == previous_status {
+            return Ok(());
+        }
+
+        let mut events = vec![OutboxEvent::subscription_status_changed(
+            SubscriptionStatusChangedEvent {
+                subscription: subscription.clone().into(),
+                previous_status,
+            },
+        )];
+        if subscription.status == SubscriptionStatusEnum::Suspended {
+            events.push(OutboxEvent::subscription_suspended(
+                subscription.clone().into(),
+            ));
+        }
+
+        for event in events {
+            self.store
+                .record_tx(conn, tenant_id, actor, AuditInput::Outbox(event))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
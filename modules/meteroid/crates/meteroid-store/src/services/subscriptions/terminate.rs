@@ -1,5 +1,7 @@
 use crate::StoreResult;
-use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::Subscription;
+use crate::domain::entity_activity::{Actor, AuditInput};
+use crate::domain::outbox_event::OutboxEvent;
 use crate::errors::StoreError;
 use crate::repositories::entity_activity::EntityActivityInterface;
 use crate::repositories::historical_rates::get_historical_rate_from_usd_by_date_cached;
@@ -28,6 +30,13 @@ impl Services {
         date: NaiveDate,
         terminate_with_state: SubscriptionStatusEnum,
     ) -> StoreResult<()> {
+        let previous_status =
+            SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .subscription
+                .status;
+
         let patch = SubscriptionCycleRowPatch {
             id: subscription_id,
             tenant_id,
@@ -82,7 +91,13 @@ impl Services {
         if !invoice_created
             && terminate_with_state == SubscriptionStatusEnum::Cancelled
             && let Err(e) = self
-                .create_churn_mrr_log(conn, tenant_id, subscription_id, date)
+                .create_mrr_log_without_invoice(
+                    conn,
+                    tenant_id,
+                    subscription_id,
+                    SubscriptionEventType::Cancelled,
+                    date,
+                )
                 .await
         {
             log::error!(
@@ -93,47 +108,64 @@ impl Services {
         }
 
         // System actor — user attribution lives on the earlier `cancellation_scheduled` row.
-        let activity_type = match terminate_with_state {
-            SubscriptionStatusEnum::Cancelled => Some(ActivityType::SubscriptionCancelled),
-            SubscriptionStatusEnum::Paused => Some(ActivityType::SubscriptionPaused),
-            _ => None,
-        };
-        if let Some(activity_type) = activity_type {
-            let customer_id = SubscriptionRow::get_customer_id(conn, &tenant_id, subscription_id)
-                .await
-                .map_err(Into::<Report<StoreError>>::into)?;
-            let activity = Activity::new(
-                activity_type,
-                EntityType::Subscription,
-                subscription_id.as_uuid(),
-            )
-            .agg_customer(customer_id);
+        // Pauses are recorded by the caller through the `SubscriptionPaused` outbox event.
+        if terminate_with_state == SubscriptionStatusEnum::Cancelled {
+            let subscription: Subscription =
+                SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                    .await
+                    .map_err(Into::<Report<StoreError>>::into)?
+                    .try_into()?;
             self.store
                 .record_tx(
                     conn,
                     tenant_id,
                     &Actor::System,
-                    AuditInput::Activity(activity),
+                    AuditInput::Outbox(OutboxEvent::subscription_cancelled(
+                        subscription.clone().into(),
+                    )),
                 )
                 .await?;
+            self.record_status_change_tx(
+                conn,
+                tenant_id,
+                &Actor::System,
+                &subscription,
+                previous_status.into(),
+            )
+            .await?;
         }
 
         Ok(())
     }
 
-    async fn create_churn_mrr_log(
+    /// Logs the MRR movement of a churn or reactivation event that no invoice picked up, and
+    /// applies its delta to the subscription. When an invoice is issued on the event date,
+    /// `process_mrr` handles both instead.
+    pub(in crate::services) async fn create_mrr_log_without_invoice(
         &self,
         conn: &mut PgConn,
         tenant_id: TenantId,
         subscription_id: SubscriptionId,
-        termination_date: NaiveDate,
+        event_type: SubscriptionEventType,
+        date: NaiveDate,
     ) -> StoreResult<()> {
-        // Fetch the cancellation subscription event
+        let (movement_type, description) = match event_type {
+            SubscriptionEventType::Cancelled => (MrrMovementType::Churn, "Subscription cancelled"),
+            SubscriptionEventType::Reactivated => {
+                (MrrMovementType::Reactivation, "Subscription reactivated")
+            }
+            other => {
+                return Err(Report::new(StoreError::InvalidArgument(format!(
+                    "No standalone MRR movement for {other:?} events"
+                ))));
+            }
+        };
+
         let event = SubscriptionEventRow::fetch_by_subscription_id_and_event_type(
             conn,
             subscription_id,
-            SubscriptionEventType::Cancelled,
-            termination_date,
+            event_type.clone(),
+            date,
         )
         .await
         .map_err(Into::<Report<StoreError>>::into)?;
@@ -142,9 +174,10 @@ impl Services {
             Some(e) => e,
             None => {
                 log::warn!(
-                    "No cancellation event found for subscription {} at date {}. Skipping churn MRR log.",
+                    "No {:?} event found for subscription {} at date {}. Skipping MRR log.",
+                    event_type,
                     subscription_id,
-                    termination_date
+                    date
                 );
                 return Ok(());
             }
@@ -153,7 +186,8 @@ impl Services {
         // Skip if already processed (idempotency)
         if event.bi_mrr_movement_log_id.is_some() {
             log::info!(
-                "Churn MRR log already exists for subscription {}. Skipping.",
+                "{:?} MRR log already exists for subscription {}. Skipping.",
+                event_type,
                 subscription_id
             );
             return Ok(());
@@ -163,7 +197,8 @@ impl Services {
             Some(delta) if delta != 0 => delta,
             _ => {
                 log::info!(
-                    "No MRR delta for cancellation event of subscription {}. Skipping churn MRR log.",
+                    "No MRR delta for {:?} event of subscription {}. Skipping MRR log.",
+                    event_type,
                     subscription_id
                 );
                 return Ok(());
@@ -176,6 +211,11 @@ impl Services {
                 .await
                 .map_err(Into::<Report<StoreError>>::into)?;
 
+        // Update subscription MRR
+        SubscriptionRow::update_subscription_mrr_delta(conn, subscription_id, mrr_delta)
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+
         // Find the last finalized invoice to link the MRR log to
         let last_invoice =
             InvoiceRow::find_last_by_subscription_id(conn, tenant_id, subscription_id)
@@ -186,21 +226,21 @@ impl Services {
             Some(inv) => inv.id,
             None => {
                 log::warn!(
-                    "No finalized invoice found for subscription {}. Cannot create churn MRR log.",
-                    subscription_id
+                    "No finalized invoice found for subscription {}. Cannot create {:?} MRR log.",
+                    subscription_id,
+                    event_type
                 );
                 return Ok(());
             }
         };
 
-        // Create the churn MRR movement log
         let mrr_log = BiMrrMovementLogRowNew {
             id: Uuid::now_v7(),
-            description: "Subscription cancelled".to_string(),
-            movement_type: MrrMovementType::Churn,
+            description: description.to_string(),
+            movement_type: movement_type.clone(),
             net_mrr_change: mrr_delta,
             currency: subscription.subscription.currency.clone(),
-            applies_to: termination_date,
+            applies_to: date,
             invoice_id,
             credit_note_id: None,
             plan_version_id: subscription.subscription.plan_version_id,
@@ -213,7 +253,7 @@ impl Services {
             .map_err(Into::<Report<StoreError>>::into)?;
 
         // Aggregate into bi_delta_mrr_daily (replaces trigger)
-        let rates = get_historical_rate_from_usd_by_date_cached(conn, termination_date).await?;
+        let rates = get_historical_rate_from_usd_by_date_cached(conn, date).await?;
         if let Some(rates) = rates {
             let rate = rates
                 .rates
@@ -232,9 +272,9 @@ impl Services {
                 MrrDailyUpsertInput {
                     tenant_id: tenant_id.as_uuid(),
                     plan_version_id: subscription.subscription.plan_version_id.as_uuid(),
-                    date: termination_date,
+                    date,
                     currency: subscription.subscription.currency.clone(),
-                    movement_type: MrrMovementType::Churn,
+                    movement_type,
                     net_mrr_change: mrr_delta,
                     net_mrr_change_usd: mrr_change_usd,
                     historical_rate_id: rates.id,
@@ -245,7 +285,7 @@ impl Services {
         } else {
             log::warn!(
                 "No historical rates found for date {}, MRR USD values will be zero",
-                termination_date
+                date
             );
         }
 
@@ -254,13 +294,9 @@ impl Services {
             .await
             .map_err(Into::<Report<StoreError>>::into)?;
 
-        // Update subscription MRR
-        SubscriptionRow::update_subscription_mrr_delta(conn, subscription_id, mrr_delta)
-            .await
-            .map_err(Into::<Report<StoreError>>::into)?;
-
         log::info!(
-            "Created churn MRR log for subscription {}: {} cents",
+            "Created {:?} MRR log for subscription {}: {} cents",
+            event_type,
             subscription_id,
             mrr_delta
         );
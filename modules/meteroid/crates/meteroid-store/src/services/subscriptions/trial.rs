@@ -0,0 +1,545 @@
+use crate::StoreResult;
+use crate::domain::Subscription;
+use crate::domain::entity_activity::{Activity, ActivityType, Actor, AuditInput, EntityType};
+use crate::domain::outbox_event::{OutboxEvent, SubscriptionTrialWillEndEvent};
+use crate::domain::pgmq::{PgmqMessageNew, PgmqQueue, SendEmailRequest};
+use crate::domain::scheduled_events::{ScheduledEventData, ScheduledEventNew};
+use crate::errors::StoreError;
+use crate::repositories::SubscriptionInterface;
+use crate::repositories::entity_activity::EntityActivityInterface;
+use crate::repositories::pgmq::PgmqInterface;
+use crate::services::Services;
+use crate::store::PgConn;
+use crate::utils::locale::document_language;
+use chrono::{Datelike, Days, NaiveDate, NaiveTime};
+use common_domain::ids::{BaseId, PlanId, ScheduledEventId, SubscriptionId, TenantId};
+use diesel_models::enums::{CycleActionEnum, ScheduledEventTypeEnum, SubscriptionStatusEnum};
+use diesel_models::plan_versions::PlanVersionFilter;
+use diesel_models::plans::PlanRow;
+use diesel_models::scheduled_events::ScheduledEventRow;
+use diesel_models::subscriptions::{SubscriptionCycleRowPatch, SubscriptionRow};
+use error_stack::Report;
+use scoped_futures::ScopedFutureExt;
+
+/// Days before the end of a trial the customer is notified that it will end.
+pub const TRIAL_WILL_END_NOTICE_DAYS: u64 = 3;
+
+const TRIAL_NOTIFICATION_BATCH_SIZE: i64 = 100;
+
+/// A running trial. A free trial is the first period of the subscription and ends with it;
+/// a paid trial is billed as usual and ends on its `EndTrial` event.
+struct RunningTrial {
+    end: NaiveDate,
+    end_trial_event_id: Option<ScheduledEventId>,
+}
+
+impl RunningTrial {
+    fn is_free(&self) -> bool {
+        self.end_trial_event_id.is_none()
+    }
+}
+
+impl Services {
+    /// Pushes back the end of a running trial. For a free trial the trial period, and with it
+    /// the first billing date, is extended; for a paid trial the `EndTrial` event is rescheduled.
+    pub(in crate::services) async fn extend_trial(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        trial_end: NaiveDate,
+    ) -> StoreResult<Subscription> {
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .subscription;
+                    let trial = get_running_trial(conn, &row).await?;
+
+                    if trial_end <= trial.end {
+                        return Err(Report::new(StoreError::InvalidArgument(format!(
+                            "The trial already ends on {}, end it early instead of shortening it",
+                            trial.end
+                        ))));
+                    }
+
+                    if row.end_date.is_some_and(|end_date| trial_end >= end_date) {
+                        return Err(Report::new(StoreError::InvalidArgument(
+                            "The trial cannot end after the subscription end date".to_string(),
+                        )));
+                    }
+
+                    self.move_trial_end(conn, &row, &trial, trial_end, "trial_extension")
+                        .await?;
+
+                    let activity = Activity::new(
+                        ActivityType::SubscriptionTrialExtended,
+                        EntityType::Subscription,
+                        subscription_id.as_uuid(),
+                    )
+                    .agg_customer(row.customer_id)
+                    .with_metadata(serde_json::json!({
+                        "previous_trial_end": trial.end.to_string(),
+                        "new_trial_end": trial_end.to_string(),
+                    }));
+                    self.store
+                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .await?;
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Ends a running trial today and converts the subscription. A free trial goes through
+    /// the usual trial end right away, which bills the first period; a paid trial, already
+    /// billed, just becomes active.
+    pub(in crate::services) async fn end_trial_now(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+    ) -> StoreResult<Subscription> {
+        let today = chrono::Utc::now().naive_utc().date();
+
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .subscription;
+                    let trial = get_running_trial(conn, &row).await?;
+
+                    if trial.end <= today {
+                        return Err(Report::new(StoreError::InvalidArgument(
+                            "The trial is already ending".to_string(),
+                        )));
+                    }
+
+                    self.move_trial_end(conn, &row, &trial, today, "trial_conversion")
+                        .await?;
+
+                    if trial.is_free() {
+                        let row = SubscriptionRow::get_subscription_by_id(
+                            conn,
+                            &tenant_id,
+                            subscription_id,
+                        )
+                        .await?
+                        .subscription;
+                        self.process_cycle_transition(conn, &row).await?;
+                    } else {
+                        SubscriptionCycleRowPatch {
+                            id: subscription_id,
+                            tenant_id,
+                            status: Some(SubscriptionStatusEnum::Active),
+                            cycle_index: None,
+                            next_cycle_action: None,
+                            current_period_start: None,
+                            current_period_end: None,
+                            pending_checkout: None,
+                            processing_started_at: None,
+                            billing_start_date: None,
+                            billing_day_anchor: None,
+                        }
+                        .patch(conn)
+                        .await?;
+
+                        self.record_trial_ended_tx(conn, tenant_id, actor, subscription_id)
+                            .await?;
+                    }
+
+                    let activity = Activity::new(
+                        ActivityType::SubscriptionTrialEndedEarly,
+                        EntityType::Subscription,
+                        subscription_id.as_uuid(),
+                    )
+                    .agg_customer(row.customer_id)
+                    .with_metadata(serde_json::json!({
+                        "scheduled_trial_end": trial.end.to_string(),
+                        "trial_end": today.to_string(),
+                    }));
+                    self.store
+                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .await?;
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Switches the plan in effect during a running trial. `None` restores the trialing plan
+    /// of the plan version.
+    pub(in crate::services) async fn change_trialing_plan(
+        &self,
+        actor: Actor,
+        subscription_id: SubscriptionId,
+        tenant_id: TenantId,
+        trialing_plan_id: Option<PlanId>,
+    ) -> StoreResult<Subscription> {
+        let db_subscription = self
+            .store
+            .transaction(|conn| {
+                let actor = &actor;
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .subscription;
+
+                    if row.status != SubscriptionStatusEnum::TrialActive {
+                        return Err(Report::new(StoreError::InvalidArgument(
+                            "The subscription is not trialing".to_string(),
+                        )));
+                    }
+
+                    if let Some(plan_id) = trialing_plan_id {
+                        // The trialing plan must have a published version to resolve to
+                        let has_active_version = PlanRow::get_with_version_by_id(
+                            conn,
+                            plan_id,
+                            tenant_id,
+                            PlanVersionFilter::Active,
+                        )
+                        .await
+                        .is_ok_and(|plan| plan.version.is_some());
+
+                        if !has_active_version {
+                            return Err(Report::new(StoreError::InvalidArgument(format!(
+                                "Plan {plan_id} not found or has no active version"
+                            ))));
+                        }
+                    }
+
+                    SubscriptionRow::update_trialing_plan(
+                        conn,
+                        subscription_id,
+                        tenant_id,
+                        trialing_plan_id,
+                    )
+                    .await?;
+
+                    let activity = Activity::new(
+                        ActivityType::SubscriptionTrialingPlanChanged,
+                        EntityType::Subscription,
+                        subscription_id.as_uuid(),
+                    )
+                    .agg_customer(row.customer_id)
+                    .with_metadata(serde_json::json!({
+                        "previous_trialing_plan_id": row.trialing_plan_id.map(|id| id.as_base62()),
+                        "new_trialing_plan_id": trialing_plan_id.map(|id| id.as_base62()),
+                    }));
+                    self.store
+                        .record_tx(conn, tenant_id, actor, AuditInput::Activity(activity))
+                        .await?;
+
+                    SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                        .await
+                        .map_err(Into::<Report<StoreError>>::into)
+                }
+                .scope_boxed()
+            })
+            .await?;
+
+        db_subscription.try_into()
+    }
+
+    /// Notifies the trials ending in the next [`TRIAL_WILL_END_NOTICE_DAYS`] days, once per
+    /// trial end: extending a trial notifies it again before its new end.
+    pub(in crate::services) async fn process_trial_will_end_notifications(
+        &self,
+    ) -> StoreResult<usize> {
+        let today = chrono::Utc::now().naive_utc().date();
+        let until = today + Days::new(TRIAL_WILL_END_NOTICE_DAYS);
+
+        let mut notified = 0;
+        let mut after = None;
+
+        loop {
+            let mut conn = self.store.get_conn().await?;
+            let batch = SubscriptionRow::list_trials_ending_before(
+                &mut conn,
+                until,
+                after,
+                TRIAL_NOTIFICATION_BATCH_SIZE,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+            drop(conn);
+
+            let Some((last_id, _)) = batch.last() else {
+                break;
+            };
+            after = Some(*last_id);
+
+            for (subscription_id, tenant_id) in batch {
+                match self
+                    .notify_trial_will_end(tenant_id, subscription_id, until)
+                    .await
+                {
+                    Ok(true) => notified += 1,
+                    Ok(false) => {}
+                    Err(err) => log::warn!(
+                        "Failed to notify the trial end of subscription {subscription_id}: {err:?}"
+                    ),
+                }
+            }
+        }
+
+        Ok(notified)
+    }
+
+    /// Emits the `SubscriptionTrialWillEnd` event and emails the customer if the trial ends
+    /// by `until` and was not notified yet. Returns whether a notification was sent.
+    async fn notify_trial_will_end(
+        &self,
+        tenant_id: TenantId,
+        subscription_id: SubscriptionId,
+        until: NaiveDate,
+    ) -> StoreResult<bool> {
+        let today = chrono::Utc::now().naive_utc().date();
+
+        self.store
+            .transaction(|conn| {
+                async move {
+                    SubscriptionRow::lock_subscription_for_update(conn, subscription_id).await?;
+
+                    let row =
+                        SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                            .await?
+                            .subscription;
+                    if row.status != SubscriptionStatusEnum::TrialActive {
+                        return Ok(false);
+                    }
+
+                    let trial = get_running_trial(conn, &row).await?;
+                    if trial.end < today
+                        || trial.end > until
+                        || row.notified_trial_end == Some(trial.end)
+                    {
+                        return Ok(false);
+                    }
+
+                    let details = self
+                        .store
+                        .get_subscription_details_with_conn(conn, tenant_id, subscription_id)
+                        .await?;
+
+                    let event = SubscriptionTrialWillEndEvent {
+                        subscription: details.subscription.clone().into(),
+                        trial_end_date: trial.end,
+                    };
+                    self.store
+                        .record_tx(
+                            conn,
+                            tenant_id,
+                            &Actor::System,
+                            AuditInput::Outbox(OutboxEvent::subscription_trial_will_end(event)),
+                        )
+                        .await?;
+
+                    let customer = details.billed_customer();
+                    let recipient_emails = if customer.invoicing_emails.is_empty() {
+                        customer.billing_email.iter().cloned().collect()
+                    } else {
+                        customer.invoicing_emails.clone()
+                    };
+
+                    let email_msg: PgmqMessageNew = SendEmailRequest::TrialWillEnd {
+                        tenant_id,
+                        subscription_id,
+                        customer_id: customer.id,
+                        invoicing_entity_id: details.invoicing_entity.id,
+                        plan_name: details.subscription.plan_name.clone(),
+                        trial_end_date: trial.end,
+                        company_name: details.invoicing_entity.legal_name.clone(),
+                        lang: Some(document_language(
+                            customer.invoice_language.as_deref(),
+                            &details.invoicing_entity.country,
+                        )),
+                        logo_attachment_id: details.invoicing_entity.logo_attachment_id,
+                        recipient_emails,
+                    }
+                    .try_into()?;
+                    self.store
+                        .pgmq_send_batch_tx(conn, PgmqQueue::SendEmailRequest, vec![email_msg])
+                        .await?;
+
+                    SubscriptionRow::set_notified_trial_end(
+                        conn,
+                        subscription_id,
+                        tenant_id,
+                        trial.end,
+                    )
+                    .await?;
+
+                    Ok(true)
+                }
+                .scope_boxed()
+            })
+            .await
+    }
+
+    /// Records `SubscriptionTrialEnded`, followed by the status change out of the trial.
+    pub(in crate::services) async fn record_trial_ended_tx(
+        &self,
+        conn: &mut PgConn,
+        tenant_id: TenantId,
+        actor: &Actor,
+        subscription_id: SubscriptionId,
+    ) -> StoreResult<()> {
+        let subscription: Subscription =
+            SubscriptionRow::get_subscription_by_id(conn, &tenant_id, subscription_id)
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?
+                .try_into()?;
+
+        self.store
+            .record_tx(
+                conn,
+                tenant_id,
+                actor,
+                AuditInput::Outbox(OutboxEvent::subscription_trial_ended(
+                    subscription.clone().into(),
+                )),
+            )
+            .await?;
+
+        self.record_status_change_tx(
+            conn,
+            tenant_id,
+            actor,
+            &subscription,
+            SubscriptionStatusEnum::TrialActive.into(),
+        )
+        .await
+    }
+
+    /// Moves the end of `trial` to `trial_end`, keeping the trial duration in sync.
+    ///
+    /// A free trial ends with its period, so the period end moves along, and with it the
+    /// billing day when it was derived from the trial end. A paid trial gets its `EndTrial`
+    /// event rescheduled, unless the trial ends today.
+    async fn move_trial_end(
+        &self,
+        conn: &mut PgConn,
+        row: &SubscriptionRow,
+        trial: &RunningTrial,
+        trial_end: NaiveDate,
+        source: &str,
+    ) -> StoreResult<()> {
+        let today = chrono::Utc::now().naive_utc().date();
+
+        match trial.end_trial_event_id {
+            None => {
+                let billing_day_anchor = (i32::from(row.billing_day_anchor)
+                    == trial.end.day() as i32)
+                    .then_some(trial_end.day() as i16);
+
+                SubscriptionCycleRowPatch {
+                    id: row.id,
+                    tenant_id: row.tenant_id,
+                    status: None,
+                    cycle_index: None,
+                    next_cycle_action: None,
+                    current_period_start: None,
+                    current_period_end: Some(Some(trial_end)),
+                    pending_checkout: None,
+                    processing_started_at: None,
+                    billing_start_date: None,
+                    billing_day_anchor,
+                }
+                .patch(conn)
+                .await?;
+            }
+            Some(event_id) => {
+                ScheduledEventRow::cancel_event(conn, &event_id, "Trial end moved").await?;
+
+                if trial_end > today {
+                    self.store
+                        .schedule_events(
+                            conn,
+                            vec![ScheduledEventNew {
+                                subscription_id: row.id,
+                                tenant_id: row.tenant_id,
+                                scheduled_time: trial_end.and_time(NaiveTime::MIN),
+                                event_data: ScheduledEventData::EndTrial,
+                                source: source.to_string(),
+                                created_by_customer: false,
+                            }],
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        if let Some(trial_duration) = row.trial_duration {
+            let trial_duration = trial_duration + (trial_end - trial.end).num_days() as i32;
+            SubscriptionRow::update_trial_duration(conn, row.id, row.tenant_id, trial_duration)
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+async fn get_running_trial(conn: &mut PgConn, row: &SubscriptionRow) -> StoreResult<RunningTrial> {
+    if row.status != SubscriptionStatusEnum::TrialActive {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "The subscription is not trialing".to_string(),
+        )));
+    }
+
+    if row.next_cycle_action == Some(CycleActionEnum::EndTrial) {
+        let end = row.current_period_end.ok_or_else(|| {
+            Report::new(StoreError::InvalidArgument(
+                "Subscription has no current_period_end".to_string(),
+            ))
+        })?;
+        return Ok(RunningTrial {
+            end,
+            end_trial_event_id: None,
+        });
+    }
+
+    let end_trial_event =
+        ScheduledEventRow::get_pending_events_for_subscription(conn, row.id, &row.tenant_id)
+            .await?
+            .into_iter()
+            .find(|e| e.event_type == ScheduledEventTypeEnum::EndTrial)
+            .ok_or_else(|| {
+                Report::new(StoreError::InvalidArgument(
+                    "The trial end of the subscription is not scheduled".to_string(),
+                ))
+            })?;
+
+    Ok(RunningTrial {
+        end: end_trial_event.scheduled_time.date(),
+        end_trial_event_id: Some(end_trial_event.id),
+    })
+}
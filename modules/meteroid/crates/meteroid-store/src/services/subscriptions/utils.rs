@@ -1,6 +1,6 @@
 use crate::StoreResult;
 use crate::domain::entity_activity::Actor;
-use crate::domain::enums::{BillingPeriodEnum, SubscriptionFeeBillingPeriod};
+use crate::domain::enums::{BillingPeriodEnum, PeriodLength, SubscriptionFeeBillingPeriod};
 use crate::domain::price_components::{PriceEntry, ProductRef};
 use crate::domain::subscriptions::PaymentMethodsConfig;
 use crate::domain::{
@@ -86,8 +86,6 @@ pub fn calculate_mrr(
 ) -> i64 {
     let mut total_cents = 0;
 
-    let period_as_months = i64::from(period.as_months());
-
     match fee {
         SubscriptionFee::Rate { rate } => {
             total_cents = rate.to_subunit_opt(precision).unwrap_or(0);
@@ -112,9 +110,11 @@ pub fn calculate_mrr(
         }
     }
 
-    let _mrr = total_cents / period_as_months;
+    let Some(length) = period.length() else {
+        return 0;
+    };
 
-    let mrr_monthly = Decimal::from(total_cents) / Decimal::from(period_as_months);
+    let mrr_monthly = length.to_monthly(Decimal::from(total_cents));
 
     mrr_monthly.to_i64().unwrap_or(0)
 }
@@ -569,6 +569,31 @@ pub fn extract_billing_period(
         .unwrap_or(BillingPeriodEnum::Monthly)
 }
 
+/// Day-based (daily, weekly) and month-based cadences never line up on the same cycle
+/// boundaries, so a subscription may only use one kind for its recurring fees.
+pub fn validate_billing_periods(
+    components: &[SubscriptionComponentNewInternal],
+    add_ons: &[SubscriptionAddOnNewInternal],
+) -> StoreResult<()> {
+    let mixed = components
+        .iter()
+        .map(|x| &x.period)
+        .chain(add_ons.iter().map(|x| &x.period))
+        .filter_map(SubscriptionFeeBillingPeriod::length)
+        .map(|length| matches!(length, PeriodLength::Days(_)))
+        .unique()
+        .count()
+        > 1;
+
+    if mixed {
+        return Err(Report::new(StoreError::InvalidArgument(
+            "Subscription cannot mix daily or weekly fees with monthly or longer fees".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
 #[allow(clippy::too_many_arguments)]
 pub fn process_create_subscription_components(
     param: &Option<CreateSubscriptionComponents>,
@@ -723,7 +748,7 @@ impl SubscriptionDetails {
             .iter()
             .filter(|c| c.is_standard())
             .map(|c| c.period)
-            .max_by(|a, b| a.as_months().cmp(&b.as_months()))
+            .max_by_key(|p| p.length().map_or(u32::MAX, |l| l.nominal_days()))
             .and_then(|p| p.as_billing_period_opt())
             .unwrap_or(BillingPeriodEnum::Monthly);
 
@@ -1,10 +1,570 @@
+use crate::StoreResult;
+use crate::domain::enums::NumberingResetEnum;
+use crate::errors::StoreError;
 use chrono::Datelike;
 use chrono::NaiveDate;
+use diesel_models::PgConn;
+use diesel_models::invoicing_entities::InvoicingEntityRow;
+use error_stack::Report;
+use std::collections::BTreeMap;
 
-pub fn format_invoice_number(number: i64, format: String, date: NaiveDate) -> String {
-    format
-        .replace("{number}", &format!("{number:04}"))
-        .replace("{YYYY}", &date.year().to_string())
-        .replace("{MM}", &format!("{:02}", date.month()))
-        .replace("{DD}", &format!("{:02}", date.day()))
+/// Zero-padding of `{number}` when the pattern does not specify a width.
+const DEFAULT_NUMBER_WIDTH: usize = 4;
+const MAX_NUMBER_WIDTH: usize = 12;
+
+/// Values substituted to the tokens of a document number pattern.
+#[derive(Debug, Clone, Copy)]
+pub struct NumberContext<'a> {
+    /// Date of the document, for the date tokens and the reset period.
+    pub date: NaiveDate,
+    pub customer_alias: Option<&'a str>,
+    /// Invoice number prefix of the subscription.
+    pub prefix: Option<&'a str>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(String),
+    Number { width: usize },
+    Year,
+    ShortYear,
+    Month,
+    Day,
+    Customer,
+    Prefix,
+}
+
+/// An invoice or credit note number pattern, such as `INV-{YYYY}-{number:5}`.
+///
+/// Tokens:
+/// - `{number}` / `{number:N}`: the sequence number, zero-padded to 4 / N digits
+/// - `{YYYY}`, `{YY}`, `{MM}`, `{DD}`: the document date
+/// - `{customer}`: the customer alias (empty when the customer has none)
+/// - `{prefix}`: the invoice number prefix of the subscription (empty when none). Each prefix
+///   gets its own sequence.
+///
+/// Unknown tokens are rendered verbatim, so that patterns saved before validation existed keep
+/// producing the same numbers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NumberPattern {
+    tokens: Vec<Token>,
+}
+
+impl NumberPattern {
+    pub fn parse(pattern: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut literal = String::new();
+        let mut rest = pattern;
+
+        while let Some(start) = rest.find('{') {
+            let Some(end) = rest[start..].find('}').map(|end| start + end) else {
+                break;
+            };
+            literal.push_str(&rest[..start]);
+            match Self::token(&rest[start + 1..end]) {
+                Some(token) => {
+                    if !literal.is_empty() {
+                        tokens.push(Token::Literal(std::mem::take(&mut literal)));
+                    }
+                    tokens.push(token);
+                }
+                None => literal.push_str(&rest[start..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            tokens.push(Token::Literal(literal));
+        }
+
+        Self { tokens }
+    }
+
+    fn token(name: &str) -> Option<Token> {
+        match name {
+            "number" => Some(Token::Number {
+                width: DEFAULT_NUMBER_WIDTH,
+            }),
+            "YYYY" => Some(Token::Year),
+            "YY" => Some(Token::ShortYear),
+            "MM" => Some(Token::Month),
+            "DD" => Some(Token::Day),
+            "customer" => Some(Token::Customer),
+            "prefix" => Some(Token::Prefix),
+            _ => name
+                .strip_prefix("number:")
+                .and_then(|width| width.parse::<usize>().ok())
+                .filter(|width| (1..=MAX_NUMBER_WIDTH).contains(width))
+                .map(|width| Token::Number { width }),
+        }
+    }
+
+    fn has(&self, predicate: impl Fn(&Token) -> bool) -> bool {
+        self.tokens.iter().any(predicate)
+    }
+
+    /// Checks that the pattern only uses known tokens and produces unique numbers with the
+    /// given reset: a yearly sequence needs the year in the number, a monthly one the year and
+    /// the month.
+    pub fn validate(pattern: &str, reset: NumberingResetEnum) -> Result<(), String> {
+        let parsed = Self::parse(pattern);
+
+        if parsed.has(|t| matches!(t, Token::Literal(l) if l.contains(['{', '}']))) {
+            return Err(format!(
+                "Number pattern `{pattern}` contains an unsupported token. Supported tokens are \
+                 {{number}}, {{number:N}} (N from 1 to {MAX_NUMBER_WIDTH}), {{YYYY}}, {{YY}}, \
+                 {{MM}}, {{DD}}, {{customer}} and {{prefix}}"
+            ));
+        }
+        if !parsed.has(|t| matches!(t, Token::Number { .. })) {
+            return Err(format!(
+                "Number pattern `{pattern}` must contain {{number}}"
+            ));
+        }
+
+        let has_year = parsed.has(|t| matches!(t, Token::Year | Token::ShortYear));
+        let has_month = parsed.has(|t| matches!(t, Token::Month));
+        match reset {
+            NumberingResetEnum::Never => Ok(()),
+            NumberingResetEnum::Yearly if !has_year => Err(format!(
+                "Number pattern `{pattern}` must contain {{YYYY}} or {{YY}} to reset yearly"
+            )),
+            NumberingResetEnum::Monthly if !(has_year && has_month) => Err(format!(
+                "Number pattern `{pattern}` must contain {{YYYY}} or {{YY}}, and {{MM}} to reset monthly"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub fn render(&self, number: i64, context: &NumberContext) -> String {
+        let date = context.date;
+        self.tokens
+            .iter()
+            .map(|token| match token {
+                Token::Literal(literal) => literal.clone(),
+                Token::Number { width } => format!("{number:0width$}"),
+                Token::Year => date.year().to_string(),
+                Token::ShortYear => format!("{:02}", date.year() % 100),
+                Token::Month => format!("{:02}", date.month()),
+                Token::Day => format!("{:02}", date.day()),
+                Token::Customer => context.customer_alias.unwrap_or_default().to_string(),
+                Token::Prefix => context.prefix.unwrap_or_default().to_string(),
+            })
+            .collect()
+    }
+
+    /// Key of the sequence the document is numbered from. Empty for the entity-wide sequence.
+    pub fn scope(&self, reset: NumberingResetEnum, context: &NumberContext) -> String {
+        let prefix = context
+            .prefix
+            .filter(|p| !p.is_empty() && self.has(|t| matches!(t, Token::Prefix)));
+        sequence_scope(reset_period(reset, context.date).as_deref(), prefix)
+    }
+}
+
+/// Period a document dated `date` is numbered in, `None` when the sequence never resets.
+fn reset_period(reset: NumberingResetEnum, date: NaiveDate) -> Option<String> {
+    match reset {
+        NumberingResetEnum::Never => None,
+        NumberingResetEnum::Yearly => Some(format!("{:04}", date.year())),
+        NumberingResetEnum::Monthly => Some(format!("{:04}-{:02}", date.year(), date.month())),
+    }
+}
+
+fn sequence_scope(period: Option<&str>, prefix: Option<&str>) -> String {
+    period
+        .map(str::to_string)
+        .into_iter()
+        .chain(prefix.map(|prefix| format!("prefix:{prefix}")))
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Splits a sequence scope into its period and prefix.
+fn parse_sequence_scope(scope: &str) -> (Option<&str>, Option<&str>) {
+    if let Some(prefix) = scope.strip_prefix("prefix:") {
+        return (None, Some(prefix));
+    }
+    match scope.split_once('/') {
+        Some((period, rest)) => (Some(period), rest.strip_prefix("prefix:")),
+        None => (Some(scope), None),
+    }
+}
+
+/// Sequences to start from when the reset of a series changes on `date`, as
+/// `(scope, next_number)`. The empty scope is the entity-wide sequence.
+///
+/// The documents already numbered in the current period, under the previous reset, must not
+/// be numbered again: the sequence of the current period continues after the highest number
+/// of the sequences it overlaps, e.g. the entity-wide sequence and this year's monthly ones
+/// when switching to a yearly reset. Prefixes keep their own sequences.
+fn carried_over_sequences(
+    pattern: &NumberPattern,
+    reset: NumberingResetEnum,
+    date: NaiveDate,
+    entity_wide_next_number: i64,
+    sequences: &[(String, i64)],
+) -> Vec<(String, i64)> {
+    let period = reset_period(reset, date);
+    let with_prefix = pattern.has(|t| matches!(t, Token::Prefix));
+
+    let mut next_numbers: BTreeMap<Option<&str>, i64> =
+        BTreeMap::from([(None, entity_wide_next_number)]);
+    for (scope, next_number) in sequences {
+        let (scope_period, prefix) = parse_sequence_scope(scope);
+        let overlaps = match (period.as_deref(), scope_period) {
+            (Some(period), Some(scope_period)) => {
+                period.starts_with(scope_period) || scope_period.starts_with(period)
+            }
+            _ => true,
+        };
+        if overlaps && (with_prefix || prefix.is_none()) {
+            let next = next_numbers.entry(prefix).or_default();
+            *next = (*next).max(*next_number);
+        }
+    }
+
+    next_numbers
+        .into_iter()
+        .map(|(prefix, next_number)| (sequence_scope(period.as_deref(), prefix), next_number))
+        .collect()
+}
+
+/// A numbered document series of an invoicing entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberSeries {
+    Invoice,
+    CreditNote,
+}
+
+impl NumberSeries {
+    pub(crate) const ALL: [NumberSeries; 2] = [NumberSeries::Invoice, NumberSeries::CreditNote];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            NumberSeries::Invoice => "INVOICE",
+            NumberSeries::CreditNote => "CREDIT_NOTE",
+        }
+    }
+}
+
+/// Allocates the next number of the series and formats it with the entity pattern.
+///
+/// The invoicing entity must have been locked (`select_for_update_by_id_and_tenant`) in the
+/// current transaction: numbers are only consumed if it commits, which keeps sequences gapless.
+pub(crate) async fn next_document_number(
+    conn: &mut PgConn,
+    invoicing_entity: &InvoicingEntityRow,
+    series: NumberSeries,
+    context: &NumberContext<'_>,
+) -> StoreResult<String> {
+    let (pattern, reset) = match series {
+        NumberSeries::Invoice => (
+            &invoicing_entity.invoice_number_pattern,
+            invoicing_entity.invoice_number_reset,
+        ),
+        NumberSeries::CreditNote => (
+            &invoicing_entity.credit_note_number_pattern,
+            invoicing_entity.credit_note_number_reset,
+        ),
+    };
+    let pattern = NumberPattern::parse(pattern);
+    let scope = pattern.scope(reset.into(), context);
+
+    let number = if scope.is_empty() {
+        match series {
+            NumberSeries::Invoice => {
+                InvoicingEntityRow::update_invoicing_entity_number(
+                    conn,
+                    invoicing_entity.id,
+                    invoicing_entity.tenant_id,
+                    invoicing_entity.next_invoice_number,
+                )
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+                invoicing_entity.next_invoice_number
+            }
+            NumberSeries::CreditNote => {
+                InvoicingEntityRow::update_credit_note_number(
+                    conn,
+                    invoicing_entity.id,
+                    invoicing_entity.tenant_id,
+                    invoicing_entity.next_credit_note_number,
+                )
+                .await
+                .map_err(Into::<Report<StoreError>>::into)?;
+                invoicing_entity.next_credit_note_number
+            }
+        }
+    } else {
+        InvoicingEntityRow::allocate_sequence_number(
+            conn,
+            invoicing_entity.id,
+            invoicing_entity.tenant_id,
+            series.as_str(),
+            &scope,
+        )
+        .await
+        .map_err(Into::<Report<StoreError>>::into)?
+    };
+
+    Ok(pattern.render(number, context))
+}
+
+/// Carries the sequences of a series over a change of its reset (see
+/// [`carried_over_sequences`]), so that numbers stay unique.
+///
+/// The invoicing entity must have been locked in the current transaction, and be passed with
+/// the new reset.
+pub(crate) async fn carry_over_number_sequences(
+    conn: &mut PgConn,
+    invoicing_entity: &InvoicingEntityRow,
+    series: NumberSeries,
+    date: NaiveDate,
+) -> StoreResult<()> {
+    let (pattern, reset, entity_wide_next_number) = match series {
+        NumberSeries::Invoice => (
+            &invoicing_entity.invoice_number_pattern,
+            invoicing_entity.invoice_number_reset,
+            invoicing_entity.next_invoice_number,
+        ),
+        NumberSeries::CreditNote => (
+            &invoicing_entity.credit_note_number_pattern,
+            invoicing_entity.credit_note_number_reset,
+            invoicing_entity.next_credit_note_number,
+        ),
+    };
+
+    let sequences = InvoicingEntityRow::list_sequences(
+        conn,
+        invoicing_entity.id,
+        invoicing_entity.tenant_id,
+        series.as_str(),
+    )
+    .await
+    .map_err(Into::<Report<StoreError>>::into)?;
+
+    let carried_over = carried_over_sequences(
+        &NumberPattern::parse(pattern),
+        reset.into(),
+        date,
+        entity_wide_next_number,
+        &sequences,
+    );
+
+    for (scope, next_number) in carried_over {
+        if scope.is_empty() {
+            if next_number > entity_wide_next_number {
+                // sets the next number to the one following the given number
+                match series {
+                    NumberSeries::Invoice => {
+                        InvoicingEntityRow::update_invoicing_entity_number(
+                            conn,
+                            invoicing_entity.id,
+                            invoicing_entity.tenant_id,
+                            next_number - 1,
+                        )
+                        .await
+                    }
+                    NumberSeries::CreditNote => {
+                        InvoicingEntityRow::update_credit_note_number(
+                            conn,
+                            invoicing_entity.id,
+                            invoicing_entity.tenant_id,
+                            next_number - 1,
+                        )
+                        .await
+                    }
+                }
+                .map_err(Into::<Report<StoreError>>::into)?;
+            }
+        } else {
+            InvoicingEntityRow::set_sequence_next_number(
+                conn,
+                invoicing_entity.id,
+                invoicing_entity.tenant_id,
+                series.as_str(),
+                &scope,
+                next_number,
+            )
+            .await
+            .map_err(Into::<Report<StoreError>>::into)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(date: NaiveDate) -> NumberContext<'static> {
+        NumberContext {
+            date,
+            customer_alias: Some("acme"),
+            prefix: None,
+        }
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_render_tokens() {
+        let ctx = context(date(2026, 3, 7));
+
+        assert_eq!(
+            NumberPattern::parse("INV-{number}").render(12, &ctx),
+            "INV-0012"
+        );
+        assert_eq!(
+            NumberPattern::parse("{YYYY}{MM}{DD}-{number:6}").render(12, &ctx),
+            "20260307-000012"
+        );
+        assert_eq!(
+            NumberPattern::parse("F{YY}/{number:1}/{customer}").render(12, &ctx),
+            "F26/12/acme"
+        );
+        // the width is a minimum
+        assert_eq!(
+            NumberPattern::parse("{number:2}").render(12345, &ctx),
+            "12345"
+        );
+    }
+
+    #[test]
+    fn test_render_keeps_unknown_tokens() {
+        let ctx = context(date(2026, 3, 7));
+
+        assert_eq!(
+            NumberPattern::parse("{foo}-{number:0}-{number}{").render(1, &ctx),
+            "{foo}-{number:0}-0001{"
+        );
+    }
+
+    #[test]
+    fn test_render_prefix() {
+        let pattern = NumberPattern::parse("{prefix}-{number}");
+        let mut ctx = context(date(2026, 3, 7));
+        assert_eq!(pattern.render(1, &ctx), "-0001");
+
+        ctx.prefix = Some("PRJ");
+        assert_eq!(pattern.render(1, &ctx), "PRJ-0001");
+    }
+
+    #[test]
+    fn test_validate() {
+        use NumberingResetEnum::*;
+
+        assert!(NumberPattern::validate("INV-{number}", Never).is_ok());
+        assert!(NumberPattern::validate("INV-{YY}-{number:5}", Yearly).is_ok());
+        assert!(NumberPattern::validate("INV-{YYYY}{MM}-{number}", Monthly).is_ok());
+
+        assert!(NumberPattern::validate("INV", Never).is_err());
+        assert!(NumberPattern::validate("INV-{number}-{foo}", Never).is_err());
+        assert!(NumberPattern::validate("INV-{number:13}", Never).is_err());
+        assert!(NumberPattern::validate("INV-{number", Never).is_err());
+        assert!(NumberPattern::validate("INV-{number}", Yearly).is_err());
+        assert!(NumberPattern::validate("INV-{YYYY}-{number}", Monthly).is_err());
+        assert!(NumberPattern::validate("INV-{MM}-{number}", Monthly).is_err());
+    }
+
+    #[test]
+    fn test_scope() {
+        let with_prefix = NumberPattern::parse("{prefix}{YYYY}-{number}");
+        let without_prefix = NumberPattern::parse("{YYYY}-{number}");
+        let mut ctx = context(date(2026, 3, 7));
+
+        assert_eq!(with_prefix.scope(NumberingResetEnum::Never, &ctx), "");
+        assert_eq!(with_prefix.scope(NumberingResetEnum::Yearly, &ctx), "2026");
+        assert_eq!(
+            with_prefix.scope(NumberingResetEnum::Monthly, &ctx),
+            "2026-03"
+        );
+
+        ctx.prefix = Some("PRJ");
+        assert_eq!(
+            with_prefix.scope(NumberingResetEnum::Never, &ctx),
+            "prefix:PRJ"
+        );
+        assert_eq!(
+            with_prefix.scope(NumberingResetEnum::Yearly, &ctx),
+            "2026/prefix:PRJ"
+        );
+        // the prefix only gets its own sequence when it is part of the number
+        assert_eq!(
+            without_prefix.scope(NumberingResetEnum::Yearly, &ctx),
+            "2026"
+        );
+    }
+
+    #[test]
+    fn test_carried_over_sequences() {
+        use NumberingResetEnum::*;
+
+        let pattern = NumberPattern::parse("INV-{YYYY}-{MM}-{number}");
+        let today = date(2026, 10, 7);
+        let sequences = |scopes: &[(&str, i64)]| {
+            scopes
+                .iter()
+                .map(|(scope, next)| (scope.to_string(), *next))
+                .collect::<Vec<_>>()
+        };
+
+        // from the entity-wide sequence: this year's numbers came from it
+        assert_eq!(
+            carried_over_sequences(&pattern, Yearly, today, 121, &[]),
+            vec![("2026".to_string(), 121)]
+        );
+
+        // from monthly sequences: only this year's months overlap
+        let monthly = sequences(&[("2025-12", 40), ("2026-03", 12), ("2026-10", 5)]);
+        assert_eq!(
+            carried_over_sequences(&pattern, Yearly, today, 1, &monthly),
+            vec![("2026".to_string(), 12)]
+        );
+
+        // from a yearly sequence to this month's
+        let yearly = sequences(&[("2025", 300), ("2026", 87)]);
+        assert_eq!(
+            carried_over_sequences(&pattern, Monthly, today, 1, &yearly),
+            vec![("2026-10".to_string(), 87)]
+        );
+
+        // back to a single sequence, which covers every period
+        assert_eq!(
+            carried_over_sequences(&pattern, Never, today, 121, &yearly),
+            vec![(String::new(), 300)]
+        );
+    }
+
+    #[test]
+    fn test_carried_over_sequences_by_prefix() {
+        use NumberingResetEnum::*;
+
+        let today = date(2026, 10, 7);
+        let sequences = vec![
+            ("prefix:PRJ".to_string(), 30),
+            ("2026/prefix:OPS".to_string(), 8),
+        ];
+
+        let with_prefix = NumberPattern::parse("{prefix}-{YYYY}-{number}");
+        assert_eq!(
+            carried_over_sequences(&with_prefix, Yearly, today, 121, &sequences),
+            vec![
+                ("2026".to_string(), 121),
+                ("2026/prefix:OPS".to_string(), 8),
+                ("2026/prefix:PRJ".to_string(), 30),
+            ]
+        );
+
+        // without the prefix in the numbers, prefixed sequences do not matter
+        let without_prefix = NumberPattern::parse("INV-{YYYY}-{number}");
+        assert_eq!(
+            carried_over_sequences(&without_prefix, Yearly, today, 121, &sequences),
+            vec![("2026".to_string(), 121)]
+        );
+    }
 }
@@ -0,0 +1,126 @@
+use crate::StoreResult;
+use crate::errors::StoreError;
+use std::collections::BTreeMap;
+
+/// Structured fields of an invoice or line item (e.g. "cost_center" => "CC-42"), shown on the
+/// PDF and exported to webhooks and accounting connectors.
+pub type CustomFields = BTreeMap<String, String>;
+
+pub const MAX_CUSTOM_FIELDS: usize = 20;
+const MAX_KEY_LENGTH: usize = 40;
+const MAX_VALUE_LENGTH: usize = 255;
+
+/// Keys are identifiers (letters, digits, `_`, `-` and `.`, up to 40 characters) and values are
+/// non-empty text of up to 255 characters, at most 20 fields.
+pub fn validate_custom_fields(fields: &CustomFields) -> StoreResult<()> {
+    if fields.len() > MAX_CUSTOM_FIELDS {
+        return Err(StoreError::InvalidArgument(format!(
+            "At most {MAX_CUSTOM_FIELDS} custom fields are allowed"
+        ))
+        .into());
+    }
+
+    for (key, value) in fields {
+        let valid_key = !key.is_empty()
+            && key.len() <= MAX_KEY_LENGTH
+            && key
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+        if !valid_key {
+            return Err(
+                StoreError::InvalidArgument(format!("Invalid custom field key '{key}'")).into(),
+            );
+        }
+        if value.trim().is_empty() || value.chars().count() > MAX_VALUE_LENGTH {
+            return Err(StoreError::InvalidArgument(format!(
+                "Custom field '{key}' must have a value of 1 to {MAX_VALUE_LENGTH} characters"
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Fields of a new invoice: the customer defaults, overridden by the subscription ones.
+pub fn resolve_invoice_custom_fields(
+    customer_defaults: &CustomFields,
+    subscription_defaults: Option<&CustomFields>,
+) -> CustomFields {
+    let mut fields = customer_defaults.clone();
+    if let Some(subscription_defaults) = subscription_defaults {
+        fields.extend(
+            subscription_defaults
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+    }
+    fields
+}
+
+/// One-line rendering (`key: value, key: value`) for connectors without structured fields.
+pub fn format_custom_fields(fields: &CustomFields) -> Option<String> {
+    if fields.is_empty() {
+        return None;
+    }
+    Some(
+        fields
+            .iter()
+            .map(|(key, value)| format!("{key}: {value}"))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(entries: &[(&str, &str)]) -> CustomFields {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_custom_fields() {
+        assert!(validate_custom_fields(&fields(&[("cost_center", "CC-42")])).is_ok());
+        assert!(validate_custom_fields(&fields(&[("project.code", "P-1")])).is_ok());
+        assert!(validate_custom_fields(&CustomFields::new()).is_ok());
+
+        assert!(validate_custom_fields(&fields(&[("", "x")])).is_err());
+        assert!(validate_custom_fields(&fields(&[("cost center", "x")])).is_err());
+        assert!(validate_custom_fields(&fields(&[("cost_center", " ")])).is_err());
+        assert!(validate_custom_fields(&fields(&[("k", &"x".repeat(256))])).is_err());
+
+        let too_many: CustomFields = (0..=MAX_CUSTOM_FIELDS)
+            .map(|i| (format!("field_{i}"), "x".to_string()))
+            .collect();
+        assert!(validate_custom_fields(&too_many).is_err());
+    }
+
+    #[test]
+    fn test_subscription_fields_override_customer_ones() {
+        let customer = fields(&[("cost_center", "CC-1"), ("contract_id", "C-9")]);
+        let subscription = fields(&[("cost_center", "CC-2"), ("project", "P-3")]);
+
+        assert_eq!(
+            resolve_invoice_custom_fields(&customer, Some(&subscription)),
+            fields(&[
+                ("contract_id", "C-9"),
+                ("cost_center", "CC-2"),
+                ("project", "P-3")
+            ])
+        );
+        assert_eq!(resolve_invoice_custom_fields(&customer, None), customer);
+    }
+
+    #[test]
+    fn test_format_custom_fields() {
+        assert_eq!(format_custom_fields(&CustomFields::new()), None);
+        assert_eq!(
+            format_custom_fields(&fields(&[("project", "P-3"), ("cost_center", "CC-2")])),
+            Some("cost_center: CC-2, project: P-3".to_string())
+        );
+    }
+}
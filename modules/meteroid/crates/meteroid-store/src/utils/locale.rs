@@ -0,0 +1,137 @@
+use crate::StoreResult;
+use crate::constants::Countries;
+use crate::errors::StoreError;
+use common_domain::country::CountryCode;
+use common_utils::lang::match_language;
+use std::collections::{BTreeMap, HashMap};
+
+/// Tenant translations of the invoice and credit note messages, by language tag
+/// (e.g. "de-DE") then by message id (e.g. "bill-to").
+pub type InvoiceTranslations = BTreeMap<String, BTreeMap<String, String>>;
+
+const DEFAULT_LANGUAGE: &str = "en-US";
+
+/// Checks the shape of a BCP 47 language tag: a 2-3 letters language, then optional
+/// alphanumeric subtags ("de", "de-AT", "zh-Hant-TW").
+pub fn validate_language_tag(tag: &str) -> StoreResult<()> {
+    let mut subtags = tag.split('-');
+
+    let valid_language = subtags
+        .next()
+        .is_some_and(|l| (2..=3).contains(&l.len()) && l.chars().all(|c| c.is_ascii_alphabetic()));
+    let valid_subtags =
+        subtags.all(|s| (2..=8).contains(&s.len()) && s.chars().all(|c| c.is_ascii_alphanumeric()));
+
+    if !valid_language || !valid_subtags {
+        return Err(StoreError::InvalidArgument(format!("Invalid language tag '{tag}'")).into());
+    }
+    Ok(())
+}
+
+/// Checks the language tags and message ids of the translations. The ids themselves are
+/// checked against the bundled messages by the API.
+pub fn validate_invoice_translations(translations: &InvoiceTranslations) -> StoreResult<()> {
+    for (lang, messages) in translations {
+        validate_language_tag(lang)?;
+
+        if let Some(id) = messages.keys().find(|id| {
+            id.is_empty()
+                || !id
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        }) {
+            return Err(StoreError::InvalidArgument(format!(
+                "Invalid message id '{id}' in {lang} translations"
+            ))
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Language of the documents and emails sent to a customer: their preference, else the
+/// language of the invoicing entity's country.
+pub fn document_language(customer_language: Option<&str>, entity_country: &CountryCode) -> String {
+    customer_language
+        .filter(|l| !l.is_empty())
+        .map(str::to_string)
+        .or_else(|| Countries::resolve_country(&entity_country.code).map(|c| c.locale.to_string()))
+        .unwrap_or_else(|| DEFAULT_LANGUAGE.to_string())
+}
+
+/// Translations for `lang`, falling back to the ones of another region of the same
+/// language ("de-AT" uses the "de-DE" translations if there are none for "de-AT").
+pub fn translation_overrides_for(
+    translations: &InvoiceTranslations,
+    lang: &str,
+) -> HashMap<String, String> {
+    match_language(translations, lang, |(tag, _)| tag.as_str())
+        .map(|(_, messages)| {
+            messages
+                .iter()
+                .map(|(id, text)| (id.clone(), text.clone()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_validate_language_tag() {
+        assert!(validate_language_tag("de").is_ok());
+        assert!(validate_language_tag("de-AT").is_ok());
+        assert!(validate_language_tag("zh-Hant-TW").is_ok());
+        assert!(validate_language_tag("").is_err());
+        assert!(validate_language_tag("german").is_err());
+        assert!(validate_language_tag("de_AT").is_err());
+        assert!(validate_language_tag("de-").is_err());
+    }
+
+    #[test]
+    fn test_document_language_prefers_customer() {
+        let country = CountryCode::from_str("FR").unwrap();
+
+        assert_eq!(document_language(Some("de-DE"), &country), "de-DE");
+        assert_eq!(document_language(None, &country), "fr-FR");
+        assert_eq!(document_language(Some(""), &country), "fr-FR");
+    }
+
+    #[test]
+    fn test_translation_overrides_match_primary_language() {
+        let translations: InvoiceTranslations = BTreeMap::from([(
+            "de-DE".to_string(),
+            BTreeMap::from([("bill-to".to_string(), "Kunde".to_string())]),
+        )]);
+
+        let exact = translation_overrides_for(&translations, "de-DE");
+        assert_eq!(exact.get("bill-to").map(String::as_str), Some("Kunde"));
+
+        let region = translation_overrides_for(&translations, "de-AT");
+        assert_eq!(region.get("bill-to").map(String::as_str), Some("Kunde"));
+
+        assert!(translation_overrides_for(&translations, "fr-FR").is_empty());
+    }
+
+    #[test]
+    fn test_validate_invoice_translations() {
+        let valid: InvoiceTranslations = BTreeMap::from([(
+            "de-DE".to_string(),
+            BTreeMap::from([("payment-terms-text".to_string(), "{$days} Tage".to_string())]),
+        )]);
+        assert!(validate_invoice_translations(&valid).is_ok());
+
+        let invalid_id: InvoiceTranslations = BTreeMap::from([(
+            "de-DE".to_string(),
+            BTreeMap::from([("Bill To".to_string(), "Kunde".to_string())]),
+        )]);
+        assert!(validate_invoice_translations(&invalid_id).is_err());
+
+        let invalid_lang: InvoiceTranslations =
+            BTreeMap::from([("deutsch".to_string(), BTreeMap::new())]);
+        assert!(validate_invoice_translations(&invalid_lang).is_err());
+    }
+}
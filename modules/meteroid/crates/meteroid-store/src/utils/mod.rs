@@ -1,5 +1,7 @@
 pub mod currency;
+pub mod custom_fields;
 pub mod datetime;
 pub mod errors;
 pub mod local_id;
+pub mod locale;
 pub mod periods;
@@ -1,8 +1,8 @@
 use common_utils::date::NaiveDateExt;
 
-use crate::domain::enums::{BillingPeriodEnum, SubscriptionFeeBillingPeriod};
+use crate::domain::enums::{BillingPeriodEnum, PeriodLength, SubscriptionFeeBillingPeriod};
 use crate::domain::{ComponentPeriods, Period};
-use chrono::{Datelike, Months, NaiveDate};
+use chrono::{Datelike, Days, Months, NaiveDate};
 
 /**
  * For a given invoice date, this checks if a component with this period should be billed in that invoice and what are the related arrear/advance periods
@@ -37,6 +37,9 @@ pub fn calculate_component_period_for_invoice_date(
             arrear: None,
         }),
         Some(billing_period) => {
+            // A resumed subscription restarts its cycles on the resume date: that first
+            // period is prorated like a first cycle and has nothing in arrears yet.
+            let is_restart = cycle_index == 0 || billing_start_or_resume_date == invoice_date;
             let (advance_period, proration_factor) = if is_completed {
                 (None, None)
             } else {
@@ -46,26 +49,22 @@ pub fn calculate_component_period_for_invoice_date(
                     billing_start_or_resume_date == invoice_date,
                     &billing_period,
                 );
-                let proration_factor = if cycle_index == 0 {
-                    subtract_months_at_billing_day(
-                        advance_period.end,
-                        billing_period.as_months(),
-                        billing_day,
-                    )
-                    .and_then(|full_start| {
-                        let full_period = Period {
-                            start: full_start,
-                            end: advance_period.end,
-                        };
-                        calculate_proration_factor(&advance_period, &full_period)
-                    })
+                let proration_factor = if is_restart {
+                    subtract_periods(advance_period.end, 1, billing_period.length(), billing_day)
+                        .and_then(|full_start| {
+                            let full_period = Period {
+                                start: full_start,
+                                end: advance_period.end,
+                            };
+                            calculate_proration_factor(&advance_period, &full_period)
+                        })
                 } else {
                     None
                 };
                 (Some(advance_period), proration_factor)
             };
 
-            let arrear_period = if cycle_index == 0 {
+            let arrear_period = if is_restart {
                 None
             } else {
                 Some(calculate_arrear_period_range(
@@ -106,17 +105,41 @@ fn applies_this_period(
     subscription_billing_period: &BillingPeriodEnum,
     component_billing_period: &SubscriptionFeeBillingPeriod,
 ) -> bool {
-    let months_elapsed = (subscription_billing_period.as_months() * cycle_index) as i32;
-    months_elapsed % component_billing_period.as_months() == 0
+    match (
+        subscription_billing_period.length(),
+        component_billing_period.length(),
+    ) {
+        // One-time fees only bill on the first cycle
+        (_, None) => cycle_index == 0,
+        (PeriodLength::Months(sub), Some(PeriodLength::Months(component)))
+        | (PeriodLength::Days(sub), Some(PeriodLength::Days(component))) => {
+            (sub * cycle_index).is_multiple_of(component)
+        }
+        // Day- and month-based cadences never share cycle boundaries. Subscriptions mixing
+        // them are rejected at creation (see `validate_billing_periods`).
+        _ => true,
+    }
 }
 
+/// Day-based periods ignore `billing_day` and `is_partial`: they always span their full
+/// length from `invoice_date`.
 pub fn calculate_advance_period_range(
     invoice_date: NaiveDate,
     billing_day: u32,
     is_partial: bool,
     billing_period: &BillingPeriodEnum,
 ) -> Period {
-    let months_per_period = billing_period.as_months();
+    let months_per_period = match billing_period.length() {
+        PeriodLength::Months(months) => months,
+        PeriodLength::Days(days) => {
+            return Period {
+                start: invoice_date,
+                end: invoice_date
+                    .checked_add_days(Days::new(u64::from(days)))
+                    .expect("Failed to calculate period end date"),
+            };
+        }
+    };
 
     // Check if billing started on or after the target billing day
     let started_after_billing_day = invoice_date.day() >= billing_day;
@@ -219,9 +242,10 @@ pub fn find_period_containing_date(
         billing_period,
     );
 
-    let period_start = add_months_at_billing_day(
+    let period_start = add_periods(
         first_full_start,
-        cycle_index * billing_period.as_months(),
+        cycle_index,
+        billing_period.length(),
         billing_day_anchor,
     )
     .expect("Failed to calculate period start");
@@ -237,7 +261,10 @@ fn count_full_periods_up_to(
     billing_day: u32,
     billing_period: &BillingPeriodEnum,
 ) -> u32 {
-    let months_per_period = billing_period.as_months();
+    let months_per_period = match billing_period.length() {
+        PeriodLength::Months(months) => months,
+        PeriodLength::Days(days) => return elapsed_days(period_origin, target_date) / days,
+    };
     let total_months = (target_date.year() - period_origin.year()) * 12
         + (target_date.month() as i32 - period_origin.month() as i32);
 
@@ -287,7 +314,10 @@ fn find_period_index_at(
     billing_day: u32,
     billing_period: &BillingPeriodEnum,
 ) -> u32 {
-    let months_per_period = billing_period.as_months();
+    let months_per_period = match billing_period.length() {
+        PeriodLength::Months(months) => months,
+        PeriodLength::Days(days) => return elapsed_days(period_origin, target_date) / days,
+    };
     let total_months = (target_date.year() - period_origin.year()) * 12
         + (target_date.month() as i32 - period_origin.month() as i32);
 
@@ -355,6 +385,55 @@ pub fn calculate_arrear_period_range(
     }
 }
 
+/// First renewal date on or after `target_date`, walking forward from the current
+/// period end the same way the lifecycle renews (see `calculate_advance_period_range`).
+pub fn first_renewal_on_or_after(
+    current_period_end: NaiveDate,
+    target_date: NaiveDate,
+    billing_day_anchor: u32,
+    billing_period: &BillingPeriodEnum,
+) -> NaiveDate {
+    let mut renewal = current_period_end;
+    while renewal < target_date {
+        renewal =
+            calculate_advance_period_range(renewal, billing_day_anchor, false, billing_period).end;
+    }
+    renewal
+}
+
+/// Whole days from `from` to `to`, zero when `to` is not after `from`.
+fn elapsed_days(from: NaiveDate, to: NaiveDate) -> u32 {
+    u32::try_from(to.signed_duration_since(from).num_days()).unwrap_or(0)
+}
+
+fn add_periods(
+    date: NaiveDate,
+    count: u32,
+    length: PeriodLength,
+    billing_day: u32,
+) -> Option<NaiveDate> {
+    match length {
+        PeriodLength::Months(months) => {
+            add_months_at_billing_day(date, count * months, billing_day)
+        }
+        PeriodLength::Days(days) => date.checked_add_days(Days::new(u64::from(count * days))),
+    }
+}
+
+pub(crate) fn subtract_periods(
+    date: NaiveDate,
+    count: u32,
+    length: PeriodLength,
+    billing_day: u32,
+) -> Option<NaiveDate> {
+    match length {
+        PeriodLength::Months(months) => {
+            subtract_months_at_billing_day(date, count * months, billing_day)
+        }
+        PeriodLength::Days(days) => date.checked_sub_days(Days::new(u64::from(count * days))),
+    }
+}
+
 fn add_months_at_billing_day(
     date: NaiveDate,
     months_to_add: u32,
@@ -378,7 +457,7 @@ mod test {
     use super::{
         Period, calculate_advance_period_range, calculate_arrear_period_range,
         calculate_component_period_for_invoice_date, calculate_elapsed_cycles,
-        calculate_proration_factor, find_period_containing_date,
+        calculate_proration_factor, find_period_containing_date, first_renewal_on_or_after,
     };
     use crate::domain::enums::{BillingPeriodEnum, SubscriptionFeeBillingPeriod};
 
@@ -535,6 +614,31 @@ mod test {
             "2023-01-01",
             "2024-01-01"
         )]
+        // Day-based periods ignore the billing day and never start partial
+        #[case(
+            BillingPeriodEnum::Weekly,
+            "2024-01-10", // invoice_date
+            1,
+            true,
+            "2024-01-10",
+            "2024-01-17"
+        )]
+        #[case(
+            BillingPeriodEnum::Weekly,
+            "2024-02-28", // invoice_date
+            1,
+            false,
+            "2024-02-28",
+            "2024-03-06"
+        )]
+        #[case(
+            BillingPeriodEnum::Daily,
+            "2024-12-31", // invoice_date
+            15,
+            true,
+            "2024-12-31",
+            "2025-01-01"
+        )]
         fn test_calculate_advance_period_range(
             #[case] billing_period: BillingPeriodEnum,
             #[case] invoice_date: NaiveDate,
@@ -716,6 +820,11 @@ mod test {
         #[case(BillingPeriodEnum::Monthly, "2024-01-31", "2024-02-29", 31, 1)]
         // Next: [Feb29, Mar31). add_months(Feb29, 1, 31) = Mar31.
         #[case(BillingPeriodEnum::Monthly, "2024-01-31", "2024-03-31", 31, 2)]
+        // Weekly and daily count whole days regardless of the billing day
+        #[case(BillingPeriodEnum::Weekly, "2024-01-10", "2024-01-16", 1, 0)]
+        #[case(BillingPeriodEnum::Weekly, "2024-01-10", "2024-01-17", 1, 1)]
+        #[case(BillingPeriodEnum::Weekly, "2024-01-10", "2024-03-01", 15, 7)]
+        #[case(BillingPeriodEnum::Daily, "2024-02-27", "2024-03-02", 1, 4)]
         fn test_calculate_elapsed_cycles(
             #[case] billing_period: BillingPeriodEnum,
             #[case] billing_start: NaiveDate,
@@ -731,6 +840,76 @@ mod test {
             );
         }
 
+        // ─── Tests for first_renewal_on_or_after ───
+
+        #[rstest]
+        // Target before the current period end → the current period end
+        #[case(
+            BillingPeriodEnum::Monthly,
+            "2024-02-01",
+            "2024-01-20",
+            1,
+            "2024-02-01"
+        )]
+        // Target exactly on a renewal → that renewal
+        #[case(
+            BillingPeriodEnum::Monthly,
+            "2024-02-01",
+            "2024-02-01",
+            1,
+            "2024-02-01"
+        )]
+        #[case(
+            BillingPeriodEnum::Monthly,
+            "2024-02-01",
+            "2024-04-01",
+            1,
+            "2024-04-01"
+        )]
+        // Mid-period target → end of the period containing it
+        #[case(
+            BillingPeriodEnum::Monthly,
+            "2024-02-01",
+            "2024-03-15",
+            1,
+            "2024-04-01"
+        )]
+        #[case(
+            BillingPeriodEnum::Quarterly,
+            "2024-04-01",
+            "2024-08-10",
+            1,
+            "2024-10-01"
+        )]
+        #[case(
+            BillingPeriodEnum::Annual,
+            "2025-01-15",
+            "2025-03-01",
+            15,
+            "2026-01-15"
+        )]
+        // Anchor clamped in short months
+        #[case(
+            BillingPeriodEnum::Monthly,
+            "2024-01-31",
+            "2024-03-01",
+            31,
+            "2024-03-31"
+        )]
+        #[case(BillingPeriodEnum::Weekly, "2024-01-17", "2024-02-01", 1, "2024-02-07")]
+        fn test_first_renewal_on_or_after(
+            #[case] billing_period: BillingPeriodEnum,
+            #[case] current_period_end: NaiveDate,
+            #[case] target: NaiveDate,
+            #[case] billing_day: u32,
+            #[case] expected: NaiveDate,
+        ) {
+            assert_eq!(
+                first_renewal_on_or_after(current_period_end, target, billing_day, &billing_period),
+                expected
+            );
+        }
+
         // ─── Tests for find_period_containing_date ───
 
         #[rstest]
@@ -967,6 +1146,8 @@ mod test {
         #[case(BillingPeriodEnum::Quarterly, "2022-01-01", "2024-10-01", 1)]
         #[case(BillingPeriodEnum::Annual, "2020-01-01", "2024-06-15", 1)]
         #[case(BillingPeriodEnum::Monthly, "2024-01-01", "2024-01-31", 10)]
+        #[case(BillingPeriodEnum::Weekly, "2023-11-29", "2024-06-15", 1)]
+        #[case(BillingPeriodEnum::Daily, "2024-02-20", "2024-03-05", 1)]
         fn test_elapsed_and_period_consistency(
             #[case] billing_period: BillingPeriodEnum,
             #[case] billing_start: NaiveDate,
@@ -1137,5 +1318,55 @@ mod test {
 
             assert_eq!(periods.proration_factor, None);
         }
+
+        #[test]
+        fn resumed_cycle_is_prorated_without_arrears() {
+            let invoice_date = NaiveDate::from_ymd_opt(2025, 6, 16).unwrap();
+            let periods = calculate_component_period_for_invoice_date(
+                invoice_date,
+                &BillingPeriodEnum::Monthly,
+                &SubscriptionFeeBillingPeriod::Monthly,
+                invoice_date,
+                4,
+                1,
+                false,
+            )
+            .expect("should return periods");
+
+            assert!(periods.arrear.is_none());
+            let advance = periods.advance.expect("should have advance");
+            assert_eq!(advance.start, invoice_date);
+            assert_eq!(advance.end, NaiveDate::from_ymd_opt(2025, 7, 1).unwrap());
+
+            let factor = periods.proration_factor.expect("should be prorated");
+            assert!((factor - 15.0 / 30.0).abs() < 1e-9, "got {factor}");
+        }
+
+        #[test]
+        fn weekly_fee_on_daily_subscription_bills_every_seventh_cycle() {
+            let start = NaiveDate::from_ymd_opt(2025, 3, 3).unwrap();
+            let periods_at = |cycle_index: u32| {
+                calculate_component_period_for_invoice_date(
+                    start + chrono::Days::new(u64::from(cycle_index)),
+                    &BillingPeriodEnum::Daily,
+                    &SubscriptionFeeBillingPeriod::Weekly,
+                    start,
+                    cycle_index,
+                    1,
+                    false,
+                )
+            };
+
+            let first = periods_at(0).expect("should bill on the first cycle");
+            assert_eq!(first.proration_factor, None);
+            assert_eq!(
+                first.advance.map(|p| p.end),
+                NaiveDate::from_ymd_opt(2025, 3, 10)
+            );
+            assert!((1..7).all(|cycle| periods_at(cycle).is_none()));
+
+            let second = periods_at(7).expect("should bill on the eighth cycle");
+            assert_eq!(second.arrear, Some(p("2025-03-03", "2025-03-10")));
+        }
     }
 }
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS price_change;
+DROP TYPE IF EXISTS "PriceChangeStatusEnum";
+DROP TYPE IF EXISTS "PriceChangeGrandfatheringEnum";
+
+-- Note: PostgreSQL cannot remove an enum value; APPLY_PRICE_CHANGE remains on
+-- "ScheduledEventTypeEnum" but is harmless if unused.
@@ -0,0 +1,49 @@
+-- Scheduled future price changes on catalog prices.
+-- The new pricing is kept on the change and only materialized as a `price` row
+-- (with the pre-allocated `new_price_id`) once it applies, so it never shows up
+-- in the catalog ahead of its effective date.
+
+CREATE TYPE "PriceChangeGrandfatheringEnum" AS ENUM (
+    'ALL_SUBSCRIPTIONS',
+    'NEW_SUBSCRIPTIONS_ONLY',
+    'RENEWING_AFTER'
+);
+
+CREATE TYPE "PriceChangeStatusEnum" AS ENUM (
+    'SCHEDULED',
+    'APPLIED',
+    'CANCELLED'
+);
+
+CREATE TABLE price_change (
+    id UUID PRIMARY KEY,
+    tenant_id UUID NOT NULL REFERENCES tenant(id) ON DELETE CASCADE,
+    product_id UUID NOT NULL REFERENCES product(id) ON DELETE CASCADE,
+    price_id UUID NOT NULL REFERENCES price(id) ON DELETE CASCADE,
+    new_price_id UUID NOT NULL,
+    new_pricing JSONB NOT NULL,
+    effective_date DATE NOT NULL,
+    grandfathering "PriceChangeGrandfatheringEnum" NOT NULL,
+    -- Only set for RENEWING_AFTER: existing subscriptions move at their first renewal on or after this date.
+    renewing_after DATE,
+    status "PriceChangeStatusEnum" NOT NULL DEFAULT 'SCHEDULED',
+    created_by UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    applied_at TIMESTAMP,
+    cancelled_at TIMESTAMP,
+
+    CONSTRAINT price_change_renewing_after_check
+        CHECK ((grandfathering = 'RENEWING_AFTER') = (renewing_after IS NOT NULL))
+);
+
+-- A price can only have one pending change at a time.
+CREATE UNIQUE INDEX idx_price_change_scheduled_price
+    ON price_change (price_id) WHERE status = 'SCHEDULED';
+
+CREATE INDEX idx_price_change_due
+    ON price_change (effective_date) WHERE status = 'SCHEDULED';
+
+CREATE INDEX idx_price_change_tenant_product
+    ON price_change (tenant_id, product_id);
+
+ALTER TYPE "ScheduledEventTypeEnum" ADD VALUE IF NOT EXISTS 'APPLY_PRICE_CHANGE';
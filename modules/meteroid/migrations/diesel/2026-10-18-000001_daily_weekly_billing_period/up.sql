@@ -0,0 +1,4 @@
+ALTER TYPE "BillingPeriodEnum" ADD VALUE 'DAILY' BEFORE 'MONTHLY';
+ALTER TYPE "BillingPeriodEnum" ADD VALUE 'WEEKLY' BEFORE 'MONTHLY';
+ALTER TYPE "SubscriptionFeeBillingPeriod" ADD VALUE 'DAILY' BEFORE 'MONTHLY';
+ALTER TYPE "SubscriptionFeeBillingPeriod" ADD VALUE 'WEEKLY' BEFORE 'MONTHLY';
@@ -0,0 +1,3 @@
+ALTER TABLE customer DROP COLUMN IF EXISTS custom_attributes;
+
+ALTER TABLE price DROP COLUMN IF EXISTS variants;
@@ -0,0 +1,7 @@
+-- Alternative pricings of a price, selected from the customer billing country or a
+-- customer custom attribute (see PriceVariant in the store domain).
+ALTER TABLE price
+  ADD COLUMN variants JSONB NOT NULL DEFAULT '[]'::jsonb;
+
+ALTER TABLE customer
+  ADD COLUMN custom_attributes JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,2 @@
+-- Note: PostgreSQL cannot remove an enum value; RESUME_SUBSCRIPTION remains on
+-- "ScheduledEventTypeEnum" but is harmless if unused.
@@ -0,0 +1,3 @@
+-- Scheduled resumption of a paused subscription, either requested directly or
+-- set up as the auto-resume date of a pause.
+ALTER TYPE "ScheduledEventTypeEnum" ADD VALUE IF NOT EXISTS 'RESUME_SUBSCRIPTION';
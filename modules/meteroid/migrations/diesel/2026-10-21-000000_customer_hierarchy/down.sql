@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS customer_parent_customer_id_idx;
+
+ALTER TABLE customer
+  DROP COLUMN IF EXISTS pool_usage_with_parent,
+  DROP COLUMN IF EXISTS parent_customer_id;
@@ -0,0 +1,8 @@
+-- Child customers are billed to their parent: invoices of their subscriptions are addressed
+-- to the parent and charged against its payment method. Hierarchies are one level deep.
+ALTER TABLE customer
+  ADD COLUMN parent_customer_id UUID REFERENCES customer (id) ON DELETE RESTRICT,
+  ADD COLUMN pool_usage_with_parent BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX customer_parent_customer_id_idx ON customer (parent_customer_id)
+  WHERE parent_customer_id IS NOT NULL;
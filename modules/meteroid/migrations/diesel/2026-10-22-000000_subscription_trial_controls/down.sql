@@ -0,0 +1,3 @@
+ALTER TABLE subscription
+  DROP COLUMN IF EXISTS notified_trial_end,
+  DROP COLUMN IF EXISTS trialing_plan_id;
@@ -0,0 +1,7 @@
+-- Per-subscription override of the plan version's trialing plan, set when the trialing plan
+-- is switched mid-trial.
+-- notified_trial_end is the trial end date the trial-will-end notification was sent for, so
+-- that extending a trial notifies the customer again before the new end.
+ALTER TABLE subscription
+  ADD COLUMN trialing_plan_id UUID REFERENCES plan (id) ON DELETE SET NULL,
+  ADD COLUMN notified_trial_end DATE;
@@ -0,0 +1,11 @@
+ALTER TABLE subscription
+  DROP COLUMN IF EXISTS renewal_notified_for,
+  DROP COLUMN IF EXISTS renewal_opt_out_days,
+  DROP COLUMN IF EXISTS renewal_notice_days,
+  DROP COLUMN IF EXISTS renewal_plan_version_id,
+  DROP COLUMN IF EXISTS renewal_term_months,
+  DROP COLUMN IF EXISTS renewal_policy;
+
+DROP TYPE IF EXISTS "RenewalPolicyEnum";
+
+-- you can't remove enum values in postgres
@@ -0,0 +1,21 @@
+-- What happens when a fixed-term subscription reaches its end_date.
+-- EXPIRE: the subscription completes (previous behaviour).
+-- RENEW_SAME_TERM: end_date moves by renewal_term_months.
+-- RENEW_MONTH_TO_MONTH: end_date moves by one month at a time.
+-- RENEW_TO_PLAN_VERSION: renews for renewal_term_months on renewal_plan_version_id, then keeps
+-- renewing for the same term.
+CREATE TYPE "RenewalPolicyEnum" AS ENUM ('EXPIRE', 'RENEW_SAME_TERM', 'RENEW_MONTH_TO_MONTH', 'RENEW_TO_PLAN_VERSION');
+
+-- renewal_notice_days: how long before the end of the term the renewal-upcoming notification is sent.
+-- renewal_opt_out_days: how long before the end of the term renewal can still be opted out of.
+-- renewal_notified_for is the end of term the renewal-upcoming notification was sent for.
+ALTER TABLE subscription
+  ADD COLUMN renewal_policy "RenewalPolicyEnum" NOT NULL DEFAULT 'EXPIRE',
+  ADD COLUMN renewal_term_months INTEGER,
+  ADD COLUMN renewal_plan_version_id UUID REFERENCES plan_version (id) ON DELETE SET NULL,
+  ADD COLUMN renewal_notice_days INTEGER,
+  ADD COLUMN renewal_opt_out_days INTEGER,
+  ADD COLUMN renewal_notified_for DATE;
+
+-- Renewals are recorded in the subscription history, like plan switches.
+ALTER TYPE "SubscriptionEventType" ADD VALUE 'RENEWED';
@@ -0,0 +1,6 @@
+DROP INDEX IF EXISTS subscription_customer_instance_unique;
+
+ALTER TABLE subscription
+  DROP COLUMN IF EXISTS instance_metadata,
+  DROP COLUMN IF EXISTS instance_usage_property,
+  DROP COLUMN IF EXISTS instance_id;
@@ -0,0 +1,19 @@
+-- Independent instances of the same plan for a customer (e.g. one per project).
+-- instance_id identifies the instance among the customer's subscriptions, and in usage events:
+-- usage components only bill the events whose instance_usage_property (default 'instance_id')
+-- matches it.
+-- instance_metadata holds free-form key/values describing the instance.
+ALTER TABLE subscription
+  ADD COLUMN instance_id TEXT,
+  ADD COLUMN instance_usage_property TEXT,
+  ADD COLUMN instance_metadata JSONB;
+
+-- An instance identifier is used by a single live subscription of the customer.
+CREATE UNIQUE INDEX subscription_customer_instance_unique
+ON subscription (tenant_id, customer_id, instance_id)
+WHERE instance_id IS NOT NULL
+  AND status NOT IN (
+    'CANCELLED'::"SubscriptionStatusEnum",
+    'COMPLETED'::"SubscriptionStatusEnum",
+    'SUPERSEDED'::"SubscriptionStatusEnum"
+  );
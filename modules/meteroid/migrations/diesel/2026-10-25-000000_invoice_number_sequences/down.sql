@@ -0,0 +1,11 @@
+DROP TABLE IF EXISTS invoice_number_sequence;
+
+ALTER TABLE subscription
+  DROP COLUMN IF EXISTS invoice_number_prefix;
+
+ALTER TABLE invoicing_entity
+  DROP COLUMN IF EXISTS credit_note_number_reset,
+  DROP COLUMN IF EXISTS credit_note_number_pattern,
+  DROP COLUMN IF EXISTS invoice_number_reset;
+
+DROP TYPE IF EXISTS "NumberingResetEnum";
@@ -0,0 +1,26 @@
+-- When the numbering sequence of a document series starts over.
+-- NEVER: a single sequence (next_invoice_number / next_credit_note_number on the entity).
+-- YEARLY / MONTHLY: a sequence per calendar year / month of the document date.
+CREATE TYPE "NumberingResetEnum" AS ENUM ('NEVER', 'YEARLY', 'MONTHLY');
+
+ALTER TABLE invoicing_entity
+  ADD COLUMN invoice_number_reset "NumberingResetEnum" NOT NULL DEFAULT 'NEVER',
+  ADD COLUMN credit_note_number_pattern TEXT NOT NULL DEFAULT 'CN-{number:6}',
+  ADD COLUMN credit_note_number_reset "NumberingResetEnum" NOT NULL DEFAULT 'NEVER';
+
+-- Substituted to {prefix} in the invoice number pattern, with its own sequence.
+ALTER TABLE subscription
+  ADD COLUMN invoice_number_prefix TEXT;
+
+-- Sequences of the numbering scopes other than the entity-wide one: a reset period
+-- (e.g. '2026' or '2026-03') and/or a subscription prefix.
+-- next_number is the number the next finalized document of the scope receives.
+CREATE TABLE invoice_number_sequence
+(
+  invoicing_entity_id UUID   NOT NULL REFERENCES invoicing_entity (id) ON DELETE CASCADE,
+  series              TEXT   NOT NULL CHECK (series IN ('INVOICE', 'CREDIT_NOTE')),
+  scope               TEXT   NOT NULL,
+  next_number         BIGINT NOT NULL,
+  tenant_id           UUID   NOT NULL REFERENCES tenant (id) ON DELETE CASCADE,
+  PRIMARY KEY (invoicing_entity_id, series, scope)
+);
@@ -0,0 +1,5 @@
+ALTER TABLE invoicing_entity
+  DROP COLUMN invoice_translations;
+
+ALTER TABLE customer
+  DROP COLUMN invoice_language;
@@ -0,0 +1,10 @@
+-- Language invoices, credit notes and customer emails are issued in (e.g. 'de-DE').
+-- NULL falls back to the locale of the invoicing entity country.
+ALTER TABLE customer
+  ADD COLUMN invoice_language TEXT;
+
+-- Tenant-provided translations, keyed by language then message id:
+-- {"de-DE": {"bill-to": "Kunde"}, "sv-SE": {"invoice-title": "Faktura", ...}}.
+-- Overrides the bundled strings, or adds a language that is not bundled.
+ALTER TABLE invoicing_entity
+  ADD COLUMN invoice_translations JSONB NOT NULL DEFAULT '{}'::jsonb;
@@ -0,0 +1,2 @@
+ALTER TABLE invoicing_entity
+  DROP COLUMN invoice_template;
@@ -0,0 +1,5 @@
+-- Typst source replacing the built-in invoice layout of the entity. It is compiled next to the
+-- built-in templates, so it can `#import "invoice.typ"` and only restyle parts of it.
+-- NULL renders the default template.
+ALTER TABLE invoicing_entity
+  ADD COLUMN invoice_template TEXT;
@@ -0,0 +1,11 @@
+ALTER TABLE price_component
+  DROP COLUMN line_metadata;
+
+ALTER TABLE subscription
+  DROP COLUMN invoice_custom_fields;
+
+ALTER TABLE customer
+  DROP COLUMN invoice_custom_fields;
+
+ALTER TABLE invoice
+  DROP COLUMN custom_fields;
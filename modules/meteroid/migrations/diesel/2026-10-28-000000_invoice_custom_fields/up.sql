@@ -0,0 +1,16 @@
+-- Structured fields of the invoice (cost center, project code, contract id...):
+-- {"cost_center": "CC-42", "project": "P-7"}.
+-- Set from the customer defaults overridden by the subscription ones, editable on drafts.
+-- Line items carry their own fields in line_items[].metadata, set from the metadata of their
+-- price component overridden by the one of the subscription instance.
+ALTER TABLE invoice
+  ADD COLUMN custom_fields JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE customer
+  ADD COLUMN invoice_custom_fields JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE subscription
+  ADD COLUMN invoice_custom_fields JSONB NOT NULL DEFAULT '{}'::jsonb;
+
+ALTER TABLE price_component
+  ADD COLUMN line_metadata JSONB NOT NULL DEFAULT '{}'::jsonb;
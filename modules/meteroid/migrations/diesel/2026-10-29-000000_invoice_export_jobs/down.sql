@@ -0,0 +1,3 @@
+ALTER TABLE batch_job DROP COLUMN IF EXISTS output_key;
+
+-- PostgreSQL cannot remove enum values; INVOICE_EXPORT remains in the enum unused.
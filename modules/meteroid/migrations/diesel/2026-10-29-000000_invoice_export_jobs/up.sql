@@ -0,0 +1,4 @@
+ALTER TYPE "BatchJobTypeEnum" ADD VALUE IF NOT EXISTS 'INVOICE_EXPORT';
+
+-- Document produced by the job (e.g. the ZIP archive of an invoice export)
+ALTER TABLE batch_job ADD COLUMN output_key TEXT;
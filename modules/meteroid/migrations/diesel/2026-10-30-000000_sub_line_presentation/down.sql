@@ -0,0 +1,5 @@
+ALTER TABLE invoice
+  DROP COLUMN sub_line_presentations;
+
+ALTER TABLE price_component
+  DROP COLUMN sub_line_presentation;
@@ -0,0 +1,10 @@
+-- How the usage lines of a component are shown on invoice documents, the portal and the
+-- upcoming invoice: {"mode": "detailed" | "collapsed" | "tiers_only"} or
+-- {"mode": "top_groups", "limit": 5}. Stored invoice lines always keep the full detail.
+ALTER TABLE price_component
+  ADD COLUMN sub_line_presentation JSONB NOT NULL DEFAULT '{"mode": "detailed"}'::jsonb;
+
+-- Presentation of the components snapshotted when the invoice is finalized, so that documents
+-- keep their layout when a component is edited. NULL on drafts, which use the current one.
+ALTER TABLE invoice
+  ADD COLUMN sub_line_presentations JSONB;
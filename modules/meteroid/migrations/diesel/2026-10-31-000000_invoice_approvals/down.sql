@@ -0,0 +1,15 @@
+ALTER TABLE invoice
+  DROP COLUMN approval_comment,
+  DROP COLUMN approval_decided_by,
+  DROP COLUMN approval_decided_at,
+  DROP COLUMN approval_requested_at,
+  DROP COLUMN approval_status;
+
+ALTER TABLE customer
+  DROP COLUMN invoice_approval_required;
+
+ALTER TABLE invoicing_entity
+  DROP COLUMN invoice_approver_ids,
+  DROP COLUMN invoice_approval_threshold;
+
+DROP TYPE IF EXISTS "InvoiceApprovalStatusEnum";
@@ -0,0 +1,19 @@
+CREATE TYPE "InvoiceApprovalStatusEnum" AS ENUM ('PENDING', 'APPROVED', 'REJECTED');
+
+-- Invoices of at least invoice_approval_threshold (in the accounting currency, in cents), or of a
+-- customer with invoice_approval_required, wait for an approver before being finalized.
+-- Approvers are users of the organization; when none are designated, its admins approve.
+ALTER TABLE invoicing_entity
+  ADD COLUMN invoice_approval_threshold BIGINT,
+  ADD COLUMN invoice_approver_ids UUID[] NOT NULL DEFAULT '{}';
+
+ALTER TABLE customer
+  ADD COLUMN invoice_approval_required BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- NULL while no approval was requested. A rejected invoice stays a draft.
+ALTER TABLE invoice
+  ADD COLUMN approval_status "InvoiceApprovalStatusEnum",
+  ADD COLUMN approval_requested_at TIMESTAMP,
+  ADD COLUMN approval_decided_at TIMESTAMP,
+  ADD COLUMN approval_decided_by UUID REFERENCES "user"(id) ON DELETE SET NULL,
+  ADD COLUMN approval_comment TEXT;
@@ -21,6 +21,7 @@ impl From<BatchJobTypeWrapper> for BatchJobTypeEnum {
             proto::BatchJobType::SubscriptionPlanMigration => {
                 BatchJobTypeEnum::SubscriptionPlanMigration
             }
+            proto::BatchJobType::InvoiceExport => BatchJobTypeEnum::InvoiceExport,
         }
     }
 }
@@ -34,6 +35,7 @@ impl From<BatchJobTypeEnum> for BatchJobTypeWrapper {
             BatchJobTypeEnum::SubscriptionPlanMigration => {
                 proto::BatchJobType::SubscriptionPlanMigration
             }
+            BatchJobTypeEnum::InvoiceExport => proto::BatchJobType::InvoiceExport,
         })
     }
 }
@@ -95,6 +97,12 @@ pub fn batch_job_to_proto(
         None
     };
 
+    let output_token = if job.output_key.is_some() {
+        generate_output_token(job.id, job.tenant_id, jwt_secret).ok()
+    } else {
+        None
+    };
+
     proto::BatchJob {
         id: job.id.to_string(),
         tenant_id: job.tenant_id.as_proto(),
@@ -111,6 +119,7 @@ pub fn batch_job_to_proto(
         input_file_name: job.input_file_name,
         created_by_display_name,
         input_file_token,
+        output_token,
     }
 }
 
@@ -164,6 +173,37 @@ pub fn generate_error_csv_url(
     ))
 }
 
+pub fn generate_output_token(
+    job_id: common_domain::ids::BatchJobId,
+    tenant_id: common_domain::ids::TenantId,
+    jwt_secret: &SecretString,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let exp = (chrono::Utc::now() + chrono::Duration::days(7)).timestamp() as usize;
+    let claims = ShareableEntityClaims {
+        exp,
+        sub: format!("output-{}", job_id),
+        entity_id: job_id.as_uuid(),
+        tenant_id,
+    };
+    jsonwebtoken::encode(
+        &jsonwebtoken::Header::default(),
+        &claims,
+        &jsonwebtoken::EncodingKey::from_secret(jwt_secret.expose_secret().as_bytes()),
+    )
+}
+
+pub fn generate_output_url(
+    job_id: common_domain::ids::BatchJobId,
+    tenant_id: common_domain::ids::TenantId,
+    jwt_secret: &SecretString,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let token = generate_output_token(job_id, tenant_id, jwt_secret)?;
+    Ok(format!(
+        "/files/v1/batch-job/output/{}?token={}",
+        job_id, token
+    ))
+}
+
 pub fn batch_job_chunk_to_proto(chunk: domain::BatchJobChunk) -> proto::BatchJobChunk {
     let events = chunk
         .events
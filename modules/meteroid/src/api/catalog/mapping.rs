@@ -0,0 +1,44 @@
+pub mod catalog {
+    use meteroid_grpc::meteroid::api::catalog::v1 as server;
+    use meteroid_store::domain::catalog as domain;
+
+    pub fn format_from_proto(value: server::CatalogFormat) -> domain::CatalogFormat {
+        match value {
+            server::CatalogFormat::Yaml => domain::CatalogFormat::Yaml,
+            server::CatalogFormat::Json => domain::CatalogFormat::Json,
+        }
+    }
+
+    fn entity_to_proto(value: domain::CatalogEntity) -> server::CatalogEntity {
+        match value {
+            domain::CatalogEntity::Feature => server::CatalogEntity::Feature,
+            domain::CatalogEntity::ProductFamily => server::CatalogEntity::ProductFamily,
+            domain::CatalogEntity::Product => server::CatalogEntity::Product,
+            domain::CatalogEntity::AddOn => server::CatalogEntity::AddOn,
+            domain::CatalogEntity::Plan => server::CatalogEntity::Plan,
+            domain::CatalogEntity::Coupon => server::CatalogEntity::Coupon,
+        }
+    }
+
+    fn action_to_proto(value: domain::CatalogAction) -> server::CatalogAction {
+        match value {
+            domain::CatalogAction::Create => server::CatalogAction::Create,
+            domain::CatalogAction::Update => server::CatalogAction::Update,
+            domain::CatalogAction::Unchanged => server::CatalogAction::Unchanged,
+            domain::CatalogAction::Conflict => server::CatalogAction::Conflict,
+            domain::CatalogAction::Archive => server::CatalogAction::Archive,
+        }
+    }
+
+    pub fn changes_to_proto(diff: domain::CatalogDiff) -> Vec<server::CatalogChange> {
+        diff.changes
+            .into_iter()
+            .map(|change| server::CatalogChange {
+                entity: entity_to_proto(change.entity).into(),
+                key: change.key,
+                action: action_to_proto(change.action).into(),
+                details: change.details,
+            })
+            .collect()
+    }
+}
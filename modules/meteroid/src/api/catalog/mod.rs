@@ -0,0 +1,15 @@
+use meteroid_grpc::meteroid::api::catalog::v1::catalog_service_server::CatalogServiceServer;
+use meteroid_store::Services;
+
+mod error;
+mod mapping;
+mod service;
+
+pub struct CatalogServiceComponents {
+    pub services: Services,
+}
+
+pub fn service(services: Services) -> CatalogServiceServer<CatalogServiceComponents> {
+    let inner = CatalogServiceComponents { services };
+    CatalogServiceServer::new(inner)
+}
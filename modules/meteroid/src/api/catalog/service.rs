@@ -0,0 +1,78 @@
+use common_grpc::middleware::server::auth::RequestExt;
+use meteroid_grpc::meteroid::api::catalog::v1::{
+    ApplyCatalogRequest, ApplyCatalogResponse, DiffCatalogRequest, DiffCatalogResponse,
+    ExportCatalogRequest, ExportCatalogResponse, catalog_service_server::CatalogService,
+};
+use meteroid_store::domain::catalog::CatalogDocument;
+use tonic::{Request, Response, Status};
+
+use crate::api::catalog::error::CatalogApiError;
+use crate::api::catalog::mapping::catalog::{changes_to_proto, format_from_proto};
+
+use super::CatalogServiceComponents;
+
+#[tonic::async_trait]
+impl CatalogService for CatalogServiceComponents {
+    #[tracing::instrument(skip_all)]
+    async fn export_catalog(
+        &self,
+        request: Request<ExportCatalogRequest>,
+    ) -> Result<Response<ExportCatalogResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let req = request.into_inner();
+
+        let document = self
+            .services
+            .export_catalog(tenant_id)
+            .await
+            .and_then(|doc| doc.render(format_from_proto(req.format())))
+            .map_err(Into::<CatalogApiError>::into)?;
+
+        Ok(Response::new(ExportCatalogResponse { document }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn diff_catalog(
+        &self,
+        request: Request<DiffCatalogRequest>,
+    ) -> Result<Response<DiffCatalogResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let req = request.into_inner();
+
+        let document = CatalogDocument::parse(&req.document, format_from_proto(req.format()))
+            .map_err(Into::<CatalogApiError>::into)?;
+
+        let diff = self
+            .services
+            .diff_catalog(tenant_id, &document, req.prune)
+            .await
+            .map_err(Into::<CatalogApiError>::into)?;
+
+        Ok(Response::new(DiffCatalogResponse {
+            changes: changes_to_proto(diff),
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn apply_catalog(
+        &self,
+        request: Request<ApplyCatalogRequest>,
+    ) -> Result<Response<ApplyCatalogResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor = request.actor_typed()?;
+        let req = request.into_inner();
+
+        let document = CatalogDocument::parse(&req.document, format_from_proto(req.format()))
+            .map_err(Into::<CatalogApiError>::into)?;
+
+        let diff = self
+            .services
+            .apply_catalog(tenant_id, actor, &document, req.prune)
+            .await
+            .map_err(Into::<CatalogApiError>::into)?;
+
+        Ok(Response::new(ApplyCatalogResponse {
+            changes: changes_to_proto(diff),
+        }))
+    }
+}
@@ -158,6 +158,12 @@ pub mod customer {
                 customer_connections: Vec::new(), // Will be populated in the service layer
                 payment_methods: Vec::new(),      // Will be populated in the service layer
                 connected_account_id: value.connected_account_id.map(|id| id.to_string()),
+                custom_attributes: value.custom_attributes.into_iter().collect(),
+                parent_customer_id: value.parent_customer_id.map(|id| id.as_proto()),
+                pool_usage_with_parent: value.pool_usage_with_parent,
+                invoice_language: value.invoice_language,
+                invoice_custom_fields: value.invoice_custom_fields.into_iter().collect(),
+                invoice_approval_required: value.invoice_approval_required,
             }))
         }
     }
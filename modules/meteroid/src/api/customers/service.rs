@@ -18,11 +18,11 @@ use meteroid_grpc::meteroid::api::customers::v1::{
     GenerateCustomerPortalTokenRequest, GenerateCustomerPortalTokenResponse,
     GetCustomerByAliasRequest, GetCustomerByAliasResponse, GetCustomerByIdRequest,
     GetCustomerByIdResponse, ListCustomerRequest, ListCustomerResponse,
-    RefreshVatValidationRequest, RefreshVatValidationResponse, SyncToHubspotRequest,
-    SyncToHubspotResponse, SyncToPennylaneRequest, SyncToPennylaneResponse,
-    TopUpCustomerBalanceRequest, TopUpCustomerBalanceResponse, UnarchiveCustomerRequest,
-    UnarchiveCustomerResponse, UpdateCustomerRequest, UpdateCustomerResponse,
-    UpsertCustomerConnectionRequest, UpsertCustomerConnectionResponse,
+    RefreshVatValidationRequest, RefreshVatValidationResponse, SetCustomerParentRequest,
+    SetCustomerParentResponse, SyncToHubspotRequest, SyncToHubspotResponse, SyncToPennylaneRequest,
+    SyncToPennylaneResponse, TopUpCustomerBalanceRequest, TopUpCustomerBalanceResponse,
+    UnarchiveCustomerRequest, UnarchiveCustomerResponse, UpdateCustomerRequest,
+    UpdateCustomerResponse, UpsertCustomerConnectionRequest, UpsertCustomerConnectionResponse,
     customers_service_server::CustomersService,
 };
 use meteroid_store::domain::{
@@ -89,6 +89,10 @@ impl CustomersService for CustomerServiceComponents {
                 .collect::<Result<Vec<_>, _>>()?,
             is_tax_exempt: inner.is_tax_exempt.unwrap_or(false),
             connected_account_id: ConnectedAccountId::from_proto_opt(inner.connected_account_id)?,
+            custom_attributes: inner.custom_attributes.into_iter().collect(),
+            invoice_language: inner.invoice_language,
+            invoice_custom_fields: inner.invoice_custom_fields.into_iter().collect(),
+            invoice_approval_required: inner.invoice_approval_required.unwrap_or(false),
         };
 
         let customer = self
@@ -157,6 +161,14 @@ impl CustomersService for CustomerServiceComponents {
                     current_payment_method_id: None,
                     is_tax_exempt: customer.is_tax_exempt,
                     connected_account_id: None,
+                    custom_attributes: customer
+                        .custom_attributes
+                        .map(|a| a.values.into_iter().collect()),
+                    invoice_language: Some(customer.invoice_language),
+                    invoice_custom_fields: customer
+                        .invoice_custom_fields
+                        .map(|a| a.values.into_iter().collect()),
+                    invoice_approval_required: customer.invoice_approval_required,
                 },
             )
             .await
@@ -498,6 +510,37 @@ impl CustomersService for CustomerServiceComponents {
         }))
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn set_customer_parent(
+        &self,
+        request: Request<SetCustomerParentRequest>,
+    ) -> Result<Response<SetCustomerParentResponse>, Status> {
+        let actor = request.actor_typed()?;
+        let tenant_id = request.tenant()?;
+
+        let req = request.into_inner();
+        let customer_id = CustomerId::from_proto(&req.customer_id)?;
+        let parent_customer_id = CustomerId::from_proto_opt(req.parent_customer_id)?;
+
+        let customer = self
+            .store
+            .set_customer_parent(
+                actor,
+                tenant_id,
+                customer_id,
+                parent_customer_id,
+                req.pool_usage_with_parent,
+            )
+            .await
+            .and_then(ServerCustomerWrapper::try_from)
+            .map(|v| v.0)
+            .map_err(Into::<CustomerApiError>::into)?;
+
+        Ok(Response::new(SetCustomerParentResponse {
+            customer: Some(customer),
+        }))
+    }
+
     #[tracing::instrument(skip_all)]
     async fn upsert_customer_connection(
         &self,
@@ -8,6 +8,8 @@ pub(crate) mod billing_period {
 
     pub fn from_proto(period: api_shared::BillingPeriod) -> domain::enums::BillingPeriodEnum {
         match period {
+            api_shared::BillingPeriod::Daily => domain::enums::BillingPeriodEnum::Daily,
+            api_shared::BillingPeriod::Weekly => domain::enums::BillingPeriodEnum::Weekly,
             api_shared::BillingPeriod::Monthly => domain::enums::BillingPeriodEnum::Monthly,
             api_shared::BillingPeriod::Quarterly => domain::enums::BillingPeriodEnum::Quarterly,
             api_shared::BillingPeriod::Semiannual => domain::enums::BillingPeriodEnum::Semiannual,
@@ -17,6 +19,8 @@ pub(crate) mod billing_period {
 
     pub fn to_proto(period: domain::enums::BillingPeriodEnum) -> api_shared::BillingPeriod {
         match period {
+            domain::enums::BillingPeriodEnum::Daily => api_shared::BillingPeriod::Daily,
+            domain::enums::BillingPeriodEnum::Weekly => api_shared::BillingPeriod::Weekly,
             domain::enums::BillingPeriodEnum::Monthly => api_shared::BillingPeriod::Monthly,
             domain::enums::BillingPeriodEnum::Quarterly => api_shared::BillingPeriod::Quarterly,
             domain::enums::BillingPeriodEnum::Semiannual => api_shared::BillingPeriod::Semiannual,
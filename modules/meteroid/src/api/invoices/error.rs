@@ -22,6 +22,9 @@ pub enum InvoiceApiError {
     #[error("Invalid argument: {0}")]
     #[code(InvalidArgument)]
     InvalidArgument(String),
+    #[error("Forbidden: {0}")]
+    #[code(PermissionDenied)]
+    Forbidden(String),
 }
 
 impl From<Report<StoreError>> for InvoiceApiError {
@@ -31,6 +34,7 @@ impl From<Report<StoreError>> for InvoiceApiError {
         match err {
             StoreError::InvalidArgument(msg) => Self::InvalidArgument(msg.clone()),
             StoreError::ValueNotFound(msg) => Self::InvalidArgument(msg.clone()),
+            StoreError::Forbidden(msg) => Self::Forbidden(msg.clone()),
             StoreError::DuplicateValue { entity, key } => {
                 let msg = match key {
                     Some(k) => format!("{} with key '{}' already exists", entity, k),
@@ -6,8 +6,8 @@ pub mod invoices {
     use common_domain::ids::BaseId;
     use error_stack::Report;
     use meteroid_grpc::meteroid::api::invoices::v1::{
-        CouponLineItem, DetailedInvoice, InlineCustomer, Invoice, InvoicePaymentStatus,
-        InvoiceStatus, InvoiceType, LineItem,
+        CouponLineItem, DetailedInvoice, InlineCustomer, Invoice, InvoiceApprovalStatus,
+        InvoicePaymentStatus, InvoiceStatus, InvoiceType, LineItem,
     };
     use meteroid_store::domain;
     use meteroid_store::domain::invoice_lines as domain_invoice_lines;
@@ -52,12 +52,23 @@ pub mod invoices {
         }
     }
 
+    pub fn approval_status_domain_to_server(
+        value: domain::enums::InvoiceApprovalStatus,
+    ) -> InvoiceApprovalStatus {
+        match value {
+            domain::enums::InvoiceApprovalStatus::Pending => InvoiceApprovalStatus::Pending,
+            domain::enums::InvoiceApprovalStatus::Approved => InvoiceApprovalStatus::Approved,
+            domain::enums::InvoiceApprovalStatus::Rejected => InvoiceApprovalStatus::Rejected,
+        }
+    }
+
     fn invoicing_type_domain_to_server(value: domain::enums::InvoiceType) -> InvoiceType {
         match value {
             domain::enums::InvoiceType::Recurring => InvoiceType::Recurring,
             domain::enums::InvoiceType::OneOff => InvoiceType::OneOff,
             domain::enums::InvoiceType::UsageThreshold => InvoiceType::UsageThreshold,
             domain::enums::InvoiceType::Adjustment => InvoiceType::Adjustment,
+            domain::enums::InvoiceType::Imported => InvoiceType::Imported,
         }
     }
 
@@ -92,6 +103,7 @@ pub mod invoices {
                     product_id: line.product_id.map(|x| x.as_proto()),
                     description: line.description,
                     group_by_dimensions: line.group_by_dimensions.unwrap_or_default(),
+                    metadata: line.metadata.into_iter().collect(),
                     sub_line_items: line.sub_lines.into_iter().map(
                         |sub_line| {
                             let attributes = match sub_line.attributes {
@@ -260,6 +272,18 @@ pub mod invoices {
             // Both populated by the GetInvoice handler (need DB reads of the children / parent).
             consolidated_children: vec![],
             consolidated_into_invoice_number: None,
+            custom_fields: invoice.custom_fields.into_iter().collect(),
+            // Populated by the GetInvoice handlers (needs the component presentation settings).
+            has_usage_annex: false,
+            approval_status: invoice
+                .approval_status
+                .map(|s| approval_status_domain_to_server(s).into()),
+            approval_requested_at: invoice.approval_requested_at.as_proto(),
+            approval_decided_at: invoice.approval_decided_at.as_proto(),
+            approval_decided_by: invoice.approval_decided_by.map(|id| id.as_proto()),
+            approval_comment: invoice.approval_comment,
+            // Resolved by the GetInvoice handler.
+            approval_decided_by_email: None,
         })
     }
 
@@ -294,6 +318,10 @@ pub mod invoices {
                 .map(|x| x.to_string()),
             // Resolved (batched) by the list_invoices handler.
             consolidated_into_invoice_number: None,
+            approval_status: value
+                .invoice
+                .approval_status
+                .map(|s| approval_status_domain_to_server(s).into()),
         }
     }
 }
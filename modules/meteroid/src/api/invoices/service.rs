@@ -4,21 +4,23 @@ use crate::api::invoices::error::InvoiceApiError;
 use crate::api::shared::conversions::{AsProtoOpt, FromProtoOpt, ProtoConv};
 use crate::api::utils::PaginationExt;
 use chrono::{NaiveDate, NaiveTime};
-use common_domain::ids::{CustomerId, InvoiceId, SubscriptionId, TenantId};
+use common_domain::ids::{CustomerId, InvoiceId, SubscriptionId, TenantId, UserId};
 use common_grpc::middleware::server::auth::RequestExt;
 use common_utils::decimals::ToSubunit;
 use meteroid_grpc::meteroid::api::invoices::v1::{
-    CreateCorrectedInvoiceRequest, CreateCorrectedInvoiceResponse, CreateInvoiceRequest,
-    CreateInvoiceResponse, DeleteInvoiceRequest, DeleteInvoiceResponse, FinalizeInvoiceRequest,
-    FinalizeInvoiceResponse, GenerateInvoicePaymentTokenRequest,
-    GenerateInvoicePaymentTokenResponse, GetInvoiceRequest, GetInvoiceResponse, Invoice,
-    ListInvoicesRequest, ListInvoicesResponse, MarkInvoiceAsUncollectibleRequest,
-    MarkInvoiceAsUncollectibleResponse, NewInvoice, PreviewInvoiceRequest, PreviewInvoiceResponse,
-    PreviewInvoiceUpdateRequest, PreviewInvoiceUpdateResponse, PreviewNewInvoiceRequest,
-    PreviewNewInvoiceResponse, RefreshInvoiceDataRequest, RefreshInvoiceDataResponse,
-    RequestPdfGenerationRequest, RequestPdfGenerationResponse, SubLineItem as ProtoSubLineItem,
-    SyncToPennylaneRequest, SyncToPennylaneResponse, UpdateInvoiceRequest, UpdateInvoiceResponse,
-    VoidInvoiceRequest, VoidInvoiceResponse, invoices_service_server::InvoicesService,
+    ApproveInvoiceRequest, ApproveInvoiceResponse, CreateCorrectedInvoiceRequest,
+    CreateCorrectedInvoiceResponse, CreateInvoiceRequest, CreateInvoiceResponse,
+    DeleteInvoiceRequest, DeleteInvoiceResponse, FinalizeInvoiceRequest, FinalizeInvoiceResponse,
+    GenerateInvoicePaymentTokenRequest, GenerateInvoicePaymentTokenResponse, GetInvoiceRequest,
+    GetInvoiceResponse, Invoice, ListInvoicesRequest, ListInvoicesResponse,
+    MarkInvoiceAsUncollectibleRequest, MarkInvoiceAsUncollectibleResponse, NewInvoice,
+    PreviewInvoiceRequest, PreviewInvoiceResponse, PreviewInvoiceUpdateRequest,
+    PreviewInvoiceUpdateResponse, PreviewNewInvoiceRequest, PreviewNewInvoiceResponse,
+    RefreshInvoiceDataRequest, RefreshInvoiceDataResponse, RejectInvoiceRequest,
+    RejectInvoiceResponse, RequestPdfGenerationRequest, RequestPdfGenerationResponse,
+    SubLineItem as ProtoSubLineItem, SyncToPennylaneRequest, SyncToPennylaneResponse,
+    UpdateInvoiceRequest, UpdateInvoiceResponse, VoidInvoiceRequest, VoidInvoiceResponse,
+    invoices_service_server::InvoicesService,
 };
 use meteroid_store::Store;
 use meteroid_store::domain::pgmq::{InvoicePdfRequestEvent, PgmqMessageNew, PgmqQueue};
@@ -31,7 +33,10 @@ use meteroid_store::repositories::invoices::compute_tax_breakdown;
 use meteroid_store::repositories::invoicing_entities::InvoicingEntityInterfaceAuto;
 use meteroid_store::repositories::payment_transactions::PaymentTransactionInterface;
 use meteroid_store::repositories::pgmq::PgmqInterface;
+use meteroid_store::repositories::users::UserInterface;
 use meteroid_store::services::CustomerDetailsUpdate;
+use meteroid_store::services::invoice_lines::presentation::present_invoice;
+use meteroid_store::utils::custom_fields::{resolve_invoice_custom_fields, validate_custom_fields};
 use meteroid_store::utils::local_id::{IdType, LocalId};
 use tonic::{Request, Response, Status};
 
@@ -124,20 +129,27 @@ impl InvoicesService for InvoiceServiceComponents {
             .map(mapping::transactions::domain_with_method_to_server)
             .collect::<Vec<_>>();
 
-        let mut invoice = self
+        let detailed = self
             .store
             .get_detailed_invoice_by_id(tenant_id, invoice_id)
             .await
-            .and_then(|inv| {
-                mapping::invoices::domain_invoice_with_transactions_to_server(
-                    inv.invoice,
-                    inv.transactions,
-                    self.jwt_secret.clone(),
-                )
-            })
             .map_err(Into::<InvoiceApiError>::into)?;
 
+        let has_usage_annex = !present_invoice(&self.store, &detailed.invoice)
+            .await
+            .map_err(Into::<InvoiceApiError>::into)?
+            .annex
+            .is_empty();
+
+        let mut invoice = mapping::invoices::domain_invoice_with_transactions_to_server(
+            detailed.invoice,
+            detailed.transactions,
+            self.jwt_secret.clone(),
+        )
+        .map_err(Into::<InvoiceApiError>::into)?;
+
         invoice.transactions = transactions;
+        invoice.has_usage_annex = has_usage_annex;
 
         invoice.child_invoice_id = self
             .store
@@ -166,6 +178,16 @@ impl InvoicesService for InvoiceServiceComponents {
             invoice.consolidated_into_invoice_number = Some(parent.invoice_number);
         }
 
+        if let Some(decided_by) = &invoice.approval_decided_by
+            && let Ok(user_id) = UserId::from_proto(decided_by)
+            && let Ok(user) = self
+                .store
+                .find_user_by_id_and_tenant(user_id, tenant_id)
+                .await
+        {
+            invoice.approval_decided_by_email = Some(user.email);
+        }
+
         let response = GetInvoiceResponse {
             invoice: Some(invoice),
         };
@@ -406,6 +428,66 @@ impl InvoicesService for InvoiceServiceComponents {
         }))
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn approve_invoice(
+        &self,
+        request: Request<ApproveInvoiceRequest>,
+    ) -> Result<Response<ApproveInvoiceResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor = request.actor_typed()?;
+
+        let req = request.into_inner();
+
+        let invoice_id = InvoiceId::from_proto(&req.id)?;
+
+        let approved = self
+            .services
+            .approve_invoice(actor, invoice_id, tenant_id, req.comment)
+            .await
+            .and_then(|inv| {
+                mapping::invoices::domain_invoice_with_transactions_to_server(
+                    inv.invoice,
+                    inv.transactions,
+                    self.jwt_secret.clone(),
+                )
+            })
+            .map_err(Into::<InvoiceApiError>::into)?;
+
+        Ok(Response::new(ApproveInvoiceResponse {
+            invoice: Some(approved),
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn reject_invoice(
+        &self,
+        request: Request<RejectInvoiceRequest>,
+    ) -> Result<Response<RejectInvoiceResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor = request.actor_typed()?;
+
+        let req = request.into_inner();
+
+        let invoice_id = InvoiceId::from_proto(&req.id)?;
+
+        let rejected = self
+            .services
+            .reject_invoice(actor, invoice_id, tenant_id, req.comment)
+            .await
+            .and_then(|inv| {
+                mapping::invoices::domain_invoice_with_transactions_to_server(
+                    inv.invoice,
+                    inv.transactions,
+                    self.jwt_secret.clone(),
+                )
+            })
+            .map_err(Into::<InvoiceApiError>::into)?;
+
+        Ok(Response::new(RejectInvoiceResponse {
+            invoice: Some(rejected),
+        }))
+    }
+
     #[tracing::instrument(skip_all)]
     async fn update_invoice(
         &self,
@@ -857,7 +939,9 @@ async fn to_domain_invoice_new(
             metric_id: None,
             description: line.description.clone(),
             group_by_dimensions: None,
+            metadata: line.metadata.clone().into_iter().collect(),
         };
+        validate_custom_fields(&item.metadata).map_err(Into::<InvoiceApiError>::into)?;
 
         lines.push(item);
     }
@@ -896,6 +980,12 @@ async fn to_domain_invoice_new(
         .await
         .map_err(Into::<InvoiceApiError>::into)?;
 
+    let custom_fields = resolve_invoice_custom_fields(
+        &customer.invoice_custom_fields,
+        Some(&invoice_req.custom_fields.into_iter().collect()),
+    );
+    validate_custom_fields(&custom_fields).map_err(Into::<InvoiceApiError>::into)?;
+
     // Use custom customer details if provided, otherwise use customer from DB
     let customer_details = if let Some(custom_details) = invoice_req.customer_details {
         let billing_address = custom_details
@@ -913,6 +1003,7 @@ async fn to_domain_invoice_new(
             vat_number: custom_details.vat_number,
             email: custom_details.email,
             alias: customer.alias,
+            invoice_language: customer.invoice_language,
         }
     } else {
         customer.into()
@@ -954,6 +1045,7 @@ async fn to_domain_invoice_new(
         invoicing_entity_id: invoicing_entity.id,
         parent_invoice_id: None,
         consolidated_into_invoice_id: None,
+        custom_fields,
     };
     Ok((invoice_new, invoicing_entity))
 }
@@ -987,6 +1079,9 @@ fn to_update_invoice_params(req: UpdateInvoiceRequest) -> Result<UpdateInvoicePa
                 tax_rate,
                 description: line.description,
                 sub_lines,
+                metadata: line
+                    .metadata
+                    .map(|metadata| metadata.fields.into_iter().collect()),
             });
         }
         Some(items)
@@ -1032,5 +1127,8 @@ fn to_update_invoice_params(req: UpdateInvoiceRequest) -> Result<UpdateInvoicePa
         discount,
         customer_details,
         invoicing_entity_id,
+        custom_fields: req
+            .custom_fields
+            .map(|custom_fields| custom_fields.fields.into_iter().collect()),
     })
 }
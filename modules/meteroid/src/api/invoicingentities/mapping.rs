@@ -1,10 +1,13 @@
 pub mod invoicing_entities {
     use common_domain::country::CountryCode;
-    use common_domain::ids::{InvoicingEntityId, StoredDocumentId};
+    use common_domain::ids::{InvoicingEntityId, StoredDocumentId, UserId};
 
     use meteroid_grpc::meteroid::api::invoicingentities::v1 as server;
-    use meteroid_grpc::meteroid::api::invoicingentities::v1::TaxResolver;
+    use meteroid_grpc::meteroid::api::invoicingentities::v1::{NumberingReset, TaxResolver};
+    use meteroid_store::domain::enums::NumberingResetEnum;
     use meteroid_store::domain::invoicing_entities as domain;
+    use meteroid_store::utils::locale::InvoiceTranslations;
+    use std::collections::HashMap;
 
     pub fn proto_to_domain(
         proto: server::InvoicingEntityData,
@@ -33,6 +36,73 @@ pub mod invoicing_entities {
             require_billing_information: proto.require_billing_information.unwrap_or(false),
             portal_theme_mode: proto.portal_theme_mode,
             portal_roundness: proto.portal_roundness,
+            invoice_number_reset: numbering_reset_server_to_domain(proto.invoice_number_reset),
+            credit_note_number_pattern: proto.credit_note_number_pattern,
+            credit_note_number_reset: numbering_reset_server_to_domain(
+                proto.credit_note_number_reset,
+            ),
+            invoice_translations: invoice_translations_server_to_domain(
+                proto
+                    .invoice_translations
+                    .map(|t| t.languages)
+                    .unwrap_or_default(),
+            )?,
+        })
+    }
+
+    fn invoice_translations_server_to_domain(
+        languages: HashMap<String, server::InvoiceTranslations>,
+    ) -> Result<InvoiceTranslations, tonic::Status> {
+        languages
+            .into_iter()
+            .map(|(lang, translations)| {
+                if let Some(id) = translations
+                    .messages
+                    .keys()
+                    .find(|id| !meteroid_invoicing::locale::is_message_id(id))
+                {
+                    return Err(tonic::Status::invalid_argument(format!(
+                        "Unknown invoice message '{id}' in {lang} translations"
+                    )));
+                }
+                Ok((lang, translations.messages.into_iter().collect()))
+            })
+            .collect()
+    }
+
+    fn invoice_translations_domain_to_server(
+        translations: InvoiceTranslations,
+    ) -> HashMap<String, server::InvoiceTranslations> {
+        translations
+            .into_iter()
+            .map(|(lang, messages)| {
+                (
+                    lang,
+                    server::InvoiceTranslations {
+                        messages: messages.into_iter().collect(),
+                    },
+                )
+            })
+            .collect()
+    }
+
+    fn numbering_reset_domain_to_server(value: NumberingResetEnum) -> NumberingReset {
+        match value {
+            NumberingResetEnum::Never => NumberingReset::Never,
+            NumberingResetEnum::Yearly => NumberingReset::Yearly,
+            NumberingResetEnum::Monthly => NumberingReset::Monthly,
+        }
+    }
+
+    fn numbering_reset_server_to_domain(value: Option<i32>) -> Option<NumberingResetEnum> {
+        value.and_then(|value| {
+            NumberingReset::try_from(value)
+                .ok()
+                .map(|value| match value {
+                    NumberingReset::Never => NumberingResetEnum::Never,
+                    NumberingReset::Yearly => NumberingResetEnum::Yearly,
+                    NumberingReset::Monthly => NumberingResetEnum::Monthly,
+                })
         })
     }
 
@@ -65,8 +135,8 @@ pub mod invoicing_entities {
     pub fn proto_to_patch_domain(
         proto: server::InvoicingEntityData,
         id: InvoicingEntityId,
-    ) -> domain::InvoicingEntityPatch {
-        domain::InvoicingEntityPatch {
+    ) -> Result<domain::InvoicingEntityPatch, tonic::Status> {
+        Ok(domain::InvoicingEntityPatch {
             id,
             legal_name: proto.legal_name,
             invoice_number_pattern: proto.invoice_number_pattern,
@@ -90,7 +160,28 @@ pub mod invoicing_entities {
             require_billing_information: proto.require_billing_information,
             portal_theme_mode: Some(proto.portal_theme_mode),
             portal_roundness: Some(proto.portal_roundness),
-        }
+            invoice_number_reset: numbering_reset_server_to_domain(proto.invoice_number_reset),
+            credit_note_number_pattern: proto.credit_note_number_pattern,
+            credit_note_number_reset: numbering_reset_server_to_domain(
+                proto.credit_note_number_reset,
+            ),
+            invoice_translations: proto
+                .invoice_translations
+                .map(|t| invoice_translations_server_to_domain(t.languages))
+                .transpose()?,
+            // set through UpdateInvoicingEntityTemplate, once validated
+            invoice_template: None,
+            invoice_approval_threshold: proto.invoice_approval.as_ref().map(|a| a.threshold),
+            invoice_approver_ids: proto
+                .invoice_approval
+                .map(|a| {
+                    a.approver_ids
+                        .iter()
+                        .map(UserId::from_proto)
+                        .collect::<Result<Vec<_>, _>>()
+                })
+                .transpose()?,
+        })
     }
 
     pub fn domain_to_proto(domain: domain::InvoicingEntity) -> server::InvoicingEntity {
@@ -121,6 +212,23 @@ pub mod invoicing_entities {
             require_billing_information: domain.require_billing_information,
             portal_theme_mode: domain.portal_theme_mode,
             portal_roundness: domain.portal_roundness,
+            invoice_number_reset: numbering_reset_domain_to_server(domain.invoice_number_reset)
+                .into(),
+            credit_note_number_pattern: domain.credit_note_number_pattern,
+            credit_note_number_reset: numbering_reset_domain_to_server(
+                domain.credit_note_number_reset,
+            )
+            .into(),
+            invoice_translations: invoice_translations_domain_to_server(
+                domain.invoice_translations,
+            ),
+            invoice_template: domain.invoice_template,
+            invoice_approval_threshold: domain.invoice_approval_threshold,
+            invoice_approver_ids: domain
+                .invoice_approver_ids
+                .into_iter()
+                .map(|id| id.as_proto())
+                .collect(),
         }
     }
 
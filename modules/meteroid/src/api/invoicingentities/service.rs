@@ -8,14 +8,17 @@ use meteroid_grpc::meteroid::api::invoicingentities::v1::{
     GetInvoicingEntityRequest, GetInvoicingEntityResponse, ListInvoicingEntitiesRequest,
     ListInvoicingEntitiesResponse, UpdateInvoicingEntityProvidersRequest,
     UpdateInvoicingEntityProvidersResponse, UpdateInvoicingEntityRequest,
-    UpdateInvoicingEntityResponse, UploadInvoicingEntityLogoRequest,
+    UpdateInvoicingEntityResponse, UpdateInvoicingEntityTemplateRequest,
+    UpdateInvoicingEntityTemplateResponse, UploadInvoicingEntityLogoRequest,
     UploadInvoicingEntityLogoResponse, invoicing_entities_service_server::InvoicingEntitiesService,
 };
+use meteroid_invoicing::template::validate_invoice_template;
 use meteroid_store::domain::{InvoicingEntityPatch, InvoicingEntityProvidersPatch};
 use meteroid_store::repositories::invoicing_entities::{
     InvoicingEntityInterface, InvoicingEntityInterfaceAuto,
 };
 use std::io::Cursor;
+use std::time::Duration;
 use tonic::{Request, Response, Status};
 
 use crate::api::invoicingentities::error::InvoicingEntitiesApiError;
@@ -23,6 +26,8 @@ use crate::services::storage::Prefix;
 
 use super::{InvoicingEntitiesServiceComponents, mapping};
 
+const TEMPLATE_VALIDATION_TIMEOUT: Duration = Duration::from_secs(10);
+
 #[tonic::async_trait]
 impl InvoicingEntitiesService for InvoicingEntitiesServiceComponents {
     #[tracing::instrument(skip_all)]
@@ -117,7 +122,7 @@ impl InvoicingEntitiesService for InvoicingEntitiesServiceComponents {
                 mapping::invoicing_entities::proto_to_patch_domain(
                     data,
                     InvoicingEntityId::from_proto(req.id)?,
-                ),
+                )?,
                 tenant,
             )
             .await
@@ -179,6 +184,56 @@ impl InvoicingEntitiesService for InvoicingEntitiesServiceComponents {
         }))
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn update_invoicing_entity_template(
+        &self,
+        request: Request<UpdateInvoicingEntityTemplateRequest>,
+    ) -> Result<Response<UpdateInvoicingEntityTemplateResponse>, Status> {
+        let tenant = request.tenant()?;
+        let actor = request.actor_typed()?;
+        let req = request.into_inner();
+
+        if let Some(template) = req.template.clone() {
+            // compiling runs arbitrary Typst code, keep it off the async workers and bounded
+            let validation = tokio::time::timeout(
+                TEMPLATE_VALIDATION_TIMEOUT,
+                tokio::task::spawn_blocking(move || validate_invoice_template(&template)),
+            )
+            .await
+            .map_err(|_| {
+                InvoicingEntitiesApiError::InvalidArgument(format!(
+                    "Template took more than {}s to compile",
+                    TEMPLATE_VALIDATION_TIMEOUT.as_secs()
+                ))
+            })?
+            .map_err(|e| {
+                InvoicingEntitiesApiError::StoreError(
+                    "Template validation failed to complete".to_string(),
+                    Box::new(e),
+                )
+            })?;
+            validation.map_err(|e| InvoicingEntitiesApiError::InvalidArgument(e.to_string()))?;
+        }
+
+        let res = self
+            .store
+            .patch_invoicing_entity(
+                actor,
+                InvoicingEntityPatch {
+                    id: InvoicingEntityId::from_proto(req.id)?,
+                    invoice_template: Some(req.template),
+                    ..InvoicingEntityPatch::default()
+                },
+                tenant,
+            )
+            .await
+            .map_err(Into::<InvoicingEntitiesApiError>::into)?;
+
+        Ok(Response::new(UpdateInvoicingEntityTemplateResponse {
+            entity: Some(mapping::invoicing_entities::domain_to_proto(res)),
+        }))
+    }
+
     async fn get_invoicing_entity_providers(
         &self,
         request: Request<GetInvoicingEntityProvidersRequest>,
@@ -10,6 +10,7 @@ pub mod apitokens;
 pub mod bankaccounts;
 pub mod batchjobs;
 pub mod billablemetrics;
+pub mod catalog;
 pub mod connectors;
 pub mod coupons;
 pub mod creditnotes;
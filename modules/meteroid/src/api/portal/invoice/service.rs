@@ -18,6 +18,7 @@ use meteroid_store::repositories::payment_transactions::PaymentTransactionInterf
 use meteroid_store::repositories::{
     InvoiceInterface, OrganizationsInterface, SubscriptionInterface,
 };
+use meteroid_store::services::invoice_lines::presentation::present_invoice;
 use std::time::Duration;
 use tonic::{Request, Response, Status};
 
@@ -51,7 +52,7 @@ impl PortalInvoiceService for PortalInvoiceServiceComponents {
             .map(mapping::transactions::domain_with_method_to_server)
             .collect::<Vec<_>>();
 
-        let invoice = self
+        let mut invoice = self
             .store
             .get_detailed_invoice_by_id(tenant, invoice_id)
             .await
@@ -140,6 +141,14 @@ impl PortalInvoiceService for PortalInvoiceServiceComponents {
             .map(crate::api::customers::mapping::customer_payment_method::domain_to_server)
             .collect();
 
+        // The portal shows the invoice as on its document: usage lines follow the
+        // sub-line presentation of their price component, the detail being in the annex.
+        let presented = present_invoice(&self.store, &invoice.invoice)
+            .await
+            .map_err(Into::<PortalInvoiceApiError>::into)?;
+        let has_usage_annex = !presented.annex.is_empty();
+        invoice.invoice.line_items = presented.lines;
+
         let mut invoice =
             crate::api::invoices::mapping::invoices::domain_invoice_with_transactions_to_server(
                 invoice.invoice,
@@ -149,6 +158,7 @@ impl PortalInvoiceService for PortalInvoiceServiceComponents {
             .map_err(Into::<PortalInvoiceApiError>::into)?;
 
         invoice.transactions = transactions;
+        invoice.has_usage_annex = has_usage_annex;
 
         let customer = ServerCustomerWrapper::try_from(customer)
             .map(|v| v.0)
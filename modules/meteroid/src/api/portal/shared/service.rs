@@ -112,6 +112,10 @@ impl PortalSharedService for PortalSharedServiceComponents {
                     current_payment_method_id: None,
                     is_tax_exempt: None,
                     connected_account_id: None,
+                    custom_attributes: None,
+                    invoice_language: None,
+                    invoice_custom_fields: None,
+                    invoice_approval_required: None,
                 },
             )
             .await
@@ -298,6 +302,10 @@ impl PortalSharedService for PortalSharedServiceComponents {
             is_tax_exempt: None,
             custom_taxes: None,
             connected_account_id: None,
+            custom_attributes: None,
+            invoice_language: None,
+            invoice_custom_fields: None,
+            invoice_approval_required: None,
         };
 
         self.store
@@ -357,6 +365,10 @@ impl PortalSharedService for PortalSharedServiceComponents {
             is_tax_exempt: None,
             custom_taxes: None,
             connected_account_id: None,
+            custom_attributes: None,
+            invoice_language: None,
+            invoice_custom_fields: None,
+            invoice_approval_required: None,
         };
 
         let updated = self
@@ -21,6 +21,7 @@ use meteroid_store::repositories::add_ons::AddOnInterface;
 use meteroid_store::repositories::plan_version_add_ons::PlanVersionAddOnInterface;
 use meteroid_store::repositories::subscriptions::CancellationEffectiveAt;
 use meteroid_store::repositories::subscriptions::SubscriptionInterfaceAuto;
+use meteroid_store::services::invoice_lines::presentation::present_invoice_lines;
 use std::collections::HashMap;
 use tonic::{Request, Response, Status};
 
@@ -30,6 +31,8 @@ use tonic::{Request, Response, Status};
 
 fn map_billing_period(period: &BillingPeriodEnum) -> i32 {
     match period {
+        BillingPeriodEnum::Daily => sub_proto::SubscriptionFeeBillingPeriod::Daily.into(),
+        BillingPeriodEnum::Weekly => sub_proto::SubscriptionFeeBillingPeriod::Weekly.into(),
         BillingPeriodEnum::Monthly => sub_proto::SubscriptionFeeBillingPeriod::Monthly.into(),
         BillingPeriodEnum::Quarterly => sub_proto::SubscriptionFeeBillingPeriod::Quarterly.into(),
         BillingPeriodEnum::Semiannual => sub_proto::SubscriptionFeeBillingPeriod::Semiannual.into(),
@@ -42,6 +45,12 @@ fn map_sub_billing_period(period: &SubscriptionFeeBillingPeriod) -> i32 {
         SubscriptionFeeBillingPeriod::OneTime => {
             sub_proto::SubscriptionFeeBillingPeriod::OneTime.into()
         }
+        SubscriptionFeeBillingPeriod::Daily => {
+            sub_proto::SubscriptionFeeBillingPeriod::Daily.into()
+        }
+        SubscriptionFeeBillingPeriod::Weekly => {
+            sub_proto::SubscriptionFeeBillingPeriod::Weekly.into()
+        }
         SubscriptionFeeBillingPeriod::Monthly => {
             sub_proto::SubscriptionFeeBillingPeriod::Monthly.into()
         }
@@ -787,12 +796,18 @@ impl PortalSubscriptionService for PortalSubscriptionServiceComponents {
             return Err(PortalSubscriptionApiError::Unauthorized.into());
         }
 
-        let content = self
+        let mut content = self
             .services
             .compute_upcoming_invoice(&details)
             .await
             .map_err(Into::<PortalSubscriptionApiError>::into)?;
 
+        content.invoice_lines =
+            present_invoice_lines(&self.store, tenant_id, &content.invoice_lines)
+                .await
+                .map_err(Into::<PortalSubscriptionApiError>::into)?
+                .lines;
+
         use crate::api::invoices::mapping::invoices::{
             domain_coupon_line_item_to_server, domain_invoice_lines_to_server,
         };
@@ -1755,6 +1770,96 @@ impl PortalSubscriptionService for PortalSubscriptionServiceComponents {
             preview: Some(map_slot_quantity_preview(&preview, currency)),
         }))
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn preview_billing_anchor_change(
+        &self,
+        request: Request<PreviewBillingAnchorChangeRequest>,
+    ) -> Result<Response<PreviewBillingAnchorChangeResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let customer_id = request.portal_resource()?.customer()?;
+        let inner = request.into_inner();
+
+        let subscription_id = SubscriptionId::from_proto(&inner.subscription_id)?;
+        let billing_day_anchor = u16::try_from(inner.billing_day_anchor).map_err(|_| {
+            PortalSubscriptionApiError::InvalidArgument("Invalid billing day".to_string())
+        })?;
+
+        let details = self
+            .store
+            .get_subscription_details(tenant_id, subscription_id)
+            .await
+            .map_err(Into::<PortalSubscriptionApiError>::into)?;
+
+        if details.subscription.customer_id != customer_id {
+            return Err(PortalSubscriptionApiError::Unauthorized.into());
+        }
+        if !self.can_self_serve(tenant_id, &details).await? {
+            return Err(PortalSubscriptionApiError::FailedPrecondition(
+                "Managed by your account team".to_string(),
+            )
+            .into());
+        }
+
+        let preview = self
+            .services
+            .preview_billing_anchor_change(subscription_id, tenant_id, billing_day_anchor)
+            .await
+            .map_err(Into::<PortalSubscriptionApiError>::into)?;
+
+        Ok(Response::new(PreviewBillingAnchorChangeResponse {
+            current_period_end: preview.current_period_end.as_proto(),
+            new_period_end: preview.new_period_end.as_proto(),
+            proration: Some(map_proration_summary(&preview.proration)),
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn change_billing_anchor(
+        &self,
+        request: Request<ChangeBillingAnchorRequest>,
+    ) -> Result<Response<ChangeBillingAnchorResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let customer_id = request.portal_resource()?.customer()?;
+        let inner = request.into_inner();
+
+        let subscription_id = SubscriptionId::from_proto(&inner.subscription_id)?;
+        let billing_day_anchor = u16::try_from(inner.billing_day_anchor).map_err(|_| {
+            PortalSubscriptionApiError::InvalidArgument("Invalid billing day".to_string())
+        })?;
+
+        let details = self
+            .store
+            .get_subscription_details(tenant_id, subscription_id)
+            .await
+            .map_err(Into::<PortalSubscriptionApiError>::into)?;
+
+        if details.subscription.customer_id != customer_id {
+            return Err(PortalSubscriptionApiError::Unauthorized.into());
+        }
+        if !self.can_self_serve(tenant_id, &details).await? {
+            return Err(PortalSubscriptionApiError::FailedPrecondition(
+                "Managed by your account team".to_string(),
+            )
+            .into());
+        }
+
+        let result = self
+            .services
+            .change_billing_anchor(
+                common_domain::actor::Actor::Customer { id: customer_id },
+                subscription_id,
+                tenant_id,
+                billing_day_anchor,
+            )
+            .await
+            .map_err(Into::<PortalSubscriptionApiError>::into)?;
+
+        Ok(Response::new(ChangeBillingAnchorResponse {
+            new_period_end: result.new_period_end.as_proto(),
+            adjustment_invoice_id: result.adjustment_invoice_id.map(|id| id.to_string()),
+        }))
+    }
 }
 
 impl PortalSubscriptionServiceComponents {
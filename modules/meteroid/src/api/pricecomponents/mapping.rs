@@ -1,7 +1,8 @@
 pub mod components {
     use crate::api::domain_mapping::billing_period;
     use crate::api::prices::mapping::prices::{
-        PriceWrapper, cadence_from_proto, pricing_from_proto, pricing_to_proto,
+        PriceWrapper, cadence_from_proto, price_variants_from_proto, pricing_from_proto,
+        pricing_to_proto,
     };
     use crate::api::productitems::mapping::products::{
         fee_structure_from_proto, fee_type_from_proto,
@@ -33,6 +34,7 @@ pub mod components {
                     pricing: pricing_to_proto(pricing),
                     created_at: None,
                     archived_at: None,
+                    variants: vec![],
                 })
                 .collect()
         } else {
@@ -45,6 +47,50 @@ pub mod components {
             name: comp.name.to_string(),
             product_id: comp.product_id.map(|p| p.as_proto()),
             prices,
+            sub_line_presentation: Some(sub_line_presentation_to_proto(
+                &comp.sub_line_presentation,
+            )),
+            line_metadata: comp.line_metadata.into_iter().collect(),
+        }
+    }
+
+    pub fn sub_line_presentation_to_proto(
+        presentation: &domain::SubLinePresentation,
+    ) -> api::SubLinePresentation {
+        use api::sub_line_presentation::Mode;
+
+        let (mode, top_groups_limit) = match presentation {
+            domain::SubLinePresentation::Detailed => (Mode::Detailed, None),
+            domain::SubLinePresentation::Collapsed => (Mode::Collapsed, None),
+            domain::SubLinePresentation::TopGroups { limit } => (Mode::TopGroups, Some(*limit)),
+            domain::SubLinePresentation::TiersOnly => (Mode::TiersOnly, None),
+        };
+
+        api::SubLinePresentation {
+            mode: mode.into(),
+            top_groups_limit,
+        }
+    }
+
+    pub fn sub_line_presentation_from_proto(
+        presentation: Option<api::SubLinePresentation>,
+    ) -> Result<domain::SubLinePresentation, Status> {
+        use api::sub_line_presentation::Mode;
+
+        let Some(presentation) = presentation else {
+            return Ok(domain::SubLinePresentation::Detailed);
+        };
+
+        match presentation.mode() {
+            Mode::Detailed => Ok(domain::SubLinePresentation::Detailed),
+            Mode::Collapsed => Ok(domain::SubLinePresentation::Collapsed),
+            Mode::TopGroups => match presentation.top_groups_limit {
+                Some(limit) if limit > 0 => Ok(domain::SubLinePresentation::TopGroups { limit }),
+                _ => Err(Status::invalid_argument(
+                    "top_groups_limit must be positive with the TOP_GROUPS presentation",
+                )),
+            },
+            Mode::TiersOnly => Ok(domain::SubLinePresentation::TiersOnly),
         }
     }
 
@@ -109,6 +155,7 @@ pub mod components {
             cadence,
             currency: pi.currency,
             pricing,
+            variants: price_variants_from_proto(pi.variants)?,
         })
     }
 }
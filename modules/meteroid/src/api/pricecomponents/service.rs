@@ -61,6 +61,8 @@ impl PriceComponentsService for PriceComponentServiceComponents {
         let plan_version_id = PlanVersionId::from_proto(&req.plan_version_id)?;
         let product_ref = mapping::components::product_ref_from_proto(req.product)?;
         let price_entries = mapping::components::price_entries_from_proto(req.prices)?;
+        let sub_line_presentation =
+            mapping::components::sub_line_presentation_from_proto(req.sub_line_presentation)?;
 
         if price_entries.is_empty() {
             return Err(Status::invalid_argument("prices must not be empty"));
@@ -72,6 +74,8 @@ impl PriceComponentsService for PriceComponentServiceComponents {
                 req.name,
                 product_ref,
                 price_entries,
+                sub_line_presentation,
+                req.line_metadata.into_iter().collect(),
                 plan_version_id,
                 tenant_id,
             )
@@ -144,6 +148,10 @@ impl PriceComponentsService for PriceComponentServiceComponents {
             id: component_id,
             prices: Vec::new(),
             legacy_pricing: None,
+            sub_line_presentation: mapping::components::sub_line_presentation_from_proto(
+                edit_comp.sub_line_presentation,
+            )?,
+            line_metadata: edit_comp.line_metadata.into_iter().collect(),
         };
 
         let component = self
@@ -1,14 +1,15 @@
 pub mod prices {
     use crate::api::domain_mapping::billing_period;
     use crate::api::shared::conversions::ProtoConv;
+    use common_domain::country::CountryCode;
 
     use meteroid_grpc::meteroid::api::prices::v1 as proto;
     use meteroid_grpc::meteroid::api::shared::v1 as api_shared;
     use meteroid_store::domain;
     use meteroid_store::domain::price_components::{
-        MatrixDimension, MatrixRow, TierRow, UsagePricingModel,
+        MatrixDimension, MatrixRow, TierGroup, TierRow, UsagePricingModel,
     };
-    use meteroid_store::domain::prices::Pricing;
+    use meteroid_store::domain::prices::{PriceCondition, PriceVariant, Pricing};
     use rust_decimal::Decimal;
     use tonic::Status;
 
@@ -24,10 +25,93 @@ pub mod prices {
                 pricing: pricing_to_proto(&price.pricing),
                 created_at: Some(price.created_at.as_proto()),
                 archived_at: price.archived_at.map(|d| d.as_proto()),
+                variants: price.variants.iter().map(price_variant_to_proto).collect(),
             })
         }
     }
 
+    pub fn price_variant_to_proto(variant: &PriceVariant) -> proto::PriceVariant {
+        use proto::price::Pricing as P;
+        use proto::price_variant::Pricing as V;
+
+        let condition = match &variant.condition {
+            PriceCondition::BillingCountry { countries } => {
+                proto::price_condition::Condition::BillingCountry(
+                    proto::price_condition::BillingCountry {
+                        countries: countries.iter().map(|c| c.as_proto()).collect(),
+                    },
+                )
+            }
+            PriceCondition::CustomerAttribute { key, values } => {
+                proto::price_condition::Condition::CustomerAttribute(
+                    proto::price_condition::CustomerAttribute {
+                        key: key.clone(),
+                        values: values.clone(),
+                    },
+                )
+            }
+        };
+
+        proto::PriceVariant {
+            condition: Some(proto::PriceCondition {
+                condition: Some(condition),
+            }),
+            pricing: pricing_to_proto(&variant.pricing).map(|p| match p {
+                P::RatePricing(p) => V::RatePricing(p),
+                P::SlotPricing(p) => V::SlotPricing(p),
+                P::CapacityPricing(p) => V::CapacityPricing(p),
+                P::UsagePricing(p) => V::UsagePricing(p),
+                P::ExtraRecurringPricing(p) => V::ExtraRecurringPricing(p),
+                P::OneTimePricing(p) => V::OneTimePricing(p),
+            }),
+        }
+    }
+
+    pub fn price_variants_from_proto(
+        variants: Vec<proto::PriceVariant>,
+    ) -> Result<Vec<PriceVariant>, Status> {
+        use meteroid_grpc::meteroid::api::components::v1::price_input::Pricing as P;
+        use proto::price_variant::Pricing as V;
+
+        variants
+            .into_iter()
+            .map(|variant| {
+                let condition = match variant.condition.and_then(|c| c.condition) {
+                    Some(proto::price_condition::Condition::BillingCountry(c)) => {
+                        PriceCondition::BillingCountry {
+                            countries: c
+                                .countries
+                                .iter()
+                                .map(CountryCode::from_proto)
+                                .collect::<Result<Vec<_>, _>>()?,
+                        }
+                    }
+                    Some(proto::price_condition::Condition::CustomerAttribute(c)) => {
+                        PriceCondition::CustomerAttribute {
+                            key: c.key,
+                            values: c.values,
+                        }
+                    }
+                    None => return Err(Status::invalid_argument("variant condition is required")),
+                };
+
+                let pricing = variant.pricing.map(|p| match p {
+                    V::RatePricing(p) => P::RatePricing(p),
+                    V::SlotPricing(p) => P::SlotPricing(p),
+                    V::CapacityPricing(p) => P::CapacityPricing(p),
+                    V::UsagePricing(p) => P::UsagePricing(p),
+                    V::ExtraRecurringPricing(p) => P::ExtraRecurringPricing(p),
+                    V::OneTimePricing(p) => P::OneTimePricing(p),
+                });
+
+                Ok(PriceVariant {
+                    condition,
+                    pricing: pricing_from_proto(pricing)?,
+                })
+            })
+            .collect()
+    }
+
     pub fn pricing_to_proto(pricing: &Pricing) -> Option<proto::price::Pricing> {
         match pricing {
             Pricing::Rate { rate } => {
@@ -84,16 +168,26 @@ pub mod prices {
             UsagePricingModel::PerUnit { rate } => {
                 proto::usage_pricing::Model::PerUnit(rate.as_proto())
             }
-            UsagePricingModel::Tiered { tiers, block_size } => {
+            UsagePricingModel::Tiered {
+                tiers,
+                block_size,
+                tier_group,
+            } => {
                 proto::usage_pricing::Model::Tiered(proto::usage_pricing::TieredAndVolumePricing {
                     rows: tiers.iter().map(tier_row_to_proto).collect(),
                     block_size: *block_size,
+                    tier_group: tier_group.as_ref().map(tier_group_to_proto),
                 })
             }
-            UsagePricingModel::Volume { tiers, block_size } => {
+            UsagePricingModel::Volume {
+                tiers,
+                block_size,
+                tier_group,
+            } => {
                 proto::usage_pricing::Model::Volume(proto::usage_pricing::TieredAndVolumePricing {
                     rows: tiers.iter().map(tier_row_to_proto).collect(),
                     block_size: *block_size,
+                    tier_group: tier_group.as_ref().map(tier_group_to_proto),
                 })
             }
             UsagePricingModel::Package { block_size, rate } => {
@@ -124,6 +218,15 @@ pub mod prices {
         }
     }
 
+    pub fn tier_group_to_proto(
+        group: &TierGroup,
+    ) -> proto::usage_pricing::tiered_and_volume_pricing::TierGroup {
+        proto::usage_pricing::tiered_and_volume_pricing::TierGroup {
+            key: group.key.clone(),
+            units_per_quantity: group.units_per_quantity.as_proto(),
+        }
+    }
+
     pub fn matrix_row_to_proto(row: &MatrixRow) -> proto::usage_pricing::matrix_pricing::MatrixRow {
         proto::usage_pricing::matrix_pricing::MatrixRow {
             per_unit_price: row.per_unit_price.as_proto(),
@@ -191,6 +294,11 @@ pub mod prices {
                 Ok(UsagePricingModel::Tiered {
                     tiers,
                     block_size: tiered.block_size,
+                    tier_group: tiered
+                        .tier_group
+                        .as_ref()
+                        .map(tier_group_from_proto)
+                        .transpose()?,
                 })
             }
             Some(proto::usage_pricing::Model::Volume(volume)) => {
@@ -202,6 +310,11 @@ pub mod prices {
                 Ok(UsagePricingModel::Volume {
                     tiers,
                     block_size: volume.block_size,
+                    tier_group: volume
+                        .tier_group
+                        .as_ref()
+                        .map(tier_group_from_proto)
+                        .transpose()?,
                 })
             }
             Some(proto::usage_pricing::Model::Package(package)) => {
@@ -245,6 +358,25 @@ pub mod prices {
         })
     }
 
+    pub fn tier_group_from_proto(
+        group: &proto::usage_pricing::tiered_and_volume_pricing::TierGroup,
+    ) -> Result<TierGroup, Status> {
+        let key = group.key.trim();
+        if key.is_empty() {
+            return Err(Status::invalid_argument("tier group key is required"));
+        }
+        let units_per_quantity = Decimal::from_proto_ref(&group.units_per_quantity)?;
+        if units_per_quantity <= Decimal::ZERO {
+            return Err(Status::invalid_argument(
+                "tier group units_per_quantity must be positive",
+            ));
+        }
+        Ok(TierGroup {
+            key: key.to_string(),
+            units_per_quantity,
+        })
+    }
+
     pub fn matrix_row_from_proto(
         row: &proto::usage_pricing::matrix_pricing::MatrixRow,
     ) -> Result<MatrixRow, Status> {
@@ -389,4 +521,114 @@ pub mod prices {
                 .collect(),
         }
     }
+
+    pub fn price_change_from_proto(
+        input: Option<proto::PriceChangeInput>,
+    ) -> Result<domain::price_changes::PriceChangeNew, Status> {
+        use meteroid_grpc::meteroid::api::components::v1::price_input::Pricing as P;
+        use proto::price_change_input::Pricing as C;
+
+        let input = input.ok_or_else(|| Status::invalid_argument("change is required"))?;
+
+        let pricing = input.pricing.map(|p| match p {
+            C::RatePricing(p) => P::RatePricing(p),
+            C::SlotPricing(p) => P::SlotPricing(p),
+            C::CapacityPricing(p) => P::CapacityPricing(p),
+            C::UsagePricing(p) => P::UsagePricing(p),
+            C::ExtraRecurringPricing(p) => P::ExtraRecurringPricing(p),
+            C::OneTimePricing(p) => P::OneTimePricing(p),
+        });
+
+        let grandfathering = match proto::PriceChangeGrandfathering::try_from(input.grandfathering)
+            .map_err(|_| Status::invalid_argument("Invalid grandfathering policy"))?
+        {
+            proto::PriceChangeGrandfathering::AllSubscriptions => {
+                domain::price_changes::PriceChangeGrandfathering::AllSubscriptions
+            }
+            proto::PriceChangeGrandfathering::NewSubscriptionsOnly => {
+                domain::price_changes::PriceChangeGrandfathering::NewSubscriptionsOnly
+            }
+            proto::PriceChangeGrandfathering::RenewingAfter => {
+                let date = input.renewing_after.as_ref().ok_or_else(|| {
+                    Status::invalid_argument("renewing_after is required for RENEWING_AFTER")
+                })?;
+                domain::price_changes::PriceChangeGrandfathering::RenewingAfter(
+                    chrono::NaiveDate::from_proto_ref(date)?,
+                )
+            }
+        };
+
+        Ok(domain::price_changes::PriceChangeNew {
+            price_id: common_domain::ids::PriceId::from_proto(&input.price_id)?,
+            new_pricing: pricing_from_proto(pricing)?,
+            effective_date: chrono::NaiveDate::from_proto_ref(&input.effective_date)?,
+            grandfathering,
+        })
+    }
+
+    pub fn price_change_to_proto(change: domain::price_changes::PriceChange) -> proto::PriceChange {
+        use domain::price_changes::PriceChangeGrandfathering as G;
+
+        let (grandfathering, renewing_after) = match change.grandfathering {
+            G::AllSubscriptions => (proto::PriceChangeGrandfathering::AllSubscriptions, None),
+            G::NewSubscriptionsOnly => {
+                (proto::PriceChangeGrandfathering::NewSubscriptionsOnly, None)
+            }
+            G::RenewingAfter(date) => (
+                proto::PriceChangeGrandfathering::RenewingAfter,
+                Some(date.as_proto()),
+            ),
+        };
+
+        let status = match change.status {
+            domain::enums::PriceChangeStatusEnum::Scheduled => proto::PriceChangeStatus::Scheduled,
+            domain::enums::PriceChangeStatusEnum::Applied => proto::PriceChangeStatus::Applied,
+            domain::enums::PriceChangeStatusEnum::Cancelled => proto::PriceChangeStatus::Cancelled,
+        };
+
+        proto::PriceChange {
+            id: change.id.as_proto(),
+            current_price: Some(PriceWrapper::from(change.current_price).0),
+            new_price: Some(PriceWrapper::from(change.new_price).0),
+            effective_date: change.effective_date.as_proto(),
+            grandfathering: grandfathering.into(),
+            renewing_after,
+            status: status.into(),
+            created_at: change.created_at.as_proto(),
+            applied_at: change.applied_at.map(|d| d.as_proto()),
+            cancelled_at: change.cancelled_at.map(|d| d.as_proto()),
+        }
+    }
+
+    pub fn price_change_preview_to_proto(
+        preview: domain::price_changes::PriceChangePreview,
+    ) -> proto::PreviewPriceChangeResponse {
+        let total_mrr_delta_cents = preview.total_mrr_delta_cents();
+
+        proto::PreviewPriceChangeResponse {
+            currency: preview.currency,
+            affected_plans: preview
+                .affected_plans
+                .into_iter()
+                .map(|p| proto::AffectedPlan {
+                    plan_name: p.plan_name,
+                    versions: p.versions,
+                })
+                .collect(),
+            subscriptions: preview
+                .subscriptions
+                .into_iter()
+                .map(|s| proto::SubscriptionPriceChangeImpact {
+                    subscription_id: s.subscription_id.as_proto(),
+                    customer_id: s.customer_id.as_proto(),
+                    customer_name: s.customer_name,
+                    plan_name: s.plan_name,
+                    apply_date: s.apply_date.as_proto(),
+                    current_mrr_cents: s.current_mrr_cents,
+                    new_mrr_cents: s.new_mrr_cents,
+                })
+                .collect(),
+            total_mrr_delta_cents,
+        }
+    }
 }
@@ -1,9 +1,13 @@
-use common_domain::ids::ProductId;
+use common_domain::ids::{PriceChangeId, PriceId, ProductId};
 use common_grpc::middleware::server::auth::RequestExt;
 use meteroid_grpc::meteroid::api::prices::v1::{
-    ListPricesByProductRequest, ListPricesByProductResponse, PreviewMatrixUpdateRequest,
-    PreviewMatrixUpdateResponse, PreviewPriceRequest, PreviewPriceResponse, PreviewPriceResult,
-    UpdateMatrixPricesRequest, UpdateMatrixPricesResponse, prices_service_server::PricesService,
+    CancelPriceChangeRequest, CancelPriceChangeResponse, ListPriceChangesRequest,
+    ListPriceChangesResponse, ListPricesByProductRequest, ListPricesByProductResponse,
+    PreviewMatrixUpdateRequest, PreviewMatrixUpdateResponse, PreviewPriceChangeRequest,
+    PreviewPriceChangeResponse, PreviewPriceRequest, PreviewPriceResponse, PreviewPriceResult,
+    SchedulePriceChangeRequest, SchedulePriceChangeResponse, UpdateMatrixPricesRequest,
+    UpdateMatrixPricesResponse, UpdatePriceVariantsRequest, UpdatePriceVariantsResponse,
+    prices_service_server::PricesService,
 };
 use meteroid_store::repositories::prices::PriceInterface;
 use meteroid_store::services::invoice_lines::fees::compute_usage_price;
@@ -12,7 +16,8 @@ use tonic::{Request, Response, Status};
 use crate::api::prices::error::PriceApiError;
 use crate::api::prices::mapping::prices::{
     PriceWrapper, matrix_preview_from_proto, matrix_price_update_from_proto,
-    matrix_update_preview_to_proto, usage_model_from_proto,
+    matrix_update_preview_to_proto, price_change_from_proto, price_change_preview_to_proto,
+    price_change_to_proto, price_variants_from_proto, usage_model_from_proto,
 };
 
 use super::PricesServiceComponents;
@@ -120,4 +125,110 @@ impl PricesService for PricesServiceComponents {
 
         Ok(Response::new(PreviewPriceResponse { results }))
     }
+
+    #[tracing::instrument(skip_all)]
+    async fn preview_price_change(
+        &self,
+        request: Request<PreviewPriceChangeRequest>,
+    ) -> Result<Response<PreviewPriceChangeResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let req = request.into_inner();
+
+        let change = price_change_from_proto(req.change)?;
+
+        let preview = self
+            .services
+            .preview_price_change(tenant_id, &change)
+            .await
+            .map_err(Into::<PriceApiError>::into)?;
+
+        Ok(Response::new(price_change_preview_to_proto(preview)))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn schedule_price_change(
+        &self,
+        request: Request<SchedulePriceChangeRequest>,
+    ) -> Result<Response<SchedulePriceChangeResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor = request.actor()?;
+        let req = request.into_inner();
+
+        let change = price_change_from_proto(req.change)?;
+
+        let price_change = self
+            .services
+            .schedule_price_change(tenant_id, change, actor)
+            .await
+            .map_err(Into::<PriceApiError>::into)?;
+
+        Ok(Response::new(SchedulePriceChangeResponse {
+            price_change: Some(price_change_to_proto(price_change)),
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn list_price_changes(
+        &self,
+        request: Request<ListPriceChangesRequest>,
+    ) -> Result<Response<ListPriceChangesResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let req = request.into_inner();
+
+        let product_id = ProductId::from_proto(req.product_id)?;
+
+        let price_changes = self
+            .services
+            .list_price_changes(tenant_id, product_id)
+            .await
+            .map_err(Into::<PriceApiError>::into)?
+            .into_iter()
+            .map(price_change_to_proto)
+            .collect();
+
+        Ok(Response::new(ListPriceChangesResponse { price_changes }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn cancel_price_change(
+        &self,
+        request: Request<CancelPriceChangeRequest>,
+    ) -> Result<Response<CancelPriceChangeResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let req = request.into_inner();
+
+        let id = PriceChangeId::from_proto(req.price_change_id)?;
+
+        let price_change = self
+            .services
+            .cancel_price_change(tenant_id, id)
+            .await
+            .map_err(Into::<PriceApiError>::into)?;
+
+        Ok(Response::new(CancelPriceChangeResponse {
+            price_change: Some(price_change_to_proto(price_change)),
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_price_variants(
+        &self,
+        request: Request<UpdatePriceVariantsRequest>,
+    ) -> Result<Response<UpdatePriceVariantsResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let req = request.into_inner();
+
+        let price_id = PriceId::from_proto(req.price_id)?;
+        let variants = price_variants_from_proto(req.variants)?;
+
+        let price = self
+            .store
+            .update_price_variants(price_id, tenant_id, variants)
+            .await
+            .map_err(Into::<PriceApiError>::into)?;
+
+        Ok(Response::new(UpdatePriceVariantsResponse {
+            price: Some(PriceWrapper::from(price).0),
+        }))
+    }
 }
@@ -34,6 +34,7 @@ pub async fn start_api_server(
         Arc::new(store.clone()),
         object_store.clone(),
         config.public_url.clone(),
+        config.rest_api_external_url.clone(),
         config.jwt_secret.clone(),
     )?;
 
@@ -93,6 +94,7 @@ pub async fn start_api_server(
             object_store.clone(),
         ))
         .add_service(api::connectors::service(store.clone(), services.clone()))
+        .add_service(api::catalog::service(services.clone()))
         .add_service(api::coupons::service(store.clone()))
         .add_service(api::deadletter::service(store.clone()))
         .add_service(api::creditnotes::service(
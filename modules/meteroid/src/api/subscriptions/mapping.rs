@@ -8,11 +8,11 @@ pub mod subscriptions {
     use common_domain::ids::{CustomerId, PlanVersionId, SubscriptionId};
     use common_utils::integers::ToNonNegativeU64;
     use meteroid_grpc::meteroid::api::subscriptions::v1 as proto2;
-    use meteroid_store::domain::SubscriptionStatusEnum;
     use meteroid_store::domain::enums::{ScheduledEventTypeEnum, SubscriptionActivationCondition};
     use meteroid_store::domain::subscriptions::{
         OnlineMethodConfig, OnlineMethodsConfig, PaymentMethodsConfig, PendingScheduledEvent,
     };
+    use meteroid_store::domain::{RenewalPolicyEnum, SubscriptionStatusEnum};
     use tonic::Status;
 
     fn map_activation_condition_proto(
@@ -126,6 +126,7 @@ pub mod subscriptions {
     }
 
     pub(crate) fn domain_to_proto(s: domain::Subscription) -> Result<proto2::Subscription, Status> {
+        let renewal_terms = renewal_terms_to_proto(&s);
         let status = map_subscription_status(s.status) as i32;
 
         Ok(proto2::Subscription {
@@ -167,6 +168,10 @@ pub mod subscriptions {
             payment_methods_config: domain_payment_methods_config_to_proto(
                 s.payment_methods_config,
             ),
+            renewal_terms,
+            instance: s.instance.map(instance_to_proto),
+            invoice_number_prefix: s.invoice_number_prefix,
+            invoice_custom_fields: s.invoice_custom_fields.into_iter().collect(),
         })
     }
 
@@ -197,7 +202,14 @@ pub mod subscriptions {
             purchase_order: param.purchase_order,
             backdate_invoices: false,
             skip_checkout_session: false,
-            skip_past_invoices: param.skip_past_invoices.unwrap_or(false),
+            past_period_billing: domain::PastPeriodBilling::resolve(
+                param
+                    .past_period_billing
+                    .map(past_period_billing_from_proto)
+                    .transpose()?,
+                param.skip_past_invoices.unwrap_or(false),
+            ),
+            instance: param.instance.map(instance_from_proto),
         };
 
         let entitlements = param
@@ -257,6 +269,7 @@ pub mod subscriptions {
         details: domain::SubscriptionDetails,
     ) -> Result<proto2::SubscriptionDetails, Status> {
         let sub = details.subscription;
+        let renewal_terms = renewal_terms_to_proto(&sub);
         let status = map_subscription_status(sub.status) as i32;
         Ok(proto2::SubscriptionDetails {
             subscription: Some(proto2::Subscription {
@@ -298,6 +311,10 @@ pub mod subscriptions {
                 payment_methods_config: domain_payment_methods_config_to_proto(
                     sub.payment_methods_config,
                 ),
+                renewal_terms,
+                instance: sub.instance.map(instance_to_proto),
+                invoice_number_prefix: sub.invoice_number_prefix,
+                invoice_custom_fields: sub.invoice_custom_fields.into_iter().collect(),
             }),
             schedules: vec![], // TODO
             price_components: details
@@ -347,6 +364,8 @@ pub mod subscriptions {
             ScheduledEventTypeEnum::PauseSubscription => proto2::ScheduledEventType::Pause,
             ScheduledEventTypeEnum::EndTrial => proto2::ScheduledEventType::EndTrial,
             ScheduledEventTypeEnum::ApplyAmendment => proto2::ScheduledEventType::Amendment,
+            ScheduledEventTypeEnum::ApplyPriceChange => proto2::ScheduledEventType::PriceChange,
+            ScheduledEventTypeEnum::ResumeSubscription => proto2::ScheduledEventType::Resume,
             // Truly internal billing events are not surfaced to API clients.
             ScheduledEventTypeEnum::FinalizeInvoice | ScheduledEventTypeEnum::RetryPayment => {
                 return None;
@@ -387,6 +406,14 @@ pub mod subscriptions {
                 .purchase_order
                 .as_ref()
                 .map(|p| if p.is_empty() { None } else { Some(p.clone()) }),
+            invoice_number_prefix: req
+                .invoice_number_prefix
+                .as_ref()
+                .map(|p| if p.is_empty() { None } else { Some(p.clone()) }),
+            invoice_custom_fields: req
+                .invoice_custom_fields
+                .as_ref()
+                .map(|f| f.fields.clone().into_iter().collect()),
             payment_methods_config: req
                 .payment_methods_config
                 .as_ref()
@@ -394,6 +421,90 @@ pub mod subscriptions {
                 .transpose()?,
         })
     }
+
+    pub fn pause_billing_from_proto(billing: i32) -> domain::PauseBilling {
+        match proto2::PauseBilling::try_from(billing) {
+            Ok(proto2::PauseBilling::KeepFixedFees) => domain::PauseBilling::KeepFixedFees,
+            _ => domain::PauseBilling::NoBilling,
+        }
+    }
+
+    pub fn resume_anchor_from_proto(anchor: i32) -> domain::ResumeBillingAnchor {
+        match proto2::ResumeBillingAnchor::try_from(anchor) {
+            Ok(proto2::ResumeBillingAnchor::Reset) => {
+                domain::ResumeBillingAnchor::ResetToResumeDate
+            }
+            _ => domain::ResumeBillingAnchor::Keep,
+        }
+    }
+
+    fn past_period_billing_from_proto(value: i32) -> Result<domain::PastPeriodBilling, Status> {
+        match proto2::PastPeriodBilling::try_from(value)
+            .map_err(|_| Status::invalid_argument("Invalid past period billing"))?
+        {
+            proto2::PastPeriodBilling::Invoice => Ok(domain::PastPeriodBilling::Invoice),
+            proto2::PastPeriodBilling::ImportAsPaid => Ok(domain::PastPeriodBilling::ImportAsPaid),
+            proto2::PastPeriodBilling::Skip => Ok(domain::PastPeriodBilling::Skip),
+        }
+    }
+
+    fn instance_to_proto(instance: domain::SubscriptionInstance) -> proto2::SubscriptionInstance {
+        proto2::SubscriptionInstance {
+            id: instance.id,
+            usage_property: instance.usage_property,
+            metadata: instance.metadata.into_iter().collect(),
+        }
+    }
+
+    fn instance_from_proto(instance: proto2::SubscriptionInstance) -> domain::SubscriptionInstance {
+        domain::SubscriptionInstance {
+            id: instance.id,
+            usage_property: instance.usage_property,
+            metadata: instance.metadata.into_iter().collect(),
+        }
+    }
+
+    fn renewal_policy_to_proto(policy: RenewalPolicyEnum) -> proto2::RenewalPolicy {
+        match policy {
+            RenewalPolicyEnum::Expire => proto2::RenewalPolicy::Expire,
+            RenewalPolicyEnum::RenewSameTerm => proto2::RenewalPolicy::RenewSameTerm,
+            RenewalPolicyEnum::RenewMonthToMonth => proto2::RenewalPolicy::RenewMonthToMonth,
+            RenewalPolicyEnum::RenewToPlanVersion => proto2::RenewalPolicy::RenewToPlanVersion,
+        }
+    }
+
+    fn renewal_terms_to_proto(s: &domain::Subscription) -> Option<proto2::RenewalTerms> {
+        s.end_date?;
+        Some(proto2::RenewalTerms {
+            policy: renewal_policy_to_proto(s.renewal_policy) as i32,
+            term_months: s.renewal_term_months,
+            plan_version_id: s.renewal_plan_version_id.map(|id| id.as_proto()),
+            notice_days: s.renewal_notice_days,
+            opt_out_days: s.renewal_opt_out_days,
+            opt_out_deadline: s.renewal_opt_out_deadline().as_proto(),
+        })
+    }
+
+    pub fn renewal_terms_from_proto(
+        terms: proto2::RenewalTerms,
+    ) -> Result<domain::RenewalTerms, Status> {
+        let policy = match proto2::RenewalPolicy::try_from(terms.policy)
+            .map_err(|_| Status::invalid_argument("Invalid renewal policy"))?
+        {
+            proto2::RenewalPolicy::Expire => RenewalPolicyEnum::Expire,
+            proto2::RenewalPolicy::RenewSameTerm => RenewalPolicyEnum::RenewSameTerm,
+            proto2::RenewalPolicy::RenewMonthToMonth => RenewalPolicyEnum::RenewMonthToMonth,
+            proto2::RenewalPolicy::RenewToPlanVersion => RenewalPolicyEnum::RenewToPlanVersion,
+        };
+
+        Ok(domain::RenewalTerms {
+            policy,
+            term_months: terms.term_months,
+            plan_version_id: PlanVersionId::from_proto_opt(terms.plan_version_id)?,
+            notice_days: terms.notice_days,
+            opt_out_days: terms.opt_out_days,
+        })
+    }
 }
 
 pub mod price_components {
@@ -504,6 +615,12 @@ pub mod price_components {
             domain::enums::SubscriptionFeeBillingPeriod::OneTime => {
                 api::SubscriptionFeeBillingPeriod::OneTime
             }
+            domain::enums::SubscriptionFeeBillingPeriod::Daily => {
+                api::SubscriptionFeeBillingPeriod::Daily
+            }
+            domain::enums::SubscriptionFeeBillingPeriod::Weekly => {
+                api::SubscriptionFeeBillingPeriod::Weekly
+            }
             domain::enums::SubscriptionFeeBillingPeriod::Monthly => {
                 api::SubscriptionFeeBillingPeriod::Monthly
             }
@@ -679,6 +796,8 @@ pub mod price_components {
         period: api_shared::BillingPeriod,
     ) -> domain::enums::BillingPeriodEnum {
         match period {
+            api_shared::BillingPeriod::Daily => domain::enums::BillingPeriodEnum::Daily,
+            api_shared::BillingPeriod::Weekly => domain::enums::BillingPeriodEnum::Weekly,
             api_shared::BillingPeriod::Monthly => domain::enums::BillingPeriodEnum::Monthly,
             api_shared::BillingPeriod::Quarterly => domain::enums::BillingPeriodEnum::Quarterly,
             api_shared::BillingPeriod::Semiannual => domain::enums::BillingPeriodEnum::Semiannual,
@@ -688,6 +807,8 @@ pub mod price_components {
 
     fn billing_period_to_grpc(period: BillingPeriodEnum) -> api_shared::BillingPeriod {
         match period {
+            BillingPeriodEnum::Daily => api_shared::BillingPeriod::Daily,
+            BillingPeriodEnum::Weekly => api_shared::BillingPeriod::Weekly,
             BillingPeriodEnum::Monthly => api_shared::BillingPeriod::Monthly,
             BillingPeriodEnum::Quarterly => api_shared::BillingPeriod::Quarterly,
             BillingPeriodEnum::Semiannual => api_shared::BillingPeriod::Semiannual,
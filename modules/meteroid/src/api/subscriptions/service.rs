@@ -12,28 +12,35 @@ use meteroid_grpc::meteroid::api::subscriptions::v1::{
     ApplyAmendmentResponse, CancelAmendmentRequest, CancelAmendmentResponse,
     CancelPlanChangeRequest, CancelPlanChangeResponse, CancelScheduledEventRequest,
     CancelScheduledEventResponse, CancelSlotTransactionRequest, CancelSlotTransactionResponse,
-    CancelSubscriptionRequest, CancelSubscriptionResponse, CreateSubscriptionRequest,
-    CreateSubscriptionResponse, CreateSubscriptionsRequest, CreateSubscriptionsResponse,
-    GenerateCheckoutTokenRequest, GenerateCheckoutTokenResponse, GetSlotsValueRequest,
-    GetSlotsValueResponse, GetSubscriptionComponentUsageRequest,
+    CancelSubscriptionRequest, CancelSubscriptionResponse, ChangeBillingAnchorRequest,
+    ChangeBillingAnchorResponse, ChangeTrialingPlanRequest, ChangeTrialingPlanResponse,
+    CreateSubscriptionRequest, CreateSubscriptionResponse, CreateSubscriptionsRequest,
+    CreateSubscriptionsResponse, EndTrialRequest, EndTrialResponse, ExtendTrialRequest,
+    ExtendTrialResponse, GenerateCheckoutTokenRequest, GenerateCheckoutTokenResponse,
+    GetSlotsValueRequest, GetSlotsValueResponse, GetSubscriptionComponentUsageRequest,
     GetSubscriptionComponentUsageResponse, GetUpcomingInvoiceRequest, GetUpcomingInvoiceResponse,
     ListSlotTransactionsRequest, ListSlotTransactionsResponse, ListSubscriptionsRequest,
-    ListSubscriptionsResponse, MrrChange, PreviewAmendmentRequest, PreviewAmendmentResponse,
-    PreviewCreateSubscriptionRequest, PreviewCreateSubscriptionResponse, PreviewPlanChangeRequest,
-    PreviewPlanChangeResponse, PreviewSlotUpdateRequest, PreviewSlotUpdateResponse,
-    SchedulePlanChangeRequest, SchedulePlanChangeResponse, SubscriptionDetails,
-    SyncToHubspotRequest, SyncToHubspotResponse, UpdateSlotsRequest, UpdateSlotsResponse,
-    UpdateSubscriptionRequest, UpdateSubscriptionResponse,
+    ListSubscriptionsResponse, MrrChange, OptOutOfRenewalRequest, OptOutOfRenewalResponse,
+    PauseSubscriptionRequest, PauseSubscriptionResponse, PreviewAmendmentRequest,
+    PreviewAmendmentResponse, PreviewBillingAnchorChangeRequest,
+    PreviewBillingAnchorChangeResponse, PreviewCreateSubscriptionRequest,
+    PreviewCreateSubscriptionResponse, PreviewPlanChangeRequest, PreviewPlanChangeResponse,
+    PreviewSlotUpdateRequest, PreviewSlotUpdateResponse, ReactivateSubscriptionRequest,
+    ReactivateSubscriptionResponse, ResumeSubscriptionRequest, ResumeSubscriptionResponse,
+    SchedulePlanChangeRequest, SchedulePlanChangeResponse, SetRenewalTermsRequest,
+    SetRenewalTermsResponse, SubscriptionDetails, SyncToHubspotRequest, SyncToHubspotResponse,
+    UpdateSlotsRequest, UpdateSlotsResponse, UpdateSubscriptionRequest, UpdateSubscriptionResponse,
 };
 
-use crate::api::shared::conversions::ProtoConv;
+use crate::api::shared::conversions::{FromProtoOpt, ProtoConv};
 use crate::api::subscriptions::error::SubscriptionApiError;
 use crate::api::subscriptions::{SubscriptionServiceComponents, mapping};
 use crate::api::utils::PaginationExt;
 use meteroid_store::repositories::SubscriptionInterface;
 use meteroid_store::repositories::subscriptions::{
-    CancellationEffectiveAt, SubscriptionInterfaceAuto,
+    CancellationEffectiveAt, PauseEffectiveAt, PauseParams, SubscriptionInterfaceAuto,
 };
+use meteroid_store::services::invoice_lines::presentation::present_invoice_lines;
 
 fn added_component_to_grpc(
     a: meteroid_store::domain::subscription_changes::AddedComponent,
@@ -344,6 +351,354 @@ impl SubscriptionsService for SubscriptionServiceComponents {
         })
     }
 
+    #[tracing::instrument(skip_all)]
+    async fn pause_subscription(
+        &self,
+        request: Request<PauseSubscriptionRequest>,
+    ) -> Result<Response<PauseSubscriptionResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        use meteroid_grpc::meteroid::api::subscriptions::v1::pause_subscription_request::EffectiveAt;
+
+        let effective_at = match inner.effective_at {
+            Some(EffectiveAt::Immediate(_)) => {
+                PauseEffectiveAt::Date(chrono::Utc::now().date_naive())
+            }
+            Some(EffectiveAt::Date(date)) => {
+                PauseEffectiveAt::Date(chrono::NaiveDate::from_proto(date)?)
+            }
+            Some(EffectiveAt::BillingPeriodEnd(_)) | None => PauseEffectiveAt::EndOfBillingPeriod,
+        };
+
+        let params = PauseParams {
+            billing: mapping::subscriptions::pause_billing_from_proto(inner.billing),
+            resume_on: chrono::NaiveDate::from_proto_opt(inner.resume_on)?,
+            resume_anchor: mapping::subscriptions::resume_anchor_from_proto(inner.resume_anchor),
+        };
+
+        let subscription = self
+            .services
+            .pause_subscription(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+                effective_at,
+                params,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to pause subscription".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(PauseSubscriptionResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn resume_subscription(
+        &self,
+        request: Request<ResumeSubscriptionRequest>,
+    ) -> Result<Response<ResumeSubscriptionResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription = self
+            .services
+            .resume_subscription(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+                chrono::NaiveDate::from_proto_opt(inner.resume_on)?,
+                mapping::subscriptions::resume_anchor_from_proto(inner.anchor),
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to resume subscription".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(ResumeSubscriptionResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn reactivate_subscription(
+        &self,
+        request: Request<ReactivateSubscriptionRequest>,
+    ) -> Result<Response<ReactivateSubscriptionResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription = self
+            .services
+            .reactivate_subscription(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to reactivate subscription".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(ReactivateSubscriptionResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn preview_billing_anchor_change(
+        &self,
+        request: Request<PreviewBillingAnchorChangeRequest>,
+    ) -> Result<Response<PreviewBillingAnchorChangeResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let inner = request.into_inner();
+
+        let billing_day_anchor = u16::try_from(inner.billing_day_anchor).map_err(|_| {
+            SubscriptionApiError::InvalidArgument("Invalid billing day".to_string())
+        })?;
+
+        let preview = self
+            .services
+            .preview_billing_anchor_change(
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+                billing_day_anchor,
+            )
+            .await
+            .map_err(Into::<SubscriptionApiError>::into)?;
+
+        Ok(Response::new(PreviewBillingAnchorChangeResponse {
+            current_period_start: preview.current_period_start.to_string(),
+            current_period_end: preview.current_period_end.to_string(),
+            new_period_end: preview.new_period_end.to_string(),
+            proration: Some(mapping::plan_change::proration_summary_to_grpc(
+                &preview.proration,
+            )),
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn change_billing_anchor(
+        &self,
+        request: Request<ChangeBillingAnchorRequest>,
+    ) -> Result<Response<ChangeBillingAnchorResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription_id = SubscriptionId::from_proto(inner.subscription_id)?;
+        let billing_day_anchor = u16::try_from(inner.billing_day_anchor).map_err(|_| {
+            SubscriptionApiError::InvalidArgument("Invalid billing day".to_string())
+        })?;
+
+        let result = self
+            .services
+            .change_billing_anchor(actor_typed, subscription_id, tenant_id, billing_day_anchor)
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to change billing day".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        let subscription = self
+            .store
+            .get_subscription(tenant_id, subscription_id)
+            .await
+            .map_err(Into::<SubscriptionApiError>::into)?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(ChangeBillingAnchorResponse {
+                subscription: Some(s),
+                adjustment_invoice_id: result.adjustment_invoice_id.map(|id| id.to_string()),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn extend_trial(
+        &self,
+        request: Request<ExtendTrialRequest>,
+    ) -> Result<Response<ExtendTrialResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription = self
+            .services
+            .extend_trial(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+                chrono::NaiveDate::from_proto(inner.trial_end)?,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to extend trial".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(ExtendTrialResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn end_trial(
+        &self,
+        request: Request<EndTrialRequest>,
+    ) -> Result<Response<EndTrialResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription = self
+            .services
+            .end_trial_now(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to end trial".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(EndTrialResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn change_trialing_plan(
+        &self,
+        request: Request<ChangeTrialingPlanRequest>,
+    ) -> Result<Response<ChangeTrialingPlanResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription = self
+            .services
+            .change_trialing_plan(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+                PlanId::from_proto_opt(inner.trialing_plan_id)?,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to change trialing plan".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(ChangeTrialingPlanResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn set_renewal_terms(
+        &self,
+        request: Request<SetRenewalTermsRequest>,
+    ) -> Result<Response<SetRenewalTermsResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let terms = inner
+            .terms
+            .ok_or_else(|| Status::invalid_argument("Missing renewal terms"))?;
+
+        let subscription = self
+            .services
+            .set_renewal_terms(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+                mapping::subscriptions::renewal_terms_from_proto(terms)?,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to set renewal terms".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(SetRenewalTermsResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn opt_out_of_renewal(
+        &self,
+        request: Request<OptOutOfRenewalRequest>,
+    ) -> Result<Response<OptOutOfRenewalResponse>, Status> {
+        let tenant_id = request.tenant()?;
+        let actor_typed = request.actor_typed()?;
+        let inner = request.into_inner();
+
+        let subscription = self
+            .services
+            .opt_out_of_renewal(
+                actor_typed,
+                SubscriptionId::from_proto(inner.subscription_id)?,
+                tenant_id,
+            )
+            .await
+            .map_err(|err| {
+                SubscriptionApiError::StoreError(
+                    "Failed to opt out of renewal".to_string(),
+                    Box::new(err.into_error()),
+                )
+            })?;
+
+        mapping::subscriptions::domain_to_proto(subscription).map(|s| {
+            Response::new(OptOutOfRenewalResponse {
+                subscription: Some(s),
+            })
+        })
+    }
+
     #[tracing::instrument(skip_all)]
     async fn sync_to_hubspot(
         &self,
@@ -890,12 +1245,19 @@ impl SubscriptionsService for SubscriptionServiceComponents {
             .await
             .map_err(Into::<SubscriptionApiError>::into)?;
 
-        let content = self
+        let mut content = self
             .services
             .compute_upcoming_invoice(&details)
             .await
             .map_err(Into::<SubscriptionApiError>::into)?;
 
+        // shown as it will be on the invoice document
+        content.invoice_lines =
+            present_invoice_lines(&self.store, tenant_id, &content.invoice_lines)
+                .await
+                .map_err(Into::<SubscriptionApiError>::into)?
+                .lines;
+
         let invoice = mapping::upcoming::computed_content_to_upcoming_proto(content, &details);
 
         Ok(Response::new(GetUpcomingInvoiceResponse {
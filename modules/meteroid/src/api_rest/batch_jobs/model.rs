@@ -40,6 +40,7 @@ pub enum BatchJobType {
     CustomerCsvImport,
     SubscriptionCsvImport,
     SubscriptionPlanMigration,
+    InvoiceExport,
 }
 
 #[derive(o2o, Clone, Debug, Serialize, Deserialize, ToSchema, PartialEq, Eq)]
@@ -94,6 +95,7 @@ pub struct BatchJobDetailResponse {
     pub error_csv_url: Option<String>,
     pub input_file_name: Option<String>,
     pub input_file_url: Option<String>,
+    pub output_url: Option<String>,
 }
 
 #[derive(Clone, Debug, Serialize, ToSchema)]
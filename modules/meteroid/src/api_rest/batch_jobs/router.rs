@@ -131,6 +131,17 @@ pub(crate) async fn get_batch_job(
         None
     };
 
+    let output_url = if detail.job.output_key.is_some() {
+        crate::api::batchjobs::mapping::generate_output_url(
+            detail.job.id,
+            authorized_state.tenant_id,
+            &app_state.jwt_secret,
+        )
+        .ok()
+    } else {
+        None
+    };
+
     let response = BatchJobDetailResponse {
         id: detail.job.id,
         job_type: detail.job.job_type.into(),
@@ -146,6 +157,7 @@ pub(crate) async fn get_batch_job(
         error_csv_url,
         input_file_name: detail.job.input_file_name,
         input_file_url,
+        output_url,
     };
 
     Ok(Json(response))
@@ -74,9 +74,9 @@ pub async fn create_checkout_session(
         Some(expires_in_hours)
     };
 
-    let components = request.components.map(Into::into);
+    let components = request.components.map(TryInto::try_into).transpose()?;
 
-    let add_ons = request.add_ons.map(sub_mapping::map_add_ons);
+    let add_ons = request.add_ons.map(sub_mapping::map_add_ons).transpose()?;
 
     let create_session = CreateCheckoutSession {
         tenant_id,
@@ -5,5 +5,7 @@ use utoipa_axum::routes;
 pub mod router;
 
 pub fn credit_note_routes() -> OpenApiRouter<AppState> {
-    OpenApiRouter::new().routes(routes!(router::download_credit_note_pdf))
+    OpenApiRouter::new()
+        .routes(routes!(router::download_credit_note_pdf))
+        .routes(routes!(router::download_credit_note_e_invoice))
 }
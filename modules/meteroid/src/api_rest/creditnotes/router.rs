@@ -1,7 +1,9 @@
 use crate::api_rest::AppState;
 use crate::api_rest::error::{ErrorCode, RestErrorResponse};
 use crate::api_rest::invoices::model::BinaryFile;
+use crate::api_rest::invoices::router::e_invoice_error;
 use crate::errors::RestApiError;
+use crate::services::credit_note_rendering::generate_ubl_credit_note;
 use crate::services::storage::Prefix;
 use axum::extract::{Path, State};
 use axum::response::{IntoResponse, Response};
@@ -9,6 +11,7 @@ use axum::{Extension, Json};
 use common_domain::ids::CreditNoteId;
 use common_grpc::middleware::server::auth::AuthorizedAsTenant;
 use hyper::StatusCode;
+use meteroid_store::domain::enums::CreditNoteStatus;
 use meteroid_store::repositories::CreditNoteInterface;
 
 #[utoipa::path(
@@ -74,3 +77,58 @@ pub(crate) async fn download_credit_note_pdf(
             .into_response()),
     }
 }
+
+/// Download the Peppol BIS Billing 3.0 (UBL) e-invoice of a finalized credit note.
+#[utoipa::path(
+    get,
+    path = "/api/v1/credit-notes/{credit_note_id}/e-invoice",
+    tag = "Credit Notes",
+    params(
+        ("credit_note_id" = CreditNoteId, Path, description = "Credit Note ID", example = "cn_123"),
+    ),
+    responses(
+        (status = 200, description = "Credit Note UBL XML", content_type = "application/xml", body = inline(BinaryFile)),
+        (status = 400, description = "Draft credit note, or credit note not representable as an e-invoice", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Credit note not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn download_credit_note_e_invoice(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    Path(credit_note_id): Path<CreditNoteId>,
+    State(app_state): State<AppState>,
+) -> Result<Response, RestApiError> {
+    let credit_note = app_state
+        .store
+        .get_credit_note_by_id(authorized_state.tenant_id, credit_note_id)
+        .await
+        .map_err(|e| {
+            log::error!("Error getting credit note by id: {}", e);
+            RestApiError::StoreError
+        })?;
+
+    if credit_note.status == CreditNoteStatus::Draft {
+        return Err(RestApiError::InvalidInput(
+            "Draft credit notes have no e-invoice".to_string(),
+        ));
+    }
+
+    let xml = generate_ubl_credit_note(&app_state.store, credit_note)
+        .await
+        .map_err(e_invoice_error)?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            ("Content-Type", "application/xml"),
+            ("Content-Disposition", "inline"),
+        ],
+        xml,
+    )
+        .into_response())
+}
@@ -36,6 +36,12 @@ pub fn domain_to_rest(d: domain::Customer) -> Result<Customer, RestApiError> {
             })
             .collect(),
         connected_account_id: d.connected_account_id.map(|id| id.to_string()),
+        custom_attributes: d.custom_attributes,
+        parent_customer_id: d.parent_customer_id,
+        pool_usage_with_parent: d.pool_usage_with_parent,
+        invoice_language: d.invoice_language,
+        invoice_custom_fields: d.invoice_custom_fields,
+        invoice_approval_required: d.invoice_approval_required,
     })
 }
 
@@ -72,6 +78,10 @@ pub fn create_req_to_domain(req: CustomerCreateRequest) -> Result<CustomerNew, R
             .map(|id| ConnectedAccountId::from_str(&id))
             .transpose()
             .map_err(|_| RestApiError::InvalidInput("Invalid connected_account_id".to_string()))?,
+        custom_attributes: req.custom_attributes,
+        invoice_language: req.invoice_language,
+        invoice_custom_fields: req.invoice_custom_fields,
+        invoice_approval_required: req.invoice_approval_required,
     })
 }
 
@@ -105,6 +115,10 @@ pub fn update_req_to_domain(
             })
             .collect(),
         is_tax_exempt: req.is_tax_exempt.unwrap_or(false),
+        custom_attributes: req.custom_attributes,
+        invoice_language: req.invoice_language,
+        invoice_custom_fields: req.invoice_custom_fields,
+        invoice_approval_required: req.invoice_approval_required,
     }
 }
 
@@ -139,5 +153,9 @@ pub fn patch_req_to_domain(id: CustomerId, req: CustomerPatchRequest) -> domain:
         current_payment_method_id: None,
         is_tax_exempt: req.is_tax_exempt,
         connected_account_id: None,
+        custom_attributes: req.custom_attributes,
+        invoice_language: req.invoice_language.map(Some),
+        invoice_custom_fields: req.invoice_custom_fields,
+        invoice_approval_required: req.invoice_approval_required,
     }
 }
@@ -15,5 +15,6 @@ pub fn customer_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(router::patch_customer))
         .routes(routes!(router::archive_customer))
         .routes(routes!(router::unarchive_customer))
+        .routes(routes!(router::set_customer_parent))
         .routes(routes!(router::create_portal_token))
 }
@@ -2,6 +2,7 @@ use crate::api_rest::addresses::model::{Address, ShippingAddress};
 use crate::api_rest::currencies::model::Currency;
 use crate::api_rest::model::{PaginatedRequest, PaginationResponse};
 use common_domain::ids::{CustomerId, InvoicingEntityId, string_serde, string_serde_opt};
+use std::collections::BTreeMap;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
@@ -50,6 +51,21 @@ pub struct Customer {
     pub vat_number: Option<String>,
     pub custom_taxes: Vec<CustomTaxRate>,
     pub connected_account_id: Option<String>,
+    /// Free-form key-value attributes. Used among others to select regional price variants.
+    pub custom_attributes: BTreeMap<String, String>,
+    /// Parent account invoiced and charged for this customer's subscriptions.
+    #[serde(default, with = "string_serde_opt")]
+    pub parent_customer_id: Option<CustomerId>,
+    /// Whether this customer's usage counts towards the tiers of its hierarchy.
+    pub pool_usage_with_parent: bool,
+    /// Language tag (e.g. "de-DE") of the invoices and emails sent to this customer.
+    /// Defaults to the language of the invoicing entity's country.
+    pub invoice_language: Option<String>,
+    /// Custom fields (e.g. cost center) set on the invoices of this customer,
+    /// unless overridden by the subscription.
+    pub invoice_custom_fields: BTreeMap<String, String>,
+    /// Whether the invoices of this customer need approval before they are finalized.
+    pub invoice_approval_required: bool,
 }
 
 #[derive(ToSchema, serde::Serialize, serde::Deserialize, Validate, Debug)]
@@ -68,6 +84,16 @@ pub struct CustomerCreateRequest {
     pub custom_taxes: Vec<CustomTaxRate>,
     pub is_tax_exempt: Option<bool>,
     pub connected_account_id: Option<String>,
+    #[serde(default)]
+    pub custom_attributes: BTreeMap<String, String>,
+    /// Language tag (e.g. "de-DE") of the invoices and emails sent to this customer.
+    pub invoice_language: Option<String>,
+    /// Custom fields (e.g. cost center) set on the invoices of this customer.
+    #[serde(default)]
+    pub invoice_custom_fields: BTreeMap<String, String>,
+    /// Whether the invoices of this customer need approval before they are finalized.
+    #[serde(default)]
+    pub invoice_approval_required: bool,
 }
 
 #[derive(ToSchema, serde::Serialize, serde::Deserialize, Validate)]
@@ -85,6 +111,16 @@ pub struct CustomerUpdateRequest {
     pub vat_number: Option<String>,
     pub custom_taxes: Vec<CustomTaxRate>,
     pub is_tax_exempt: Option<bool>,
+    #[serde(default)]
+    pub custom_attributes: BTreeMap<String, String>,
+    /// Language tag (e.g. "de-DE") of the invoices and emails sent to this customer.
+    pub invoice_language: Option<String>,
+    /// Custom fields (e.g. cost center) set on the invoices of this customer.
+    #[serde(default)]
+    pub invoice_custom_fields: BTreeMap<String, String>,
+    /// Whether the invoices of this customer need approval before they are finalized.
+    #[serde(default)]
+    pub invoice_approval_required: bool,
 }
 
 #[derive(ToSchema, serde::Serialize, serde::Deserialize, Validate, Default)]
@@ -102,6 +138,24 @@ pub struct CustomerPatchRequest {
     pub vat_number: Option<String>,
     pub custom_taxes: Option<Vec<CustomTaxRate>>,
     pub is_tax_exempt: Option<bool>,
+    /// Replaces all custom attributes when set.
+    pub custom_attributes: Option<BTreeMap<String, String>>,
+    /// Language tag (e.g. "de-DE") of the invoices and emails sent to this customer.
+    pub invoice_language: Option<String>,
+    /// Replaces all invoice custom fields when set.
+    pub invoice_custom_fields: Option<BTreeMap<String, String>>,
+    /// Whether the invoices of this customer need approval before they are finalized.
+    pub invoice_approval_required: Option<bool>,
+}
+
+#[derive(ToSchema, serde::Serialize, serde::Deserialize, Validate)]
+pub struct CustomerParentRequest {
+    /// The parent customer. Omit to detach the customer from its parent.
+    #[serde(default, with = "string_serde_opt")]
+    pub parent_customer_id: Option<CustomerId>,
+    /// Pool the customer's usage with the rest of the hierarchy for tiering.
+    #[serde(default)]
+    pub pool_usage_with_parent: bool,
 }
 
 // TODO : allow importing from stripe
@@ -4,7 +4,8 @@ use crate::api_rest::customers::mapping::{
 };
 use crate::api_rest::customers::model::{
     Customer, CustomerCreateRequest, CustomerListRequest, CustomerListResponse,
-    CustomerPatchRequest, CustomerPortalTokenResponse, CustomerUpdateRequest,
+    CustomerParentRequest, CustomerPatchRequest, CustomerPortalTokenResponse,
+    CustomerUpdateRequest,
 };
 use crate::api_rest::error::RestErrorResponse;
 use crate::api_rest::model::{PaginationExt, validate_order_by};
@@ -347,6 +348,63 @@ pub(crate) async fn unarchive_customer(
         .map(|()| StatusCode::NO_CONTENT)
 }
 
+/// Set the parent of a customer
+///
+/// Subscriptions of a child customer are invoiced to, and charged against the payment method of, its parent.
+/// Hierarchies are one level deep and must share the same invoicing entity.
+#[utoipa::path(
+    put,
+    tag = "Customers",
+    path = "/api/v1/customers/{id_or_alias}/parent",
+    params(
+        ("id_or_alias" = String, Path, description = "customer ID or alias")
+    ),
+    request_body(content = CustomerParentRequest, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Customer", body = Customer),
+        (status = 400, description = "Bad request", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Customer not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn set_customer_parent(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Valid(Path(id_or_alias)): Valid<Path<AliasOr<CustomerId>>>,
+    Valid(Json(payload)): Valid<Json<CustomerParentRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let customer = app_state
+        .store
+        .find_customer_by_id_or_alias(id_or_alias, authorized_state.tenant_id)
+        .await
+        .map_err(|e| {
+            log::error!("Error finding customer: {e}");
+            RestApiError::from(e)
+        })?;
+
+    app_state
+        .store
+        .set_customer_parent(
+            authorized_state.as_actor(),
+            authorized_state.tenant_id,
+            customer.id,
+            payload.parent_customer_id,
+            payload.pool_usage_with_parent,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling set_customer_parent: {e}");
+            RestApiError::from(e)
+        })
+        .and_then(domain_to_rest)
+        .map(Json)
+}
+
 /// Generate a portal token for a customer
 ///
 /// Generates a JWT token that grants access to the customer portal.
@@ -8,6 +8,10 @@ pub fn file_routes() -> Router<AppState> {
     Router::new()
         .route("/v1/logo/{uuid}", get(router::get_logo))
         .route("/v1/invoice/pdf/{uuid}", get(router::get_invoice_pdf))
+        .route(
+            "/v1/invoice/usage/{uuid}",
+            get(router::get_invoice_usage_annex),
+        )
         .route(
             "/v1/credit-note/pdf/{uuid}",
             get(router::get_credit_note_pdf),
@@ -24,4 +28,8 @@ pub fn file_routes() -> Router<AppState> {
             "/v1/batch-job/input/{batch_job_id}",
             get(router::get_batch_job_input_file),
         )
+        .route(
+            "/v1/batch-job/output/{batch_job_id}",
+            get(router::get_batch_job_output),
+        )
 }
@@ -14,16 +14,19 @@ use crate::errors;
 use crate::api::sharable::ShareableEntityClaims;
 use crate::api_rest::error::{ErrorCode, RestErrorResponse};
 use crate::services::storage::Prefix;
+use crate::services::usage_annex::usage_annex_csv;
 use common_domain::ids::{
     BaseId, BatchJobId, CreditNoteId, EntityActivityId, InvoiceId, StoredDocumentId,
 };
 use error_stack::{Report, ResultExt};
 use image::ImageFormat::Png;
 use jsonwebtoken::{DecodingKey, Validation, decode};
+use meteroid_store::jwt_claims::{PortalJwtClaims, ResourceAccess};
 use meteroid_store::repositories::CreditNoteInterface;
 use meteroid_store::repositories::InvoiceInterface;
 use meteroid_store::repositories::batch_jobs::BatchJobsInterface;
 use meteroid_store::repositories::entity_activity::EntityActivityInterfaceEmail;
+use meteroid_store::services::invoice_lines::presentation::present_invoice;
 use secrecy::ExposeSecret;
 use serde::Deserialize;
 
@@ -135,6 +138,89 @@ async fn get_invoice_pdf_handler(
     }
 }
 
+#[axum::debug_handler]
+pub async fn get_invoice_usage_annex(
+    Path(uid): Path<InvoiceId>,
+    Query(params): Query<TokenParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match get_invoice_usage_annex_handler(uid, params, app_state).await {
+        Ok(r) => r.into_response(),
+        Err(e) => {
+            log::error!("Error handling invoice usage annex: {e:?}");
+            e.current_context().clone().into_response()
+        }
+    }
+}
+
+/// Accepts the document sharing key, or the invoice portal token linked from the PDF.
+async fn get_invoice_usage_annex_handler(
+    invoice_uid: InvoiceId,
+    token: TokenParams,
+    app_state: AppState,
+) -> Result<Response, Report<errors::RestApiError>> {
+    let decoding_key = DecodingKey::from_secret(app_state.jwt_secret.expose_secret().as_bytes());
+
+    let (tenant_id, invoice_id) = match decode::<ShareableEntityClaims>(
+        &token.token,
+        &decoding_key,
+        &Validation::default(),
+    ) {
+        Ok(decoded) => (decoded.claims.tenant_id, decoded.claims.entity_id.into()),
+        Err(_) => {
+            let mut validation = Validation::default();
+            validation.set_required_spec_claims(&Vec::<String>::new());
+            let claims = decode::<PortalJwtClaims>(&token.token, &decoding_key, &validation)
+                .map_err(|_| Report::new(errors::RestApiError::Unauthorized))?
+                .claims;
+            match claims.resource {
+                ResourceAccess::Invoice(invoice_id) => (claims.tenant_id, invoice_id),
+                _ => return Err(Report::new(errors::RestApiError::Forbidden)),
+            }
+        }
+    };
+
+    if invoice_id != invoice_uid {
+        return Err(Report::new(errors::RestApiError::Forbidden));
+    }
+
+    let invoice = app_state
+        .store
+        .get_invoice_by_id(tenant_id, invoice_id)
+        .await
+        .change_context(errors::RestApiError::StoreError)?;
+
+    let presented = present_invoice(&app_state.store, &invoice)
+        .await
+        .change_context(errors::RestApiError::StoreError)?;
+
+    if presented.annex.is_empty() {
+        return Err(Report::new(errors::RestApiError::NotFound));
+    }
+
+    let data = usage_annex_csv(&presented.annex, &invoice.currency)
+        .change_context(errors::RestApiError::StoreError)?;
+
+    let filename = if invoice.invoice_number.is_empty() {
+        format!("usage-{}.csv", invoice.id)
+    } else {
+        format!("usage-{}.csv", invoice.invoice_number)
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            ("Content-Type", "text/csv"),
+            (
+                "Content-Disposition",
+                &format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
 #[axum::debug_handler]
 pub async fn get_credit_note_pdf(
     Path(uid): Path<CreditNoteId>,
@@ -389,6 +475,80 @@ async fn get_batch_job_input_file_handler(
         .into_response())
 }
 
+#[axum::debug_handler]
+pub async fn get_batch_job_output(
+    Path(batch_job_id): Path<BatchJobId>,
+    Query(params): Query<TokenParams>,
+    State(app_state): State<AppState>,
+) -> impl IntoResponse {
+    match get_batch_job_output_handler(batch_job_id, params, app_state).await {
+        Ok(r) => r.into_response(),
+        Err(e) => {
+            log::error!("Error handling batch job output: {e:?}");
+            e.current_context().clone().into_response()
+        }
+    }
+}
+
+async fn get_batch_job_output_handler(
+    batch_job_id: BatchJobId,
+    token: TokenParams,
+    app_state: AppState,
+) -> Result<Response, Report<errors::RestApiError>> {
+    let claims = decode::<ShareableEntityClaims>(
+        &token.token,
+        &DecodingKey::from_secret(app_state.jwt_secret.expose_secret().as_bytes()),
+        &Validation::default(),
+    )
+    .map_err(|_| Report::new(errors::RestApiError::Unauthorized))?
+    .claims;
+
+    let detail = app_state
+        .store
+        .get_batch_job(claims.entity_id.into(), claims.tenant_id)
+        .await
+        .change_context(errors::RestApiError::StoreError)?;
+
+    if detail.job.id != batch_job_id {
+        return Err(Report::new(errors::RestApiError::Forbidden));
+    }
+
+    let output_key = detail
+        .job
+        .output_key
+        .ok_or(Report::new(errors::RestApiError::NotFound))?;
+
+    let doc_id: StoredDocumentId = output_key
+        .parse()
+        .map_err(|_| Report::new(errors::RestApiError::StoreError))?;
+
+    let data = app_state
+        .object_store
+        .retrieve(
+            doc_id,
+            Prefix::BatchJobOutput {
+                tenant_id: claims.tenant_id,
+            },
+        )
+        .await
+        .change_context(errors::RestApiError::ObjectStoreError)?;
+
+    let filename = format!("invoices-{}.zip", batch_job_id);
+
+    Ok((
+        StatusCode::OK,
+        [
+            ("Content-Type", "application/zip"),
+            (
+                "Content-Disposition",
+                &format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        data,
+    )
+        .into_response())
+}
+
 async fn get_batch_job_error_csv_handler(
     batch_job_id: BatchJobId,
     token: TokenParams,
@@ -1,10 +1,11 @@
 use crate::api_rest::currencies;
 use crate::api_rest::invoices::model::{
-    Address, CouponLineItem, CustomerDetails, Invoice, InvoicePaymentStatus, InvoiceType,
-    PaymentStatusEnum, PaymentTypeEnum, SubLineItem, TaxBreakdownItem, TaxExemptionType,
-    Transaction,
+    Address, CouponLineItem, CreateInvoiceLineItem, CreateInvoiceRequest, CustomerDetails, Invoice,
+    InvoiceApprovalStatus, InvoicePaymentStatus, InvoiceType, PaymentStatusEnum, PaymentTypeEnum,
+    SubLineItem, TaxBreakdownItem, TaxExemptionType, Transaction,
 };
 use crate::errors::RestApiError;
+use common_domain::ids::CustomerId;
 use meteroid_store::domain;
 
 pub fn domain_to_rest(
@@ -40,6 +41,7 @@ pub fn domain_to_rest(
                     end_date: li.end_date,
                     tax_rate: li.tax_rate,
                     sub_line_items: li.sub_lines.into_iter().map(map_subline_to_rest).collect(),
+                    metadata: li.metadata,
                 })
             })
             .collect::<Result<Vec<_>, RestApiError>>()?,
@@ -61,11 +63,14 @@ pub fn domain_to_rest(
         net_terms: d.net_terms,
         reference: d.reference,
         purchase_order: d.purchase_order,
+        custom_fields: d.custom_fields,
         created_at: d.created_at,
         updated_at: d.updated_at,
         finalized_at: d.finalized_at,
         voided_at: d.voided_at,
         marked_as_uncollectible_at: d.marked_as_uncollectible_at,
+        approval_status: d.approval_status.map(map_approval_status_to_rest),
+        approval_comment: d.approval_comment,
     })
 }
 
@@ -130,6 +135,16 @@ fn map_payment_status_to_rest(status: domain::enums::InvoicePaymentStatus) -> In
     }
 }
 
+fn map_approval_status_to_rest(
+    status: domain::enums::InvoiceApprovalStatus,
+) -> InvoiceApprovalStatus {
+    match status {
+        domain::enums::InvoiceApprovalStatus::Pending => InvoiceApprovalStatus::Pending,
+        domain::enums::InvoiceApprovalStatus::Approved => InvoiceApprovalStatus::Approved,
+        domain::enums::InvoiceApprovalStatus::Rejected => InvoiceApprovalStatus::Rejected,
+    }
+}
+
 fn map_customer_details_to_rest(details: domain::InlineCustomer) -> CustomerDetails {
     CustomerDetails {
         id: details.id,
@@ -167,6 +182,7 @@ fn map_invoice_type_to_rest(invoice_type: domain::enums::InvoiceType) -> Invoice
         domain::enums::InvoiceType::OneOff => InvoiceType::OneOff,
         domain::enums::InvoiceType::Adjustment => InvoiceType::Adjustment,
         domain::enums::InvoiceType::UsageThreshold => InvoiceType::UsageThreshold,
+        domain::enums::InvoiceType::Imported => InvoiceType::Imported,
     }
 }
 
@@ -179,3 +195,46 @@ fn map_subline_to_rest(subline: domain::invoice_lines::SubLineItem) -> SubLineIt
         unit_price: subline.unit_price,
     }
 }
+
+pub fn create_request_to_domain(
+    req: CreateInvoiceRequest,
+    customer_id: CustomerId,
+) -> domain::OneOffInvoiceNew {
+    domain::OneOffInvoiceNew {
+        customer_id,
+        currency: req.currency,
+        invoice_date: req.invoice_date,
+        due_date: req.due_date,
+        memo: req.memo,
+        reference: req.reference,
+        purchase_order: req.purchase_order,
+        line_items: req
+            .line_items
+            .into_iter()
+            .map(map_line_item_to_domain)
+            .collect(),
+        coupon_codes: req.coupon_codes,
+        custom_fields: req.custom_fields,
+    }
+}
+
+fn map_line_item_to_domain(line: CreateInvoiceLineItem) -> domain::OneOffLineItemNew {
+    match line {
+        CreateInvoiceLineItem::Price(line) => domain::OneOffLineItemNew {
+            item: domain::OneOffItem::Price(line.price_id),
+            quantity: line.quantity,
+            description: line.description,
+            metadata: line.metadata,
+        },
+        CreateInvoiceLineItem::Custom(line) => domain::OneOffLineItemNew {
+            item: domain::OneOffItem::Custom {
+                name: line.name,
+                unit_price: line.unit_price,
+                product_id: line.product_id,
+            },
+            quantity: line.quantity,
+            description: line.description,
+            metadata: line.metadata,
+        },
+    }
+}
@@ -10,5 +10,11 @@ pub fn invoice_routes() -> OpenApiRouter<AppState> {
     OpenApiRouter::new()
         .routes(routes!(router::list_invoices))
         .routes(routes!(router::get_invoice_by_id))
+        .routes(routes!(router::create_invoice))
+        .routes(routes!(router::finalize_invoice))
+        .routes(routes!(router::void_invoice))
+        .routes(routes!(router::mark_invoice_as_uncollectible))
+        .routes(routes!(router::record_invoice_payment))
         .routes(routes!(router::download_invoice_pdf))
+        .routes(routes!(router::download_invoice_e_invoice))
 }
@@ -2,11 +2,13 @@ use crate::api_rest::currencies::model::Currency;
 use crate::api_rest::model::{PaginatedRequest, PaginationResponse};
 use chrono::{NaiveDate, NaiveDateTime};
 use common_domain::ids::{
-    AliasOr, CustomerId, CustomerPaymentMethodId, InvoiceId, PaymentTransactionId, SubscriptionId,
+    AliasOr, CustomerId, CustomerPaymentMethodId, InvoiceId, PaymentTransactionId, PriceId,
+    ProductId, SubscriptionId,
 };
 use common_domain::ids::{string_serde, string_serde_opt};
 use o2o::o2o;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
@@ -42,11 +44,18 @@ pub struct Invoice {
     pub net_terms: i32,
     pub reference: Option<String>,
     pub purchase_order: Option<String>,
+    /// Structured fields (cost center, project code...), from the customer and subscription defaults
+    #[serde(default)]
+    pub custom_fields: BTreeMap<String, String>,
     pub created_at: NaiveDateTime,
     pub updated_at: Option<NaiveDateTime>,
     pub finalized_at: Option<NaiveDateTime>,
     pub voided_at: Option<NaiveDateTime>,
     pub marked_as_uncollectible_at: Option<NaiveDateTime>,
+    /// Set once the draft was held for approval before finalization.
+    pub approval_status: Option<InvoiceApprovalStatus>,
+    /// Comment of the approver on the last approval decision.
+    pub approval_comment: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -65,6 +74,8 @@ pub struct InvoiceLineItem {
     #[schema(value_type = String, format = "decimal")]
     pub tax_rate: rust_decimal::Decimal,
     pub sub_line_items: Vec<SubLineItem>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
 }
 
 #[derive(o2o, Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -96,6 +107,21 @@ pub struct InvoiceListRequest {
     pub order_by: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EInvoiceFormat {
+    FacturX,
+    PeppolBis,
+}
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EInvoiceRequest {
+    /// E-invoice format: `FACTUR_X` for the Factur-X (CII) XML embedded in the invoice PDF, or `PEPPOL_BIS` for Peppol BIS Billing 3.0 (UBL).
+    #[param(inline)]
+    pub format: EInvoiceFormat,
+}
+
 #[derive(ToSchema, Serialize, Deserialize)]
 pub struct InvoiceListResponse {
     pub data: Vec<Invoice>,
@@ -171,6 +197,14 @@ pub enum PaymentMethodTypeEnum {
     Other,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InvoiceApprovalStatus {
+    Pending,
+    Approved,
+    Rejected,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum InvoicePaymentStatus {
@@ -209,6 +243,7 @@ pub enum InvoiceType {
     OneOff,
     Adjustment,
     UsageThreshold,
+    Imported,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -222,6 +257,84 @@ pub struct SubLineItem {
     pub unit_price: rust_decimal::Decimal,
 }
 
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct CreateInvoiceRequest {
+    #[schema(format = "CustomerId or customer alias")]
+    pub customer_id_or_alias: String,
+    /// Defaults to the customer currency
+    #[schema(nullable = false)]
+    pub currency: Option<String>,
+    /// Defaults to today
+    #[schema(nullable = false, examples("2025-11-01"))]
+    pub invoice_date: Option<NaiveDate>,
+    /// Defaults to the net terms of the invoicing entity
+    #[schema(nullable = false, examples("2025-11-30"))]
+    pub due_date: Option<NaiveDate>,
+    #[schema(nullable = false)]
+    pub memo: Option<String>,
+    #[schema(nullable = false)]
+    pub reference: Option<String>,
+    #[schema(nullable = false)]
+    pub purchase_order: Option<String>,
+    #[validate(length(min = 1))]
+    pub line_items: Vec<CreateInvoiceLineItem>,
+    #[serde(default)]
+    pub coupon_codes: Vec<String>,
+    /// Merged over the customer defaults
+    #[serde(default)]
+    pub custom_fields: Option<BTreeMap<String, String>>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Debug)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CreateInvoiceLineItem {
+    /// A catalog price, billed at the pricing that applies to the customer (usage prices are not supported)
+    Price(PriceInvoiceLineItem),
+    /// A free-form line
+    Custom(CustomInvoiceLineItem),
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Debug)]
+pub struct PriceInvoiceLineItem {
+    #[serde(with = "string_serde")]
+    pub price_id: PriceId,
+    #[schema(value_type = String, format = "decimal")]
+    pub quantity: rust_decimal::Decimal,
+    #[schema(nullable = false)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Debug)]
+pub struct CustomInvoiceLineItem {
+    pub name: String,
+    #[schema(value_type = String, format = "decimal")]
+    pub unit_price: rust_decimal::Decimal,
+    #[schema(value_type = String, format = "decimal")]
+    pub quantity: rust_decimal::Decimal,
+    /// Product whose tax settings apply to the line
+    #[serde(default, with = "string_serde_opt")]
+    pub product_id: Option<ProductId>,
+    #[schema(nullable = false)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct RecordInvoicePaymentRequest {
+    /// Amount received, in the invoice currency (e.g. "120.50")
+    #[schema(value_type = String, format = "decimal")]
+    pub amount: rust_decimal::Decimal,
+    /// Defaults to now
+    #[schema(nullable = false)]
+    pub payment_date: Option<NaiveDateTime>,
+    /// Bank transfer reference, check number...
+    #[schema(nullable = false)]
+    pub reference: Option<String>,
+}
+
 /// Unused dummy struct only used for an OpenAPI definition.
 #[derive(ToSchema)]
 #[schema(value_type = String, format = Binary)]
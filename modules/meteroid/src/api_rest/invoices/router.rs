@@ -1,22 +1,30 @@
 use crate::api_rest::AppState;
 use crate::api_rest::QueryParams;
 use crate::api_rest::error::{ErrorCode, RestErrorResponse};
-use crate::api_rest::invoices::mapping::domain_to_rest;
+use crate::api_rest::invoices::mapping::{create_request_to_domain, domain_to_rest};
 use crate::api_rest::invoices::model::{
-    BinaryFile, Invoice, InvoiceListRequest, InvoiceListResponse,
+    BinaryFile, CreateInvoiceRequest, EInvoiceFormat, EInvoiceRequest, Invoice, InvoiceListRequest,
+    InvoiceListResponse, RecordInvoicePaymentRequest,
 };
 use crate::api_rest::model::{PaginationExt, validate_order_by};
-use crate::errors::RestApiError;
+use crate::errors::{InvoicingRenderError, RestApiError};
+use crate::services::invoice_rendering::generate_ubl_invoice;
 use crate::services::storage::Prefix;
 use axum::extract::{Path, State};
 use axum::response::{IntoResponse, Response};
 use axum::{Extension, Json};
 use axum_valid::Valid;
-use common_domain::ids::InvoiceId;
+use common_domain::ids::{AliasOr, CustomerId, InvoiceId};
 use common_grpc::middleware::server::auth::AuthorizedAsTenant;
+use error_stack::Report;
 use hyper::StatusCode;
+use meteroid_invoicing::errors::InvoicingError;
+use meteroid_store::domain;
+use meteroid_store::domain::enums::InvoiceStatusEnum;
+use meteroid_store::repositories::CustomersInterface;
 use meteroid_store::repositories::InvoiceInterface;
 use meteroid_store::repositories::payment_transactions::PaymentTransactionInterface;
+use std::str::FromStr;
 
 /// List invoices
 ///
@@ -144,6 +152,267 @@ pub(crate) async fn get_invoice_by_id(
     Ok(Json(rest_model))
 }
 
+/// Create invoice
+///
+/// Create a draft one-off invoice for a customer, outside of any subscription. Lines either
+/// reference a catalog price or are free-form. The invoice stays in draft until finalized.
+#[utoipa::path(
+    post,
+    tag = "Invoices",
+    path = "/api/v1/invoices",
+    request_body(content = CreateInvoiceRequest, content_type = "application/json"),
+    responses(
+        (status = 201, description = "Draft invoice created", body = Invoice),
+        (status = 400, description = "Bad request", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Customer, price, product or coupon not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn create_invoice(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Valid(Json(payload)): Valid<Json<CreateInvoiceRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let id_or_alias: AliasOr<CustomerId> = AliasOr::from_str(payload.customer_id_or_alias.as_str())
+        .map_err(|_| RestApiError::InvalidInput("invalid customer_id format".to_string()))?;
+
+    let customer_id = match id_or_alias {
+        AliasOr::Id(id) => id,
+        AliasOr::Alias(alias) => {
+            app_state
+                .store
+                .find_customer_id_by_alias(alias, authorized_state.tenant_id)
+                .await
+                .map_err(RestApiError::from)?
+                .id
+        }
+    };
+
+    let created = app_state
+        .services
+        .create_oneoff_invoice(
+            authorized_state.tenant_id,
+            create_request_to_domain(payload, customer_id),
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error creating invoice: {}", e);
+            RestApiError::from(e)
+        })?;
+
+    let rest_model = domain_to_rest(created.invoice, vec![])?;
+
+    Ok((StatusCode::CREATED, Json(rest_model)))
+}
+
+/// Finalize invoice
+///
+/// Finalize a draft invoice: it gets its number, its documents are generated and it is sent to
+/// the customer. A draft that requires an approval is returned as a draft with a pending
+/// approval status instead, and is finalized once approved.
+#[utoipa::path(
+    post,
+    tag = "Invoices",
+    path = "/api/v1/invoices/{invoice_id}/finalize",
+    params(
+        ("invoice_id" = InvoiceId, Path, description = "Invoice ID", example = "inv_123"),
+    ),
+    responses(
+        (status = 200, description = "Invoice finalized, or held as a draft pending approval", body = Invoice),
+        (status = 400, description = "Invoice is not a draft", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Invoice not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn finalize_invoice(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    Path(invoice_id): Path<InvoiceId>,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let res = app_state
+        .services
+        .finalize_invoice(
+            authorized_state.as_actor(),
+            invoice_id,
+            authorized_state.tenant_id,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error finalizing invoice: {}", e);
+            RestApiError::from(e)
+        })?;
+
+    with_transactions(&app_state, res.invoice).await
+}
+
+/// Void invoice
+///
+/// Void a finalized invoice that is not paid. Its amount is no longer due.
+#[utoipa::path(
+    post,
+    tag = "Invoices",
+    path = "/api/v1/invoices/{invoice_id}/void",
+    params(
+        ("invoice_id" = InvoiceId, Path, description = "Invoice ID", example = "inv_123"),
+    ),
+    responses(
+        (status = 200, description = "Invoice voided", body = Invoice),
+        (status = 400, description = "Invoice cannot be voided", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Invoice not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn void_invoice(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    Path(invoice_id): Path<InvoiceId>,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let res = app_state
+        .store
+        .void_invoice(
+            authorized_state.as_actor(),
+            invoice_id,
+            authorized_state.tenant_id,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error voiding invoice: {}", e);
+            RestApiError::from(e)
+        })?;
+
+    with_transactions(&app_state, res.invoice).await
+}
+
+/// Mark invoice as uncollectible
+///
+/// Mark a finalized invoice as uncollectible, when payment is no longer expected.
+#[utoipa::path(
+    post,
+    tag = "Invoices",
+    path = "/api/v1/invoices/{invoice_id}/mark-uncollectible",
+    params(
+        ("invoice_id" = InvoiceId, Path, description = "Invoice ID", example = "inv_123"),
+    ),
+    responses(
+        (status = 200, description = "Invoice marked as uncollectible", body = Invoice),
+        (status = 400, description = "Invoice cannot be marked as uncollectible", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Invoice not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn mark_invoice_as_uncollectible(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    Path(invoice_id): Path<InvoiceId>,
+    State(app_state): State<AppState>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let res = app_state
+        .store
+        .mark_invoice_as_uncollectible(invoice_id, authorized_state.tenant_id)
+        .await
+        .map_err(|e| {
+            log::error!("Error marking invoice as uncollectible: {}", e);
+            RestApiError::from(e)
+        })?;
+
+    with_transactions(&app_state, res.invoice).await
+}
+
+/// Record invoice payment
+///
+/// Record a payment received outside of Meteroid (bank transfer, check...) against a finalized
+/// invoice. The invoice is marked as paid once fully settled.
+#[utoipa::path(
+    post,
+    tag = "Invoices",
+    path = "/api/v1/invoices/{invoice_id}/payments",
+    params(
+        ("invoice_id" = InvoiceId, Path, description = "Invoice ID", example = "inv_123"),
+    ),
+    request_body(content = RecordInvoicePaymentRequest, content_type = "application/json"),
+    responses(
+        (status = 200, description = "Payment recorded", body = Invoice),
+        (status = 400, description = "Invalid amount or invoice not payable", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Invoice not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn record_invoice_payment(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    Path(invoice_id): Path<InvoiceId>,
+    State(app_state): State<AppState>,
+    Valid(Json(payload)): Valid<Json<RecordInvoicePaymentRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    app_state
+        .services
+        .add_manual_payment_transaction(
+            authorized_state.as_actor(),
+            authorized_state.tenant_id,
+            invoice_id,
+            payload.amount,
+            payload
+                .payment_date
+                .unwrap_or_else(|| chrono::Utc::now().naive_utc()),
+            payload.reference,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error recording invoice payment: {}", e);
+            RestApiError::from(e)
+        })?;
+
+    let invoice = app_state
+        .store
+        .get_invoice_by_id(authorized_state.tenant_id, invoice_id)
+        .await
+        .map_err(RestApiError::from)?;
+
+    with_transactions(&app_state, invoice).await
+}
+
+async fn with_transactions(
+    app_state: &AppState,
+    invoice: domain::Invoice,
+) -> Result<Json<Invoice>, RestApiError> {
+    let transactions = app_state
+        .store
+        .list_payment_tx_by_invoice_id(invoice.tenant_id, invoice.id)
+        .await
+        .map_err(|e| {
+            log::error!("Error getting transactions for invoice: {}", e);
+            RestApiError::StoreError
+        })?
+        .into_iter()
+        .map(|tx| tx.transaction)
+        .collect::<Vec<_>>();
+
+    Ok(Json(domain_to_rest(invoice, transactions)?))
+}
+
 /// Download invoice PDF
 ///
 /// Download the PDF document for an invoice.
@@ -210,3 +479,98 @@ pub(crate) async fn download_invoice_pdf(
             .into_response()),
     }
 }
+
+/// Download invoice e-invoice
+///
+/// Download the structured e-invoice of a finalized invoice, as Factur-X (CII) or Peppol BIS
+/// Billing 3.0 (UBL) XML.
+#[utoipa::path(
+    get,
+    tag = "Invoices",
+    path = "/api/v1/invoices/{invoice_id}/e-invoice",
+    params(
+        ("invoice_id" = InvoiceId, Path, description = "Invoice ID", example = "inv_123"),
+        EInvoiceRequest,
+    ),
+    responses(
+        (status = 200, description = "Invoice XML", content_type = "application/xml", body = inline(BinaryFile)),
+        (status = 400, description = "Draft invoice, or invoice not representable as an e-invoice", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Invoice not found or XML not available", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn download_invoice_e_invoice(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    Path(invoice_id): Path<InvoiceId>,
+    QueryParams(request): QueryParams<EInvoiceRequest>,
+    State(app_state): State<AppState>,
+) -> Result<Response, RestApiError> {
+    let invoice = app_state
+        .store
+        .get_invoice_by_id(authorized_state.tenant_id, invoice_id)
+        .await
+        .map_err(|e| {
+            log::error!("Error getting invoice by id: {}", e);
+            RestApiError::StoreError
+        })?;
+
+    if invoice.status == InvoiceStatusEnum::Draft {
+        return Err(RestApiError::InvalidInput(
+            "Draft invoices have no e-invoice".to_string(),
+        ));
+    }
+
+    let data = match request.format {
+        EInvoiceFormat::FacturX => match invoice.xml_document_id {
+            Some(id) => app_state
+                .object_store
+                .retrieve(id, Prefix::InvoiceXml)
+                .await
+                .map_err(|e| {
+                    log::error!("Error getting invoice file by id: {}", e);
+                    RestApiError::ObjectStoreError
+                })?,
+            None => {
+                return Ok((
+                    StatusCode::NOT_FOUND,
+                    Json(RestErrorResponse {
+                        code: ErrorCode::NotFound,
+                        message: "No attached Factur-X XML. Generation may be pending".to_string(),
+                    }),
+                )
+                    .into_response());
+            }
+        },
+        EInvoiceFormat::PeppolBis => generate_ubl_invoice(&app_state.store, invoice)
+            .await
+            .map_err(e_invoice_error)?
+            .into(),
+    };
+
+    Ok((
+        StatusCode::OK,
+        [
+            ("Content-Type", "application/xml"),
+            ("Content-Disposition", "inline"),
+        ],
+        data,
+    )
+        .into_response())
+}
+
+pub(crate) fn e_invoice_error(error: Report<InvoicingRenderError>) -> RestApiError {
+    match error.downcast_ref::<InvoicingError>() {
+        Some(InvoicingError::XmlGenerationError(message)) => {
+            RestApiError::InvalidInput(message.clone())
+        }
+        _ => {
+            log::error!("Error generating e-invoice: {:?}", error);
+            RestApiError::StoreError
+        }
+    }
+}
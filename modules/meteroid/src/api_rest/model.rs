@@ -123,6 +123,8 @@ pub fn validate_order_by(
 #[map_owned(meteroid_store::domain::enums::BillingPeriodEnum)]
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum BillingPeriodEnum {
+    Daily,
+    Weekly,
     Monthly,
     Quarterly,
     Semiannual,
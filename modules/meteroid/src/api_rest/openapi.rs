@@ -273,6 +273,14 @@ impl Modify for WebhooksAddon {
                             mrr_cents: 0,
                             period: BillingPeriodEnum::Monthly,
                             status: SubscriptionStatusEnum::PendingActivation,
+                            trial_end_date: None,
+                            effective_date: None,
+                            cancellation_reason: None,
+                            previous_plan_version_id: None,
+                            previous_status: None,
+                            renewal_date: None,
+                            opt_out_deadline: None,
+                            previous_end_date: None,
                         },
                         timestamp: Default::default(),
                     };
@@ -291,6 +299,10 @@ impl Modify for WebhooksAddon {
                             tax_amount: 2000,
                             created_at: Default::default(),
                             consolidated_into_invoice_id: None,
+                            custom_fields: BTreeMap::from([(
+                                "cost_center".to_string(),
+                                "CC-42".to_string(),
+                            )]),
                         },
                         timestamp: Default::default(),
                     };
@@ -295,18 +295,24 @@ fn usage_pricing_to_rest(
         domain::price_components::UsagePricingModel::PerUnit { rate } => {
             UsagePricingModel::PerUnit(PerUnitPlanPricing { rate: *rate })
         }
-        domain::price_components::UsagePricingModel::Tiered { tiers, block_size } => {
-            UsagePricingModel::Tiered(TieredPlanPricing {
-                tiers: tiers.iter().map(tier_row_to_rest).collect(),
-                block_size: *block_size,
-            })
-        }
-        domain::price_components::UsagePricingModel::Volume { tiers, block_size } => {
-            UsagePricingModel::Volume(VolumePlanPricing {
-                tiers: tiers.iter().map(tier_row_to_rest).collect(),
-                block_size: *block_size,
-            })
-        }
+        domain::price_components::UsagePricingModel::Tiered {
+            tiers,
+            block_size,
+            tier_group,
+        } => UsagePricingModel::Tiered(TieredPlanPricing {
+            tiers: tiers.iter().map(tier_row_to_rest).collect(),
+            block_size: *block_size,
+            tier_group: tier_group.clone().map(Into::into),
+        }),
+        domain::price_components::UsagePricingModel::Volume {
+            tiers,
+            block_size,
+            tier_group,
+        } => UsagePricingModel::Volume(VolumePlanPricing {
+            tiers: tiers.iter().map(tier_row_to_rest).collect(),
+            block_size: *block_size,
+            tier_group: tier_group.clone().map(Into::into),
+        }),
         domain::price_components::UsagePricingModel::Package { block_size, rate } => {
             UsagePricingModel::Package(PackagePlanPricing {
                 block_size: *block_size,
@@ -400,6 +406,7 @@ fn fee_to_domain(
                         cadence: r.term.into(),
                         currency: currency.to_string(),
                         pricing: Pricing::Rate { rate: r.price },
+                        variants: vec![],
                     })
                 })
                 .collect();
@@ -423,6 +430,7 @@ fn fee_to_domain(
                             min_slots: f.minimum_count,
                             max_slots: f.quota,
                         },
+                        variants: vec![],
                     })
                 })
                 .collect();
@@ -454,6 +462,7 @@ fn fee_to_domain(
                             included: t.included_amount,
                             overage_rate: t.per_unit_overage,
                         },
+                        variants: vec![],
                     })
                 })
                 .collect();
@@ -466,12 +475,13 @@ fn fee_to_domain(
             ))
         }
         Fee::Usage(f) => {
-            let domain_model = rest_usage_pricing_to_domain(&f.pricing);
+            let domain_model = rest_usage_pricing_to_domain(&f.pricing)?;
             let usage_model = UsageModel::from(&domain_model);
             let prices = vec![PriceEntry::New(PriceInput {
                 cadence: f.cadence.into(),
                 currency: currency.to_string(),
                 pricing: Pricing::Usage(domain_model),
+                variants: vec![],
             })];
             Ok((
                 FeeTypeEnum::Usage,
@@ -490,6 +500,7 @@ fn fee_to_domain(
                     unit_price: f.unit_price,
                     quantity: f.quantity,
                 },
+                variants: vec![],
             })];
             Ok((
                 FeeTypeEnum::ExtraRecurring,
@@ -510,6 +521,7 @@ fn fee_to_domain(
                     unit_price: f.unit_price,
                     quantity: f.quantity,
                 },
+                variants: vec![],
             })];
             Ok((FeeTypeEnum::OneTime, FeeStructure::OneTime {}, prices))
         }
@@ -518,18 +530,20 @@ fn fee_to_domain(
 
 fn rest_usage_pricing_to_domain(
     model: &UsagePricingModel,
-) -> domain::price_components::UsagePricingModel {
+) -> Result<domain::price_components::UsagePricingModel, RestApiError> {
     use domain::price_components::UsagePricingModel as D;
 
-    match model {
+    Ok(match model {
         UsagePricingModel::PerUnit(p) => D::PerUnit { rate: p.rate },
         UsagePricingModel::Tiered(p) => D::Tiered {
             tiers: p.tiers.iter().map(tier_row_to_domain).collect(),
             block_size: p.block_size,
+            tier_group: p.tier_group.clone().map(TryInto::try_into).transpose()?,
         },
         UsagePricingModel::Volume(p) => D::Volume {
             tiers: p.tiers.iter().map(tier_row_to_domain).collect(),
             block_size: p.block_size,
+            tier_group: p.tier_group.clone().map(TryInto::try_into).transpose()?,
         },
         UsagePricingModel::Package(p) => D::Package {
             block_size: p.block_size,
@@ -538,7 +552,7 @@ fn rest_usage_pricing_to_domain(
         UsagePricingModel::Matrix(p) => D::Matrix {
             rates: p.rates.iter().map(matrix_row_to_domain).collect(),
         },
-    }
+    })
 }
 
 fn tier_row_to_domain(t: &TierRow) -> domain::price_components::TierRow {
@@ -669,3 +683,39 @@ fn extract_available_parameters(
         slot_components,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::rest_usage_pricing_to_domain;
+    use crate::api_rest::plans::model::UsagePricingModel;
+
+    fn tiered_with_group(group: serde_json::Value) -> UsagePricingModel {
+        serde_json::from_value(serde_json::json!({
+            "type": "TIERED",
+            "tiers": [{ "first_unit": 0, "rate": "1.00" }],
+            "tier_group": group,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn tier_group_is_validated() {
+        let blank_key = tiered_with_group(serde_json::json!({
+            "key": "  ",
+            "units_per_quantity": "1",
+        }));
+        assert!(rest_usage_pricing_to_domain(&blank_key).is_err());
+
+        let zero_units = tiered_with_group(serde_json::json!({
+            "key": "api-calls",
+            "units_per_quantity": "0",
+        }));
+        assert!(rest_usage_pricing_to_domain(&zero_units).is_err());
+
+        let valid = tiered_with_group(serde_json::json!({
+            "key": " api-calls ",
+            "units_per_quantity": "2.5",
+        }));
+        assert!(rest_usage_pricing_to_domain(&valid).is_ok());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::api_rest::entitlements::model::Entitlement;
 use crate::api_rest::model::PaginatedRequest;
 use crate::api_rest::model::PaginationResponse;
+use crate::errors::RestApiError;
 use chrono::NaiveDateTime;
 use common_domain::ids::{
     AddOnId, BillableMetricId, PlanId, PlanVersionId, PriceComponentId, PriceId, ProductFamilyId,
@@ -216,12 +217,14 @@ pub struct PerUnitPlanPricing {
 pub struct TieredPlanPricing {
     pub tiers: Vec<TierRow>,
     pub block_size: Option<u64>,
+    pub tier_group: Option<TierGroup>,
 }
 
 #[derive(Clone, ToSchema, serde::Serialize, serde::Deserialize, Debug)]
 pub struct VolumePlanPricing {
     pub tiers: Vec<TierRow>,
     pub block_size: Option<u64>,
+    pub tier_group: Option<TierGroup>,
 }
 
 #[derive(Clone, ToSchema, serde::Serialize, serde::Deserialize, Debug)]
@@ -259,6 +262,48 @@ pub struct TierRow {
     pub flat_cap: Option<Decimal>,
 }
 
+/// Pools usage with the other fees of the subscription sharing `key`: tiers are evaluated on
+/// the combined quantity and each fee is billed its share.
+#[derive(Clone, ToSchema, serde::Serialize, serde::Deserialize, Debug)]
+pub struct TierGroup {
+    pub key: String,
+    /// Pooled units contributed by one unit of the fee's metric.
+    #[schema(value_type = String, format = "decimal")]
+    pub units_per_quantity: Decimal,
+}
+
+impl From<meteroid_store::domain::price_components::TierGroup> for TierGroup {
+    fn from(group: meteroid_store::domain::price_components::TierGroup) -> Self {
+        Self {
+            key: group.key,
+            units_per_quantity: group.units_per_quantity,
+        }
+    }
+}
+
+/// Same rules as the gRPC mapping: a pooled fee needs a key and a positive conversion factor.
+impl TryFrom<TierGroup> for meteroid_store::domain::price_components::TierGroup {
+    type Error = RestApiError;
+
+    fn try_from(group: TierGroup) -> Result<Self, Self::Error> {
+        let key = group.key.trim();
+        if key.is_empty() {
+            return Err(RestApiError::InvalidInput(
+                "tier group key is required".to_string(),
+            ));
+        }
+        if group.units_per_quantity <= Decimal::ZERO {
+            return Err(RestApiError::InvalidInput(
+                "tier group units_per_quantity must be positive".to_string(),
+            ));
+        }
+        Ok(Self {
+            key: key.to_string(),
+            units_per_quantity: group.units_per_quantity,
+        })
+    }
+}
+
 #[derive(o2o, Clone, ToSchema, serde::Serialize, serde::Deserialize, Debug)]
 #[map_owned(meteroid_store::domain::price_components::MatrixRow)]
 pub struct MatrixRow {
@@ -3,15 +3,39 @@ use crate::api_rest::currencies;
 use crate::api_rest::entitlements::mapping::entitlement_to_rest;
 use crate::api_rest::subscriptions::model::{
     AppliedCoupon, AppliedCouponDetailed, Coupon, CreateSubscriptionAddOn,
-    CreateSubscriptionComponents, Subscription, SubscriptionAddOnCustomization,
-    SubscriptionCreateRequest, SubscriptionDetails, SubscriptionUpdateRequest,
+    CreateSubscriptionComponents, RenewalTerms, SetRenewalTermsRequest, Subscription,
+    SubscriptionAddOnCustomization, SubscriptionCreateRequest, SubscriptionDetails,
+    SubscriptionUpdateRequest,
 };
 use crate::errors::RestApiError;
 use common_domain::ids::{CouponId, CustomerId, PlanVersionId, SubscriptionId};
 use meteroid_store::domain;
 use meteroid_store::domain::{CreateSubscription, SubscriptionNew, SubscriptionPatch};
 
+fn renewal_terms_to_rest(s: &domain::Subscription) -> Option<RenewalTerms> {
+    s.end_date?;
+    Some(RenewalTerms {
+        policy: s.renewal_policy.into(),
+        term_months: s.renewal_term_months,
+        plan_version_id: s.renewal_plan_version_id,
+        notice_days: s.renewal_notice_days,
+        opt_out_days: s.renewal_opt_out_days,
+        opt_out_deadline: s.renewal_opt_out_deadline(),
+    })
+}
+
+pub fn rest_to_domain_renewal_terms(req: SetRenewalTermsRequest) -> domain::RenewalTerms {
+    domain::RenewalTerms {
+        policy: req.policy.into(),
+        term_months: req.term_months,
+        plan_version_id: req.plan_version_id,
+        notice_days: req.notice_days,
+        opt_out_days: req.opt_out_days,
+    }
+}
+
 pub fn domain_to_rest(s: domain::Subscription) -> Result<Subscription, RestApiError> {
+    let renewal = renewal_terms_to_rest(&s);
     Ok(Subscription {
         id: s.id,
         customer_id: s.customer_id,
@@ -41,6 +65,10 @@ pub fn domain_to_rest(s: domain::Subscription) -> Result<Subscription, RestApiEr
         auto_advance_invoices: s.auto_advance_invoices,
         charge_automatically: s.charge_automatically,
         payment_methods_config: s.payment_methods_config.map(Into::into),
+        renewal,
+        instance: s.instance.map(Into::into),
+        invoice_number_prefix: s.invoice_number_prefix,
+        invoice_custom_fields: s.invoice_custom_fields,
     })
 }
 
@@ -57,6 +85,8 @@ pub fn domain_to_rest_details(
         .map(domain_applied_coupon_to_rest)
         .collect::<Result<Vec<_>, _>>()?;
 
+    let renewal = renewal_terms_to_rest(&s.subscription);
+
     Ok(SubscriptionDetails {
         id: s.subscription.id,
         customer_id: s.subscription.customer_id,
@@ -85,6 +115,8 @@ pub fn domain_to_rest_details(
         auto_advance_invoices: s.subscription.auto_advance_invoices,
         charge_automatically: s.subscription.charge_automatically,
         payment_methods_config: s.subscription.payment_methods_config.map(Into::into),
+        renewal,
+        instance: s.subscription.instance.map(Into::into),
         components,
         add_ons,
         applied_coupons,
@@ -167,14 +199,18 @@ pub fn rest_to_domain_create_request(
             charge_automatically: sub.charge_automatically.unwrap_or(true),
             backdate_invoices: false,
             skip_checkout_session: false,
-            skip_past_invoices: sub.skip_past_invoices.unwrap_or(false),
+            past_period_billing: domain::PastPeriodBilling::resolve(
+                sub.past_period_billing.map(Into::into),
+                sub.skip_past_invoices.unwrap_or(false),
+            ),
+            instance: sub.instance.map(Into::into),
 
             payment_methods_config: sub.payment_methods_config.map(Into::into),
             invoice_threshold: None,
             billing_start_date: None,
         },
-        price_components: sub.price_components.map(Into::into),
-        add_ons: sub.add_ons.map(map_add_ons),
+        price_components: sub.price_components.map(TryInto::try_into).transpose()?,
+        add_ons: sub.add_ons.map(map_add_ons).transpose()?,
         coupons: resolved_coupon_ids.map(|ids| domain::CreateSubscriptionCoupons {
             coupons: ids
                 .into_iter()
@@ -187,9 +223,11 @@ pub fn rest_to_domain_create_request(
     Ok(converted)
 }
 
-impl From<CreateSubscriptionComponents> for domain::CreateSubscriptionComponents {
-    fn from(pc: CreateSubscriptionComponents) -> Self {
-        Self {
+impl TryFrom<CreateSubscriptionComponents> for domain::CreateSubscriptionComponents {
+    type Error = RestApiError;
+
+    fn try_from(pc: CreateSubscriptionComponents) -> Result<Self, Self::Error> {
+        Ok(Self {
             parameterized_components: pc
                 .parameterized_components
                 .unwrap_or_default()
@@ -200,22 +238,24 @@ impl From<CreateSubscriptionComponents> for domain::CreateSubscriptionComponents
                 .overridden_components
                 .unwrap_or_default()
                 .into_iter()
-                .map(Into::into)
-                .collect(),
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
             extra_components: pc
                 .extra_components
                 .unwrap_or_default()
                 .into_iter()
-                .map(Into::into)
-                .collect(),
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
             remove_components: pc.remove_components.unwrap_or_default(),
-        }
+        })
     }
 }
 
-impl From<CreateSubscriptionAddOn> for domain::CreateSubscriptionAddOn {
-    fn from(add_on: CreateSubscriptionAddOn) -> Self {
-        Self {
+impl TryFrom<CreateSubscriptionAddOn> for domain::CreateSubscriptionAddOn {
+    type Error = RestApiError;
+
+    fn try_from(add_on: CreateSubscriptionAddOn) -> Result<Self, Self::Error> {
+        Ok(Self {
             add_on_id: add_on.add_on_id,
             customization: match add_on.customization {
                 None => domain::SubscriptionAddOnCustomization::None,
@@ -225,7 +265,7 @@ impl From<CreateSubscriptionAddOn> for domain::CreateSubscriptionAddOn {
                 Some(SubscriptionAddOnCustomization::PriceOverride(o)) => {
                     domain::SubscriptionAddOnCustomization::PriceOverride {
                         name: o.name,
-                        price_entry: o.price_entry.into(),
+                        price_entry: o.price_entry.try_into()?,
                     }
                 }
             },
@@ -234,14 +274,19 @@ impl From<CreateSubscriptionAddOn> for domain::CreateSubscriptionAddOn {
             } else {
                 add_on.quantity.min(i32::MAX as u32) as i32
             },
-        }
+        })
     }
 }
 
-pub fn map_add_ons(add_ons: Vec<CreateSubscriptionAddOn>) -> domain::CreateSubscriptionAddOns {
-    domain::CreateSubscriptionAddOns {
-        add_ons: add_ons.into_iter().map(Into::into).collect(),
-    }
+pub fn map_add_ons(
+    add_ons: Vec<CreateSubscriptionAddOn>,
+) -> Result<domain::CreateSubscriptionAddOns, RestApiError> {
+    Ok(domain::CreateSubscriptionAddOns {
+        add_ons: add_ons
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect::<Result<_, _>>()?,
+    })
 }
 
 pub fn rest_to_domain_update_request(
@@ -259,6 +304,10 @@ pub fn rest_to_domain_update_request(
         purchase_order: req
             .purchase_order
             .map(|p| if p.is_empty() { None } else { Some(p) }),
+        invoice_number_prefix: req
+            .invoice_number_prefix
+            .map(|p| if p.is_empty() { None } else { Some(p) }),
+        invoice_custom_fields: req.invoice_custom_fields,
         payment_methods_config: req.payment_methods_config.map(|c| Some(c.into())),
     }
 }
@@ -12,5 +12,15 @@ pub fn subscription_routes() -> OpenApiRouter<AppState> {
         .routes(routes!(router::list_subscriptions))
         .routes(routes!(router::subscription_details))
         .routes(routes!(router::cancel_subscription))
+        .routes(routes!(router::pause_subscription))
+        .routes(routes!(router::resume_subscription))
+        .routes(routes!(router::reactivate_subscription))
+        .routes(routes!(router::extend_trial))
+        .routes(routes!(router::end_trial))
+        .routes(routes!(router::change_trialing_plan))
+        .routes(routes!(router::set_renewal_terms))
+        .routes(routes!(router::opt_out_of_renewal))
+        .routes(routes!(router::preview_billing_anchor_change))
+        .routes(routes!(router::change_billing_anchor))
         .routes(routes!(router::update_subscription))
 }
@@ -1,15 +1,17 @@
 use crate::api_rest::currencies::model::Currency;
 use crate::api_rest::entitlements::model::Entitlement;
 use crate::api_rest::model::{BillingPeriodEnum, PaginatedRequest, PaginationResponse};
+use crate::errors::RestApiError;
 use chrono::NaiveDate;
 use common_domain::ids::{
     AddOnId, AliasOr, AppliedCouponId, BankAccountId, BillableMetricId, CouponId, CustomerId,
-    PlanVersionId, PriceComponentId, PriceId, ProductId, SubscriptionAddOnId,
+    InvoiceId, PlanVersionId, PriceComponentId, PriceId, ProductId, SubscriptionAddOnId,
 };
 use common_domain::ids::{PlanId, string_serde_opt, string_serde_vec_opt};
 use common_domain::ids::{SubscriptionId, string_serde};
 use o2o::o2o;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
 
@@ -189,6 +191,14 @@ pub struct Subscription {
     pub charge_automatically: bool,
     /// Payment methods configuration (Online, BankTransfer, or External)
     pub payment_methods_config: Option<PaymentMethodsConfig>,
+    /// Renewal terms, for subscriptions with an end date
+    pub renewal: Option<RenewalTerms>,
+    /// Instance of the plan, when the customer holds several subscriptions to it
+    pub instance: Option<SubscriptionInstance>,
+    /// Substituted to {prefix} in invoice numbers, with its own numbering sequence
+    pub invoice_number_prefix: Option<String>,
+    /// Custom fields set on the invoices of this subscription, over the customer ones
+    pub invoice_custom_fields: BTreeMap<String, String>,
 }
 
 /// Coupon as embedded in subscription details — a subset of the `Coupon` resource
@@ -273,6 +283,10 @@ pub struct SubscriptionDetails {
     pub auto_advance_invoices: bool,
     pub charge_automatically: bool,
     pub payment_methods_config: Option<PaymentMethodsConfig>,
+    /// Renewal terms, for subscriptions with an end date
+    pub renewal: Option<RenewalTerms>,
+    /// Instance of the plan, when the customer holds several subscriptions to it
+    pub instance: Option<SubscriptionInstance>,
     pub components: Vec<SubscriptionComponent>,
     pub add_ons: Vec<SubscriptionAddOn>,
     pub applied_coupons: Vec<AppliedCouponDetailed>,
@@ -322,8 +336,40 @@ pub struct SubscriptionCreateRequest {
     pub payment_methods_config: Option<PaymentMethodsConfig>,
     /// Migration mode: when true with a past start_date, skip creating invoices for past cycles.
     /// The subscription will be set to the current billing period with correct cycle_index.
+    /// Superseded by `past_period_billing`.
     #[schema(nullable = false)]
     pub skip_past_invoices: Option<bool>,
+    /// How the periods before a past start_date are handled. Defaults to INVOICE.
+    /// IMPORT_AS_PAID records them as already-paid imported invoices, SKIP ignores them.
+    /// Both set the subscription to the current billing period.
+    #[schema(nullable = false)]
+    pub past_period_billing: Option<PastPeriodBillingEnum>,
+    /// Subscribes as one of several independent instances of the plan for this customer.
+    #[schema(nullable = false)]
+    pub instance: Option<SubscriptionInstance>,
+}
+
+/// One of several independent subscriptions of a customer to the same plan (e.g. one per project).
+/// Usage components only bill the events whose `usage_property` matches the instance `id`.
+#[derive(o2o, Clone, ToSchema, Serialize, Deserialize, Debug)]
+#[map_owned(meteroid_store::domain::SubscriptionInstance)]
+pub struct SubscriptionInstance {
+    /// Unique among the customer's active subscriptions
+    pub id: String,
+    /// Event property carrying the instance identifier. Defaults to `instance_id`.
+    #[schema(nullable = false)]
+    pub usage_property: Option<String>,
+    #[serde(default)]
+    pub metadata: BTreeMap<String, String>,
+}
+
+#[derive(o2o, Clone, ToSchema, Serialize, Deserialize, Debug)]
+#[map_owned(meteroid_store::domain::PastPeriodBilling)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PastPeriodBillingEnum {
+    Invoice,
+    ImportAsPaid,
+    Skip,
 }
 
 #[derive(o2o, Clone, ToSchema, Serialize, Deserialize, Debug)]
@@ -340,6 +386,8 @@ pub enum SubscriptionActivationConditionEnum {
 #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum SubscriptionFeeBillingPeriodEnum {
     OneTime,
+    Daily,
+    Weekly,
     Monthly,
     Quarterly,
     Semiannual,
@@ -364,6 +412,37 @@ pub enum SubscriptionStatusEnum {
     Errored,
 }
 
+#[derive(o2o, Clone, Copy, ToSchema, Serialize, Deserialize, Debug)]
+#[map_owned(meteroid_store::domain::enums::RenewalPolicyEnum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+#[schema(title = "RenewalPolicy")]
+pub enum RenewalPolicyEnum {
+    /// The subscription completes at the end of its term
+    Expire,
+    /// A new term of the same length starts
+    RenewSameTerm,
+    /// The subscription keeps renewing one month at a time
+    RenewMonthToMonth,
+    /// A new term starts on another plan version
+    RenewToPlanVersion,
+}
+
+#[derive(Clone, ToSchema, Serialize, Deserialize, Debug)]
+pub struct RenewalTerms {
+    pub policy: RenewalPolicyEnum,
+    /// Length of each renewed term, in months
+    pub term_months: Option<u32>,
+    /// Plan version the subscription renews onto
+    #[serde(default, with = "string_serde_opt")]
+    pub plan_version_id: Option<PlanVersionId>,
+    /// Days before the end of the term the renewal notice is sent
+    pub notice_days: Option<u32>,
+    /// Days before the end of the term renewal can still be opted out of
+    pub opt_out_days: Option<u32>,
+    /// Last day renewal can be opted out of
+    pub opt_out_deadline: Option<NaiveDate>,
+}
+
 #[derive(ToSchema, Serialize, Deserialize, Clone, Debug)]
 pub struct CreateSubscriptionComponents {
     pub parameterized_components: Option<Vec<ComponentParameterization>>,
@@ -488,10 +567,12 @@ impl From<meteroid_store::domain::subscription_components::SubscriptionFee> for
     }
 }
 
-impl From<SubscriptionFee> for meteroid_store::domain::subscription_components::SubscriptionFee {
-    fn from(fee: SubscriptionFee) -> Self {
+impl TryFrom<SubscriptionFee> for meteroid_store::domain::subscription_components::SubscriptionFee {
+    type Error = RestApiError;
+
+    fn try_from(fee: SubscriptionFee) -> Result<Self, Self::Error> {
         use meteroid_store::domain::subscription_components::SubscriptionFee as DomainFee;
-        match fee {
+        Ok(match fee {
             SubscriptionFee::Rate(f) => DomainFee::Rate { rate: f.rate },
             SubscriptionFee::OneTime(f) => DomainFee::OneTime {
                 rate: f.rate,
@@ -517,15 +598,15 @@ impl From<SubscriptionFee> for meteroid_store::domain::subscription_components::
             },
             SubscriptionFee::Usage(f) => DomainFee::Usage {
                 metric_id: f.metric_id,
-                model: f.model.into(),
+                model: f.model.try_into()?,
             },
-        }
+        })
     }
 }
 
 // Pricing row types are shared with the plans API — re-exported so both surfaces emit a single
 // `TierRow` / `MatrixRow` OpenAPI schema instead of colliding definitions.
-pub use crate::api_rest::plans::model::{MatrixRow, TierRow};
+pub use crate::api_rest::plans::model::{MatrixRow, TierGroup, TierRow};
 
 #[derive(o2o, Clone, ToSchema, Serialize, Deserialize, Debug)]
 #[map_owned(meteroid_store::domain::enums::BillingType)]
@@ -545,12 +626,14 @@ pub struct PerUnitPricing {
 pub struct TieredPricing {
     pub tiers: Vec<TierRow>,
     pub block_size: Option<u64>,
+    pub tier_group: Option<TierGroup>,
 }
 
 #[derive(ToSchema, Serialize, Deserialize, Clone, Debug)]
 pub struct VolumePricing {
     pub tiers: Vec<TierRow>,
     pub block_size: Option<u64>,
+    pub tier_group: Option<TierGroup>,
 }
 
 #[derive(ToSchema, Serialize, Deserialize, Clone, Debug)]
@@ -585,13 +668,23 @@ impl From<meteroid_store::domain::price_components::UsagePricingModel> for Usage
         use meteroid_store::domain::price_components::UsagePricingModel as DomainModel;
         match model {
             DomainModel::PerUnit { rate } => UsagePricingModel::PerUnit(PerUnitPricing { rate }),
-            DomainModel::Tiered { tiers, block_size } => UsagePricingModel::Tiered(TieredPricing {
+            DomainModel::Tiered {
+                tiers,
+                block_size,
+                tier_group,
+            } => UsagePricingModel::Tiered(TieredPricing {
                 tiers: tiers.into_iter().map(|t| t.into()).collect(),
                 block_size,
+                tier_group: tier_group.map(|g| g.into()),
             }),
-            DomainModel::Volume { tiers, block_size } => UsagePricingModel::Volume(VolumePricing {
+            DomainModel::Volume {
+                tiers,
+                block_size,
+                tier_group,
+            } => UsagePricingModel::Volume(VolumePricing {
                 tiers: tiers.into_iter().map(|t| t.into()).collect(),
                 block_size,
+                tier_group: tier_group.map(|g| g.into()),
             }),
             DomainModel::Package { block_size, rate } => {
                 UsagePricingModel::Package(PackagePricing { block_size, rate })
@@ -603,18 +696,22 @@ impl From<meteroid_store::domain::price_components::UsagePricingModel> for Usage
     }
 }
 
-impl From<UsagePricingModel> for meteroid_store::domain::price_components::UsagePricingModel {
-    fn from(model: UsagePricingModel) -> Self {
+impl TryFrom<UsagePricingModel> for meteroid_store::domain::price_components::UsagePricingModel {
+    type Error = RestApiError;
+
+    fn try_from(model: UsagePricingModel) -> Result<Self, Self::Error> {
         use meteroid_store::domain::price_components::UsagePricingModel as DomainModel;
-        match model {
+        Ok(match model {
             UsagePricingModel::PerUnit(p) => DomainModel::PerUnit { rate: p.rate },
             UsagePricingModel::Tiered(p) => DomainModel::Tiered {
                 tiers: p.tiers.into_iter().map(|t| t.into()).collect(),
                 block_size: p.block_size,
+                tier_group: p.tier_group.map(TryInto::try_into).transpose()?,
             },
             UsagePricingModel::Volume(p) => DomainModel::Volume {
                 tiers: p.tiers.into_iter().map(|t| t.into()).collect(),
                 block_size: p.block_size,
+                tier_group: p.tier_group.map(TryInto::try_into).transpose()?,
             },
             UsagePricingModel::Package(p) => DomainModel::Package {
                 block_size: p.block_size,
@@ -623,18 +720,12 @@ impl From<UsagePricingModel> for meteroid_store::domain::price_components::Usage
             UsagePricingModel::Matrix(p) => DomainModel::Matrix {
                 rates: p.rates.into_iter().map(|r| r.into()).collect(),
             },
-        }
+        })
     }
 }
 
 #[derive(o2o, ToSchema, Serialize, Deserialize, Clone, Debug)]
-#[owned_into(meteroid_store::domain::subscription_components::SubscriptionComponentNewInternal)]
 #[from_owned(meteroid_store::domain::subscription_components::SubscriptionComponent)]
-#[ghosts(
-    is_override: {@.price_component_id.is_some()},
-    price_id: {None},
-    effective_from: {chrono::Utc::now().naive_utc().date()},
-)]
 pub struct SubscriptionComponent {
     #[serde(default, with = "string_serde_opt")]
     pub price_component_id: Option<PriceComponentId>,
@@ -772,32 +863,39 @@ pub struct NewProductRef {
 
 // ── PriceEntry / ProductRef From conversions ───────────────────
 
-impl From<PriceEntry> for meteroid_store::domain::price_components::PriceEntry {
-    fn from(val: PriceEntry) -> Self {
-        match val {
+impl TryFrom<PriceEntry> for meteroid_store::domain::price_components::PriceEntry {
+    type Error = RestApiError;
+
+    fn try_from(val: PriceEntry) -> Result<Self, Self::Error> {
+        Ok(match val {
             PriceEntry::Existing(e) => {
                 meteroid_store::domain::price_components::PriceEntry::Existing(e.id)
             }
             PriceEntry::New(input) => {
-                meteroid_store::domain::price_components::PriceEntry::New(input.into())
+                meteroid_store::domain::price_components::PriceEntry::New(input.try_into()?)
             }
-        }
+        })
     }
 }
 
-impl From<PriceInput> for meteroid_store::domain::price_components::PriceInput {
-    fn from(val: PriceInput) -> Self {
-        Self {
+impl TryFrom<PriceInput> for meteroid_store::domain::price_components::PriceInput {
+    type Error = RestApiError;
+
+    fn try_from(val: PriceInput) -> Result<Self, Self::Error> {
+        Ok(Self {
             cadence: val.cadence.into(),
             currency: val.currency,
-            pricing: val.pricing.into(),
-        }
+            pricing: val.pricing.try_into()?,
+            variants: vec![],
+        })
     }
 }
 
-impl From<Pricing> for meteroid_store::domain::prices::Pricing {
-    fn from(val: Pricing) -> Self {
-        match val {
+impl TryFrom<Pricing> for meteroid_store::domain::prices::Pricing {
+    type Error = RestApiError;
+
+    fn try_from(val: Pricing) -> Result<Self, Self::Error> {
+        Ok(match val {
             Pricing::Rate(r) => meteroid_store::domain::prices::Pricing::Rate { rate: r.rate },
             Pricing::Slot(s) => meteroid_store::domain::prices::Pricing::Slot {
                 unit_rate: s.unit_rate,
@@ -809,7 +907,9 @@ impl From<Pricing> for meteroid_store::domain::prices::Pricing {
                 included: c.included,
                 overage_rate: c.overage_rate,
             },
-            Pricing::Usage(u) => meteroid_store::domain::prices::Pricing::Usage(u.model.into()),
+            Pricing::Usage(u) => {
+                meteroid_store::domain::prices::Pricing::Usage(u.model.try_into()?)
+            }
             Pricing::ExtraRecurring(e) => meteroid_store::domain::prices::Pricing::ExtraRecurring {
                 unit_price: e.unit_price,
                 quantity: e.quantity,
@@ -818,7 +918,7 @@ impl From<Pricing> for meteroid_store::domain::prices::Pricing {
                 unit_price: o.unit_price,
                 quantity: o.quantity,
             },
-        }
+        })
     }
 }
 
@@ -847,15 +947,17 @@ pub struct ComponentOverride {
     pub price_entry: PriceEntry,
 }
 
-impl From<ComponentOverride>
+impl TryFrom<ComponentOverride>
     for meteroid_store::domain::subscription_components::ComponentOverride
 {
-    fn from(val: ComponentOverride) -> Self {
-        Self {
+    type Error = RestApiError;
+
+    fn try_from(val: ComponentOverride) -> Result<Self, Self::Error> {
+        Ok(Self {
             component_id: val.component_id,
             name: val.name,
-            price_entry: val.price_entry.into(),
-        }
+            price_entry: val.price_entry.try_into()?,
+        })
     }
 }
 
@@ -866,13 +968,15 @@ pub struct ExtraComponent {
     pub price_entry: PriceEntry,
 }
 
-impl From<ExtraComponent> for meteroid_store::domain::subscription_components::ExtraComponent {
-    fn from(val: ExtraComponent) -> Self {
-        Self {
+impl TryFrom<ExtraComponent> for meteroid_store::domain::subscription_components::ExtraComponent {
+    type Error = RestApiError;
+
+    fn try_from(val: ExtraComponent) -> Result<Self, Self::Error> {
+        Ok(Self {
             name: val.name,
             product_ref: val.product_ref.into(),
-            price_entry: val.price_entry.into(),
-        }
+            price_entry: val.price_entry.try_into()?,
+        })
     }
 }
 
@@ -948,6 +1052,149 @@ pub struct CancelSubscriptionResponse {
     pub subscription: Subscription,
 }
 
+#[derive(o2o, Clone, Copy, ToSchema, Serialize, Deserialize, Debug, Default)]
+#[map_owned(meteroid_store::domain::PauseBilling)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PauseBillingEnum {
+    /// Billing cycles stop until the subscription is resumed.
+    #[default]
+    NoBilling,
+    /// Cycles continue and fixed fees keep being billed.
+    KeepFixedFees,
+}
+
+#[derive(o2o, Clone, Copy, ToSchema, Serialize, Deserialize, Debug, Default)]
+#[map_owned(meteroid_store::domain::ResumeBillingAnchor)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ResumeBillingAnchorEnum {
+    /// Keep the billing day; the first period after resumption is prorated.
+    #[default]
+    Keep,
+    /// Re-anchor billing on the resume date.
+    ResetToResumeDate,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct PauseSubscriptionRequest {
+    /// If not provided, the pause will be effective at the end of the current billing period.
+    pub effective_date: Option<NaiveDate>,
+    #[serde(default)]
+    pub billing: PauseBillingEnum,
+    /// Automatically resume the subscription on that date.
+    pub resume_on: Option<NaiveDate>,
+    /// Billing anchor applied by the automatic resumption.
+    #[serde(default)]
+    pub resume_anchor: ResumeBillingAnchorEnum,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct PauseSubscriptionResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct ResumeSubscriptionRequest {
+    /// If not provided, the subscription resumes today. A future date schedules the resumption.
+    pub resume_on: Option<NaiveDate>,
+    #[serde(default)]
+    pub anchor: ResumeBillingAnchorEnum,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ResumeSubscriptionResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ReactivateSubscriptionResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct ExtendTrialRequest {
+    /// New end of the trial, after the current one
+    pub trial_end: NaiveDate,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ExtendTrialResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct EndTrialResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct ChangeTrialingPlanRequest {
+    /// Plan in effect during the trial. If not provided, the trialing plan of the plan version applies.
+    #[serde(default, with = "string_serde_opt")]
+    pub trialing_plan_id: Option<PlanId>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct ChangeTrialingPlanResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct SetRenewalTermsRequest {
+    pub policy: RenewalPolicyEnum,
+    /// Length of each renewed term, in months. Defaults to the length of the current term.
+    #[validate(range(min = 1, max = 120))]
+    pub term_months: Option<u32>,
+    /// Plan version to renew onto. Required for `RENEW_TO_PLAN_VERSION`.
+    #[serde(default, with = "string_serde_opt")]
+    pub plan_version_id: Option<PlanVersionId>,
+    /// Days before the end of the term the renewal notice is sent
+    #[validate(range(max = 365))]
+    pub notice_days: Option<u32>,
+    /// Days before the end of the term renewal can still be opted out of
+    #[validate(range(max = 365))]
+    pub opt_out_days: Option<u32>,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct SetRenewalTermsResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct OptOutOfRenewalResponse {
+    pub subscription: Subscription,
+}
+
+#[derive(ToSchema, Serialize, Deserialize, Validate, Debug)]
+pub struct BillingAnchorChangeRequest {
+    /// Day of the month the subscription renews on
+    #[validate(range(min = 1, max = 31))]
+    pub billing_day_anchor: u16,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct BillingAnchorChangePreview {
+    pub current_period_start: NaiveDate,
+    pub current_period_end: NaiveDate,
+    /// End of the stub period, on the first occurrence of the new billing day
+    pub new_period_end: NaiveDate,
+    /// Credit for the days cut off from the current period, in cents
+    pub credits_total: i64,
+    /// Charge for the days added to the current period, in cents
+    pub charges_total: i64,
+    pub net_amount: i64,
+    pub days_remaining: u32,
+    pub days_in_period: u32,
+}
+
+#[derive(ToSchema, Serialize, Deserialize)]
+pub struct BillingAnchorChangeResponse {
+    pub subscription: Subscription,
+    /// Invoice settling the stub period, if any amount is due or credited
+    #[serde(default, with = "string_serde_opt")]
+    pub adjustment_invoice_id: Option<InvoiceId>,
+}
+
 #[derive(ToSchema, Serialize, Deserialize)]
 pub struct SubscriptionListResponse {
     pub data: Vec<Subscription>,
@@ -968,6 +1215,10 @@ pub struct SubscriptionUpdateRequest {
     pub purchase_order: Option<String>,
     /// Payment methods configuration (Online, BankTransfer, or External)
     pub payment_methods_config: Option<PaymentMethodsConfig>,
+    /// Substituted to {prefix} in invoice numbers, with its own numbering sequence. Empty to remove it.
+    pub invoice_number_prefix: Option<String>,
+    /// Custom fields set on the invoices of this subscription, over the customer ones. Replaces all fields when set.
+    pub invoice_custom_fields: Option<BTreeMap<String, String>>,
 }
 
 #[derive(ToSchema, Serialize, Deserialize)]
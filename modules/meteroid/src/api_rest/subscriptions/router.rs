@@ -9,7 +9,7 @@ use crate::api_rest::error::RestErrorResponse;
 use crate::api_rest::model::{PaginationExt, validate_order_by};
 use crate::api_rest::subscriptions::mapping::{
     domain_to_rest, domain_to_rest_details, rest_to_domain_create_request,
-    rest_to_domain_update_request,
+    rest_to_domain_renewal_terms, rest_to_domain_update_request,
 };
 use crate::api_rest::subscriptions::model::{
     Subscription, SubscriptionCreateRequest, SubscriptionDetails, SubscriptionListResponse,
@@ -24,7 +24,7 @@ use http::StatusCode;
 use itertools::Itertools;
 use meteroid_store::repositories::coupons::CouponInterface;
 use meteroid_store::repositories::subscriptions::{
-    CancellationEffectiveAt, SubscriptionInterfaceAuto,
+    CancellationEffectiveAt, PauseEffectiveAt, PauseParams, SubscriptionInterfaceAuto,
 };
 use meteroid_store::repositories::{CustomersInterface, PlansInterface, SubscriptionInterface};
 
@@ -309,6 +309,508 @@ pub(crate) async fn cancel_subscription(
     }))
 }
 
+/// Pause subscription
+///
+/// Pause an active subscription either immediately or at the end of the billing period,
+/// optionally resuming it automatically on a given date.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/pause",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::PauseSubscriptionRequest,
+    responses(
+        (status = 200, description = "Subscription paused", body = super::model::PauseSubscriptionResponse),
+        (status = 400, description = "Subscription cannot be paused", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn pause_subscription(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::PauseSubscriptionRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .pause_subscription(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+            request
+                .effective_date
+                .map(PauseEffectiveAt::Date)
+                .unwrap_or(PauseEffectiveAt::EndOfBillingPeriod),
+            PauseParams {
+                billing: request.billing.into(),
+                resume_on: request.resume_on,
+                resume_anchor: request.resume_anchor.into(),
+            },
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling pause_subscription: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::PauseSubscriptionResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Resume subscription
+///
+/// Resume a paused subscription, restarting its billing cycles either on the original
+/// billing anchor (prorated) or anchored on the resume date.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/resume",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::ResumeSubscriptionRequest,
+    responses(
+        (status = 200, description = "Subscription resumed", body = super::model::ResumeSubscriptionResponse),
+        (status = 400, description = "Subscription is not paused", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn resume_subscription(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::ResumeSubscriptionRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .resume_subscription(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+            request.resume_on,
+            request.anchor.into(),
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling resume_subscription: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::ResumeSubscriptionResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Reactivate subscription
+///
+/// Revert a scheduled cancellation, or restart a subscription cancelled within the last 30 days
+/// on its original billing anchor.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/reactivate",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    responses(
+        (status = 200, description = "Subscription reactivated", body = super::model::ReactivateSubscriptionResponse),
+        (status = 400, description = "Subscription cannot be reactivated", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn reactivate_subscription(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .reactivate_subscription(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling reactivate_subscription: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::ReactivateSubscriptionResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Extend trial
+///
+/// Push back the end of a running trial. For a free trial, the first billing date moves along.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/trial/extend",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::ExtendTrialRequest,
+    responses(
+        (status = 200, description = "Trial extended", body = super::model::ExtendTrialResponse),
+        (status = 400, description = "Trial cannot be extended", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn extend_trial(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::ExtendTrialRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .extend_trial(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+            request.trial_end,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling extend_trial: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::ExtendTrialResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// End trial
+///
+/// End a running trial today and convert the subscription. A free trial is billed right away.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/trial/end",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    responses(
+        (status = 200, description = "Trial ended", body = super::model::EndTrialResponse),
+        (status = 400, description = "Subscription is not trialing", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn end_trial(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .end_trial_now(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling end_trial: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::EndTrialResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Change trialing plan
+///
+/// Switch the plan whose features apply during a running trial.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/trial/plan",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::ChangeTrialingPlanRequest,
+    responses(
+        (status = 200, description = "Trialing plan changed", body = super::model::ChangeTrialingPlanResponse),
+        (status = 400, description = "Subscription is not trialing", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn change_trialing_plan(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::ChangeTrialingPlanRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .change_trialing_plan(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+            request.trialing_plan_id,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling change_trialing_plan: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::ChangeTrialingPlanResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Set renewal terms
+///
+/// Define what happens when a fixed-term subscription reaches its end date: expire, renew for
+/// the same term, renew month-to-month or renew onto another plan version.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/renewal",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::SetRenewalTermsRequest,
+    responses(
+        (status = 200, description = "Renewal terms updated", body = super::model::SetRenewalTermsResponse),
+        (status = 400, description = "Invalid renewal terms", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn set_renewal_terms(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::SetRenewalTermsRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .set_renewal_terms(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+            rest_to_domain_renewal_terms(request),
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling set_renewal_terms: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::SetRenewalTermsResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Opt out of renewal
+///
+/// Let the subscription expire at the end of its current term. Only possible until the opt-out deadline.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/renewal/opt-out",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    responses(
+        (status = 200, description = "Renewal opted out of", body = super::model::OptOutOfRenewalResponse),
+        (status = 400, description = "Opt-out deadline has passed", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn opt_out_of_renewal(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let subscription = app_state
+        .services
+        .opt_out_of_renewal(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling opt_out_of_renewal: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::OptOutOfRenewalResponse {
+        subscription: domain_to_rest(subscription)?,
+    }))
+}
+
+/// Preview billing day change
+///
+/// Preview moving the billing day of a subscription: the current period is shortened or
+/// extended up to the first occurrence of the new billing day, and prorated accordingly.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/billing-anchor/preview",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::BillingAnchorChangeRequest,
+    responses(
+        (status = 200, description = "Billing day change preview", body = super::model::BillingAnchorChangePreview),
+        (status = 400, description = "Billing day cannot be changed", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn preview_billing_anchor_change(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::BillingAnchorChangeRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let preview = app_state
+        .services
+        .preview_billing_anchor_change(
+            subscription_id,
+            authorized_state.tenant_id,
+            request.billing_day_anchor,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling preview_billing_anchor_change: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::BillingAnchorChangePreview {
+        current_period_start: preview.current_period_start,
+        current_period_end: preview.current_period_end,
+        new_period_end: preview.new_period_end,
+        credits_total: preview.proration.credits_total_cents,
+        charges_total: preview.proration.charges_total_cents,
+        net_amount: preview.proration.net_amount_cents,
+        days_remaining: preview.proration.days_remaining,
+        days_in_period: preview.proration.days_in_period,
+    }))
+}
+
+/// Change billing day
+///
+/// Move the billing day of an active subscription. The current period ends on the first
+/// occurrence of the new billing day, and the prorated difference is invoiced right away.
+#[utoipa::path(
+    post,
+    tag = "Subscriptions",
+    path = "/api/v1/subscriptions/{subscription_id}/billing-anchor",
+    params(
+        ("subscription_id" = SubscriptionId, Path, description = "Subscription ID", example = "sub_123"),
+    ),
+    request_body = super::model::BillingAnchorChangeRequest,
+    responses(
+        (status = 200, description = "Billing day changed", body = super::model::BillingAnchorChangeResponse),
+        (status = 400, description = "Billing day cannot be changed", body = RestErrorResponse),
+        (status = 401, description = "Unauthorized", body = RestErrorResponse),
+        (status = 404, description = "Subscription not found", body = RestErrorResponse),
+        (status = 500, description = "Internal error", body = RestErrorResponse),
+    ),
+    security(
+        ("bearer_auth" = [])
+    )
+)]
+#[axum::debug_handler]
+pub(crate) async fn change_billing_anchor(
+    Extension(authorized_state): Extension<AuthorizedAsTenant>,
+    State(app_state): State<AppState>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Valid(Json(request)): Valid<Json<super::model::BillingAnchorChangeRequest>>,
+) -> Result<impl IntoResponse, RestApiError> {
+    let result = app_state
+        .services
+        .change_billing_anchor(
+            authorized_state.as_actor(),
+            subscription_id,
+            authorized_state.tenant_id,
+            request.billing_day_anchor,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Error handling change_billing_anchor: {e}");
+            RestApiError::from(e)
+        })?;
+
+    let subscription = app_state
+        .store
+        .get_subscription(authorized_state.tenant_id, subscription_id)
+        .await
+        .map_err(|e| {
+            log::error!("Error handling change_billing_anchor: {e}");
+            RestApiError::from(e)
+        })?;
+
+    Ok(Json(super::model::BillingAnchorChangeResponse {
+        subscription: domain_to_rest(subscription)?,
+        adjustment_invoice_id: result.adjustment_invoice_id,
+    }))
+}
+
 /// Update subscription
 ///
 /// Update subscription settings like payment configuration, billing options, etc.
@@ -371,6 +371,10 @@ async fn attach_payment_method_from_snapshot(
         is_tax_exempt: None,
         custom_taxes: None,
         connected_account_id: None,
+        custom_attributes: None,
+        invoice_language: None,
+        invoice_custom_fields: None,
+        invoice_approval_required: None,
     };
     store
         .patch_customer(
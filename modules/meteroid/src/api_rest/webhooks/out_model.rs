@@ -12,8 +12,8 @@ use crate::api_rest::subscriptions::model::SubscriptionStatusEnum;
 use chrono::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, Utc};
 use common_domain::ids::{AddOnId, CouponId, PriceId};
 use common_domain::ids::{
-    BillableMetricId, CreditNoteId, CustomerId, EventId, InvoiceId, PlanId, ProductFamilyId,
-    ProductId, QuoteId, SubscriptionId, string_serde, string_serde_opt,
+    BillableMetricId, CreditNoteId, CustomerId, EventId, InvoiceId, PlanId, PlanVersionId,
+    ProductFamilyId, ProductId, QuoteId, SubscriptionId, string_serde, string_serde_opt,
 };
 use meteroid_store::domain::outbox_event::{
     AddOnEvent, BillableMetricEvent, CouponEvent, CreditNoteEvent, CustomerEvent, InvoiceEvent,
@@ -22,6 +22,7 @@ use meteroid_store::domain::outbox_event::{
 use o2o::o2o;
 use serde::{Serialize, Serializer};
 use serde_with::skip_serializing_none;
+use std::collections::BTreeMap;
 use strum::{Display, EnumIter, EnumString};
 use svix::api::MessageIn;
 
@@ -59,6 +60,9 @@ pub struct WebhookOutInvoiceEventData {
     /// Set on `invoice.consolidated`: the parent invoice this one was merged into.
     #[serde(serialize_with = "string_serde_opt::serialize")]
     pub consolidated_into_invoice_id: Option<InvoiceId>,
+    /// Custom fields of the invoice (cost center, project code...)
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom_fields: BTreeMap<String, String>,
 }
 
 #[skip_serializing_none]
@@ -92,6 +96,32 @@ pub struct WebhookOutSubscriptionEventData {
     pub period: BillingPeriodEnum,
     #[from(~.into())]
     pub status: SubscriptionStatusEnum,
+    /// End of the trial, on `subscription.trial_will_end` events
+    #[ghost({None})]
+    pub trial_end_date: Option<NaiveDate>,
+    /// Date the change applies, on `subscription.cancellation_scheduled`, `subscription.plan_changed`
+    /// and `subscription.amended` events
+    #[ghost({None})]
+    pub effective_date: Option<NaiveDate>,
+    /// Reason given for the cancellation, on `subscription.cancellation_scheduled` events
+    #[ghost({None})]
+    pub cancellation_reason: Option<String>,
+    /// Plan version before the change, on `subscription.plan_changed` events
+    #[serde(serialize_with = "string_serde_opt::serialize")]
+    #[ghost({None})]
+    pub previous_plan_version_id: Option<PlanVersionId>,
+    /// Status before the transition, on `subscription.status_changed` events
+    #[ghost({None})]
+    pub previous_status: Option<SubscriptionStatusEnum>,
+    /// Date the current term renews, on `subscription.renewal_upcoming` events
+    #[ghost({None})]
+    pub renewal_date: Option<NaiveDate>,
+    /// Last day renewal can be opted out of, on `subscription.renewal_upcoming` events
+    #[ghost({None})]
+    pub opt_out_deadline: Option<NaiveDate>,
+    /// End of the previous term, on `subscription.term_renewed` events
+    #[ghost({None})]
+    pub previous_end_date: Option<NaiveDate>,
 }
 
 #[skip_serializing_none]
@@ -585,6 +615,48 @@ pub enum WebhookOutEventTypeEnum {
     #[strum(serialize = "subscription.created")]
     #[serde(rename = "subscription.created")]
     SubscriptionCreated,
+    #[strum(serialize = "subscription.paused")]
+    #[serde(rename = "subscription.paused")]
+    SubscriptionPaused,
+    #[strum(serialize = "subscription.resumed")]
+    #[serde(rename = "subscription.resumed")]
+    SubscriptionResumed,
+    #[strum(serialize = "subscription.reactivated")]
+    #[serde(rename = "subscription.reactivated")]
+    SubscriptionReactivated,
+    #[strum(serialize = "subscription.trial_will_end")]
+    #[serde(rename = "subscription.trial_will_end")]
+    SubscriptionTrialWillEnd,
+    #[strum(serialize = "subscription.trial_ended")]
+    #[serde(rename = "subscription.trial_ended")]
+    SubscriptionTrialEnded,
+    #[strum(serialize = "subscription.cancellation_scheduled")]
+    #[serde(rename = "subscription.cancellation_scheduled")]
+    SubscriptionCancellationScheduled,
+    #[strum(serialize = "subscription.cancelled")]
+    #[serde(rename = "subscription.cancelled")]
+    SubscriptionCancelled,
+    #[strum(serialize = "subscription.plan_changed")]
+    #[serde(rename = "subscription.plan_changed")]
+    SubscriptionPlanChanged,
+    #[strum(serialize = "subscription.amended")]
+    #[serde(rename = "subscription.amended")]
+    SubscriptionAmended,
+    #[strum(serialize = "subscription.suspended")]
+    #[serde(rename = "subscription.suspended")]
+    SubscriptionSuspended,
+    #[strum(serialize = "subscription.status_changed")]
+    #[serde(rename = "subscription.status_changed")]
+    SubscriptionStatusChanged,
+    #[strum(serialize = "subscription.renewed")]
+    #[serde(rename = "subscription.renewed")]
+    SubscriptionRenewed,
+    #[strum(serialize = "subscription.renewal_upcoming")]
+    #[serde(rename = "subscription.renewal_upcoming")]
+    SubscriptionRenewalUpcoming,
+    #[strum(serialize = "subscription.term_renewed")]
+    #[serde(rename = "subscription.term_renewed")]
+    SubscriptionTermRenewed,
     #[strum(serialize = "invoice.created")]
     #[serde(rename = "invoice.created")]
     InvoiceCreated,
@@ -705,6 +777,40 @@ impl WebhookOutEventTypeEnum {
         match self {
             WebhookOutEventTypeEnum::CustomerCreated => WebhookOutEventGroupEnum::Customer,
             WebhookOutEventTypeEnum::SubscriptionCreated => WebhookOutEventGroupEnum::Subscription,
+            WebhookOutEventTypeEnum::SubscriptionPaused => WebhookOutEventGroupEnum::Subscription,
+            WebhookOutEventTypeEnum::SubscriptionResumed => WebhookOutEventGroupEnum::Subscription,
+            WebhookOutEventTypeEnum::SubscriptionReactivated => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionTrialWillEnd => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionTrialEnded => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionCancellationScheduled => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionCancelled => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionPlanChanged => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionAmended => WebhookOutEventGroupEnum::Subscription,
+            WebhookOutEventTypeEnum::SubscriptionSuspended => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionStatusChanged => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionRenewed => WebhookOutEventGroupEnum::Subscription,
+            WebhookOutEventTypeEnum::SubscriptionRenewalUpcoming => {
+                WebhookOutEventGroupEnum::Subscription
+            }
+            WebhookOutEventTypeEnum::SubscriptionTermRenewed => {
+                WebhookOutEventGroupEnum::Subscription
+            }
             WebhookOutEventTypeEnum::InvoiceCreated => WebhookOutEventGroupEnum::Invoice,
             WebhookOutEventTypeEnum::InvoiceFinalized => WebhookOutEventGroupEnum::Invoice,
             WebhookOutEventTypeEnum::InvoicePaid => WebhookOutEventGroupEnum::Invoice,
@@ -745,6 +851,46 @@ impl WebhookOutEventTypeEnum {
             WebhookOutEventTypeEnum::SubscriptionCreated => {
                 "A new subscription was created".to_string()
             }
+            WebhookOutEventTypeEnum::SubscriptionPaused => "A subscription was paused".to_string(),
+            WebhookOutEventTypeEnum::SubscriptionResumed => {
+                "A paused subscription was resumed".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionReactivated => {
+                "A cancelled subscription was reactivated".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionTrialWillEnd => {
+                "The trial of a subscription will end in a few days".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionTrialEnded => {
+                "The trial of a subscription ended".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionCancellationScheduled => {
+                "The cancellation of a subscription was scheduled".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionCancelled => {
+                "A subscription was cancelled".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionPlanChanged => {
+                "The plan of a subscription was changed".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionAmended => {
+                "The components or add-ons of a subscription were amended".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionSuspended => {
+                "A subscription was suspended for non-payment".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionStatusChanged => {
+                "The status of a subscription changed".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionRenewed => {
+                "A subscription was renewed for a new billing period".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionRenewalUpcoming => {
+                "The term of a subscription will renew in a few days".to_string()
+            }
+            WebhookOutEventTypeEnum::SubscriptionTermRenewed => {
+                "A fixed-term subscription was renewed for a new term".to_string()
+            }
             WebhookOutEventTypeEnum::InvoiceCreated => "A new invoice was created".to_string(),
             WebhookOutEventTypeEnum::InvoiceFinalized => "An invoice was finalized".to_string(),
             WebhookOutEventTypeEnum::InvoicePaid => "An invoice was paid".to_string(),
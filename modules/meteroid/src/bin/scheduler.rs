@@ -59,6 +59,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         object_store_service.clone(),
         store.clone(),
         config.public_url.clone(),
+        config.rest_api_external_url.clone(),
         config.jwt_secret.clone(),
     )?);
 
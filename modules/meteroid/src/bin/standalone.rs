@@ -126,6 +126,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         object_store_service.clone(),
         store_arc.clone(),
         config.public_url.clone(),
+        config.rest_api_external_url.clone(),
         config.jwt_secret.clone(),
     )?);
 
@@ -18,7 +18,7 @@ use meteroid_store::clients::usage::{
     EventSearchOptions, EventSearchResult, GroupedUsageData, UsageClient, UsageData,
     WindowedUsageData, WindowedUsagePoint,
 };
-use meteroid_store::domain::{BillableMetric, UsagePeriod};
+use meteroid_store::domain::{BillableMetric, UsageFilter, UsagePeriod};
 use meteroid_store::errors::StoreError;
 use meteroid_store::{StoreResult, domain};
 use rust_decimal::Decimal;
@@ -38,6 +38,18 @@ fn map_aggregation_type(agg: &domain::enums::BillingMetricAggregateEnum) -> i32
     }) as i32
 }
 
+/// An empty filter value matches the events without the property, as missing properties
+/// read as empty strings.
+fn build_property_filters(filter: Option<&UsageFilter>) -> Vec<Filter> {
+    filter
+        .map(|f| Filter {
+            property_name: f.property.clone(),
+            property_value: vec![f.value.clone()],
+        })
+        .into_iter()
+        .collect()
+}
+
 fn build_segmentation_filter(
     matrix: Option<domain::SegmentationMatrix>,
 ) -> Option<SegmentationFilter> {
@@ -121,6 +133,7 @@ impl UsageClient for MeteringUsageClient {
         customer_id: &CustomerId,
         metric: &BillableMetric,
         period: UsagePeriod,
+        filter: Option<&UsageFilter>,
     ) -> StoreResult<UsageData> {
         if period.start >= period.end {
             bail!(StoreError::InvalidArgument("invalid period".to_string()));
@@ -143,6 +156,7 @@ impl UsageClient for MeteringUsageClient {
             timezone: None,
             segmentation_filter: build_segmentation_filter(metric.segmentation_matrix.clone()),
             value_property: metric.aggregation_key.clone(),
+            property_filters: build_property_filters(filter),
         };
 
         let mut metering_client_mut = self.usage_grpc_client.clone();
@@ -210,6 +224,7 @@ impl UsageClient for MeteringUsageClient {
             timezone: None,
             segmentation_filter: build_segmentation_filter(metric.segmentation_matrix.clone()),
             value_property: metric.aggregation_key.clone(),
+            property_filters: vec![],
         };
 
         let mut client = self.usage_grpc_client.clone();
@@ -243,6 +258,7 @@ impl UsageClient for MeteringUsageClient {
         customer_id: &CustomerId,
         metric: &BillableMetric,
         period: UsagePeriod,
+        filter: Option<&UsageFilter>,
     ) -> StoreResult<WindowedUsageData> {
         if period.start >= period.end {
             bail!(StoreError::InvalidArgument("invalid period".to_string()));
@@ -265,6 +281,7 @@ impl UsageClient for MeteringUsageClient {
             timezone: None,
             segmentation_filter: build_segmentation_filter(metric.segmentation_matrix.clone()),
             value_property: metric.aggregation_key.clone(),
+            property_filters: build_property_filters(filter),
         };
 
         let mut metering_client_mut = self.usage_grpc_client.clone();
@@ -337,6 +354,7 @@ impl UsageClient for MeteringUsageClient {
             timezone: None,
             segmentation_filter: build_segmentation_filter(metric.segmentation_matrix.clone()),
             value_property: metric.aggregation_key.clone(),
+            property_filters: vec![],
         };
 
         let mut metering_client_mut = self.usage_grpc_client.clone();
@@ -71,6 +71,14 @@ pub enum WorkerError {
     CheckoutSessionCleanupError,
     #[error("Failed to enqueue VAT revalidations")]
     VatRevalidation,
+    #[error("Failed to apply due price changes")]
+    PriceChangeError,
+    #[error("Failed to process usage thresholds")]
+    UsageThresholdError,
+    #[error("Failed to process trial end notifications")]
+    TrialWillEndError,
+    #[error("Failed to process renewal notifications")]
+    RenewalUpcomingError,
 }
 
 #[derive(Debug, thiserror::Error)]
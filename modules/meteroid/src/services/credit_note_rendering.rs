@@ -15,6 +15,33 @@ use meteroid_store::repositories::invoicing_entities::{
 use std::io::Cursor;
 use std::sync::Arc;
 
+/// Generates the Peppol BIS Billing 3.0 (UBL) e-invoice of a credit note, on demand.
+pub async fn generate_ubl_credit_note(
+    store: &Store,
+    credit_note: CreditNote,
+) -> Result<String, Report<InvoicingRenderError>> {
+    let invoicing_entity = store
+        .get_invoicing_entity(credit_note.tenant_id, Some(credit_note.invoicing_entity_id))
+        .await
+        .change_context(InvoicingRenderError::StoreError)?;
+
+    let related_invoice = store
+        .get_invoice_by_id(credit_note.tenant_id, credit_note.invoice_id)
+        .await
+        .change_context(InvoicingRenderError::StoreError)?;
+
+    let mapped = mapper::map_credit_note_to_invoicing(
+        credit_note,
+        &invoicing_entity,
+        None,
+        None,
+        related_invoice.invoice_date,
+    )?;
+
+    meteroid_invoicing::xml_ubl::generate_ubl_credit_note(&mapped)
+        .change_context(InvoicingRenderError::RenderError)
+}
+
 #[derive(Clone)]
 pub struct CreditNotePreviewRenderingService {
     store: Arc<Store>,
@@ -275,9 +302,9 @@ mod mapper {
     use crate::errors::InvoicingRenderError;
     use error_stack::Report;
     use meteroid_invoicing::credit_note_model as invoicing_model;
-    use meteroid_store::constants::Countries;
     use meteroid_store::domain as store_model;
     use meteroid_store::domain::historical_rates::HistoricalRate;
+    use meteroid_store::utils::locale::{document_language, translation_overrides_for};
     use rust_decimal::Decimal;
     use rust_decimal::prelude::FromPrimitive;
 
@@ -408,8 +435,12 @@ mod mapper {
             })
             .collect();
 
-        let lang = Countries::resolve_country(&invoicing_entity.country.code)
-            .map_or_else(|| "en-US", |c| c.locale);
+        let lang = document_language(
+            credit_note.customer_details.invoice_language.as_deref(),
+            &invoicing_entity.country,
+        );
+        let translation_overrides =
+            translation_overrides_for(&invoicing_entity.invoice_translations, &lang);
 
         let tax_breakdown = credit_note
             .tax_breakdown
@@ -428,6 +459,10 @@ mod mapper {
                     name: t.name.clone(),
                     rate: t.tax_rate,
                     // Negate to match the credit note's negative subtotal/total rendering
+                    taxable_amount: rusty_money::Money::from_minor(
+                        -(t.taxable_amount as i64),
+                        currency,
+                    ),
                     amount: rusty_money::Money::from_minor(-(t.tax_amount as i64), currency),
                     exemption_type,
                 }
@@ -435,7 +470,8 @@ mod mapper {
             .collect();
 
         Ok(invoicing_model::CreditNote {
-            lang: lang.to_string(),
+            lang,
+            translation_overrides,
             customer,
             lines,
             metadata,
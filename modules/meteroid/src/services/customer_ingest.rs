@@ -48,6 +48,8 @@ pub struct NewCustomerCsv {
     pub tax_rates: CustomTaxRatesCsv,
     #[serde(default, with = "optional_bool")]
     pub is_tax_exempt: Option<bool>,
+    #[serde(default, with = "optional_csv_string")]
+    pub invoice_language: Option<CsvString>,
     #[serde(flatten, default)]
     pub billing_address: AddressCsv,
     #[serde(flatten, default)]
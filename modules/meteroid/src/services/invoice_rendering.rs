@@ -1,8 +1,11 @@
 use crate::errors::InvoicingRenderError;
 use crate::services::storage::{ObjectStoreService, Prefix};
+use bytes::Bytes;
 use common_domain::ids::{InvoiceId, InvoicingEntityId, StoredDocumentId, TenantId};
 use error_stack::{Report, ResultExt};
 use image::ImageFormat::Png;
+use meteroid_invoicing::errors::InvoicingError;
+use meteroid_invoicing::model as invoicing_model;
 use meteroid_invoicing::{pdf, svg};
 use meteroid_store::Store;
 use meteroid_store::domain::subscriptions::PaymentMethodsConfig;
@@ -17,6 +20,7 @@ use meteroid_store::repositories::invoicing_entities::{
     InvoicingEntityInterface, InvoicingEntityInterfaceAuto,
 };
 use meteroid_store::repositories::subscriptions::SubscriptionInterface;
+use meteroid_store::services::invoice_lines::presentation::present_invoice;
 use std::collections::HashMap;
 use std::io::Cursor;
 use std::sync::Arc;
@@ -63,6 +67,37 @@ async fn build_line_groups(
     Ok(map)
 }
 
+/// Applies the sub-line presentation of the price components to the invoice lines. Returns the
+/// link to the usage annex when some lines were merged.
+async fn present_lines(
+    store: &Arc<Store>,
+    invoice: &mut Invoice,
+    rest_api_url: &str,
+    jwt_secret: &secrecy::SecretString,
+) -> Result<Option<String>, Report<InvoicingRenderError>> {
+    let presented = present_invoice(store, invoice)
+        .await
+        .change_context(InvoicingRenderError::StoreError)?;
+
+    invoice.line_items = presented.lines;
+
+    if presented.annex.is_empty() {
+        return Ok(None);
+    }
+
+    let token = generate_portal_token(
+        jwt_secret,
+        invoice.tenant_id,
+        ResourceAccess::Invoice(invoice.id),
+    )
+    .change_context(InvoicingRenderError::StoreError)?;
+
+    Ok(Some(format!(
+        "{}/files/v1/invoice/usage/{}?token={}",
+        rest_api_url, invoice.id, token
+    )))
+}
+
 async fn resolve_payment_info(
     store: &Arc<Store>,
     invoice: &Invoice,
@@ -185,7 +220,7 @@ async fn resolve_payment_info(
 }
 
 async fn fetch_bank_details(
-    store: &Arc<Store>,
+    store: &Store,
     invoicing_entity: &InvoicingEntity,
     invoice: &Invoice,
 ) -> Result<(Option<HashMap<String, String>>, Option<String>), Report<InvoicingRenderError>> {
@@ -204,12 +239,53 @@ async fn fetch_bank_details(
     Ok((bank_details, None))
 }
 
+/// Generates the Peppol BIS Billing 3.0 (UBL) e-invoice of an invoice. Unlike the Factur-X XML,
+/// it is not stored but produced on demand, as only Peppol customers request it.
+pub async fn generate_ubl_invoice(
+    store: &Store,
+    invoice: Invoice,
+) -> Result<String, Report<InvoicingRenderError>> {
+    let invoicing_entity = store
+        .get_invoicing_entity(invoice.tenant_id, Some(invoice.seller_details.id))
+        .await
+        .change_context(InvoicingRenderError::StoreError)?;
+
+    // Peppol invoices are settled by credit transfer, so the bank account is always given
+    let (bank_details, _) = fetch_bank_details(store, &invoicing_entity, &invoice).await?;
+
+    let parent_invoice = match invoice.parent_invoice_id {
+        Some(parent_id) => Some(
+            store
+                .get_invoice_by_id(invoice.tenant_id, parent_id)
+                .await
+                .change_context(InvoicingRenderError::StoreError)?,
+        ),
+        None => None,
+    };
+
+    let mapped = mapper::map_invoice_to_invoicing(
+        invoice,
+        &invoicing_entity,
+        None,
+        None,
+        bank_details,
+        None,
+        None,
+        parent_invoice.as_ref(),
+        &HashMap::new(),
+    )?;
+
+    meteroid_invoicing::xml_ubl::generate_ubl_invoice(&mapped)
+        .change_context(InvoicingRenderError::RenderError)
+}
+
 #[derive(Clone)]
 pub struct InvoicePreviewRenderingService {
     store: Arc<Store>,
     generator: Arc<dyn svg::SvgGenerator>,
     storage: Arc<dyn ObjectStoreService>,
     public_url: String,
+    rest_api_url: String,
     jwt_secret: secrecy::SecretString,
 }
 
@@ -218,6 +294,7 @@ impl InvoicePreviewRenderingService {
         store: Arc<Store>,
         storage: Arc<dyn ObjectStoreService>,
         public_url: String,
+        rest_api_url: String,
         jwt_secret: secrecy::SecretString,
     ) -> Result<Self, Report<InvoicingRenderError>> {
         let generator = svg::TypstSvgGenerator::new()
@@ -229,13 +306,14 @@ impl InvoicePreviewRenderingService {
             generator: Arc::new(generator),
             storage,
             public_url,
+            rest_api_url,
             jwt_secret,
         })
     }
 
     pub async fn preview_invoice(
         &self,
-        invoice: Invoice,
+        mut invoice: Invoice,
         invoicing_entity: InvoicingEntity,
     ) -> Result<Vec<String>, Report<InvoicingRenderError>> {
         let organization_logo = match invoicing_entity.logo_attachment_id.as_ref() {
@@ -280,22 +358,35 @@ impl InvoicePreviewRenderingService {
 
         let line_groups = build_line_groups(&self.store, &invoice).await?;
 
-        let mapped = mapper::map_invoice_to_invoicing(
+        let usage_annex_url = present_lines(
+            &self.store,
+            &mut invoice,
+            &self.rest_api_url,
+            &self.jwt_secret,
+        )
+        .await?;
+
+        let mut mapped = mapper::map_invoice_to_invoicing(
             invoice,
             &invoicing_entity,
             organization_logo,
             rate,
             bank_details,
             payment_url,
+            usage_annex_url,
             parent_invoice.as_ref(),
             &line_groups,
         )?;
 
-        let svgs = self
-            .generator
-            .generate_svg(&mapped)
-            .await
-            .change_context(InvoicingRenderError::RenderError)?;
+        let svgs = match self.generator.generate_svg(&mapped).await {
+            Err(error) if mapped.template.is_some() => {
+                log::warn!("Custom template failed for the invoice preview: {error}");
+                mapped.template = None;
+                self.generator.generate_svg(&mapped).await
+            }
+            res => res,
+        }
+        .change_context(InvoicingRenderError::RenderError)?;
 
         Ok(svgs)
     }
@@ -339,6 +430,7 @@ pub struct PdfRenderingService {
     pdf: Arc<dyn pdf::PdfGenerator>,
     store: Arc<Store>,
     public_url: String,
+    rest_api_url: String,
     jwt_secret: secrecy::SecretString,
 }
 
@@ -347,6 +439,7 @@ impl PdfRenderingService {
         storage: Arc<dyn ObjectStoreService>,
         store: Arc<Store>,
         public_url: String,
+        rest_api_url: String,
         jwt_secret: secrecy::SecretString,
     ) -> Result<Self, Report<InvoicingRenderError>> {
         let pdf_generator = Arc::new(
@@ -360,6 +453,7 @@ impl PdfRenderingService {
             pdf: pdf_generator,
             store,
             public_url,
+            rest_api_url,
             jwt_secret,
         })
     }
@@ -421,7 +515,7 @@ impl PdfRenderingService {
 
     async fn generate_pdf_and_save(
         &self,
-        invoice: Invoice,
+        mut invoice: Invoice,
         invoicing_entities: &[InvoicingEntity],
     ) -> Result<StoredDocumentId, Report<InvoicingRenderError>> {
         let invoicing_entity = invoicing_entities
@@ -480,22 +574,39 @@ impl PdfRenderingService {
         // group line items under per-plan headings.
         let line_groups = build_line_groups(&self.store, &invoice).await?;
 
-        let mapped_invoice = mapper::map_invoice_to_invoicing(
+        // Usage lines are merged on the document only; the stored invoice keeps every line.
+        let usage_annex_url = present_lines(
+            &self.store,
+            &mut invoice,
+            &self.rest_api_url,
+            &self.jwt_secret,
+        )
+        .await?;
+
+        let mut mapped_invoice = mapper::map_invoice_to_invoicing(
             invoice,
             invoicing_entity,
             organization_logo,
             rate,
             bank_details,
             payment_url,
+            usage_annex_url,
             parent_invoice.as_ref(),
             &line_groups,
         )?;
 
-        let pdf = self
-            .pdf
-            .generate_pdf(&mapped_invoice)
-            .await
-            .change_context(InvoicingRenderError::PdfError)?;
+        // a custom template was validated on upload, but must not block invoicing if it no
+        // longer renders (e.g. after a change of the inputs)
+        let (pdf, xml) = match self.generate_documents(&mapped_invoice).await {
+            Err(error) if mapped_invoice.template.is_some() => {
+                log::warn!(
+                    "Custom template failed for invoice {invoice_id}, using the default one: {error:?}"
+                );
+                mapped_invoice.template = None;
+                self.generate_documents(&mapped_invoice).await?
+            }
+            res => res?,
+        };
 
         let pdf_id = self
             .storage
@@ -503,20 +614,54 @@ impl PdfRenderingService {
             .await
             .change_context(InvoicingRenderError::StorageError)?;
 
+        let xml_id = match xml {
+            Some(xml) => Some(
+                self.storage
+                    .store(xml, Prefix::InvoiceXml)
+                    .await
+                    .change_context(InvoicingRenderError::StorageError)?,
+            ),
+            None => None,
+        };
+
         self.store
-            .save_invoice_documents(invoice_id, tenant_id, customer_id, pdf_id, None)
+            .save_invoice_documents(invoice_id, tenant_id, customer_id, pdf_id, xml_id)
             .await
             .change_context(InvoicingRenderError::StoreError)?;
 
         Ok(pdf_id)
     }
+
+    /// Invoices are Factur-X hybrid documents. The XML is also stored on its own for e-invoicing
+    /// exchanges; an invoice it cannot describe still gets a plain PDF.
+    async fn generate_documents(
+        &self,
+        invoice: &invoicing_model::Invoice,
+    ) -> Result<(Bytes, Option<Bytes>), Report<InvoicingRenderError>> {
+        match self.pdf.generate_facturx_pdf(invoice).await {
+            Ok(document) => Ok((document.pdf, Some(document.xml))),
+            Err(InvoicingError::XmlGenerationError(error)) => {
+                log::warn!(
+                    "Skipping Factur-X for invoice {}: {error}",
+                    invoice.metadata.number
+                );
+                let pdf = self
+                    .pdf
+                    .generate_pdf(invoice)
+                    .await
+                    .change_context(InvoicingRenderError::PdfError)?;
+                Ok((pdf, None))
+            }
+            Err(error) => Err(Report::new(error).change_context(InvoicingRenderError::PdfError)),
+        }
+    }
 }
 
 mod mapper {
     use crate::errors::InvoicingRenderError;
     use error_stack::Report;
     use meteroid_invoicing::model as invoicing_model;
-    use meteroid_store::constants::Countries;
+    use meteroid_store::utils::locale::{document_language, translation_overrides_for};
 
     use meteroid_invoicing::model::Flags;
     use meteroid_store::domain as store_model;
@@ -533,6 +678,7 @@ mod mapper {
         accounting_rate: Option<HistoricalRate>,
         bank_details: Option<HashMap<String, String>>,
         payment_url: Option<String>,
+        usage_annex_url: Option<String>,
         parent_invoice: Option<&store_model::Invoice>,
         line_groups: &HashMap<String, String>,
     ) -> Result<invoicing_model::Invoice, Report<InvoicingRenderError>> {
@@ -568,6 +714,7 @@ mod mapper {
             discount: rusty_money::Money::from_minor(invoice.discount, currency),
             memo: invoice.memo.clone(),
             payment_url,
+            usage_annex_url,
             flags: Flags {
                 show_payment_status: Some(true),
                 show_payment_info: Some(true),
@@ -578,6 +725,7 @@ mod mapper {
                 whitelabel: Some(false),
             },
             purchase_order: invoice.purchase_order.clone(),
+            custom_fields: invoice.custom_fields.clone(),
             parent_invoice_number: parent_invoice.map(|pi| pi.invoice_number.clone()),
             parent_invoice_date: parent_invoice.map(|pi| pi.invoice_date),
         };
@@ -644,6 +792,7 @@ mod mapper {
                     })
                     .collect(),
                 group_label: line_groups.get(&line.local_id).cloned(),
+                metadata: line.metadata.clone(),
             })
             .collect();
 
@@ -656,8 +805,12 @@ mod mapper {
             })
             .collect();
 
-        let lang = Countries::resolve_country(&invoicing_entity.country.code)
-            .map_or_else(|| "en-US", |c| c.locale);
+        let lang = document_language(
+            invoice.customer_details.invoice_language.as_deref(),
+            &invoicing_entity.country,
+        );
+        let translation_overrides =
+            translation_overrides_for(&invoicing_entity.invoice_translations, &lang);
 
         let tax_breakdown = invoice
             .tax_breakdown
@@ -675,6 +828,10 @@ mod mapper {
                 invoicing_model::TaxBreakdownItem {
                     name: t.name.clone(),
                     rate: t.tax_rate,
+                    taxable_amount: rusty_money::Money::from_minor(
+                        t.taxable_amount as i64,
+                        currency,
+                    ),
                     amount: rusty_money::Money::from_minor(t.tax_amount as i64, currency),
                     exemption_type,
                 }
@@ -682,7 +839,9 @@ mod mapper {
             .collect();
 
         Ok(invoicing_model::Invoice {
-            lang: lang.to_string(),
+            lang,
+            translation_overrides,
+            template: invoicing_entity.invoice_template.clone(),
             customer,
             lines,
             metadata,
@@ -7,3 +7,4 @@ pub mod invoice_rendering;
 pub mod storage;
 pub mod subscription_ingest;
 pub mod svix_cache;
+pub mod usage_annex;
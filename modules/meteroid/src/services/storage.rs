@@ -1,7 +1,7 @@
 use crate::errors::ObjectStoreError;
 use async_trait::async_trait;
 use bytes::Bytes;
-use common_domain::ids::{BaseId, StoredDocumentId, TenantId};
+use common_domain::ids::{BaseId, BatchJobId, StoredDocumentId, TenantId};
 use error_stack::{Report, ResultExt};
 use http::Method;
 use object_store::aws::AmazonS3Builder;
@@ -9,7 +9,8 @@ use object_store::local::LocalFileSystem;
 use object_store::memory::InMemory;
 use object_store::path::Path;
 use object_store::signer::Signer;
-use object_store::{ObjectStore, ObjectStoreExt, ObjectStoreScheme, PutPayload};
+use object_store::{ObjectStore, ObjectStoreExt, ObjectStoreScheme, PutPayload, WriteMultipart};
+use std::io::Read;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -30,6 +31,14 @@ pub enum Prefix {
     BatchJobErrorOutput {
         tenant_id: TenantId,
     },
+    BatchJobOutput {
+        tenant_id: TenantId,
+    },
+    /// Intermediate output of a chunk, merged into the job output once all chunks are done.
+    BatchJobPart {
+        tenant_id: TenantId,
+        job_id: BatchJobId,
+    },
 }
 
 impl Prefix {
@@ -48,15 +57,31 @@ impl Prefix {
             Prefix::BatchJobErrorOutput { tenant_id } => {
                 format!("batch_job_errors/{tenant_id}")
             }
+            Prefix::BatchJobOutput { tenant_id } => format!("batch_job_outputs/{tenant_id}"),
+            Prefix::BatchJobPart { tenant_id, job_id } => {
+                format!("batch_job_parts/{tenant_id}/{job_id}")
+            }
         }
     }
 }
 
+const MULTIPART_READ_BUFFER_SIZE: usize = 1024 * 1024;
+const MULTIPART_MAX_CONCURRENCY: usize = 4;
+
 pub type Result<T> = core::result::Result<T, Report<ObjectStoreError>>;
 
 #[async_trait]
 pub trait ObjectStoreService: Send + Sync {
     async fn store(&self, binary: Bytes, prefix: Prefix) -> Result<StoredDocumentId>;
+    /// Stores the document under a known id, replacing any previous version.
+    async fn store_with_id(
+        &self,
+        uid: StoredDocumentId,
+        binary: Bytes,
+        prefix: Prefix,
+    ) -> Result<()>;
+    /// Uploads a local file in parts, without loading it in memory.
+    async fn store_file(&self, file: std::fs::File, prefix: Prefix) -> Result<StoredDocumentId>;
     async fn retrieve(&self, uid: StoredDocumentId, prefix: Prefix) -> Result<Bytes>;
     async fn get_url(
         &self,
@@ -115,10 +140,21 @@ impl S3Storage {
 #[async_trait]
 impl ObjectStoreService for S3Storage {
     async fn store(&self, binary: Bytes, document_type: Prefix) -> Result<StoredDocumentId> {
-        let payload = PutPayload::from_bytes(binary);
-
         let uid = StoredDocumentId::new();
 
+        self.store_with_id(uid, binary, document_type).await?;
+
+        Ok(uid)
+    }
+
+    async fn store_with_id(
+        &self,
+        uid: StoredDocumentId,
+        binary: Bytes,
+        document_type: Prefix,
+    ) -> Result<()> {
+        let payload = PutPayload::from_bytes(binary);
+
         let path = self
             .path
             .clone()
@@ -130,6 +166,51 @@ impl ObjectStoreService for S3Storage {
             .await
             .change_context(ObjectStoreError::SaveError)?;
 
+        Ok(())
+    }
+
+    async fn store_file(
+        &self,
+        mut file: std::fs::File,
+        document_type: Prefix,
+    ) -> Result<StoredDocumentId> {
+        let uid = StoredDocumentId::new();
+
+        let path = self
+            .path
+            .clone()
+            .join(document_type.to_path_string().as_str())
+            .join(uid.as_uuid().to_string().as_str());
+
+        let upload = self
+            .object_store_client
+            .put_multipart(&path)
+            .await
+            .change_context(ObjectStoreError::SaveError)?;
+        let mut writer = WriteMultipart::new(upload);
+
+        let mut buf = vec![0u8; MULTIPART_READ_BUFFER_SIZE];
+        loop {
+            let read = match file.read(&mut buf) {
+                Ok(0) => break,
+                Ok(read) => read,
+                Err(e) => {
+                    let _ = writer.abort().await;
+                    return Err(Report::new(e).change_context(ObjectStoreError::SaveError));
+                }
+            };
+            writer
+                .wait_for_capacity(MULTIPART_MAX_CONCURRENCY)
+                .await
+                .change_context(ObjectStoreError::SaveError)?;
+            writer.write(&buf[..read]);
+        }
+
+        writer
+            .finish()
+            .await
+            .change_context(ObjectStoreError::SaveError)?;
+
         Ok(uid)
     }
 
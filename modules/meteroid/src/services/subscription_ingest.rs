@@ -1,11 +1,11 @@
 use chrono::NaiveDate;
 use common_domain::ids::{AliasOr, CustomerId, PlanId};
-use meteroid_store::domain::PaymentMethodsConfig;
 use meteroid_store::domain::enums::SubscriptionActivationCondition;
+use meteroid_store::domain::{PastPeriodBilling, PaymentMethodsConfig};
 use serde::Deserialize;
 
 use super::csv_ingest::{
-    CsvString, optional_csv_string, optional_naive_date, optional_u16, optional_u32,
+    CsvString, optional_bool, optional_csv_string, optional_naive_date, optional_u16, optional_u32,
 };
 
 #[derive(Deserialize)]
@@ -30,7 +30,10 @@ pub struct NewSubscriptionCsv {
     pub payment_method: Option<PaymentMethodCsv>,
     #[serde(default, with = "optional_csv_string")]
     pub purchase_order: Option<CsvString>,
-    pub skip_past_invoices: bool,
+    #[serde(default, with = "optional_bool")]
+    pub skip_past_invoices: Option<bool>,
+    #[serde(default)]
+    pub past_period_billing: Option<PastPeriodBillingCsv>,
 }
 
 #[derive(Deserialize)]
@@ -70,3 +73,21 @@ impl From<PaymentMethodCsv> for PaymentMethodsConfig {
         }
     }
 }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PastPeriodBillingCsv {
+    Invoice,
+    ImportAsPaid,
+    Skip,
+}
+
+impl From<PastPeriodBillingCsv> for PastPeriodBilling {
+    fn from(v: PastPeriodBillingCsv) -> Self {
+        match v {
+            PastPeriodBillingCsv::Invoice => PastPeriodBilling::Invoice,
+            PastPeriodBillingCsv::ImportAsPaid => PastPeriodBilling::ImportAsPaid,
+            PastPeriodBillingCsv::Skip => PastPeriodBilling::Skip,
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use common_utils::decimals::ToUnit;
+use meteroid_store::constants::Currencies;
+use meteroid_store::domain::invoice_lines::LineItem;
+
+const HEADERS: [&str; 9] = [
+    "line",
+    "group",
+    "period_start",
+    "period_end",
+    "detail",
+    "quantity",
+    "unit_price",
+    "amount",
+    "currency",
+];
+
+/// CSV of the usage lines that are merged on the invoice documents, one row per sub-line
+/// (or per line when it has none).
+pub fn usage_annex_csv(lines: &[LineItem], currency: &str) -> Result<Vec<u8>, csv::Error> {
+    let precision = Currencies::resolve_currency_precision(currency).unwrap_or(2);
+    let fmt_opt = |value: Option<rust_decimal::Decimal>| -> String {
+        value.map(|v| v.normalize().to_string()).unwrap_or_default()
+    };
+
+    let mut wtr = csv::Writer::from_writer(Vec::new());
+    wtr.write_record(HEADERS)?;
+
+    for line in lines {
+        let group = line
+            .group_by_dimensions
+            .as_ref()
+            .map(|dimensions| {
+                let mut sorted: Vec<_> = dimensions.iter().collect();
+                sorted.sort_by_key(|(k, _)| *k);
+                sorted
+                    .into_iter()
+                    .map(|(k, v)| format!("{k}={v}"))
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            })
+            .unwrap_or_default();
+
+        let line_columns = [
+            line.name.clone(),
+            group,
+            line.start_date.to_string(),
+            line.end_date.to_string(),
+        ];
+
+        if line.sub_lines.is_empty() {
+            wtr.write_record(line_columns.iter().cloned().chain([
+                String::new(),
+                fmt_opt(line.quantity),
+                fmt_opt(line.unit_price),
+                line.amount_subtotal.to_unit(precision).to_string(),
+                currency.to_string(),
+            ]))?;
+            continue;
+        }
+
+        for sub_line in &line.sub_lines {
+            wtr.write_record(line_columns.iter().cloned().chain([
+                sub_line.name.clone(),
+                fmt_opt(Some(sub_line.quantity)),
+                fmt_opt(Some(sub_line.unit_price)),
+                sub_line.total.to_unit(precision).to_string(),
+                currency.to_string(),
+            ]))?;
+        }
+    }
+
+    wtr.into_inner().map_err(|e| e.into_error().into())
+}
@@ -67,6 +67,22 @@ pub trait BatchJobProcessor: Send + Sync {
         input_data: Option<Bytes>,
     ) -> Result<ChunkResult, String>;
 
+    /// Build an output spanning all chunks (e.g. merge per-chunk files into one archive).
+    ///
+    /// Called by the finalizing worker once no chunk is pending or processing, and at least one
+    /// completed, before the job leaves PROCESSING. It can run more than once (concurrent workers,
+    /// crash before finalization), so it must be idempotent.
+    ///
+    /// If this fails, the job transitions to FAILED.
+    async fn complete_job(&self, _job: &BatchJob, _chunks: &[BatchJobChunk]) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Whether `complete_job` has to run for this job type.
+    fn has_job_output(&self) -> bool {
+        false
+    }
+
     /// Max retries for chunks of this job type.
     fn max_retries(&self) -> i32 {
         3
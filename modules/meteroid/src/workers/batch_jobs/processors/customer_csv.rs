@@ -63,6 +63,10 @@ fn map_to_domain(csv: NewCustomerCsv) -> Result<CustomerNew, String> {
         custom_taxes,
         is_tax_exempt: csv.is_tax_exempt.unwrap_or(false),
         connected_account_id: None,
+        custom_attributes: Default::default(),
+        invoice_language: csv.invoice_language.map(|l| l.0),
+        invoice_custom_fields: Default::default(),
+        invoice_approval_required: false,
     })
 }
 
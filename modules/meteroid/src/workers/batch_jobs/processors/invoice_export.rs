@@ -0,0 +1,1087 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Seek, SeekFrom, Write};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::NaiveDate;
+use common_domain::ids::{BaseId, CreditNoteId, InvoiceId, InvoicingEntityId, StoredDocumentId};
+use common_utils::decimals::ToUnit;
+use meteroid_store::Store;
+use meteroid_store::constants::Currencies;
+use meteroid_store::domain::batch_jobs::{BatchJob, BatchJobChunk};
+use meteroid_store::domain::enums::{
+    BatchJobChunkStatusEnum, CreditNoteStatus, CreditType, InvoicePaymentStatus, InvoiceStatusEnum,
+};
+use meteroid_store::domain::invoice_lines::LineItem;
+use meteroid_store::domain::invoices::{TaxBreakdownItem, TaxExemptionType};
+use meteroid_store::domain::{CreditNote, InlineCustomer, Invoice};
+use meteroid_store::repositories::batch_jobs::BatchJobsInterface;
+use meteroid_store::repositories::historical_rates::HistoricalRatesInterface;
+use meteroid_store::repositories::invoicing_entities::InvoicingEntityInterfaceAuto;
+use meteroid_store::repositories::{CreditNoteInterface, InvoiceInterface};
+use rust_decimal::Decimal;
+use rust_decimal::prelude::FromPrimitive;
+use serde::Deserialize;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::services::storage::{ObjectStoreService, Prefix};
+use crate::workers::batch_jobs::engine::{
+    BatchJobProcessor, ChunkDefinition, ChunkResult, ItemFailure,
+};
+
+/// Exports every issued invoice and credit note of an invoicing entity for an accounting period
+/// as a ZIP archive: the PDFs (and e-invoice XMLs when available), a line-level ledger and a
+/// tax breakdown, both as CSV. The archive is saved as the job output.
+///
+/// The documents of the period are snapshotted into a manifest CSV when chunking, which becomes
+/// the job input. Each chunk archives its slice of the manifest as a part, and the parts are merged
+/// into the final archive, spooled on disk, once all chunks are done. Parts are kept so that failed
+/// chunks can be retried and the archive rebuilt.
+/// Documents whose PDF is missing are reported as failed items but are still part of the ledger.
+pub struct InvoiceExportProcessor {
+    store: Arc<Store>,
+    object_store: Arc<dyn ObjectStoreService>,
+}
+
+impl InvoiceExportProcessor {
+    pub fn new(store: Arc<Store>, object_store: Arc<dyn ObjectStoreService>) -> Self {
+        Self {
+            store,
+            object_store,
+        }
+    }
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+#[derive(Deserialize)]
+struct InvoiceExportParams {
+    invoicing_entity_id: InvoicingEntityId,
+    from: NaiveDate,
+    to: NaiveDate,
+    #[serde(default = "default_delimiter")]
+    delimiter: char,
+}
+
+fn parse_export_params(job: &BatchJob) -> Result<InvoiceExportParams, String> {
+    let params = job.input_params.as_ref().ok_or("Missing input_params")?;
+
+    let params: InvoiceExportParams =
+        serde_json::from_value(params.clone()).map_err(|e| format!("Invalid input_params: {e}"))?;
+
+    if params.from > params.to {
+        return Err("'from' must be before or equal to 'to'".to_string());
+    }
+    if !params.delimiter.is_ascii() {
+        return Err("delimiter must be an ASCII character".to_string());
+    }
+
+    Ok(params)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum DocumentKind {
+    Invoice,
+    CreditNote,
+}
+
+impl DocumentKind {
+    fn parse(value: &str) -> Option<Self> {
+        match value {
+            "invoice" => Some(DocumentKind::Invoice),
+            "credit_note" => Some(DocumentKind::CreditNote),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            DocumentKind::Invoice => "invoice",
+            DocumentKind::CreditNote => "credit_note",
+        }
+    }
+
+    fn folder(&self) -> &'static str {
+        match self {
+            DocumentKind::Invoice => "invoices",
+            DocumentKind::CreditNote => "credit_notes",
+        }
+    }
+}
+
+/// Common view over invoices and credit notes for the ledger.
+/// Credit note amounts are negative, so the ledger sums up to the period revenue.
+struct ExportDocument {
+    kind: DocumentKind,
+    number: String,
+    date: NaiveDate,
+    status: &'static str,
+    related_invoice_number: Option<String>,
+    customer: InlineCustomer,
+    currency: String,
+    line_items: Vec<LineItem>,
+    tax_breakdown: Vec<TaxBreakdownItem>,
+    subtotal: i64,
+    tax_amount: i64,
+    total: i64,
+    payment_status: Option<&'static str>,
+    credit_type: Option<&'static str>,
+    pdf_document_id: Option<StoredDocumentId>,
+    xml_document_id: Option<StoredDocumentId>,
+}
+
+impl ExportDocument {
+    /// Tax breakdown amounts are stored unsigned; credit notes are negated like their totals.
+    fn tax_sign(&self) -> i64 {
+        match self.kind {
+            DocumentKind::Invoice => 1,
+            DocumentKind::CreditNote => -1,
+        }
+    }
+
+    fn file_stem(&self) -> String {
+        sanitize_file_name(&self.number)
+    }
+}
+
+impl From<Invoice> for ExportDocument {
+    fn from(invoice: Invoice) -> Self {
+        Self {
+            kind: DocumentKind::Invoice,
+            number: invoice.invoice_number,
+            date: invoice.invoice_date,
+            status: match invoice.status {
+                InvoiceStatusEnum::Draft => "draft",
+                InvoiceStatusEnum::Finalized => "finalized",
+                InvoiceStatusEnum::Void => "void",
+                InvoiceStatusEnum::Uncollectible => "uncollectible",
+            },
+            related_invoice_number: None,
+            customer: invoice.customer_details,
+            currency: invoice.currency,
+            line_items: invoice.line_items,
+            tax_breakdown: invoice.tax_breakdown,
+            subtotal: invoice.subtotal,
+            tax_amount: invoice.tax_amount,
+            total: invoice.total,
+            payment_status: Some(match invoice.payment_status {
+                InvoicePaymentStatus::Unpaid => "unpaid",
+                InvoicePaymentStatus::PartiallyPaid => "partially_paid",
+                InvoicePaymentStatus::Paid => "paid",
+                InvoicePaymentStatus::Errored => "errored",
+                InvoicePaymentStatus::Processing => "processing",
+            }),
+            credit_type: None,
+            pdf_document_id: invoice.pdf_document_id,
+            xml_document_id: invoice.xml_document_id,
+        }
+    }
+}
+
+impl From<CreditNote> for ExportDocument {
+    fn from(credit_note: CreditNote) -> Self {
+        Self {
+            kind: DocumentKind::CreditNote,
+            number: credit_note.credit_note_number,
+            date: credit_note
+                .finalized_at
+                .unwrap_or(credit_note.created_at)
+                .date(),
+            status: match credit_note.status {
+                CreditNoteStatus::Draft => "draft",
+                CreditNoteStatus::Finalized => "finalized",
+                CreditNoteStatus::Voided => "voided",
+            },
+            related_invoice_number: Some(credit_note.invoice_number),
+            customer: credit_note.customer_details,
+            currency: credit_note.currency,
+            line_items: credit_note.line_items,
+            tax_breakdown: credit_note.tax_breakdown,
+            subtotal: credit_note.subtotal,
+            tax_amount: credit_note.tax_amount,
+            total: credit_note.total,
+            payment_status: None,
+            credit_type: Some(match credit_note.credit_type {
+                CreditType::CreditToBalance => "credit_to_balance",
+                CreditType::Refund => "refund",
+                CreditType::DebtCancellation => "debt_cancellation",
+            }),
+            pdf_document_id: credit_note.pdf_document_id,
+            xml_document_id: None,
+        }
+    }
+}
+
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c if c.is_control() => '_',
+            c => c,
+        })
+        .collect()
+}
+
+fn exemption_label(exemption: &Option<TaxExemptionType>) -> &'static str {
+    match exemption {
+        None => "",
+        Some(TaxExemptionType::ReverseCharge) => "reverse_charge",
+        Some(TaxExemptionType::TaxExempt) => "tax_exempt",
+        Some(TaxExemptionType::NotRegistered) => "not_registered",
+    }
+}
+
+fn precision(currency: &str) -> u8 {
+    Currencies::resolve_currency_precision(currency).unwrap_or(2)
+}
+
+fn fmt_opt<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Conversion of a document to the accounting currency of the invoicing entity.
+struct FxConversion {
+    rate: Option<Decimal>,
+    precision: u8,
+}
+
+impl FxConversion {
+    fn convert(&self, amount: Decimal) -> String {
+        fmt_opt(
+            self.rate
+                .map(|r| (amount * r).round_dp(self.precision as u32)),
+        )
+    }
+}
+
+const LEDGER_HEADERS: &[&str] = &[
+    "document_type",
+    "document_number",
+    "document_date",
+    "status",
+    "related_invoice_number",
+    "customer_id",
+    "customer_name",
+    "customer_vat_number",
+    "currency",
+    "line_name",
+    "line_description",
+    "period_start",
+    "period_end",
+    "quantity",
+    "unit_price",
+    "line_subtotal",
+    "line_tax_rate",
+    "line_tax_amount",
+    "line_total",
+    "document_subtotal",
+    "document_tax_amount",
+    "document_total",
+    "payment_status",
+    "credit_type",
+    "accounting_currency",
+    "fx_rate",
+    "accounting_document_total",
+];
+
+const TAX_BREAKDOWN_HEADERS: &[&str] = &[
+    "document_type",
+    "document_number",
+    "document_date",
+    "currency",
+    "tax_name",
+    "tax_rate",
+    "exemption_type",
+    "taxable_amount",
+    "tax_amount",
+    "accounting_currency",
+    "fx_rate",
+    "accounting_taxable_amount",
+    "accounting_tax_amount",
+];
+
+fn write_ledger(
+    documents: &[ExportDocument],
+    fx: &[FxConversion],
+    accounting_currency: &str,
+    delimiter: u8,
+) -> Result<Vec<u8>, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+
+    wtr.write_record(LEDGER_HEADERS)
+        .map_err(|e| format!("write header: {e}"))?;
+
+    for (doc, fx) in documents.iter().zip(fx) {
+        let precision = precision(&doc.currency);
+        let document_columns = |line: [String; 10]| -> Vec<String> {
+            let mut record = vec![
+                doc.kind.as_str().to_string(),
+                doc.number.clone(),
+                doc.date.to_string(),
+                doc.status.to_string(),
+                doc.related_invoice_number.clone().unwrap_or_default(),
+                doc.customer.id.to_string(),
+                doc.customer.name.clone(),
+                doc.customer.vat_number.clone().unwrap_or_default(),
+                doc.currency.clone(),
+            ];
+            record.extend(line);
+            record.extend([
+                doc.subtotal.to_unit(precision).to_string(),
+                doc.tax_amount.to_unit(precision).to_string(),
+                doc.total.to_unit(precision).to_string(),
+                doc.payment_status.unwrap_or_default().to_string(),
+                doc.credit_type.unwrap_or_default().to_string(),
+                accounting_currency.to_string(),
+                fmt_opt(fx.rate),
+                fx.convert(doc.total.to_unit(precision)),
+            ]);
+            record
+        };
+
+        if doc.line_items.is_empty() {
+            wtr.write_record(document_columns(Default::default()))
+                .map_err(|e| format!("write row: {e}"))?;
+            continue;
+        }
+
+        for line in &doc.line_items {
+            wtr.write_record(document_columns([
+                line.name.clone(),
+                line.description.clone().unwrap_or_default(),
+                line.start_date.to_string(),
+                line.end_date.to_string(),
+                fmt_opt(line.quantity),
+                fmt_opt(line.unit_price),
+                line.amount_subtotal.to_unit(precision).to_string(),
+                line.tax_rate.to_string(),
+                line.tax_amount.to_unit(precision).to_string(),
+                line.amount_total.to_unit(precision).to_string(),
+            ]))
+            .map_err(|e| format!("write row: {e}"))?;
+        }
+    }
+
+    wtr.into_inner().map_err(|e| format!("flush csv: {e}"))
+}
+
+fn write_tax_breakdown(
+    documents: &[ExportDocument],
+    fx: &[FxConversion],
+    accounting_currency: &str,
+    delimiter: u8,
+) -> Result<Vec<u8>, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+
+    wtr.write_record(TAX_BREAKDOWN_HEADERS)
+        .map_err(|e| format!("write header: {e}"))?;
+
+    for (doc, fx) in documents.iter().zip(fx) {
+        let precision = precision(&doc.currency);
+        for item in &doc.tax_breakdown {
+            let taxable = (item.taxable_amount as i64 * doc.tax_sign()).to_unit(precision);
+            let tax = (item.tax_amount as i64 * doc.tax_sign()).to_unit(precision);
+            wtr.write_record([
+                doc.kind.as_str().to_string(),
+                doc.number.clone(),
+                doc.date.to_string(),
+                doc.currency.clone(),
+                item.name.clone(),
+                item.tax_rate.to_string(),
+                exemption_label(&item.exemption_type).to_string(),
+                taxable.to_string(),
+                tax.to_string(),
+                accounting_currency.to_string(),
+                fmt_opt(fx.rate),
+                fx.convert(taxable),
+                fx.convert(tax),
+            ])
+            .map_err(|e| format!("write row: {e}"))?;
+        }
+    }
+
+    wtr.into_inner().map_err(|e| format!("flush csv: {e}"))
+}
+
+/// Entries of a chunk part, merged across parts into the final archive.
+const LEDGER_FILE: &str = "ledger.csv";
+const TAX_BREAKDOWN_FILE: &str = "tax_breakdown.csv";
+
+const MANIFEST_HEADERS: &[&str] = &["document_type", "document_id", "document_number"];
+
+/// A document of the export snapshot, one row of the manifest.
+#[derive(Debug, PartialEq, Eq)]
+struct ManifestEntry {
+    kind: DocumentKind,
+    id: String,
+    number: String,
+}
+
+fn write_manifest(entries: &[ManifestEntry], delimiter: u8) -> Result<Vec<u8>, String> {
+    let mut wtr = csv::WriterBuilder::new()
+        .delimiter(delimiter)
+        .from_writer(Vec::new());
+
+    wtr.write_record(MANIFEST_HEADERS)
+        .map_err(|e| format!("write header: {e}"))?;
+    for entry in entries {
+        wtr.write_record([entry.kind.as_str(), &entry.id, &entry.number])
+            .map_err(|e| format!("write row: {e}"))?;
+    }
+
+    wtr.into_inner().map_err(|e| format!("flush csv: {e}"))
+}
+
+fn read_manifest(data: &[u8], delimiter: u8) -> Result<Vec<ManifestEntry>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .delimiter(delimiter)
+        .from_reader(data);
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Invalid document snapshot: {e}"))?;
+            let kind = record
+                .get(0)
+                .and_then(DocumentKind::parse)
+                .ok_or("Invalid document type in snapshot")?;
+            Ok(ManifestEntry {
+                kind,
+                id: record.get(1).unwrap_or_default().to_string(),
+                number: record.get(2).unwrap_or_default().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Parts are stored under the chunk id, so a retried chunk replaces its part.
+fn part_id(chunk: &BatchJobChunk) -> StoredDocumentId {
+    StoredDocumentId::from(chunk.id.as_uuid())
+}
+
+fn zip_error(e: impl std::fmt::Display) -> String {
+    format!("Failed to write archive: {e}")
+}
+
+/// Merges the chunk parts, in chunk order, into a single archive spooled to a temporary file.
+/// The CSVs are concatenated keeping the first header only, documents are copied as-is.
+fn merge_parts(parts: Vec<File>, modified_at: zip::DateTime) -> Result<File, String> {
+    let mut archives = parts
+        .into_iter()
+        .map(ZipArchive::new)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to read export part: {e}"))?;
+
+    let spool = tempfile::tempfile().map_err(zip_error)?;
+    let mut writer = ZipWriter::new(spool);
+    let csv_options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .last_modified_time(modified_at)
+        .large_file(true);
+
+    for name in [LEDGER_FILE, TAX_BREAKDOWN_FILE] {
+        writer.start_file(name, csv_options).map_err(zip_error)?;
+        for (idx, archive) in archives.iter_mut().enumerate() {
+            let entry = archive
+                .by_name(name)
+                .map_err(|e| format!("Failed to read export part: {e}"))?;
+            let mut entry = BufReader::new(entry);
+            if idx > 0 {
+                entry
+                    .read_until(b'\n', &mut Vec::new())
+                    .map_err(zip_error)?;
+            }
+            io::copy(&mut entry, &mut writer).map_err(zip_error)?;
+        }
+    }
+
+    for archive in &mut archives {
+        for idx in 0..archive.len() {
+            let entry = archive
+                .by_index_raw(idx)
+                .map_err(|e| format!("Failed to read export part: {e}"))?;
+            if entry.name() == LEDGER_FILE || entry.name() == TAX_BREAKDOWN_FILE {
+                continue;
+            }
+            writer.raw_copy_file(entry).map_err(zip_error)?;
+        }
+    }
+
+    let mut file = writer.finish().map_err(zip_error)?;
+    file.seek(SeekFrom::Start(0)).map_err(zip_error)?;
+    Ok(file)
+}
+
+impl InvoiceExportProcessor {
+    /// Snapshot of the documents of the period, invoices first.
+    async fn list_manifest(
+        &self,
+        job: &BatchJob,
+        params: &InvoiceExportParams,
+    ) -> Result<Vec<ManifestEntry>, String> {
+        let invoices = self
+            .store
+            .list_invoices_for_export(
+                job.tenant_id,
+                params.invoicing_entity_id,
+                params.from,
+                params.to,
+            )
+            .await
+            .map_err(|e| format!("Failed to list invoices: {e:?}"))?;
+
+        let credit_notes = self
+            .store
+            .list_credit_notes_for_export(
+                job.tenant_id,
+                params.invoicing_entity_id,
+                params.from,
+                params.to,
+            )
+            .await
+            .map_err(|e| format!("Failed to list credit notes: {e:?}"))?;
+
+        Ok(invoices
+            .into_iter()
+            .map(|invoice| ManifestEntry {
+                kind: DocumentKind::Invoice,
+                id: invoice.id.to_string(),
+                number: invoice.invoice_number,
+            })
+            .chain(credit_notes.into_iter().map(|credit_note| ManifestEntry {
+                kind: DocumentKind::CreditNote,
+                id: credit_note.id.to_string(),
+                number: credit_note.credit_note_number,
+            }))
+            .collect())
+    }
+
+    /// Loads the documents of the entries, in the same order. Documents that no longer exist are `None`.
+    async fn load_documents(
+        &self,
+        job: &BatchJob,
+        entries: &[ManifestEntry],
+    ) -> Result<Vec<Option<ExportDocument>>, String> {
+        let invalid_id = |e| format!("Invalid document id in snapshot: {e}");
+
+        let mut invoice_ids = Vec::new();
+        let mut credit_note_ids = Vec::new();
+        for entry in entries {
+            match entry.kind {
+                DocumentKind::Invoice => {
+                    invoice_ids.push(entry.id.parse::<InvoiceId>().map_err(invalid_id)?)
+                }
+                DocumentKind::CreditNote => {
+                    credit_note_ids.push(entry.id.parse::<CreditNoteId>().map_err(invalid_id)?)
+                }
+            }
+        }
+
+        let mut invoices: HashMap<String, Invoice> = self
+            .store
+            .list_invoices_by_ids(invoice_ids)
+            .await
+            .map_err(|e| format!("Failed to load invoices: {e:?}"))?
+            .into_iter()
+            .filter(|invoice| invoice.tenant_id == job.tenant_id)
+            .map(|invoice| (invoice.id.to_string(), invoice))
+            .collect();
+
+        let mut credit_notes: HashMap<String, CreditNote> = self
+            .store
+            .list_credit_notes_by_ids(credit_note_ids)
+            .await
+            .map_err(|e| format!("Failed to load credit notes: {e:?}"))?
+            .into_iter()
+            .filter(|credit_note| credit_note.tenant_id == job.tenant_id)
+            .map(|credit_note| (credit_note.id.to_string(), credit_note))
+            .collect();
+
+        Ok(entries
+            .iter()
+            .map(|entry| match entry.kind {
+                DocumentKind::Invoice => invoices.remove(&entry.id).map(ExportDocument::from),
+                DocumentKind::CreditNote => {
+                    credit_notes.remove(&entry.id).map(ExportDocument::from)
+                }
+            })
+            .collect())
+    }
+
+    /// Resolves the FX rate of each document at its date, reusing rates across documents.
+    async fn resolve_fx(
+        &self,
+        documents: &[ExportDocument],
+        accounting_currency: &str,
+    ) -> Result<Vec<FxConversion>, String> {
+        let accounting_precision = precision(accounting_currency);
+        let mut rates: HashMap<(String, NaiveDate), Option<Decimal>> = HashMap::new();
+        let mut conversions = Vec::with_capacity(documents.len());
+
+        for doc in documents {
+            let rate = if doc.currency == accounting_currency {
+                Some(Decimal::ONE)
+            } else if let Some(rate) = rates.get(&(doc.currency.clone(), doc.date)) {
+                *rate
+            } else {
+                let rate = self
+                    .store
+                    .get_historical_rate(&doc.currency, accounting_currency, doc.date)
+                    .await
+                    .map_err(|e| format!("Failed to get FX rate: {e:?}"))?
+                    .and_then(|r| Decimal::from_f32(r.rate));
+                rates.insert((doc.currency.clone(), doc.date), rate);
+                rate
+            };
+
+            conversions.push(FxConversion {
+                rate,
+                precision: accounting_precision,
+            });
+        }
+
+        Ok(conversions)
+    }
+}
+
+#[async_trait]
+impl BatchJobProcessor for InvoiceExportProcessor {
+    async fn prepare_chunks(
+        &self,
+        job: &BatchJob,
+        _input_data: Option<Bytes>,
+    ) -> Result<Vec<ChunkDefinition>, String> {
+        let params = parse_export_params(job)?;
+
+        self.store
+            .get_invoicing_entity(job.tenant_id, Some(params.invoicing_entity_id))
+            .await
+            .map_err(|e| format!("Invoicing entity not found: {e:?}"))?;
+
+        let manifest = self.list_manifest(job, &params).await?;
+        if manifest.is_empty() {
+            return Err("No invoices or credit notes issued in this period".to_string());
+        }
+
+        let manifest_id = self
+            .object_store
+            .store(
+                Bytes::from(write_manifest(&manifest, params.delimiter as u8)?),
+                Prefix::BatchJobInput {
+                    tenant_id: job.tenant_id,
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to store document snapshot: {e}"))?;
+
+        self.store
+            .set_input_source_key(job.id, manifest_id.to_string())
+            .await
+            .map_err(|e| format!("Failed to save document snapshot: {e:?}"))?;
+
+        let total = manifest.len() as i32;
+        Ok((0..total)
+            .step_by(self.chunk_size() as usize)
+            .map(|item_offset| ChunkDefinition {
+                item_offset,
+                item_count: self.chunk_size().min(total - item_offset),
+            })
+            .collect())
+    }
+
+    async fn process_chunk(
+        &self,
+        job: &BatchJob,
+        chunk: &BatchJobChunk,
+        input_data: Option<Bytes>,
+    ) -> Result<ChunkResult, String> {
+        let params = parse_export_params(job)?;
+        let delimiter = params.delimiter as u8;
+
+        let manifest = read_manifest(&input_data.ok_or("Missing document snapshot")?, delimiter)?;
+        let entries = manifest
+            .get(chunk.item_offset as usize..(chunk.item_offset + chunk.item_count) as usize)
+            .ok_or("Chunk out of the document snapshot")?;
+
+        let invoicing_entity = self
+            .store
+            .get_invoicing_entity(job.tenant_id, Some(params.invoicing_entity_id))
+            .await
+            .map_err(|e| format!("Invoicing entity not found: {e:?}"))?;
+        let accounting_currency = invoicing_entity.accounting_currency;
+
+        let mut failures = Vec::new();
+        let mut documents = Vec::with_capacity(entries.len());
+        let mut item_indices = Vec::with_capacity(entries.len());
+        for (idx, (entry, doc)) in entries
+            .iter()
+            .zip(self.load_documents(job, entries).await?)
+            .enumerate()
+        {
+            let item_index = chunk.item_offset + idx as i32;
+            match doc {
+                Some(doc) => {
+                    documents.push(doc);
+                    item_indices.push(item_index);
+                }
+                None => failures.push(ItemFailure {
+                    item_index,
+                    item_identifier: Some(entry.number.clone()),
+                    reason: "Document not found".to_string(),
+                }),
+            }
+        }
+
+        let fx = self.resolve_fx(&documents, &accounting_currency).await?;
+
+        let modified_at =
+            zip::DateTime::try_from(chrono::Utc::now().naive_utc()).map_err(zip_error)?;
+        let csv_options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .last_modified_time(modified_at);
+        // PDFs are already compressed
+        let document_options = csv_options.compression_method(CompressionMethod::Stored);
+
+        let mut part = ZipWriter::new(Cursor::new(Vec::new()));
+        part.start_file(LEDGER_FILE, csv_options)
+            .map_err(zip_error)?;
+        part.write_all(&write_ledger(
+            &documents,
+            &fx,
+            &accounting_currency,
+            delimiter,
+        )?)
+        .map_err(zip_error)?;
+        part.start_file(TAX_BREAKDOWN_FILE, csv_options)
+            .map_err(zip_error)?;
+        part.write_all(&write_tax_breakdown(
+            &documents,
+            &fx,
+            &accounting_currency,
+            delimiter,
+        )?)
+        .map_err(zip_error)?;
+
+        for (doc, item_index) in documents.iter().zip(item_indices) {
+            let pdf_prefix = match doc.kind {
+                DocumentKind::Invoice => Prefix::InvoicePdf,
+                DocumentKind::CreditNote => Prefix::CreditNotePdf,
+            };
+
+            let mut files = vec![];
+            match doc.pdf_document_id {
+                Some(pdf_id) => files.push((pdf_id, pdf_prefix, "pdf", document_options)),
+                None => failures.push(ItemFailure {
+                    item_index,
+                    item_identifier: Some(doc.number.clone()),
+                    reason: "PDF not generated yet".to_string(),
+                }),
+            }
+            if let Some(xml_id) = doc.xml_document_id {
+                files.push((xml_id, Prefix::InvoiceXml, "xml", csv_options));
+            }
+
+            for (document_id, prefix, extension, options) in files {
+                match self.object_store.retrieve(document_id, prefix).await {
+                    Ok(data) => {
+                        let path = format!("{}/{}.{extension}", doc.kind.folder(), doc.file_stem());
+                        part.start_file(path, options).map_err(zip_error)?;
+                        part.write_all(&data).map_err(zip_error)?;
+                    }
+                    Err(e) => failures.push(ItemFailure {
+                        item_index,
+                        item_identifier: Some(doc.number.clone()),
+                        reason: format!("Failed to retrieve {extension} document: {e}"),
+                    }),
+                }
+            }
+        }
+
+        let part = part.finish().map_err(zip_error)?.into_inner();
+
+        self.object_store
+            .store_with_id(
+                part_id(chunk),
+                Bytes::from(part),
+                Prefix::BatchJobPart {
+                    tenant_id: job.tenant_id,
+                    job_id: job.id,
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to store export part: {e}"))?;
+
+        let mut failed_indices: Vec<i32> = failures.iter().map(|f| f.item_index).collect();
+        failed_indices.sort_unstable();
+        failed_indices.dedup();
+
+        Ok(ChunkResult {
+            processed: chunk.item_count - failed_indices.len() as i32,
+            failures,
+            created_entities: vec![],
+        })
+    }
+
+    async fn complete_job(&self, job: &BatchJob, chunks: &[BatchJobChunk]) -> Result<(), String> {
+        let mut completed: Vec<&BatchJobChunk> = chunks
+            .iter()
+            .filter(|c| c.status == BatchJobChunkStatusEnum::Completed)
+            .collect();
+        completed.sort_by_key(|c| c.chunk_index);
+
+        // Parts are spooled to disk so that only one is held in memory at a time
+        let mut parts = Vec::with_capacity(completed.len());
+        for chunk in completed {
+            let data = self
+                .object_store
+                .retrieve(
+                    part_id(chunk),
+                    Prefix::BatchJobPart {
+                        tenant_id: job.tenant_id,
+                        job_id: job.id,
+                    },
+                )
+                .await
+                .map_err(|e| format!("Failed to retrieve export part: {e}"))?;
+            let mut file = tempfile::tempfile().map_err(zip_error)?;
+            file.write_all(&data).map_err(zip_error)?;
+            parts.push(file);
+        }
+
+        let modified_at =
+            zip::DateTime::try_from(chrono::Utc::now().naive_utc()).map_err(zip_error)?;
+        let archive = tokio::task::spawn_blocking(move || merge_parts(parts, modified_at))
+            .await
+            .map_err(|e| format!("Failed to merge export parts: {e}"))??;
+
+        let output_id = self
+            .object_store
+            .store_file(
+                archive,
+                Prefix::BatchJobOutput {
+                    tenant_id: job.tenant_id,
+                },
+            )
+            .await
+            .map_err(|e| format!("Failed to store export archive: {e}"))?;
+
+        self.store
+            .set_output_key(job.id, output_id.to_string())
+            .await
+            .map_err(|e| format!("Failed to save export archive: {e:?}"))
+    }
+
+    fn has_job_output(&self) -> bool {
+        true
+    }
+
+    /// Each chunk archives its documents in memory before storing its part.
+    fn chunk_size(&self) -> i32 {
+        100
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_domain::ids::CustomerId;
+    use std::io::Read;
+    use std::str::FromStr;
+
+    fn document(currency: &str) -> ExportDocument {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 15).unwrap();
+        ExportDocument {
+            kind: DocumentKind::Invoice,
+            number: "INV-001".to_string(),
+            date,
+            status: "finalized",
+            related_invoice_number: None,
+            customer: InlineCustomer {
+                id: CustomerId::new(),
+                name: "Acme".to_string(),
+                email: None,
+                alias: None,
+                vat_number: None,
+                billing_address: None,
+                snapshot_at: date.and_hms_opt(0, 0, 0).unwrap(),
+                invoice_language: None,
+            },
+            currency: currency.to_string(),
+            line_items: vec![LineItem {
+                local_id: "line-1".to_string(),
+                name: "Seats".to_string(),
+                amount_subtotal: 10000,
+                tax_rate: Decimal::from_str("0.2").unwrap(),
+                taxable_amount: 10000,
+                tax_amount: 2000,
+                amount_total: 12000,
+                tax_details: vec![],
+                quantity: Some(Decimal::from(10)),
+                unit_price: Some(Decimal::from(10)),
+                start_date: date,
+                end_date: date,
+                sub_lines: vec![],
+                is_prorated: false,
+                price_component_id: None,
+                sub_component_id: None,
+                sub_add_on_id: None,
+                product_id: None,
+                metric_id: None,
+                description: None,
+                group_by_dimensions: None,
+                metadata: Default::default(),
+            }],
+            tax_breakdown: vec![TaxBreakdownItem {
+                taxable_amount: 10000,
+                tax_amount: 2000,
+                tax_rate: Decimal::from_str("0.2").unwrap(),
+                name: "VAT".to_string(),
+                exemption_type: None,
+            }],
+            subtotal: 10000,
+            tax_amount: 2000,
+            total: 12000,
+            payment_status: Some("paid"),
+            credit_type: None,
+            pdf_document_id: None,
+            xml_document_id: None,
+        }
+    }
+
+    /// Reads a CSV as records keyed by header.
+    fn read_csv(data: &[u8]) -> Vec<HashMap<String, String>> {
+        let mut reader = csv::Reader::from_reader(data);
+        let headers = reader.headers().unwrap().clone();
+        reader
+            .records()
+            .map(|record| {
+                headers
+                    .iter()
+                    .zip(record.unwrap().iter())
+                    .map(|(h, v)| (h.to_string(), v.to_string()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ledger_and_tax_breakdown_converted_to_accounting_currency() {
+        let documents = vec![document("USD")];
+        let fx = vec![FxConversion {
+            rate: Some(Decimal::from_str("0.9").unwrap()),
+            precision: 2,
+        }];
+
+        let ledger = read_csv(&write_ledger(&documents, &fx, "EUR", b',').unwrap());
+        assert_eq!(ledger.len(), 1);
+        assert_eq!(ledger[0]["currency"], "USD");
+        assert_eq!(ledger[0]["line_total"], "120");
+        assert_eq!(ledger[0]["document_total"], "120");
+        assert_eq!(ledger[0]["accounting_currency"], "EUR");
+        assert_eq!(ledger[0]["fx_rate"], "0.9");
+        assert_eq!(ledger[0]["accounting_document_total"], "108.0");
+
+        let taxes = read_csv(&write_tax_breakdown(&documents, &fx, "EUR", b',').unwrap());
+        assert_eq!(taxes.len(), 1);
+        assert_eq!(taxes[0]["taxable_amount"], "100");
+        assert_eq!(taxes[0]["tax_amount"], "20");
+        assert_eq!(taxes[0]["accounting_taxable_amount"], "90.0");
+        assert_eq!(taxes[0]["accounting_tax_amount"], "18.0");
+    }
+
+    #[test]
+    fn test_ledger_and_tax_breakdown_without_fx_rate() {
+        let mut credit_note = document("USD");
+        credit_note.kind = DocumentKind::CreditNote;
+        let documents = vec![credit_note];
+        let fx = vec![FxConversion {
+            rate: None,
+            precision: 2,
+        }];
+
+        let ledger = read_csv(&write_ledger(&documents, &fx, "EUR", b',').unwrap());
+        assert_eq!(ledger[0]["document_type"], "credit_note");
+        assert_eq!(ledger[0]["document_total"], "120");
+        assert_eq!(ledger[0]["accounting_currency"], "EUR");
+        assert_eq!(ledger[0]["fx_rate"], "");
+        assert_eq!(ledger[0]["accounting_document_total"], "");
+
+        let taxes = read_csv(&write_tax_breakdown(&documents, &fx, "EUR", b',').unwrap());
+        assert_eq!(taxes[0]["taxable_amount"], "-100");
+        assert_eq!(taxes[0]["tax_amount"], "-20");
+        assert_eq!(taxes[0]["fx_rate"], "");
+        assert_eq!(taxes[0]["accounting_taxable_amount"], "");
+        assert_eq!(taxes[0]["accounting_tax_amount"], "");
+    }
+
+    #[test]
+    fn test_manifest_roundtrip() {
+        let entries = vec![
+            ManifestEntry {
+                kind: DocumentKind::Invoice,
+                id: InvoiceId::new().to_string(),
+                number: "INV-001".to_string(),
+            },
+            ManifestEntry {
+                kind: DocumentKind::CreditNote,
+                id: CreditNoteId::new().to_string(),
+                number: "CN;001".to_string(),
+            },
+        ];
+
+        let data = write_manifest(&entries, b';').unwrap();
+        assert_eq!(read_manifest(&data, b';').unwrap(), entries);
+    }
+
+    #[test]
+    fn test_merge_parts() {
+        let options = SimpleFileOptions::default();
+        let part = |ledger: &str, pdf: &str| {
+            let mut file = tempfile::tempfile().unwrap();
+            let mut writer = ZipWriter::new(&mut file);
+            writer.start_file(LEDGER_FILE, options).unwrap();
+            writer.write_all(ledger.as_bytes()).unwrap();
+            writer.start_file(TAX_BREAKDOWN_FILE, options).unwrap();
+            writer.write_all(b"tax\n").unwrap();
+            writer.start_file(pdf, options).unwrap();
+            writer.write_all(pdf.as_bytes()).unwrap();
+            writer.finish().unwrap();
+            file.seek(SeekFrom::Start(0)).unwrap();
+            file
+        };
+
+        let merged = merge_parts(
+            vec![
+                part("number\nINV-1\n", "invoices/INV-1.pdf"),
+                part("number\nINV-2\n", "invoices/INV-2.pdf"),
+            ],
+            zip::DateTime::default(),
+        )
+        .unwrap();
+
+        let mut archive = ZipArchive::new(merged).unwrap();
+        let mut read = |name: &str| {
+            let mut content = String::new();
+            archive
+                .by_name(name)
+                .unwrap()
+                .read_to_string(&mut content)
+                .unwrap();
+            content
+        };
+        assert_eq!(read(LEDGER_FILE), "number\nINV-1\nINV-2\n");
+        assert_eq!(read(TAX_BREAKDOWN_FILE), "tax\n");
+        assert_eq!(read("invoices/INV-1.pdf"), "invoices/INV-1.pdf");
+        assert_eq!(read("invoices/INV-2.pdf"), "invoices/INV-2.pdf");
+    }
+}
@@ -1,7 +1,9 @@
 pub mod customer_csv;
 pub mod event_csv;
+pub mod invoice_export;
 pub mod subscription_csv;
 
 pub use customer_csv::CustomerCsvProcessor;
 pub use event_csv::EventCsvProcessor;
+pub use invoice_export::InvoiceExportProcessor;
 pub use subscription_csv::SubscriptionCsvProcessor;
@@ -32,7 +32,6 @@ const REQUIRED_HEADERS: &[&str] = &[
     "activation_condition",
     "auto_advance_invoices",
     "charge_automatically",
-    "skip_past_invoices",
 ];
 
 pub struct SubscriptionCsvProcessor {
@@ -240,7 +239,7 @@ fn map_to_domain(
     customer_id: CustomerId,
     plan_version_id: PlanVersionId,
 ) -> CreateSubscription {
-    use meteroid_store::domain::SubscriptionNew;
+    use meteroid_store::domain::{PastPeriodBilling, SubscriptionNew};
 
     CreateSubscription {
         subscription: SubscriptionNew {
@@ -261,7 +260,11 @@ fn map_to_domain(
             purchase_order: csv.purchase_order.map(|s| s.0),
             backdate_invoices: false,
             skip_checkout_session: false,
-            skip_past_invoices: csv.skip_past_invoices,
+            past_period_billing: PastPeriodBilling::resolve(
+                csv.past_period_billing.map(Into::into),
+                csv.skip_past_invoices.unwrap_or(false),
+            ),
+            instance: None,
         },
         price_components: None,
         add_ons: None,
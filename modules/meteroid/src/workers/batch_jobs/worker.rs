@@ -476,6 +476,14 @@ impl BatchJobWorker {
     }
 
     async fn try_finalize(&self, job_id: BatchJobId) {
+        if let Err(e) = self.complete_job(job_id).await {
+            log::error!("Failed to build output of job {}: {}", job_id, e);
+            if let Err(err) = self.store.fail_job(job_id, Some(e)).await {
+                log::error!("Failed to fail job {}: {err:?}", job_id);
+            }
+            return;
+        }
+
         match self.store.try_finalize_job(job_id).await {
             Ok(Some(status)) => {
                 log::info!("Job {} finalized with status {:?}", job_id, status);
@@ -495,6 +503,47 @@ impl BatchJobWorker {
         }
     }
 
+    /// Let the processor build the job output once all chunks are settled.
+    ///
+    /// Runs while the job is still PROCESSING, so a crash before finalization is retried
+    /// by the stalled-job sweep.
+    async fn complete_job(&self, job_id: BatchJobId) -> Result<(), String> {
+        let job = self
+            .store
+            .get_batch_job_unscoped(job_id)
+            .await
+            .map_err(|e| format!("get_batch_job_unscoped: {e:?}"))?;
+
+        let Some(processor) = self.get_processor(&job.job_type) else {
+            return Ok(());
+        };
+        if !processor.has_job_output() || job.status != BatchJobStatusEnum::Processing {
+            return Ok(());
+        }
+
+        let detail = self
+            .store
+            .get_batch_job(job_id, job.tenant_id)
+            .await
+            .map_err(|e| format!("get_batch_job: {e:?}"))?;
+
+        let settled = detail.chunks.iter().all(|c| {
+            !matches!(
+                c.status,
+                BatchJobChunkStatusEnum::Pending | BatchJobChunkStatusEnum::Processing
+            )
+        });
+        let any_completed = detail
+            .chunks
+            .iter()
+            .any(|c| c.status == BatchJobChunkStatusEnum::Completed);
+        if !settled || !any_completed {
+            return Ok(());
+        }
+
+        processor.complete_job(&detail.job, &detail.chunks).await
+    }
+
     /// Build a CSV containing only the failed rows (with original data + _error column),
     /// upload it to S3, and save the document key on the job.
     ///
@@ -509,9 +558,11 @@ impl BatchJobWorker {
             .map_err(|e| format!("get_batch_job_unscoped: {e:?}"))?;
 
         let tenant_id = job.tenant_id;
-        let input_key = job
-            .input_source_key
-            .ok_or("Job has no input_source_key — cannot generate error CSV")?;
+        // Jobs without an input have no rows to hand back;
+        // their failures are listed on the job itself.
+        let Some(input_key) = job.input_source_key else {
+            return Ok(());
+        };
 
         let doc_id = input_key
             .parse()
@@ -1,5 +1,9 @@
 pub mod checkout_session_cleanup;
 pub mod currency_rates_worker;
 pub mod hosted_payment_sweeper;
+pub mod price_change_worker;
 pub mod reconciliation_worker;
+pub mod renewal_upcoming_worker;
+pub mod trial_will_end_worker;
+pub mod usage_threshold_worker;
 pub mod vat_revalidation_worker;
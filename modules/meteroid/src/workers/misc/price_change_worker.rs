@@ -0,0 +1,36 @@
+use crate::errors;
+use error_stack::{Report, ResultExt};
+use meteroid_store::Services;
+use std::sync::Arc;
+use std::time::Duration;
+
+const APPLY_INTERVAL_SECS: u64 = 3600; // 1 hour
+
+pub async fn run_price_change_worker(services: Arc<Services>) {
+    loop {
+        // Simple jitter for easy concurrency
+        let jitter_duration = Duration::from_secs(rand::random::<u64>() % 60);
+
+        match apply_due_price_changes(&services).await {
+            Ok(applied) => {
+                if applied > 0 {
+                    log::info!("Price change worker: applied {} price changes", applied);
+                }
+            }
+            Err(err) => {
+                log::error!("Price change worker encountered error: {err:?}");
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(APPLY_INTERVAL_SECS) + jitter_duration).await;
+    }
+}
+
+async fn apply_due_price_changes(
+    services: &Arc<Services>,
+) -> Result<usize, Report<errors::WorkerError>> {
+    services
+        .apply_due_price_changes()
+        .await
+        .change_context(errors::WorkerError::PriceChangeError)
+}
@@ -0,0 +1,36 @@
+use crate::errors;
+use error_stack::{Report, ResultExt};
+use meteroid_store::Services;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CHECK_INTERVAL_SECS: u64 = 3600; // 1 hour
+
+pub async fn run_renewal_upcoming_worker(services: Arc<Services>) {
+    loop {
+        // Simple jitter for easy concurrency
+        let jitter_duration = Duration::from_secs(rand::random::<u64>() % 60);
+
+        match process_renewal_upcoming_notifications(&services).await {
+            Ok(notified) => {
+                if notified > 0 {
+                    log::info!("Renewal upcoming worker: notified {} renewals", notified);
+                }
+            }
+            Err(err) => {
+                log::error!("Renewal upcoming worker encountered error: {err:?}");
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS) + jitter_duration).await;
+    }
+}
+
+async fn process_renewal_upcoming_notifications(
+    services: &Arc<Services>,
+) -> Result<usize, Report<errors::WorkerError>> {
+    services
+        .process_renewal_upcoming_notifications()
+        .await
+        .change_context(errors::WorkerError::RenewalUpcomingError)
+}
@@ -0,0 +1,36 @@
+use crate::errors;
+use error_stack::{Report, ResultExt};
+use meteroid_store::Services;
+use std::sync::Arc;
+use std::time::Duration;
+
+const CHECK_INTERVAL_SECS: u64 = 900; // 15 minutes
+
+pub async fn run_usage_threshold_worker(services: Arc<Services>) {
+    loop {
+        // Simple jitter for easy concurrency
+        let jitter_duration = Duration::from_secs(rand::random::<u64>() % 60);
+
+        match process_usage_thresholds(&services).await {
+            Ok(issued) => {
+                if issued > 0 {
+                    log::info!("Usage threshold worker: issued {} invoices", issued);
+                }
+            }
+            Err(err) => {
+                log::error!("Usage threshold worker encountered error: {err:?}");
+            }
+        }
+
+        tokio::time::sleep(Duration::from_secs(CHECK_INTERVAL_SECS) + jitter_duration).await;
+    }
+}
+
+async fn process_usage_thresholds(
+    services: &Arc<Services>,
+) -> Result<usize, Report<errors::WorkerError>> {
+    services
+        .process_usage_thresholds()
+        .await
+        .change_context(errors::WorkerError::UsageThresholdError)
+}
@@ -191,6 +191,34 @@ pub async fn spawn_workers(
         });
     }
 
+    {
+        let services = services.clone();
+        join_set.spawn(async move {
+            misc::price_change_worker::run_price_change_worker(services).await;
+        });
+    }
+
+    {
+        let services = services.clone();
+        join_set.spawn(async move {
+            misc::usage_threshold_worker::run_usage_threshold_worker(services).await;
+        });
+    }
+
+    {
+        let services = services.clone();
+        join_set.spawn(async move {
+            misc::trial_will_end_worker::run_trial_will_end_worker(services).await;
+        });
+    }
+
+    {
+        let services = services.clone();
+        join_set.spawn(async move {
+            misc::renewal_upcoming_worker::run_renewal_upcoming_worker(services).await;
+        });
+    }
+
     // Batch job worker
     {
         let store = store.clone();
@@ -199,8 +227,10 @@ pub async fn spawn_workers(
         let usage_client = services.usage_clients();
         let idempotency = idempotency.clone();
         join_set.spawn(async move {
-            let mut worker =
-                batch_jobs::worker::BatchJobWorker::new(store.clone(), object_store_service);
+            let mut worker = batch_jobs::worker::BatchJobWorker::new(
+                store.clone(),
+                object_store_service.clone(),
+            );
 
             worker.register_processor(
                 BatchJobTypeEnum::EventCsvImport,
@@ -208,7 +238,16 @@ pub async fn spawn_workers(
             );
             worker.register_processor(
                 BatchJobTypeEnum::CustomerCsvImport,
-                Arc::new(batch_jobs::processors::CustomerCsvProcessor::new(store)),
+                Arc::new(batch_jobs::processors::CustomerCsvProcessor::new(
+                    store.clone(),
+                )),
+            );
+            worker.register_processor(
+                BatchJobTypeEnum::InvoiceExport,
+                Arc::new(batch_jobs::processors::InvoiceExportProcessor::new(
+                    store,
+                    object_store_service,
+                )),
             );
             worker.register_processor(
                 BatchJobTypeEnum::SubscriptionCsvImport,
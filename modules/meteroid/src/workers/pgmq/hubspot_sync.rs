@@ -25,6 +25,7 @@ use meteroid_store::domain::pgmq::{
 use meteroid_store::repositories::connectors::ConnectorsInterface;
 use meteroid_store::repositories::oauth::OauthInterface;
 use meteroid_store::repositories::{CustomersInterface, SubscriptionInterface};
+use meteroid_store::utils::custom_fields::format_custom_fields;
 use meteroid_store::{Store, StoreResult};
 use moka::Expiry;
 use moka::future::Cache;
@@ -37,6 +38,8 @@ pub(crate) struct HubspotSync {
     pub(crate) store: Arc<Store>,
     pub(crate) client: Arc<HubspotClient>,
     pub(crate) token_cache: Cache<ConnectorId, OAuthTokens>,
+    /// Connectors on which the properties added after connecting have been created.
+    pub(crate) late_properties_cache: Cache<ConnectorId, ()>,
 }
 
 impl HubspotSync {
@@ -50,6 +53,7 @@ impl HubspotSync {
             store,
             client,
             token_cache,
+            late_properties_cache: Cache::builder().max_capacity(500).build(),
         }
     }
 
@@ -152,6 +156,14 @@ impl HubspotSync {
 
         let conn = conn.connector;
 
+        let has_outbox = !customer_domains_to_sync.is_empty()
+            || !subscriptions_to_sync.is_empty()
+            || !customer_outbox_to_sync.is_empty()
+            || !subscription_outbox_to_sync.is_empty();
+        if has_outbox {
+            self.ensure_late_properties(&conn).await;
+        }
+
         let succeeded_props = self
             .sync_custom_props(&conn, custom_props_to_sync)
             .await
@@ -202,6 +214,26 @@ impl HubspotSync {
         Ok(props)
     }
 
+    /// Properties added after a connector was connected are missing from its HubSpot account
+    /// until it is reconnected, so they are created before its first companies and deals sync.
+    async fn ensure_late_properties(&self, conn: &HubspotConnector) {
+        if self.late_properties_cache.contains_key(&conn.id) {
+            return;
+        }
+
+        match self
+            .client
+            .create_invoice_custom_fields_properties(&conn.access_token)
+            .await
+        {
+            Ok(()) => self.late_properties_cache.insert(conn.id, ()).await,
+            Err(e) => log::warn!(
+                "Failed to create invoice custom fields properties for connector {}: {e:?}",
+                conn.id
+            ),
+        }
+    }
+
     async fn sync_customer_domains(
         &self,
         conn: &HubspotConnector,
@@ -324,6 +356,7 @@ impl HubspotSync {
                     state: a.state,
                     zip_code: a.zip_code,
                 }),
+                invoice_custom_fields: format_custom_fields(&ce.invoice_custom_fields),
             })
             .collect_vec();
 
@@ -419,6 +452,9 @@ impl HubspotSync {
                             subscription_currency: event.currency.clone(),
                             subscription_mrr_cents: event.mrr_cents,
                             subscription_status: event.status.as_screaming_snake_case(),
+                            invoice_custom_fields: format_custom_fields(
+                                &event.invoice_custom_fields,
+                            ),
                         },
                         ext_id,
                         *msg_id,
@@ -17,6 +17,7 @@ use meteroid_store::domain::pgmq::{
 use meteroid_store::domain::{Address, ConnectorProviderEnum, DetailedInvoice};
 use meteroid_store::repositories::connectors::ConnectorsInterface;
 use meteroid_store::repositories::{CustomersInterface, InvoiceInterface};
+use meteroid_store::utils::custom_fields::format_custom_fields;
 use meteroid_store::{Store, StoreResult};
 use moka::Expiry;
 use moka::future::Cache;
@@ -453,6 +454,9 @@ impl PennylaneSync {
             .as_ref()
             .and_then(|x| x.country.as_ref());
 
+        // Pennylane has no custom fields on imports: they go to the line descriptions instead
+        let custom_fields = invoice.invoice.custom_fields.clone();
+
         let to_sync = NewCustomerInvoiceImport {
             file_attachment_id: created.id,
             customer_id: pennylane_cus_id,
@@ -493,6 +497,10 @@ impl PennylaneSync {
                         ),
                     };
 
+                    let mut fields = custom_fields.clone();
+                    fields.extend(x.metadata);
+                    let description = format_custom_fields(&fields);
+
                     CustomerInvoiceLine {
                         currency_amount: total_amount.to_string(),
                         currency_tax: tax_amount.to_string(),
@@ -501,7 +509,7 @@ impl PennylaneSync {
                         raw_currency_unit_price: raw_unit_price.to_string(),
                         unit: String::new(),
                         vat_rate,
-                        description: None,
+                        description,
                         imputation_dates: Some(CustomerInvoiceLineImputationDates {
                             start_date: x.start_date,
                             end_date: x.end_date,
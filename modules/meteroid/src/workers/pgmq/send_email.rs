@@ -60,6 +60,9 @@ impl EmailSender {
             SendEmailRequest::PaymentReminder { tenant_id, .. } => Ok(*tenant_id),
             SendEmailRequest::PaymentRejected { tenant_id, .. } => Ok(*tenant_id),
             SendEmailRequest::QuoteReady { tenant_id, .. } => Ok(*tenant_id),
+            SendEmailRequest::TrialWillEnd { tenant_id, .. } => Ok(*tenant_id),
+            SendEmailRequest::RenewalUpcoming { tenant_id, .. } => Ok(*tenant_id),
+            SendEmailRequest::InvoiceApprovalRequested { tenant_id, .. } => Ok(*tenant_id),
         }
     }
 
@@ -106,6 +109,7 @@ impl EmailSender {
                 amount_due,
                 currency,
                 company_name,
+                lang,
                 logo_attachment_id,
                 invoicing_emails,
                 invoice_pdf_id,
@@ -173,6 +177,7 @@ impl EmailSender {
                         currency,
                         payment_url,
                         company_name,
+                        lang: lang.unwrap_or_default(),
                         logo_url,
                         recipients,
                         attachment,
@@ -206,6 +211,7 @@ impl EmailSender {
                 amount_paid,
                 currency,
                 company_name,
+                lang,
                 logo_attachment_id,
                 invoicing_emails,
                 invoice_pdf_id,
@@ -285,6 +291,7 @@ impl EmailSender {
                         amount_paid,
                         currency,
                         company_name,
+                        lang: lang.unwrap_or_default(),
                         logo_url,
                         recipients,
                         attachments,
@@ -314,6 +321,7 @@ impl EmailSender {
                 quote_number,
                 expires_at,
                 company_name,
+                lang,
                 logo_attachment_id,
                 recipient_emails,
                 portal_url,
@@ -344,6 +352,7 @@ impl EmailSender {
                         quote_number,
                         expires_at,
                         company_name,
+                        lang: lang.unwrap_or_default(),
                         logo_url,
                         recipients,
                         portal_url,
@@ -357,6 +366,170 @@ impl EmailSender {
                 Ok(None)
             }
 
+            SendEmailRequest::TrialWillEnd {
+                tenant_id,
+                subscription_id,
+                customer_id,
+                invoicing_entity_id,
+                plan_name,
+                trial_end_date,
+                company_name,
+                lang,
+                logo_attachment_id,
+                recipient_emails,
+            } => {
+                if recipient_emails.is_empty() {
+                    log::warn!("No recipient emails found for subscription {subscription_id}");
+                    return Ok(None);
+                }
+
+                let recipients = recipient_emails
+                    .into_iter()
+                    .map(|email| EmailRecipient {
+                        email,
+                        first_name: None,
+                        last_name: None,
+                    })
+                    .collect_vec();
+
+                let logo_url = logo_attachment_id.map(|logo_attachment_id| {
+                    format!("{}/files/v1/logo/{}", self.rest_api_url, logo_attachment_id)
+                });
+
+                let rendered = self
+                    .mailer
+                    .send_trial_will_end(meteroid_mailer::model::TrialWillEnd {
+                        plan_name,
+                        trial_end_date,
+                        company_name,
+                        lang: lang.unwrap_or_default(),
+                        logo_url,
+                        recipients,
+                        account: invoicing_entity_id.as_base62(),
+                    })
+                    .await
+                    .change_context(StoreError::MailServiceError)?;
+
+                if !rendered.delivered {
+                    return Ok(None);
+                }
+                Ok(Some(rendered_into_sent(
+                    rendered,
+                    tenant_id,
+                    EntityType::Subscription,
+                    subscription_id.as_uuid(),
+                    "trial_will_end",
+                    Some(customer_id),
+                    Some(subscription_id),
+                    vec![],
+                )))
+            }
+
+            SendEmailRequest::RenewalUpcoming {
+                tenant_id,
+                subscription_id,
+                customer_id,
+                invoicing_entity_id,
+                plan_name,
+                renewal_date,
+                opt_out_deadline,
+                company_name,
+                lang,
+                logo_attachment_id,
+                recipient_emails,
+            } => {
+                if recipient_emails.is_empty() {
+                    log::warn!("No recipient emails found for subscription {subscription_id}");
+                    return Ok(None);
+                }
+
+                let recipients = recipient_emails
+                    .into_iter()
+                    .map(|email| EmailRecipient {
+                        email,
+                        first_name: None,
+                        last_name: None,
+                    })
+                    .collect_vec();
+
+                let logo_url = logo_attachment_id.map(|logo_attachment_id| {
+                    format!("{}/files/v1/logo/{}", self.rest_api_url, logo_attachment_id)
+                });
+
+                let rendered = self
+                    .mailer
+                    .send_renewal_upcoming(meteroid_mailer::model::RenewalUpcoming {
+                        plan_name,
+                        renewal_date,
+                        opt_out_deadline,
+                        company_name,
+                        lang: lang.unwrap_or_default(),
+                        logo_url,
+                        recipients,
+                        account: invoicing_entity_id.as_base62(),
+                    })
+                    .await
+                    .change_context(StoreError::MailServiceError)?;
+
+                if !rendered.delivered {
+                    return Ok(None);
+                }
+                Ok(Some(rendered_into_sent(
+                    rendered,
+                    tenant_id,
+                    EntityType::Subscription,
+                    subscription_id.as_uuid(),
+                    "renewal_upcoming",
+                    Some(customer_id),
+                    Some(subscription_id),
+                    vec![],
+                )))
+            }
+
+            SendEmailRequest::InvoiceApprovalRequested {
+                invoice_id,
+                tenant_name,
+                invoice_number,
+                customer_name,
+                total,
+                currency,
+                invoice_url,
+                recipient_emails,
+                ..
+            } => {
+                if recipient_emails.is_empty() {
+                    log::warn!("No approvers to notify for invoice {invoice_id}");
+                    return Ok(None);
+                }
+
+                let recipients = recipient_emails
+                    .into_iter()
+                    .map(|email| EmailRecipient {
+                        email,
+                        first_name: None,
+                        last_name: None,
+                    })
+                    .collect_vec();
+
+                self.mailer
+                    .send_invoice_approval_requested(
+                        meteroid_mailer::model::InvoiceApprovalRequested {
+                            tenant_name,
+                            invoice_number,
+                            customer_name,
+                            total,
+                            currency,
+                            invoice_url,
+                            recipients,
+                        },
+                    )
+                    .await
+                    .change_context(StoreError::MailServiceError)?;
+
+                // Internal notice: the approval request itself is the audit entry.
+                Ok(None)
+            }
+
             _ => Ok(None),
         }
     }
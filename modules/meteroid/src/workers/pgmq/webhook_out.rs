@@ -218,6 +218,170 @@ impl WebhookOut {
                 };
                 Some(event.try_into())
             }
+            OutboxEvent::SubscriptionPaused(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionPaused,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionResumed(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionResumed,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionReactivated(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionReactivated,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionTrialWillEnd(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    trial_end_date: Some(event.trial_end_date),
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionTrialWillEnd,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionTrialEnded(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionTrialEnded,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionCancellationScheduled(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    effective_date: Some(event.effective_date),
+                    cancellation_reason: event.reason,
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionCancellationScheduled,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionCancelled(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionCancelled,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionPlanChanged(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    effective_date: Some(event.change_date),
+                    previous_plan_version_id: Some(event.previous_plan_version_id),
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionPlanChanged,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionAmended(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    effective_date: Some(event.effective_date),
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionAmended,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionSuspended(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionSuspended,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionStatusChanged(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    previous_status: Some(event.previous_status.into()),
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionStatusChanged,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionRenewed(event) => {
+                let data = WebhookOutSubscriptionEventData::from(*event);
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionRenewed,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionRenewalUpcoming(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    renewal_date: Some(event.renewal_date),
+                    opt_out_deadline: event.opt_out_deadline,
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionRenewalUpcoming,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
+            OutboxEvent::SubscriptionTermRenewed(event) => {
+                let data = WebhookOutSubscriptionEventData {
+                    previous_end_date: Some(event.previous_end_date),
+                    ..WebhookOutSubscriptionEventData::from(event.subscription)
+                };
+                let event = WebhookOutSubscriptionEvent {
+                    id: event_id,
+                    event_type: WebhookOutEventTypeEnum::SubscriptionTermRenewed,
+                    data,
+                    timestamp,
+                };
+                Some(event.try_into())
+            }
             OutboxEvent::QuoteAccepted(event) => {
                 let data = WebhookOutQuoteEventData::from(*event);
                 let event = WebhookOutQuoteEvent {
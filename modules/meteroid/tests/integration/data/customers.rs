@@ -35,6 +35,10 @@ pub async fn run_customers_seed(pool: &PgPool) {
             vat_number_format_valid: false,
             connected_account_id: None,
             vat_number_validation_status: None,
+            custom_attributes: serde_json::json!({}),
+            invoice_language: None,
+            invoice_custom_fields: serde_json::json!({}),
+            invoice_approval_required: false,
         }
         .insert(tx)
         .await?;
@@ -60,6 +64,10 @@ pub async fn run_customers_seed(pool: &PgPool) {
             vat_number_format_valid: false,
             connected_account_id: None,
             vat_number_validation_status: None,
+            custom_attributes: serde_json::json!({}),
+            invoice_language: None,
+            invoice_custom_fields: serde_json::json!({}),
+            invoice_approval_required: false,
         }
         .insert(tx)
         .await?;
@@ -85,6 +93,10 @@ pub async fn run_customers_seed(pool: &PgPool) {
             vat_number_format_valid: false,
             connected_account_id: None,
             vat_number_validation_status: None,
+            custom_attributes: serde_json::json!({}),
+            invoice_language: None,
+            invoice_custom_fields: serde_json::json!({}),
+            invoice_approval_required: false,
         }
         .insert(tx)
         .await?;
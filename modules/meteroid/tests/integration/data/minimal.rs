@@ -106,6 +106,13 @@ pub async fn run_minimal_seed(pool: &PgPool) {
             require_billing_information: false,
             portal_theme_mode: None,
             portal_roundness: None,
+            invoice_number_reset: diesel_models::enums::NumberingResetEnum::Never,
+            credit_note_number_pattern: "CN-{number:6}".to_string(),
+            credit_note_number_reset: diesel_models::enums::NumberingResetEnum::Never,
+            invoice_translations: serde_json::json!({}),
+            invoice_template: None,
+            invoice_approval_threshold: None,
+            invoice_approver_ids: vec![],
         }.insert(tx).await?;
 
         HistoricalRatesFromUsdRowNew::insert_batch(tx, vec![
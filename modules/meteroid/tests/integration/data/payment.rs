@@ -449,6 +449,10 @@ pub async fn set_customer_default_payment_method(
         vat_number_validation_status: None,
         vat_number_checked_at: None,
         vat_number_vies_check: None,
+        custom_attributes: None,
+        invoice_language: None,
+        invoice_custom_fields: None,
+        invoice_approval_required: None,
     }
     .update(&mut conn, ids::TENANT_ID)
     .await
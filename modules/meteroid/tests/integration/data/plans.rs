@@ -583,6 +583,8 @@ impl PlanSeed {
                 plan_version_id: version_id,
                 product_id: Some(comp.product_id),
                 billable_metric_id: comp.billable_metric_id,
+                sub_line_presentation: serde_json::json!({"mode": "detailed"}),
+                line_metadata: serde_json::json!({}),
             }
             .insert(tx)
             .await?;
@@ -596,6 +598,7 @@ impl PlanSeed {
                     pricing: serde_json::to_value(&price.pricing).unwrap(),
                     tenant_id: ids::TENANT_ID,
                     catalog: true,
+                    variants: serde_json::json!([]),
                 }
                 .insert(tx)
                 .await?;
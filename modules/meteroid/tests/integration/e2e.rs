@@ -401,9 +401,12 @@ async fn test_metering_e2e() {
                                     },
                                 ),
                             ),
+                            variants: vec![],
                         },
                     )),
                 }],
+                sub_line_presentation: None,
+                line_metadata: Default::default(),
             },
         ))
         .await
@@ -458,6 +461,8 @@ async fn test_metering_e2e() {
                         charge_automatically: None,
                         payment_methods_config: None,
                         skip_past_invoices: None,
+                        past_period_billing: None,
+                        instance: None,
                         entitlements: vec![],
                     },
                 )
@@ -509,6 +514,7 @@ async fn test_metering_e2e() {
                 vat_number: None,
                 alias: None,
                 snapshot_at: period_2_start.naive_utc(),
+                invoice_language: None,
             },
             seller_details: InlineInvoicingEntity {
                 id: InvoicingEntityId::new(),
@@ -531,6 +537,7 @@ async fn test_metering_e2e() {
             invoicing_entity_id: INVOICING_ENTITY_ID,
             parent_invoice_id: None,
             consolidated_into_invoice_id: None,
+            custom_fields: Default::default(),
         })
         .await
         .unwrap();
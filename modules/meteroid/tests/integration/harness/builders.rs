@@ -3,12 +3,13 @@
 use chrono::NaiveDate;
 use common_domain::actor::Actor;
 use common_domain::ids::{CouponId, CustomerId, PlanVersionId, SubscriptionId};
-use meteroid_store::Services;
 use meteroid_store::domain::subscriptions::PaymentMethodsConfig;
 use meteroid_store::domain::{
-    CreateSubscription, CreateSubscriptionCoupon, CreateSubscriptionCoupons,
-    SubscriptionActivationCondition, SubscriptionNew,
+    CreateSubscription, CreateSubscriptionCoupon, CreateSubscriptionCoupons, PastPeriodBilling,
+    SubscriptionActivationCondition, SubscriptionInstance, SubscriptionNew,
 };
+use meteroid_store::{Services, StoreResult};
+use rust_decimal::Decimal;
 
 use crate::data::ids::{CUST_UBER_ID, PLAN_VERSION_1_LEETCODE_ID, TENANT_ID, USER_ID};
 
@@ -23,9 +24,11 @@ pub struct SubscriptionBuilder {
     charge_automatically: bool,
     coupon_ids: Vec<CouponId>,
     payment_methods_config: Option<PaymentMethodsConfig>,
-    skip_past_invoices: bool,
+    past_period_billing: PastPeriodBilling,
     end_date: Option<NaiveDate>,
     net_terms: Option<u32>,
+    invoice_threshold: Option<Decimal>,
+    instance: Option<SubscriptionInstance>,
 }
 
 impl Default for SubscriptionBuilder {
@@ -39,9 +42,11 @@ impl Default for SubscriptionBuilder {
             charge_automatically: false,
             coupon_ids: vec![],
             payment_methods_config: None,
-            skip_past_invoices: false,
+            past_period_billing: PastPeriodBilling::Invoice,
             end_date: None,
             net_terms: None,
+            invoice_threshold: None,
+            instance: None,
         }
     }
 }
@@ -140,9 +145,15 @@ impl SubscriptionBuilder {
         self.payment_methods_config(PaymentMethodsConfig::external())
     }
 
-    /// Enable skip_past_invoices (migration mode).
+    /// Skip past periods (migration mode).
     pub fn skip_past_invoices(mut self) -> Self {
-        self.skip_past_invoices = true;
+        self.past_period_billing = PastPeriodBilling::Skip;
+        self
+    }
+
+    /// Import past periods as already-paid invoices (migration mode).
+    pub fn import_past_invoices_as_paid(mut self) -> Self {
+        self.past_period_billing = PastPeriodBilling::ImportAsPaid;
         self
     }
 
@@ -159,8 +170,41 @@ impl SubscriptionBuilder {
         self
     }
 
+    /// Set the usage amount (in currency units) that triggers an early usage threshold invoice.
+    pub fn invoice_threshold(mut self, threshold: Decimal) -> Self {
+        self.invoice_threshold = Some(threshold);
+        self
+    }
+
+    /// Subscribe as the given instance of the plan, with the default usage property.
+    pub fn instance(mut self, id: &str) -> Self {
+        self.instance = Some(SubscriptionInstance {
+            id: id.to_string(),
+            usage_property: None,
+            metadata: Default::default(),
+        });
+        self
+    }
+
+    /// Add a metadata entry to the instance set with [`Self::instance`].
+    pub fn instance_metadata(mut self, key: &str, value: &str) -> Self {
+        self.instance
+            .as_mut()
+            .expect("instance must be set before its metadata")
+            .metadata
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+
     /// Create the subscription using the provided services.
     pub async fn create(self, services: &Services) -> SubscriptionId {
+        self.try_create(services)
+            .await
+            .expect("Failed to create subscription")
+    }
+
+    /// Create the subscription, returning the error if it is rejected.
+    pub async fn try_create(self, services: &Services) -> StoreResult<SubscriptionId> {
         let coupons = if self.coupon_ids.is_empty() {
             None
         } else {
@@ -182,7 +226,7 @@ impl SubscriptionBuilder {
                         plan_version_id: self.plan_version_id,
                         net_terms: self.net_terms,
                         invoice_memo: None,
-                        invoice_threshold: None,
+                        invoice_threshold: self.invoice_threshold,
                         start_date: self.start_date,
                         end_date: self.end_date,
                         billing_start_date: None,
@@ -195,7 +239,8 @@ impl SubscriptionBuilder {
                         purchase_order: None,
                         backdate_invoices: false,
                         skip_checkout_session: false,
-                        skip_past_invoices: self.skip_past_invoices,
+                        past_period_billing: self.past_period_billing,
+                        instance: self.instance,
                     },
                     price_components: None,
                     add_ons: None,
@@ -205,8 +250,7 @@ impl SubscriptionBuilder {
                 TENANT_ID,
             )
             .await
-            .expect("Failed to create subscription")
-            .id
+            .map(|created| created.id)
     }
 }
 
@@ -10,7 +10,9 @@ mod env;
 mod invoices;
 mod payments;
 mod subscriptions;
+mod usage;
 
 pub use assertions::*;
 pub use builders::*;
 pub use env::*;
+pub use usage::*;
@@ -0,0 +1,102 @@
+//! Usage client returning distinct usage per customer.
+
+use std::collections::HashMap;
+
+use common_domain::ids::{CustomerId, TenantId};
+use meteroid_store::StoreResult;
+use meteroid_store::clients::usage::{
+    EventSearchOptions, EventSearchResult, IngestEventsRequest, IngestEventsResult,
+    MockUsageClient, UsageClient, UsageData, WindowedUsageData,
+};
+use meteroid_store::domain::{BillableMetric, UsageFilter, UsagePeriod};
+use rust_decimal::Decimal;
+
+/// Delegates to the [`MockUsageClient`] of the requested customer, customers without one
+/// have no usage. Used where several customers are billed together, like hierarchies.
+pub struct CustomerUsageClient {
+    pub customers: HashMap<CustomerId, MockUsageClient>,
+    noop: MockUsageClient,
+}
+
+impl CustomerUsageClient {
+    pub fn new(customers: HashMap<CustomerId, MockUsageClient>) -> Self {
+        Self {
+            customers,
+            noop: MockUsageClient::noop(),
+        }
+    }
+
+    fn client(&self, customer_id: &CustomerId) -> &MockUsageClient {
+        self.customers.get(customer_id).unwrap_or(&self.noop)
+    }
+}
+
+#[async_trait::async_trait]
+impl UsageClient for CustomerUsageClient {
+    async fn fetch_usage(
+        &self,
+        tenant_id: &TenantId,
+        customer_id: &CustomerId,
+        metric: &BillableMetric,
+        period: UsagePeriod,
+        filter: Option<&UsageFilter>,
+    ) -> StoreResult<UsageData> {
+        self.client(customer_id)
+            .fetch_usage(tenant_id, customer_id, metric, period, filter)
+            .await
+    }
+
+    async fn fetch_total_usage(
+        &self,
+        tenant_id: &TenantId,
+        customer_id: &CustomerId,
+        metric: &BillableMetric,
+        period: UsagePeriod,
+    ) -> StoreResult<Decimal> {
+        self.client(customer_id)
+            .fetch_total_usage(tenant_id, customer_id, metric, period)
+            .await
+    }
+
+    async fn fetch_windowed_usage(
+        &self,
+        tenant_id: &TenantId,
+        customer_id: &CustomerId,
+        metric: &BillableMetric,
+        period: UsagePeriod,
+        filter: Option<&UsageFilter>,
+    ) -> StoreResult<WindowedUsageData> {
+        self.client(customer_id)
+            .fetch_windowed_usage(tenant_id, customer_id, metric, period, filter)
+            .await
+    }
+
+    async fn fetch_usage_summary(
+        &self,
+        tenant_id: &TenantId,
+        customer_id: Option<&CustomerId>,
+        metric: &BillableMetric,
+        period: UsagePeriod,
+    ) -> StoreResult<UsageData> {
+        let client = customer_id.map_or(&self.noop, |id| self.client(id));
+        client
+            .fetch_usage_summary(tenant_id, customer_id, metric, period)
+            .await
+    }
+
+    async fn search_events(
+        &self,
+        tenant_id: &TenantId,
+        options: EventSearchOptions,
+    ) -> StoreResult<EventSearchResult> {
+        self.noop.search_events(tenant_id, options).await
+    }
+
+    async fn ingest_events(
+        &self,
+        tenant_id: &TenantId,
+        request: IngestEventsRequest,
+    ) -> StoreResult<IngestEventsResult> {
+        self.noop.ingest_events(tenant_id, request).await
+    }
+}
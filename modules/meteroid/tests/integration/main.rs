@@ -11,6 +11,7 @@ mod test_auth_api_key;
 mod test_auth_jwt;
 mod test_basic;
 mod test_billable_metric;
+mod test_catalog;
 mod test_coupon;
 mod test_customer;
 mod test_idempotency;
@@ -34,5 +35,7 @@ mod test_webhook;
 
 mod data;
 mod test_entitlements;
+mod test_invoice_approval;
 mod test_invoice_computation;
 mod test_metering_ingestion;
+mod test_oneoff_invoice;
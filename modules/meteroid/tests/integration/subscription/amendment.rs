@@ -56,6 +56,7 @@ async fn create_rate_addon(
                 pricing: Pricing::Rate {
                     rate: Decimal::new(rate_cents, 2),
                 },
+                variants: vec![],
             }),
             None,
             true,
@@ -126,6 +127,7 @@ fn extra_rate_component(name: &str, rate_cents: i64) -> AddExtraComponent {
             pricing: Pricing::Rate {
                 rate: Decimal::new(rate_cents, 2),
             },
+            variants: vec![],
         }),
     }
 }
@@ -148,6 +150,7 @@ fn extra_arrears_component(name: &str, rate_cents: i64) -> AddExtraComponent {
                 unit_price: Decimal::new(rate_cents, 2),
                 quantity: 1,
             },
+            variants: vec![],
         }),
     }
 }
@@ -169,6 +172,7 @@ fn extra_onetime_component(name: &str, rate_cents: i64) -> AddExtraComponent {
                 unit_price: Decimal::new(rate_cents, 2),
                 quantity: 1,
             },
+            variants: vec![],
         }),
     }
 }
@@ -633,6 +637,7 @@ async fn test_apply_amendment_add_addon_immediate(#[future] test_env: TestEnv) {
                 pricing: Pricing::Rate {
                     rate: Decimal::new(2000, 2),
                 },
+                variants: vec![],
             }),
             None,
             true,
@@ -724,6 +729,7 @@ async fn test_apply_amendment_add_multi_instance_addon_shows_count(#[future] tes
                 pricing: Pricing::Rate {
                     rate: Decimal::new(2000, 2),
                 },
+                variants: vec![],
             }),
             None,
             true,
@@ -1268,6 +1274,7 @@ async fn test_remove_arrears_addon_final_segment_billed() {
                 pricing: Pricing::Usage(UsagePricingModel::PerUnit {
                     rate: Decimal::new(10, 2),
                 }),
+                variants: vec![],
             }),
             None,
             true,
@@ -1513,6 +1520,7 @@ async fn test_amendment_edit_component_price(#[future] test_env: TestEnv) {
                             pricing: Pricing::Rate {
                                 rate: Decimal::new(9900, 2),
                             },
+                            variants: vec![],
                         }),
                     }],
                     added: vec![],
@@ -1684,6 +1692,7 @@ async fn test_amendment_edit_usage_component_temporal_split() {
                             pricing: Pricing::Usage(UsagePricingModel::PerUnit {
                                 rate: Decimal::new(20, 2),
                             }),
+                            variants: vec![],
                         }),
                     }],
                     added: vec![],
@@ -0,0 +1,109 @@
+//! Billing anchor change tests.
+//!
+//! Tests for:
+//! - Moving the billing day earlier, crediting the days cut off the current period
+//! - Rejecting a billing day that is unchanged or already passed in the current period
+
+use chrono::{Datelike, Days, Months};
+use common_domain::actor::Actor;
+use rstest::rstest;
+
+use crate::data::ids::*;
+use crate::harness::{SubscriptionAssertExt, TestEnv, subscription, test_env};
+
+/// The current period is cut short at the new billing day and the unused days are
+/// credited on an adjustment invoice.
+#[rstest]
+#[tokio::test]
+async fn test_change_billing_anchor_shortens_period_with_credit(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let period_end = today.checked_add_months(Months::new(1)).unwrap();
+    let new_period_end = today.checked_add_days(Days::new(10)).unwrap();
+    let new_anchor = new_period_end.day() as u16;
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID) // $35/month
+        .start_date(today)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let invoice_count = env.get_invoices(sub_id).await.len();
+
+    let preview = env
+        .services()
+        .preview_billing_anchor_change(sub_id, TENANT_ID, new_anchor)
+        .await
+        .expect("preview_billing_anchor_change failed");
+
+    let days_in_period = (period_end - today).num_days();
+    let days_cut = (period_end - new_period_end).num_days();
+    let expected_credit = -((3500 * days_cut) as f64 / days_in_period as f64).round() as i64;
+
+    assert_eq!(preview.new_period_end, new_period_end);
+    assert_eq!(preview.proration.net_amount_cents, expected_credit);
+    assert_eq!(preview.proration.days_remaining as i64, days_cut);
+
+    let result = env
+        .services()
+        .change_billing_anchor(Actor::System, sub_id, TENANT_ID, new_anchor)
+        .await
+        .expect("change_billing_anchor failed");
+
+    assert_eq!(result.new_period_end, new_period_end);
+    assert!(result.adjustment_invoice_id.is_some());
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_period_start(today)
+        .has_period_end(new_period_end);
+    assert_eq!(sub.billing_day_anchor, new_anchor as i16);
+
+    let invoices = env.get_invoices(sub_id).await;
+    assert_eq!(invoices.len(), invoice_count + 1);
+    let adjustment = invoices
+        .iter()
+        .find(|i| Some(i.id) == result.adjustment_invoice_id)
+        .expect("adjustment invoice not found");
+    assert!(adjustment.subtotal < 0, "the cut off days are credited");
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_change_billing_anchor_rejects_invalid_days(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let start_date = today.checked_sub_days(Days::new(20)).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let unchanged = env
+        .services()
+        .change_billing_anchor(Actor::System, sub_id, TENANT_ID, start_date.day() as u16)
+        .await;
+    assert!(unchanged.is_err(), "the billing day is unchanged");
+
+    let passed_day = today.checked_sub_days(Days::new(5)).unwrap().day() as u16;
+    let passed = env
+        .services()
+        .change_billing_anchor(Actor::System, sub_id, TENANT_ID, passed_day)
+        .await;
+    assert!(
+        passed.is_err(),
+        "the new billing day already passed in the current period"
+    );
+
+    let sub = env.get_subscription(sub_id).await;
+    assert_eq!(sub.billing_day_anchor, start_date.day() as i16);
+}
@@ -376,7 +376,8 @@ async fn test_plan_restricted_coupon_rejected(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -588,7 +589,8 @@ async fn test_expired_coupon_rejected(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -650,7 +652,8 @@ async fn test_disabled_coupon_rejected(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -707,7 +710,8 @@ async fn test_archived_coupon_rejected(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -777,7 +781,8 @@ async fn test_non_reusable_coupon_same_customer_rejected(#[future] test_env: Tes
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -947,7 +952,8 @@ async fn test_redemption_limit_reached(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -1011,7 +1017,8 @@ async fn test_currency_mismatch_rejected(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -1209,7 +1216,8 @@ async fn test_multiple_coupons_one_fails_plan_restriction(#[future] test_env: Te
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -0,0 +1,385 @@
+//! Customer hierarchy tests.
+//!
+//! Tests for:
+//! - Validation of parent assignments (self, nested hierarchies)
+//! - Subscriptions of a child customer invoiced to its parent
+//! - Detaching a child from its parent
+//! - Archiving a parent with children
+//! - Children charged on the payment method of their parent
+//! - Usage pooled across the hierarchy for tiering
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{NaiveDate, NaiveTime};
+use common_domain::actor::Actor;
+use common_domain::ids::*;
+use diesel_models::enums::BillingPeriodEnum as DieselBillingPeriodEnum;
+use rstest::rstest;
+use rust_decimal::Decimal;
+use uuid::uuid;
+
+use crate::data::ids::*;
+use crate::data::plans::{PlanSeed, SeedComp};
+use crate::harness::{
+    CustomerUsageClient, InvoicesAssertExt, TestEnv, subscription, test_env,
+    test_env_with_seed_and_usage,
+};
+use crate::meteroid_it::container::SeedLevel;
+use meteroid_store::clients::usage::{
+    GroupedUsageData, MockUsageClient, MockUsageDataParams, UsageData,
+};
+use meteroid_store::domain::{
+    ResolvedPaymentMethod, TierGroup, TierRow, UsagePeriod, UsagePricingModel,
+};
+use meteroid_store::repositories::CustomersInterface;
+use meteroid_store::repositories::customer_payment_methods::CustomerPaymentMethodsInterface;
+
+/// A customer cannot be its own parent, nor be nested under a child.
+#[rstest]
+#[tokio::test]
+async fn test_set_customer_parent_rejects_invalid_hierarchies(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let own_parent = env
+        .store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_UBER_ID,
+            Some(CUST_UBER_ID),
+            false,
+        )
+        .await;
+    assert!(own_parent.is_err(), "a customer cannot be its own parent");
+
+    let child = env
+        .store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_UBER_ID,
+            Some(CUST_SPOTIFY_ID),
+            true,
+        )
+        .await
+        .expect("failed to set parent");
+    assert_eq!(child.parent_customer_id, Some(CUST_SPOTIFY_ID));
+    assert!(child.pool_usage_with_parent);
+
+    // Hierarchies are one level deep
+    let nested = env
+        .store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_COMODO_ID,
+            Some(CUST_UBER_ID),
+            false,
+        )
+        .await;
+    assert!(nested.is_err(), "a child cannot be a parent");
+
+    let parent_of_parent = env
+        .store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_SPOTIFY_ID,
+            Some(CUST_COMODO_ID),
+            false,
+        )
+        .await;
+    assert!(
+        parent_of_parent.is_err(),
+        "a customer with children cannot have a parent"
+    );
+
+    let detached = env
+        .store()
+        .set_customer_parent(Actor::System, TENANT_ID, CUST_UBER_ID, None, true)
+        .await
+        .expect("failed to detach");
+    assert_eq!(detached.parent_customer_id, None);
+    assert!(
+        !detached.pool_usage_with_parent,
+        "pooling is meaningless without a parent"
+    );
+}
+
+/// The subscription stays on the child, while its invoices are addressed to the parent.
+#[rstest]
+#[tokio::test]
+async fn test_child_subscription_is_invoiced_to_parent(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    env.store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_UBER_ID,
+            Some(CUST_SPOTIFY_ID),
+            false,
+        )
+        .await
+        .expect("failed to set parent");
+
+    let sub_id = subscription()
+        .customer(CUST_UBER_ID)
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let subscription = env.get_subscription(sub_id).await;
+    assert_eq!(subscription.customer_id, CUST_UBER_ID);
+
+    let invoices = env.get_invoices(sub_id).await;
+    assert_eq!(invoices.len(), 1);
+    assert_eq!(invoices[0].customer_id, CUST_SPOTIFY_ID);
+    assert_eq!(invoices[0].customer_details.id, CUST_SPOTIFY_ID);
+
+    // Renewals too
+    env.process_cycles().await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    assert_eq!(invoices.len(), 2);
+    assert!(
+        invoices
+            .iter()
+            .all(|invoice| invoice.customer_id == CUST_SPOTIFY_ID)
+    );
+}
+
+/// A parent cannot be archived while it has children, they must be detached first.
+#[rstest]
+#[tokio::test]
+async fn test_archive_parent_with_children_fails(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    env.store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_UBER_ID,
+            Some(CUST_SPOTIFY_ID),
+            false,
+        )
+        .await
+        .expect("failed to set parent");
+
+    let archived = env
+        .store()
+        .archive_customer(Actor::System, TENANT_ID, CUST_SPOTIFY_ID.into())
+        .await;
+    assert!(
+        archived.is_err(),
+        "a parent with children cannot be archived"
+    );
+
+    env.store()
+        .set_customer_parent(Actor::System, TENANT_ID, CUST_UBER_ID, None, false)
+        .await
+        .expect("failed to detach");
+
+    env.store()
+        .archive_customer(Actor::System, TENANT_ID, CUST_SPOTIFY_ID.into())
+        .await
+        .expect("failed to archive the former parent");
+
+    // An archived customer cannot become a parent either
+    let attached = env
+        .store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_UBER_ID,
+            Some(CUST_SPOTIFY_ID),
+            false,
+        )
+        .await;
+    assert!(attached.is_err(), "the parent is archived");
+}
+
+/// A child without payment method of its own is charged on the one of its parent.
+#[rstest]
+#[tokio::test]
+async fn test_child_is_charged_with_parent_payment_method(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    env.seed_payments().await;
+
+    env.store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_COMODO_ID,
+            Some(CUST_SPOTIFY_ID),
+            false,
+        )
+        .await
+        .expect("failed to set parent");
+
+    let sub_id = subscription()
+        .customer(CUST_COMODO_ID)
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let resolved = env
+        .store()
+        .resolve_payment_method_for_subscription(TENANT_ID, sub_id)
+        .await
+        .expect("failed to resolve payment method");
+    assert_eq!(
+        resolved,
+        ResolvedPaymentMethod::CustomerPaymentMethod(CUST_SPOTIFY_PAYMENT_METHOD_ID)
+    );
+
+    // Once detached, the former child has nothing to be charged on
+    env.store()
+        .set_customer_parent(Actor::System, TENANT_ID, CUST_COMODO_ID, None, false)
+        .await
+        .expect("failed to detach");
+
+    let resolved = env
+        .store()
+        .resolve_payment_method_for_subscription(TENANT_ID, sub_id)
+        .await
+        .expect("failed to resolve payment method");
+    assert_eq!(resolved, ResolvedPaymentMethod::NotConfigured);
+}
+
+const PLAN_POOLED_ID: PlanId = PlanId::from_const(uuid!("019438e0-0300-7000-8000-000000000001"));
+const PLAN_VERSION_POOLED_ID: PlanVersionId =
+    PlanVersionId::from_const(uuid!("019438e0-0301-7000-8000-000000000001"));
+const COMP_POOLED_RATE_ID: PriceComponentId =
+    PriceComponentId::from_const(uuid!("019438e0-0302-7000-8000-000000000001"));
+const COMP_POOLED_BANDWIDTH_ID: PriceComponentId =
+    PriceComponentId::from_const(uuid!("019438e0-0303-7000-8000-000000000001"));
+const PRICE_POOLED_RATE_ID: PriceId =
+    PriceId::from_const(uuid!("019438e0-0304-7000-8000-000000000001"));
+const PRICE_POOLED_BANDWIDTH_ID: PriceId =
+    PriceId::from_const(uuid!("019438e0-0305-7000-8000-000000000001"));
+
+/// Rate EUR 20/mo + Bandwidth graduated on the tier group: EUR 0.10/unit up to 1000 units,
+/// EUR 0.05/unit above.
+fn pooled_plan() -> PlanSeed {
+    PlanSeed::new(PLAN_POOLED_ID, "Pooled Plan", PLAN_VERSION_POOLED_ID).components(vec![
+        SeedComp::rate(
+            COMP_POOLED_RATE_ID,
+            "Platform Fee",
+            PRODUCT_PLATFORM_FEE_ID,
+            PRICE_POOLED_RATE_ID,
+            DieselBillingPeriodEnum::Monthly,
+            Decimal::new(2000, 2),
+        ),
+        SeedComp::usage(
+            COMP_POOLED_BANDWIDTH_ID,
+            "Bandwidth",
+            PRODUCT_BANDWIDTH_ID,
+            METRIC_BANDWIDTH,
+            PRICE_POOLED_BANDWIDTH_ID,
+            DieselBillingPeriodEnum::Monthly,
+            UsagePricingModel::Tiered {
+                tiers: vec![
+                    TierRow {
+                        first_unit: 0,
+                        rate: Decimal::new(10, 2),
+                        flat_fee: None,
+                        flat_cap: None,
+                    },
+                    TierRow {
+                        first_unit: 1000,
+                        rate: Decimal::new(5, 2),
+                        flat_fee: None,
+                        flat_cap: None,
+                    },
+                ],
+                block_size: None,
+                tier_group: Some(TierGroup {
+                    key: "bandwidth".to_string(),
+                    units_per_quantity: Decimal::ONE,
+                }),
+            },
+        ),
+    ])
+}
+
+fn bandwidth_usage(units: i64, start: NaiveDate, end: NaiveDate) -> MockUsageClient {
+    let period = UsagePeriod {
+        start: start.and_time(NaiveTime::MIN),
+        end: end.and_time(NaiveTime::MIN),
+    };
+    let mut data = HashMap::new();
+    data.insert(
+        MockUsageDataParams {
+            metric_id: METRIC_BANDWIDTH,
+            period_start: period.start,
+            period_end: period.end,
+        },
+        UsageData {
+            data: vec![GroupedUsageData {
+                value: Decimal::from(units),
+                dimensions: HashMap::new(),
+            }],
+            period,
+        },
+    );
+    MockUsageClient { data }
+}
+
+/// A child pooling its usage is tiered on the usage of the whole hierarchy, and billed its
+/// share of the pooled charge. Without pooling, it is tiered on its own usage.
+#[rstest]
+#[case::pooled(true, 2000 + 5500)] // (1000 × 0.10 + 200 × 0.05) × 600 / 1200
+#[case::not_pooled(false, 2000 + 6000)] // 600 × 0.10
+#[tokio::test]
+async fn test_child_usage_is_pooled_with_parent(#[case] pool: bool, #[case] expected: i64) {
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let period_end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+    let usage_client = CustomerUsageClient::new(HashMap::from([
+        (CUST_UBER_ID, bandwidth_usage(600, start_date, period_end)),
+        (
+            CUST_SPOTIFY_ID,
+            bandwidth_usage(600, start_date, period_end),
+        ),
+    ]));
+    let env = test_env_with_seed_and_usage(SeedLevel::PLANS, Arc::new(usage_client)).await;
+
+    let mut conn = env.conn().await;
+    pooled_plan().seed(&mut conn).await.unwrap();
+    drop(conn);
+
+    env.store()
+        .set_customer_parent(
+            Actor::System,
+            TENANT_ID,
+            CUST_UBER_ID,
+            Some(CUST_SPOTIFY_ID),
+            pool,
+        )
+        .await
+        .expect("failed to set parent");
+
+    let sub_id = subscription()
+        .customer(CUST_UBER_ID)
+        .plan_version(PLAN_VERSION_POOLED_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    // Renewal bills the rate and the usage of period 0
+    env.process_cycles().await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(2);
+    invoices.assert().invoice_at(1).has_total(expected);
+}
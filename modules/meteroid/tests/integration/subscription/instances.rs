@@ -0,0 +1,277 @@
+//! Plan instance tests.
+//!
+//! Tests for:
+//! - Several subscriptions of a customer to the same plan, each billing its own usage
+//! - Subscriptions without an instance leaving out the usage tagged with one
+//! - Line metadata coming from the price component, overridden by the instance metadata
+//! - Uniqueness of instance identifiers among the customer's live subscriptions, ended ones
+//!   releasing theirs
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+
+use chrono::{Days, NaiveDate, NaiveTime};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use rstest::rstest;
+use rust_decimal::Decimal;
+
+use common_domain::actor::Actor;
+use diesel_models::enums::SubscriptionStatusEnum;
+
+use crate::data::ids::*;
+use crate::harness::{
+    InvoicesAssertExt, SubscriptionAssertExt, TestEnv, subscription, test_env,
+    test_env_with_seed_and_usage,
+};
+use crate::meteroid_it::container::SeedLevel;
+use meteroid_store::clients::usage::{
+    GroupedUsageData, MockUsageClient, MockUsageDataParams, UsageData,
+};
+use meteroid_store::domain::{LineItem, UsagePeriod};
+use meteroid_store::errors::StoreError;
+use meteroid_store::repositories::subscriptions::CancellationEffectiveAt;
+
+/// Bandwidth usage of the customer for the period, split across its two projects, plus
+/// usage reported without a project.
+fn build_usage_client(start: NaiveDate, end: NaiveDate) -> MockUsageClient {
+    let usage = |instance: &str, units: i64| GroupedUsageData {
+        value: Decimal::from(units),
+        dimensions: HashMap::from([("instance_id".to_string(), instance.to_string())]),
+    };
+
+    let mut data = HashMap::new();
+    data.insert(
+        MockUsageDataParams {
+            metric_id: METRIC_BANDWIDTH,
+            period_start: start.and_time(NaiveTime::MIN),
+            period_end: end.and_time(NaiveTime::MIN),
+        },
+        UsageData {
+            data: vec![
+                usage("project-a", 100),
+                usage("project-b", 300),
+                GroupedUsageData {
+                    value: Decimal::from(50),
+                    dimensions: HashMap::new(),
+                },
+            ],
+            period: UsagePeriod {
+                start: start.and_time(NaiveTime::MIN),
+                end: end.and_time(NaiveTime::MIN),
+            },
+        },
+    );
+    MockUsageClient { data }
+}
+
+/// Two instances of the usage plan only bill the usage reported for their own identifier,
+/// and their lines carry it.
+///
+/// Plan: Rate EUR 20/mo (advance) + Bandwidth EUR 0.10/unit (arrear)
+#[tokio::test]
+async fn test_instances_bill_their_own_usage() {
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let period1_end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+    let usage_client = build_usage_client(start_date, period1_end);
+    let env = test_env_with_seed_and_usage(SeedLevel::PLANS, Arc::new(usage_client)).await;
+
+    let project_a = subscription()
+        .plan_version(PLAN_VERSION_USAGE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .instance("project-a")
+        .create(env.services())
+        .await;
+    let project_b = subscription()
+        .plan_version(PLAN_VERSION_USAGE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .instance("project-b")
+        .create(env.services())
+        .await;
+    let untagged = subscription()
+        .plan_version(PLAN_VERSION_USAGE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    // Renewal bills the rate and the usage of period 0
+    env.process_cycles().await;
+
+    let rate_cents = 2000i64;
+
+    let invoices_a = env.get_invoices(project_a).await;
+    invoices_a.assert().has_count(2);
+    invoices_a
+        .assert()
+        .invoice_at(1)
+        .has_total(rate_cents + 1000); // 100 units × 10 cents
+
+    let invoices_b = env.get_invoices(project_b).await;
+    invoices_b.assert().has_count(2);
+    invoices_b
+        .assert()
+        .invoice_at(1)
+        .has_total(rate_cents + 3000); // 300 units × 10 cents
+
+    // Only the usage reported without a project
+    let invoices_untagged = env.get_invoices(untagged).await;
+    invoices_untagged.assert().has_count(2);
+    invoices_untagged
+        .assert()
+        .invoice_at(1)
+        .has_total(rate_cents + 500); // 50 units × 10 cents
+
+    for line in &invoices_a[1].line_items {
+        assert_eq!(
+            line.description.as_deref(),
+            Some("project-a"),
+            "line {} should carry the instance identifier",
+            line.name
+        );
+    }
+}
+
+/// Lines carry the metadata of their price component, overridden key by key by the one of the
+/// plan instance.
+#[tokio::test]
+async fn test_line_metadata_from_component_and_instance() {
+    use diesel_models::schema::price_component::dsl as pc;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let period1_end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+    let usage_client = build_usage_client(start_date, period1_end);
+    let env = test_env_with_seed_and_usage(SeedLevel::PLANS, Arc::new(usage_client)).await;
+
+    let mut conn = env.conn().await;
+    diesel::update(pc::price_component.filter(pc::id.eq(COMP_USAGE_BANDWIDTH_ID)))
+        .set(pc::line_metadata.eq(serde_json::json!({
+            "cost_center": "CC-1",
+            "contract_id": "C-0",
+        })))
+        .execute(&mut conn)
+        .await
+        .expect("Failed to set the component line metadata");
+
+    let project_a = subscription()
+        .plan_version(PLAN_VERSION_USAGE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .instance("project-a")
+        .instance_metadata("contract_id", "C-a")
+        .create(env.services())
+        .await;
+    let untagged = subscription()
+        .plan_version(PLAN_VERSION_USAGE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.process_cycles().await;
+
+    let metadata = |entries: &[(&str, &str)]| -> BTreeMap<String, String> {
+        entries
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    };
+    let line_metadata = |lines: &[LineItem], component| {
+        lines
+            .iter()
+            .find(|line| line.price_component_id == Some(component))
+            .map(|line| line.metadata.clone())
+            .expect("missing component line")
+    };
+
+    let invoices_a = env.get_invoices(project_a).await;
+    invoices_a.assert().has_count(2);
+    assert_eq!(
+        line_metadata(&invoices_a[1].line_items, COMP_USAGE_BANDWIDTH_ID),
+        metadata(&[("contract_id", "C-a"), ("cost_center", "CC-1")])
+    );
+    assert_eq!(
+        line_metadata(&invoices_a[1].line_items, COMP_USAGE_RATE_ID),
+        metadata(&[("contract_id", "C-a")])
+    );
+
+    let invoices_untagged = env.get_invoices(untagged).await;
+    invoices_untagged.assert().has_count(2);
+    assert_eq!(
+        line_metadata(&invoices_untagged[1].line_items, COMP_USAGE_BANDWIDTH_ID),
+        metadata(&[("contract_id", "C-0"), ("cost_center", "CC-1")])
+    );
+    assert!(line_metadata(&invoices_untagged[1].line_items, COMP_USAGE_RATE_ID).is_empty());
+}
+
+/// An instance identifier is used by a single live subscription of the customer.
+#[rstest]
+#[tokio::test]
+async fn test_instance_id_is_unique_per_customer(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+
+    let first = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(today - Days::new(20))
+        .on_start()
+        .no_trial()
+        .instance("project-a")
+        .create(env.services())
+        .await;
+
+    let duplicate = subscription()
+        .instance("project-a")
+        .try_create(env.services())
+        .await;
+    assert!(
+        matches!(
+            duplicate.as_ref().map_err(|e| e.current_context()),
+            Err(StoreError::DuplicateValue { .. })
+        ),
+        "a second live subscription cannot reuse the instance identifier"
+    );
+
+    // Other customers and other identifiers are not affected
+    subscription()
+        .customer(CUST_SPOTIFY_ID)
+        .instance("project-a")
+        .create(env.services())
+        .await;
+    subscription()
+        .instance("project-b")
+        .create(env.services())
+        .await;
+
+    // Ended subscriptions release their identifier
+    env.services()
+        .cancel_subscription(
+            Actor::System,
+            first,
+            TENANT_ID,
+            None,
+            CancellationEffectiveAt::Date(today - Days::new(5)),
+        )
+        .await
+        .expect("cancel_subscription failed");
+    env.process_cycles().await;
+    env.get_subscription(first)
+        .await
+        .assert()
+        .has_status(SubscriptionStatusEnum::Cancelled);
+
+    subscription()
+        .instance("project-a")
+        .create(env.services())
+        .await;
+}
@@ -1011,3 +1011,105 @@ async fn test_lifecycle_end_date_billing_terminates(#[future] test_env: TestEnv)
     let invoices = env.get_invoices(sub_id).await;
     invoices.assert().has_count(2);
 }
+
+// =============================================================================
+// LIFECYCLE EVENTS
+// =============================================================================
+
+/// Renewals and cancellations reach the activity feed through their outbox events.
+/// Transitions that have their own event get no extra `status_changed` row.
+#[rstest]
+#[tokio::test]
+async fn test_lifecycle_events_recorded_in_activity_feed(#[future] test_env: TestEnv) {
+    use chrono::Days;
+    use common_domain::actor::Actor;
+    use common_domain::ids::BaseId;
+    use diesel_models::entity_activity::EntityActivityRow;
+    use diesel_models::enums::ActorTypeEnum;
+    use meteroid_store::repositories::subscriptions::CancellationEffectiveAt;
+
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let start_date = today - Days::new(40);
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    // The first period is over: renews
+    env.process_cycles().await;
+    env.get_subscription(sub_id)
+        .await
+        .assert()
+        .is_active()
+        .has_cycle_index(1);
+
+    env.services()
+        .cancel_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            Some("Too expensive".to_string()),
+            CancellationEffectiveAt::Date(today - Days::new(5)),
+        )
+        .await
+        .expect("cancel_subscription failed");
+
+    env.process_cycles().await;
+    env.get_subscription(sub_id)
+        .await
+        .assert()
+        .has_status(SubscriptionStatusEnum::Cancelled);
+
+    let mut conn = env.conn().await;
+    let activities = EntityActivityRow::list_by_entity(
+        &mut conn,
+        TENANT_ID,
+        "subscription",
+        sub_id.as_uuid(),
+        None,
+        50,
+    )
+    .await
+    .expect("list activities");
+    let types = activities
+        .iter()
+        .map(|a| a.activity_type.as_str())
+        .collect::<Vec<_>>();
+
+    for expected in [
+        "subscription.renewed",
+        "subscription.cancellation_scheduled",
+        "subscription.cancelled",
+    ] {
+        assert_eq!(
+            types.iter().filter(|t| **t == expected).count(),
+            1,
+            "expected one '{expected}' activity, got {types:?}"
+        );
+    }
+    assert!(
+        !types.contains(&"subscription.status_changed"),
+        "cancellation has its own activity, got {types:?}"
+    );
+
+    let scheduled = activities
+        .iter()
+        .find(|a| a.activity_type == "subscription.cancellation_scheduled")
+        .unwrap();
+    assert_eq!(
+        scheduled.metadata.as_ref().and_then(|m| m.get("reason")),
+        Some(&serde_json::json!("Too expensive"))
+    );
+
+    let cancelled = activities
+        .iter()
+        .find(|a| a.activity_type == "subscription.cancelled")
+        .unwrap();
+    assert_eq!(cancelled.actor_type, ActorTypeEnum::System);
+}
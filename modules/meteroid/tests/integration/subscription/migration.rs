@@ -1,19 +1,24 @@
-//! Subscription migration tests (skip_past_invoices).
+//! Subscription migration tests (past periods skipped or imported as paid).
 //!
 //! Tests the migration mode for creating subscriptions with a past start_date
-//! without generating historical invoices. Verifies:
+//! without billing historical periods. Verifies:
 //! - Correct subscription state (Active, correct cycle_index, period boundaries)
 //! - No invoices created at import time
 //! - imported_at is set
 //! - Renewal works normally after migration
 //! - Validation rejects invalid combinations
+//! - Imported past invoices and the MRR history
 
 use chrono::{Datelike, NaiveDate};
 use rstest::rstest;
 
 use crate::data::ids::*;
-use crate::harness::{InvoicesAssertExt, SubscriptionAssertExt, TestEnv, subscription, test_env};
-use diesel_models::enums::{CycleActionEnum, SubscriptionStatusEnum};
+use crate::harness::{
+    InvoiceAssertExt, InvoicesAssertExt, SubscriptionAssertExt, TestEnv, subscription, test_env,
+};
+use diesel_models::enums::{CycleActionEnum, SubscriptionEventType, SubscriptionStatusEnum};
+use diesel_models::subscription_events::SubscriptionEventRow;
+use meteroid_store::domain::enums::InvoiceType;
 
 // =============================================================================
 // BASIC MIGRATION TESTS
@@ -266,7 +271,8 @@ async fn test_migration_rejects_future_start_date(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: true,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Skip,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -313,7 +319,8 @@ async fn test_migration_rejects_manual_activation(#[future] test_env: TestEnv) {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: true,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Skip,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -360,7 +367,8 @@ async fn test_migration_rejects_on_checkout_activation(#[future] test_env: TestE
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: true,
+                    past_period_billing: meteroid_store::domain::PastPeriodBilling::Skip,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -738,3 +746,103 @@ async fn test_migration_end_date_before_today(#[future] test_env: TestEnv) {
     let invoices = env.get_invoices(sub_id).await;
     invoices.assert().assert_empty();
 }
+
+// =============================================================================
+// IMPORT AS PAID
+// =============================================================================
+
+/// Migration importing past periods: every elapsed period, the current one included, gets an
+/// imported invoice that is finalized and already paid, and the MRR is recorded.
+#[rstest]
+#[tokio::test]
+async fn test_migration_import_as_paid_records_past_invoices(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let billing_day = if today.day() == 15 { 10 } else { 15 };
+    let start_date = NaiveDate::from_ymd_opt(today.year() - 1, today.month(), billing_day).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID) // $35/month
+        .on_start()
+        .no_trial()
+        .start_date(start_date)
+        .import_past_invoices_as_paid()
+        .create(env.services())
+        .await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert().is_active().is_imported().has_mrr(3500);
+    let imported_count = sub.cycle_index.unwrap() as usize + 1;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(imported_count);
+    for invoice in &invoices {
+        assert_eq!(invoice.invoice_type, InvoiceType::Imported);
+        invoice
+            .assert()
+            .is_finalized_paid()
+            .has_total(3500)
+            .has_amount_due(0);
+    }
+    assert_eq!(
+        invoices.iter().map(|i| i.invoice_date).min(),
+        Some(start_date)
+    );
+    assert_eq!(
+        invoices.iter().map(|i| i.invoice_date).max(),
+        Some(sub.current_period_start)
+    );
+
+    // The current period was imported, the cycle worker does not bill it again
+    env.process_cycles().await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(imported_count);
+    env.get_subscription(sub_id).await.assert().has_mrr(3500);
+}
+
+/// Migration skipping past periods: the subscription creation, dated at the original
+/// start_date, is logged to the MRR history with the first invoice issued after the import.
+#[rstest]
+#[tokio::test]
+async fn test_migration_skip_logs_mrr_on_first_invoice(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    // Renewal boundary: the first invoice is issued today
+    let start_date = NaiveDate::from_ymd_opt(today.year() - 1, today.month(), today.day()).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID) // $35/month
+        .on_start()
+        .no_trial()
+        .start_date(start_date)
+        .skip_past_invoices()
+        .create(env.services())
+        .await;
+
+    env.get_subscription(sub_id).await.assert().has_mrr(0);
+
+    env.process_cycles().await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(1);
+    assert_eq!(invoices[0].invoice_type, InvoiceType::Recurring);
+    env.get_subscription(sub_id).await.assert().has_mrr(3500);
+
+    let mut conn = env.conn().await;
+    let created_event = SubscriptionEventRow::fetch_by_subscription_id_and_event_type(
+        &mut conn,
+        sub_id,
+        SubscriptionEventType::Created,
+        start_date,
+    )
+    .await
+    .unwrap()
+    .expect("Created event should exist");
+    assert!(
+        created_event.bi_mrr_movement_log_id.is_some(),
+        "Created event should be logged to the MRR history"
+    );
+}
@@ -1,17 +1,25 @@
 mod activation;
 mod amendment;
+mod billing_anchor;
 mod checkout;
 mod checkout_session;
 mod consolidation;
 mod coupons;
+mod hierarchy;
 mod hosted_checkout;
 mod hosted_invoice_payment;
+mod instances;
 mod lifecycle;
 mod migration;
+mod pause;
 mod payment_double_charge;
 mod payment_methods_config;
 mod payment_webhook_settlement;
 mod plan_change;
 mod plan_change_checkout;
+mod price_change;
+mod price_variants;
+mod reactivation;
+mod renewal;
 mod trials;
 mod usage;
@@ -0,0 +1,363 @@
+//! Subscription pause and resume tests.
+//!
+//! Tests for:
+//! - Pausing without billing, then resuming on the original anchor (prorated)
+//! - Resuming with a billing anchor reset to the resume date
+//! - Pausing while keeping fixed fees billed
+//! - Scheduled pause at period end with automatic resumption
+
+use chrono::NaiveDate;
+use common_domain::actor::Actor;
+use rstest::rstest;
+
+use crate::data::ids::*;
+use crate::harness::{InvoicesAssertExt, SubscriptionAssertExt, TestEnv, subscription, test_env};
+use diesel_models::enums::{CycleActionEnum, SubscriptionStatusEnum};
+use meteroid_store::domain::{PauseBilling, ResumeBillingAnchor};
+use meteroid_store::repositories::subscriptions::{PauseEffectiveAt, PauseParams};
+
+/// Pause without billing stops the cycles; resuming on the original anchor
+/// bills a prorated period up to the next billing day.
+#[rstest]
+#[tokio::test]
+async fn test_pause_then_resume_keeps_anchor_and_prorates(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let pause_date = NaiveDate::from_ymd_opt(2024, 2, 15).unwrap();
+    let resume_date = NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID) // $35/month
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    // Cycle 1: February
+    env.process_cycles().await;
+
+    env.services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::Date(pause_date),
+            PauseParams::default(),
+        )
+        .await
+        .expect("pause_subscription failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Paused)
+        .has_next_action(None)
+        .has_cycle_index(1);
+
+    let invoice_count = env.get_invoices(sub_id).await.len();
+
+    // No renewal while paused
+    env.process_cycles().await;
+    env.get_invoices(sub_id)
+        .await
+        .assert()
+        .has_count(invoice_count);
+
+    env.services()
+        .resume_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            Some(resume_date),
+            ResumeBillingAnchor::Keep,
+        )
+        .await
+        .expect("resume_subscription failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_next_action(Some(CycleActionEnum::RenewSubscription))
+        .has_cycle_index(2)
+        .has_period_start(resume_date)
+        .has_period_end(NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+    assert_eq!(sub.billing_day_anchor, 1);
+
+    // 16 of 31 days of March
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(invoice_count + 1);
+    invoices
+        .assert()
+        .invoice_at(invoice_count)
+        .has_invoice_date(resume_date)
+        .has_total(1806);
+
+    // Back to full periods on the original anchor
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_cycle_index(3)
+        .has_period_start(NaiveDate::from_ymd_opt(2024, 4, 1).unwrap());
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices
+        .assert()
+        .has_count(invoice_count + 2)
+        .invoice_at(invoice_count + 1)
+        .has_total(3500);
+}
+
+/// Resuming with a reset anchor starts a full period on the resume date.
+#[rstest]
+#[tokio::test]
+async fn test_resume_resets_anchor_to_resume_date(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let resume_date = NaiveDate::from_ymd_opt(2024, 3, 16).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::Date(NaiveDate::from_ymd_opt(2024, 1, 20).unwrap()),
+            PauseParams::default(),
+        )
+        .await
+        .expect("pause_subscription failed");
+
+    let invoice_count = env.get_invoices(sub_id).await.len();
+
+    env.services()
+        .resume_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            Some(resume_date),
+            ResumeBillingAnchor::ResetToResumeDate,
+        )
+        .await
+        .expect("resume_subscription failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_period_start(resume_date)
+        .has_period_end(NaiveDate::from_ymd_opt(2024, 4, 16).unwrap());
+    assert_eq!(sub.billing_day_anchor, 16);
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices
+        .assert()
+        .has_count(invoice_count + 1)
+        .invoice_at(invoice_count)
+        .has_total(3500);
+}
+
+/// Keeping fixed fees billed: the subscription stays paused but keeps renewing.
+#[rstest]
+#[tokio::test]
+async fn test_pause_keeping_fixed_fees_continues_billing(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::Date(NaiveDate::from_ymd_opt(2024, 1, 10).unwrap()),
+            PauseParams {
+                billing: PauseBilling::KeepFixedFees,
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("pause_subscription failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Paused)
+        .has_next_action(Some(CycleActionEnum::RenewSubscription));
+
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Paused)
+        .has_cycle_index(1);
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(2).invoice_at(1).has_total(3500);
+
+    // The cycles never stopped, so the anchor cannot be reset
+    let reset = env
+        .services()
+        .resume_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            None,
+            ResumeBillingAnchor::ResetToResumeDate,
+        )
+        .await;
+    assert!(reset.is_err());
+
+    env.services()
+        .resume_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            None,
+            ResumeBillingAnchor::Keep,
+        )
+        .await
+        .expect("resume_subscription failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert().is_active().has_cycle_index(1);
+    env.get_invoices(sub_id).await.assert().has_count(2);
+}
+
+/// A pause at period end applies right away once that date has passed, and the
+/// automatic resumption fires on its date.
+#[rstest]
+#[tokio::test]
+async fn test_scheduled_pause_with_auto_resume(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let resume_date = NaiveDate::from_ymd_opt(2024, 2, 10).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::EndOfBillingPeriod,
+            PauseParams {
+                resume_on: Some(resume_date),
+                ..Default::default()
+            },
+        )
+        .await
+        .expect("pause_subscription failed");
+
+    // Period end is in the past, so the pause applied right away
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Paused)
+        .has_next_action(None);
+
+    // The resume event is due: the subscription restarts on its date
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_period_start(resume_date)
+        .has_period_end(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap());
+}
+
+/// Only paused subscriptions can be resumed, only active ones paused.
+#[rstest]
+#[tokio::test]
+async fn test_pause_resume_validation(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let resume = env
+        .services()
+        .resume_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            None,
+            ResumeBillingAnchor::Keep,
+        )
+        .await;
+    assert!(resume.is_err(), "an active subscription cannot be resumed");
+
+    let pause_date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+    let invalid_resume = env
+        .services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::Date(pause_date),
+            PauseParams {
+                resume_on: Some(pause_date),
+                ..Default::default()
+            },
+        )
+        .await;
+    assert!(
+        invalid_resume.is_err(),
+        "the resume date must be after the pause date"
+    );
+
+    env.services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::Date(pause_date),
+            PauseParams::default(),
+        )
+        .await
+        .expect("pause_subscription failed");
+
+    let pause_again = env
+        .services()
+        .pause_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            PauseEffectiveAt::Date(pause_date),
+            PauseParams::default(),
+        )
+        .await;
+    assert!(
+        pause_again.is_err(),
+        "a paused subscription cannot be paused"
+    );
+}
@@ -186,6 +186,8 @@ async fn test_config_change_affects_resolved_checkout_options(#[future] test_env
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: Some(Some(PaymentMethodsConfig::external())),
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let updated_sub = env
@@ -232,6 +234,8 @@ async fn test_config_change_affects_resolved_checkout_options(#[future] test_env
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: Some(Some(PaymentMethodsConfig::online_specific(true, false))),
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let updated_sub = env
@@ -407,6 +411,8 @@ async fn test_migrate_external_to_card_enables_checkout(#[future] test_env: Test
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: Some(Some(PaymentMethodsConfig::online_specific(true, false))),
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let updated_sub = env
@@ -639,6 +645,8 @@ async fn test_charge_auto_validation_rejects_external_config(#[future] test_env:
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: None, // Keep External
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let result = env.store().patch_subscription(TENANT_ID, patch).await;
@@ -690,6 +698,8 @@ async fn test_charge_auto_validation_rejects_no_provider(#[future] test_env: Tes
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: None, // Keep default (Online)
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let result = env.store().patch_subscription(TENANT_ID, patch).await;
@@ -747,6 +757,8 @@ async fn test_charge_auto_validation_rejects_config_change_to_external(
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: Some(Some(PaymentMethodsConfig::external())), // Try to change to External
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let result = env.store().patch_subscription(TENANT_ID, patch).await;
@@ -786,6 +798,8 @@ async fn test_charge_auto_validation_accepts_valid_config(#[future] test_env: Te
         invoice_memo: None,
         purchase_order: None,
         payment_methods_config: Some(Some(PaymentMethodsConfig::online())), // Explicit Online
+        invoice_number_prefix: None,
+        invoice_custom_fields: None,
     };
 
     let result = env.store().patch_subscription(TENANT_ID, patch).await;
@@ -0,0 +1,381 @@
+//! Scheduled price change tests.
+//!
+//! Tests for:
+//! - The catalog switching plan components, add-ons and plan version add-ons to the new price
+//! - Existing subscriptions migrating to the new price at their scheduled renewal
+//! - Grandfathered subscriptions staying on the old price
+//! - Subscriptions created after scheduling being picked up when the change applies
+
+use chrono::{Datelike, Days, Months, NaiveDate, Utc};
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use rstest::rstest;
+use rust_decimal::Decimal;
+
+use crate::data::ids::*;
+use crate::harness::{TestEnv, subscription, test_env};
+use common_domain::ids::{PriceChangeId, PriceId, ScheduledEventId, SubscriptionId};
+use diesel_models::plan_component_prices::PlanComponentPriceRow;
+use diesel_models::prices::PriceRow;
+use diesel_models::scheduled_events::ScheduledEventRow;
+use meteroid_store::domain::add_ons::AddOnNew;
+use meteroid_store::domain::enums::PriceChangeStatusEnum;
+use meteroid_store::domain::plan_version_add_ons::PlanVersionAddOnNew;
+use meteroid_store::domain::price_changes::{
+    PriceChange, PriceChangeGrandfathering, PriceChangeNew,
+};
+use meteroid_store::domain::prices::Pricing;
+use meteroid_store::domain::scheduled_events::ScheduledEventData;
+use meteroid_store::repositories::add_ons::AddOnInterface;
+use meteroid_store::repositories::plan_version_add_ons::PlanVersionAddOnInterface;
+
+fn tomorrow() -> NaiveDate {
+    Utc::now().date_naive() + Days::new(1)
+}
+
+/// $35/month LeetCode rate raised to $40.
+fn leetcode_rate_change(grandfathering: PriceChangeGrandfathering) -> PriceChangeNew {
+    PriceChangeNew {
+        price_id: PRICE_LEETCODE_RATE_ID,
+        new_pricing: Pricing::Rate {
+            rate: Decimal::new(4000, 2),
+        },
+        effective_date: tomorrow(),
+        grandfathering,
+    }
+}
+
+async fn schedule(env: &TestEnv, grandfathering: PriceChangeGrandfathering) -> PriceChange {
+    env.services()
+        .schedule_price_change(TENANT_ID, leetcode_rate_change(grandfathering), *USER_ID)
+        .await
+        .expect("schedule_price_change failed")
+}
+
+/// The effective date must be in the future when scheduling; move it to today so the
+/// catalog job picks the change up.
+async fn make_due(env: &TestEnv, id: PriceChangeId) {
+    use diesel_models::schema::price_change::dsl as pc;
+
+    let mut conn = env.conn().await;
+    diesel::update(pc::price_change.filter(pc::id.eq(id)))
+        .set(pc::effective_date.eq(Utc::now().date_naive()))
+        .execute(&mut conn)
+        .await
+        .expect("Failed to backdate price change");
+}
+
+async fn move_event(env: &TestEnv, id: ScheduledEventId, date: NaiveDate) {
+    use diesel_models::schema::scheduled_event::dsl as se;
+
+    let mut conn = env.conn().await;
+    diesel::update(se::scheduled_event.filter(se::id.eq(id)))
+        .set(se::scheduled_time.eq(date.and_time(chrono::NaiveTime::MIN)))
+        .execute(&mut conn)
+        .await
+        .expect("Failed to move scheduled event");
+}
+
+async fn pending_price_change_events(
+    env: &TestEnv,
+    subscription_id: SubscriptionId,
+    id: PriceChangeId,
+) -> Vec<ScheduledEventRow> {
+    let mut conn = env.conn().await;
+    ScheduledEventRow::get_pending_events_for_subscription(&mut conn, subscription_id, &TENANT_ID)
+        .await
+        .expect("failed to list pending scheduled events")
+        .into_iter()
+        .filter(|row| {
+            matches!(
+                serde_json::from_value::<ScheduledEventData>(row.event_data.clone()),
+                Ok(ScheduledEventData::ApplyPriceChange { price_change_id }) if price_change_id == id
+            )
+        })
+        .collect()
+}
+
+async fn active_price_ids(env: &TestEnv, subscription_id: SubscriptionId) -> Vec<Option<PriceId>> {
+    env.get_subscription_components(subscription_id)
+        .await
+        .into_iter()
+        .filter(|c| c.effective_to.is_none())
+        .map(|c| c.price_id)
+        .collect()
+}
+
+/// Applying a change repoints plan components, add-ons and plan version add-ons to the
+/// new price and archives the old one.
+#[rstest]
+#[tokio::test]
+async fn test_price_change_replaces_price_across_catalog(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let add_on = env
+        .store()
+        .create_add_on(
+            meteroid_store::domain::entity_activity::Actor::User { id: USER_ID },
+            AddOnNew {
+                name: "Priority support".to_string(),
+                tenant_id: TENANT_ID,
+                product_id: PRODUCT_PLATFORM_FEE_ID,
+                price_id: PRICE_LEETCODE_RATE_ID,
+                description: None,
+                self_serviceable: false,
+                max_instances_per_subscription: None,
+                entitlements: vec![],
+            },
+        )
+        .await
+        .expect("create_add_on failed");
+
+    env.store()
+        .attach_add_on_to_plan_version(PlanVersionAddOnNew {
+            plan_version_id: PLAN_VERSION_1_LEETCODE_ID,
+            add_on_id: add_on.id,
+            price_id: Some(PRICE_LEETCODE_RATE_ID),
+            self_serviceable: None,
+            max_instances_per_subscription: None,
+            tenant_id: TENANT_ID,
+        })
+        .await
+        .expect("attach_add_on_to_plan_version failed");
+
+    let change = schedule(&env, PriceChangeGrandfathering::AllSubscriptions).await;
+    assert_eq!(change.status, PriceChangeStatusEnum::Scheduled);
+
+    // Nothing is due before the effective date.
+    let applied = env.services().apply_due_price_changes().await.unwrap();
+    assert_eq!(applied, 0);
+
+    make_due(&env, change.id).await;
+    let applied = env.services().apply_due_price_changes().await.unwrap();
+    assert_eq!(applied, 1);
+
+    let mut conn = env.conn().await;
+    let component_prices =
+        PlanComponentPriceRow::list_by_component_ids(&mut conn, &[COMP_LEETCODE_RATE_ID])
+            .await
+            .unwrap();
+    assert_eq!(
+        component_prices
+            .iter()
+            .map(|r| r.price_id)
+            .collect::<Vec<_>>(),
+        vec![change.new_price.id]
+    );
+
+    let add_on = env
+        .store()
+        .get_add_on_by_id(TENANT_ID, add_on.id)
+        .await
+        .unwrap();
+    assert_eq!(add_on.price_id, change.new_price.id);
+
+    let attachments = env
+        .store()
+        .list_plan_version_add_ons(PLAN_VERSION_1_LEETCODE_ID, TENANT_ID)
+        .await
+        .unwrap();
+    assert_eq!(attachments.len(), 1);
+    assert_eq!(attachments[0].price_id, Some(change.new_price.id));
+
+    let old_price =
+        PriceRow::find_by_id_and_tenant_id(&mut conn, PRICE_LEETCODE_RATE_ID, TENANT_ID)
+            .await
+            .unwrap();
+    assert!(
+        old_price.archived_at.is_some(),
+        "old price should be archived"
+    );
+
+    let new_price = PriceRow::find_by_id_and_tenant_id(&mut conn, change.new_price.id, TENANT_ID)
+        .await
+        .unwrap();
+    assert!(new_price.archived_at.is_none());
+
+    let changes = env
+        .services()
+        .list_price_changes(TENANT_ID, PRODUCT_PLATFORM_FEE_ID)
+        .await
+        .unwrap();
+    assert_eq!(changes.len(), 1);
+    assert_eq!(changes[0].status, PriceChangeStatusEnum::Applied);
+
+    // Applied changes are not picked up twice.
+    let applied = env.services().apply_due_price_changes().await.unwrap();
+    assert_eq!(applied, 0);
+}
+
+/// A subscription already on the old price moves to the new one at its scheduled
+/// renewal, keeping the component lineage and updating its MRR.
+#[rstest]
+#[tokio::test]
+async fn test_price_change_migrates_existing_subscription(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let preview = env
+        .services()
+        .preview_price_change(
+            TENANT_ID,
+            &leetcode_rate_change(PriceChangeGrandfathering::AllSubscriptions),
+        )
+        .await
+        .expect("preview_price_change failed");
+    let impact = preview
+        .subscriptions
+        .iter()
+        .find(|s| s.subscription_id == sub_id)
+        .expect("subscription should be in the preview");
+    assert_eq!(impact.current_mrr_cents, 3500);
+    assert_eq!(impact.new_mrr_cents, 4000);
+
+    let change = schedule(&env, PriceChangeGrandfathering::AllSubscriptions).await;
+
+    let events = pending_price_change_events(&env, sub_id, change.id).await;
+    assert_eq!(events.len(), 1, "one migration event for the subscription");
+
+    // Not migrated before the renewal.
+    assert_eq!(
+        active_price_ids(&env, sub_id).await,
+        vec![Some(PRICE_LEETCODE_RATE_ID)]
+    );
+
+    // Scheduled at the first renewal on or after the effective date, the 1st of the month.
+    let effective_date = tomorrow();
+    let first_renewal = if effective_date.day() == 1 {
+        effective_date
+    } else {
+        effective_date.with_day(1).unwrap() + Months::new(1)
+    };
+    assert_eq!(
+        events[0].scheduled_time,
+        first_renewal.and_time(chrono::NaiveTime::MIN)
+    );
+
+    // The subscription lags behind since 2024; bring the event back to its next renewal so
+    // the cycle transition applies it there.
+    let sub = env.get_subscription(sub_id).await;
+    let renewal = sub
+        .current_period_end
+        .expect("active subscription has a period end");
+    move_event(&env, events[0].id, renewal).await;
+    env.process_cycles().await;
+
+    assert!(
+        pending_price_change_events(&env, sub_id, change.id)
+            .await
+            .is_empty()
+    );
+
+    let components = env
+        .get_all_subscription_components(sub_id, sub.start_date, renewal + Months::new(1))
+        .await;
+    let (closed, active): (Vec<_>, Vec<_>) = components
+        .into_iter()
+        .partition(|c| c.effective_to.is_some());
+
+    assert_eq!(closed.len(), 1);
+    assert_eq!(closed[0].price_id, Some(PRICE_LEETCODE_RATE_ID));
+    assert_eq!(closed[0].effective_to, Some(renewal));
+
+    assert_eq!(active.len(), 1);
+    assert_eq!(active[0].price_id, Some(change.new_price.id));
+    assert_eq!(active[0].effective_from, renewal);
+    assert_eq!(active[0].lineage_id, Some(closed[0].id));
+    let sub = env.get_subscription(sub_id).await;
+    assert_eq!(sub.mrr_cents, 4000);
+}
+
+/// Grandfathered subscriptions keep the old price after the catalog switches; new
+/// subscriptions get the new one.
+#[rstest]
+#[tokio::test]
+async fn test_price_change_grandfathers_existing_subscriptions(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let existing = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let change = schedule(&env, PriceChangeGrandfathering::NewSubscriptionsOnly).await;
+    assert!(
+        pending_price_change_events(&env, existing, change.id)
+            .await
+            .is_empty()
+    );
+
+    make_due(&env, change.id).await;
+    assert_eq!(env.services().apply_due_price_changes().await.unwrap(), 1);
+
+    assert!(
+        pending_price_change_events(&env, existing, change.id)
+            .await
+            .is_empty(),
+        "grandfathered subscription is not migrated"
+    );
+    assert_eq!(
+        active_price_ids(&env, existing).await,
+        vec![Some(PRICE_LEETCODE_RATE_ID)]
+    );
+
+    let newcomer = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .customer(CUST_UBER_ID)
+        .start_date(Utc::now().date_naive())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+    assert_eq!(
+        active_price_ids(&env, newcomer).await,
+        vec![Some(change.new_price.id)]
+    );
+}
+
+/// Subscriptions created on the old price between scheduling and the effective date are
+/// scheduled for migration when the catalog switches.
+#[rstest]
+#[tokio::test]
+async fn test_price_change_picks_up_subscriptions_created_after_scheduling(
+    #[future] test_env: TestEnv,
+) {
+    let env = test_env.await;
+
+    let change = schedule(&env, PriceChangeGrandfathering::AllSubscriptions).await;
+
+    let late = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+    assert!(
+        pending_price_change_events(&env, late, change.id)
+            .await
+            .is_empty()
+    );
+
+    make_due(&env, change.id).await;
+    assert_eq!(env.services().apply_due_price_changes().await.unwrap(), 1);
+
+    assert_eq!(
+        pending_price_change_events(&env, late, change.id)
+            .await
+            .len(),
+        1
+    );
+}
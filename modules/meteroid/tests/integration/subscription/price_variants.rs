@@ -0,0 +1,143 @@
+//! Price variant tests.
+//!
+//! Tests for:
+//! - Customers matching a variant being billed the variant pricing, others the base pricing
+//! - Updating the variants of a price replacing it in the catalog, existing subscriptions untouched
+
+use chrono::NaiveDate;
+use diesel::{ExpressionMethods, QueryDsl};
+use diesel_async::RunQueryDsl;
+use rstest::rstest;
+use rust_decimal::Decimal;
+
+use crate::data::ids::*;
+use crate::harness::{TestEnv, subscription, test_env};
+use common_domain::ids::{CustomerId, SubscriptionId};
+use meteroid_store::domain::SubscriptionFee;
+use meteroid_store::domain::prices::{Price, PriceCondition, PriceVariant, Pricing};
+use meteroid_store::repositories::prices::PriceInterface;
+use meteroid_store::repositories::subscriptions::SubscriptionInterfaceAuto;
+
+async fn set_region(env: &TestEnv, customer_id: CustomerId, region: &str) {
+    use diesel_models::schema::customer::dsl as c;
+
+    let mut conn = env.conn().await;
+    diesel::update(c::customer.filter(c::id.eq(customer_id)))
+        .set(c::custom_attributes.eq(serde_json::json!({ "region": region })))
+        .execute(&mut conn)
+        .await
+        .expect("Failed to set customer attributes");
+}
+
+/// $20/month instead of $35 for the LATAM region on the LeetCode rate.
+async fn add_latam_variant(env: &TestEnv) -> Price {
+    env.store()
+        .update_price_variants(
+            PRICE_LEETCODE_RATE_ID,
+            TENANT_ID,
+            vec![PriceVariant {
+                condition: PriceCondition::CustomerAttribute {
+                    key: "region".to_string(),
+                    values: vec!["latam".to_string()],
+                },
+                pricing: Pricing::Rate {
+                    rate: Decimal::new(2000, 2),
+                },
+            }],
+        )
+        .await
+        .expect("update_price_variants failed")
+}
+
+async fn billed_rate(env: &TestEnv, subscription_id: SubscriptionId) -> Decimal {
+    let details = env
+        .store()
+        .get_subscription_details(TENANT_ID, subscription_id)
+        .await
+        .expect("get_subscription_details failed");
+    assert_eq!(details.price_components.len(), 1);
+    match &details.price_components[0].fee {
+        SubscriptionFee::Rate { rate } => *rate,
+        other => panic!("expected a rate fee, got {other:?}"),
+    }
+}
+
+fn rate(cents: i64) -> Decimal {
+    Decimal::new(cents, 2)
+}
+
+async fn create_subscription(env: &TestEnv, customer_id: CustomerId) -> SubscriptionId {
+    subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .customer(customer_id)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_price_variant_billed_to_matching_customers(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    set_region(&env, CUST_UBER_ID, "latam").await;
+    set_region(&env, CUST_SPOTIFY_ID, "emea").await;
+    let price = add_latam_variant(&env).await;
+
+    let latam = create_subscription(&env, CUST_UBER_ID).await;
+    let emea = create_subscription(&env, CUST_SPOTIFY_ID).await;
+
+    assert_eq!(env.get_subscription(latam).await.mrr_cents, 2000);
+    assert_eq!(env.get_subscription(emea).await.mrr_cents, 3500);
+
+    // The variant is resolved into the stored fee, the price id stays the catalog one.
+    assert_eq!(billed_rate(&env, latam).await, rate(2000));
+    assert_eq!(billed_rate(&env, emea).await, rate(3500));
+    for sub_id in [latam, emea] {
+        let components = env.get_subscription_components(sub_id).await;
+        assert_eq!(components.len(), 1);
+        assert_eq!(components[0].price_id, Some(price.id));
+    }
+}
+
+/// Price rows are not mutated: the variants land on a new price replacing the current one in
+/// the catalog, subscriptions on the former price are billed as before.
+#[rstest]
+#[tokio::test]
+async fn test_price_variant_update_keeps_existing_subscriptions(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    set_region(&env, CUST_UBER_ID, "latam").await;
+    let existing = create_subscription(&env, CUST_UBER_ID).await;
+
+    let price = add_latam_variant(&env).await;
+    assert_ne!(price.id, PRICE_LEETCODE_RATE_ID);
+    assert_eq!(price.variants.len(), 1);
+
+    let previous = env
+        .store()
+        .list_prices_by_ids(&[PRICE_LEETCODE_RATE_ID], TENANT_ID)
+        .await
+        .expect("list_prices_by_ids failed");
+    assert!(previous[0].variants.is_empty());
+    assert!(previous[0].archived_at.is_some());
+
+    assert_eq!(env.get_subscription(existing).await.mrr_cents, 3500);
+    assert_eq!(billed_rate(&env, existing).await, rate(3500));
+    let components = env.get_subscription_components(existing).await;
+    assert_eq!(components.len(), 1);
+    assert_eq!(components[0].price_id, Some(PRICE_LEETCODE_RATE_ID));
+
+    let newcomer = create_subscription(&env, CUST_UBER_ID).await;
+    assert_eq!(env.get_subscription(newcomer).await.mrr_cents, 2000);
+    assert_eq!(billed_rate(&env, newcomer).await, rate(2000));
+
+    // Removing the variants again does not reprice the subscriptions billed on one.
+    env.store()
+        .update_price_variants(price.id, TENANT_ID, vec![])
+        .await
+        .expect("update_price_variants failed");
+    assert_eq!(billed_rate(&env, newcomer).await, rate(2000));
+}
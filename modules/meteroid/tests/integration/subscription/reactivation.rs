@@ -0,0 +1,210 @@
+//! Subscription reactivation tests.
+//!
+//! Tests for:
+//! - Reverting a scheduled cancellation before it applies
+//! - Restarting a recently cancelled subscription on its billing anchor
+//! - Rejecting reactivations outside of the reactivation window
+
+use chrono::{Days, NaiveDate};
+use common_domain::actor::Actor;
+use rstest::rstest;
+
+use crate::data::ids::*;
+use crate::harness::{InvoicesAssertExt, SubscriptionAssertExt, TestEnv, subscription, test_env};
+use diesel_models::enums::{SubscriptionEventType, SubscriptionStatusEnum};
+use diesel_models::scheduled_events::ScheduledEventRow;
+use diesel_models::subscription_events::SubscriptionEventRow;
+use meteroid_store::repositories::subscriptions::CancellationEffectiveAt;
+
+/// A scheduled cancellation is dropped, along with its pending churn, and the
+/// subscription keeps renewing.
+#[rstest]
+#[tokio::test]
+async fn test_reactivate_reverts_scheduled_cancellation(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let period_end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .cancel_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            None,
+            CancellationEffectiveAt::EndOfBillingPeriod,
+        )
+        .await
+        .expect("cancel_subscription failed");
+
+    env.services()
+        .reactivate_subscription(Actor::System, sub_id, TENANT_ID)
+        .await
+        .expect("reactivate_subscription failed");
+
+    let mut conn = env.conn().await;
+    let pending =
+        ScheduledEventRow::get_pending_events_for_subscription(&mut conn, sub_id, &TENANT_ID)
+            .await
+            .expect("query pending events");
+    assert!(pending.is_empty(), "the cancellation should be cancelled");
+
+    let churn = SubscriptionEventRow::fetch_by_subscription_id_and_event_type(
+        &mut conn,
+        sub_id,
+        SubscriptionEventType::Cancelled,
+        period_end,
+    )
+    .await
+    .expect("query subscription events");
+    assert!(churn.is_none(), "the pending churn should be removed");
+
+    // Renews instead of terminating
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_cycle_index(1)
+        .has_period_start(period_end);
+    assert_eq!(sub.mrr_cents, 3500, "no churn was recorded");
+
+    // Nothing left to reactivate
+    let again = env
+        .services()
+        .reactivate_subscription(Actor::System, sub_id, TENANT_ID)
+        .await;
+    assert!(again.is_err(), "there is no scheduled cancellation anymore");
+}
+
+/// A recently cancelled subscription restarts today, on its original anchor.
+#[rstest]
+#[tokio::test]
+async fn test_reactivate_restarts_cancelled_subscription(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let start_date = today - Days::new(20);
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .cancel_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            None,
+            CancellationEffectiveAt::Date(today - Days::new(5)),
+        )
+        .await
+        .expect("cancel_subscription failed");
+
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert().has_status(SubscriptionStatusEnum::Cancelled);
+    assert_eq!(sub.mrr_cents, 0, "the churn should be recorded");
+    let anchor = sub.billing_day_anchor;
+    let invoice_count = env.get_invoices(sub_id).await.len();
+
+    env.services()
+        .reactivate_subscription(Actor::System, sub_id, TENANT_ID)
+        .await
+        .expect("reactivate_subscription failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert().is_active().has_period_start(today);
+    assert_eq!(sub.billing_day_anchor, anchor);
+    assert_eq!(sub.mrr_cents, 3500, "the MRR should be restored");
+
+    let mut conn = env.conn().await;
+    let reactivated = SubscriptionEventRow::fetch_by_subscription_id_and_event_type(
+        &mut conn,
+        sub_id,
+        SubscriptionEventType::Reactivated,
+        today,
+    )
+    .await
+    .expect("query subscription events")
+    .expect("missing reactivation event");
+    assert_eq!(reactivated.mrr_delta, Some(3500));
+    assert!(
+        reactivated.bi_mrr_movement_log_id.is_some(),
+        "the reactivation should be logged as an MRR movement"
+    );
+
+    // The restarted period is billed right away
+    env.get_invoices(sub_id)
+        .await
+        .assert()
+        .has_count(invoice_count + 1);
+}
+
+/// Only recently cancelled subscriptions can be reactivated.
+#[rstest]
+#[tokio::test]
+async fn test_reactivate_outside_window_fails(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let start_date = today - Days::new(45);
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    // Active, without any scheduled cancellation
+    let result = env
+        .services()
+        .reactivate_subscription(Actor::System, sub_id, TENANT_ID)
+        .await;
+    assert!(
+        result.is_err(),
+        "an active subscription cannot be reactivated"
+    );
+
+    env.services()
+        .cancel_subscription(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            None,
+            CancellationEffectiveAt::Date(today - Days::new(35)),
+        )
+        .await
+        .expect("cancel_subscription failed");
+
+    env.process_cycles().await;
+
+    env.get_subscription(sub_id)
+        .await
+        .assert()
+        .has_status(SubscriptionStatusEnum::Cancelled);
+
+    let result = env
+        .services()
+        .reactivate_subscription(Actor::System, sub_id, TENANT_ID)
+        .await;
+    assert!(
+        result.is_err(),
+        "cancelled more than 30 days ago, cannot be reactivated"
+    );
+}
@@ -0,0 +1,414 @@
+//! Fixed-term renewal tests.
+//!
+//! Tests for:
+//! - Term renewal at the end date
+//! - Month-to-month renewal and renewal onto another plan version
+//! - Renewal opt-out and its deadline
+//! - Renewal-upcoming notifications
+
+use chrono::{Days, Months, NaiveDate};
+use common_domain::actor::Actor;
+use common_domain::ids::{BaseId, SubscriptionId};
+use rstest::rstest;
+
+use crate::data::ids::*;
+use crate::harness::{InvoicesAssertExt, SubscriptionAssertExt, TestEnv, subscription, test_env};
+use diesel_models::entity_activity::EntityActivityRow;
+use diesel_models::enums::{RenewalPolicyEnum, SubscriptionEventType, SubscriptionStatusEnum};
+use diesel_models::subscription_events::SubscriptionEventRow;
+use meteroid_store::domain;
+
+fn renewal_terms(policy: domain::RenewalPolicyEnum) -> domain::RenewalTerms {
+    domain::RenewalTerms {
+        policy,
+        term_months: None,
+        plan_version_id: None,
+        notice_days: None,
+        opt_out_days: None,
+    }
+}
+
+/// A subscription renewing for the same term keeps going past its end date, which moves
+/// forward by the length of the original term.
+#[rstest]
+#[tokio::test]
+async fn test_same_term_renewal_extends_end_date(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .end_date(end_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let subscription = env
+        .services()
+        .set_renewal_terms(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            renewal_terms(domain::RenewalPolicyEnum::RenewSameTerm),
+        )
+        .await
+        .expect("set_renewal_terms failed");
+    assert_eq!(subscription.renewal_term_months, Some(2));
+
+    // [Feb1, Mar1], then the end of the term
+    env.process_cycles().await;
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Active)
+        .has_cycle_index(2)
+        .has_period_start(end_date);
+    assert_eq!(sub.end_date, NaiveDate::from_ymd_opt(2024, 5, 1));
+
+    let mut conn = env.conn().await;
+    let activities = EntityActivityRow::list_by_entity(
+        &mut conn,
+        TENANT_ID,
+        "subscription",
+        sub_id.as_uuid(),
+        None,
+        50,
+    )
+    .await
+    .expect("list activities");
+    let types = activities
+        .iter()
+        .map(|a| a.activity_type.as_str())
+        .collect::<Vec<_>>();
+    assert!(
+        types.contains(&"subscription.renewal_terms_updated"),
+        "got {types:?}"
+    );
+    assert!(
+        types.contains(&"subscription.term_renewed"),
+        "got {types:?}"
+    );
+}
+
+/// Asserts that the renewal at `date` was recorded, extending the term to `end_date`.
+async fn assert_renewed(
+    env: &TestEnv,
+    sub_id: SubscriptionId,
+    date: NaiveDate,
+    end_date: NaiveDate,
+) {
+    let mut conn = env.conn().await;
+    let event = SubscriptionEventRow::fetch_by_subscription_id_and_event_type(
+        &mut conn,
+        sub_id,
+        SubscriptionEventType::Renewed,
+        date,
+    )
+    .await
+    .expect("query subscription events")
+    .unwrap_or_else(|| panic!("no renewal recorded on {date}"));
+
+    let details = event.details.expect("renewal details");
+    assert_eq!(details["previous_end_date"], date.to_string());
+    assert_eq!(details["end_date"], end_date.to_string());
+    assert_eq!(event.mrr_delta, None);
+}
+
+/// Month-to-month renewals extend the subscription one month at a time.
+#[rstest]
+#[tokio::test]
+async fn test_month_to_month_renewal_extends_by_a_month(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .end_date(end_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let subscription = env
+        .services()
+        .set_renewal_terms(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            renewal_terms(domain::RenewalPolicyEnum::RenewMonthToMonth),
+        )
+        .await
+        .expect("set_renewal_terms failed");
+    assert_eq!(subscription.renewal_term_months, None);
+
+    // [Feb1, Mar1], then the end of the term
+    env.process_cycles().await;
+    env.process_cycles().await;
+
+    let april = NaiveDate::from_ymd_opt(2024, 4, 1).unwrap();
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Active)
+        .has_period_start(end_date);
+    assert_eq!(sub.end_date, Some(april));
+    assert_eq!(sub.renewal_policy, RenewalPolicyEnum::RenewMonthToMonth);
+    assert_renewed(&env, sub_id, end_date, april).await;
+
+    // and again at the end of the renewed month
+    env.process_cycles().await;
+
+    let may = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Active)
+        .has_period_start(april);
+    assert_eq!(sub.end_date, Some(may));
+    assert_renewed(&env, sub_id, april, may).await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(4);
+    invoices.assert().invoice_at(3).has_total(3500);
+}
+
+/// Renewing onto another plan version switches the plan at the end of the term, further
+/// terms renewing on that version.
+#[rstest]
+#[tokio::test]
+async fn test_renewal_to_plan_version_switches_plan(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_STARTER_ID)
+        .start_date(start_date)
+        .end_date(end_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let subscription = env
+        .services()
+        .set_renewal_terms(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            domain::RenewalTerms {
+                plan_version_id: Some(PLAN_VERSION_PRO_ID),
+                ..renewal_terms(domain::RenewalPolicyEnum::RenewToPlanVersion)
+            },
+        )
+        .await
+        .expect("set_renewal_terms failed");
+    assert_eq!(subscription.renewal_term_months, Some(2));
+
+    // [Feb1, Mar1], then the end of the term
+    env.process_cycles().await;
+    env.process_cycles().await;
+
+    let renewed_end_date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .has_status(SubscriptionStatusEnum::Active)
+        .has_period_start(end_date);
+    assert_eq!(sub.plan_version_id, PLAN_VERSION_PRO_ID);
+    assert_eq!(sub.end_date, Some(renewed_end_date));
+    assert_eq!(sub.renewal_policy, RenewalPolicyEnum::RenewSameTerm);
+    assert_eq!(sub.renewal_term_months, Some(2));
+    assert_eq!(sub.renewal_plan_version_id, None);
+    assert_renewed(&env, sub_id, end_date, renewed_end_date).await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(3);
+    invoices
+        .assert()
+        .invoice_at(1)
+        .with_context("last Starter invoice")
+        .has_total(3900);
+    invoices
+        .assert()
+        .invoice_at(2)
+        .with_context("first invoice of the renewed term")
+        .has_total(12400);
+}
+
+/// Renewing onto a draft plan version is rejected.
+#[rstest]
+#[tokio::test]
+async fn test_renewal_to_draft_plan_version_rejected(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_PRO_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .end_date(NaiveDate::from_ymd_opt(2024, 3, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    let result = env
+        .services()
+        .set_renewal_terms(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            domain::RenewalTerms {
+                plan_version_id: Some(PLAN_VERSION_PRO_DRAFT_ID),
+                ..renewal_terms(domain::RenewalPolicyEnum::RenewToPlanVersion)
+            },
+        )
+        .await;
+    assert!(result.is_err());
+}
+
+/// Without renewal terms, the subscription still completes at its end date.
+#[rstest]
+#[tokio::test]
+async fn test_expire_policy_completes_at_end_date(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let end_date = NaiveDate::from_ymd_opt(2024, 3, 1).unwrap();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(start_date)
+        .end_date(end_date)
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.process_cycles().await;
+    env.process_cycles().await;
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert().has_status(SubscriptionStatusEnum::Completed);
+    assert_eq!(sub.end_date, Some(end_date));
+}
+
+/// Renewal can be opted out of until the deadline, after which the request is rejected.
+#[rstest]
+#[tokio::test]
+async fn test_opt_out_of_renewal_before_deadline(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(today)
+        .end_date(today + Months::new(3))
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .set_renewal_terms(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            domain::RenewalTerms {
+                opt_out_days: Some(30),
+                ..renewal_terms(domain::RenewalPolicyEnum::RenewMonthToMonth)
+            },
+        )
+        .await
+        .expect("set_renewal_terms failed");
+
+    let opted_out = env
+        .services()
+        .opt_out_of_renewal(Actor::System, sub_id, TENANT_ID)
+        .await
+        .expect("opt_out_of_renewal failed");
+    assert_eq!(opted_out.renewal_policy, domain::RenewalPolicyEnum::Expire);
+
+    let sub = env.get_subscription(sub_id).await;
+    assert_eq!(sub.renewal_policy, RenewalPolicyEnum::Expire);
+
+    // Past the deadline
+    let late_sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(today)
+        .end_date(today + Days::new(10))
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .set_renewal_terms(
+            Actor::System,
+            late_sub_id,
+            TENANT_ID,
+            domain::RenewalTerms {
+                opt_out_days: Some(30),
+                ..renewal_terms(domain::RenewalPolicyEnum::RenewMonthToMonth)
+            },
+        )
+        .await
+        .expect("set_renewal_terms failed");
+
+    let result = env
+        .services()
+        .opt_out_of_renewal(Actor::System, late_sub_id, TENANT_ID)
+        .await;
+    assert!(result.is_err());
+}
+
+/// Renewing subscriptions are notified once per term, within the notice window.
+#[rstest]
+#[tokio::test]
+async fn test_renewal_upcoming_notified_once(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(today - Days::new(20))
+        .end_date(today + Days::new(5))
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .set_renewal_terms(
+            Actor::System,
+            sub_id,
+            TENANT_ID,
+            domain::RenewalTerms {
+                notice_days: Some(10),
+                ..renewal_terms(domain::RenewalPolicyEnum::RenewMonthToMonth)
+            },
+        )
+        .await
+        .expect("set_renewal_terms failed");
+
+    let notified = env
+        .services()
+        .process_renewal_upcoming_notifications()
+        .await
+        .expect("process_renewal_upcoming_notifications failed");
+    assert_eq!(notified, 1);
+
+    let notified = env
+        .services()
+        .process_renewal_upcoming_notifications()
+        .await
+        .expect("process_renewal_upcoming_notifications failed");
+    assert_eq!(notified, 0);
+}
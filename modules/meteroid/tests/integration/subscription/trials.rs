@@ -4,8 +4,10 @@
 //! - Effective plan resolution during trial
 //! - Trial end transitions
 //! - Free vs Paid trial behavior
+//! - Trial extension, early conversion and trialing plan switch
 
-use chrono::NaiveDate;
+use chrono::{Datelike, Days, NaiveDate};
+use common_domain::actor::Actor;
 use rstest::rstest;
 
 use crate::data::ids::*;
@@ -849,3 +851,221 @@ async fn test_free_plan_with_trial_generates_no_invoices(#[future] test_env: Tes
     let sub = env.get_subscription(sub_id).await;
     sub.assert().is_active();
 }
+
+// =============================================================================
+// TRIAL CONTROLS TESTS
+// =============================================================================
+
+/// Extending a free trial pushes back the trial end and the first billing date.
+#[rstest]
+#[tokio::test]
+async fn test_extend_free_trial_moves_first_billing_date(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+    let new_trial_end = today + Days::new(30);
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_PAID_FREE_TRIAL_ID)
+        .start_date(today)
+        .on_start()
+        .trial_days(14)
+        .no_auto_charge()
+        .create(env.services())
+        .await;
+
+    // Shortening the trial is rejected
+    let result = env
+        .services()
+        .extend_trial(Actor::System, sub_id, TENANT_ID, today + Days::new(7))
+        .await;
+    assert!(result.is_err());
+
+    env.services()
+        .extend_trial(Actor::System, sub_id, TENANT_ID, new_trial_end)
+        .await
+        .expect("extend_trial failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_trial_active()
+        .has_next_action(Some(CycleActionEnum::EndTrial))
+        .has_period_end(new_trial_end)
+        .has_trial_duration(Some(30));
+    assert_eq!(sub.billing_day_anchor as u32, new_trial_end.day());
+
+    // Trial is not over yet
+    env.process_cycles().await;
+    env.get_subscription(sub_id)
+        .await
+        .assert()
+        .is_trial_active();
+    env.get_invoices(sub_id).await.assert().has_count(0);
+}
+
+/// Ending a free trial early converts the subscription and bills it right away.
+#[rstest]
+#[tokio::test]
+async fn test_end_free_trial_early_bills_immediately(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    // Finalization is still scheduled after the grace period, make it due at the invoice date
+    env.set_consolidate_and_grace(false, 0).await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_PAID_FREE_TRIAL_ID)
+        .start_date(today - Days::new(3))
+        .on_start()
+        .trial_days(14)
+        .no_auto_charge()
+        .create(env.services())
+        .await;
+
+    env.services()
+        .end_trial_now(Actor::System, sub_id, TENANT_ID)
+        .await
+        .expect("end_trial_now failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert().is_active().has_trial_duration(Some(3));
+
+    env.process_due_events().await;
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(1);
+    invoices
+        .assert()
+        .invoice_at(0)
+        .is_finalized_unpaid()
+        .has_total(4900)
+        .has_invoice_date(today);
+
+    // Not trialing anymore
+    let result = env
+        .services()
+        .end_trial_now(Actor::System, sub_id, TENANT_ID)
+        .await;
+    assert!(result.is_err());
+}
+
+/// Ending a paid trial early activates the subscription without billing it again.
+#[rstest]
+#[tokio::test]
+async fn test_end_paid_trial_early_activates(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_PAID_TRIAL_ID)
+        .start_date(today)
+        .on_start()
+        .trial_days(7)
+        .no_auto_charge()
+        .create(env.services())
+        .await;
+
+    env.get_invoices(sub_id).await.assert().has_count(1);
+
+    env.services()
+        .end_trial_now(Actor::System, sub_id, TENANT_ID)
+        .await
+        .expect("end_trial_now failed");
+
+    let sub = env.get_subscription(sub_id).await;
+    sub.assert()
+        .is_active()
+        .has_next_action(Some(CycleActionEnum::RenewSubscription))
+        .has_trial_duration(Some(0));
+
+    env.get_invoices(sub_id).await.assert().has_count(1);
+}
+
+/// Switching the trialing plan mid-trial changes the effective plan.
+#[rstest]
+#[tokio::test]
+async fn test_change_trialing_plan_mid_trial(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_PRO_WITH_TRIAL_ID)
+        .start_date(chrono::Utc::now().naive_utc().date())
+        .on_start()
+        .trial_days(7)
+        .create(env.services())
+        .await;
+
+    env.services()
+        .change_trialing_plan(Actor::System, sub_id, TENANT_ID, Some(PLAN_LEETCODE_ID))
+        .await
+        .expect("change_trialing_plan failed");
+
+    let effective_plan = env
+        .services()
+        .get_subscription_effective_plan(&mut env.conn().await, TENANT_ID, sub_id)
+        .await
+        .expect("Failed to get effective plan");
+    assert_eq!(effective_plan.plan_id, PLAN_LEETCODE_ID);
+    assert_eq!(effective_plan.source, EffectivePlanSource::TrialingPlan);
+
+    // Clearing it restores the trialing plan of the plan version
+    env.services()
+        .change_trialing_plan(Actor::System, sub_id, TENANT_ID, None)
+        .await
+        .expect("change_trialing_plan failed");
+
+    let effective_plan = env
+        .services()
+        .get_subscription_effective_plan(&mut env.conn().await, TENANT_ID, sub_id)
+        .await
+        .expect("Failed to get effective plan");
+    assert_eq!(effective_plan.plan_id, PLAN_ENTERPRISE_ID);
+}
+
+/// Trials ending within the notice period are notified once per trial end.
+#[rstest]
+#[tokio::test]
+async fn test_trial_will_end_notified_once(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let today = chrono::Utc::now().naive_utc().date();
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_PAID_FREE_TRIAL_ID)
+        .start_date(today)
+        .on_start()
+        .trial_days(2)
+        .no_auto_charge()
+        .create(env.services())
+        .await;
+
+    let notified = env
+        .services()
+        .process_trial_will_end_notifications()
+        .await
+        .expect("process_trial_will_end_notifications failed");
+    assert_eq!(notified, 1);
+
+    let sub = env.get_subscription(sub_id).await;
+    assert_eq!(sub.notified_trial_end, Some(today + Days::new(2)));
+
+    let notified = env
+        .services()
+        .process_trial_will_end_notifications()
+        .await
+        .expect("process_trial_will_end_notifications failed");
+    assert_eq!(notified, 0);
+
+    // An extended trial is notified again before its new end
+    env.services()
+        .extend_trial(Actor::System, sub_id, TENANT_ID, today + Days::new(3))
+        .await
+        .expect("extend_trial failed");
+
+    let notified = env
+        .services()
+        .process_trial_will_end_notifications()
+        .await
+        .expect("process_trial_will_end_notifications failed");
+    assert_eq!(notified, 1);
+}
@@ -3,6 +3,7 @@
 //! Tests for:
 //! - Invoice generation with usage (arrear) components
 //! - Final invoice generation at cancellation with arrear usage
+//! - Early usage threshold invoices deducted from the period invoice
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -19,7 +20,7 @@ use diesel_models::enums::SubscriptionStatusEnum;
 use meteroid_store::clients::usage::{
     GroupedUsageData, MockUsageClient, MockUsageDataParams, UsageData,
 };
-use meteroid_store::domain::UsagePeriod;
+use meteroid_store::domain::{InvoiceStatusEnum, InvoiceType, UsagePeriod};
 use meteroid_store::repositories::subscriptions::CancellationEffectiveAt;
 
 /// Build a MockUsageClient that returns usage data for METRIC_BANDWIDTH.
@@ -198,3 +199,57 @@ async fn test_cancel_with_usage_produces_final_arrear_invoice() {
         sub.mrr_cents
     );
 }
+
+/// Usage crossing the invoice threshold is billed early, and the period invoice
+/// only bills what is left.
+///
+/// Plan: Rate EUR 20/mo (advance) + Bandwidth EUR 0.10/unit (arrear), threshold EUR 5
+#[tokio::test]
+async fn test_usage_threshold_invoice_is_deducted_from_period_invoice() {
+    let start_date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+    let period1_end = NaiveDate::from_ymd_opt(2024, 2, 1).unwrap();
+
+    // 100 units × EUR 0.10 = EUR 10.00
+    let usage_client = build_usage_client(Decimal::from(100), &[(start_date, period1_end)]);
+    let env = test_env_with_seed_and_usage(SeedLevel::PLANS, Arc::new(usage_client)).await;
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_USAGE_ID)
+        .start_date(start_date)
+        .on_start()
+        .no_trial()
+        .invoice_threshold(Decimal::from(5))
+        .create(env.services())
+        .await;
+
+    let threshold_invoice = env
+        .services()
+        .invoice_usage_threshold(TENANT_ID, sub_id)
+        .await
+        .expect("invoice_usage_threshold failed")
+        .expect("the threshold is crossed");
+    assert_eq!(
+        threshold_invoice.invoice.invoice_type,
+        InvoiceType::UsageThreshold
+    );
+    assert_eq!(
+        threshold_invoice.invoice.status,
+        InvoiceStatusEnum::Finalized
+    );
+    assert_eq!(threshold_invoice.invoice.total, 1000);
+
+    // Nothing left above the threshold
+    let again = env
+        .services()
+        .invoice_usage_threshold(TENANT_ID, sub_id)
+        .await
+        .expect("invoice_usage_threshold failed");
+    assert!(again.is_none());
+
+    // Renewal: the usage of period 0 was already invoiced
+    env.process_cycles().await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    invoices.assert().has_count(3);
+    invoices.assert().invoice_at(2).has_total(2000);
+}
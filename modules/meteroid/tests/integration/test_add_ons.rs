@@ -48,6 +48,7 @@ async fn test_add_ons_basic() {
                                 rate: "9.99".into(),
                             },
                         )),
+                        variants: vec![],
                     },
                 )),
             }),
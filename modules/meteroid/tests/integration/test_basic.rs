@@ -111,9 +111,12 @@ async fn test_main() {
                                     },
                                 ),
                             ),
+                            variants: vec![],
                         },
                     )),
                 }],
+                sub_line_presentation: None,
+                line_metadata: Default::default(),
             },
         ))
         .await
@@ -156,6 +159,10 @@ async fn test_main() {
                     custom_taxes: vec![],
                     is_tax_exempt: Some(false),
                     connected_account_id: None,
+                    custom_attributes: Default::default(),
+                    invoice_language: None,
+                    invoice_custom_fields: Default::default(),
+                    invoice_approval_required: None,
                 }),
             },
         ))
@@ -13,8 +13,8 @@ use meteroid_mailer::service::MockMailerService;
 use meteroid_store::clients::usage::MockUsageClient;
 use meteroid_store::domain::enums::InvoiceStatusEnum;
 use meteroid_store::domain::{
-    CreateSubscription, CustomerNew, Invoice, PaginationRequest, PaymentMethodsConfig,
-    SubscriptionActivationCondition, SubscriptionNew,
+    CreateSubscription, CustomerNew, Invoice, PaginationRequest, PastPeriodBilling,
+    PaymentMethodsConfig, SubscriptionActivationCondition, SubscriptionNew,
 };
 use meteroid_store::repositories::subscriptions::CancellationEffectiveAt;
 use meteroid_store::repositories::{CustomersInterface, InvoiceInterface};
@@ -98,6 +98,10 @@ async fn test_issuing(
                 vat_number: None,
                 custom_taxes: vec![],
                 connected_account_id: None,
+                custom_attributes: Default::default(),
+                invoice_language: None,
+                invoice_custom_fields: Default::default(),
+                invoice_approval_required: false,
             },
             TENANT_ID,
         )
@@ -591,9 +595,14 @@ async fn test_mrr_cancellation_after_multiple_cycles(
     )
     .await
     .unwrap();
-    // process_mrr does NOT set bi_mrr_movement_log_id — this is a known gap
-    // The Created event's MRR delta is applied by process_mrr on invoice insertion
-    assert!(created_event.is_some());
+    // The Created event's MRR delta is applied by process_mrr on invoice insertion,
+    // which links the event to its movement log
+    assert!(
+        created_event
+            .expect("Created event should exist")
+            .bi_mrr_movement_log_id
+            .is_some()
+    );
 
     // Run through 3 billing cycles to build up state
     for cycle in 1..=3 {
@@ -893,7 +902,8 @@ async fn create_subscription(services: &Services, params: SubscriptionParams) ->
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
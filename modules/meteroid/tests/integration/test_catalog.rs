@@ -0,0 +1,369 @@
+//! Declarative catalog tests.
+//!
+//! Tests for:
+//! - Applying a document to a tenant without catalog
+//! - Re-applying the same document being a no-op
+//! - Price changes creating a new draft version next to the published one
+//! - Pruning archiving the entries missing from the document
+//! - Documents referencing unknown entries being rejected without writing anything
+
+use rstest::rstest;
+use rust_decimal::Decimal;
+
+use crate::data::ids::*;
+use crate::harness::{TestEnv, test_env_minimal};
+use meteroid_store::domain::catalog::{
+    CatalogAction, CatalogCoupon, CatalogDiff, CatalogDocument, CatalogEntity, CatalogFormat,
+};
+use meteroid_store::domain::coupons::CouponDiscount;
+use meteroid_store::domain::entity_activity::Actor;
+use meteroid_store::domain::enums::PlanStatusEnum;
+use meteroid_store::domain::prices::Pricing;
+use meteroid_store::domain::{PaginationRequest, PlanFilters, PlanOverview, PlanVersionFilter};
+use meteroid_store::errors::StoreError;
+use meteroid_store::repositories::PlansInterface;
+
+const DOCUMENT: &str = r#"
+features:
+  - code: sso
+    name: Single sign-on
+product_families:
+  - name: Default
+    products:
+      - name: Platform fee
+        fee:
+          type: Rate
+      - name: Support
+        fee:
+          type: Rate
+    plans:
+      - name: Pro
+        plan_type: Standard
+        currency: EUR
+        net_terms: 30
+        components:
+          - name: Platform fee
+            product: Platform fee
+            prices:
+              - cadence: Monthly
+                pricing:
+                  type: Rate
+                  rate: "49.00"
+        entitlements:
+          - feature: sso
+            value:
+              type: boolean
+              enabled: true
+      - name: Starter
+        plan_type: Standard
+        currency: EUR
+        components:
+          - name: Platform fee
+            product: Platform fee
+            prices:
+              - cadence: Monthly
+                pricing:
+                  type: Rate
+                  rate: "19.00"
+"#;
+
+fn document() -> CatalogDocument {
+    let mut document =
+        CatalogDocument::parse(DOCUMENT, CatalogFormat::Yaml).expect("invalid test document");
+    document.coupons.push(CatalogCoupon {
+        code: "WELCOME".to_string(),
+        description: "Welcome offer".to_string(),
+        discount: CouponDiscount::Percentage(Decimal::new(10, 0)),
+        expires_at: None,
+        redemption_limit: None,
+        recurring_value: None,
+        reusable: false,
+    });
+    document
+}
+
+async fn apply(env: &TestEnv, document: &CatalogDocument, prune: bool) -> CatalogDiff {
+    env.services()
+        .apply_catalog(TENANT_ID, Actor::User { id: USER_ID }, document, prune)
+        .await
+        .expect("apply_catalog failed")
+}
+
+fn keys(diff: &CatalogDiff, action: CatalogAction) -> Vec<(CatalogEntity, &str)> {
+    diff.changes
+        .iter()
+        .filter(|c| c.action == action)
+        .map(|c| (c.entity, c.key.as_str()))
+        .collect()
+}
+
+async fn plans(env: &TestEnv) -> Vec<PlanOverview> {
+    env.store()
+        .list_plans(
+            TENANT_ID,
+            None,
+            PlanFilters {
+                search: None,
+                filter_status: vec![],
+                filter_type: vec![],
+                filter_currency: None,
+            },
+            PaginationRequest {
+                per_page: Some(100),
+                page: 0,
+            },
+            None,
+        )
+        .await
+        .expect("list_plans failed")
+        .items
+}
+
+async fn plan(env: &TestEnv, name: &str) -> PlanOverview {
+    plans(env)
+        .await
+        .into_iter()
+        .find(|p| p.name == name)
+        .unwrap_or_else(|| panic!("plan {name} not found"))
+}
+
+async fn plan_rate(env: &TestEnv, plan: &PlanOverview, filter: PlanVersionFilter) -> Pricing {
+    let full = env
+        .store()
+        .get_full_plan(plan.id, TENANT_ID, filter)
+        .await
+        .expect("get_full_plan failed");
+    full.price_components[0].prices[0].pricing.clone()
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_catalog_apply_to_empty_tenant(#[future] test_env_minimal: TestEnv) {
+    let env = test_env_minimal.await;
+    let document = document();
+
+    let diff = env
+        .services()
+        .diff_catalog(TENANT_ID, &document, false)
+        .await
+        .expect("diff_catalog failed");
+    assert!(diff.has_changes());
+    assert!(!diff.has_conflicts());
+    assert!(plans(&env).await.is_empty(), "diff must not write");
+
+    let applied = apply(&env, &document, false).await;
+    assert_eq!(applied, diff);
+    assert_eq!(
+        keys(&applied, CatalogAction::Create),
+        vec![
+            (CatalogEntity::Feature, "sso"),
+            (CatalogEntity::Product, "Default/Platform fee"),
+            (CatalogEntity::Product, "Default/Support"),
+            (CatalogEntity::Plan, "Default/Pro"),
+            (CatalogEntity::Plan, "Default/Starter"),
+            (CatalogEntity::Coupon, "WELCOME"),
+        ]
+    );
+    // the seeded family is reused
+    assert_eq!(
+        keys(&applied, CatalogAction::Unchanged),
+        vec![(CatalogEntity::ProductFamily, "Default")]
+    );
+
+    let pro = plan(&env, "Pro").await;
+    assert_eq!(pro.status, PlanStatusEnum::Draft);
+    assert!(pro.has_draft_version);
+
+    let exported = env
+        .services()
+        .export_catalog(TENANT_ID)
+        .await
+        .expect("export_catalog failed");
+    assert_eq!(exported, document);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_catalog_reapply_is_noop(#[future] test_env_minimal: TestEnv) {
+    let env = test_env_minimal.await;
+    let document = document();
+
+    apply(&env, &document, false).await;
+    let pro = plan(&env, "Pro").await;
+
+    let reapplied = apply(&env, &document, true).await;
+    assert!(!reapplied.has_changes());
+    assert!(
+        reapplied
+            .changes
+            .iter()
+            .all(|c| c.action == CatalogAction::Unchanged),
+        "unexpected changes: {:?}",
+        reapplied.changes
+    );
+
+    let versions = env
+        .store()
+        .list_plan_versions(
+            pro.id,
+            TENANT_ID,
+            PaginationRequest {
+                per_page: Some(10),
+                page: 0,
+            },
+        )
+        .await
+        .expect("list_plan_versions failed");
+    assert_eq!(versions.items.len(), 1);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_catalog_price_change_creates_plan_version(#[future] test_env_minimal: TestEnv) {
+    let env = test_env_minimal.await;
+    let mut document = document();
+
+    apply(&env, &document, false).await;
+    let pro = plan(&env, "Pro").await;
+    let draft = env
+        .store()
+        .get_full_plan(pro.id, TENANT_ID, PlanVersionFilter::Draft)
+        .await
+        .expect("get_full_plan failed");
+    env.store()
+        .publish_plan_version(Actor::User { id: USER_ID }, draft.version.id, TENANT_ID)
+        .await
+        .expect("publish_plan_version failed");
+
+    document.product_families[0].plans[0].components[0].prices[0].pricing = Pricing::Rate {
+        rate: Decimal::new(5900, 2),
+    };
+    let diff = apply(&env, &document, false).await;
+
+    let change = diff
+        .changes
+        .iter()
+        .find(|c| c.key == "Default/Pro")
+        .expect("missing plan change");
+    assert_eq!(change.action, CatalogAction::Update);
+    assert_eq!(change.details, vec!["components".to_string()]);
+    assert_eq!(
+        keys(&diff, CatalogAction::Update),
+        vec![(CatalogEntity::Plan, "Default/Pro")]
+    );
+
+    // the published version keeps its price, the change lands in a new draft
+    let pro = plan(&env, "Pro").await;
+    assert_eq!(pro.status, PlanStatusEnum::Active);
+    assert!(pro.has_draft_version);
+    assert_eq!(
+        plan_rate(&env, &pro, PlanVersionFilter::Active).await,
+        Pricing::Rate {
+            rate: Decimal::new(4900, 2)
+        }
+    );
+    assert_eq!(
+        plan_rate(&env, &pro, PlanVersionFilter::Draft).await,
+        Pricing::Rate {
+            rate: Decimal::new(5900, 2)
+        }
+    );
+
+    let draft = env
+        .store()
+        .get_full_plan(pro.id, TENANT_ID, PlanVersionFilter::Draft)
+        .await
+        .expect("get_full_plan failed");
+    assert_eq!(draft.version.version, 2);
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_catalog_prune_archives_missing_entries(#[future] test_env_minimal: TestEnv) {
+    let env = test_env_minimal.await;
+    let mut document = document();
+
+    apply(&env, &document, false).await;
+
+    let family = &mut document.product_families[0];
+    family.plans.retain(|p| p.name != "Starter");
+    family.products.retain(|p| p.name != "Support");
+    document.coupons.clear();
+
+    // without pruning, missing entries are left untouched
+    let diff = apply(&env, &document, false).await;
+    assert!(!diff.has_changes());
+    assert_eq!(plan(&env, "Starter").await.status, PlanStatusEnum::Draft);
+
+    let diff = apply(&env, &document, true).await;
+    assert_eq!(
+        keys(&diff, CatalogAction::Archive),
+        vec![
+            (CatalogEntity::Plan, "Default/Starter"),
+            (CatalogEntity::Product, "Default/Support"),
+            (CatalogEntity::Coupon, "WELCOME"),
+        ]
+    );
+    assert_eq!(plan(&env, "Starter").await.status, PlanStatusEnum::Archived);
+    assert_eq!(plan(&env, "Pro").await.status, PlanStatusEnum::Draft);
+
+    let exported = env
+        .services()
+        .export_catalog(TENANT_ID)
+        .await
+        .expect("export_catalog failed");
+    assert_eq!(exported, document);
+
+    let diff = apply(&env, &document, true).await;
+    assert!(!diff.has_changes());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_catalog_unknown_references_are_rejected(#[future] test_env_minimal: TestEnv) {
+    let env = test_env_minimal.await;
+    let mut document = document();
+
+    let pro = &mut document.product_families[0].plans[0];
+    pro.components[0].product = "Missing".to_string();
+    pro.entitlements[0].feature = "unknown".to_string();
+
+    let diff = env
+        .services()
+        .diff_catalog(TENANT_ID, &document, false)
+        .await
+        .expect("diff_catalog failed");
+    let conflict = diff
+        .changes
+        .iter()
+        .find(|c| c.action == CatalogAction::Conflict)
+        .expect("missing conflict");
+    assert_eq!(conflict.key, "Default/Pro");
+    assert_eq!(
+        conflict.details,
+        vec![
+            "unknown feature 'unknown'".to_string(),
+            "component 'Platform fee' uses unknown product 'Missing'".to_string(),
+        ]
+    );
+
+    let err = env
+        .services()
+        .apply_catalog(TENANT_ID, Actor::User { id: USER_ID }, &document, false)
+        .await
+        .expect_err("apply_catalog must fail on conflicts");
+    assert!(matches!(
+        err.current_context(),
+        StoreError::InvalidArgument(_)
+    ));
+
+    // valid entries of the document are not applied either
+    assert!(plans(&env).await.is_empty());
+    let exported = env
+        .services()
+        .export_catalog(TENANT_ID)
+        .await
+        .expect("export_catalog failed");
+    assert!(exported.features.is_empty());
+    assert!(exported.product_families[0].products.is_empty());
+}
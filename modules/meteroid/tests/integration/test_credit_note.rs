@@ -20,8 +20,8 @@ use meteroid_store::domain::enums::InvoiceStatusEnum;
 use meteroid_store::domain::subscription_coupons::CreateSubscriptionCoupon;
 use meteroid_store::domain::{
     Address, BillingPeriodEnum, CreateSubscription, CreateSubscriptionCoupons, CustomerCustomTax,
-    FeeType, InvoicingEntityPatch, PaginationRequest, SubscriptionActivationCondition,
-    SubscriptionNew, TermRate,
+    FeeType, InvoicingEntityPatch, PaginationRequest, PastPeriodBilling,
+    SubscriptionActivationCondition, SubscriptionNew, TermRate,
 };
 use meteroid_store::repositories::coupons::CouponInterface;
 use meteroid_store::repositories::credit_notes::{
@@ -121,7 +121,8 @@ async fn test_credit_note_partial_credits() {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -532,7 +533,8 @@ async fn test_credit_note_race_condition() {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -697,7 +699,8 @@ async fn test_credit_note_refund_with_applied_credits() {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -973,7 +976,8 @@ async fn test_credit_note_partial_amounts() {
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -1275,7 +1279,8 @@ async fn setup_unpaid_invoice() -> (
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -2060,6 +2065,8 @@ async fn create_plan_with_4_components(
                 plan_version_id,
                 product_id: Some(product_id),
                 billable_metric_id: None,
+                sub_line_presentation: serde_json::json!({"mode": "detailed"}),
+                line_metadata: serde_json::json!({}),
             }
             .insert(tx)
             .await?;
@@ -2114,6 +2121,10 @@ async fn create_customer_with_tax(conn: &mut PgConn, balance_cents: i64) -> Cust
         vat_number_format_valid: true,
         connected_account_id: None,
         vat_number_validation_status: None,
+        custom_attributes: serde_json::json!({}),
+        invoice_language: None,
+        invoice_custom_fields: serde_json::json!({}),
+        invoice_approval_required: false,
     };
 
     customer_row.insert(conn).await.unwrap();
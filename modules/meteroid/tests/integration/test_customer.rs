@@ -55,6 +55,10 @@ async fn test_customers_basic() {
                 custom_taxes: vec![],
                 is_tax_exempt: Some(false),
                 connected_account_id: None,
+                custom_attributes: Default::default(),
+                invoice_language: None,
+                invoice_custom_fields: Default::default(),
+                invoice_approval_required: None,
             }),
         })
         .await
@@ -81,6 +85,10 @@ async fn test_customers_basic() {
                 custom_taxes: vec![],
                 is_tax_exempt: Some(false),
                 connected_account_id: None,
+                custom_attributes: Default::default(),
+                invoice_language: None,
+                invoice_custom_fields: Default::default(),
+                invoice_approval_required: None,
             }),
         })
         .await
@@ -231,6 +239,10 @@ async fn test_customers_basic() {
                 vat_number: None,
                 custom_taxes: None,
                 is_tax_exempt: None,
+                custom_attributes: None,
+                invoice_language: None,
+                invoice_custom_fields: Default::default(),
+                invoice_approval_required: None,
             }),
         })
         .await
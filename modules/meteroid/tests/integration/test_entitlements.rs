@@ -1795,6 +1795,7 @@ async fn test_resolved_entitlements_for_addon_includes_product_features() {
                                     },
                                 ),
                             ),
+                            variants: vec![],
                         },
                     ),
                 ),
@@ -2369,6 +2370,7 @@ async fn make_single_instance_add_on(
                                     },
                                 ),
                             ),
+                            variants: vec![],
                         },
                     ),
                 ),
@@ -0,0 +1,322 @@
+//! Invoice approval integration tests.
+//!
+//! Tests for:
+//! - Drafts reaching the invoicing entity threshold held for approval
+//! - Customers flagged for approval
+//! - Approval, rejection and resubmission
+//! - Approvers of the invoicing entity
+
+use chrono::NaiveDate;
+use common_domain::actor::Actor;
+use common_domain::ids::{BaseId, UserId};
+use diesel_models::enums::OrganizationUserRole;
+use diesel_models::organization_members::OrganizationMemberRow;
+use diesel_models::users::UserRowNew;
+use rstest::rstest;
+use rust_decimal_macros::dec;
+
+use crate::data::ids::*;
+use crate::harness::{TestEnv, subscription, test_env};
+use meteroid_store::domain::enums::{InvoiceApprovalStatus, InvoiceStatusEnum};
+use meteroid_store::domain::{
+    Invoice, InvoicingEntityPatch, OneOffInvoiceNew, OneOffItem, OneOffLineItemNew,
+    UpdateInvoiceParams,
+};
+use meteroid_store::errors::StoreError;
+use meteroid_store::repositories::invoicing_entities::InvoicingEntityInterface;
+
+const ADMIN: Actor = Actor::User { id: USER_ID };
+
+impl TestEnv {
+    async fn set_approval_settings(&self, threshold: Option<i64>, approver_ids: Vec<UserId>) {
+        self.store()
+            .patch_invoicing_entity(
+                ADMIN,
+                InvoicingEntityPatch {
+                    id: INVOICING_ENTITY_ID,
+                    invoice_approval_threshold: Some(threshold),
+                    invoice_approver_ids: Some(approver_ids),
+                    ..Default::default()
+                },
+                TENANT_ID,
+            )
+            .await
+            .expect("Failed to set approval settings");
+    }
+
+    async fn flag_customer_for_approval(&self) {
+        use diesel::{ExpressionMethods, QueryDsl};
+        use diesel_async::RunQueryDsl;
+        use diesel_models::schema::customer::dsl as c;
+
+        let mut conn = self.conn().await;
+        diesel::update(c::customer.filter(c::id.eq(CUST_SPOTIFY_ID)))
+            .set(c::invoice_approval_required.eq(true))
+            .execute(&mut conn)
+            .await
+            .expect("Failed to flag customer");
+    }
+
+    async fn add_member(&self, role: OrganizationUserRole) -> UserId {
+        let mut conn = self.conn().await;
+        let id = UserId::new();
+        UserRowNew {
+            id,
+            email: format!("{id}@meteroid.dev"),
+            password_hash: None,
+        }
+        .insert(&mut conn)
+        .await
+        .expect("Failed to insert user");
+        OrganizationMemberRow {
+            user_id: id,
+            organization_id: ORGANIZATION_ID,
+            role,
+        }
+        .insert(&mut conn)
+        .await
+        .expect("Failed to insert member");
+        id
+    }
+
+    async fn remove_member(&self, id: UserId) {
+        let mut conn = self.conn().await;
+        OrganizationMemberRow::delete_member(&mut conn, id.as_uuid(), ORGANIZATION_ID)
+            .await
+            .expect("Failed to remove member");
+    }
+
+    /// A €200 one-off draft for the Spotify customer.
+    async fn create_oneoff_draft(&self) -> Invoice {
+        self.services()
+            .create_oneoff_invoice(
+                TENANT_ID,
+                OneOffInvoiceNew {
+                    customer_id: CUST_SPOTIFY_ID,
+                    currency: None,
+                    invoice_date: None,
+                    due_date: None,
+                    memo: None,
+                    reference: None,
+                    purchase_order: None,
+                    line_items: vec![OneOffLineItemNew {
+                        item: OneOffItem::Custom {
+                            name: "Consulting".to_string(),
+                            unit_price: dec!(200),
+                            product_id: None,
+                        },
+                        quantity: dec!(1),
+                        description: None,
+                        metadata: Default::default(),
+                    }],
+                    coupon_codes: vec![],
+                    custom_fields: None,
+                },
+            )
+            .await
+            .expect("Failed to create one-off invoice")
+            .invoice
+    }
+
+    async fn finalize(&self, invoice: &Invoice, actor: Actor) -> Invoice {
+        self.services()
+            .finalize_invoice(actor, invoice.id, TENANT_ID)
+            .await
+            .expect("Failed to finalize invoice")
+            .invoice
+    }
+}
+
+/// A renewal draft reaching the threshold stays a draft when its finalization is due, until
+/// approved. The invoice issued at activation is not held, the customer committed to it.
+/// €35 plan, threshold €10.
+#[rstest]
+#[tokio::test]
+async fn test_scheduled_finalization_above_threshold_is_held(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    env.set_approval_settings(Some(1000), vec![]).await;
+
+    let sub_id = subscription()
+        .plan_version(PLAN_VERSION_1_LEETCODE_ID)
+        .start_date(NaiveDate::from_ymd_opt(2024, 1, 1).unwrap())
+        .on_start()
+        .no_trial()
+        .create(env.services())
+        .await;
+
+    // Renewal drafts the invoice of period 1, its finalization is due after the grace period
+    env.process_cycles().await;
+
+    let invoices = env.get_invoices(sub_id).await;
+    assert_eq!(invoices.len(), 2);
+    assert_eq!(invoices[0].status, InvoiceStatusEnum::Finalized);
+    assert_eq!(invoices[0].approval_status, None);
+
+    let invoice = &invoices[1];
+    assert_eq!(invoice.status, InvoiceStatusEnum::Draft);
+    assert_eq!(
+        invoice.approval_status,
+        Some(InvoiceApprovalStatus::Pending)
+    );
+    assert!(invoice.approval_requested_at.is_some());
+
+    let approved = env
+        .services()
+        .approve_invoice(ADMIN, invoice.id, TENANT_ID, None)
+        .await
+        .unwrap()
+        .invoice;
+    assert_eq!(approved.status, InvoiceStatusEnum::Finalized);
+    assert_eq!(
+        approved.approval_status,
+        Some(InvoiceApprovalStatus::Approved)
+    );
+    assert_eq!(approved.approval_decided_by, Some(USER_ID));
+    assert_eq!(approved.total, invoice.total);
+}
+
+/// Drafts below the threshold are finalized right away.
+#[rstest]
+#[tokio::test]
+async fn test_draft_below_threshold_is_finalized(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    env.set_approval_settings(Some(100_000), vec![]).await;
+
+    let draft = env.create_oneoff_draft().await;
+    let invoice = env.finalize(&draft, ADMIN).await;
+
+    assert_eq!(invoice.status, InvoiceStatusEnum::Finalized);
+    assert_eq!(invoice.approval_status, None);
+}
+
+/// The invoices of a flagged customer are held whatever their amount, and cannot be edited
+/// while awaiting approval.
+#[rstest]
+#[tokio::test]
+async fn test_flagged_customer_invoice_is_held(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    env.flag_customer_for_approval().await;
+
+    let draft = env.create_oneoff_draft().await;
+    let invoice = env.finalize(&draft, ADMIN).await;
+
+    assert_eq!(invoice.status, InvoiceStatusEnum::Draft);
+    assert_eq!(
+        invoice.approval_status,
+        Some(InvoiceApprovalStatus::Pending)
+    );
+
+    let err = env
+        .services()
+        .update_draft_invoice(
+            invoice.id,
+            TENANT_ID,
+            UpdateInvoiceParams {
+                memo: Some(Some("Edited".to_string())),
+                reference: None,
+                purchase_order: None,
+                due_date: None,
+                line_items: None,
+                discount: None,
+                customer_details: None,
+                invoicing_entity_id: None,
+                custom_fields: None,
+            },
+        )
+        .await
+        .expect_err("a draft awaiting approval should not be editable");
+    assert!(matches!(
+        err.current_context(),
+        StoreError::InvalidArgument(_)
+    ));
+}
+
+/// A rejected draft stays a draft, and finalizing it again requests a new approval.
+#[rstest]
+#[tokio::test]
+async fn test_rejected_invoice_is_resubmitted(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    env.flag_customer_for_approval().await;
+
+    let draft = env.create_oneoff_draft().await;
+    env.finalize(&draft, ADMIN).await;
+
+    let rejected = env
+        .services()
+        .reject_invoice(ADMIN, draft.id, TENANT_ID, "Wrong amount".to_string())
+        .await
+        .unwrap()
+        .invoice;
+    assert_eq!(rejected.status, InvoiceStatusEnum::Draft);
+    assert_eq!(
+        rejected.approval_status,
+        Some(InvoiceApprovalStatus::Rejected)
+    );
+    assert_eq!(rejected.approval_comment.as_deref(), Some("Wrong amount"));
+
+    let resubmitted = env.finalize(&rejected, ADMIN).await;
+    assert_eq!(resubmitted.status, InvoiceStatusEnum::Draft);
+    assert_eq!(
+        resubmitted.approval_status,
+        Some(InvoiceApprovalStatus::Pending)
+    );
+
+    let approved = env
+        .services()
+        .approve_invoice(ADMIN, draft.id, TENANT_ID, Some("Fixed".to_string()))
+        .await
+        .unwrap()
+        .invoice;
+    assert_eq!(approved.status, InvoiceStatusEnum::Finalized);
+}
+
+/// Only the designated approvers can decide; the admins are not approvers anymore.
+#[rstest]
+#[tokio::test]
+async fn test_non_approver_is_forbidden(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    let approver = env.add_member(OrganizationUserRole::Member).await;
+    let member = env.add_member(OrganizationUserRole::Member).await;
+    env.set_approval_settings(Some(0), vec![approver]).await;
+
+    let draft = env.create_oneoff_draft().await;
+    env.finalize(&draft, ADMIN).await;
+
+    for actor in [Actor::User { id: member }, ADMIN] {
+        let err = env
+            .services()
+            .approve_invoice(actor, draft.id, TENANT_ID, None)
+            .await
+            .expect_err("only the designated approver can approve");
+        assert!(matches!(err.current_context(), StoreError::Forbidden(_)));
+    }
+
+    let approved = env
+        .services()
+        .approve_invoice(Actor::User { id: approver }, draft.id, TENANT_ID, None)
+        .await
+        .unwrap()
+        .invoice;
+    assert_eq!(approved.status, InvoiceStatusEnum::Finalized);
+}
+
+/// When none of the designated approvers is still a member, the admins approve.
+#[rstest]
+#[tokio::test]
+async fn test_admins_approve_when_approvers_left(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+    let approver = env.add_member(OrganizationUserRole::Member).await;
+    env.set_approval_settings(Some(0), vec![approver]).await;
+    env.remove_member(approver).await;
+
+    let draft = env.create_oneoff_draft().await;
+    env.finalize(&draft, ADMIN).await;
+
+    let approved = env
+        .services()
+        .approve_invoice(ADMIN, draft.id, TENANT_ID, None)
+        .await
+        .unwrap()
+        .invoice;
+    assert_eq!(approved.status, InvoiceStatusEnum::Finalized);
+}
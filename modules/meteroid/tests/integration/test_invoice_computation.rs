@@ -11,7 +11,7 @@ use meteroid_store::domain::coupons::{CouponDiscount, CouponNew};
 use meteroid_store::domain::subscription_coupons::CreateSubscriptionCoupon;
 use meteroid_store::domain::{
     CreateSubscription, CreateSubscriptionCoupons, CustomerCustomTax, InvoicingEntityPatch,
-    SubscriptionActivationCondition, SubscriptionNew,
+    PastPeriodBilling, SubscriptionActivationCondition, SubscriptionNew,
 };
 use meteroid_store::repositories::SubscriptionInterface;
 use meteroid_store::repositories::coupons::CouponInterface;
@@ -73,7 +73,8 @@ async fn test_compute_invoice_basic(
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -206,7 +207,8 @@ async fn test_compute_invoice_with_eu_vat(
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -297,6 +299,10 @@ async fn create_french_b2b_customer(
         vat_number_format_valid: true,
         connected_account_id: None,
         vat_number_validation_status: None,
+        custom_attributes: serde_json::json!({}),
+        invoice_language: None,
+        invoice_custom_fields: serde_json::json!({}),
+        invoice_approval_required: false,
     };
 
     customer_row.insert(conn).await.unwrap();
@@ -347,7 +353,8 @@ async fn test_compute_invoice_with_reverse_charge(
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -446,7 +453,8 @@ async fn test_compute_invoice_with_manual_tax_and_coupon(
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: None,
                 add_ons: None,
@@ -548,6 +556,10 @@ async fn create_german_b2b_customer(
         vat_number_format_valid: true,
         connected_account_id: None,
         vat_number_validation_status: None,
+        custom_attributes: serde_json::json!({}),
+        invoice_language: None,
+        invoice_custom_fields: serde_json::json!({}),
+        invoice_approval_required: false,
     };
 
     customer_row.insert(conn).await.unwrap();
@@ -600,6 +612,10 @@ async fn create_customer_with_custom_tax_rate(
         vat_number_format_valid: false,
         connected_account_id: None,
         vat_number_validation_status: None,
+        custom_attributes: serde_json::json!({}),
+        invoice_language: None,
+        invoice_custom_fields: serde_json::json!({}),
+        invoice_approval_required: false,
     };
 
     customer_row.insert(conn).await.unwrap();
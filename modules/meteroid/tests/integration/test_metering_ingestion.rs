@@ -465,6 +465,7 @@ async fn get_eventually_usage(
                     start: period_start.and_time(NaiveTime::MIN),
                     end: period_end.and_time(NaiveTime::MIN),
                 },
+                None,
             )
             .await
             .unwrap();
@@ -0,0 +1,271 @@
+//! One-off invoice integration tests.
+//!
+//! Tests for:
+//! - Lines from catalog prices and free-form lines
+//! - Usage prices rejected
+//! - Coupon redemptions
+//! - Finalization and manual payment
+
+use chrono::Utc;
+use rstest::rstest;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+use crate::data::ids::*;
+use crate::harness::{TestEnv, test_env};
+use common_domain::actor::Actor;
+use meteroid_store::StoreResult;
+use meteroid_store::domain::enums::InvoiceStatusEnum;
+use meteroid_store::domain::{
+    DetailedInvoice, InvoicePaymentStatus, InvoiceType, OneOffInvoiceNew, OneOffItem,
+    OneOffLineItemNew,
+};
+use meteroid_store::errors::StoreError;
+use meteroid_store::repositories::coupons::CouponInterface;
+
+fn oneoff_invoice(line_items: Vec<OneOffLineItemNew>, coupon_codes: &[&str]) -> OneOffInvoiceNew {
+    OneOffInvoiceNew {
+        customer_id: CUST_SPOTIFY_ID,
+        currency: None,
+        invoice_date: None,
+        due_date: None,
+        memo: None,
+        reference: None,
+        purchase_order: None,
+        line_items,
+        coupon_codes: coupon_codes.iter().map(|c| c.to_string()).collect(),
+        custom_fields: None,
+    }
+}
+
+fn line(item: OneOffItem, quantity: Decimal) -> OneOffLineItemNew {
+    OneOffLineItemNew {
+        item,
+        quantity,
+        description: None,
+        metadata: Default::default(),
+    }
+}
+
+fn custom(name: &str, unit_price: Decimal) -> OneOffItem {
+    OneOffItem::Custom {
+        name: name.to_string(),
+        unit_price,
+        product_id: None,
+    }
+}
+
+async fn create(env: &TestEnv, params: OneOffInvoiceNew) -> StoreResult<DetailedInvoice> {
+    env.services()
+        .create_oneoff_invoice(TENANT_ID, params)
+        .await
+}
+
+/// A catalog price is billed at its rate, named after its product.
+/// 2 x €35 = €70.
+#[rstest]
+#[tokio::test]
+async fn test_oneoff_invoice_from_price(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let created = create(
+        &env,
+        oneoff_invoice(
+            vec![line(OneOffItem::Price(PRICE_LEETCODE_RATE_ID), dec!(2))],
+            &[],
+        ),
+    )
+    .await
+    .unwrap();
+
+    let invoice = created.invoice;
+    assert_eq!(invoice.status, InvoiceStatusEnum::Draft);
+    assert_eq!(invoice.invoice_type, InvoiceType::OneOff);
+    assert_eq!(invoice.subscription_id, None);
+    assert_eq!(invoice.currency, "EUR");
+    assert_eq!(invoice.subtotal, 7000);
+    assert_eq!(invoice.line_items.len(), 1);
+    assert_eq!(invoice.line_items[0].unit_price, Some(dec!(35)));
+    assert_eq!(
+        invoice.line_items[0].product_id,
+        Some(PRODUCT_PLATFORM_FEE_ID)
+    );
+}
+
+/// Free-form lines are billed at their own unit price.
+/// 3 x €12.50 + 1 x €100 = €137.50.
+#[rstest]
+#[tokio::test]
+async fn test_oneoff_invoice_free_form(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let created = create(
+        &env,
+        oneoff_invoice(
+            vec![
+                line(custom("Training session", dec!(12.50)), dec!(3)),
+                line(custom("Onboarding", dec!(100)), dec!(1)),
+            ],
+            &[],
+        ),
+    )
+    .await
+    .unwrap();
+
+    let invoice = created.invoice;
+    assert_eq!(invoice.line_items.len(), 2);
+    assert_eq!(invoice.line_items[0].name, "Training session");
+    assert_eq!(invoice.line_items[0].amount_subtotal, 3750);
+    assert_eq!(invoice.line_items[1].amount_subtotal, 10000);
+    assert_eq!(invoice.subtotal, 13750);
+}
+
+/// Usage prices depend on metered quantities and cannot be billed on a one-off invoice.
+#[rstest]
+#[tokio::test]
+async fn test_oneoff_invoice_rejects_usage_price(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let result = create(
+        &env,
+        oneoff_invoice(
+            vec![line(OneOffItem::Price(PRICE_USAGE_BANDWIDTH_ID), dec!(1))],
+            &[],
+        ),
+    )
+    .await;
+
+    let err = result.expect_err("usage price should be rejected");
+    assert!(matches!(
+        err.current_context(),
+        StoreError::InvalidArgument(_)
+    ));
+    assert!(env.get_customer_invoices(CUST_SPOTIFY_ID).await.is_empty());
+}
+
+/// A coupon discounts the invoice and counts as a redemption.
+/// A non-reusable coupon cannot be redeemed twice by the same customer.
+#[rstest]
+#[tokio::test]
+async fn test_oneoff_invoice_coupon(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let coupon_id = env.create_fixed_coupon("ONEOFF10", 10, "EUR").await;
+
+    let created = create(
+        &env,
+        oneoff_invoice(
+            vec![line(custom("Audit", dec!(50)), dec!(1))],
+            &["ONEOFF10"],
+        ),
+    )
+    .await
+    .unwrap();
+
+    let invoice = created.invoice;
+    assert_eq!(invoice.subtotal, 5000);
+    assert_eq!(invoice.discount, 1000);
+    assert_eq!(invoice.coupons.len(), 1);
+    assert_eq!(invoice.coupons[0].coupon_id, coupon_id);
+
+    let coupon = env
+        .store()
+        .get_coupon_by_id(TENANT_ID, coupon_id)
+        .await
+        .unwrap();
+    assert_eq!(coupon.redemption_count, 1);
+    assert!(coupon.last_redemption_at.is_some());
+
+    let result = create(
+        &env,
+        oneoff_invoice(
+            vec![line(custom("Audit", dec!(50)), dec!(1))],
+            &["ONEOFF10"],
+        ),
+    )
+    .await;
+
+    let err = result.expect_err("non-reusable coupon should not be redeemed twice");
+    assert!(matches!(
+        err.current_context(),
+        StoreError::InvalidArgument(_)
+    ));
+
+    let coupon = env
+        .store()
+        .get_coupon_by_id(TENANT_ID, coupon_id)
+        .await
+        .unwrap();
+    assert_eq!(coupon.redemption_count, 1);
+}
+
+/// A coupon that reached its redemption limit is rejected.
+#[rstest]
+#[tokio::test]
+async fn test_oneoff_invoice_coupon_redemption_limit(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    env.create_limited_redemption_coupon("ONCE", 10, 1).await;
+
+    create(
+        &env,
+        oneoff_invoice(vec![line(custom("Audit", dec!(50)), dec!(1))], &["ONCE"]),
+    )
+    .await
+    .unwrap();
+
+    let mut other_customer =
+        oneoff_invoice(vec![line(custom("Audit", dec!(50)), dec!(1))], &["ONCE"]);
+    other_customer.customer_id = CUST_UBER_ID;
+    other_customer.currency = Some("EUR".to_string());
+
+    let err = create(&env, other_customer)
+        .await
+        .expect_err("coupon limit should be enforced");
+    assert!(matches!(
+        err.current_context(),
+        StoreError::InvalidArgument(_)
+    ));
+}
+
+/// A finalized one-off invoice can be paid manually.
+#[rstest]
+#[tokio::test]
+async fn test_oneoff_invoice_finalize_and_pay(#[future] test_env: TestEnv) {
+    let env = test_env.await;
+
+    let created = create(
+        &env,
+        oneoff_invoice(vec![line(custom("Consulting", dec!(200)), dec!(1))], &[]),
+    )
+    .await
+    .unwrap();
+
+    let finalized = env
+        .services()
+        .finalize_invoice(Actor::System, created.invoice.id, TENANT_ID)
+        .await
+        .unwrap();
+
+    let invoice = finalized.invoice;
+    assert_eq!(invoice.status, InvoiceStatusEnum::Finalized);
+    assert_eq!(invoice.payment_status, InvoicePaymentStatus::Unpaid);
+
+    let amount_due = Decimal::new(invoice.amount_due, 2);
+    env.services()
+        .mark_invoice_as_paid(
+            Actor::System,
+            TENANT_ID,
+            invoice.id,
+            amount_due,
+            Utc::now().naive_utc(),
+            None,
+        )
+        .await
+        .unwrap();
+    env.run_outbox_and_orchestration().await;
+
+    let invoice = env.get_detailed_invoice(invoice.id).await.invoice;
+    assert_eq!(invoice.payment_status, InvoicePaymentStatus::Paid);
+    assert_eq!(invoice.amount_due, 0);
+}
@@ -15,7 +15,7 @@ use meteroid_store::domain::price_components::{PriceEntry, PriceInput};
 use meteroid_store::domain::prices::Pricing;
 use meteroid_store::domain::{
     ComponentOverride, ComponentParameterization, ComponentParameters, CreateSubscription,
-    CreateSubscriptionComponents, SlotUpgradeBillingMode, SubscriptionNew,
+    CreateSubscriptionComponents, PastPeriodBilling, SlotUpgradeBillingMode, SubscriptionNew,
 };
 use meteroid_store::repositories::InvoiceInterface;
 use meteroid_store::repositories::subscriptions::slots::SubscriptionSlotsInterfaceAuto;
@@ -972,7 +972,8 @@ async fn create_subscription_with_slots(
                     purchase_order: None,
                     backdate_invoices: false,
                     skip_checkout_session: false,
-                    skip_past_invoices: false,
+                    past_period_billing: PastPeriodBilling::Invoice,
+                    instance: None,
                 },
                 price_components: Some(CreateSubscriptionComponents {
                     parameterized_components: vec![ComponentParameterization {
@@ -994,6 +995,7 @@ async fn create_subscription_with_slots(
                                 min_slots,
                                 max_slots,
                             },
+                            variants: vec![],
                         }),
                     }],
                     extra_components: vec![],
@@ -29,6 +29,7 @@ import {
   PencilLine,
   Plus,
   Receipt,
+  RefreshCw,
   Send,
   ShieldCheck,
   XCircle,
@@ -198,6 +199,24 @@ function renderEntry(entry: ActivityEntry): RenderedEntry {
       }
     }
 
+    case 'invoice.approval_requested':
+      return {
+        icon: <ShieldCheck className="h-4 w-4 text-warning" />,
+        title: <>{entityRef(entry, 'Invoice', 'Invoice')} held for approval</>,
+      }
+    case 'invoice.approved':
+      return {
+        icon: <ShieldCheck className="h-4 w-4 text-success" />,
+        title: <>{entityRef(entry, 'Invoice', 'Invoice')} approved</>,
+        subtitle: typeof md.comment === 'string' ? md.comment : undefined,
+      }
+    case 'invoice.rejected':
+      return {
+        icon: <XCircle className="h-4 w-4 text-destructive" />,
+        title: <>{entityRef(entry, 'Invoice', 'Invoice')} rejected</>,
+        subtitle: typeof md.comment === 'string' ? md.comment : undefined,
+      }
+
     case 'product.created':
       return {
         icon: <Plus className="h-4 w-4 text-success" />,
@@ -459,6 +478,23 @@ function renderEntry(entry: ActivityEntry): RenderedEntry {
         icon: <CalendarX className="h-4 w-4 text-warning" />,
         title: <>{entityRef(entry, 'Subscription on', 'Subscription')} paused</>,
       }
+    case 'subscription.pause_scheduled':
+      return {
+        icon: <CalendarX className="h-4 w-4 text-warning" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} pause scheduled</>,
+        subtitle: typeof md.pause_date === 'string' ? `on ${md.pause_date}` : undefined,
+      }
+    case 'subscription.resumed':
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-success" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} resumed</>,
+      }
+    case 'subscription.resume_scheduled':
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-muted-foreground" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} resume scheduled</>,
+        subtitle: typeof md.resume_on === 'string' ? `on ${md.resume_on}` : undefined,
+      }
     case 'subscription.cancellation_scheduled': {
       const reason = typeof md.reason === 'string' ? md.reason : undefined
       return {
@@ -477,6 +513,11 @@ function renderEntry(entry: ActivityEntry): RenderedEntry {
         icon: <CheckCircle2 className="h-4 w-4 text-success" />,
         title: <>{entityRef(entry, 'Subscription on', 'Subscription')} cancellation undone</>,
       }
+    case 'subscription.reactivated':
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-success" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} reactivated</>,
+      }
     case 'subscription.plan_change_scheduled': {
       const effectiveAt = typeof md.effective_at === 'string' ? md.effective_at : undefined
       return {
@@ -498,6 +539,95 @@ function renderEntry(entry: ActivityEntry): RenderedEntry {
         subtitle: changeDate ? `effective ${changeDate}` : undefined,
       }
     }
+    case 'subscription.billing_anchor_changed': {
+      const day = typeof md.new_billing_day_anchor === 'number' ? md.new_billing_day_anchor : undefined
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-success" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} billing day changed</>,
+        subtitle: day ? `now billed on day ${day}` : undefined,
+      }
+    }
+    case 'subscription.trial_extended': {
+      const trialEnd = typeof md.new_trial_end === 'string' ? md.new_trial_end : undefined
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-success" />,
+        title: <>{entityRef(entry, 'Trial of', 'Trial')} extended</>,
+        subtitle: trialEnd ? `now ends on ${trialEnd}` : undefined,
+      }
+    }
+    case 'subscription.trial_ended_early':
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-success" />,
+        title: <>{entityRef(entry, 'Trial of', 'Trial')} ended early</>,
+        subtitle:
+          typeof md.scheduled_trial_end === 'string'
+            ? `was scheduled to end on ${md.scheduled_trial_end}`
+            : undefined,
+      }
+    case 'subscription.trialing_plan_changed':
+      return {
+        icon: <PencilLine className="h-4 w-4" />,
+        title: <>{entityRef(entry, 'Trialing plan of', 'Trialing plan')} changed</>,
+      }
+    case 'subscription.trial_will_end':
+      return {
+        icon: <Mail className="h-4 w-4 text-muted-foreground" />,
+        title: <>{entityRef(entry, 'Trial of', 'Trial')} ending soon</>,
+        subtitle:
+          typeof md.trial_end_date === 'string' ? `ends on ${md.trial_end_date}` : undefined,
+      }
+    case 'subscription.trial_ended':
+      return {
+        icon: <CheckCircle2 className="h-4 w-4 text-muted-foreground" />,
+        title: <>{entityRef(entry, 'Trial of', 'Trial')} ended</>,
+      }
+    case 'subscription.suspended':
+      return {
+        icon: <CircleX className="h-4 w-4 text-destructive" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} suspended</>,
+        subtitle: 'for non-payment',
+      }
+    case 'subscription.status_changed': {
+      const previous = typeof md.previous_status === 'string' ? md.previous_status : undefined
+      const status = typeof md.status === 'string' ? md.status : undefined
+      return {
+        icon: <PencilLine className="h-4 w-4" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} status changed</>,
+        subtitle: previous && status ? `${previous} → ${status}` : undefined,
+      }
+    }
+    case 'subscription.renewed':
+      return {
+        icon: <RefreshCw className="h-4 w-4 text-muted-foreground" />,
+        title: <>{entityRef(entry, 'Subscription on', 'Subscription')} renewed</>,
+      }
+    case 'subscription.renewal_terms_updated': {
+      const policy = typeof md.renewal_policy === 'string' ? md.renewal_policy : undefined
+      return {
+        icon: <PencilLine className="h-4 w-4" />,
+        title: <>{entityRef(entry, 'Renewal terms of', 'Renewal terms')} updated</>,
+        subtitle: policy ? policy.replaceAll('_', ' ') : undefined,
+      }
+    }
+    case 'subscription.renewal_opted_out':
+      return {
+        icon: <XCircle className="h-4 w-4 text-warning" />,
+        title: <>{entityRef(entry, 'Renewal of', 'Renewal')} opted out</>,
+        subtitle: typeof md.end_date === 'string' ? `expires on ${md.end_date}` : undefined,
+      }
+    case 'subscription.renewal_upcoming':
+      return {
+        icon: <Mail className="h-4 w-4 text-muted-foreground" />,
+        title: <>{entityRef(entry, 'Renewal of', 'Renewal')} upcoming</>,
+        subtitle:
+          typeof md.renewal_date === 'string' ? `renews on ${md.renewal_date}` : undefined,
+      }
+    case 'subscription.term_renewed':
+      return {
+        icon: <RefreshCw className="h-4 w-4 text-muted-foreground" />,
+        title: <>{entityRef(entry, 'Term of', 'Term')} renewed</>,
+        subtitle: typeof md.end_date === 'string' ? `now ends on ${md.end_date}` : undefined,
+      }
     case 'subscription.amendment_scheduled': {
       const effectiveAt = typeof md.effective_at === 'string' ? md.effective_at : undefined
       return {
@@ -13,6 +13,9 @@ export const KNOWN_ACTIVITY_TYPES = [
   'invoice.paid',
   'invoice.voided',
   'invoice.consolidated',
+  'invoice.approval_requested',
+  'invoice.approved',
+  'invoice.rejected',
   'product.created',
   'product.updated',
   'product.archived',
@@ -45,12 +48,29 @@ export const KNOWN_ACTIVITY_TYPES = [
   'quote.sent',
   'customer.logged_in',
   'subscription.paused',
+  'subscription.pause_scheduled',
+  'subscription.resumed',
+  'subscription.resume_scheduled',
   'subscription.cancellation_scheduled',
   'subscription.cancelled',
   'subscription.cancellation_undone',
+  'subscription.reactivated',
   'subscription.plan_change_scheduled',
   'subscription.plan_change_cancelled',
   'subscription.plan_changed',
+  'subscription.billing_anchor_changed',
+  'subscription.trial_extended',
+  'subscription.trial_ended_early',
+  'subscription.trialing_plan_changed',
+  'subscription.trial_will_end',
+  'subscription.trial_ended',
+  'subscription.suspended',
+  'subscription.status_changed',
+  'subscription.renewed',
+  'subscription.renewal_terms_updated',
+  'subscription.renewal_opted_out',
+  'subscription.renewal_upcoming',
+  'subscription.term_renewed',
   'subscription.amendment_scheduled',
   'subscription.amendment_cancelled',
   'subscription.amended',
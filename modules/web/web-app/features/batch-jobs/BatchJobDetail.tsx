@@ -187,6 +187,15 @@ export const BatchJobDetail: FunctionComponent<BatchJobDetailProps> = ({ jobId,
     link.click()
   }, [jobId, jobQuery.data?.job?.inputFileToken, jobQuery.data?.job?.inputFileName])
 
+  const handleDownloadOutput = useCallback(() => {
+    const token = jobQuery.data?.job?.outputToken
+    if (!token) return
+    const link = document.createElement('a')
+    link.href = `${env.meteroidRestApiUri}/files/v1/batch-job/output/${jobId}?token=${token}`
+    link.download = `invoices-${jobId}.zip`
+    link.click()
+  }, [jobId, jobQuery.data?.job?.outputToken])
+
   if (jobQuery.isLoading) {
     return (
       <div className="py-4 space-y-4">
@@ -363,6 +372,20 @@ export const BatchJobDetail: FunctionComponent<BatchJobDetailProps> = ({ jobId,
             </div>
           )}
 
+          {job.outputToken && (
+            <div className="border-t border-border pt-4 space-y-2">
+              <h4 className="text-sm font-medium">Export</h4>
+              <Button variant="outline" size="sm" onClick={handleDownloadOutput}>
+                <DownloadIcon size={14} className="mr-1" />
+                Download archive
+              </Button>
+              <p className="text-xs text-muted-foreground">
+                ZIP containing the invoice and credit note PDFs, e-invoice XMLs, the ledger and the
+                tax breakdown (CSV).
+              </p>
+            </div>
+          )}
+
           {/* Fix & re-import section */}
           {!isActive && job.errorCsvToken && (
             <div className="border-t border-border pt-4 space-y-2">
@@ -107,6 +107,7 @@ export const BatchJobsTab: FunctionComponent = () => {
             <SelectItem value={String(BatchJobType.SUBSCRIPTION_PLAN_MIGRATION)}>
               Plan Migration
             </SelectItem>
+            <SelectItem value={String(BatchJobType.INVOICE_EXPORT)}>Invoice Export</SelectItem>
           </SelectContent>
         </Select>
 
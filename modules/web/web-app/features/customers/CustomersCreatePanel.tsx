@@ -70,6 +70,7 @@ export const CustomersCreatePanel = ({ visible, closePanel }: CustomersCreatePan
     defaultValues: {
       customTaxes: [],
       isTaxExempt: false,
+      invoiceApprovalRequired: false,
     },
   })
 
@@ -119,6 +120,7 @@ export const CustomersCreatePanel = ({ visible, closePanel }: CustomersCreatePan
           rate: percentToRate(tax.rate),
         })),
         isTaxExempt: values.isTaxExempt,
+        invoiceApprovalRequired: values.invoiceApprovalRequired,
         billingAddress: values.billingAddress,
         shippingAddress: values.shippingAddress,
         connectedAccountId: values.connectedAccountId || undefined,
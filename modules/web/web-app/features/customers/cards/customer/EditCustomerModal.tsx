@@ -55,6 +55,7 @@ export const EditCustomerModal = ({ customer, visible, onCancel }: Props) => {
         rate: rateToPercent(tax.rate),
       })) ?? [],
     isTaxExempt: customer.isTaxExempt,
+    invoiceApprovalRequired: customer.invoiceApprovalRequired,
     billingAddress: customer.billingAddress,
     shippingAddress: customer.shippingAddress,
   })
@@ -89,6 +90,7 @@ export const EditCustomerModal = ({ customer, visible, onCancel }: Props) => {
           })),
         },
         isTaxExempt: data.isTaxExempt,
+        invoiceApprovalRequired: data.invoiceApprovalRequired,
         billingAddress: data.billingAddress,
         shippingAddress: data.shippingAddress,
       },
@@ -170,6 +170,11 @@ export const CustomerFormFields = <T extends CustomerFormSchema>({
         {!hasCustomTaxes && (
           <CheckboxFormField control={control} label="Tax exempt" name="isTaxExempt" />
         )}
+        <CheckboxFormField
+          control={control}
+          label="Require approval before invoices are finalized"
+          name="invoiceApprovalRequired"
+        />
       </div>
 
       {/* Billing Address Section */}
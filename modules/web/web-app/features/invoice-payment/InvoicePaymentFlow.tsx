@@ -229,6 +229,7 @@ const InvoicePaymentFlow: React.FC<Props> = ({
             invoiceNumber={invoice.invoiceNumber}
             documentSharingKey={invoice.documentSharingKey}
             pdfDocumentId={invoice.pdfDocumentId}
+            hasUsageAnnex={invoice.hasUsageAnnex}
           />
 
           {/* Render based on payment availability */}
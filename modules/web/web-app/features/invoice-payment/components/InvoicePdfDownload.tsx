@@ -8,6 +8,7 @@ interface InvoicePdfDownloadProps {
   invoiceNumber?: string | null
   documentSharingKey?: string | null
   pdfDocumentId?: string | null
+  hasUsageAnnex?: boolean
   compact?: boolean
 }
 
@@ -16,6 +17,7 @@ export const InvoicePdfDownload: React.FC<InvoicePdfDownloadProps> = ({
   invoiceNumber,
   documentSharingKey,
   pdfDocumentId,
+  hasUsageAnnex = false,
   compact = false,
 }) => {
   if (!documentSharingKey || !pdfDocumentId) {
@@ -27,12 +29,25 @@ export const InvoicePdfDownload: React.FC<InvoicePdfDownloadProps> = ({
     window.open(pdfUrl, '_blank')
   }
 
+  const handleUsageDownload = () => {
+    const usageUrl = `${env.meteroidRestApiUri}/files/v1/invoice/usage/${invoiceId}?token=${documentSharingKey}`
+    window.open(usageUrl, '_blank')
+  }
+
   if (compact) {
     return (
-      <Button variant="ghost" size="sm" onClick={handleDownload} className="text-sm">
-        <Download className="h-4 w-4 mr-2" />
-        Download PDF
-      </Button>
+      <>
+        <Button variant="ghost" size="sm" onClick={handleDownload} className="text-sm">
+          <Download className="h-4 w-4 mr-2" />
+          Download PDF
+        </Button>
+        {hasUsageAnnex && (
+          <Button variant="ghost" size="sm" onClick={handleUsageDownload} className="text-sm">
+            <Download className="h-4 w-4 mr-2" />
+            Usage detail (CSV)
+          </Button>
+        )}
+      </>
     )
   }
 
@@ -62,6 +77,20 @@ export const InvoicePdfDownload: React.FC<InvoicePdfDownloadProps> = ({
           Download
         </Button>
       </div>
+      {hasUsageAnnex && (
+        <div className="mt-3 flex items-center justify-between">
+          <p className="ml-8 text-sm text-gray-600">Usage detail</p>
+          <Button
+            variant="ghost"
+            size="sm"
+            onClick={handleUsageDownload}
+            style={{ color: 'var(--mtp-text)' }}
+          >
+            <Download className="h-4 w-4 mr-2" />
+            CSV
+          </Button>
+        </div>
+      )}
     </div>
   )
 }
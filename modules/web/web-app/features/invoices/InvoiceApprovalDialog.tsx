@@ -0,0 +1,140 @@
+import { createConnectQueryKey, useMutation } from '@connectrpc/connect-query'
+import {
+  Button,
+  Dialog,
+  DialogContent,
+  DialogDescription,
+  DialogFooter,
+  DialogHeader,
+  DialogTitle,
+  Label,
+  Textarea,
+} from '@md/ui'
+import { useQueryClient } from '@tanstack/react-query'
+import { useState } from 'react'
+import { toast } from 'sonner'
+
+import {
+  approveInvoice,
+  getInvoice,
+  listInvoices,
+  rejectInvoice,
+} from '@/rpc/api/invoices/v1/invoices-InvoicesService_connectquery'
+
+export type InvoiceApprovalDecision = 'approve' | 'reject'
+
+interface InvoiceApprovalDialogProps {
+  open: boolean
+  onOpenChange: (open: boolean) => void
+  decision: InvoiceApprovalDecision
+  invoiceId: string
+  invoiceNumber: string
+}
+
+export const InvoiceApprovalDialog: React.FC<InvoiceApprovalDialogProps> = ({
+  open,
+  onOpenChange,
+  decision,
+  invoiceId,
+  invoiceNumber,
+}) => {
+  const queryClient = useQueryClient()
+  const [comment, setComment] = useState('')
+  const [isSubmitting, setIsSubmitting] = useState(false)
+
+  const invalidate = async () => {
+    await Promise.all([
+      queryClient.invalidateQueries({
+        queryKey: createConnectQueryKey({
+          schema: getInvoice,
+          input: { id: invoiceId },
+          cardinality: 'finite',
+        }),
+      }),
+      queryClient.invalidateQueries({
+        queryKey: createConnectQueryKey({
+          schema: listInvoices.parent,
+          cardinality: undefined,
+        }),
+      }),
+    ])
+  }
+
+  const approveMutation = useMutation(approveInvoice, { onSuccess: invalidate })
+  const rejectMutation = useMutation(rejectInvoice, { onSuccess: invalidate })
+
+  const isReject = decision === 'reject'
+  const trimmed = comment.trim()
+
+  const handleSubmit = async (e: React.FormEvent) => {
+    e.preventDefault()
+
+    setIsSubmitting(true)
+
+    try {
+      if (isReject) {
+        await rejectMutation.mutateAsync({ id: invoiceId, comment: trimmed })
+        toast.success(`Invoice ${invoiceNumber} rejected`)
+      } else {
+        await approveMutation.mutateAsync({ id: invoiceId, comment: trimmed || undefined })
+        toast.success(`Invoice ${invoiceNumber} approved and finalized`)
+      }
+
+      setComment('')
+      onOpenChange(false)
+    } catch (error) {
+      console.error('Error deciding invoice approval:', error)
+      toast.error(error instanceof Error ? error.message : 'Failed to record the decision')
+    } finally {
+      setIsSubmitting(false)
+    }
+  }
+
+  const handleCancel = () => {
+    setComment('')
+    onOpenChange(false)
+  }
+
+  return (
+    <Dialog open={open} onOpenChange={onOpenChange}>
+      <DialogContent>
+        <DialogHeader>
+          <DialogTitle>{isReject ? 'Reject invoice' : 'Approve invoice'}</DialogTitle>
+          <DialogDescription>
+            {isReject
+              ? `Invoice ${invoiceNumber} will stay in draft. Finalizing it again will request a new approval.`
+              : `Invoice ${invoiceNumber} will be finalized and sent to the customer.`}
+          </DialogDescription>
+        </DialogHeader>
+
+        <form onSubmit={handleSubmit} className="space-y-4">
+          <div className="space-y-2">
+            <Label htmlFor="approvalComment">Comment</Label>
+            <Textarea
+              id="approvalComment"
+              value={comment}
+              onChange={e => setComment(e.target.value)}
+              placeholder={isReject ? 'Explain what needs to change' : 'Optional note'}
+            />
+            <p className="text-xs text-muted-foreground">
+              {isReject ? 'Required.' : 'Optional.'} Recorded in the invoice activity.
+            </p>
+          </div>
+
+          <DialogFooter>
+            <Button type="button" variant="outline" onClick={handleCancel} disabled={isSubmitting}>
+              Cancel
+            </Button>
+            <Button
+              type="submit"
+              variant={isReject ? 'destructive' : 'default'}
+              disabled={isSubmitting || (isReject && !trimmed)}
+            >
+              {isReject ? 'Reject' : 'Approve & Finalize'}
+            </Button>
+          </DialogFooter>
+        </form>
+      </DialogContent>
+    </Dialog>
+  )
+}
@@ -4,7 +4,7 @@ import { useQueryClient } from '@tanstack/react-query'
 import { useSetAtom } from 'jotai'
 import { useHydrateAtoms } from 'jotai/utils'
 import { ScopeProvider } from 'jotai-scope'
-import { ReactNode, useMemo } from 'react'
+import { ReactNode, useMemo, useState } from 'react'
 import { z } from 'zod'
 
 import { usePlanWithVersion } from '@/features/plans/hooks/usePlan'
@@ -15,6 +15,10 @@ import {
   buildDefaultsFromPrices,
   getComponentSchema,
 } from '@/features/plans/pricecomponents/ProductPricingForm'
+import {
+  SubLinePresentationSelect,
+  SubLinePresentationValue,
+} from '@/features/plans/pricecomponents/SubLinePresentationSelect'
 import { editedComponentsAtom, useCurrency } from '@/features/plans/pricecomponents/utils'
 import {
   buildPriceInputs,
@@ -24,6 +28,7 @@ import {
 import { useZodForm } from '@/hooks/useZodForm'
 import { useQuery } from '@/lib/connectrpc'
 import { getResolvedEntitlementsForPlanVersion } from '@/rpc/api/entitlements/v1/entitlements-EntitlementsService_connectquery'
+import { SubLinePresentation_Mode } from '@/rpc/api/pricecomponents/v1/models_pb'
 import {
   editPriceComponent as editPriceComponentMutation,
   listPriceComponents as listPriceComponentsQuery,
@@ -108,6 +113,12 @@ export const EditPriceComponent = ({ component }: EditPriceComponentProps) => {
 
   const methods = useZodForm({ schema: schema as z.ZodType, defaultValues })
 
+  // Sent back on every edit, as the edit replaces the whole component
+  const [subLinePresentation, setSubLinePresentation] = useState<SubLinePresentationValue>({
+    mode: component.subLinePresentation?.mode ?? SubLinePresentation_Mode.DETAILED,
+    topGroupsLimit: component.subLinePresentation?.topGroupsLimit,
+  })
+
   const editPriceComponent = useMutation(editPriceComponentMutation, {
     onSuccess: data => {
       if (!version?.id) return
@@ -149,6 +160,8 @@ export const EditPriceComponent = ({ component }: EditPriceComponentProps) => {
             id: component.id,
             name: component.name,
             productId: component.productId,
+            subLinePresentation,
+            lineMetadata: component.lineMetadata,
           },
           prices: wrapAsNewPriceEntries(priceInputs),
         })
@@ -172,6 +185,12 @@ export const EditPriceComponent = ({ component }: EditPriceComponentProps) => {
             editableStructure={!hasProduct}
             isEdit
           />
+          {feeType === 'usage' && (
+            <SubLinePresentationSelect
+              value={subLinePresentation}
+              onChange={setSubLinePresentation}
+            />
+          )}
         </EditPriceComponentCard>
       </Form>
     </ProviderWrapper>
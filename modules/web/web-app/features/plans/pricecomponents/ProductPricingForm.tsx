@@ -52,7 +52,7 @@ import type { Price } from '@/rpc/api/prices/v1/models_pb'
 
 // --- Schemas ---
 
-const Cadence = z.enum(['MONTHLY', 'QUARTERLY', 'SEMIANNUAL', 'ANNUAL'])
+const Cadence = z.enum(['DAILY', 'WEEKLY', 'MONTHLY', 'QUARTERLY', 'SEMIANNUAL', 'ANNUAL'])
 
 // Full component schemas — include structural fields (metricId, billingType, slotUnitName, etc.)
 const fullSchemas: Record<ComponentFeeType, z.ZodType> = {
@@ -606,6 +606,8 @@ const CadenceSelect = ({ methods }: { methods: Methods<z.ZodType> }) => (
     control={methods.control}
     placeholder="Select cadence"
   >
+    <SelectItem value="DAILY">Daily</SelectItem>
+    <SelectItem value="WEEKLY">Weekly</SelectItem>
     <SelectItem value="MONTHLY">Monthly</SelectItem>
     <SelectItem value="QUARTERLY">Quarterly</SelectItem>
     <SelectItem value="SEMIANNUAL">Semiannual</SelectItem>
@@ -0,0 +1,67 @@
+import { Input, Select, SelectContent, SelectItem, SelectTrigger, SelectValue } from '@md/ui'
+
+import { SubLinePresentation_Mode } from '@/rpc/api/pricecomponents/v1/models_pb'
+
+export interface SubLinePresentationValue {
+  mode: SubLinePresentation_Mode
+  topGroupsLimit?: number
+}
+
+const modeLabels: Record<SubLinePresentation_Mode, string> = {
+  [SubLinePresentation_Mode.DETAILED]: 'Detailed (one line per group)',
+  [SubLinePresentation_Mode.COLLAPSED]: 'Collapsed (single line)',
+  [SubLinePresentation_Mode.TOP_GROUPS]: 'Top groups + "Other"',
+  [SubLinePresentation_Mode.TIERS_ONLY]: 'Tiers only',
+}
+
+interface SubLinePresentationSelectProps {
+  value: SubLinePresentationValue
+  onChange: (value: SubLinePresentationValue) => void
+}
+
+/**
+ * How the usage lines of the component are shown on invoice documents.
+ * Merged lines remain available in the invoice's usage annex (CSV).
+ */
+export const SubLinePresentationSelect = ({ value, onChange }: SubLinePresentationSelectProps) => {
+  return (
+    <div className="flex items-center gap-2 mt-4">
+      <label className="text-sm font-medium text-muted-foreground whitespace-nowrap">
+        Invoice presentation
+      </label>
+      <Select
+        value={String(value.mode)}
+        onValueChange={v => {
+          const mode = Number(v) as SubLinePresentation_Mode
+          onChange({
+            mode,
+            topGroupsLimit:
+              mode === SubLinePresentation_Mode.TOP_GROUPS ? (value.topGroupsLimit ?? 5) : undefined,
+          })
+        }}
+      >
+        <SelectTrigger className="max-w-[280px]">
+          <SelectValue />
+        </SelectTrigger>
+        <SelectContent>
+          {Object.entries(modeLabels).map(([mode, label]) => (
+            <SelectItem value={mode} key={mode}>
+              {label}
+            </SelectItem>
+          ))}
+        </SelectContent>
+      </Select>
+      {value.mode === SubLinePresentation_Mode.TOP_GROUPS && (
+        <Input
+          type="number"
+          min={1}
+          className="max-w-[80px]"
+          value={value.topGroupsLimit ?? 5}
+          onChange={e =>
+            onChange({ ...value, topGroupsLimit: Math.max(1, Number(e.target.value) || 1) })
+          }
+        />
+      )}
+    </div>
+  )
+}
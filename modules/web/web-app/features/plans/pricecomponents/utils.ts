@@ -40,13 +40,15 @@ export const useCurrency = () => {
 }
 
 export const mapCadence = (
-  cadence: 'ANNUAL' | 'SEMIANNUAL' | 'QUARTERLY' | 'MONTHLY' | 'COMMITTED'
+  cadence: 'ANNUAL' | 'SEMIANNUAL' | 'QUARTERLY' | 'MONTHLY' | 'WEEKLY' | 'DAILY' | 'COMMITTED'
 ): string => {
   return match(cadence)
     .with('ANNUAL', () => 'Annual')
     .with('SEMIANNUAL', () => 'Semiannual')
     .with('MONTHLY', () => 'Monthly')
     .with('QUARTERLY', () => 'Quarterly')
+    .with('WEEKLY', () => 'Weekly')
+    .with('DAILY', () => 'Daily')
     .with('COMMITTED', () => 'Committed')
     .exhaustive()
 }
@@ -77,6 +79,8 @@ export function feeTypeIcon(type: ComponentFeeType): LucideIcon {
 
 function cadenceShortLabel(cadence: BillingPeriod): string {
   switch (cadence) {
+    case BillingPeriod.DAILY: return 'day'
+    case BillingPeriod.WEEKLY: return 'wk'
     case BillingPeriod.MONTHLY: return 'mo'
     case BillingPeriod.QUARTERLY: return 'qtr'
     case BillingPeriod.SEMIANNUAL: return '6mo'
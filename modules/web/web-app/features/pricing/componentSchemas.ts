@@ -13,7 +13,7 @@ import {
   TierRowSchema,
 } from './schemas'
 
-const Cadence = z.enum(['MONTHLY', 'QUARTERLY', 'SEMIANNUAL', 'ANNUAL'])
+const Cadence = z.enum(['DAILY', 'WEEKLY', 'MONTHLY', 'QUARTERLY', 'SEMIANNUAL', 'ANNUAL'])
 
 // --- Single-cadence: rate, slot ---
 
@@ -25,10 +25,14 @@ import type { FeeStructure, Price } from '@/rpc/api/prices/v1/models_pb';
 
 // --- Cadence helpers ---
 
-type Cadence = 'MONTHLY' | 'QUARTERLY' | 'SEMIANNUAL' | 'ANNUAL'
+type Cadence = 'DAILY' | 'WEEKLY' | 'MONTHLY' | 'QUARTERLY' | 'SEMIANNUAL' | 'ANNUAL'
 
 function cadenceToProto(cadence: Cadence): BillingPeriod {
   switch (cadence) {
+    case 'DAILY':
+      return BillingPeriod.DAILY
+    case 'WEEKLY':
+      return BillingPeriod.WEEKLY
     case 'MONTHLY':
       return BillingPeriod.MONTHLY
     case 'QUARTERLY':
@@ -42,6 +46,10 @@ function cadenceToProto(cadence: Cadence): BillingPeriod {
 
 function protoToCadence(period: BillingPeriod): Cadence {
   switch (period) {
+    case BillingPeriod.DAILY:
+      return 'DAILY'
+    case BillingPeriod.WEEKLY:
+      return 'WEEKLY'
     case BillingPeriod.MONTHLY:
       return 'MONTHLY'
     case BillingPeriod.QUARTERLY:
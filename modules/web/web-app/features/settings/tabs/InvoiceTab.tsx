@@ -5,6 +5,8 @@ import {
   Card,
   Form,
   InputFormField,
+  MultiSelectFormField,
+  MultiSelectItem,
   Switch,
   SwitchFormField,
   TextareaFormField,
@@ -18,10 +20,13 @@ import { Loading } from '@/components/Loading'
 import { InvoicingEntitySelect } from '@/features/settings/components/InvoicingEntitySelect'
 import { useInvoicingEntity } from '@/features/settings/hooks/useInvoicingEntity'
 import { useZodForm } from '@/hooks/useZodForm'
+import { useQuery } from '@/lib/connectrpc'
+import { majorToMinorUnit, minorToMajorUnit } from '@/lib/utils/numbers'
 import {
   listInvoicingEntities,
   updateInvoicingEntity,
 } from '@/rpc/api/invoicingentities/v1/invoicingentities-InvoicingEntitiesService_connectquery'
+import { listUsers } from '@/rpc/api/users/v1/users-UsersService_connectquery'
 
 const invoiceDetailsSchema = z.object({
   invoiceNumberPattern: z.string().optional(),
@@ -32,11 +37,14 @@ const invoiceDetailsSchema = z.object({
   logoAttachmentId: z.string().optional(),
   brandColor: z.string().optional(),
   requireBillingInformation: z.boolean().optional(),
+  approvalThreshold: z.number().optional(),
+  approverIds: z.array(z.string()),
 })
 
 export const InvoiceTab = () => {
   const queryClient = useQueryClient()
   const { selectedEntityId: invoiceEntityId, isLoading, currentEntity } = useInvoicingEntity()
+  const users = useQuery(listUsers).data?.users ?? []
 
   const updateInvoicingEntityMut = useMutation(updateInvoicingEntity, {
     onSuccess: async res => {
@@ -67,6 +75,14 @@ export const InvoiceTab = () => {
         logoAttachmentId: currentEntity.logoAttachmentId || '',
         brandColor: currentEntity.brandColor || '',
         requireBillingInformation: currentEntity.requireBillingInformation ?? false,
+        approvalThreshold:
+          currentEntity.invoiceApprovalThreshold !== undefined
+            ? minorToMajorUnit(
+                currentEntity.invoiceApprovalThreshold,
+                currentEntity.accountingCurrency
+              )
+            : undefined,
+        approverIds: currentEntity.invoiceApproverIds,
       })
     } else {
       methods.reset()
@@ -90,6 +106,13 @@ export const InvoiceTab = () => {
         logoAttachmentId: values.logoAttachmentId,
         netTerms: values.netTerms,
         requireBillingInformation: values.requireBillingInformation,
+        invoiceApproval: {
+          threshold:
+            values.approvalThreshold !== undefined && !Number.isNaN(values.approvalThreshold)
+              ? majorToMinorUnit(values.approvalThreshold, currentEntity?.accountingCurrency ?? '')
+              : undefined,
+          approverIds: values.approverIds,
+        },
       },
     })
   }
@@ -161,6 +184,35 @@ export const InvoiceTab = () => {
                 </div>
               </div>
             </div>
+            <div className="pt-4">
+              <h3 className="font-medium text-lg">Invoice approval</h3>
+            </div>
+            <div className="grid grid-cols-6 gap-4 pt-1 ">
+              <InputFormField
+                name="approvalThreshold"
+                control={methods.control}
+                label={`Approval threshold (${currentEntity?.accountingCurrency ?? ''})`}
+                type="number"
+                placeholder="No threshold"
+                containerClassName="col-span-6"
+                description="Invoices totalling at least this amount are held as drafts until approved. Customers can also require approval for every invoice."
+              />
+              <MultiSelectFormField
+                name="approverIds"
+                control={methods.control}
+                label="Approvers"
+                placeholder="All admins"
+                hasSearch
+                containerClassName="col-span-6"
+                description="Members who can approve or reject held invoices. Defaults to every admin when empty."
+              >
+                {users.map(u => (
+                  <MultiSelectItem key={u.id} value={u.id} keywords={[u.email]}>
+                    {u.email}
+                  </MultiSelectItem>
+                ))}
+              </MultiSelectFormField>
+            </div>
             <div className="pt-4">
               <h3 className="font-medium text-lg">Invoice footer</h3>
             </div>
@@ -22,7 +22,11 @@ const subscriptionRowSchema = z.object({
   }),
   auto_advance_invoices: z.string().regex(BOOL_RE, 'Must be true or false'),
   charge_automatically: z.string().regex(BOOL_RE, 'Must be true or false'),
-  skip_past_invoices: z.string().regex(BOOL_RE, 'Must be true or false'),
+  skip_past_invoices: optionalValid(BOOL_RE, 'Must be true or false'),
+  past_period_billing: optionalValid(
+    /^(INVOICE|IMPORT_AS_PAID|SKIP)$/,
+    'Must be INVOICE, IMPORT_AS_PAID, or SKIP'
+  ),
   end_date: optionalValid(DATE_RE, 'Must be YYYY-MM-DD'),
   billing_day_anchor: z.string().refine(v => {
     if (!v) return true
@@ -117,7 +121,6 @@ export const SubscriptionsImportModal: FunctionComponent<SubscriptionsImportModa
         },
         { name: 'auto_advance_invoices', tooltipMessage: 'true or false' },
         { name: 'charge_automatically', tooltipMessage: 'true or false' },
-        { name: 'skip_past_invoices', tooltipMessage: 'true or false' },
       ]}
       optionalColumns={[
         {
@@ -133,6 +136,12 @@ export const SubscriptionsImportModal: FunctionComponent<SubscriptionsImportModa
           tooltipMessage: 'ONLINE, BANK_TRANSFER, or EXTERNAL',
         },
         { name: 'purchase_order' },
+        {
+          name: 'past_period_billing',
+          tooltipMessage:
+            'For a past start date: INVOICE (default), IMPORT_AS_PAID (already-paid imported invoices), or SKIP',
+        },
+        { name: 'skip_past_invoices', tooltipMessage: 'true or false — same as SKIP' },
       ]}
       additionalInfo={
         <ul>
@@ -8,6 +8,10 @@ export const formatSubscriptionFeeBillingPeriod = (
   switch (period) {
     case SubscriptionFeeBillingPeriod.ONE_TIME:
       return 'One Time'
+    case SubscriptionFeeBillingPeriod.DAILY:
+      return 'Daily'
+    case SubscriptionFeeBillingPeriod.WEEKLY:
+      return 'Weekly'
     case SubscriptionFeeBillingPeriod.MONTHLY:
       return 'Monthly'
     case SubscriptionFeeBillingPeriod.QUARTERLY:
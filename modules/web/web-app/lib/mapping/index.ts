@@ -28,9 +28,13 @@ export const mapDateFromGrpcv2 = (date: string): Date => {
   return parseISO(date)
 }
 
-export type BillingPeriod = 'MONTHLY' | 'QUARTERLY' | 'SEMIANNUAL' | 'ANNUAL'
+export type BillingPeriod = 'DAILY' | 'WEEKLY' | 'MONTHLY' | 'QUARTERLY' | 'SEMIANNUAL' | 'ANNUAL'
 export const mapBillingPeriod = (period: BillingPeriod): BillingPeriodMessage => {
   switch (period) {
+    case 'DAILY':
+      return BillingPeriodMessage.DAILY
+    case 'WEEKLY':
+      return BillingPeriodMessage.WEEKLY
     case 'MONTHLY':
       return BillingPeriodMessage.MONTHLY
     case 'QUARTERLY':
@@ -44,6 +48,10 @@ export const mapBillingPeriod = (period: BillingPeriod): BillingPeriodMessage =>
 
 export const mapBillingPeriodFromGrpc = (period: BillingPeriodMessage): BillingPeriod => {
   switch (period) {
+    case BillingPeriodMessage.DAILY:
+      return 'DAILY'
+    case BillingPeriodMessage.WEEKLY:
+      return 'WEEKLY'
     case BillingPeriodMessage.MONTHLY:
       return 'MONTHLY'
     case BillingPeriodMessage.QUARTERLY:
@@ -57,7 +65,7 @@ export const mapBillingPeriodFromGrpc = (period: BillingPeriodMessage): BillingP
 
 export const sortBillingPeriods = (periods: BillingPeriod[]) => {
   return periods.sort((a, b) => {
-    const order = ['MONTHLY', 'QUARTERLY', 'SEMIANNUAL', 'ANNUAL']
+    const order = ['DAILY', 'WEEKLY', 'MONTHLY', 'QUARTERLY', 'SEMIANNUAL', 'ANNUAL']
     return order.indexOf(a) - order.indexOf(b)
   })
 }
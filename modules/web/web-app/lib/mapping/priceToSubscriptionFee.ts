@@ -56,6 +56,10 @@ export function billingPeriodToSubscriptionPeriod(
   bp: BillingPeriod
 ): SubscriptionFeeBillingPeriod {
   switch (bp) {
+    case BillingPeriod.DAILY:
+      return SubscriptionFeeBillingPeriod.DAILY
+    case BillingPeriod.WEEKLY:
+      return SubscriptionFeeBillingPeriod.WEEKLY
     case BillingPeriod.MONTHLY:
       return SubscriptionFeeBillingPeriod.MONTHLY
     case BillingPeriod.QUARTERLY:
@@ -22,6 +22,10 @@ export function feeTypeLabel(feeType: FeeType): string {
 
 export function formatCadence(cadence: BillingPeriod): string {
   switch (cadence) {
+    case BillingPeriod.DAILY:
+      return 'Daily'
+    case BillingPeriod.WEEKLY:
+      return 'Weekly'
     case BillingPeriod.MONTHLY:
       return 'Monthly'
     case BillingPeriod.QUARTERLY:
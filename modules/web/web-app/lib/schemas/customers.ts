@@ -30,6 +30,7 @@ export const customerFormSchema = z.object({
   vatNumber: z.string().optional(),
   customTaxes: z.array(customTaxSchema).optional(),
   isTaxExempt: z.boolean().default(false),
+  invoiceApprovalRequired: z.boolean().default(false),
   billingAddress: addressSchema.optional(),
   shippingAddress: shippingAddressSchema.optional(), 
 })
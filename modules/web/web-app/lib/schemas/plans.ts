@@ -43,6 +43,8 @@ const BillingType = z.enum(['ARREAR', 'ADVANCE'])
 export type BillingType = z.infer<typeof BillingType>
 
 export const Cadence = z.enum([
+  'DAILY',
+  'WEEKLY',
   'MONTHLY',
   'QUARTERLY',
   'SEMIANNUAL',
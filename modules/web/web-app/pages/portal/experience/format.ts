@@ -48,6 +48,8 @@ export const date = (iso?: string) => {
 
 const PERIOD_SUFFIX: Record<SubscriptionFeeBillingPeriod, string> = {
   [SubscriptionFeeBillingPeriod.ONE_TIME]: 'one-time',
+  [SubscriptionFeeBillingPeriod.DAILY]: '/day',
+  [SubscriptionFeeBillingPeriod.WEEKLY]: '/wk',
   [SubscriptionFeeBillingPeriod.MONTHLY]: '/mo',
   [SubscriptionFeeBillingPeriod.QUARTERLY]: '/qtr',
   [SubscriptionFeeBillingPeriod.YEARLY]: '/yr',
@@ -92,6 +94,10 @@ export const feeMonthlyCents = (fee: ComponentFee | undefined): number => {
       return Math.round((n / 3) * 100)
     case SubscriptionFeeBillingPeriod.SEMIANNUAL:
       return Math.round((n / 6) * 100)
+    case SubscriptionFeeBillingPeriod.WEEKLY:
+      return Math.round(((n * 52) / 12) * 100)
+    case SubscriptionFeeBillingPeriod.DAILY:
+      return Math.round(((n * 365) / 12) * 100)
     default:
       return Math.round(n * 100)
   }
@@ -51,6 +51,10 @@ type Mode = 'idle' | 'changing' | 'confirmed'
 
 const cadenceLabel = (cadence: SubscriptionFeeBillingPeriod): string => {
   switch (cadence) {
+    case SubscriptionFeeBillingPeriod.DAILY:
+      return '/day'
+    case SubscriptionFeeBillingPeriod.WEEKLY:
+      return '/wk'
     case SubscriptionFeeBillingPeriod.MONTHLY:
       return '/mo'
     case SubscriptionFeeBillingPeriod.QUARTERLY:
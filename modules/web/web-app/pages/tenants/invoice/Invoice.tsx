@@ -1,5 +1,6 @@
 import { createConnectQueryKey, useMutation } from '@connectrpc/connect-query'
 import {
+  Badge,
   Button,
   cn,
   DropdownMenu,
@@ -27,7 +28,9 @@ import {
   FileX2Icon,
   FolderSyncIcon,
   RefreshCcw,
+  ShieldCheck,
   Trash2,
+  XCircle,
 } from 'lucide-react'
 import { Fragment, useEffect, useState } from 'react'
 import { Link, useNavigate } from 'react-router-dom'
@@ -38,6 +41,10 @@ import { CreditNoteStatusBadge } from '@/features/creditNotes'
 import { AddressLinesCompact } from '@/features/customers/cards/address/AddressCard'
 import { AddManualPaymentDialog } from '@/features/invoices/AddManualPaymentDialog'
 import { CreateCreditNoteDialog } from '@/features/invoices/CreateCreditNoteDialog'
+import {
+  InvoiceApprovalDecision,
+  InvoiceApprovalDialog,
+} from '@/features/invoices/InvoiceApprovalDialog'
 import { InvoiceStatusBadge } from '@/features/invoices/InvoiceStatusBadge'
 import { MarkAsPaidDialog } from '@/features/invoices/MarkAsPaidDialog'
 import { PaymentStatusBadge } from '@/features/invoices/PaymentStatusBadge'
@@ -77,6 +84,7 @@ import {
 } from '@/rpc/api/invoices/v1/invoices-InvoicesService_connectquery'
 import {
   DetailedInvoice,
+  InvoiceApprovalStatus,
   InvoicePaymentStatus,
   InvoiceStatus,
   LineItem,
@@ -342,6 +350,11 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
     invoice.documentSharingKey &&
     `${env.meteroidRestApiUri}/files/v1/invoice/pdf/${invoice.localId}?token=${invoice.documentSharingKey}`
 
+  const usage_annex_url =
+    invoice.hasUsageAnnex &&
+    invoice.documentSharingKey &&
+    `${env.meteroidRestApiUri}/files/v1/invoice/usage/${invoice.localId}?token=${invoice.documentSharingKey}`
+
   const connectorsQuery = useQuery(listConnectors, {})
   const connectorsData = connectorsQuery.data?.connectors ?? []
 
@@ -352,6 +365,7 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
   const [showMarkAsUncollectibleConfirmation, setShowMarkAsUncollectibleConfirmation] =
     useState(false)
   const [showCreateCreditNoteDialog, setShowCreateCreditNoteDialog] = useState(false)
+  const [approvalDecision, setApprovalDecision] = useState<InvoiceApprovalDecision | null>(null)
   const isPennylaneConnected = connectorsData.some(
     connector => connector.provider === ConnectorProviderEnum.PENNYLANE
   )
@@ -366,7 +380,8 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
 
   const canRefresh = isDraft && !invoice.manual
   const canDelete = isDraft
-  const canFinalize = isDraft
+  const isPendingApproval = isDraft && invoice.approvalStatus === InvoiceApprovalStatus.PENDING
+  const canFinalize = isDraft && !isPendingApproval
   const canCreateCreditNote = isFinalized
   const canSendToPennylane = isPennylaneConnected
 
@@ -480,6 +495,16 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
         invoice={invoice}
       />
 
+      {approvalDecision && (
+        <InvoiceApprovalDialog
+          open
+          onOpenChange={open => !open && setApprovalDecision(null)}
+          decision={approvalDecision}
+          invoiceId={invoice.id}
+          invoiceNumber={invoice.invoiceNumber}
+        />
+      )}
+
       {/* Left Panel - Invoice Details */}
       <Flex direction="column" className="w-1/3 border-r border-border">
         {/* Fixed Header - Always Visible */}
@@ -490,6 +515,7 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
                 status={invoice.status}
                 consolidatedInto={invoice.consolidatedIntoInvoiceId}
               />
+              {isPendingApproval && <Badge variant="warning">Awaiting approval</Badge>}
               {invoice.consolidatedIntoInvoiceId ? (
                 <Link
                   to={`${basePath}/invoices/${invoice.consolidatedIntoInvoiceId}`}
@@ -585,6 +611,18 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
                         Mark as Paid
                       </DropdownMenuItem>
                     )}
+                  {isPendingApproval && (
+                    <>
+                      <DropdownMenuItem onClick={() => setApprovalDecision('approve')}>
+                        <ShieldCheck size="16" className="mr-2" />
+                        Approve & Finalize
+                      </DropdownMenuItem>
+                      <DropdownMenuItem onClick={() => setApprovalDecision('reject')}>
+                        <XCircle size="16" className="mr-2" />
+                        Reject
+                      </DropdownMenuItem>
+                    </>
+                  )}
                   {!isExpress && (
                     <Tooltip>
                       <TooltipTrigger asChild>
@@ -599,7 +637,11 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
                         </span>
                       </TooltipTrigger>
                       {!canFinalize && (
-                        <TooltipContent>Only draft invoices can be finalized</TooltipContent>
+                        <TooltipContent>
+                          {isPendingApproval
+                            ? 'This invoice is awaiting approval'
+                            : 'Only draft invoices can be finalized'}
+                        </TooltipContent>
                       )}
                     </Tooltip>
                   )}
@@ -684,6 +726,20 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
                       <TooltipContent>PDF not yet generated</TooltipContent>
                     )}
                   </Tooltip>
+                  {usage_annex_url && (
+                    <DropdownMenuItem>
+                      <a
+                        href={usage_annex_url}
+                        download={`usage_${invoice.invoiceNumber}.csv`}
+                        target="_blank"
+                        rel="noreferrer"
+                        className="flex items-center gap-2"
+                      >
+                        <Download size="16" />
+                        Download usage detail (CSV)
+                      </a>
+                    </DropdownMenuItem>
+                  )}
                   {!isExpress && (
                     <Tooltip>
                       <TooltipTrigger asChild>
@@ -765,6 +821,22 @@ export const InvoiceView: React.FC<Props & { invoiceId: string }> = ({ invoice,
             </Link>
           )}
 
+          {isDraft && invoice.approvalStatus === InvoiceApprovalStatus.REJECTED && (
+            <div className="flex items-start gap-2 rounded-md border border-destructive/40 bg-destructive/5 px-3 py-2 text-[13px] text-muted-foreground">
+              <XCircle size={14} className="mt-0.5 text-destructive" />
+              <span>
+                Rejected
+                {invoice.approvalDecidedByEmail && <> by {invoice.approvalDecidedByEmail}</>}
+                {invoice.approvalComment && (
+                  <>
+                    : <span className="text-foreground">{invoice.approvalComment}</span>
+                  </>
+                )}
+                . Finalizing again will request a new approval.
+              </span>
+            </div>
+          )}
+
           <div className="text-3xl font-bold">
             {formatCurrency(Number(invoice.total) || 0, invoice.currency)}
           </div>
@@ -78,8 +78,12 @@ const usageModelProtoToForm: Record<string, string> = {
 
 const periodToCadence = (
   period: SubscriptionFeeBillingPeriod
-): 'MONTHLY' | 'QUARTERLY' | 'SEMIANNUAL' | 'ANNUAL' => {
+): 'DAILY' | 'WEEKLY' | 'MONTHLY' | 'QUARTERLY' | 'SEMIANNUAL' | 'ANNUAL' => {
   switch (period) {
+    case SubscriptionFeeBillingPeriod.DAILY:
+      return 'DAILY'
+    case SubscriptionFeeBillingPeriod.WEEKLY:
+      return 'WEEKLY'
     case SubscriptionFeeBillingPeriod.QUARTERLY:
       return 'QUARTERLY'
     case SubscriptionFeeBillingPeriod.SEMIANNUAL:
@@ -171,6 +171,10 @@ const cleanRpcMessage = (error: unknown): string => {
 
 const termToPeriodLabel = (term: unknown): string | undefined => {
   switch (term) {
+    case 'DAILY':
+      return 'Daily'
+    case 'WEEKLY':
+      return 'Weekly'
     case 'MONTHLY':
       return 'Monthly'
     case 'QUARTERLY':
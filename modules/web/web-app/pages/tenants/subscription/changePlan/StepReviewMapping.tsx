@@ -17,6 +17,8 @@ import { previewPlanChange } from '@/rpc/api/subscriptions/v1/subscriptions-Subs
 const formatPeriod = (period: SubscriptionFeeBillingPeriod): string => {
   const map: Record<number, string> = {
     [SubscriptionFeeBillingPeriod.ONE_TIME]: 'One Time',
+    [SubscriptionFeeBillingPeriod.DAILY]: 'Daily',
+    [SubscriptionFeeBillingPeriod.WEEKLY]: 'Weekly',
     [SubscriptionFeeBillingPeriod.MONTHLY]: 'Monthly',
     [SubscriptionFeeBillingPeriod.QUARTERLY]: 'Quarterly',
     [SubscriptionFeeBillingPeriod.SEMIANNUAL]: 'Semiannual',
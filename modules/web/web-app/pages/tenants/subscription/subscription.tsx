@@ -194,6 +194,8 @@ const DetailSection = ({ title, children }: { title: string; children: ReactNode
 const formatBillingPeriod = (period: SubscriptionFeeBillingPeriod) => {
   const periodMap = {
     [SubscriptionFeeBillingPeriod.ONE_TIME]: 'One Time',
+    [SubscriptionFeeBillingPeriod.DAILY]: 'Daily',
+    [SubscriptionFeeBillingPeriod.WEEKLY]: 'Weekly',
     [SubscriptionFeeBillingPeriod.MONTHLY]: 'Monthly',
     [SubscriptionFeeBillingPeriod.QUARTERLY]: 'Quarterly',
     [SubscriptionFeeBillingPeriod.SEMIANNUAL]: 'Semiannual',
@@ -243,6 +245,11 @@ const scheduledEventLabel = (event: PendingScheduledEvent): { message: string; v
         message: `Subscription amendment scheduled for ${parseAndFormatDate(event.scheduledDate)}`,
         variant: 'default',
       }
+    case ScheduledEventType.PRICE_CHANGE:
+      return {
+        message: `Price change applies on ${parseAndFormatDate(event.scheduledDate)}`,
+        variant: 'default',
+      }
     default:
       return {
         message: `Scheduled event on ${parseAndFormatDate(event.scheduledDate)}`,